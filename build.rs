@@ -5,8 +5,9 @@
 
 // spell-checker:ignore (vars) krate mangen tldr
 
+use std::collections::{BTreeSet, HashMap};
 use std::env;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::Write;
 use std::path::Path;
 
@@ -18,6 +19,8 @@ pub fn main() {
     // Do not rebuild build script unless the script itself or the enabled features are modified
     // See <https://doc.rust-lang.org/cargo/reference/build-scripts.html#change-detection>
     println!("cargo:rerun-if-changed=build.rs");
+    // The feature sets read for the target matrix below
+    println!("cargo:rerun-if-changed=Cargo.toml");
 
     // Check for tldr.zip when building uudoc to warn users once at build time
     // instead of repeatedly at runtime for each utility
@@ -59,6 +62,35 @@ pub fn main() {
     }
     crates.sort();
 
+    let feature_sets = read_feature_sets(
+        &fs::read_to_string(Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("Cargo.toml"))
+            .unwrap(),
+    );
+    let target_sets = target_feature_sets();
+    let mut target_utils = BTreeSet::new();
+    // The opt-in utilities are supported on every target, just not built by default
+    for set in target_sets.iter().chain(&["feat_opt_in"]) {
+        resolve_feature_set(&feature_sets, set, &mut target_utils);
+    }
+    for krate in &crates {
+        if !target_utils.contains(krate) {
+            println!(
+                "cargo:warning={} is not in {}, the utilities supported on this target; it may not build or work here",
+                krate.strip_prefix(OVERRIDE_PREFIX).unwrap_or(krate),
+                target_sets.join(" + ")
+            );
+        }
+    }
+
+    let mut tf = File::create(Path::new(&out_dir).join("uutils_targets.rs")).unwrap();
+    writeln!(tf, "const TARGET_FEATURE_SETS: &[&str] = &{target_sets:?};").unwrap();
+    let mut target_names: Vec<_> = target_utils
+        .iter()
+        .map(|krate| krate.strip_prefix(OVERRIDE_PREFIX).unwrap_or(krate))
+        .collect();
+    target_names.sort_unstable();
+    writeln!(tf, "const TARGET_UTILITIES: &[&str] = &{target_names:?};").unwrap();
+
     let mut mf = File::create(Path::new(&out_dir).join("uutils_map.rs")).unwrap();
 
     mf.write_all(
@@ -108,3 +140,74 @@ pub fn main() {
 
     mf.flush().unwrap();
 }
+
+/// The feature sets of `Cargo.toml` listing the utilities that build and
+/// run on the target being compiled for.
+///
+/// This is the per-target matrix used by CI; a utility missing from a
+/// target's sets is expected to fail to build there.
+fn target_feature_sets() -> &'static [&'static str] {
+    let family = env::var("CARGO_CFG_TARGET_FAMILY").unwrap_or_default();
+    let os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    let abi = env::var("CARGO_CFG_TARGET_ABI").unwrap_or_default();
+
+    if family.split(',').any(|f| f == "wasm") {
+        return &["feat_wasm"];
+    }
+    match os.as_str() {
+        "windows" => &["feat_os_windows"],
+        "macos" => &["feat_os_macos"],
+        "android" => &["feat_os_unix_android"],
+        "fuchsia" => &["feat_os_unix_fuchsia"],
+        "redox" => &["feat_os_unix_redox"],
+        "linux" if target_env == "musl" => &["feat_os_unix_musl", "feat_require_selinux"],
+        "linux" if abi == "eabihf" => &["feat_os_unix_gnueabihf", "feat_require_selinux"],
+        "linux" => &["feat_os_unix", "feat_require_selinux"],
+        _ if target_env == "musl" => &["feat_os_unix_musl"],
+        _ if family.split(',').any(|f| f == "unix") => &["feat_os_unix"],
+        _ => &["feat_common_core"],
+    }
+}
+
+/// Read the `[features]` table of `Cargo.toml` into a map from each feature
+/// to the features it enables.
+fn read_feature_sets(manifest: &str) -> HashMap<String, Vec<String>> {
+    let manifest: toml_edit::DocumentMut = manifest.parse().expect("Cargo.toml is valid TOML");
+    let Some(features) = manifest.get("features").and_then(|item| item.as_table()) else {
+        return HashMap::new();
+    };
+    features
+        .iter()
+        .filter_map(|(name, value)| {
+            let items = value
+                .as_array()?
+                .iter()
+                .filter_map(|item| item.as_str())
+                .map(String::from)
+                .collect();
+            Some((name.to_string(), items))
+        })
+        .collect()
+}
+
+/// Collect the utilities enabled by the feature set `name`, following the
+/// nested `feat_*` sets.
+fn resolve_feature_set(
+    sets: &HashMap<String, Vec<String>>,
+    name: &str,
+    utils: &mut BTreeSet<String>,
+) {
+    for item in sets.get(name).into_iter().flatten() {
+        if item.starts_with("feat_") {
+            resolve_feature_set(sets, item, utils);
+        } else if !item.contains('/') {
+            // 'test' is enabled through 'uu_test', see above
+            utils.insert(if item == "test" {
+                "uu_test".to_string()
+            } else {
+                item.clone()
+            });
+        }
+    }
+}
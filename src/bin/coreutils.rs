@@ -89,6 +89,12 @@ fn main() {
             }
             "--version" | "-V" => {
                 println!("{binary_as_util} {VERSION} (multi-call binary)");
+                if uucore::version::details_requested() {
+                    // Also tell which utilities this binary provides
+                    let utils: Vec<_> = utils.keys().copied().collect();
+                    print!("{}", uucore::version::details());
+                    println!("utilities={}", utils.join(","));
+                }
                 process::exit(0);
             }
             // Not a special command: fallthrough to calling a util
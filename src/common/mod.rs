@@ -3,4 +3,5 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
+pub mod registry;
 pub mod validation;
@@ -0,0 +1,141 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Name-based dispatch to the utilities enabled in this build.
+//!
+//! Hosts embedding uutils (such as brush-uutils) can use this module to run
+//! any enabled utility by name instead of linking and wiring up each `uu_*`
+//! crate by hand. The set of utilities is generated from the enabled cargo
+//! features, exactly as for the multi-call binary.
+//!
+//! [`target_utilities`] lists the utilities the target supports, from the
+//! `feat_*` set of `Cargo.toml` that the build script picks for it (for
+//! example `feat_wasm` or `feat_os_windows`), and from `feat_opt_in`, whose
+//! utilities are supported everywhere but only built on request. Enabling a
+//! utility outside of them gives a build warning.
+
+use std::ffi::OsString;
+use std::sync::LazyLock;
+
+use clap::Command;
+use uucore::Args;
+
+use crate::validation;
+
+include!(concat!(env!("OUT_DIR"), "/uutils_map.rs"));
+include!(concat!(env!("OUT_DIR"), "/uutils_targets.rs"));
+
+/// The arguments passed to a utility's entry point.
+pub type UtilArgs = std::vec::IntoIter<OsString>;
+
+/// The entry point of a utility, as returned by [`lookup`].
+///
+/// The first argument is the name the utility is invoked as.
+pub type UtilMain = fn(UtilArgs) -> i32;
+
+static NAMES: LazyLock<Vec<&'static str>> =
+    LazyLock::new(|| util_map::<UtilArgs>().keys().copied().collect());
+
+/// Returns the entry point of the utility called `name`, if it is enabled.
+///
+/// Aliases such as `[` and `dir` are accepted as well.
+pub fn lookup(name: &str) -> Option<UtilMain> {
+    util_map::<UtilArgs>().get(name).map(|&(uumain, _)| uumain)
+}
+
+/// Returns the names of all enabled utilities, sorted.
+pub fn list() -> &'static [&'static str] {
+    &NAMES
+}
+
+/// Returns the `Cargo.toml` feature sets, such as `feat_wasm` or
+/// `feat_os_windows`, listing the utilities supported on the target this
+/// build is for.
+pub fn target_feature_sets() -> &'static [&'static str] {
+    TARGET_FEATURE_SETS
+}
+
+/// Returns the names of the utilities supported on the target this build is
+/// for, sorted, whether or not they are enabled.
+///
+/// A host can use this to tell a utility that is merely disabled in its
+/// build from one that cannot run on its platform at all.
+pub fn target_utilities() -> &'static [&'static str] {
+    TARGET_UTILITIES
+}
+
+/// Runs the utility called `name` with `args`, returning its exit code, or
+/// `None` if no such utility is enabled.
+///
+/// `args` must not include the utility name; it is prepended here. The
+/// utility's localization is set up before it runs.
+pub fn run(name: &str, args: impl IntoIterator<Item = OsString>) -> Option<i32> {
+    let uumain = lookup(name)?;
+    // The localizer can only be set once per thread, so a host running
+    // several utilities on one thread keeps the first one; that is not an
+    // error worth refusing to run for.
+    let _ = uucore::locale::setup_localization(validation::get_canonical_util_name(name));
+    let args: Vec<OsString> = std::iter::once(OsString::from(name)).chain(args).collect();
+    Some(uumain(args.into_iter()))
+}
+
+/// Like [`run`], but the utility can be stopped by cancelling `token`,
+/// typically from another thread once a timeout expires.
+///
+/// This is how a host stops a utility where there are no signals to send,
+/// or where a signal would hit the host as well. Utilities stopped this way
+/// return [`uucore::cancel::INTERRUPTED_EXIT_CODE`]; see [`uucore::cancel`]
+/// for which of them check for it.
+pub fn run_cancellable(
+    name: &str,
+    args: impl IntoIterator<Item = OsString>,
+    token: uucore::cancel::CancellationToken,
+) -> Option<i32> {
+    uucore::cancel::with_cancellation(token, || run(name, args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_is_sorted_and_matches_lookup() {
+        let names = list();
+        assert!(names.is_sorted());
+        assert!(names.iter().all(|name| lookup(name).is_some()));
+    }
+
+    #[test]
+    fn test_target_utilities() {
+        assert!(!target_feature_sets().is_empty());
+        assert!(target_utilities().is_sorted());
+        // the portable core set is supported on every target
+        for name in ["cat", "echo", "ls", "sort", "test"] {
+            assert!(target_utilities().contains(&name), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_lookup_unknown() {
+        assert!(lookup("definitely-not-a-utility").is_none());
+        assert!(run("definitely-not-a-utility", []).is_none());
+    }
+
+    #[test]
+    fn test_run_cancellable() {
+        if lookup("sleep").is_none() {
+            return;
+        }
+        let token = uucore::cancel::CancellationToken::new();
+        let remote = token.clone();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            remote.cancel();
+        });
+        let code = run_cancellable("sleep", [OsString::from("10")], token);
+        canceller.join().unwrap();
+        assert_eq!(code, Some(uucore::cancel::INTERRUPTED_EXIT_CODE));
+    }
+}
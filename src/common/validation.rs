@@ -54,7 +54,7 @@ pub fn setup_localization_or_exit(util_name: &str) {
 }
 
 /// Gets the canonical utility name, resolving aliases
-fn get_canonical_util_name(util_name: &str) -> &str {
+pub(crate) fn get_canonical_util_name(util_name: &str) -> &str {
     match util_name {
         // uu_test aliases - '[' is an alias for test
         "[" => "test",
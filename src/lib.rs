@@ -5,4 +5,4 @@
 
 pub mod common;
 
-pub use common::validation;
+pub use common::{registry, validation};
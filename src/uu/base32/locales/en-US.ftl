@@ -43,9 +43,15 @@ basenc-help-z85 = ascii85-like encoding;
   when encoding, input length must be a multiple of 4;
   when decoding, input length must be a multiple of 5
 basenc-help-base58 = visually unambiguous base58 encoding
+basenc-help-uuencode = the 'uuencode' format, framed by 'begin' and 'end' lines;
+  when decoding, either format is accepted and the data are written to
+  standard output
+basenc-help-uuencode-base64 = the 'uuencode -m' format: base64 framed by
+  'begin-base64' and '====' lines
 
 # Error messages
 basenc-error-missing-encoding-type = missing encoding type
+basenc-error-invalid-input = error: invalid input
 
 # Shared base_common error messages (used by base32, base64, basenc)
 base-common-extra-operand = extra operand {$operand}
@@ -38,9 +38,15 @@ basenc-help-z85 = encodage de type ascii85 ;
   lors de l'encodage, la longueur d'entrée doit être un multiple de 4 ;
   lors du décodage, la longueur d'entrée doit être un multiple de 5
 basenc-help-base58 = encodage base58 visuellement non ambigu
+basenc-help-uuencode = le format 'uuencode', encadré par les lignes 'begin' et 'end' ;
+  lors du décodage, les deux formats sont acceptés et les données sont écrites
+  sur la sortie standard
+basenc-help-uuencode-base64 = le format 'uuencode -m' : base64 encadré par les
+  lignes 'begin-base64' et '===='
 
 # Messages d'erreur
 basenc-error-missing-encoding-type = type d'encodage manquant
+basenc-error-invalid-input = erreur : entrée invalide
 
 # Messages d'erreur partagés de base_common (utilisés par base32, base64, basenc)
 base-common-extra-operand = opérande supplémentaire {$operand}
@@ -8,7 +8,9 @@
 use clap::builder::ValueParser;
 use clap::{Arg, ArgAction, Command};
 use std::ffi::OsString;
-use std::io::{Write, stdout};
+use std::io::Write;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use std::path::PathBuf;
 use uucore::display::Quotable;
 use uucore::error::{UResult, UUsageError};
@@ -16,6 +18,8 @@ use uucore::format_usage;
 use uucore::line_ending::LineEnding;
 
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::stdout;
 
 pub mod options {
     pub static MULTIPLE: &str = "multiple";
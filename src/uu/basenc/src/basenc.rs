@@ -3,9 +3,12 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-// spell-checker:ignore lsbf msbf
+// spell-checker:ignore lsbf msbf uuencode
+
+mod uuencode;
 
 use clap::{Arg, ArgAction, Command};
+use std::io;
 use uu_base32::base_common::{self, BASE_CMD_PARSE_ERROR, Config};
 use uucore::translate;
 use uucore::{
@@ -13,33 +16,69 @@ use uucore::{
     error::{UResult, UUsageError},
 };
 
-fn get_encodings() -> Vec<(&'static str, Format, String)> {
+#[derive(Clone, Copy)]
+enum Encoding {
+    Base(Format),
+    Uuencode(uuencode::Style),
+}
+
+fn get_encodings() -> Vec<(&'static str, Encoding, String)> {
     vec![
-        ("base64", Format::Base64, translate!("basenc-help-base64")),
+        (
+            "base64",
+            Encoding::Base(Format::Base64),
+            translate!("basenc-help-base64"),
+        ),
         (
             "base64url",
-            Format::Base64Url,
+            Encoding::Base(Format::Base64Url),
             translate!("basenc-help-base64url"),
         ),
-        ("base32", Format::Base32, translate!("basenc-help-base32")),
+        (
+            "base32",
+            Encoding::Base(Format::Base32),
+            translate!("basenc-help-base32"),
+        ),
         (
             "base32hex",
-            Format::Base32Hex,
+            Encoding::Base(Format::Base32Hex),
             translate!("basenc-help-base32hex"),
         ),
-        ("base16", Format::Base16, translate!("basenc-help-base16")),
+        (
+            "base16",
+            Encoding::Base(Format::Base16),
+            translate!("basenc-help-base16"),
+        ),
         (
             "base2lsbf",
-            Format::Base2Lsbf,
+            Encoding::Base(Format::Base2Lsbf),
             translate!("basenc-help-base2lsbf"),
         ),
         (
             "base2msbf",
-            Format::Base2Msbf,
+            Encoding::Base(Format::Base2Msbf),
             translate!("basenc-help-base2msbf"),
         ),
-        ("z85", Format::Z85, translate!("basenc-help-z85")),
-        ("base58", Format::Base58, translate!("basenc-help-base58")),
+        (
+            "z85",
+            Encoding::Base(Format::Z85),
+            translate!("basenc-help-z85"),
+        ),
+        (
+            "base58",
+            Encoding::Base(Format::Base58),
+            translate!("basenc-help-base58"),
+        ),
+        (
+            "uuencode",
+            Encoding::Uuencode(uuencode::Style::Historical),
+            translate!("basenc-help-uuencode"),
+        ),
+        (
+            "uuencode-base64",
+            Encoding::Uuencode(uuencode::Style::Base64),
+            translate!("basenc-help-uuencode-base64"),
+        ),
     ]
 }
 
@@ -60,7 +99,7 @@ pub fn uu_app() -> Command {
     command
 }
 
-fn parse_cmd_args(args: impl uucore::Args) -> UResult<(Config, Format)> {
+fn parse_cmd_args(args: impl uucore::Args) -> UResult<(Config, Encoding)> {
     let matches = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
 
     let encodings = get_encodings();
@@ -80,9 +119,43 @@ fn parse_cmd_args(args: impl uucore::Args) -> UResult<(Config, Format)> {
 
 #[uucore::main]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
-    let (config, format) = parse_cmd_args(args)?;
+    let (config, encoding) = parse_cmd_args(args)?;
 
     let mut input = base_common::get_input(&config)?;
 
-    base_common::handle_input(&mut input, format, config)
+    match encoding {
+        Encoding::Base(format) => base_common::handle_input(&mut input, format, config),
+        Encoding::Uuencode(style) => {
+            let mut stdout = io::stdout().lock();
+            let result = if config.decode {
+                uuencode::decode(&mut input, &mut stdout, config.ignore_garbage)
+            } else {
+                let (mode, name) = uuencode_header(&config);
+                uuencode::encode(&mut input, &mut stdout, style, mode, &name)
+            };
+            // Like the other encodings, keep what was decoded before an error.
+            match (result, io::Write::flush(&mut stdout)) {
+                (res, Ok(())) => res,
+                (Ok(()), Err(err)) => Err(err.into()),
+                (Err(original), Err(_)) => Err(original),
+            }
+        }
+    }
+}
+
+/// The permissions and name recorded in the `begin` line: those of the
+/// input file, or `644` and `-` for standard input.
+fn uuencode_header(config: &Config) -> (u32, Vec<u8>) {
+    let Some(path) = &config.to_read else {
+        return (0o644, b"-".to_vec());
+    };
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).map_or(0o644, |md| md.permissions().mode() & 0o777)
+    };
+    #[cfg(not(unix))]
+    let mode = 0o644;
+    let name = path.file_name().unwrap_or(path.as_os_str());
+    (mode, uucore::os_str_as_bytes_lossy(name).into_owned())
 }
@@ -0,0 +1,259 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+// spell-checker:ignore uuencode uudecode sharutils
+
+//! The `uuencode` and `uuencode -m` formats, as written by the historical
+//! `uuencode` utility and read by `uudecode`.
+//!
+//! Unlike the other encodings, the data is framed by a `begin MODE NAME`
+//! header and an `end` (or `====`) trailer. When decoding, the header's mode
+//! and name are ignored and the data are written to standard output, like
+//! the rest of `basenc`.
+
+use std::io::{self, BufRead, Read, Write};
+use uucore::encoding::for_base_common::BASE64;
+use uucore::error::{UResult, USimpleError, strip_errno};
+use uucore::translate;
+
+/// Bytes encoded on each line; 45 gives the usual 61 character lines.
+const HISTORICAL_LINE_BYTES: usize = 45;
+/// Bytes encoded on each line of `uuencode -m`, giving 76 character lines.
+const BASE64_LINE_BYTES: usize = 57;
+/// Lines encoded from each read of the input.
+const LINES_PER_READ: usize = 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// The original format: each line starts with its length and maps six
+    /// bits to a printable character from space to backquote.
+    Historical,
+    /// `uuencode -m`: base64 lines between `begin-base64` and `====`.
+    Base64,
+}
+
+fn invalid_input() -> Box<dyn uucore::error::UError> {
+    USimpleError::new(1, translate!("basenc-error-invalid-input"))
+}
+
+fn read_error(error: &io::Error) -> Box<dyn uucore::error::UError> {
+    USimpleError::new(
+        1,
+        translate!("base-common-read-error", "error" => strip_errno(error)),
+    )
+}
+
+/// Reads until `buf` is full or the input ends, and returns how much was read.
+fn fill<R: Read>(input: &mut R, buf: &mut [u8]) -> UResult<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match input.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(read_error(&e)),
+        }
+    }
+    Ok(len)
+}
+
+/// Maps six bits to a character; zero is a backquote rather than a space, so
+/// that lines have no trailing blanks to lose.
+fn encode_char(bits: u8) -> u8 {
+    if bits == 0 { b'`' } else { bits + b' ' }
+}
+
+fn decode_char(c: u8) -> u8 {
+    c.wrapping_sub(b' ') & 0x3f
+}
+
+fn encode_historical_line(chunk: &[u8], out: &mut Vec<u8>) {
+    out.push(encode_char(chunk.len() as u8));
+    for group in chunk.chunks(3) {
+        let b = [
+            group[0],
+            group.get(1).copied().unwrap_or(0),
+            group.get(2).copied().unwrap_or(0),
+        ];
+        out.extend([
+            encode_char(b[0] >> 2),
+            encode_char(((b[0] << 4) | (b[1] >> 4)) & 0x3f),
+            encode_char(((b[1] << 2) | (b[2] >> 6)) & 0x3f),
+            encode_char(b[2] & 0x3f),
+        ]);
+    }
+    out.push(b'\n');
+}
+
+/// Encodes `input` with a header naming `name` with permissions `mode`.
+pub fn encode<R: Read>(
+    input: &mut R,
+    output: &mut dyn Write,
+    style: Style,
+    mode: u32,
+    name: &[u8],
+) -> UResult<()> {
+    let (begin, line_bytes) = match style {
+        Style::Historical => (&b"begin"[..], HISTORICAL_LINE_BYTES),
+        Style::Base64 => (&b"begin-base64"[..], BASE64_LINE_BYTES),
+    };
+    output.write_all(begin)?;
+    write!(output, " {mode:o} ")?;
+    output.write_all(name)?;
+    output.write_all(b"\n")?;
+
+    let mut buf = vec![0; line_bytes * LINES_PER_READ];
+    let mut encoded = Vec::new();
+    loop {
+        let len = fill(input, &mut buf)?;
+        for chunk in buf[..len].chunks(line_bytes) {
+            match style {
+                Style::Historical => encode_historical_line(chunk, &mut encoded),
+                Style::Base64 => {
+                    encoded.extend(BASE64.encode(chunk).as_bytes());
+                    encoded.push(b'\n');
+                }
+            }
+        }
+        output.write_all(&encoded)?;
+        encoded.clear();
+        if len < buf.len() {
+            break;
+        }
+    }
+
+    match style {
+        Style::Historical => output.write_all(b"`\nend\n")?,
+        Style::Base64 => output.write_all(b"====\n")?,
+    }
+    Ok(())
+}
+
+/// Returns the next line without its line ending, or `None` at the end of
+/// the input.
+fn next_line<R: BufRead>(input: &mut R, line: &mut Vec<u8>) -> UResult<Option<()>> {
+    line.clear();
+    if input.read_until(b'\n', line).map_err(|e| read_error(&e))? == 0 {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+    }
+    Ok(Some(()))
+}
+
+/// Decodes the first encoded file in `input`, in either style. Lines before
+/// the header are skipped, as `uudecode` does, so mail headers and the like
+/// need no `--ignore-garbage`. With `ignore_garbage`, bytes that are not in
+/// the base64 alphabet are also skipped in `uuencode -m` data.
+pub fn decode<R: BufRead>(
+    input: &mut R,
+    output: &mut dyn Write,
+    ignore_garbage: bool,
+) -> UResult<()> {
+    let mut line = Vec::new();
+    let style = loop {
+        if next_line(input, &mut line)?.is_none() {
+            return Err(invalid_input());
+        }
+        if line.starts_with(b"begin-base64 ") {
+            break Style::Base64;
+        }
+        if line.starts_with(b"begin ") {
+            break Style::Historical;
+        }
+    };
+
+    match style {
+        Style::Historical => decode_historical(input, output, &mut line),
+        Style::Base64 => decode_base64(input, output, &mut line, ignore_garbage),
+    }
+}
+
+fn decode_historical<R: BufRead>(
+    input: &mut R,
+    output: &mut dyn Write,
+    line: &mut Vec<u8>,
+) -> UResult<()> {
+    let mut decoded = Vec::new();
+    loop {
+        if next_line(input, line)?.is_none() {
+            break;
+        }
+        let Some((&first, data)) = line.split_first() else {
+            break;
+        };
+        let len = usize::from(decode_char(first));
+        if len == 0 {
+            break;
+        }
+        let groups = len.div_ceil(3);
+        if data.len() < groups * 4 {
+            output.write_all(&decoded)?;
+            return Err(invalid_input());
+        }
+        for group in data[..groups * 4].chunks_exact(4) {
+            let c = [
+                decode_char(group[0]),
+                decode_char(group[1]),
+                decode_char(group[2]),
+                decode_char(group[3]),
+            ];
+            decoded.extend([
+                (c[0] << 2) | (c[1] >> 4),
+                (c[1] << 4) | (c[2] >> 2),
+                (c[2] << 6) | c[3],
+            ]);
+        }
+        decoded.truncate(decoded.len() - (groups * 3 - len));
+        output.write_all(&decoded)?;
+        decoded.clear();
+    }
+
+    if next_line(input, line)?.is_none() || line.trim_ascii_end() != b"end" {
+        return Err(invalid_input());
+    }
+    Ok(())
+}
+
+fn decode_base64<R: BufRead>(
+    input: &mut R,
+    output: &mut dyn Write,
+    line: &mut Vec<u8>,
+    ignore_garbage: bool,
+) -> UResult<()> {
+    // Characters carried over to the next line, as groups of four may span
+    // lines.
+    let mut pending = Vec::new();
+    loop {
+        if next_line(input, line)?.is_none() {
+            return Err(invalid_input());
+        }
+        if line.trim_ascii_end() == b"====" {
+            break;
+        }
+        pending.extend(line.iter().copied().filter(|&c| {
+            !c.is_ascii_whitespace() && (!ignore_garbage || c == b'=' || is_base64_char(c))
+        }));
+        let whole = pending.len() / 4 * 4;
+        let decoded = BASE64
+            .decode(&pending[..whole])
+            .map_err(|_| invalid_input())?;
+        output.write_all(&decoded)?;
+        pending.drain(..whole);
+    }
+
+    if !pending.is_empty() {
+        return Err(invalid_input());
+    }
+    Ok(())
+}
+
+fn is_base64_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'+' || c == b'/'
+}
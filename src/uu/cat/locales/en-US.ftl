@@ -20,3 +20,4 @@ cat-error-is-directory = Is a directory
 cat-error-input-file-is-output-file = input file is output file
 cat-error-too-many-symbolic-links = Too many levels of symbolic links
 cat-error-no-such-device-or-address = No such device or address
+cat-error-write-error = write error
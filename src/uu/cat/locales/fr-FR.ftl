@@ -20,3 +20,4 @@ cat-error-is-directory = Est un répertoire
 cat-error-input-file-is-output-file = le fichier d'entrée est le fichier de sortie
 cat-error-too-many-symbolic-links = Trop de niveaux de liens symboliques
 cat-error-no-such-device-or-address = Aucun appareil ou adresse de ce type
+cat-error-write-error = erreur d'écriture
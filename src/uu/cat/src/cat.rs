@@ -9,7 +9,7 @@ mod platform;
 
 use crate::platform::is_unsafe_overwrite;
 use clap::{Arg, ArgAction, Command};
-use memchr::memchr2;
+use memchr::{memchr, memchr2, memchr3};
 use std::ffi::OsString;
 use std::fs::{File, metadata};
 use std::io::{self, BufWriter, ErrorKind, IsTerminal, Read, Write};
@@ -25,8 +25,18 @@ fn stdout() -> uucore::wasm_io::WasmStdout {
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn stdout() -> std::io::Stdout {
-    std::io::stdout()
+fn stdout() -> io::Stdout {
+    io::stdout()
+}
+
+#[cfg(target_family = "wasm")]
+fn stdout_lock() -> uucore::wasm_io::WasmStdoutLock {
+    stdout().lock()
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn stdout_lock() -> uucore::display::RawStdout {
+    uucore::display::raw_stdout()
 }
 
 #[cfg(target_family = "wasm")]
@@ -35,15 +45,15 @@ fn stdin() -> uucore::wasm_io::WasmStdin {
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn stdin() -> std::io::Stdin {
-    std::io::stdin()
+fn stdin() -> io::Stdin {
+    io::stdin()
 }
 use uucore::display::Quotable;
-use uucore::error::{UResult, strip_errno};
+use uucore::error::{UResult, USimpleError, strip_errno};
 use uucore::translate;
-use uucore::{fast_inc::fast_inc_one, format_usage};
+use uucore::{fast_inc::fast_inc_one, format_usage, show};
 
-/// Linux splice support
+/// Linux splice and copy_file_range support
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod splice;
 
@@ -104,6 +114,9 @@ enum CatError {
     /// Wrapper around `io::Error`
     #[error("{}", strip_errno(.0))]
     Io(#[from] io::Error),
+    /// Failure to write to standard output
+    #[error("{}: {}", translate!("cat-error-write-error"), strip_errno(.0))]
+    Write(io::Error),
     /// Wrapper around `nix::Error`
     #[cfg(any(target_os = "linux", target_os = "android"))]
     #[error("{0}")]
@@ -123,6 +136,9 @@ enum CatError {
     OutputIsInput,
     #[error("{}", translate!("cat-error-too-many-symbolic-links"))]
     TooManySymlinks,
+    /// The host running us in-process asked us to stop, see [`uucore::cancel`]
+    #[error("")]
+    Interrupted,
 }
 
 type CatResult<T> = Result<T, CatError>;
@@ -392,9 +408,19 @@ fn cat_path(path: &OsString, options: &OutputOptions, state: &mut OutputState) -
         InputType::StdIn => {
             let is_interactive = stdin().is_terminal();
             let stdin = stdin();
+            #[cfg(not(target_os = "wasi"))]
             if is_unsafe_overwrite(&stdin, &stdout()) {
                 return Err(CatError::OutputIsInput);
             }
+            #[cfg(target_os = "wasi")]
+            if let (Some(input), Some(output)) = (
+                uucore::wasm_io::host_stdin(),
+                uucore::wasm_io::host_stdout(),
+            ) {
+                if is_unsafe_overwrite(&input, &output) {
+                    return Err(CatError::OutputIsInput);
+                }
+            }
             let mut handle = InputHandle {
                 reader: stdin,
                 is_interactive,
@@ -405,7 +431,32 @@ fn cat_path(path: &OsString, options: &OutputOptions, state: &mut OutputState) -
         #[cfg(unix)]
         InputType::Socket => Err(CatError::NoSuchDeviceOrAddress),
         _ => {
+            // On WASM, use VFS file hooks instead of std::fs::File::open.
+            #[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
             let reader = uucore::wasm_io::open_file(path)?;
+            // On WASI, files not provided by the host are real files, so they
+            // get the same overwrite check as on other platforms.
+            #[cfg(target_os = "wasi")]
+            let reader: Box<dyn Read> = match uucore::wasm_io::open_host_file(path) {
+                Some(file) => {
+                    let file = file?;
+                    if let Some(output) = uucore::wasm_io::host_stdout() {
+                        if is_unsafe_overwrite(&file, &output) {
+                            return Err(CatError::OutputIsInput);
+                        }
+                    }
+                    Box::new(file)
+                }
+                None => uucore::wasm_io::open_file(path)?,
+            };
+            #[cfg(not(target_family = "wasm"))]
+            let reader = {
+                let file = File::open(path)?;
+                if is_unsafe_overwrite(&file, &stdout()) {
+                    return Err(CatError::OutputIsInput);
+                }
+                file
+            };
             let mut handle = InputHandle {
                 reader,
                 is_interactive: false,
@@ -422,27 +473,30 @@ fn cat_files(files: &[OsString], options: &OutputOptions) -> UResult<()> {
         skipped_carriage_return: false,
         one_blank_kept: false,
     };
-    let mut error_messages: Vec<String> = Vec::new();
-
+    // Like GNU, report each failing file before going on with the next one,
+    // so that the messages come out between the files around them.
     for path in files {
-        if let Err(err) = cat_path(path, options, &mut state) {
-            error_messages.push(format!("{}: {err}", path.maybe_quote()));
+        match cat_path(path, options, &mut state) {
+            Ok(()) => {}
+            Err(CatError::Interrupted) => {
+                return Err(uucore::error::ExitCode::new(
+                    uucore::cancel::INTERRUPTED_EXIT_CODE,
+                ));
+            }
+            Err(err @ CatError::Write(_)) => {
+                // there is no point in going on once stdout is unwritable
+                return Err(USimpleError::new(1, err.to_string()));
+            }
+            Err(err) => show!(USimpleError::new(
+                1,
+                format!("{}: {err}", path.maybe_quote())
+            )),
         }
     }
     if state.skipped_carriage_return {
         print!("\r");
     }
-    if error_messages.is_empty() {
-        Ok(())
-    } else {
-        // each next line is expected to display "cat: …"
-        let line_joiner = format!("\n{}: ", uucore::util_name());
-
-        Err(uucore::error::USimpleError::new(
-            error_messages.len() as i32,
-            error_messages.join(&line_joiner),
-        ))
-    }
+    Ok(())
 }
 
 /// Classifies the `InputType` of file at `path` if possible
@@ -502,13 +556,15 @@ fn get_input_type(path: &OsString) -> CatResult<InputType> {
 /// Writes handle to stdout with no configuration. This allows a
 /// simple memory copy.
 fn write_fast<R: FdReadable>(handle: &mut InputHandle<R>) -> CatResult<()> {
-    let stdout = stdout();
-    let mut stdout_lock = stdout.lock();
+    let mut stdout_lock = stdout_lock();
     #[cfg(any(target_os = "linux", target_os = "android"))]
     {
-        // If we're on Linux or Android, try to use the splice() system call
-        // for faster writing. If it works, we're done.
-        if !splice::write_fast_using_splice(handle, &stdout_lock)? {
+        // If we're on Linux or Android, try to copy inside the kernel with
+        // copy_file_range() between files, or splice() when a pipe is
+        // involved. If it works, we're done.
+        if !splice::write_fast_using_copy_file_range(handle, &stdout_lock)?
+            || !splice::write_fast_using_splice(handle, &stdout_lock)?
+        {
             return Ok(());
         }
     }
@@ -516,6 +572,9 @@ fn write_fast<R: FdReadable>(handle: &mut InputHandle<R>) -> CatResult<()> {
     // fall back on slower writing.
     let mut buf = [0; 1024 * 64];
     loop {
+        if uucore::cancel::is_cancelled() {
+            return Err(CatError::Interrupted);
+        }
         match handle.reader.read(&mut buf) {
             Ok(n) => {
                 if n == 0 {
@@ -523,7 +582,8 @@ fn write_fast<R: FdReadable>(handle: &mut InputHandle<R>) -> CatResult<()> {
                 }
                 stdout_lock
                     .write_all(&buf[..n])
-                    .inspect_err(handle_broken_pipe)?;
+                    .inspect_err(handle_broken_pipe)
+                    .map_err(CatError::Write)?;
             }
             Err(e) if e.kind() == ErrorKind::Interrupted => {}
             Err(e) => return Err(e.into()),
@@ -535,7 +595,10 @@ fn write_fast<R: FdReadable>(handle: &mut InputHandle<R>) -> CatResult<()> {
     // that will succeed, data pushed through splice will be output before
     // the data buffered in stdout.lock. Therefore additional explicit flush
     // is required here.
-    stdout_lock.flush().inspect_err(handle_broken_pipe)?;
+    stdout_lock
+        .flush()
+        .inspect_err(handle_broken_pipe)
+        .map_err(CatError::Write)?;
     Ok(())
 }
 
@@ -547,12 +610,14 @@ fn write_lines<R: FdReadable>(
     state: &mut OutputState,
 ) -> CatResult<()> {
     let mut in_buf = [0; 1024 * 31];
-    let stdout = stdout();
-    let stdout = stdout.lock();
     // Add a 32K buffer for stdout - this greatly improves performance.
-    let mut writer = BufWriter::with_capacity(32 * 1024, stdout);
+    let mut writer = BufWriter::with_capacity(32 * 1024, stdout_lock());
 
     loop {
+        if uucore::cancel::is_cancelled() {
+            writer.flush().map_err(CatError::Write)?;
+            return Err(CatError::Interrupted);
+        }
         let n = match handle.reader.read(&mut in_buf) {
             Ok(0) => break,
             Ok(n) => n,
@@ -564,24 +629,29 @@ fn write_lines<R: FdReadable>(
         while pos < n {
             // skip empty line_number enumerating them if needed
             if in_buf[pos] == b'\n' {
-                write_new_line(&mut writer, options, state, handle.is_interactive)?;
+                write_new_line(&mut writer, options, state, handle.is_interactive)
+                    .map_err(CatError::Write)?;
                 state.at_line_start = true;
                 pos += 1;
                 continue;
             }
             if state.skipped_carriage_return {
-                writer.write_all(b"\r")?;
+                writer.write_all(b"\r").map_err(CatError::Write)?;
                 state.skipped_carriage_return = false;
                 state.at_line_start = false;
             }
             state.one_blank_kept = false;
             if state.at_line_start && options.number != NumberingMode::None {
-                state.line_number.write(&mut writer)?;
+                state
+                    .line_number
+                    .write(&mut writer)
+                    .map_err(CatError::Write)?;
                 state.line_number.increment();
             }
 
             // print to end of line or end of buffer
-            let offset = write_end(&mut writer, &in_buf[pos..], options)?;
+            let offset =
+                write_end(&mut writer, &in_buf[pos..], options).map_err(CatError::Write)?;
 
             // end of buffer?
             if offset + pos == in_buf.len() {
@@ -597,7 +667,8 @@ fn write_lines<R: FdReadable>(
                     &mut writer,
                     options.end_of_line().as_bytes(),
                     handle.is_interactive,
-                )?;
+                )
+                .map_err(CatError::Write)?;
                 state.at_line_start = true;
             }
             pos += offset + 1;
@@ -609,7 +680,10 @@ fn write_lines<R: FdReadable>(
         // and not be buffered internally to the `cat` process.
         // Hence it's necessary to flush our buffer before every time we could potentially block
         // on a `std::io::Read::read` call.
-        writer.flush().inspect_err(handle_broken_pipe)?;
+        writer
+            .flush()
+            .inspect_err(handle_broken_pipe)
+            .map_err(CatError::Write)?;
     }
 
     Ok(())
@@ -621,7 +695,7 @@ fn write_new_line<W: Write>(
     options: &OutputOptions,
     state: &mut OutputState,
     is_interactive: bool,
-) -> CatResult<()> {
+) -> io::Result<()> {
     if state.skipped_carriage_return {
         if options.show_ends {
             writer.write_all(b"^M")?;
@@ -634,7 +708,9 @@ fn write_new_line<W: Write>(
         return Ok(());
     }
     if !state.at_line_start || !options.squeeze_blank || !state.one_blank_kept {
-        state.one_blank_kept = true;
+        // A newline ending a line read up to the end of the previous buffer
+        // is not a blank line.
+        state.one_blank_kept = state.at_line_start;
         if state.at_line_start && options.number == NumberingMode::All {
             state.line_number.write(writer)?;
             state.line_number.increment();
@@ -678,10 +754,7 @@ fn write_to_end<W: Write>(in_buf: &[u8], writer: &mut W) -> io::Result<usize> {
 fn write_tab_to_end<W: Write>(mut in_buf: &[u8], writer: &mut W) -> io::Result<usize> {
     let mut count = 0;
     loop {
-        if let Some(p) = in_buf
-            .iter()
-            .position(|c| *c == b'\n' || *c == b'\t' || *c == b'\r')
-        {
+        if let Some(p) = memchr3(b'\n', b'\t', b'\r', in_buf) {
             writer.write_all(&in_buf[..p])?;
             if in_buf[p] == b'\t' {
                 writer.write_all(b"^I")?;
@@ -698,32 +771,72 @@ fn write_tab_to_end<W: Write>(mut in_buf: &[u8], writer: &mut W) -> io::Result<u
     }
 }
 
-fn write_nonprint_to_end<W: Write>(in_buf: &[u8], writer: &mut W, tab: &[u8]) -> io::Result<usize> {
-    let mut count = 0;
+/// The `^` and `M-` notation of every byte for `--show-nonprinting`, like GNU:
+/// control characters become `^` and the character 64 above them, DEL is
+/// `^?`, and bytes with the high bit set are `M-` followed by the notation
+/// of the byte without it. Only the first `len` bytes of each entry are used.
+const NONPRINT_NOTATION: [([u8; 4], usize); 256] = {
+    let mut table = [([0; 4], 0); 256];
+    let mut byte = 0;
+    while byte < 256 {
+        let (notation, len) = &mut table[byte];
+        let mut c = byte as u8;
+        if c >= 128 {
+            notation[0] = b'M';
+            notation[1] = b'-';
+            *len = 2;
+            c -= 128;
+        }
+        match c {
+            0..=31 => {
+                notation[*len] = b'^';
+                notation[*len + 1] = c + 64;
+                *len += 2;
+            }
+            127 => {
+                notation[*len] = b'^';
+                notation[*len + 1] = b'?';
+                *len += 2;
+            }
+            _ => {
+                notation[*len] = c;
+                *len += 1;
+            }
+        }
+        byte += 1;
+    }
+    table
+};
 
-    for byte in in_buf.iter().copied() {
-        if byte == b'\n' {
+fn write_nonprint_to_end<W: Write>(in_buf: &[u8], writer: &mut W, tab: &[u8]) -> io::Result<usize> {
+    let end = memchr(b'\n', in_buf).unwrap_or(in_buf.len());
+    let mut rest = &in_buf[..end];
+    while !rest.is_empty() {
+        // Printable ASCII is shown as is, so copy runs of it in one go.
+        let printable = rest
+            .iter()
+            .position(|c| !(b' '..=b'~').contains(c))
+            .unwrap_or(rest.len());
+        writer.write_all(&rest[..printable])?;
+        let Some(&byte) = rest.get(printable) else {
             break;
+        };
+        if byte == b'\t' {
+            writer.write_all(tab)?;
+        } else {
+            let (notation, len) = &NONPRINT_NOTATION[byte as usize];
+            writer.write_all(&notation[..*len])?;
         }
-        match byte {
-            9 => writer.write_all(tab),
-            0..=8 | 10..=31 => writer.write_all(&[b'^', byte + 64]),
-            32..=126 => writer.write_all(&[byte]),
-            127 => writer.write_all(b"^?"),
-            128..=159 => writer.write_all(&[b'M', b'-', b'^', byte - 64]),
-            160..=254 => writer.write_all(&[b'M', b'-', byte - 128]),
-            _ => writer.write_all(b"M-^?"),
-        }?;
-        count += 1;
+        rest = &rest[printable + 1..];
     }
-    Ok(count)
+    Ok(end)
 }
 
 fn write_end_of_line<W: Write>(
     writer: &mut W,
     end_of_line: &[u8],
     is_interactive: bool,
-) -> CatResult<()> {
+) -> io::Result<()> {
     writer.write_all(end_of_line)?;
     if is_interactive {
         writer.flush().inspect_err(handle_broken_pipe)?;
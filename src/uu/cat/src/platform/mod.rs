@@ -9,7 +9,10 @@ pub use self::unix::is_unsafe_overwrite;
 #[cfg(windows)]
 pub use self::windows::is_unsafe_overwrite;
 
-#[cfg(target_family = "wasm")]
+#[cfg(target_os = "wasi")]
+pub use self::wasi::is_unsafe_overwrite;
+
+#[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
 pub use self::wasm::is_unsafe_overwrite;
 
 #[cfg(unix)]
@@ -18,5 +21,8 @@ mod unix;
 #[cfg(windows)]
 mod windows;
 
-#[cfg(target_family = "wasm")]
+#[cfg(target_os = "wasi")]
+mod wasi;
+
+#[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
 mod wasm;
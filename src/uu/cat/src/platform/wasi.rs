@@ -0,0 +1,52 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+// spell-checker:ignore lseek
+
+use std::os::fd::{AsFd, AsRawFd};
+use uucore::fs::FileInformation;
+
+/// An unsafe overwrite occurs when the same nonempty file is used as both stdin and stdout,
+/// and the file offset of stdin is positioned earlier than that of stdout.
+///
+/// This mirrors the Unix implementation, using the WASI libc directly since `nix`
+/// does not support WASI.
+pub fn is_unsafe_overwrite<I: AsFd, O: AsFd>(input: &I, output: &O) -> bool {
+    let Ok(input_info) = FileInformation::from_file(input) else {
+        return false;
+    };
+    let Ok(output_info) = FileInformation::from_file(output) else {
+        return false;
+    };
+    if input_info != output_info {
+        return false;
+    }
+    let file_size = output_info.file_size();
+    if file_size == 0 {
+        return false;
+    }
+    let input_pos = current_offset(input);
+    if is_appending(output) {
+        return input_pos.is_none_or(|pos| pos < file_size);
+    }
+    match (input_pos, current_offset(output)) {
+        (Some(input_pos), Some(output_pos)) => input_pos < output_pos,
+        _ => false,
+    }
+}
+
+/// The current offset of the file, or `None` if it is not seekable
+fn current_offset<F: AsFd>(file: &F) -> Option<u64> {
+    // SAFETY: the descriptor is borrowed for the duration of the call.
+    let pos = unsafe { libc::lseek(file.as_fd().as_raw_fd(), 0, libc::SEEK_CUR) };
+    u64::try_from(pos).ok()
+}
+
+/// Whether the file is opened with the `O_APPEND` flag
+fn is_appending<F: AsFd>(file: &F) -> bool {
+    // SAFETY: the descriptor is borrowed for the duration of the call.
+    let flags = unsafe { libc::fcntl(file.as_fd().as_raw_fd(), libc::F_GETFL) };
+    flags != -1 && flags & libc::O_APPEND != 0
+}
@@ -2,8 +2,10 @@
 //
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
-use super::{CatResult, FdReadable, InputHandle};
+use super::{CatError, CatResult, FdReadable, InputHandle};
 
+use nix::fcntl::copy_file_range;
+use nix::sys::stat::{SFlag, fstat};
 use nix::unistd;
 use std::os::{fd::AsFd, unix::io::AsRawFd};
 
@@ -11,12 +13,16 @@ use uucore::pipes::{pipe, splice, splice_exact};
 
 const SPLICE_SIZE: usize = 1024 * 128;
 const BUF_SIZE: usize = 1024 * 16;
+const COPY_FILE_RANGE_SIZE: usize = 16 * 1024 * 1024;
 
 /// This function is called from `write_fast()` on Linux and Android. The
 /// function `splice()` is used to move data between two file descriptors
 /// without copying between kernel and user spaces. This results in a large
 /// speedup.
 ///
+/// When the input or the output is a pipe, the data is spliced straight
+/// across; otherwise it goes through an intermediate pipe.
+///
 /// The `bool` in the result value indicates if we need to fall back to normal
 /// copying or not. False means we don't have to.
 #[inline]
@@ -24,9 +30,16 @@ pub(super) fn write_fast_using_splice<R: FdReadable, S: AsRawFd + AsFd>(
     handle: &InputHandle<R>,
     write_fd: &S,
 ) -> CatResult<bool> {
+    if is_pipe(&handle.reader) || is_pipe(write_fd) {
+        return splice_directly(handle, write_fd);
+    }
+
     let (pipe_rd, pipe_wr) = pipe()?;
 
     loop {
+        if uucore::cancel::is_cancelled() {
+            return Err(CatError::Interrupted);
+        }
         match splice(&handle.reader, &pipe_wr, SPLICE_SIZE) {
             Ok(n) => {
                 if n == 0 {
@@ -38,7 +51,7 @@ pub(super) fn write_fast_using_splice<R: FdReadable, S: AsRawFd + AsFd>(
                     // we can recover by copying the data that we have from the
                     // intermediate pipe to stdout using normal read/write. Then
                     // we tell the caller to fall back.
-                    copy_exact(&pipe_rd, write_fd, n)?;
+                    copy_exact(&pipe_rd, write_fd, n).map_err(|e| CatError::Write(e.into()))?;
                     return Ok(true);
                 }
             }
@@ -49,6 +62,60 @@ pub(super) fn write_fast_using_splice<R: FdReadable, S: AsRawFd + AsFd>(
     }
 }
 
+/// Splice from `handle` into `write_fd`, one of which is a pipe. A failed
+/// splice moves nothing, so the caller can fall back at any point.
+fn splice_directly<R: FdReadable, S: AsFd>(
+    handle: &InputHandle<R>,
+    write_fd: &S,
+) -> CatResult<bool> {
+    loop {
+        if uucore::cancel::is_cancelled() {
+            return Err(CatError::Interrupted);
+        }
+        match splice(&handle.reader, write_fd, SPLICE_SIZE) {
+            Ok(0) => return Ok(false),
+            Ok(_) => {}
+            Err(_) => return Ok(true),
+        }
+    }
+}
+
+/// Copy from `handle` into `write_fd` with `copy_file_range()`, which copies
+/// between files inside the kernel and lets the file system share or offload
+/// the data. Neither side may be a pipe; [`write_fast_using_splice`] is the
+/// better choice for those.
+///
+/// As with [`write_fast_using_splice`], `true` means the caller has to copy
+/// the rest of the data itself. That is the case for anything the call does
+/// not support, e.g. an output opened for appending, and also when nothing
+/// is copied at all, as happens for files in `/proc` that report no size.
+pub(super) fn write_fast_using_copy_file_range<R: FdReadable, S: AsFd>(
+    handle: &InputHandle<R>,
+    write_fd: &S,
+) -> CatResult<bool> {
+    if is_pipe(&handle.reader) || is_pipe(write_fd) {
+        return Ok(true);
+    }
+
+    let mut copied_any = false;
+    loop {
+        if uucore::cancel::is_cancelled() {
+            return Err(CatError::Interrupted);
+        }
+        match copy_file_range(&handle.reader, None, write_fd, None, COPY_FILE_RANGE_SIZE) {
+            Ok(0) => return Ok(!copied_any),
+            Ok(_) => copied_any = true,
+            Err(_) => return Ok(true),
+        }
+    }
+}
+
+fn is_pipe(fd: &impl AsFd) -> bool {
+    fstat(fd).is_ok_and(|stat| {
+        SFlag::from_bits_truncate(stat.st_mode & SFlag::S_IFMT.bits()) == SFlag::S_IFIFO
+    })
+}
+
 /// Move exactly `num_bytes` bytes from `read_fd` to `write_fd`.
 ///
 /// Panics if not enough bytes can be read.
@@ -16,4 +16,5 @@ ck-common-help-warn = warn about improperly formatted checksum lines
 ck-common-help-status = don't output anything, status code shows success
 ck-common-help-quiet = don't print OK for each successfully verified file
 ck-common-help-ignore-missing = don't fail or report status for missing files
+ck-common-help-fail-fast = stop at the first line that makes the check fail
 ck-common-help-debug = print CPU hardware capability detection info used by cksum
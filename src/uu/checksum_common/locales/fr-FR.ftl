@@ -15,5 +15,6 @@ ck-common-help-warn = avertir des lignes de somme de contrôle mal formatées
 ck-common-help-status = ne rien afficher, le code de statut indique le succès
 ck-common-help-quiet = ne pas afficher OK pour chaque fichier vérifié avec succès
 ck-common-help-ignore-missing = ne pas échouer ou signaler le statut pour les fichiers manquants
+ck-common-help-fail-fast = s'arrêter à la première ligne qui fait échouer la vérification
 ck-common-help-zero = terminer chaque ligne de sortie avec NUL, pas un saut de ligne, et désactiver l'échappement des noms de fichiers
 ck-common-help-debug = afficher les informations de débogage sur la détection de la prise en charge matérielle du processeur
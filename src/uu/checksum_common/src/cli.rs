@@ -30,6 +30,7 @@ pub mod options {
     pub const STATUS: &str = "status";
     pub const WARN: &str = "warn";
     pub const IGNORE_MISSING: &str = "ignore-missing";
+    pub const FAIL_FAST: &str = "fail-fast";
     pub const QUIET: &str = "quiet";
 }
 
@@ -123,6 +124,12 @@ impl ChecksumCommand for Command {
                 .help(translate!("ck-common-help-strict"))
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::FAIL_FAST)
+                .long(options::FAIL_FAST)
+                .help(translate!("ck-common-help-fail-fast"))
+                .action(ArgAction::SetTrue),
+        )
     }
 
     fn with_binary(self) -> Self {
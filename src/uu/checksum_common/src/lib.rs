@@ -64,7 +64,8 @@ pub fn standalone_with_length_main(
     args: impl uucore::Args,
     validate_len: fn(&str) -> UResult<Option<usize>>,
 ) -> UResult<()> {
-    let matches = uucore::clap_localization::handle_clap_result(cmd, args)?;
+    let args: Vec<OsString> = args.collect();
+    let matches = uucore::clap_localization::handle_clap_result(cmd, args.iter().cloned())?;
     let algo = Some(algo);
 
     let length = matches
@@ -74,17 +75,18 @@ pub fn standalone_with_length_main(
         .transpose()?
         .flatten();
 
-    let format = OutputFormat::from_standalone(std::env::args_os());
+    let format = OutputFormat::from_standalone(args.iter());
 
     checksum_main(algo, length, matches, format?)
 }
 
 /// Entrypoint for standalone checksums *NOT* accepting the `--length` argument
 pub fn standalone_main(algo: AlgoKind, cmd: Command, args: impl uucore::Args) -> UResult<()> {
-    let matches = uucore::clap_localization::handle_clap_result(cmd, args)?;
+    let args: Vec<OsString> = args.collect();
+    let matches = uucore::clap_localization::handle_clap_result(cmd, args.iter().cloned())?;
     let algo = Some(algo);
 
-    let format = OutputFormat::from_standalone(std::env::args_os());
+    let format = OutputFormat::from_standalone(args.iter());
 
     checksum_main(algo, None, matches, format?)
 }
@@ -155,6 +157,7 @@ pub fn checksum_main(
     let quiet = check_flag("quiet")?;
     let strict = check_flag("strict")?;
     let status = check_flag("status")?;
+    let fail_fast = check_flag(options::FAIL_FAST)?;
 
     // clap provides the default value -. So we unwrap() safety.
     let files = matches
@@ -183,6 +186,7 @@ pub fn checksum_main(
             ignore_missing,
             strict,
             verbose,
+            fail_fast,
         };
 
         return validate::perform_checksum_validation(files, algo, length, opts);
@@ -337,13 +337,7 @@ impl Chmoder {
         path: &Path,
         is_command_line_arg: bool,
     ) -> UResult<()> {
-        let should_follow_symlink = match self.traverse_symlinks {
-            TraverseSymlinks::All => true,
-            TraverseSymlinks::First => is_command_line_arg,
-            TraverseSymlinks::None => false,
-        };
-
-        if !should_follow_symlink {
+        if !self.traverse_symlinks.should_follow(is_command_line_arg) {
             return self.chmod_file_internal(path, false);
         }
 
@@ -354,12 +348,24 @@ impl Chmoder {
                 self.chmod_file(path)
             }
             Err(_) => {
-                // Dangling symlink, chmod it without dereferencing
-                self.chmod_file_internal(path, false)
+                self.report_dangling_symlink(path);
+                Ok(())
             }
         }
     }
 
+    /// Report that the symlink `path` cannot be followed because it dangles.
+    ///
+    /// Like GNU, this is not fatal: the remaining files are still processed,
+    /// but the exit status is 1, even with `--quiet`.
+    fn report_dangling_symlink(&self, path: &Path) {
+        if self.quiet {
+            set_exit_code(1);
+        } else {
+            show!(ChmodError::DanglingSymlink(path.into()));
+        }
+    }
+
     fn chmod(&self, files: &[OsString]) -> UResult<()> {
         let mut r = Ok(());
 
@@ -372,14 +378,11 @@ impl Chmoder {
                         // Don't try to change the mode of the symlink itself
                         continue;
                     }
-                    if self.recursive && self.traverse_symlinks == TraverseSymlinks::None {
+                    if self.recursive && !self.traverse_symlinks.should_follow(true) {
                         continue;
                     }
 
-                    if !self.quiet {
-                        show!(ChmodError::DanglingSymlink(filename.into()));
-                        set_exit_code(1);
-                    }
+                    self.report_dangling_symlink(file);
 
                     if self.verbose {
                         println!(
@@ -422,11 +425,7 @@ impl Chmoder {
         let mut r = self.chmod_file(file_path);
 
         // Determine whether to traverse symlinks based on context and traversal mode
-        let should_follow_symlink = match self.traverse_symlinks {
-            TraverseSymlinks::All => true,
-            TraverseSymlinks::First => is_command_line_arg, // Only follow symlinks that are command line args
-            TraverseSymlinks::None => false,
-        };
+        let should_follow_symlink = self.traverse_symlinks.should_follow(is_command_line_arg);
 
         // If the path is a directory (or we should follow symlinks), recurse into it
         if (!file_path.is_symlink() || should_follow_symlink) && file_path.is_dir() {
@@ -465,11 +464,7 @@ impl Chmoder {
         let mut r = self.chmod_file(file_path);
 
         // Determine whether to traverse symlinks based on context and traversal mode
-        let should_follow_symlink = match self.traverse_symlinks {
-            TraverseSymlinks::All => true,
-            TraverseSymlinks::First => is_command_line_arg, // Only follow symlinks that are command line args
-            TraverseSymlinks::None => false,
-        };
+        let should_follow_symlink = self.traverse_symlinks.should_follow(is_command_line_arg);
 
         // If the path is a directory (or we should follow symlinks), recurse into it using safe traversal
         if (!file_path.is_symlink() || should_follow_symlink) && file_path.is_dir() {
@@ -496,14 +491,19 @@ impl Chmoder {
 
         let entries = dir_fd.read_dir()?;
 
-        // Determine if we should follow symlinks (doesn't depend on entry_name)
-        let should_follow_symlink = self.traverse_symlinks == TraverseSymlinks::All;
+        // Symlinks found during the traversal are never command line arguments
+        let should_follow_symlink = self.traverse_symlinks.should_follow(false);
 
         for entry_name in entries {
             let entry_path = dir_path.join(&entry_name);
 
             let dir_meta = dir_fd.metadata_at(&entry_name, should_follow_symlink.into());
             let Ok(meta) = dir_meta else {
+                if entry_path.is_symlink() {
+                    // A dangling symlink, which can only fail to stat when following it
+                    self.report_dangling_symlink(&entry_path);
+                    continue;
+                }
                 // Handle permission denied with proper file path context
                 let e = dir_meta.unwrap_err();
                 let error = if e.kind() == std::io::ErrorKind::PermissionDenied {
@@ -553,27 +553,21 @@ impl Chmoder {
         dir_fd: &DirFd,
         entry_name: &std::ffi::OsStr,
     ) -> UResult<()> {
-        // During recursion, determine behavior based on traversal mode
-        match self.traverse_symlinks {
-            TraverseSymlinks::All => {
-                // Follow all symlinks during recursion
-                // Check if the symlink target is a directory, but handle dangling symlinks gracefully
-                match fs::metadata(path) {
-                    Ok(meta) if meta.is_dir() => self.walk_dir_with_context(path, false),
-                    Ok(meta) => {
-                        // It's a file symlink, chmod it using safe traversal
-                        self.safe_chmod_file(path, dir_fd, entry_name, meta.mode() & 0o7777)
-                    }
-                    Err(_) => {
-                        // Dangling symlink, chmod it without dereferencing
-                        self.chmod_file_internal(path, false)
-                    }
-                }
+        if !self.traverse_symlinks.should_follow(false) {
+            // Don't follow symlinks encountered during recursion
+            // For these symlinks, don't dereference them even if dereference is normally true
+            return self.chmod_file_internal(path, false);
+        }
+
+        match fs::metadata(path) {
+            Ok(meta) if meta.is_dir() => self.walk_dir_with_context(path, false),
+            Ok(meta) => {
+                // It's a file symlink, chmod it using safe traversal
+                self.safe_chmod_file(path, dir_fd, entry_name, meta.mode() & 0o7777)
             }
-            TraverseSymlinks::First | TraverseSymlinks::None => {
-                // Don't follow symlinks encountered during recursion
-                // For these symlinks, don't dereference them even if dereference is normally true
-                self.chmod_file_internal(path, false)
+            Err(_) => {
+                self.report_dangling_symlink(path);
+                Ok(())
             }
         }
     }
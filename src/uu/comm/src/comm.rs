@@ -14,6 +14,7 @@ use uucore::display::Quotable;
 use uucore::error::{FromIo, UResult, USimpleError};
 use uucore::format_usage;
 use uucore::fs::paths_refer_to_same_file;
+use uucore::i18n::collator::{init_locale_collation, locale_cmp};
 use uucore::line_ending::LineEnding;
 use uucore::translate;
 
@@ -106,14 +107,19 @@ impl OrderChecker {
         }
     }
 
-    fn verify_order(&mut self, current_line: &[u8]) -> bool {
+    /// Check that `current_line` does not sort before the previous line of
+    /// this file. The first disorder is reported once, after flushing the
+    /// lines already written to `out`, so that the message shows up where
+    /// the problem is, like GNU.
+    fn verify_order(&mut self, current_line: &[u8], out: &mut impl Write) -> bool {
         if self.last_line.is_empty() {
             self.last_line = current_line.to_vec();
             return true;
         }
 
-        let is_ordered = *current_line >= *self.last_line;
+        let is_ordered = compare_lines(current_line, &self.last_line) != Ordering::Less;
         if !is_ordered && !self.has_error {
+            let _ = out.flush();
             let _ = writeln!(
                 stderr(),
                 "{}",
@@ -127,6 +133,15 @@ impl OrderChecker {
     }
 }
 
+/// Compare two lines without their line endings, in the collating order of
+/// the current locale. Lines that collate equally are ordered by their bytes,
+/// so that only identical lines are reported as common to both files.
+fn compare_lines(a: &[u8], b: &[u8]) -> Ordering {
+    let a = &a[..a.len() - 1];
+    let b = &b[..b.len() - 1];
+    locale_cmp(a, b).then_with(|| a.cmp(b))
+}
+
 // Check if two files are identical by comparing their contents
 pub fn are_files_identical(path1: &Path, path2: &Path) -> io::Result<bool> {
     // First compare file sizes
@@ -242,12 +257,12 @@ fn comm(
         let ord = match (na, nb) {
             (0, _) => Ordering::Greater,
             (_, 0) => Ordering::Less,
-            (_, _) => ra.as_slice().cmp(rb.as_slice()),
+            (_, _) => compare_lines(ra, rb),
         };
 
         match ord {
             Ordering::Less => {
-                if should_check_order && !checker1.verify_order(ra) {
+                if should_check_order && !checker1.verify_order(ra, &mut writer) {
                     break;
                 }
                 if !opts.get_flag(options::COLUMN_1) {
@@ -262,7 +277,7 @@ fn comm(
                 total_col_1 += 1;
             }
             Ordering::Greater => {
-                if should_check_order && !checker2.verify_order(rb) {
+                if should_check_order && !checker2.verify_order(rb, &mut writer) {
                     break;
                 }
                 if !opts.get_flag(options::COLUMN_2) {
@@ -275,7 +290,9 @@ fn comm(
                 total_col_2 += 1;
             }
             Ordering::Equal => {
-                if should_check_order && (!checker1.verify_order(ra) || !checker2.verify_order(rb))
+                if should_check_order
+                    && (!checker1.verify_order(ra, &mut writer)
+                        || !checker2.verify_order(rb, &mut writer))
                 {
                     break;
                 }
@@ -340,6 +357,7 @@ fn open_file(name: &OsString, line_ending: LineEnding) -> io::Result<LineReader>
 #[uucore::main]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     let matches = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
+    init_locale_collation();
     let line_ending = LineEnding::from_zero_flag(matches.get_flag(options::ZERO_TERMINATED));
     let filename1 = matches.get_one::<OsString>(options::FILE_1).unwrap();
     let filename2 = matches.get_one::<OsString>(options::FILE_2).unwrap();
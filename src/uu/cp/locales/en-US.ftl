@@ -45,6 +45,7 @@ cp-help-sparse = control creation of sparse files. See below
 cp-help-selinux = set SELinux security context of destination file to default type
 cp-help-context = like -Z, or if CTX is specified then set the SELinux or SMACK security context to CTX
 cp-help-progress = Display a progress bar. Note: this feature is not supported by GNU coreutils.
+cp-help-sync = Flush each copy and its directory to the storage device before exiting. Note: this feature is not supported by GNU coreutils.
 cp-help-copy-contents = NotImplemented: copy contents of special files when recursive
 
 # Error messages
@@ -55,7 +56,7 @@ cp-error-same-file = { $source } and { $dest } are the same file
 cp-error-backing-up-destroy-source = backing up { $dest } might destroy source;  { $source } not copied
 cp-error-cannot-open-for-reading = cannot open { $source } for reading
 cp-error-not-writing-dangling-symlink = not writing through dangling symlink { $dest }
-cp-error-failed-to-clone = failed to clone { $source } from { $dest }: { $error }
+cp-error-failed-to-clone = failed to clone { $dest } from { $source }: { $error }
 cp-error-cannot-change-attribute = cannot change attribute { $dest }: Source file is a non regular file
 cp-error-cannot-stat = cannot stat { $source }: No such file or directory
 cp-error-cannot-create-symlink = cannot create symlink { $dest } to { $source }
@@ -75,7 +76,7 @@ cp-error-backup-mutually-exclusive = options --backup and --no-clobber are mutua
 cp-error-invalid-argument = invalid argument { $arg } for '{ $option }'
 cp-error-option-not-implemented = Option '{ $option }' not yet implemented.
 cp-error-not-all-files-copied = Not all files were copied
-cp-error-reflink-always-sparse-auto = `--reflink=always` can be used only with --sparse=auto
+cp-error-reflink-always-sparse-auto = --reflink can be used only with --sparse=auto
 cp-error-file-exists = { $path }: File exists
 cp-error-invalid-backup-argument = --backup is mutually exclusive with -n or --update=none-fail
 cp-error-reflink-not-supported = --reflink is only supported on linux and macOS
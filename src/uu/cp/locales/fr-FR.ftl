@@ -45,6 +45,7 @@ cp-help-sparse = contrôler la création de fichiers épars. Voir ci-dessous
 cp-help-selinux = définir le contexte de sécurité SELinux du fichier de destination au type par défaut
 cp-help-context = comme -Z, ou si CTX est spécifié, définir le contexte de sécurité SELinux ou SMACK à CTX
 cp-help-progress = Afficher une barre de progression. Note : cette fonctionnalité n'est pas supportée par GNU coreutils.
+cp-help-sync = Écrire chaque copie et son répertoire sur le périphérique de stockage avant de terminer. Note : cette fonctionnalité n'est pas supportée par GNU coreutils.
 cp-help-copy-contents = Non implémenté : copier le contenu des fichiers spéciaux lors de la récursion
 
 # Messages d'erreur
@@ -55,7 +56,7 @@ cp-error-same-file = { $source } et { $dest } sont le même fichier
 cp-error-backing-up-destroy-source = sauvegarder { $dest } pourrait détruire la source ; { $source } non copié
 cp-error-cannot-open-for-reading = impossible d'ouvrir { $source } en lecture
 cp-error-not-writing-dangling-symlink = ne pas écrire à travers le lien symbolique pendant { $dest }
-cp-error-failed-to-clone = échec du clonage de { $source } depuis { $dest } : { $error }
+cp-error-failed-to-clone = échec du clonage de { $dest } depuis { $source } : { $error }
 cp-error-cannot-change-attribute = impossible de changer l'attribut { $dest } : Le fichier source n'est pas un fichier régulier
 cp-error-cannot-stat = impossible de faire stat sur { $source } : Aucun fichier ou répertoire de ce type
 cp-error-cannot-create-symlink = impossible de créer le lien symbolique { $dest } vers { $source }
@@ -75,7 +76,7 @@ cp-error-backup-mutually-exclusive = les options --backup et --no-clobber sont m
 cp-error-invalid-argument = argument invalide { $arg } pour '{ $option }'
 cp-error-option-not-implemented = Option '{ $option }' pas encore implémentée.
 cp-error-not-all-files-copied = Tous les fichiers n'ont pas été copiés
-cp-error-reflink-always-sparse-auto = `--reflink=always` ne peut être utilisé qu'avec --sparse=auto
+cp-error-reflink-always-sparse-auto = --reflink ne peut être utilisé qu'avec --sparse=auto
 cp-error-file-exists = { $path } : Le fichier existe
 cp-error-invalid-backup-argument = --backup est mutuellement exclusif avec -n ou --update=none-fail
 cp-error-reflink-not-supported = --reflink n'est supporté que sur linux et macOS
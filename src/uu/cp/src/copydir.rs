@@ -10,17 +10,17 @@
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::convert::identity;
-use std::env;
 use std::fs::{self, exists};
 use std::io;
 use std::path::{Path, PathBuf, StripPrefixError};
 
-use indicatif::ProgressBar;
 use uucore::display::Quotable;
 use uucore::error::UIoError;
 use uucore::fs::{
-    FileInformation, MissingHandling, ResolveMode, canonicalize, path_ends_with_terminator,
+    FileInformation, MissingHandling, ResolveMode, canonicalize, current_dir,
+    path_ends_with_terminator,
 };
+use uucore::progress::ProgressBar;
 use uucore::show;
 use uucore::translate;
 use uucore::uio_error;
@@ -119,7 +119,7 @@ struct Context<'a> {
 
 impl<'a> Context<'a> {
     fn new(root: &'a Path, target: &'a Path) -> io::Result<Self> {
-        let current_dir = env::current_dir()?;
+        let current_dir = current_dir()?;
         let root_path = current_dir.join(root);
         let target_is_file = target.is_file();
         let root_parent = if target.exists() && !root.to_str().unwrap().ends_with("/.") {
@@ -671,5 +671,8 @@ fn build_dir(
     }
 
     builder.create(path)?;
+    if options.sync {
+        uucore::fs::sync_parent_dir(path)?;
+    }
     Ok(())
 }
@@ -21,7 +21,6 @@ use uucore::translate;
 
 use clap::{Arg, ArgAction, ArgMatches, Command, builder::ValueParser, value_parser};
 use filetime::FileTime;
-use indicatif::{ProgressBar, ProgressStyle};
 use thiserror::Error;
 
 use platform::copy_on_write;
@@ -31,9 +30,10 @@ use uucore::error::{UError, UResult, UUsageError, set_exit_code};
 use uucore::fs::make_fifo;
 use uucore::fs::{
     FileInformation, MissingHandling, ResolveMode, are_hardlinks_to_same_file, canonicalize,
-    get_filename, is_symlink_loop, normalize_path, path_ends_with_terminator,
+    get_filename, is_same_file, is_symlink_loop, normalize_path, path_ends_with_terminator,
     paths_refer_to_same_file,
 };
+use uucore::progress::{self, ProgressBar};
 use uucore::{backup_control, update_control};
 // These are exposed for projects (e.g. nushell) that want to create an `Options` value, which
 // requires these enum.
@@ -342,6 +342,8 @@ pub struct Options {
     pub verbose: bool,
     /// `-g`, `--progress`
     pub progress_bar: bool,
+    /// `--sync`, or the `UUTILS_SYNC` environment variable
+    pub sync: bool,
     /// -Z
     pub set_selinux_context: bool,
     // --context
@@ -372,6 +374,7 @@ impl Default for Options {
             debug: false,
             verbose: false,
             progress_bar: false,
+            sync: false,
             set_selinux_context: false,
             context: None,
         }
@@ -485,6 +488,7 @@ mod options {
     pub const SPARSE: &str = "sparse";
     pub const STRIP_TRAILING_SLASHES: &str = "strip-trailing-slashes";
     pub const SYMBOLIC_LINK: &str = "symbolic-link";
+    pub const SYNC: &str = "sync";
     pub const TARGET_DIRECTORY: &str = "target-directory";
     pub const DEBUG: &str = "debug";
     pub const VERBOSE: &str = "verbose";
@@ -788,6 +792,12 @@ pub fn uu_app() -> Command {
                 .action(ArgAction::SetTrue)
                 .help(translate!("cp-help-progress")),
         )
+        .arg(
+            Arg::new(options::SYNC)
+                .long(options::SYNC)
+                .action(ArgAction::SetTrue)
+                .help(translate!("cp-help-sync")),
+        )
         // TODO: implement the following args
         .arg(
             Arg::new(options::COPY_CONTENTS)
@@ -820,6 +830,13 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         ));
     }
 
+    if options.reflink_mode == ReflinkMode::Always && options.sparse_mode != SparseMode::Auto {
+        return Err(UUsageError::new(
+            EXIT_ERR,
+            translate!("cp-error-reflink-always-sparse-auto"),
+        ));
+    }
+
     let paths: Vec<PathBuf> = matches
         .get_many::<OsString>(options::PATHS)
         .map(|v| v.map(PathBuf::from).collect())
@@ -1132,10 +1149,8 @@ impl Options {
                 options::PRESERVE => {
                     attributes = attributes.union(&Attributes::parse_iter(val.into_iter())?);
                 }
-                options::NO_PRESERVE => {
-                    if !val.is_empty() {
-                        attributes = attributes.diff(&Attributes::parse_iter(val.into_iter())?);
-                    }
+                options::NO_PRESERVE if !val.is_empty() => {
+                    attributes = attributes.diff(&Attributes::parse_iter(val.into_iter())?);
                 }
                 _ => (),
             }
@@ -1233,6 +1248,7 @@ impl Options {
             recursive,
             target_dir,
             progress_bar: matches.get_flag(options::PROGRESS_BAR),
+            sync: uucore::fs::sync_requested(matches.get_flag(options::SYNC)),
             set_selinux_context: set_selinux_context || context.is_some(),
             context,
         };
@@ -1395,14 +1411,7 @@ pub fn copy(sources: &[PathBuf], target: &Path, options: &Options) -> CopyResult
     let mut created_parent_dirs: HashSet<PathBuf> = HashSet::new();
 
     let progress_bar = if options.progress_bar {
-        let pb = ProgressBar::new(disk_usage(sources, options.recursive)?)
-            .with_style(
-                ProgressStyle::with_template(
-                    "{msg}: [{elapsed_precise}] {wide_bar} {bytes:>7}/{total_bytes:7}",
-                )
-                .unwrap(),
-            )
-            .with_message(uucore::util_name());
+        let pb = progress::bytes_bar(disk_usage(sources, options.recursive)?);
         pb.tick();
         Some(pb)
     } else {
@@ -1754,48 +1763,34 @@ fn copy_extended_attrs(source: &Path, dest: &Path, skip_selinux: bool) -> CopyRe
     Ok(())
 }
 
-/// Copy the specified attributes from one path to another.
-/// If `skip_selinux_xattr` is true, the security.selinux xattr will not be copied
-/// (used when -Z is specified to set the default context instead).
-#[allow(unused_variables)]
-pub(crate) fn copy_attributes(
-    source: &Path,
-    dest: &Path,
-    attributes: &Attributes,
-    dest_is_freshly_created_dir: bool,
-    skip_selinux_xattr: bool,
-) -> CopyResult<()> {
-    let context = &*format!("{} -> {}", source.quote(), dest.quote());
-    let source_metadata =
-        fs::symlink_metadata(source).map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
-
-    let mode_explicitly_disabled = matches!(attributes.mode, Preserve::No { explicit: true });
-
-    // preserve is true by default if the destination is created by us and it's a directory
-    let mode = if !mode_explicitly_disabled && dest_is_freshly_created_dir {
-        Preserve::Yes { required: false }
-    } else {
-        attributes.mode
-    };
+/// The source and destination of an attribute-copying operation, shared by
+/// the individual preservation steps of [`copy_attributes`].
+struct AttributeCopy<'a> {
+    source: &'a Path,
+    dest: &'a Path,
+    source_metadata: Metadata,
+    context: &'a str,
+}
 
-    // Ownership must be changed first to avoid interfering with mode change.
+impl AttributeCopy<'_> {
     #[cfg(unix)]
-    handle_preserve(attributes.ownership, || -> CopyResult<()> {
+    fn ownership(&self) -> CopyResult<()> {
         use std::os::unix::prelude::MetadataExt;
         use uucore::perms::Verbosity;
         use uucore::perms::VerbosityLevel;
         use uucore::perms::wrap_chown;
 
-        let dest_uid = source_metadata.uid();
-        let dest_gid = source_metadata.gid();
-        let meta = &dest
+        let dest_uid = self.source_metadata.uid();
+        let dest_gid = self.source_metadata.gid();
+        let meta = &self
+            .dest
             .symlink_metadata()
-            .map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
+            .map_err(|e| CpError::IoErrContext(e, self.context.to_owned()))?;
 
         let try_chown = {
             |uid| {
                 wrap_chown(
-                    dest,
+                    self.dest,
                     meta,
                     uid,
                     Some(dest_gid),
@@ -1813,65 +1808,65 @@ pub(crate) fn copy_attributes(
             let _ = try_chown(None);
         }
         Ok(())
-    })?;
+    }
 
-    handle_preserve(mode, || -> CopyResult<()> {
+    fn mode(&self) -> CopyResult<()> {
         // The `chmod()` system call that underlies the
         // `fs::set_permissions()` call is unable to change the
         // permissions of a symbolic link. In that case, we just
         // do nothing, since every symbolic link has the same
         // permissions.
-        if !dest.is_symlink() {
-            fs::set_permissions(dest, source_metadata.permissions())
-                .map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
+        if !self.dest.is_symlink() {
+            fs::set_permissions(self.dest, self.source_metadata.permissions())
+                .map_err(|e| CpError::IoErrContext(e, self.context.to_owned()))?;
             // FIXME: Implement this for windows as well
             #[cfg(feature = "feat_acl")]
-            exacl::getfacl(source, None)
-                .and_then(|acl| exacl::setfacl(&[dest], &acl, None))
+            exacl::getfacl(self.source, None)
+                .and_then(|acl| exacl::setfacl(&[self.dest], &acl, None))
                 .map_err(|err| CpError::Error(err.to_string()))?;
         }
 
         Ok(())
-    })?;
+    }
 
-    handle_preserve(attributes.timestamps, || -> CopyResult<()> {
-        let atime = FileTime::from_last_access_time(&source_metadata);
-        let mtime = FileTime::from_last_modification_time(&source_metadata);
-        if dest.is_symlink() {
-            filetime::set_symlink_file_times(dest, atime, mtime)?;
+    fn timestamps(&self) -> CopyResult<()> {
+        let atime = FileTime::from_last_access_time(&self.source_metadata);
+        let mtime = FileTime::from_last_modification_time(&self.source_metadata);
+        if self.dest.is_symlink() {
+            filetime::set_symlink_file_times(self.dest, atime, mtime)?;
         } else {
-            filetime::set_file_times(dest, atime, mtime)?;
+            filetime::set_file_times(self.dest, atime, mtime)?;
         }
 
         Ok(())
-    })?;
+    }
 
     #[cfg(all(feature = "selinux", any(target_os = "linux", target_os = "android")))]
-    handle_preserve(attributes.context, || -> CopyResult<()> {
+    fn context(&self) -> CopyResult<()> {
         // Get the source context and apply it to the destination
-        if let Ok(context) = selinux::SecurityContext::of_path(source, false, false) {
+        if let Ok(context) = selinux::SecurityContext::of_path(self.source, false, false) {
             if let Some(context) = context {
-                if let Err(e) = context.set_for_path(dest, false, false) {
+                if let Err(e) = context.set_for_path(self.dest, false, false) {
                     return Err(CpError::Error(
-                        translate!("cp-error-selinux-set-context", "path" => dest.quote(), "error" => e),
+                        translate!("cp-error-selinux-set-context", "path" => self.dest.quote(), "error" => e),
                     ));
                 }
             }
         } else {
             return Err(CpError::Error(
-                translate!("cp-error-selinux-get-context", "path" => source.quote()),
+                translate!("cp-error-selinux-get-context", "path" => self.source.quote()),
             ));
         }
         Ok(())
-    })?;
+    }
 
-    handle_preserve(attributes.xattr, || -> CopyResult<()> {
+    #[allow(unused_variables)]
+    fn xattr(&self, skip_selinux_xattr: bool) -> CopyResult<()> {
         #[cfg(all(unix, not(target_os = "android")))]
         {
-            copy_extended_attrs(source, dest, skip_selinux_xattr)?;
+            copy_extended_attrs(self.source, self.dest, skip_selinux_xattr)?;
         }
         #[cfg(not(all(unix, not(target_os = "android"))))]
-        #[allow(unused_variables)]
         {
             // The documentation for GNU cp states:
             //
@@ -1886,7 +1881,49 @@ pub(crate) fn copy_attributes(
         }
 
         Ok(())
-    })?;
+    }
+}
+
+/// Copy the specified attributes from one path to another.
+/// If `skip_selinux_xattr` is true, the security.selinux xattr will not be copied
+/// (used when -Z is specified to set the default context instead).
+///
+/// Each attribute is applied by its own step, so any subset selected with
+/// `--preserve` and `--no-preserve` only touches the requested attributes.
+pub(crate) fn copy_attributes(
+    source: &Path,
+    dest: &Path,
+    attributes: &Attributes,
+    dest_is_freshly_created_dir: bool,
+    skip_selinux_xattr: bool,
+) -> CopyResult<()> {
+    let context = &*format!("{} -> {}", source.quote(), dest.quote());
+    let source_metadata =
+        fs::symlink_metadata(source).map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
+    let copy = AttributeCopy {
+        source,
+        dest,
+        source_metadata,
+        context,
+    };
+
+    let mode_explicitly_disabled = matches!(attributes.mode, Preserve::No { explicit: true });
+
+    // preserve is true by default if the destination is created by us and it's a directory
+    let mode = if !mode_explicitly_disabled && dest_is_freshly_created_dir {
+        Preserve::Yes { required: false }
+    } else {
+        attributes.mode
+    };
+
+    // Ownership must be changed first to avoid interfering with mode change.
+    #[cfg(unix)]
+    handle_preserve(attributes.ownership, || copy.ownership())?;
+    handle_preserve(mode, || copy.mode())?;
+    handle_preserve(attributes.timestamps, || copy.timestamps())?;
+    #[cfg(all(feature = "selinux", any(target_os = "linux", target_os = "android")))]
+    handle_preserve(attributes.context, || copy.context())?;
+    handle_preserve(attributes.xattr, || copy.xattr(skip_selinux_xattr))?;
 
     Ok(())
 }
@@ -1896,28 +1933,20 @@ fn symlink_file(
     dest: &Path,
     symlinked_files: &mut HashSet<FileInformation>,
 ) -> CopyResult<()> {
-    #[cfg(not(windows))]
-    {
-        std::os::unix::fs::symlink(source, dest).map_err(|e| {
-            CpError::IoErrContext(
-                e,
-                translate!("cp-error-cannot-create-symlink",
-                           "dest" => get_filename(dest).unwrap_or("?").quote(),
-                           "source" => get_filename(source).unwrap_or("?").quote()),
-            )
-        })?;
-    }
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(source, dest);
     #[cfg(windows)]
-    {
-        std::os::windows::fs::symlink_file(source, dest).map_err(|e| {
-            CpError::IoErrContext(
-                e,
-                translate!("cp-error-cannot-create-symlink",
-                           "dest" => get_filename(dest).unwrap_or("?").quote(),
-                           "source" => get_filename(source).unwrap_or("?").quote()),
-            )
-        })?;
-    }
+    let result = std::os::windows::fs::symlink_file(source, dest);
+    #[cfg(target_family = "wasm")]
+    let result = uucore::wasm_io::symlink(source, dest);
+    result.map_err(|e| {
+        CpError::IoErrContext(
+            e,
+            translate!("cp-error-cannot-create-symlink",
+                       "dest" => get_filename(dest).unwrap_or("?").quote(),
+                       "source" => get_filename(source).unwrap_or("?").quote()),
+        )
+    })?;
     if let Ok(file_info) = FileInformation::from_path(dest, false) {
         symlinked_files.insert(file_info);
     }
@@ -2024,7 +2053,7 @@ fn handle_existing_dest(
     let mut is_dest_removed = false;
     let backup_path = backup_control::get_backup_path(options.backup, dest, &options.backup_suffix);
     if let Some(backup_path) = backup_path {
-        if paths_refer_to_same_file(source, &backup_path, true) {
+        if is_same_file(source, &backup_path) {
             return Err(translate!("cp-error-backing-up-destroy-source", "dest" => dest.quote(), "source" => source.quote())
             .into());
         }
@@ -2233,6 +2262,8 @@ fn handle_copy_mode(
     #[cfg(unix)] source_is_stream: bool,
 ) -> CopyResult<PerformedAction> {
     let source_is_symlink = source_metadata.is_symlink();
+    #[cfg(unix)]
+    let no_follow = !options.dereference(source_in_command_line);
 
     match options.copy_mode {
         CopyMode::Link => {
@@ -2274,6 +2305,8 @@ fn handle_copy_mode(
                 created_parent_dirs,
                 #[cfg(unix)]
                 source_is_stream,
+                #[cfg(unix)]
+                no_follow,
             )?;
         }
         CopyMode::SymLink => {
@@ -2298,6 +2331,8 @@ fn handle_copy_mode(
                             created_parent_dirs,
                             #[cfg(unix)]
                             source_is_stream,
+                            #[cfg(unix)]
+                            no_follow,
                         )?;
                     }
                     UpdateMode::None => {
@@ -2335,6 +2370,8 @@ fn handle_copy_mode(
                             created_parent_dirs,
                             #[cfg(unix)]
                             source_is_stream,
+                            #[cfg(unix)]
+                            no_follow,
                         )?;
                     }
                 }
@@ -2351,16 +2388,20 @@ fn handle_copy_mode(
                     created_parent_dirs,
                     #[cfg(unix)]
                     source_is_stream,
+                    #[cfg(unix)]
+                    no_follow,
                 )?;
             }
         }
         CopyMode::AttrOnly => {
+            // No data is copied; only make sure there is a file to apply the
+            // attributes to, leaving existing contents untouched.
             OpenOptions::new()
                 .write(true)
                 .truncate(false)
                 .create(true)
                 .open(dest)
-                .unwrap();
+                .map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
         }
     }
 
@@ -2466,7 +2507,7 @@ fn copy_file(
                 translate!("cp-error-not-writing-dangling-symlink", "dest" => dest.quote()),
             ));
         }
-        if paths_refer_to_same_file(source, dest, true)
+        if is_same_file(source, dest)
             && matches!(
                 options.overwrite,
                 OverwriteMode::Clobber(ClobberMode::RemoveDestination)
@@ -2494,7 +2535,7 @@ fn copy_file(
                 OverwriteMode::Clobber(ClobberMode::RemoveDestination)
             ))
     {
-        if paths_refer_to_same_file(source, dest, true) && options.copy_mode == CopyMode::Link {
+        if is_same_file(source, dest) && options.copy_mode == CopyMode::Link {
             if source_is_symlink {
                 if !dest_is_symlink {
                     return Ok(());
@@ -2653,9 +2694,12 @@ fn copy_file(
         )
     };
 
-    // GNU cp truncates the destination when a required attribute cannot be preserved
+    // GNU cp truncates the destination when a required attribute cannot be preserved,
+    // unless only attributes were requested, in which case its data is left alone.
     copy_attributes_result.inspect_err(|_| {
-        fs::File::create(dest).map(|f| f.set_len(0)).ok();
+        if options.copy_mode != CopyMode::AttrOnly {
+            fs::File::create(dest).map(|f| f.set_len(0)).ok();
+        }
     })?;
 
     #[cfg(all(feature = "selinux", target_os = "linux"))]
@@ -2672,6 +2716,10 @@ fn copy_file(
         );
     }
 
+    if options.sync {
+        uucore::fs::sync_file_and_parent(dest)?;
+    }
+
     if let Some(progress_bar) = progress_bar {
         progress_bar.inc(source_metadata.len());
     }
@@ -2753,6 +2801,7 @@ fn copy_helper(
     symlinked_files: &mut HashSet<FileInformation>,
     created_parent_dirs: &mut HashSet<PathBuf>,
     #[cfg(unix)] source_is_stream: bool,
+    #[cfg(unix)] no_follow: bool,
 ) -> CopyResult<()> {
     if options.parents {
         let parent = dest.parent().unwrap_or(dest);
@@ -2782,6 +2831,10 @@ fn copy_helper(
             context,
             #[cfg(unix)]
             source_is_stream,
+            #[cfg(unix)]
+            no_follow,
+            #[cfg(target_os = "macos")]
+            &options.attributes,
         )?;
 
         if !options.attributes_only && options.debug {
@@ -4,15 +4,17 @@
 // file that was distributed with this source code.
 // spell-checker:ignore ficlone reflink ftruncate pwrite fiemap lseek
 
-use libc::{SEEK_DATA, SEEK_HOLE};
+use libc::SEEK_DATA;
+use std::cell::RefCell;
 use std::fs::{File, OpenOptions};
-use std::io::Read;
-use std::os::unix::fs::FileExt;
+use std::io::{Read, Write};
 use std::os::unix::fs::MetadataExt;
-use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
+use std::os::unix::fs::{FileTypeExt, OpenOptionsExt, PermissionsExt};
 use std::os::unix::io::AsRawFd;
 use std::path::Path;
 use uucore::buf_copy;
+use uucore::copy::{self, CopyOptions, Reflink, Sparse};
+use uucore::display::Quotable;
 use uucore::mode::get_umask;
 use uucore::translate;
 
@@ -24,10 +26,7 @@ use crate::{
 /// The fallback behavior for [`clone`] on failed system call.
 #[derive(Clone, Copy)]
 enum CloneFallback {
-    /// Raise an error.
-    Error,
-
-    /// Use [`std::fs::copy`].
+    /// Use [`fs_copy`].
     FSCopy,
 
     /// Use [`sparse_copy`]
@@ -42,7 +41,7 @@ enum CloneFallback {
 enum CopyMethod {
     /// Do a sparse copy
     SparseCopy,
-    /// Use [`std::fs::copy`].
+    /// Use [`fs_copy`].
     FSCopy,
     /// Default (can either be [`CopyMethod::SparseCopy`] or [`CopyMethod::FSCopy`])
     Default,
@@ -50,9 +49,83 @@ enum CopyMethod {
     SparseCopyWithoutHole,
 }
 
+/// Regular files up to this size are copied with [`copy_small_file`].
+const SMALL_FILE_MAX: u64 = 128 * 1024;
+
+thread_local! {
+    /// The buffer [`copy_small_file`] reads into, kept across calls so that
+    /// copying a tree of many small files doesn't allocate for each of them.
+    static SMALL_FILE_BUF: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// How [`copy_small_file`] copied a file.
+enum SmallFileCopy {
+    /// The source is not a candidate, `dest` was not touched.
+    Skipped,
+    /// `dest` was cloned from the source.
+    Cloned,
+    /// The contents were read and written, after a failed clone if `try_clone`.
+    Written,
+}
+
+/// Copy a small, non-sparse regular file with one read and one write.
+///
+/// For trees with many tiny files, the general path costs far more than the
+/// data itself: the source is opened several times for sparse detection, a
+/// clone is attempted and the copy is finally done by [`fs_copy`]. Here
+/// the source is opened and inspected once, and the destination is created
+/// and given the source's permissions through its open descriptor, as
+/// [`std::fs::copy`] does. With `try_clone`, the destination is cloned from
+/// the source if the file system supports it, so it still shares extents.
+///
+/// With `no_follow`, the source is opened with `O_NOFOLLOW`, so a symlink
+/// swapped in after the caller looked at the source is not followed.
+fn copy_small_file(
+    source: &Path,
+    dest: &Path,
+    no_follow: bool,
+    try_clone: bool,
+) -> std::io::Result<SmallFileCopy> {
+    let mut src_file = OpenOptions::new()
+        .read(true)
+        .custom_flags(if no_follow { libc::O_NOFOLLOW } else { 0 })
+        .open(source)?;
+    let metadata = src_file.metadata()?;
+    let size = metadata.size();
+    // Empty files may be virtual files with contents (e.g. in /proc), and
+    // files with fewer blocks than their size may be sparse or virtual; both
+    // need the detection done by the general path.
+    if !metadata.is_file() || size == 0 || size > SMALL_FILE_MAX || metadata.blocks() < size / 512 {
+        return Ok(SmallFileCopy::Skipped);
+    }
+
+    let permissions = metadata.permissions();
+    let mut dst_file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(permissions.mode())
+        .open(dest)?;
+    let dest_is_file = dst_file.metadata()?.is_file();
+    if dest_is_file {
+        dst_file.set_permissions(permissions)?;
+    }
+    if try_clone && dest_is_file && copy::reflink(&src_file, &dst_file).is_ok() {
+        return Ok(SmallFileCopy::Cloned);
+    }
+
+    SMALL_FILE_BUF.with(|buf| {
+        let mut buf = buf.borrow_mut();
+        buf.clear();
+        src_file.read_to_end(&mut buf)?;
+        dst_file.write_all(&buf)?;
+        Ok(SmallFileCopy::Written)
+    })
+}
+
 /// Use the Linux `ioctl_ficlone` API to do a copy-on-write clone.
 ///
-/// `fallback` controls what to do if the system call fails.
+/// `fallback` controls how to copy instead if the system call fails.
 #[cfg(any(target_os = "linux", target_os = "android"))]
 fn clone<P>(source: P, dest: P, fallback: CloneFallback) -> std::io::Result<()>
 where
@@ -60,20 +133,44 @@ where
 {
     let src_file = File::open(&source)?;
     let dst_file = File::create(&dest)?;
-    let src_fd = src_file.as_raw_fd();
-    let dst_fd = dst_file.as_raw_fd();
-    let result = unsafe { libc::ioctl(dst_fd, libc::FICLONE, src_fd) };
-    if result == 0 {
+    if copy::reflink(&src_file, &dst_file).is_ok() {
         return Ok(());
     }
     match fallback {
-        CloneFallback::Error => Err(std::io::Error::last_os_error()),
-        CloneFallback::FSCopy => std::fs::copy(source, dest).map(|_| ()),
+        CloneFallback::FSCopy => fs_copy(source, dest),
         CloneFallback::SparseCopy => sparse_copy(source, dest),
         CloneFallback::SparseCopyWithoutHole => sparse_copy_without_hole(source, dest),
     }
 }
 
+/// Copy `source` to `dest` without cloning or making holes, giving `dest`
+/// the permissions of `source` like [`std::fs::copy`].
+fn fs_copy<P>(source: P, dest: P) -> std::io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let options = CopyOptions {
+        reflink: Reflink::Never,
+        sparse: Sparse::Never,
+    };
+    copy::copy_path(source.as_ref(), dest.as_ref(), options).map(|_| ())
+}
+
+/// Copy the contents of `source` into `dest`, which is created or truncated,
+/// with `sparse` deciding which holes it gets.
+fn copy_contents<P>(source: P, dest: P, sparse: Sparse) -> std::io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let src_file = File::open(source)?;
+    let dst_file = File::create(dest)?;
+    let options = CopyOptions {
+        reflink: Reflink::Never,
+        sparse,
+    };
+    copy::copy_file(&src_file, &dst_file, options).map(|_| ())
+}
+
 /// Checks whether a file contains any non null bytes i.e. any byte != 0x0
 /// This function returns a tuple of (bool, u64, u64) signifying a tuple of (whether a file has
 /// data, its size, no of blocks it has allocated in disk)
@@ -124,49 +221,9 @@ fn sparse_copy_without_hole<P>(source: P, dest: P) -> std::io::Result<()>
 where
     P: AsRef<Path>,
 {
-    let src_file = File::open(source)?;
-    let dst_file = File::create(dest)?;
-    let dst_fd = dst_file.as_raw_fd();
-
-    let size = src_file.metadata()?.size();
-    if unsafe { libc::ftruncate(dst_fd, size.try_into().unwrap()) } < 0 {
-        return Err(std::io::Error::last_os_error());
-    }
-    let src_fd = src_file.as_raw_fd();
-    let mut current_offset: isize = 0;
-    // Maximize the data read at once to 16 MiB to avoid memory hogging with large files
-    // 16 MiB chunks should saturate an SSD
-    let step = std::cmp::min(size, 16 * 1024 * 1024) as usize;
-    let mut buf: Vec<u8> = vec![0x0; step];
-    loop {
-        let result = unsafe { libc::lseek(src_fd, current_offset.try_into().unwrap(), SEEK_DATA) }
-            .try_into()
-            .unwrap();
-
-        current_offset = result;
-        let hole: isize =
-            unsafe { libc::lseek(src_fd, current_offset.try_into().unwrap(), SEEK_HOLE) }
-                .try_into()
-                .unwrap();
-        if result == -1 || hole == -1 {
-            break;
-        }
-        if result <= -2 || hole <= -2 {
-            return Err(std::io::Error::last_os_error());
-        }
-        let len: isize = hole - current_offset;
-        // Read and write data in chunks of `step` while reusing the same buffer
-        for i in (0..len).step_by(step) {
-            // Ensure we don't read past the end of the file or the start of the next hole
-            let read_len = std::cmp::min((len - i) as usize, step);
-            let buf = &mut buf[..read_len];
-            src_file.read_exact_at(buf, (current_offset + i) as u64)?;
-            dst_file.write_all_at(buf, (current_offset + i) as u64)?;
-        }
-        current_offset = hole;
-    }
-    Ok(())
+    copy_contents(source, dest, Sparse::Auto)
 }
+
 /// Perform a sparse copy from one file to another.
 /// Creates a holes for large sequences of zeros in `non_sparse_files`, used for `--sparse=always`
 #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -174,31 +231,7 @@ fn sparse_copy<P>(source: P, dest: P) -> std::io::Result<()>
 where
     P: AsRef<Path>,
 {
-    let mut src_file = File::open(source)?;
-    let dst_file = File::create(dest)?;
-    let dst_fd = dst_file.as_raw_fd();
-
-    let size: usize = src_file.metadata()?.size().try_into().unwrap();
-    if unsafe { libc::ftruncate(dst_fd, size.try_into().unwrap()) } < 0 {
-        return Err(std::io::Error::last_os_error());
-    }
-
-    let blksize = dst_file.metadata()?.blksize();
-    let mut buf: Vec<u8> = vec![0; blksize.try_into().unwrap()];
-    let mut current_offset: usize = 0;
-
-    // TODO Perhaps we can employ the "fiemap ioctl" API to get the
-    // file extent mappings:
-    // https://www.kernel.org/doc/html/latest/filesystems/fiemap.html
-    while current_offset < size {
-        let this_read = src_file.read(&mut buf)?;
-        let buf = &buf[..this_read];
-        if buf.iter().any(|&x| x != 0) {
-            dst_file.write_all_at(buf, current_offset.try_into().unwrap())?;
-        }
-        current_offset += this_read;
-    }
-    Ok(())
+    copy_contents(source, dest, Sparse::Always)
 }
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -264,12 +297,38 @@ pub(crate) fn copy_on_write(
     sparse_mode: SparseMode,
     context: &str,
     source_is_stream: bool,
+    no_follow: bool,
 ) -> CopyResult<CopyDebug> {
     let mut copy_debug = CopyDebug {
         offload: OffloadReflinkDebug::Unknown,
         reflink: OffloadReflinkDebug::Unsupported,
         sparse_detection: SparseDebug::No,
     };
+
+    // Small files gain nothing from sparse copies, so unless that or a
+    // clone was explicitly requested they are copied directly, still cloned
+    // where possible with `--reflink=auto`.
+    if !source_is_stream && reflink_mode != ReflinkMode::Always && sparse_mode != SparseMode::Always
+    {
+        let try_clone = reflink_mode == ReflinkMode::Auto;
+        let copied = copy_small_file(source, dest, no_follow, try_clone)
+            .map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
+        match copied {
+            SmallFileCopy::Skipped => {}
+            SmallFileCopy::Cloned => {
+                copy_debug.reflink = OffloadReflinkDebug::Yes;
+                copy_debug.sparse_detection = SparseDebug::Unknown;
+                return Ok(copy_debug);
+            }
+            SmallFileCopy::Written => {
+                copy_debug.offload = OffloadReflinkDebug::No;
+                if !try_clone {
+                    copy_debug.reflink = OffloadReflinkDebug::No;
+                }
+                return Ok(copy_debug);
+            }
+        }
+    }
     let result = match (reflink_mode, sparse_mode) {
         (ReflinkMode::Never, SparseMode::Always) => {
             copy_debug.sparse_detection = SparseDebug::Zeros;
@@ -287,7 +346,7 @@ pub(crate) fn copy_on_write(
                 }
 
                 match copy_method {
-                    CopyMethod::FSCopy => std::fs::copy(source, dest).map(|_| ()),
+                    CopyMethod::FSCopy => fs_copy(source, dest),
                     _ => sparse_copy(source, dest),
                 }
             }
@@ -303,7 +362,7 @@ pub(crate) fn copy_on_write(
                 if let Ok(debug) = result {
                     copy_debug = debug;
                 }
-                std::fs::copy(source, dest).map(|_| ())
+                fs_copy(source, dest)
             }
         }
         (ReflinkMode::Never, SparseMode::Auto) => {
@@ -322,7 +381,7 @@ pub(crate) fn copy_on_write(
 
                 match copy_method {
                     CopyMethod::SparseCopyWithoutHole => sparse_copy_without_hole(source, dest),
-                    _ => std::fs::copy(source, dest).map(|_| ()),
+                    _ => fs_copy(source, dest),
                 }
             }
         }
@@ -386,7 +445,14 @@ pub(crate) fn copy_on_write(
             copy_debug.sparse_detection = SparseDebug::No;
             copy_debug.reflink = OffloadReflinkDebug::Yes;
 
-            clone(source, dest, CloneFallback::Error)
+            let src_file =
+                File::open(source).map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
+            let dst_file =
+                File::create(dest).map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
+            copy::reflink(&src_file, &dst_file).map_err(|e| {
+                translate!("cp-error-failed-to-clone", "source" => source.quote(), "dest" => dest.quote(), "error" => uucore::error::strip_errno(&e))
+            })?;
+            return Ok(copy_debug);
         }
         (ReflinkMode::Always, _) => {
             return Err(translate!("cp-error-reflink-always-sparse-auto").into());
@@ -458,7 +524,7 @@ fn handle_reflink_never_sparse_never(source: &Path) -> Result<CopyDebug, std::io
 }
 
 /// Handles debug results when flags are "--reflink=auto" and "--sparse=never", files will be copied
-/// through cloning them with fallback switching to [`std::fs::copy`]
+/// through cloning them with fallback switching to [`fs_copy`]
 fn handle_reflink_auto_sparse_never(source: &Path) -> Result<CopyDebug, std::io::Error> {
     let mut copy_debug = CopyDebug {
         offload: OffloadReflinkDebug::Unknown,
@@ -5,10 +5,12 @@
 // spell-checker:ignore reflink
 use std::ffi::CString;
 use std::fs::{self, File, OpenOptions};
+use std::io;
 use std::os::unix::ffi::OsStrExt;
 use std::os::unix::fs::OpenOptionsExt;
 use std::path::Path;
 
+use filetime::FileTime;
 use uucore::buf_copy;
 use uucore::display::Quotable;
 use uucore::translate;
@@ -16,11 +18,19 @@ use uucore::translate;
 use uucore::mode::get_umask;
 
 use crate::{
-    CopyDebug, CopyResult, CpError, OffloadReflinkDebug, ReflinkMode, SparseDebug, SparseMode,
-    is_stream,
+    Attributes, CopyDebug, CopyResult, CpError, OffloadReflinkDebug, Preserve, ReflinkMode,
+    SparseDebug, SparseMode, is_stream,
 };
 
+/// `clonefile(2)` flags, from `<sys/clonefile.h>`.
+const CLONE_NOFOLLOW: u32 = 0x0001;
+const CLONE_NOOWNERCOPY: u32 = 0x0002;
+
 /// Copies `source` to `dest` using copy-on-write if possible.
+///
+/// `clonefile(2)` also copies the owner and timestamps of `source`, so the
+/// clone only keeps them if `attributes` asks for them.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn copy_on_write(
     source: &Path,
     dest: &Path,
@@ -28,6 +38,8 @@ pub(crate) fn copy_on_write(
     sparse_mode: SparseMode,
     context: &str,
     source_is_stream: bool,
+    no_follow: bool,
+    attributes: &Attributes,
 ) -> CopyResult<CopyDebug> {
     if sparse_mode != SparseMode::Auto {
         return Err(translate!("cp-error-sparse-not-supported")
@@ -48,10 +60,23 @@ pub(crate) fn copy_on_write(
 
     // clonefile(2) was introduced in macOS 10.12 so we cannot statically link against it
     // for backward compatibility.
-    let clonefile = CString::new("clonefile").unwrap();
-    let raw_pfn = unsafe { libc::dlsym(libc::RTLD_NEXT, clonefile.as_ptr()) };
+    let raw_pfn = if reflink_mode == ReflinkMode::Never {
+        std::ptr::null_mut()
+    } else {
+        let clonefile = CString::new("clonefile").unwrap();
+        unsafe { libc::dlsym(libc::RTLD_NEXT, clonefile.as_ptr()) }
+    };
+
+    let mut flags = 0;
+    if no_follow {
+        flags |= CLONE_NOFOLLOW;
+    }
+    if !matches!(attributes.ownership, Preserve::Yes { .. }) {
+        flags |= CLONE_NOOWNERCOPY;
+    }
 
     let mut error = 0;
+    let mut clone_error = None;
     if !raw_pfn.is_null() {
         // Call clonefile(2).
         // Safety: Casting a C function pointer to a rust function value is one of the few
@@ -62,8 +87,8 @@ pub(crate) fn copy_on_write(
                 dst: *const libc::c_char,
                 flags: u32,
             ) -> libc::c_int = std::mem::transmute(raw_pfn);
-            error = pfn(src.as_ptr(), dst.as_ptr(), 0);
-            if std::io::Error::last_os_error().kind() == std::io::ErrorKind::AlreadyExists
+            error = pfn(src.as_ptr(), dst.as_ptr(), flags);
+            if io::Error::last_os_error().kind() == io::ErrorKind::AlreadyExists
                 // Only remove the `dest` if the `source` and `dest` are not the same
                 && source != dest
             {
@@ -76,9 +101,12 @@ pub(crate) fn copy_on_write(
                     // linux first opens the source file and destination file then uses the file
                     // descriptors to do the clone.
                     let _ = fs::remove_file(dest);
-                    error = pfn(src.as_ptr(), dst.as_ptr(), 0);
+                    error = pfn(src.as_ptr(), dst.as_ptr(), flags);
                 }
             }
+            if error != 0 {
+                clone_error = Some(io::Error::last_os_error());
+            }
         }
     }
 
@@ -86,7 +114,8 @@ pub(crate) fn copy_on_write(
         // clonefile(2) is either not supported or it errored out (possibly because the FS does not
         // support COW).
         if reflink_mode == ReflinkMode::Always {
-            return Err(translate!("cp-error-failed-to-clone", "source" => source.quote(), "dest" => dest.quote(), "error" => error)
+            let error = clone_error.unwrap_or_else(|| io::ErrorKind::Unsupported.into());
+            return Err(translate!("cp-error-failed-to-clone", "source" => source.quote(), "dest" => dest.quote(), "error" => uucore::error::strip_errno(&error))
                 .into());
         }
         copy_debug.reflink = OffloadReflinkDebug::Yes;
@@ -111,6 +140,10 @@ pub(crate) fn copy_on_write(
         } else {
             fs::copy(source, dest).map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
         }
+    } else if !matches!(attributes.timestamps, Preserve::Yes { .. }) {
+        let now = FileTime::now();
+        filetime::set_file_times(dest, now, now)
+            .map_err(|e| CpError::IoErrContext(e, context.to_owned()))?;
     }
 
     Ok(copy_debug)
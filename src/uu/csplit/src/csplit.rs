@@ -7,11 +7,13 @@
 
 use std::cmp::Ordering;
 use std::ffi::OsString;
+#[cfg(not(target_family = "wasm"))]
+use std::fs::File;
+use std::fs::remove_file;
 use std::io::{self, BufReader, ErrorKind};
-use std::{
-    fs::{File, remove_file},
-    io::{BufRead, BufWriter, Write},
-};
+use std::io::{BufRead, BufWriter, Write};
+#[cfg(not(target_family = "wasm"))]
+use std::io::{stdin, stdout};
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use regex::Regex;
@@ -27,6 +29,15 @@ use crate::csplit_error::CsplitError;
 use crate::split_name::SplitName;
 
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{WriteMode, open_file, open_file_write, stdin, stdout};
+
+/// The file a split is written to. On WASM, splits are created in the host
+/// VFS through the write hooks.
+#[cfg(not(target_family = "wasm"))]
+type SplitFile = File;
+#[cfg(target_family = "wasm")]
+type SplitFile = Box<dyn Write>;
 
 mod options {
     pub const SUFFIX_FORMAT: &str = "suffix-format";
@@ -231,7 +242,7 @@ struct SplitWriter<'a> {
     /// a split counter
     counter: usize,
     /// the writer to the current split
-    current_writer: Option<BufWriter<File>>,
+    current_writer: Option<BufWriter<SplitFile>>,
     /// the size in bytes of the current split
     size: usize,
     /// flag to indicate that no content should be written to a split
@@ -270,7 +281,10 @@ impl SplitWriter<'_> {
     /// The creation of the split file may fail with some [`io::Error`].
     fn new_writer(&mut self) -> io::Result<()> {
         let file_name = self.options.split_name.get(self.counter);
+        #[cfg(not(target_family = "wasm"))]
         let file = File::create(file_name)?;
+        #[cfg(target_family = "wasm")]
+        let file = open_file_write(file_name, WriteMode::Truncate)?;
         self.current_writer = Some(BufWriter::new(file));
         self.counter += 1;
         self.size = 0;
@@ -322,7 +336,7 @@ impl SplitWriter<'_> {
             if self.options.elide_empty_files && self.size == 0 {
                 self.counter -= 1;
             } else if !self.options.quiet {
-                println!("{}", self.size);
+                writeln!(stdout(), "{}", self.size)?;
             }
         }
         Ok(())
@@ -634,11 +648,15 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         .collect();
     let options = CsplitOptions::new(&matches)?;
     if file_name == "-" {
-        let stdin = io::stdin();
+        let stdin = stdin();
         Ok(csplit(&options, &patterns, stdin.lock())?)
     } else {
-        let file = File::open(file_name)
-            .map_err_context(|| format!("cannot open {} for reading", file_name.quote()))?;
+        #[cfg(not(target_family = "wasm"))]
+        let file = File::open(file_name);
+        #[cfg(target_family = "wasm")]
+        let file = open_file(file_name);
+        let file =
+            file.map_err_context(|| format!("cannot open {} for reading", file_name.quote()))?;
         Ok(csplit(&options, &patterns, BufReader::new(file))?)
     }
 }
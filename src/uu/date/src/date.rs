@@ -23,6 +23,7 @@ use uucore::error::FromIo;
 use uucore::error::{UResult, USimpleError};
 #[cfg(feature = "i18n-datetime")]
 use uucore::i18n::datetime::{localize_format_string, should_use_icu_locale};
+use uucore::time::{local_time_zone, local_time_zone_at, now};
 use uucore::translate;
 use uucore::{format_usage, show};
 #[cfg(windows)]
@@ -359,9 +360,9 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
 
     // Get the current time, either in the local time zone or UTC.
     let now = if utc {
-        Timestamp::now().to_zoned(TimeZone::UTC)
+        now().to_zoned(TimeZone::UTC)
     } else {
-        Zoned::now()
+        now().to_zoned(local_time_zone())
     };
 
     let set_to = match matches
@@ -522,13 +523,13 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
                     translate!("date-error-cannot-set-date", "path" => path.quote(), "error" => e),
                 )
             })?;
-            let date = ts.to_zoned(TimeZone::try_system().unwrap_or(TimeZone::UTC));
+            let date = ts.to_zoned(local_time_zone_at(ts));
             let iter = std::iter::once(Ok(date));
             Box::new(iter)
         }
         DateSource::Resolution => {
             let resolution = get_clock_resolution();
-            let date = resolution.to_zoned(TimeZone::system());
+            let date = resolution.to_zoned(local_time_zone());
             let iter = std::iter::once(Ok(date));
             Box::new(iter)
         }
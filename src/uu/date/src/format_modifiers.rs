@@ -237,11 +237,9 @@ fn apply_modifiers(value: &str, flags: &str, width: usize, specifier: &str) -> S
                 uppercase = true;
                 swap_case = false; // ^ overrides #
             }
-            '#' => {
-                if !uppercase {
-                    // Only apply # if ^ hasn't been set
-                    swap_case = true;
-                }
+            '#' if !uppercase => {
+                // Only apply # if ^ hasn't been set
+                swap_case = true;
             }
             '+' => {
                 force_sign = true;
@@ -122,6 +122,7 @@ dd-standard-output = 'standard output'
 dd-error-failed-to-open = failed to open { $path }
 dd-error-write-error = write error
 dd-error-failed-to-seek = failed to seek in output file
+dd-error-failed-to-truncate = failed to truncate to { $size } bytes in output file { $path }
 dd-error-io-error = IO error
 dd-error-cannot-skip-offset = '{ $file }': cannot skip to specified offset
 dd-error-cannot-skip-invalid = '{ $file }': cannot skip: Invalid argument
@@ -134,7 +135,7 @@ dd-error-unrecognized-operand = Unrecognized operand '{ $operand }'
 dd-error-multiple-format-table = Only one of conv=ascii conv=ebcdic or conv=ibm may be specified
 dd-error-multiple-case = Only one of conv=lcase or conv=ucase may be specified
 dd-error-multiple-block = Only one of conv=block or conv=unblock may be specified
-dd-error-multiple-excl = Only one ov conv=excl or conv=nocreat may be specified
+dd-error-multiple-excl = cannot combine excl and nocreat
 dd-error-invalid-flag = invalid input flag: ‘{ $flag }’
   Try '{ $cmd } --help' for more information.
 dd-error-conv-flag-no-match = Unrecognized conv=CONV -> { $flag }
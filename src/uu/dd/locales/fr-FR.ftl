@@ -122,6 +122,7 @@ dd-standard-output = 'sortie standard'
 dd-error-failed-to-open = échec de l'ouverture de { $path }
 dd-error-write-error = erreur d'écriture
 dd-error-failed-to-seek = échec de la recherche dans le fichier de sortie
+dd-error-failed-to-truncate = échec de la troncature à { $size } octets dans le fichier de sortie { $path }
 dd-error-io-error = erreur E/S
 dd-error-cannot-skip-offset = '{ $file }' : impossible d'ignorer jusqu'au décalage spécifié
 dd-error-cannot-skip-invalid = '{ $file }' : impossible d'ignorer : Argument invalide
@@ -134,7 +135,7 @@ dd-error-unrecognized-operand = Opérande non reconnue '{ $operand }'
 dd-error-multiple-format-table = Seul un seul de conv=ascii conv=ebcdic ou conv=ibm peut être spécifié
 dd-error-multiple-case = Seul un seul de conv=lcase ou conv=ucase peut être spécifié
 dd-error-multiple-block = Seul un seul de conv=block ou conv=unblock peut être spécifié
-dd-error-multiple-excl = Seul un seul de conv=excl ou conv=nocreat peut être spécifié
+dd-error-multiple-excl = impossible de combiner excl et nocreat
 dd-error-invalid-flag = indicateur d'entrée invalide : '{ $flag }'
   Essayez '{ $cmd } --help' pour plus d'informations.
 dd-error-conv-flag-no-match = conv=CONV non reconnu -> { $flag }
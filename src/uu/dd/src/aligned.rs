@@ -0,0 +1,71 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+//
+// spell-checker:ignore PAGESIZE sysconf
+//! Page-aligned buffers for `iflag=direct` and `oflag=direct`.
+//!
+//! The kernel fails `O_DIRECT` reads and writes with `EINVAL` unless the
+//! memory they use is suitably aligned, which the buffers of the main loop
+//! are not, so the data goes through an [`AlignedBuf`] instead.
+
+/// A reusable buffer whose slices start on a page boundary, which satisfies
+/// the alignment every file system asks of `O_DIRECT`.
+pub(crate) struct AlignedBuf {
+    storage: Vec<u8>,
+    align: usize,
+}
+
+impl AlignedBuf {
+    pub(crate) fn new() -> Self {
+        Self {
+            storage: Vec::new(),
+            align: page_size(),
+        }
+    }
+
+    /// Whether `buf` can be used for `O_DIRECT` as it is.
+    pub(crate) fn is_aligned(&self, buf: &[u8]) -> bool {
+        buf.as_ptr().align_offset(self.align) == 0
+    }
+
+    /// An aligned slice of `len` bytes, growing the buffer if needed.
+    pub(crate) fn get_mut(&mut self, len: usize) -> &mut [u8] {
+        if self.storage.len() < len + self.align {
+            self.storage.resize(len + self.align, 0);
+        }
+        let offset = self.storage.as_ptr().align_offset(self.align);
+        &mut self.storage[offset..offset + len]
+    }
+}
+
+fn page_size() -> usize {
+    #[cfg(unix)]
+    {
+        // SAFETY: `sysconf` has no preconditions.
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if let Ok(size) = usize::try_from(size) {
+            if size.is_power_of_two() {
+                return size;
+            }
+        }
+    }
+    4096
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AlignedBuf;
+
+    #[test]
+    fn test_get_mut_is_aligned() {
+        let mut buf = AlignedBuf::new();
+        for len in [1, 512, 4096, 10_000] {
+            let slice = buf.get_mut(len);
+            assert_eq!(slice.len(), len);
+            let ptr = slice.as_ptr();
+            assert_eq!(ptr.align_offset(buf.align), 0);
+        }
+    }
+}
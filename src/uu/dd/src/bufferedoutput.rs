@@ -61,6 +61,11 @@ impl<'a> BufferedOutput<'a> {
         self.inner.dst.truncate()
     }
 
+    /// Extend the underlying file to the current stream position, if needed.
+    pub(crate) fn extend(&mut self) -> std::io::Result<()> {
+        self.inner.extend()
+    }
+
     /// Write the given bytes one block at a time.
     ///
     /// Only complete blocks will be written. Partial blocks will be
@@ -114,10 +119,7 @@ mod tests {
             obs: 3,
             ..Default::default()
         };
-        let inner = Output {
-            dst: Dest::Sink,
-            settings: &settings,
-        };
+        let inner = Output::with_dest(Dest::Sink, &settings);
         let mut output = BufferedOutput::new(inner);
         let wstat = output.write_blocks(&[]).unwrap();
         assert_eq!(wstat.writes_complete, 0);
@@ -132,10 +134,7 @@ mod tests {
             obs: 3,
             ..Default::default()
         };
-        let inner = Output {
-            dst: Dest::Sink,
-            settings: &settings,
-        };
+        let inner = Output::with_dest(Dest::Sink, &settings);
         let mut output = BufferedOutput::new(inner);
         let wstat = output.write_blocks(b"ab").unwrap();
         assert_eq!(wstat.writes_complete, 0);
@@ -150,10 +149,7 @@ mod tests {
             obs: 3,
             ..Default::default()
         };
-        let inner = Output {
-            dst: Dest::Sink,
-            settings: &settings,
-        };
+        let inner = Output::with_dest(Dest::Sink, &settings);
         let mut output = BufferedOutput::new(inner);
         let wstat = output.write_blocks(b"abcd").unwrap();
         assert_eq!(wstat.writes_complete, 1);
@@ -168,10 +164,7 @@ mod tests {
             obs: 3,
             ..Default::default()
         };
-        let inner = Output {
-            dst: Dest::Sink,
-            settings: &settings,
-        };
+        let inner = Output::with_dest(Dest::Sink, &settings);
         let mut output = BufferedOutput {
             inner,
             buf: b"ab".to_vec(),
@@ -189,10 +182,7 @@ mod tests {
             obs: 10,
             ..Default::default()
         };
-        let inner = Output {
-            dst: Dest::Sink,
-            settings: &settings,
-        };
+        let inner = Output::with_dest(Dest::Sink, &settings);
         let mut output = BufferedOutput {
             inner,
             buf: b"abc".to_vec(),
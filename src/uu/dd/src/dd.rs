@@ -5,14 +5,15 @@
 
 // spell-checker:ignore fname, ftype, tname, fpath, specfile, testfile, unspec, ifile, ofile, outfile, fullblock, urand, fileio, atoe, atoibm, behaviour, bmax, bremain, cflags, creat, ctable, ctty, datastructures, doesnt, etoa, fileout, fname, gnudd, iconvflags, iseek, nocache, noctty, noerror, nofollow, nolinks, nonblock, oconvflags, oseek, outfile, parseargs, rlen, rmax, rremain, rsofar, rstat, sigusr, wlen, wstat seekable oconv canonicalized fadvise Fadvise FADV DONTNEED ESPIPE bufferedoutput, SETFL
 
+mod aligned;
 mod blocks;
 mod bufferedoutput;
 mod conversion_tables;
 mod datastructures;
-mod numbers;
 mod parseargs;
 mod progress;
 
+use crate::aligned::AlignedBuf;
 use crate::bufferedoutput::BufferedOutput;
 use blocks::conv_block_unblock_helper;
 use datastructures::{ConversionMode, IConvFlags, IFlags, OConvFlags, OFlags, options};
@@ -23,8 +24,6 @@ use nix::fcntl::OFlag;
 use parseargs::Parser;
 use progress::ProgUpdateType;
 use progress::{ProgUpdate, ReadStat, StatusLevel, WriteStat, gen_prog_updater};
-#[cfg(target_os = "linux")]
-use progress::{check_and_reset_sigusr1, install_sigusr1_handler};
 use uucore::io::OwnedFileDescriptorOrHandle;
 use uucore::translate;
 
@@ -353,9 +352,20 @@ struct Input<'a> {
 
     /// Configuration settings for how to read the data.
     settings: &'a Settings,
+
+    /// What `iflag=direct` reads go through.
+    direct_buf: Option<AlignedBuf>,
 }
 
 impl<'a> Input<'a> {
+    fn with_source(src: Source, settings: &'a Settings) -> Self {
+        Self {
+            src,
+            settings,
+            direct_buf: settings.iflags.direct.then(AlignedBuf::new),
+        }
+    }
+
     /// Instantiate this struct with stdin as a source.
     fn new_stdin(settings: &'a Settings) -> UResult<Self> {
         #[cfg(not(unix))]
@@ -391,7 +401,7 @@ impl<'a> Input<'a> {
         if settings.skip > 0 {
             src.skip(settings.skip, settings.ibs)?;
         }
-        Ok(Self { src, settings })
+        Ok(Self::with_source(src, settings))
     }
 
     /// Instantiate this struct with the named file as a source.
@@ -414,7 +424,7 @@ impl<'a> Input<'a> {
         if settings.skip > 0 {
             src.skip(settings.skip, settings.ibs)?;
         }
-        Ok(Self { src, settings })
+        Ok(Self::with_source(src, settings))
     }
 
     /// Instantiate this struct with the named pipe as a source.
@@ -428,7 +438,7 @@ impl<'a> Input<'a> {
         if settings.skip > 0 {
             src.skip(settings.skip, settings.ibs)?;
         }
-        Ok(Self { src, settings })
+        Ok(Self::with_source(src, settings))
     }
 }
 
@@ -469,7 +479,7 @@ impl Read for Input<'_> {
         let mut base_idx = 0;
         let target_len = buf.len();
         loop {
-            match self.src.read(&mut buf[base_idx..]) {
+            match self.read_src(&mut buf[base_idx..]) {
                 Ok(0) => return Ok(base_idx),
                 Ok(rlen) if self.settings.iflags.fullblock => {
                     base_idx += rlen;
@@ -488,6 +498,19 @@ impl Read for Input<'_> {
 }
 
 impl Input<'_> {
+    /// Read from the source, through an aligned buffer with `iflag=direct`.
+    fn read_src(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.direct_buf {
+            Some(aligned) if !aligned.is_aligned(buf) => {
+                let bounce = aligned.get_mut(buf.len());
+                let len = self.src.read(bounce)?;
+                buf[..len].copy_from_slice(&bounce[..len]);
+                Ok(len)
+            }
+            _ => self.src.read(buf),
+        }
+    }
+
     /// Discard the system file cache for the given portion of the input.
     ///
     /// `offset` and `len` specify a contiguous portion of the input.
@@ -567,7 +590,7 @@ impl Input<'_> {
                     bytes_total += rlen;
                     reads_partial += 1;
                     let padding = vec![pad; target_len - rlen];
-                    buf.splice(base_idx + rlen..next_blk, padding.into_iter());
+                    buf.splice(base_idx + rlen..next_blk, padding);
                 }
                 rlen => {
                     bytes_total += rlen;
@@ -631,6 +654,14 @@ impl Dest {
         }
     }
 
+    /// Whether the output is a regular file, which has data to flush.
+    fn is_regular_file(&self) -> bool {
+        match self {
+            Self::File(f, _) => f.metadata().is_ok_and(|m| m.is_file()),
+            _ => false,
+        }
+    }
+
     fn fdatasync(&mut self) -> io::Result<()> {
         match self {
             Self::Stdout(stdout) => stdout.flush(),
@@ -666,7 +697,14 @@ impl Dest {
                         return Ok(len);
                     }
                 }
-                f.seek(SeekFrom::Current(n.try_into().unwrap()))
+                match f.seek(SeekFrom::Current(n.try_into().unwrap())) {
+                    // Outputs that cannot seek, like a terminal, are
+                    // padded with zeros instead.
+                    Err(e) if e.kind() == io::ErrorKind::NotSeekable => {
+                        io::copy(&mut io::repeat(0).take(n), f)
+                    }
+                    result => result,
+                }
             }
             #[cfg(unix)]
             Self::Fifo(f) => {
@@ -679,10 +717,12 @@ impl Dest {
     }
 
     /// Truncate the underlying file to the current stream position, if possible.
+    ///
+    /// Only regular files are truncated, devices keep their contents.
     fn truncate(&mut self) -> io::Result<()> {
         #[allow(clippy::match_wildcard_for_single_variants)]
         match self {
-            Self::File(f, _) => {
+            Self::File(f, _) if is_regular_file(f) => {
                 let pos = f.stream_position()?;
                 f.set_len(pos)
             }
@@ -690,6 +730,21 @@ impl Dest {
         }
     }
 
+    /// Extend the underlying file to the current stream position, if it ends
+    /// before it because the last blocks were sparse.
+    fn extend(&mut self) -> io::Result<()> {
+        match self {
+            Self::File(f, Density::Sparse) if is_regular_file(f) => {
+                let pos = f.stream_position()?;
+                if f.metadata()?.len() < pos {
+                    f.set_len(pos)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Discard the system file cache for the given portion of the destination.
     ///
     /// `offset` and `len` specify a contiguous portion of the
@@ -819,16 +874,27 @@ struct Output<'a> {
 
     /// Configuration settings for how to read and write the data.
     settings: &'a Settings,
+
+    /// What `oflag=direct` writes go through.
+    direct_buf: Option<AlignedBuf>,
 }
 
 impl<'a> Output<'a> {
+    fn with_dest(dst: Dest, settings: &'a Settings) -> Self {
+        Self {
+            dst,
+            settings,
+            direct_buf: settings.oflags.direct.then(AlignedBuf::new),
+        }
+    }
+
     /// Instantiate this struct with stdout as a destination.
     fn new_stdout(settings: &'a Settings) -> UResult<Self> {
         let fx = OwnedFileDescriptorOrHandle::from(io::stdout())?;
         let mut dst = Dest::Stdout(fx.into_file());
         dst.seek(settings.seek, settings.obs)
             .map_err_context(|| translate!("dd-error-write-error"))?;
-        Ok(Self { dst, settings })
+        Ok(Self::with_dest(dst, settings))
     }
 
     /// Instantiate this struct with the named file as a destination.
@@ -854,14 +920,14 @@ impl<'a> Output<'a> {
 
         // Seek to the index in the output file, truncating if requested.
         //
-        // Calling `set_len()` may result in an error (for example,
-        // when calling it on `/dev/null`), but we don't want to
-        // terminate the process when that happens.  Instead, we
-        // suppress the error by calling `Result::ok()`. This matches
-        // the behavior of GNU `dd` when given the command-line
-        // argument `of=/dev/null`.
-        if !settings.oconv.notrunc {
-            dst.set_len(settings.seek).ok();
+        // Like GNU `dd`, only regular files are truncated. Character
+        // devices such as `/dev/null` or a terminal and block devices
+        // are left alone, so `of=/dev/null` or `of=/dev/sda` don't
+        // fail.
+        if !settings.oconv.notrunc && is_regular_file(&dst) {
+            dst.set_len(settings.seek).map_err_context(|| {
+                translate!("dd-error-failed-to-truncate", "size" => settings.seek, "path" => filename.quote())
+            })?;
         }
 
         Self::prepare_file(dst, settings)
@@ -876,7 +942,7 @@ impl<'a> Output<'a> {
         let mut dst = Dest::File(dst, density);
         dst.seek(settings.seek, settings.obs)
             .map_err_context(|| translate!("dd-error-failed-to-seek"))?;
-        Ok(Self { dst, settings })
+        Ok(Self::with_dest(dst, settings))
     }
 
     /// Instantiate this struct with file descriptor as a destination.
@@ -911,7 +977,7 @@ impl<'a> Output<'a> {
         // indefinitely.
         if let Some(Num::Blocks(0) | Num::Bytes(0)) = settings.count {
             let dst = Dest::Sink;
-            return Ok(Self { dst, settings });
+            return Ok(Self::with_dest(dst, settings));
         }
         // At this point, we know there is at least one block to write
         // to the output, so we open the file for writing.
@@ -923,7 +989,7 @@ impl<'a> Output<'a> {
         #[cfg(any(target_os = "linux", target_os = "android"))]
         opts.custom_flags(make_linux_oflags(&settings.oflags).unwrap_or(0));
         let dst = Dest::Fifo(opts.open(filename)?);
-        Ok(Self { dst, settings })
+        Ok(Self::with_dest(dst, settings))
     }
 
     /// Discard the system file cache for the given portion of the output.
@@ -955,6 +1021,18 @@ impl<'a> Output<'a> {
         }
     }
 
+    /// Write to the destination, through an aligned buffer with `oflag=direct`.
+    fn write_dst(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.direct_buf {
+            Some(aligned) if !aligned.is_aligned(buf) => {
+                let bounce = aligned.get_mut(buf.len());
+                bounce.copy_from_slice(buf);
+                self.dst.write(bounce)
+            }
+            _ => self.dst.write(buf),
+        }
+    }
+
     /// writes a block of data. optionally retries when first try didn't complete
     ///
     /// this is needed by gnu-test: tests/dd/stats.s
@@ -965,7 +1043,7 @@ impl<'a> Output<'a> {
         let full_len = chunk.len();
         let mut base_idx = 0;
         loop {
-            match self.dst.write(&chunk[base_idx..]) {
+            match self.write_dst(&chunk[base_idx..]) {
                 Ok(wlen) => {
                     base_idx += wlen;
                     // take iflags.fullblock as oflags shall not have this option
@@ -1009,9 +1087,19 @@ impl<'a> Output<'a> {
     }
 
     /// Flush the output to disk, if configured to do so.
+    ///
+    /// Setting [`uucore::fs::SYNC_ENV`] has the same effect as `conv=fsync`
+    /// for regular files. Other outputs are left alone, and a file system
+    /// that can't flush the file isn't an error then, since the variable is
+    /// meant to be set for whole builds rather than for this command.
     fn sync(&mut self) -> io::Result<()> {
         if self.settings.oconv.fsync {
             self.dst.fsync()
+        } else if uucore::fs::sync_requested(false) && self.dst.is_regular_file() {
+            match self.dst.fsync() {
+                Err(e) if e.kind() == io::ErrorKind::InvalidInput => Ok(()),
+                result => result,
+            }
         } else if self.settings.oconv.fdatasync {
             self.dst.fdatasync()
         } else {
@@ -1024,6 +1112,11 @@ impl<'a> Output<'a> {
     fn truncate(&mut self) -> io::Result<()> {
         self.dst.truncate()
     }
+
+    /// Extend the underlying file to the current stream position, if needed.
+    fn extend(&mut self) -> io::Result<()> {
+        self.dst.extend()
+    }
 }
 
 /// The block writer either with or without partial block buffering.
@@ -1075,6 +1168,13 @@ impl BlockWriter<'_> {
         };
     }
 
+    fn extend(&mut self) -> io::Result<()> {
+        match self {
+            Self::Unbuffered(o) => o.extend(),
+            Self::Buffered(o) => o.extend(),
+        }
+    }
+
     fn write_blocks(&mut self, buf: &[u8]) -> io::Result<WriteStat> {
         match self {
             Self::Unbuffered(o) => o.write_blocks(buf),
@@ -1134,7 +1234,7 @@ fn dd_copy(mut i: Input, o: Output) -> io::Result<()> {
     //
     // The `dd` program reports its progress after every block is written,
     // at most every 1 second, and only if `status=progress` is given on
-    // the command-line or a SIGUSR1 signal is received. We
+    // the command-line or a SIGUSR1 (SIGINFO on BSD) signal is received. We
     // perform this reporting in a new thread so as not to take
     // any CPU time away from the actual reading and writing of
     // data. We send a `ProgUpdate` from the transmitter `prog_tx`
@@ -1176,10 +1276,16 @@ fn dd_copy(mut i: Input, o: Output) -> io::Result<()> {
     // This avoids the need to query the OS monotonic clock for every block.
     let alarm = Alarm::with_interval(Duration::from_secs(1));
 
-    #[cfg(target_os = "linux")]
-    if let Err(e) = install_sigusr1_handler() {
-        if i.settings.status != Some(StatusLevel::None) {
-            eprintln!("{}\n\t{e}", translate!("dd-warning-signal-handler"));
+    // Like GNU, leave `SIGUSR1` to its default action, which terminates the
+    // process, under POSIXLY_CORRECT when it is what the stats are asked with.
+    #[cfg(unix)]
+    if uucore::signals::STATUS_SIGNAL != nix::sys::signal::Signal::SIGUSR1
+        || env::var_os("POSIXLY_CORRECT").is_none()
+    {
+        if let Err(e) = uucore::signals::watch_status_signal() {
+            if i.settings.status != Some(StatusLevel::None) {
+                eprintln!("{}\n\t{e}", translate!("dd-warning-signal-handler"));
+            }
         }
     }
 
@@ -1207,7 +1313,15 @@ fn dd_copy(mut i: Input, o: Output) -> io::Result<()> {
     // blocks to this output. Read/write statistics are updated on
     // each iteration and cumulative statistics are reported to
     // the progress reporting thread.
+    let mut interrupted = false;
     while below_count_limit(i.settings.count, &rstat) {
+        // Like GNU dd on SIGINT, stop copying but still report statistics
+        // when the host cancels us.
+        if uucore::cancel::is_cancelled() {
+            interrupted = true;
+            break;
+        }
+
         // Read a block from the input then write the block to the output.
         //
         // As an optimization, make an educated guess about the
@@ -1258,8 +1372,8 @@ fn dd_copy(mut i: Input, o: Output) -> io::Result<()> {
         // error.
         rstat += rstat_update;
         wstat += wstat_update;
-        #[cfg(target_os = "linux")]
-        if check_and_reset_sigusr1() {
+        #[cfg(unix)]
+        if uucore::signals::status_requested() {
             alarm.manual_trigger();
         }
         match alarm.get_trigger() {
@@ -1276,7 +1390,11 @@ fn dd_copy(mut i: Input, o: Output) -> io::Result<()> {
         }
     }
 
-    finalize(o, rstat, wstat, start, &prog_tx, output_thread, truncate)
+    finalize(o, rstat, wstat, start, &prog_tx, output_thread, truncate)?;
+    if interrupted {
+        return Err(io::ErrorKind::Interrupted.into());
+    }
+    Ok(())
 }
 
 /// Flush output, print final stats, and join with the progress thread.
@@ -1296,9 +1414,13 @@ fn finalize<T>(
     // Sync the output, if configured to do so.
     output.sync()?;
 
-    // Truncate the file to the final cursor location.
+    // Truncate the file to the final cursor location, or with
+    // `conv=notrunc` extend it there if the last blocks were
+    // skipped over by `conv=sparse`.
     if truncate {
         output.truncate();
+    } else {
+        output.extend()?;
     }
 
     // Print the final read/write statistics.
@@ -1478,6 +1600,11 @@ fn is_stdout_redirected_to_seekable_file() -> bool {
     }
 }
 
+/// Decide whether the given file is a regular file.
+fn is_regular_file(file: &File) -> bool {
+    file.metadata().is_ok_and(|m| m.is_file())
+}
+
 /// Try to get the len if it is a block device
 #[cfg(unix)]
 fn try_get_len_of_block_device(file: &mut File) -> io::Result<Option<u64>> {
@@ -1531,7 +1658,28 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         None if is_stdout_redirected_to_seekable_file() => Output::new_file_from_stdout(&settings)?,
         None => Output::new_stdout(&settings)?,
     };
-    dd_copy(i, o).map_err_context(|| translate!("dd-error-io-error"))
+    let result = dd_copy(i, o);
+    if matches!(&result, Err(e) if e.kind() == io::ErrorKind::Interrupted)
+        && uucore::cancel::is_cancelled()
+    {
+        return Err(uucore::error::ExitCode::new(
+            uucore::cancel::INTERRUPTED_EXIT_CODE,
+        ));
+    }
+    result.map_err_context(|| translate!("dd-error-io-error"))?;
+
+    // With `UUTILS_SYNC`, the name of a newly created output file is flushed
+    // too. Devices and FIFOs have no new name to flush.
+    if let Some(outfile) = settings.outfile.as_ref().filter(|outfile| {
+        uucore::fs::sync_requested(false)
+            && Path::new(outfile).metadata().is_ok_and(|m| m.is_file())
+    }) {
+        match uucore::fs::sync_parent_dir(Path::new(outfile)) {
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput => {}
+            result => result.map_err_context(|| translate!("dd-error-io-error"))?,
+        }
+    }
+    Ok(())
 }
 
 pub fn uu_app() -> Command {
@@ -1547,8 +1695,10 @@ pub fn uu_app() -> Command {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Output, Parser, calc_bsize};
+    use crate::{Density, Dest, Output, Parser, calc_bsize};
 
+    use std::fs::File;
+    use std::io::Read;
     use std::path::Path;
 
     #[test]
@@ -1621,6 +1771,19 @@ mod tests {
         assert_eq!(res, m);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_seek_non_seekable_writes_zeros() {
+        let (reader, writer) = nix::unistd::pipe().unwrap();
+        let mut dst = Dest::File(File::from(writer), Density::Dense);
+        assert_eq!(dst.seek(5, 512).unwrap(), 5);
+        drop(dst);
+
+        let mut buf = Vec::new();
+        File::from(reader).read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, [0; 5]);
+    }
+
     #[test]
     fn test_nocreat_causes_failure_when_ofile_doesnt_exist() {
         let args = &["conv=nocreat", "of=not-a-real.file"];
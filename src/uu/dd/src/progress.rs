@@ -10,19 +10,16 @@
 //! [`gen_prog_updater`] function can be used to implement a progress
 //! updater that runs in its own thread.
 use std::io::Write;
-#[cfg(target_os = "linux")]
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::time::Duration;
 use uucore::{
     error::{UResult, set_exit_code},
     format::num_format::{FloatVariant, Formatter},
     locale::setup_localization,
+    progress::{SuffixType, bytes_per_second, to_magnitude_and_suffix},
     translate,
 };
 
-use crate::numbers::{SuffixType, to_magnitude_and_suffix};
-
 #[derive(PartialEq, Eq)]
 pub(crate) enum ProgUpdateType {
     Periodic,
@@ -143,8 +140,7 @@ impl ProgUpdate {
 
         // Compute the throughput (bytes per second) as a string.
         let duration = self.duration.as_secs_f64();
-        let safe_millis = std::cmp::max(1, self.duration.as_millis());
-        let rate = (1000u128 * btotal) / safe_millis;
+        let rate = bytes_per_second(btotal, self.duration);
         let transfer_rate = to_magnitude_and_suffix(rate, SuffixType::Si);
 
         // If we are rewriting the progress line, do write a carriage
@@ -421,7 +417,7 @@ pub(crate) enum StatusLevel {
 /// This function returns a closure that receives [`ProgUpdate`]
 /// instances sent through `rx`. When a [`ProgUpdate`] instance is
 /// received, the transfer statistics are re-printed to stderr.
-#[cfg(not(target_os = "linux"))]
+#[cfg(not(unix))]
 pub(crate) fn gen_prog_updater(
     rx: mpsc::Receiver<ProgUpdate>,
     print_level: Option<StatusLevel>,
@@ -445,34 +441,16 @@ pub(crate) fn gen_prog_updater(
     }
 }
 
-#[cfg(target_os = "linux")]
-static SIGUSR1_RECEIVED: AtomicBool = AtomicBool::new(false);
-
-#[cfg(target_os = "linux")]
-pub(crate) fn check_and_reset_sigusr1() -> bool {
-    SIGUSR1_RECEIVED.swap(false, Ordering::Relaxed)
-}
-
-#[cfg(target_os = "linux")]
-extern "C" fn sigusr1_handler(_: std::os::raw::c_int) {
-    SIGUSR1_RECEIVED.store(true, Ordering::Relaxed);
-}
-
-#[cfg(target_os = "linux")]
-pub(crate) fn install_sigusr1_handler() -> Result<(), nix::errno::Errno> {
-    uucore::signals::install_signal_handler(nix::sys::signal::Signal::SIGUSR1, sigusr1_handler)
-}
-
 /// Return a closure that can be used in its own thread to print progress info.
 ///
 /// This function returns a closure that receives [`ProgUpdate`]
 /// instances sent through `rx`. When a [`ProgUpdate`] instance is
 /// received, the transfer statistics are re-printed to stderr.
 ///
-/// The closure also registers a signal handler for `SIGUSR1`. When
-/// the `SIGUSR1` signal is sent to this process, the transfer
-/// statistics are printed to stderr.
-#[cfg(target_os = "linux")]
+/// It also prints the transfer statistics, as a line of their own, for
+/// the updates `dd` sends when it receives
+/// [`uucore::signals::STATUS_SIGNAL`].
+#[cfg(unix)]
 pub(crate) fn gen_prog_updater(
     rx: mpsc::Receiver<ProgUpdate>,
     print_level: Option<StatusLevel>,
@@ -711,9 +689,10 @@ mod tests {
         let mut cursor = Cursor::new(vec![]);
         let rewrite = false;
         prog_update.write_prog_line(&mut cursor, rewrite).unwrap();
+        // Like `printf %g`, as GNU prints it
         assert_eq!(
             std::str::from_utf8(cursor.get_ref()).unwrap(),
-            "0 bytes copied, 0.000000123 s, 0.0 B/s\n"
+            "0 bytes copied, 1.23e-07 s, 0.0 B/s\n"
         );
     }
 }
@@ -9,11 +9,16 @@ df-after-help = Display values are in units of the first available SIZE from --b
   Units are K, M, G, T, P, E, Z, Y (powers of 1024) or KB, MB,... (powers
   of 1000). Units can be decimal, hexadecimal, octal, binary.
 
+  FIELD_LIST is a comma-separated list of columns to be included. Valid
+  field names are: 'source', 'fstype', 'itotal', 'iused', 'iavail', 'ipcent',
+  'size', 'used', 'avail', 'pcent', 'file' and 'target'.
+
 # Help messages
 df-help-print-help = Print help information.
 df-help-all = include dummy file systems
 df-help-block-size = scale sizes by SIZE before printing them; e.g. '-BM' prints sizes in units of 1,048,576 bytes
 df-help-total = produce a grand total
+df-help-json = print one JSON object per file system instead of the table (JSON Lines)
 df-help-human-readable = print sizes in human readable format (e.g., 1K 234M 2G)
 df-help-si = likewise, but use powers of 1000 not 1024
 df-help-inodes = list inode information instead of block usage
@@ -32,6 +37,8 @@ df-error-block-size-too-large = --block-size argument '{ $size }' too large
 df-error-invalid-block-size = invalid --block-size argument { $size }
 df-error-invalid-suffix = invalid suffix in --block-size argument { $size }
 df-error-field-used-more-than-once = option --output: field { $field } used more than once
+df-error-field-unknown = option --output: field { $field } unknown
+df-error-option-conflicts-with-output = options { $option } and --output are mutually exclusive
 df-error-filesystem-type-both-selected-and-excluded = file system type { $type } both selected and excluded
 df-error-no-such-file-or-directory = { $path }: No such file or directory
 df-error-no-file-systems-processed = no file systems processed
@@ -9,11 +9,16 @@ df-after-help = Les valeurs affichées sont en unités de la première TAILLE di
   Les unités sont K, M, G, T, P, E, Z, Y (puissances de 1024) ou KB, MB,... (puissances
   de 1000). Les unités peuvent être décimales, hexadécimales, octales, binaires.
 
+  FIELD_LIST est une liste de colonnes à inclure, séparées par des virgules.
+  Les noms de champs valides sont : 'source', 'fstype', 'itotal', 'iused',
+  'iavail', 'ipcent', 'size', 'used', 'avail', 'pcent', 'file' et 'target'.
+
 # Messages d'aide
 df-help-print-help = afficher les informations d'aide.
 df-help-all = inclure les systèmes de fichiers factices
 df-help-block-size = mettre les tailles à l'échelle par TAILLE avant de les afficher ; par ex. '-BM' affiche les tailles en unités de 1 048 576 octets
 df-help-total = produire un total général
+df-help-json = afficher un objet JSON par système de fichiers au lieu du tableau (JSON Lines)
 df-help-human-readable = afficher les tailles dans un format lisible par l'homme (par ex., 1K 234M 2G)
 df-help-si = pareillement, mais utiliser les puissances de 1000 pas 1024
 df-help-inodes = lister les informations d'inode au lieu de l'utilisation des blocs
@@ -32,6 +37,8 @@ df-error-block-size-too-large = argument --block-size '{ $size }' trop grand
 df-error-invalid-block-size = argument --block-size invalide { $size }
 df-error-invalid-suffix = suffixe invalide dans l'argument --block-size { $size }
 df-error-field-used-more-than-once = option --output : champ { $field } utilisé plus d'une fois
+df-error-field-unknown = option --output : champ { $field } inconnu
+df-error-option-conflicts-with-output = les options { $option } et --output sont mutuellement exclusives
 df-error-filesystem-type-both-selected-and-excluded = type de système de fichiers { $type } à la fois sélectionné et exclu
 df-error-no-such-file-or-directory = { $path } : aucun fichier ou répertoire de ce type
 df-error-no-file-systems-processed = aucun système de fichiers traité
@@ -3,10 +3,11 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 // spell-checker:ignore itotal iused iavail ipcent pcent squashfs
-use crate::{OPT_INODES, OPT_OUTPUT, OPT_PRINT_TYPE};
+use crate::{OPT_INODES, OPT_OUTPUT, OPT_PORTABILITY, OPT_PRINT_TYPE, OUTPUT_FIELD_LIST};
 use clap::{ArgMatches, parser::ValueSource};
 use thiserror::Error;
 use uucore::display::Quotable;
+use uucore::translate;
 
 /// The columns in the output table produced by `df`.
 ///
@@ -61,8 +62,17 @@ pub(crate) enum Column {
 #[derive(Debug, Error)]
 pub(crate) enum ColumnError {
     /// If a column appears more than once in the `--output` argument.
-    #[error("{}", .0.quote())]
+    #[error("{}", translate!("df-error-field-used-more-than-once", "field" => .0.quote()))]
     MultipleColumns(String),
+
+    /// If a name in the `--output` argument is not that of a column.
+    #[error("{}", translate!("df-error-field-unknown", "field" => .0.quote()))]
+    UnknownColumn(String),
+
+    /// If `--output` is given along with `-i`, `-P` or `-T`, which choose
+    /// the columns themselves.
+    #[error("{}", translate!("df-error-option-conflicts-with-output", "option" => .0))]
+    ConflictingOption(&'static str),
 }
 
 impl Column {
@@ -75,12 +85,26 @@ impl Column {
     /// # Errors
     ///
     /// This function returns an error if a column is specified more
-    /// than once in the command-line argument.
+    /// than once in the command-line argument, if a name is not that of
+    /// a column, or if `--output` is given with `-i`, `-P` or `-T`.
     pub(crate) fn from_matches(matches: &ArgMatches) -> Result<Vec<Self>, ColumnError> {
+        let output = matches.value_source(OPT_OUTPUT) == Some(ValueSource::CommandLine);
+        if output {
+            for (option, id) in [
+                ("-i", OPT_INODES),
+                ("-T", OPT_PRINT_TYPE),
+                ("-P", OPT_PORTABILITY),
+            ] {
+                if matches.get_flag(id) {
+                    return Err(ColumnError::ConflictingOption(option));
+                }
+            }
+        }
+
         match (
             matches.get_flag(OPT_PRINT_TYPE),
             matches.get_flag(OPT_INODES),
-            matches.value_source(OPT_OUTPUT) == Some(ValueSource::CommandLine),
+            output,
         ) {
             (false, false, false) => Ok(vec![
                 Self::Source,
@@ -93,13 +117,17 @@ impl Column {
                 Self::Target,
             ]),
             (false, false, true) => {
-                // Unwrapping should not panic because in this arm of
-                // the `match` statement, we know that `OPT_OUTPUT`
-                // is non-empty.
-                let names = matches
+                // A bare `--output` adds no names; if no occurrence names
+                // any, all the columns are shown.
+                let mut names: Vec<&str> = matches
                     .get_many::<String>(OPT_OUTPUT)
-                    .unwrap()
-                    .map(String::as_str);
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect();
+                if names.is_empty() {
+                    names = OUTPUT_FIELD_LIST.to_vec();
+                }
                 let mut seen: Vec<&str> = vec![];
                 let mut columns = vec![];
                 for name in names {
@@ -107,11 +135,8 @@ impl Column {
                         return Err(ColumnError::MultipleColumns(name.to_string()));
                     }
                     seen.push(name);
-                    // Unwrapping here should not panic because the
-                    // command-line argument parsing library should be
-                    // responsible for ensuring each comma-separated
-                    // string is a valid column label.
-                    let column = Self::parse(name).unwrap();
+                    let column = Self::parse(name)
+                        .map_err(|()| ColumnError::UnknownColumn(name.to_string()))?;
                     columns.push(column);
                 }
                 Ok(columns)
@@ -145,9 +170,8 @@ impl Column {
                 Self::Target,
             ]),
             // The command-line arguments -T and -i are each mutually
-            // exclusive with --output, so the command-line argument
-            // parser should reject those combinations before we get
-            // to this point in the code.
+            // exclusive with --output, so those combinations have been
+            // rejected above.
             _ => unreachable!(),
         }
     }
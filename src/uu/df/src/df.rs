@@ -12,14 +12,16 @@ use blocks::HumanReadable;
 use clap::builder::ValueParser;
 use table::HeaderMode;
 use uucore::display::Quotable;
-use uucore::error::{UError, UResult, USimpleError, get_exit_code};
+use uucore::error::{ExitCodePolicy, FailureClass, UError, UResult, USimpleError, get_exit_code};
 use uucore::fsext::{MountInfo, read_fs_list};
+use uucore::io::TagWriteErrors;
 use uucore::parser::parse_size::ParseSizeError;
 use uucore::translate;
 use uucore::{format_usage, show, show_warning};
 
 use clap::{Arg, ArgAction, ArgMatches, Command, parser::ValueSource};
 
+use std::collections::HashMap;
 use std::ffi::OsString;
 use std::io::stdout;
 use std::path::Path;
@@ -29,7 +31,7 @@ use crate::blocks::{BlockSize, read_block_size};
 use crate::columns::{Column, ColumnError};
 use crate::filesystem::Filesystem;
 use crate::filesystem::FsError;
-use crate::table::Table;
+use crate::table::{Table, write_json};
 
 static OPT_HELP: &str = "help";
 static OPT_ALL: &str = "all";
@@ -48,6 +50,7 @@ static OPT_SYNC: &str = "sync";
 static OPT_TYPE: &str = "type";
 static OPT_PRINT_TYPE: &str = "print-type";
 static OPT_EXCLUDE_TYPE: &str = "exclude-type";
+static OPT_JSON: &str = "json";
 static OUTPUT_FIELD_LIST: [&str; 12] = [
     "source", "fstype", "itotal", "iused", "iavail", "ipcent", "size", "used", "avail", "pcent",
     "file", "target",
@@ -85,6 +88,9 @@ struct Options {
 
     /// Sequence of columns to display in the output table.
     columns: Vec<Column>,
+
+    /// Whether to print one JSON record per filesystem instead of the table.
+    json: bool,
 }
 
 impl Default for Options {
@@ -107,6 +113,7 @@ impl Default for Options {
                 Column::Pcent,
                 Column::Target,
             ],
+            json: false,
         }
     }
 }
@@ -134,7 +141,7 @@ enum OptionsError {
     InvalidSuffix(String),
 
     /// An error getting the columns to display in the output table.
-    #[error("{}", translate!("df-error-field-used-more-than-once", "field" => format!("{}", .0)))]
+    #[error("{}", .0)]
     ColumnError(ColumnError),
 
     #[error(
@@ -203,6 +210,7 @@ impl Options {
             exclude,
             show_total: matches.get_flag(OPT_TOTAL),
             columns: Column::from_matches(matches).map_err(OptionsError::ColumnError)?,
+            json: matches.get_flag(OPT_JSON),
         })
     }
 
@@ -280,17 +288,43 @@ fn mount_info_lt(m1: &MountInfo, m2: &MountInfo) -> bool {
     true
 }
 
-/// Whether to prioritize given mount info over all others on the same device.
+/// Drop all but the preferred entry for each device, as GNU df does unless
+/// `--all` is given, to hide bind mounts and other duplicates.
 ///
-/// This function decides whether the mount info `mi` is better than
-/// all others in `previous` that mount the same device as `mi`.
-fn is_best(previous: &[MountInfo], mi: &MountInfo) -> bool {
-    for seen in previous {
-        if seen.dev_id == mi.dev_id && mount_info_lt(mi, seen) {
-            return false;
+/// The preferred entry takes the place of the first one seen for its device
+/// (see [`mount_info_lt`]), so the order of the mount table is preserved.
+fn filter_mount_list(mounts: Vec<MountInfo>, opt: &Options) -> Vec<MountInfo> {
+    if opt.show_all_fs {
+        return mounts;
+    }
+
+    let mut result: Vec<MountInfo> = Vec::with_capacity(mounts.len());
+    let mut seen_devices: HashMap<String, usize> = HashMap::new();
+    for mi in mounts {
+        // Entries whose mount point can't be accessed are kept, so that we
+        // can complain about them later.
+        if mi.dev_id.is_empty() {
+            result.push(mi);
+            continue;
+        }
+
+        let Some(&index) = seen_devices.get(&mi.dev_id) else {
+            seen_devices.insert(mi.dev_id.clone(), result.len());
+            result.push(mi);
+            continue;
+        };
+
+        let seen = &result[index];
+        if !opt.show_total && mi.remote && seen.remote && mi.dev_name != seen.dev_name {
+            // Don't discard remote entries with different locations, as
+            // these are more likely to be explicitly mounted. However avoid
+            // this when producing a total to give a more accurate value.
+            result.push(mi);
+        } else if !mount_info_lt(&mi, seen) {
+            result[index] = mi;
         }
     }
-    true
+    result
 }
 
 /// Get all currently mounted filesystems.
@@ -309,30 +343,25 @@ fn get_all_filesystems(opt: &Options) -> UResult<Vec<Filesystem>> {
         }
     }
 
-    let mut mounts = vec![];
-    for mut mi in read_fs_list()? {
-        // TODO The running time of the `is_best()` function is linear
-        // in the length of `result`. That makes the running time of
-        // this loop quadratic in the length of `vmi`. This could be
-        // improved by a more efficient implementation of `is_best()`,
-        // but `vmi` is probably not very long in practice.
-        if is_included(&mi, opt) && is_best(&mounts, &mi) {
-            let dev_path: &Path = Path::new(&mi.dev_name);
-            // Only check is_symlink() for absolute paths. For non-absolute paths
-            // like "tmpfs", "sysfs", etc., is_symlink() would resolve relative to
-            // the current working directory, which is extremely slow in deeply
-            // nested directories (O(n) syscalls where n is the directory depth).
-            if dev_path.is_absolute() && dev_path.is_symlink() {
-                if let Ok(canonicalized_symlink) = uucore::fs::canonicalize(
-                    dev_path,
-                    uucore::fs::MissingHandling::Existing,
-                    uucore::fs::ResolveMode::Logical,
-                ) {
-                    mi.dev_name = canonicalized_symlink.to_string_lossy().to_string();
-                }
+    let included = read_fs_list()?
+        .into_iter()
+        .filter(|mi| is_included(mi, opt))
+        .collect();
+    let mut mounts = filter_mount_list(included, opt);
+    for mi in &mut mounts {
+        let dev_path: &Path = Path::new(&mi.dev_name);
+        // Only check is_symlink() for absolute paths. For non-absolute paths
+        // like "tmpfs", "sysfs", etc., is_symlink() would resolve relative to
+        // the current working directory, which is extremely slow in deeply
+        // nested directories (O(n) syscalls where n is the directory depth).
+        if dev_path.is_absolute() && dev_path.is_symlink() {
+            if let Ok(canonicalized_symlink) = uucore::fs::canonicalize(
+                dev_path,
+                uucore::fs::MissingHandling::Existing,
+                uucore::fs::ResolveMode::Logical,
+            ) {
+                mi.dev_name = canonicalized_symlink.to_string_lossy().to_string();
             }
-
-            mounts.push(mi);
         }
     }
 
@@ -404,7 +433,7 @@ where
             }
             Err(FsError::InvalidPath) => {
                 show!(USimpleError::new(
-                    1,
+                    ExitCodePolicy::DF.operand,
                     translate!("df-error-no-such-file-or-directory", "path" => path.as_ref().maybe_quote())
                 ));
             }
@@ -417,7 +446,7 @@ where
             #[cfg(not(windows))]
             Err(FsError::OverMounted) => {
                 show!(USimpleError::new(
-                    1,
+                    ExitCodePolicy::DF.operand,
                     translate!("df-error-cannot-access-over-mounted", "path" => path.as_ref().quote())
                 ));
             }
@@ -442,6 +471,12 @@ enum DfError {
 }
 
 impl UError for DfError {
+    fn code(&self) -> i32 {
+        match self {
+            Self::OptionsError(_) => ExitCodePolicy::DF.usage,
+        }
+    }
+
     fn usage(&self) -> bool {
         matches!(self, Self::OptionsError(OptionsError::ColumnError(_)))
     }
@@ -449,7 +484,11 @@ impl UError for DfError {
 
 #[uucore::main]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
-    let matches = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
+    let matches = uucore::clap_localization::handle_clap_result_with_exit_code(
+        uu_app(),
+        args,
+        ExitCodePolicy::DF.usage,
+    )?;
 
     #[cfg(windows)]
     {
@@ -497,9 +536,13 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         }
     };
 
-    Table::new(&opt, filesystems).write_to(&mut stdout())?;
-
-    Ok(())
+    let mut out = TagWriteErrors(stdout());
+    let written = if opt.json {
+        write_json(&opt, filesystems, &mut out)
+    } else {
+        Table::new(&opt, filesystems).write_to(&mut out)
+    };
+    written.map_err(|e| ExitCodePolicy::DF.classify_io_error(FailureClass::Other, e.into()))
 }
 
 pub fn uu_app() -> Command {
@@ -540,6 +583,12 @@ pub fn uu_app() -> Command {
                 .help(translate!("df-help-total"))
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(OPT_JSON)
+                .long(OPT_JSON)
+                .help(translate!("df-help-json"))
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(OPT_HUMAN_READABLE_BINARY)
                 .short('h')
@@ -594,10 +643,7 @@ pub fn uu_app() -> Command {
                 .num_args(0..)
                 .require_equals(true)
                 .use_value_delimiter(true)
-                .value_parser(OUTPUT_FIELD_LIST)
-                .default_missing_values(OUTPUT_FIELD_LIST)
                 .default_values(["source", "size", "used", "avail", "pcent", "target"])
-                .conflicts_with_all([OPT_INODES, OPT_PORTABILITY, OPT_PRINT_TYPE])
                 .help(translate!("df-help-output")),
         )
         .arg(
@@ -703,16 +749,16 @@ mod tests {
         }
     }
 
-    mod is_best {
+    mod filter_mount_list {
 
-        use crate::is_best;
+        use crate::{Options, filter_mount_list};
         use uucore::fsext::MountInfo;
 
         /// Instantiate a [`MountInfo`] with the given fields.
-        fn mount_info(dev_id: &str, mount_dir: &str) -> MountInfo {
+        fn mount_info(dev_id: &str, dev_name: &str, mount_dir: &str) -> MountInfo {
             MountInfo {
                 dev_id: String::from(dev_id),
-                dev_name: String::new(),
+                dev_name: String::from(dev_name),
                 fs_type: String::new(),
                 mount_dir: mount_dir.into(),
                 mount_option: String::new(),
@@ -722,18 +768,24 @@ mod tests {
             }
         }
 
+        fn mount_dirs(mounts: &[MountInfo]) -> Vec<&str> {
+            mounts
+                .iter()
+                .map(|m| m.mount_dir.to_str().unwrap())
+                .collect()
+        }
+
         #[test]
         fn test_empty() {
-            let m = mount_info("0", "/mnt/bar");
-            assert!(is_best(&[], &m));
+            assert!(filter_mount_list(vec![], &Options::default()).is_empty());
         }
 
         #[test]
         fn test_different_dev_id() {
-            let m1 = mount_info("0", "/mnt/bar");
-            let m2 = mount_info("1", "/mnt/bar");
-            assert!(is_best(std::slice::from_ref(&m1), &m2));
-            assert!(is_best(&[m2], &m1));
+            let m1 = mount_info("0", "/dev/foo", "/mnt/bar");
+            let m2 = mount_info("1", "/dev/foo", "/mnt/bar");
+            let result = filter_mount_list(vec![m1, m2], &Options::default());
+            assert_eq!(result.len(), 2);
         }
 
         #[test]
@@ -741,10 +793,63 @@ mod tests {
             // There are several conditions under which a `MountInfo` is
             // considered "better" than the others, we're just checking
             // one condition in this test.
-            let m1 = mount_info("0", "/mnt/bar");
-            let m2 = mount_info("0", "/mnt/bar/baz");
-            assert!(!is_best(std::slice::from_ref(&m1), &m2));
-            assert!(is_best(&[m2], &m1));
+            let m1 = mount_info("0", "/dev/foo", "/mnt/bar");
+            let m2 = mount_info("0", "/dev/foo", "/mnt/bar/baz");
+            let result = filter_mount_list(vec![m1.clone(), m2.clone()], &Options::default());
+            assert_eq!(mount_dirs(&result), ["/mnt/bar"]);
+            let result = filter_mount_list(vec![m2, m1], &Options::default());
+            assert_eq!(mount_dirs(&result), ["/mnt/bar"]);
+        }
+
+        #[test]
+        fn test_better_entry_keeps_position() {
+            let bind = mount_info("0", "/dev/foo", "/mnt/bind");
+            let other = mount_info("1", "/dev/bar", "/home");
+            let root = mount_info("0", "/dev/foo", "/");
+            let result = filter_mount_list(vec![bind, other, root], &Options::default());
+            assert_eq!(mount_dirs(&result), ["/", "/home"]);
+        }
+
+        #[test]
+        fn test_show_all() {
+            let opt = Options {
+                show_all_fs: true,
+                ..Default::default()
+            };
+            let m1 = mount_info("0", "/dev/foo", "/mnt/bar");
+            let m2 = mount_info("0", "/dev/foo", "/mnt/bar/baz");
+            assert_eq!(filter_mount_list(vec![m1, m2], &opt).len(), 2);
+        }
+
+        #[test]
+        fn test_inaccessible_mount_points_are_kept() {
+            let m1 = mount_info("", "/dev/foo", "/mnt/bar");
+            let m2 = mount_info("", "/dev/bar", "/mnt/baz");
+            assert_eq!(
+                filter_mount_list(vec![m1, m2], &Options::default()).len(),
+                2
+            );
+        }
+
+        #[test]
+        fn test_remote_with_different_locations() {
+            let mut m1 = mount_info("0", "server:/foo", "/mnt/foo");
+            let mut m2 = mount_info("0", "server:/bar", "/mnt/foo/bar");
+            m1.remote = true;
+            m2.remote = true;
+            let mounts = vec![m1, m2];
+            assert_eq!(
+                filter_mount_list(mounts.clone(), &Options::default()).len(),
+                2
+            );
+
+            // With a total, remote duplicates are dropped so they are not
+            // counted twice.
+            let opt = Options {
+                show_total: true,
+                ..Default::default()
+            };
+            assert_eq!(filter_mount_list(mounts, &opt).len(), 1);
         }
     }
 
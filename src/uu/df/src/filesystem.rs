@@ -17,6 +17,8 @@ use std::{ffi::OsString, path::Path};
 #[cfg(unix)]
 use std::os::unix::fs::MetadataExt;
 
+#[cfg(windows)]
+use uucore::fsext::volume_path_name;
 #[cfg(unix)]
 use uucore::fsext::{FsMeta, pretty_fstype, statfs};
 use uucore::fsext::{FsUsage, MountInfo};
@@ -107,6 +109,7 @@ pub(crate) fn find_mount_point<P: AsRef<Path>>(path: P) -> io::Result<PathBuf> {
 ///
 /// * [`Path::canonicalize`]
 /// * [`MountInfo::mount_dir`]
+#[cfg(not(windows))]
 fn mount_info_from_path<P>(
     mounts: &[MountInfo],
     path: P,
@@ -150,6 +153,25 @@ where
         .ok_or(FsError::MountMissing)
 }
 
+/// Find the mount info of the volume that `path` is on.
+///
+/// On Windows, the mount point of the volume is asked of the system rather
+/// than found among the mount directories, which does not work for
+/// canonicalized paths: those start with `\\?\`.
+#[cfg(windows)]
+fn mount_info_from_volume_path<P>(mounts: &[MountInfo], path: P) -> Result<&MountInfo, FsError>
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    std::fs::metadata(path).map_err(|_| FsError::InvalidPath)?;
+    let volume_path = volume_path_name(path).map_err(|_| FsError::InvalidPath)?;
+    mounts
+        .iter()
+        .find(|mi| mi.mount_dir.eq_ignore_ascii_case(&volume_path))
+        .ok_or(FsError::MountMissing)
+}
+
 impl Filesystem {
     // TODO: resolve uuid in `mount_info.dev_name` if exists
     pub(crate) fn new(mount_info: MountInfo, file: Option<OsString>) -> Option<Self> {
@@ -219,13 +241,13 @@ impl Filesystem {
         P: AsRef<Path>,
     {
         let file = path.as_ref().as_os_str().to_owned();
-        let canonicalize = true;
 
-        let result = mount_info_from_path(mounts, path, canonicalize);
         #[cfg(windows)]
-        return result.and_then(|mount_info| Self::from_mount(mount_info, Some(file)));
+        return mount_info_from_volume_path(mounts, path)
+            .and_then(|mount_info| Self::from_mount(mount_info, Some(file)));
         #[cfg(not(windows))]
-        return result.and_then(|mount_info| Self::from_mount(mounts, mount_info, Some(file)));
+        return mount_info_from_path(mounts, path, true)
+            .and_then(|mount_info| Self::from_mount(mounts, mount_info, Some(file)));
     }
 
     /// Fallback using statfs when mount table is unavailable.
@@ -269,6 +291,7 @@ impl Filesystem {
 #[cfg(test)]
 mod tests {
 
+    #[cfg(not(windows))]
     mod mount_info_from_path {
 
         use std::ffi::OsString;
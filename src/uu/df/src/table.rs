@@ -13,10 +13,12 @@ use crate::blocks::{SuffixType, to_magnitude_and_suffix};
 use crate::columns::{Alignment, Column};
 use crate::filesystem::Filesystem;
 use crate::{BlockSize, Options};
+use uucore::format::machine::Record;
 use uucore::fsext::{FsUsage, MountInfo};
 use uucore::translate;
 
 use std::ffi::OsString;
+use std::io::{self, Write};
 use std::iter;
 use std::ops::{Add, AddAssign};
 
@@ -192,6 +194,42 @@ impl Row {
     }
 }
 
+impl Row {
+    /// The `--json` record of this row, keyed by the field names of
+    /// `--output`. Sizes are in bytes, whatever the block size, and
+    /// percentages are rounded up like in the table.
+    fn json_record(&self, is_total_row: bool) -> Record {
+        let count = |n: u128| u64::try_from(n).unwrap_or(u64::MAX);
+        let percent = |fraction: Option<f64>| fraction.map(|x| (100.0 * x).ceil() as u64);
+        let mut record = Record::new();
+        if is_total_row {
+            record
+                .string("source", "total")
+                .null("fstype")
+                .null("target");
+        } else {
+            record
+                .string("source", &self.fs_device)
+                .string("fstype", &self.fs_type)
+                .os_string("target", &self.fs_mount);
+        }
+        record
+            .unsigned("size", self.bytes.bytes)
+            .unsigned("used", self.bytes_used.bytes)
+            .unsigned("avail", self.bytes_avail.bytes)
+            .optional_unsigned("pcent", percent(self.bytes_usage))
+            .unsigned("itotal", count(self.inodes))
+            .unsigned("iused", count(self.inodes_used))
+            .unsigned("iavail", count(self.inodes_free))
+            .optional_unsigned("ipcent", percent(self.inodes_usage));
+        match &self.file {
+            Some(file) => record.os_string("file", file),
+            None => record.null("file"),
+        };
+        record
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 struct BytesCell {
     bytes: u64,
@@ -533,7 +571,7 @@ impl Table {
         alignments
     }
 
-    pub(crate) fn write_to(&self, writer: &mut dyn std::io::Write) -> std::io::Result<()> {
+    pub(crate) fn write_to(&self, writer: &mut dyn Write) -> io::Result<()> {
         for row in &self.rows {
             let mut col_iter = row.iter().enumerate().peekable();
             while let Some((i, elem)) = col_iter.next() {
@@ -569,6 +607,29 @@ impl Table {
     }
 }
 
+/// Write the `--json` records of the rows [`Table::new`] would show, one per
+/// line.
+pub(crate) fn write_json(
+    options: &Options,
+    filesystems: Vec<Filesystem>,
+    writer: &mut dyn Write,
+) -> io::Result<()> {
+    let mut total = Row::new(&translate!("df-total"));
+    for filesystem in filesystems {
+        if options.show_all_fs || filesystem.usage.blocks > 0 {
+            let row = Row::from_filesystem(filesystem, &options.block_size);
+            writeln!(writer, "{}", row.json_record(false))?;
+            if options.show_total {
+                total += row;
+            }
+        }
+    }
+    if options.show_total {
+        writeln!(writer, "{}", total.json_record(true))?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -793,6 +854,23 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_row_json_record() {
+        let row = Row::default();
+        assert_eq!(
+            row.json_record(false).to_string(),
+            r#"{"source":"my_device","fstype":"my_type","target":"my_mount","size":100,"used":25,"avail":75,"pcent":25,"itotal":10,"iused":2,"iavail":8,"ipcent":20,"file":"/path/to/file"}"#
+        );
+
+        let mut total = Row::new("total");
+        total += Row::default();
+        total.bytes_usage = None;
+        assert_eq!(
+            total.json_record(true).to_string(),
+            r#"{"source":"total","fstype":null,"target":null,"size":100,"used":25,"avail":75,"pcent":null,"itotal":10,"iused":2,"iavail":8,"ipcent":20,"file":null}"#
+        );
+    }
+
     #[test]
     fn test_row_formatter_with_fs_type() {
         init();
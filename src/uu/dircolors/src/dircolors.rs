@@ -73,49 +73,6 @@ fn get_colors_format_strings(fmt: &OutputFmt) -> (String, String) {
     (prefix, suffix)
 }
 
-pub fn generate_type_output(fmt: &OutputFmt) -> String {
-    match fmt {
-        OutputFmt::Display => FILE_TYPES
-            .iter()
-            .map(|&(_, key, val)| format!("\x1b[{val}m{key}\t{val}\x1b[0m"))
-            .collect::<Vec<String>>()
-            .join("\n"),
-        _ => {
-            // Existing logic for other formats
-            FILE_TYPES
-                .iter()
-                .map(|&(_, v1, v2)| format!("{v1}={v2}"))
-                .collect::<Vec<String>>()
-                .join(":")
-        }
-    }
-}
-
-fn generate_ls_colors(fmt: &OutputFmt, sep: &str) -> String {
-    if let OutputFmt::Display = fmt {
-        let mut display_parts = vec![];
-        let type_output = generate_type_output(fmt);
-        display_parts.push(type_output);
-        for &(extension, code) in FILE_COLORS {
-            let prefix = if extension.starts_with('*') { "" } else { "*" };
-            let formatted_extension = format!("\x1b[{code}m{prefix}{extension}\t{code}\x1b[0m");
-            display_parts.push(formatted_extension);
-        }
-        display_parts.join("\n")
-    } else {
-        // existing logic for other formats
-        let mut parts = vec![];
-        for &(extension, code) in FILE_COLORS {
-            let prefix = if extension.starts_with('*') { "" } else { "*" };
-            let formatted_extension = format!("{prefix}{extension}");
-            parts.push(format!("{formatted_extension}={code}"));
-        }
-        let (prefix, suffix) = get_colors_format_strings(fmt);
-        let ls_colors = parts.join(sep);
-        format!("{prefix}{}:{ls_colors}:{suffix}", generate_type_output(fmt),)
-    }
-}
-
 #[uucore::main]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     let matches = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
@@ -178,20 +135,10 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
 
     let result;
     if files.is_empty() {
-        writeln!(stdout(), "{}", generate_ls_colors(&out_format, ":"))?;
-        return Ok(());
-        /*
-        // Check if data is being piped into the program
-        if std::io::stdin().is_terminal() {
-            // No data piped, use default behavior
-            writeln!(stdout(), "{}", generate_ls_colors(&out_format, ":"))?;
-            return Ok(());
-        } else {
-            // Data is piped, process the input from stdin
-            let fin = BufReader::new(std::io::stdin());
-            result = parse(fin.lines().map_while(Result::ok), &out_format, "-");
-        }
-         */
+        // Like GNU, run the precompiled database through the same parser as a
+        // user file, so that its TERM and COLORTERM entries are honored. It
+        // has no invalid lines, so there's no file name to report.
+        result = parse(generate_dircolors_config().lines(), &out_format, "");
     } else if files.len() > 1 {
         return Err(UUsageError::new(
             1,
@@ -230,7 +177,10 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
 
     match result {
         Ok(s) => {
-            writeln!(stdout(), "{s}")?;
+            // --print-ls-colors prints nothing at all when no entry applies
+            if !s.is_empty() {
+                writeln!(stdout(), "{s}")?;
+            }
             Ok(())
         }
         Err(s) => Err(USimpleError::new(1, s)),
@@ -358,7 +308,10 @@ where
     result.push_str(&prefix);
 
     // Get environment variables once at the start
-    let term = env::var("TERM").unwrap_or_else(|_| "none".to_owned());
+    let term = env::var("TERM")
+        .ok()
+        .filter(|term| !term.is_empty())
+        .unwrap_or_else(|| "none".to_owned());
     let colorterm = env::var("COLORTERM").unwrap_or_default();
 
     let mut state = ParseState::Global;
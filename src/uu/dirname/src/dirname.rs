@@ -6,13 +6,16 @@
 use clap::{Arg, ArgAction, Command};
 use std::borrow::Cow;
 use std::ffi::OsString;
-#[cfg(unix)]
-use uucore::display::print_verbatim;
+use std::io::Write;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use uucore::error::{UResult, UUsageError};
 use uucore::format_usage;
 use uucore::line_ending::LineEnding;
 
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::stdout;
 
 mod options {
     pub const ZERO: &str = "zero";
@@ -115,28 +118,25 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         return Err(UUsageError::new(1, translate!("dirname-missing-operand")));
     }
 
+    let mut out = stdout();
     for path in &dirnames {
         let path_bytes = uucore::os_str_as_bytes(path.as_os_str()).unwrap_or(&[]);
         let result = dirname_string_manipulation(path_bytes);
 
-        #[cfg(unix)]
+        #[cfg(any(unix, target_family = "wasm"))]
+        out.write_all(&result)?;
+        #[cfg(not(any(unix, target_family = "wasm")))]
         {
-            use std::os::unix::ffi::OsStrExt;
-            let result_os = std::ffi::OsStr::from_bytes(&result);
-            print_verbatim(result_os).unwrap();
-        }
-        #[cfg(not(unix))]
-        {
-            // On non-Unix, fall back to lossy conversion
+            // On Windows, fall back to lossy conversion
             if let Ok(s) = std::str::from_utf8(&result) {
-                print!("{s}");
+                write!(out, "{s}")?;
             } else {
-                // Fallback for non-UTF-8 paths on non-Unix systems
-                print!(".");
+                // Fallback for non-UTF-8 paths on Windows
+                write!(out, ".")?;
             }
         }
 
-        print!("{line_ending}");
+        write!(out, "{line_ending}")?;
     }
 
     Ok(())
@@ -22,9 +22,10 @@ du-help-apparent-size = print apparent sizes, rather than disk usage although th
 du-help-block-size = scale sizes by SIZE before printing them. E.g., '-BM' prints sizes in units of 1,048,576 bytes. See SIZE format below.
 du-help-bytes = equivalent to '--apparent-size --block-size=1'
 du-help-total = produce a grand total
+du-help-json = print one JSON object per entry instead of the size and name (JSON Lines)
 du-help-max-depth = print the total for a directory (or file, with --all) only if it is N or fewer levels below the command line argument;  --max-depth=0 is the same as --summarize
 du-help-human-readable = print sizes in human readable format (e.g., 1K 234M 2G)
-du-help-inodes = list inode usage information instead of block usage like --block-size=1K
+du-help-inodes = list inode usage information instead of block usage
 du-help-block-size-1k = like --block-size=1K
 du-help-count-links = count sizes many times if hard linked
 du-help-dereference = follow all symbolic links
@@ -40,6 +41,8 @@ du-help-threshold = exclude entries smaller than SIZE if positive, or entries gr
 du-help-verbose = verbose mode (option not present in GNU/Coreutils)
 du-help-exclude = exclude files that match PATTERN
 du-help-exclude-from = exclude files that match any pattern in FILE
+du-help-exclude-ignore-case = match exclude patterns regardless of case (option not present in GNU/Coreutils)
+du-help-exclude-globstar = in exclude patterns, let '*' stop at '/' and a '**' component match any number of directories (option not present in GNU/Coreutils)
 du-help-files0-from = summarize device usage of the NUL-terminated file names specified in file F; if F is -, then read names from standard input
 du-help-time = show time of the last modification of any file in the directory, or any of its subdirectories. If WORD is given, show time as WORD instead of modification time: atime, access, use, ctime, status, birth or creation
 du-help-time-style = show times using style STYLE: full-iso, long-iso, iso, +FORMAT FORMAT is interpreted like 'date'
@@ -22,9 +22,10 @@ du-help-apparent-size = afficher les tailles apparentes, plutôt que l'utilisati
 du-help-block-size = mettre à l'échelle les tailles par TAILLE avant de les afficher. Par ex., '-BM' affiche les tailles en unités de 1 048 576 octets. Voir le format TAILLE ci-dessous.
 du-help-bytes = équivalent à '--apparent-size --block-size=1'
 du-help-total = produire un total général
+du-help-json = afficher un objet JSON par entrée au lieu de la taille et du nom (JSON Lines)
 du-help-max-depth = afficher le total pour un répertoire (ou fichier, avec --all) seulement s'il est à N niveaux ou moins sous l'argument de ligne de commande ; --max-depth=0 est identique à --summarize
 du-help-human-readable = afficher les tailles dans un format lisible par l'homme (p. ex., 1K 234M 2G)
-du-help-inodes = lister les informations d'utilisation des inodes au lieu de l'utilisation des blocs comme --block-size=1K
+du-help-inodes = lister les informations d'utilisation des inodes au lieu de l'utilisation des blocs
 du-help-block-size-1k = comme --block-size=1K
 du-help-count-links = compter les tailles plusieurs fois si liées en dur
 du-help-dereference = suivre tous les liens symboliques
@@ -40,6 +41,8 @@ du-help-threshold = exclure les entrées plus petites que TAILLE si positive, ou
 du-help-verbose = mode verbeux (option non présente dans GNU/Coreutils)
 du-help-exclude = exclure les fichiers qui correspondent au MOTIF
 du-help-exclude-from = exclure les fichiers qui correspondent à n'importe quel motif dans FICHIER
+du-help-exclude-ignore-case = faire correspondre les motifs d'exclusion sans tenir compte de la casse (option non présente dans GNU/Coreutils)
+du-help-exclude-globstar = dans les motifs d'exclusion, '*' s'arrête à '/' et un composant '**' correspond à un nombre quelconque de répertoires (option non présente dans GNU/Coreutils)
 du-help-files0-from = résumer l'utilisation du périphérique des noms de fichiers terminés par NUL spécifiés dans le fichier F ; si F est -, alors lire les noms depuis l'entrée standard
 du-help-time = montrer l'heure de la dernière modification de n'importe quel fichier dans le répertoire, ou n'importe lequel de ses sous-répertoires. Si MOT est donné, montrer l'heure comme MOT au lieu de l'heure de modification : atime, access, use, ctime, status, birth ou creation
 du-help-time-style = montrer les heures en utilisant le style STYLE : full-iso, long-iso, iso, +FORMAT FORMAT est interprété comme 'date'
@@ -6,33 +6,55 @@
 // spell-checker:ignore fstatat openat dirfd
 
 use clap::{Arg, ArgAction, ArgMatches, Command, builder::PossibleValue};
-use glob::Pattern;
+#[cfg(all(unix, not(target_os = "redox")))]
+use prefetch::{ListedEntry, Prefetched, list_dir, read_ahead};
 use rustc_hash::FxHashSet as HashSet;
+#[cfg(all(unix, not(target_os = "redox")))]
+use std::collections::VecDeque;
 use std::env;
 use std::ffi::{OsStr, OsString};
-use std::fs::{self, DirEntry, File, Metadata};
-use std::io::{BufRead, BufReader, stdout};
-#[cfg(not(windows))]
+#[cfg(not(target_family = "wasm"))]
+use std::fs::{self, Metadata};
+use std::fs::{DirEntry, File};
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
+use std::io::{BufRead, BufReader, Write};
+#[cfg(unix)]
 use std::os::unix::fs::MetadataExt;
 #[cfg(windows)]
 use std::os::windows::io::AsRawHandle;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::mpsc;
+#[cfg(not(target_family = "wasm"))]
 use std::thread;
+#[cfg(target_family = "wasm")]
+use std::time::SystemTime;
 use thiserror::Error;
-use uucore::display::{Quotable, print_verbatim};
-use uucore::error::{FromIo, UError, UResult, USimpleError, set_exit_code};
-use uucore::fsext::{MetadataTimeField, metadata_get_time};
+use uucore::display::{OsWrite, Quotable};
+#[cfg(not(target_family = "wasm"))]
+use uucore::error::FromIo;
+use uucore::error::{
+    ExitCodePolicy, FailureClass, UError, UResult, USimpleError, UUsageError, set_exit_code,
+    strip_errno,
+};
+use uucore::files0::files0;
+use uucore::format::machine::Record;
+use uucore::fsext::MetadataTimeField;
+#[cfg(not(target_family = "wasm"))]
+use uucore::fsext::metadata_get_time;
+use uucore::io::TagWriteErrors;
 use uucore::line_ending::LineEnding;
 #[cfg(all(unix, not(target_os = "redox")))]
 use uucore::safe_traversal::{DirFd, SymlinkBehavior};
 use uucore::translate;
 
-use uucore::parser::parse_glob;
+use uucore::parser::parse_glob::PatternSet;
 use uucore::parser::parse_size::{ParseSizeError, parse_size_non_zero_u64, parse_size_u64};
 use uucore::parser::shortcut_value_parser::ShortcutValueParser;
 use uucore::time::{FormatSystemTimeFallback, format, format_system_time};
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{WasmMetadata as Metadata, stdout};
 use uucore::{format_usage, show, show_error, show_warning};
 #[cfg(windows)]
 use windows_sys::Win32::Foundation::HANDLE;
@@ -42,6 +64,13 @@ use windows_sys::Win32::Storage::FileSystem::{
     GetFileInformationByHandleEx,
 };
 
+#[cfg(all(unix, not(target_os = "redox")))]
+mod prefetch;
+#[cfg(target_family = "wasm")]
+mod wasm;
+#[cfg(target_family = "wasm")]
+use wasm::du_regular;
+
 mod options {
     pub const HELP: &str = "help";
     pub const NULL: &str = "0";
@@ -68,19 +97,23 @@ mod options {
     pub const INODES: &str = "inodes";
     pub const EXCLUDE: &str = "exclude";
     pub const EXCLUDE_FROM: &str = "exclude-from";
+    pub const EXCLUDE_IGNORE_CASE: &str = "exclude-ignore-case";
+    pub const EXCLUDE_GLOBSTAR: &str = "exclude-globstar";
     pub const FILES0_FROM: &str = "files0-from";
     pub const VERBOSE: &str = "verbose";
+    pub const JSON: &str = "json";
     pub const FILE: &str = "FILE";
 }
 
 struct TraversalOptions {
     all: bool,
     separate_dirs: bool,
+    #[cfg_attr(target_family = "wasm", allow(dead_code))]
     one_file_system: bool,
     dereference: Deref,
     count_links: bool,
     verbose: bool,
-    excludes: Vec<Pattern>,
+    excludes: PatternSet,
 }
 
 struct StatPrinter {
@@ -95,6 +128,7 @@ struct StatPrinter {
     line_ending: LineEnding,
     summarize: bool,
     total_text: String,
+    json: bool,
 }
 
 #[derive(PartialEq, Clone)]
@@ -139,6 +173,7 @@ impl Stat {
             Deref::None => false,
         };
 
+        #[cfg(not(target_family = "wasm"))]
         let metadata = if should_dereference {
             // Get metadata, following symbolic links if necessary
             fs::metadata(path)
@@ -149,6 +184,15 @@ impl Stat {
             // Get metadata from the filesystem without following symbolic links
             fs::symlink_metadata(path)
         }?;
+        #[cfg(target_family = "wasm")]
+        let metadata = {
+            let _ = dir_entry;
+            if should_dereference {
+                uucore::wasm_io::metadata(path)
+            } else {
+                uucore::wasm_io::symlink_metadata(path)
+            }?
+        };
 
         let file_info = get_file_info(path, &metadata);
         let blocks = get_blocks(path, &metadata);
@@ -198,11 +242,17 @@ impl Stat {
     }
 }
 
-#[cfg(not(windows))]
+#[cfg(unix)]
 fn get_blocks(_path: &Path, metadata: &Metadata) -> u64 {
     metadata.blocks()
 }
 
+/// Hosts only report the apparent size, so assume no sparse files.
+#[cfg(target_family = "wasm")]
+fn get_blocks(_path: &Path, metadata: &Metadata) -> u64 {
+    metadata.len().div_ceil(512)
+}
+
 #[cfg(windows)]
 fn get_blocks(path: &Path, _metadata: &Metadata) -> u64 {
     let mut size_on_disk = 0;
@@ -232,7 +282,7 @@ fn get_blocks(path: &Path, _metadata: &Metadata) -> u64 {
     size_on_disk / 1024 * 2
 }
 
-#[cfg(not(windows))]
+#[cfg(unix)]
 #[expect(
     clippy::unnecessary_wraps,
     reason = "fn sig must match on all platforms"
@@ -244,6 +294,12 @@ fn get_file_info(_path: &Path, metadata: &Metadata) -> Option<FileInfo> {
     })
 }
 
+/// There are no inodes on WASM, so hard links can't be detected.
+#[cfg(target_family = "wasm")]
+fn get_file_info(_path: &Path, _metadata: &Metadata) -> Option<FileInfo> {
+    None
+}
+
 #[cfg(windows)]
 fn get_file_info(path: &Path, _metadata: &Metadata) -> Option<FileInfo> {
     let mut result = None;
@@ -286,8 +342,12 @@ fn block_size_from_env() -> Option<u64> {
 
 fn read_block_size(s: Option<&str>) -> UResult<u64> {
     if let Some(s) = s {
-        parse_size_u64(s)
-            .map_err(|e| USimpleError::new(1, format_error_message(&e, s, options::BLOCK_SIZE)))
+        parse_size_u64(s).map_err(|e| {
+            USimpleError::new(
+                ExitCodePolicy::DU.usage,
+                format_error_message(&e, s, options::BLOCK_SIZE),
+            )
+        })
     } else if let Some(bytes) = block_size_from_env() {
         Ok(bytes)
     } else if env::var("POSIXLY_CORRECT").is_ok() {
@@ -297,9 +357,55 @@ fn read_block_size(s: Option<&str>) -> UResult<u64> {
     }
 }
 
+/// The stat of the entry `path` of the directory open as `parent_fd`.
+#[cfg(all(unix, not(target_os = "redox")))]
+fn stat_in_parent(parent_fd: &DirFd, path: &Path) -> std::io::Result<Stat> {
+    let dir_name = path.file_name().unwrap_or(path.as_os_str());
+    let safe_metadata = parent_fd.metadata_at(dir_name, SymlinkBehavior::NoFollow)?;
+    let file_info = safe_metadata.file_info();
+    let file_info_option = Some(FileInfo {
+        file_id: file_info.inode() as u128,
+        dev_id: file_info.device(),
+    });
+    let blocks = safe_metadata.blocks();
+
+    // For compatibility, still try to get std::fs::Metadata
+    // but fallback to a minimal approach if it fails
+    let std_metadata = fs::symlink_metadata(path).unwrap_or_else(|_| {
+        // If we can't get std metadata, create a minimal fake one
+        // This should rarely happen but provides a fallback
+        fs::symlink_metadata("/").expect("root should be accessible")
+    });
+
+    Ok(Stat {
+        path: path.to_path_buf(),
+        size: if safe_metadata.is_dir() {
+            0
+        } else {
+            safe_metadata.len()
+        },
+        blocks,
+        inodes: 1,
+        inode: file_info_option,
+        metadata: std_metadata,
+    })
+}
+
+#[cfg(all(unix, not(target_os = "redox")))]
+fn is_dir(lstat: &uucore::libc::stat) -> bool {
+    const S_IFMT: u32 = 0o170_000;
+    const S_IFDIR: u32 = 0o040_000;
+    #[allow(clippy::unnecessary_cast)]
+    let is_dir = (lstat.st_mode as u32 & S_IFMT) == S_IFDIR;
+    is_dir
+}
+
 #[cfg(all(unix, not(target_os = "redox")))]
 // Implement safe_du on Unix (except Redox which lacks full stat support)
 // This is done for TOCTOU safety
+//
+// What is already known about `path` is passed as `prefetched`: the stat of
+// a command line argument, or the directory itself if it was read ahead.
 fn safe_du(
     path: &Path,
     options: &TraversalOptions,
@@ -307,43 +413,24 @@ fn safe_du(
     seen_inodes: &mut HashSet<FileInfo>,
     print_tx: &mpsc::Sender<UResult<StatPrintInfo>>,
     parent_fd: Option<&DirFd>,
-    initial_stat: Option<std::io::Result<Stat>>,
+    prefetched: Option<Prefetched>,
 ) -> Result<Stat, Box<mpsc::SendError<UResult<StatPrintInfo>>>> {
+    let (initial_stat, prefetched_dir) = match prefetched {
+        // Once here, the directory is no longer counted as read ahead.
+        Some(Prefetched { stat, dir, .. }) => (Some(stat), dir),
+        None => (None, None),
+    };
+
     // Get initial stat for this path - use DirFd if available to avoid path length issues
-    let mut my_stat = if let Some(parent_fd) = parent_fd {
-        // We have a parent fd, this is a subdirectory - use openat
-        let dir_name = path.file_name().unwrap_or(path.as_os_str());
-        match parent_fd.metadata_at(dir_name, SymlinkBehavior::NoFollow) {
-            Ok(safe_metadata) => {
-                // Create Stat from safe metadata
-                let file_info = safe_metadata.file_info();
-                let file_info_option = Some(FileInfo {
-                    file_id: file_info.inode() as u128,
-                    dev_id: file_info.device(),
-                });
-                let blocks = safe_metadata.blocks();
-
-                // For compatibility, still try to get std::fs::Metadata
-                // but fallback to a minimal approach if it fails
-                let std_metadata = fs::symlink_metadata(path).unwrap_or_else(|_| {
-                    // If we can't get std metadata, create a minimal fake one
-                    // This should rarely happen but provides a fallback
-                    fs::symlink_metadata("/").expect("root should be accessible")
-                });
-
-                Stat {
-                    path: path.to_path_buf(),
-                    size: if safe_metadata.is_dir() {
-                        0
-                    } else {
-                        safe_metadata.len()
-                    },
-                    blocks,
-                    inodes: 1,
-                    inode: file_info_option,
-                    metadata: std_metadata,
-                }
-            }
+    let mut my_stat = if depth > 0 {
+        // This is a subdirectory - use openat, unless it was read ahead
+        let stat = match (initial_stat, parent_fd) {
+            (Some(stat), _) => stat,
+            (None, Some(parent_fd)) => stat_in_parent(parent_fd, path),
+            (None, None) => Stat::new(path, None, options),
+        };
+        match stat {
+            Ok(stat) => stat,
             Err(e) => {
                 let error = e.map_err_context(
                     || translate!("du-error-cannot-access", "path" => path.quote()),
@@ -404,17 +491,23 @@ fn safe_du(
         return Ok(my_stat);
     }
 
-    // Open the directory using DirFd
-    let open_result = match parent_fd {
-        Some(parent) => parent.open_subdir(
-            path.file_name().unwrap_or(path.as_os_str()),
-            SymlinkBehavior::Follow,
-        ),
-        None => DirFd::open(path, SymlinkBehavior::Follow),
-    };
+    // Open the directory using DirFd and read its entries, unless that was
+    // done ahead. The directory stays open to open the subdirectories.
+    let dir = prefetched_dir.unwrap_or_else(|| {
+        let open_result = match parent_fd {
+            Some(parent) => parent.open_subdir(
+                path.file_name().unwrap_or(path.as_os_str()),
+                SymlinkBehavior::Follow,
+            ),
+            None => DirFd::open(path, SymlinkBehavior::Follow),
+        };
+        let dir_fd = open_result?;
+        let entries = list_dir(&dir_fd)?;
+        Ok((dir_fd, entries))
+    });
 
-    let dir_fd = match open_result {
-        Ok(fd) => fd,
+    let (dir_fd, entries) = match dir {
+        Ok(dir) => dir,
         Err(e) => {
             print_tx.send(Err(e.map_err_context(
                 || translate!("du-error-cannot-read-directory", "path" => path.quote()),
@@ -423,22 +516,33 @@ fn safe_du(
         }
     };
 
-    // Read directory entries
-    let entries = match dir_fd.read_dir() {
-        Ok(entries) => entries,
-        Err(e) => {
-            print_tx.send(Err(e.map_err_context(
-                || translate!("du-error-cannot-read-directory", "path" => path.quote()),
-            )))?;
-            return Ok(my_stat);
+    // The next subdirectories are read ahead once those read before have
+    // all been reached.
+    let mut entries = VecDeque::from(entries);
+    let mut ahead = 0;
+    'file_loop: loop {
+        if ahead == 0 {
+            ahead = read_ahead(
+                &dir_fd,
+                path,
+                my_stat.inode,
+                options,
+                entries.make_contiguous(),
+            );
         }
-    };
-
-    'file_loop: for entry_name in entries {
+        let Some(entry) = entries.pop_front() else {
+            break;
+        };
+        ahead -= 1;
+        let ListedEntry {
+            name: entry_name,
+            lstat,
+            subdir,
+        } = entry;
         let entry_path = path.join(&entry_name);
 
         // First get the lstat (without following symlinks) to check if it's a symlink
-        let lstat = match dir_fd.stat_at(&entry_name, SymlinkBehavior::NoFollow) {
+        let lstat = match lstat {
             Ok(stat) => stat,
             Err(e) => {
                 print_tx.send(Err(e.map_err_context(
@@ -450,7 +554,6 @@ fn safe_du(
 
         // Check if it's a symlink
         const S_IFMT: u32 = 0o170_000;
-        const S_IFDIR: u32 = 0o040_000;
         const S_IFLNK: u32 = 0o120_000;
         #[allow(clippy::unnecessary_cast)]
         let is_symlink = (lstat.st_mode as u32 & S_IFMT) == S_IFLNK;
@@ -464,8 +567,7 @@ fn safe_du(
             continue;
         }
 
-        #[allow(clippy::unnecessary_cast)]
-        let is_dir = (lstat.st_mode as u32 & S_IFMT) == S_IFDIR;
+        let is_dir = is_dir(&lstat);
         let entry_stat = lstat;
 
         #[allow(clippy::unnecessary_cast)]
@@ -504,18 +606,14 @@ fn safe_du(
         };
 
         // Check excludes
-        for pattern in &options.excludes {
-            if pattern.matches(&this_stat.path.to_string_lossy())
-                || pattern.matches(&entry_name.to_string_lossy())
-            {
-                if options.verbose {
-                    println!(
-                        "{}",
-                        translate!("du-verbose-ignored", "path" => this_stat.path.quote())
-                    );
-                }
-                continue 'file_loop;
+        if options.excludes.matches(&this_stat.path.to_string_lossy()) {
+            if options.verbose {
+                println!(
+                    "{}",
+                    translate!("du-verbose-ignored", "path" => this_stat.path.quote())
+                );
             }
+            continue 'file_loop;
         }
 
         // Handle inodes
@@ -543,7 +641,7 @@ fn safe_du(
                 seen_inodes,
                 print_tx,
                 Some(&dir_fd),
-                None,
+                subdir,
             )?;
 
             if !options.separate_dirs {
@@ -576,6 +674,7 @@ fn safe_du(
 // Regular traversal using std::fs
 // Used on non-Linux platforms and as fallback for symlinks on Linux
 #[allow(clippy::cognitive_complexity)]
+#[cfg(not(target_family = "wasm"))]
 fn du_regular(
     mut my_stat: Stat,
     options: &TraversalOptions,
@@ -655,24 +754,18 @@ fn du_regular(
                                 }
                             }
 
-                            // We have an exclude list
-                            for pattern in &options.excludes {
-                                // Look at all patterns with both short and long paths
-                                // if we have 'du foo' but search to exclude 'foo/bar'
-                                // we need the full path
-                                if pattern.matches(&this_stat.path.to_string_lossy())
-                                    || pattern.matches(&entry.file_name().into_string().unwrap())
-                                {
-                                    // if the directory is ignored, leave early
-                                    if options.verbose {
-                                        println!(
-                                            "{}",
-                                            translate!("du-verbose-ignored", "path" => this_stat.path.quote())
-                                        );
-                                    }
-                                    // Go to the next file
-                                    continue 'file_loop;
+                            // The patterns are matched against the whole path as
+                            // well as its trailing components, so both 'foo/bar'
+                            // and 'bar' exclude 'foo/bar'. An excluded directory is
+                            // not descended into.
+                            if options.excludes.matches(&this_stat.path.to_string_lossy()) {
+                                if options.verbose {
+                                    println!(
+                                        "{}",
+                                        translate!("du-verbose-ignored", "path" => this_stat.path.quote())
+                                    );
                                 }
+                                continue 'file_loop;
                             }
 
                             if let Some(inode) = this_stat.inode {
@@ -773,30 +866,46 @@ impl UError for DuError {
     }
 }
 
-/// Read a file and return each line in a vector of String
-fn file_as_vec(filename: impl AsRef<Path>) -> Vec<String> {
-    let file = File::open(filename).expect("no such file");
-    let buf = BufReader::new(file);
-
-    buf.lines()
-        .map(|l| l.expect("Could not parse line"))
-        .collect()
+/// Read the patterns of an `--exclude-from` file, one per line, or of stdin
+/// for `-`
+fn file_as_vec(filename: &str) -> UResult<Vec<String>> {
+    let lines: Result<Vec<_>, _> = if filename == "-" {
+        std::io::stdin().lock().split(b'\n').collect()
+    } else {
+        File::open(filename).and_then(|file| BufReader::new(file).split(b'\n').collect())
+    };
+    let lines = lines.map_err(|e| {
+        UUsageError::new(
+            1,
+            format!("{}: {}", filename.maybe_quote(), strip_errno(&e)),
+        )
+    })?;
+    Ok(lines
+        .iter()
+        .map(|l| String::from_utf8_lossy(l).into_owned())
+        .collect())
 }
 
-/// Given the `--exclude-from` and/or `--exclude` arguments, returns the globset lists
-/// to ignore the files
-fn build_exclude_patterns(matches: &ArgMatches) -> UResult<Vec<Pattern>> {
-    let exclude_from_iterator = matches
+/// Given the `--exclude-from` and/or `--exclude` arguments, returns the set of
+/// patterns of the files to ignore
+fn build_exclude_patterns(matches: &ArgMatches) -> UResult<PatternSet> {
+    let mut exclude_from = Vec::new();
+    for filename in matches
         .get_many::<String>(options::EXCLUDE_FROM)
         .unwrap_or_default()
-        .flat_map(file_as_vec);
+    {
+        exclude_from.extend(file_as_vec(filename)?);
+    }
+    let exclude_from_iterator = exclude_from.into_iter();
 
     let excludes_iterator = matches
         .get_many::<String>(options::EXCLUDE)
         .unwrap_or_default()
         .cloned();
 
-    let mut exclude_patterns = Vec::new();
+    let mut exclude_patterns = PatternSet::new()
+        .globstar(matches.get_flag(options::EXCLUDE_GLOBSTAR))
+        .ignore_case(matches.get_flag(options::EXCLUDE_IGNORE_CASE));
     for f in excludes_iterator.chain(exclude_from_iterator) {
         if matches.get_flag(options::VERBOSE) {
             println!(
@@ -804,9 +913,8 @@ fn build_exclude_patterns(matches: &ArgMatches) -> UResult<Vec<Pattern>> {
                 translate!("du-verbose-adding-to-exclude-list", "pattern" => f.clone())
             );
         }
-        match parse_glob::from_str(&f) {
-            Ok(glob) => exclude_patterns.push(glob),
-            Err(err) => return Err(DuError::InvalidGlob(err.to_string()).into()),
+        if let Err(err) = exclude_patterns.add(&f) {
+            return Err(DuError::InvalidGlob(err.to_string()).into());
         }
     }
     Ok(exclude_patterns)
@@ -821,7 +929,13 @@ impl StatPrinter {
     fn choose_size(&self, stat: &Stat) -> u64 {
         if self.inodes {
             stat.inodes
-        } else if self.apparent_size {
+        } else {
+            self.size_in_bytes(stat)
+        }
+    }
+
+    fn size_in_bytes(&self, stat: &Stat) -> u64 {
+        if self.apparent_size {
             stat.size
         } else {
             // The st_blocks field indicates the number of blocks allocated to the file, 512-byte units.
@@ -831,7 +945,13 @@ impl StatPrinter {
     }
 
     fn print_stats(&self, rx: &mpsc::Receiver<UResult<StatPrintInfo>>) -> UResult<()> {
+        let mut out = TagWriteErrors(stdout());
+        // Like GNU, errors are still reported once the output is gone, and
+        // the first write error at the end.
+        let mut write_error = None;
         let mut grand_total = 0;
+        // The size in bytes and the number of inodes, for the `--json` total.
+        let mut json_total = (0, 0);
         loop {
             let received = rx.recv();
 
@@ -842,17 +962,26 @@ impl StatPrinter {
 
                         if stat_info.depth == 0 {
                             grand_total += size;
+                            json_total.0 += self.size_in_bytes(&stat_info.stat);
+                            json_total.1 += stat_info.stat.inodes;
                         }
 
-                        if !self
-                            .threshold
-                            .is_some_and(|threshold| threshold.should_exclude(size))
+                        if write_error.is_none()
+                            && !self
+                                .threshold
+                                .is_some_and(|threshold| threshold.should_exclude(size))
                             && self
                                 .max_depth
                                 .is_none_or(|max_depth| stat_info.depth <= max_depth)
                             && (!self.summarize || stat_info.depth == 0)
                         {
-                            self.print_stat(&stat_info.stat, size)?;
+                            let printed = if self.json {
+                                writeln!(out, "{}", self.json_record(&stat_info.stat))
+                                    .map_err(Into::into)
+                            } else {
+                                self.print_stat(&mut out, &stat_info.stat, size)
+                            };
+                            write_error = printed.err();
                         }
                     }
                     Err(e) => show!(e),
@@ -860,11 +989,29 @@ impl StatPrinter {
                 Err(_) => break,
             }
         }
+        if let Some(e) = write_error {
+            return Err(e);
+        }
 
-        if self.total {
-            print!("{}\t{}", self.convert_size(grand_total), self.total_text);
-            print!("{}", self.line_ending);
+        if self.total && self.json {
+            let mut record = Record::new();
+            record
+                .null("path")
+                .unsigned("size", json_total.0)
+                .unsigned("inodes", json_total.1)
+                .null("time")
+                .bool("total", true);
+            writeln!(out, "{record}")?;
+        } else if self.total {
+            write!(
+                out,
+                "{}\t{}",
+                self.convert_size(grand_total),
+                self.total_text
+            )?;
+            write!(out, "{}", self.line_ending)?;
         }
+        out.flush()?;
 
         Ok(())
     }
@@ -890,25 +1037,42 @@ impl StatPrinter {
         }
     }
 
-    fn print_stat(&self, stat: &Stat, size: u64) -> UResult<()> {
-        print!("{}\t", self.convert_size(size));
+    /// The `--json` record of `stat`. Both the size in bytes and the number
+    /// of inodes are given, whether or not `--inodes` is; the time is `null`
+    /// without `--time`.
+    fn json_record(&self, stat: &Stat) -> Record {
+        let time = self
+            .time
+            .and_then(|md_time| metadata_get_time(&stat.metadata, md_time));
+        let mut record = Record::new();
+        record
+            .os_string("path", stat.path.as_os_str())
+            .unsigned("size", self.size_in_bytes(stat))
+            .unsigned("inodes", stat.inodes)
+            .time("time", time)
+            .bool("total", false);
+        record
+    }
+
+    fn print_stat(&self, out: &mut impl OsWrite, stat: &Stat, size: u64) -> UResult<()> {
+        write!(out, "{}\t", self.convert_size(size))?;
 
         if let Some(md_time) = &self.time {
             if let Some(time) = metadata_get_time(&stat.metadata, *md_time) {
                 format_system_time(
-                    &mut stdout(),
+                    out,
                     time,
                     &self.time_format,
                     FormatSystemTimeFallback::IntegerError,
                 )?;
-                print!("\t");
+                write!(out, "\t")?;
             } else {
-                print!("???\t");
+                write!(out, "???\t")?;
             }
         }
 
-        print_verbatim(&stat.path).unwrap();
-        print!("{}", self.line_ending);
+        out.write_all_os(stat.path.as_os_str())?;
+        write!(out, "{}", self.line_ending)?;
 
         Ok(())
     }
@@ -942,8 +1106,8 @@ fn read_files_from(file_name: &OsStr) -> Result<Vec<PathBuf>, std::io::Error> {
 
     let mut paths = Vec::new();
 
-    for (i, line) in reader.split(b'\0').enumerate() {
-        let path = line?;
+    for (i, path) in files0(reader).enumerate() {
+        let path = path?;
 
         if path.is_empty() {
             let line_number = i + 1;
@@ -951,12 +1115,12 @@ fn read_files_from(file_name: &OsStr) -> Result<Vec<PathBuf>, std::io::Error> {
                 "{}",
                 translate!("du-error-invalid-zero-length-file-name", "file" => file_name.maybe_quote(), "line" => line_number)
             );
-            set_exit_code(1);
-        } else if path == b"-" && file_name == "-" {
+            set_exit_code(ExitCodePolicy::DU.operand);
+        } else if path == "-" && file_name == "-" {
             show_error!("{}", translate!("du-error-hyphen-file-name-not-allowed"));
-            set_exit_code(1);
+            set_exit_code(ExitCodePolicy::DU.operand);
         } else {
-            let p = PathBuf::from(&*uucore::os_str_from_bytes(&path).unwrap());
+            let p = PathBuf::from(path);
             if !paths.contains(&p) {
                 paths.push(p);
             }
@@ -1026,7 +1190,11 @@ fn parse_size_format(matches: &ArgMatches) -> UResult<SizeFormat> {
 #[uucore::main]
 #[allow(clippy::cognitive_complexity)]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
-    let matches = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
+    let matches = uucore::clap_localization::handle_clap_result_with_exit_code(
+        uu_app(),
+        args,
+        ExitCodePolicy::DU.usage,
+    )?;
 
     let summarize = matches.get_flag(options::SUMMARIZE);
 
@@ -1109,7 +1277,10 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
             .get_one::<String>(options::THRESHOLD)
             .map(|s| {
                 Threshold::from_str(s).map_err(|e| {
-                    USimpleError::new(1, format_error_message(&e, s, options::THRESHOLD))
+                    USimpleError::new(
+                        ExitCodePolicy::DU.usage,
+                        format_error_message(&e, s, options::THRESHOLD),
+                    )
                 })
             })
             .transpose()?,
@@ -1118,6 +1289,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         time_format,
         line_ending: LineEnding::from_zero_flag(matches.get_flag(options::NULL)),
         total_text: translate!("du-total"),
+        json: matches.get_flag(options::JSON),
     };
 
     if stat_printer.inodes
@@ -1131,6 +1303,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
 
     // Use separate thread to print output, so we can print finished results while computation is still running
     let (print_tx, rx) = mpsc::channel::<UResult<StatPrintInfo>>();
+    #[cfg(not(target_family = "wasm"))]
     let printing_thread = thread::spawn(move || stat_printer.print_stats(&rx));
 
     // Check existence of path provided in argument
@@ -1138,19 +1311,17 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
 
     'loop_file: for path in files {
         // Skip if we don't want to ignore anything
-        if !&traversal_options.excludes.is_empty() {
+        if !traversal_options.excludes.is_empty() {
             let path_string = path.to_string_lossy();
-            for pattern in &traversal_options.excludes {
-                if pattern.matches(&path_string) {
-                    // if the directory is ignored, leave early
-                    if traversal_options.verbose {
-                        println!(
-                            "{}",
-                            translate!("du-verbose-ignored", "path" => path_string.quote())
-                        );
-                    }
-                    continue 'loop_file;
+            if traversal_options.excludes.matches(&path_string) {
+                // if the directory is ignored, leave early
+                if traversal_options.verbose {
+                    println!(
+                        "{}",
+                        translate!("du-verbose-ignored", "path" => path_string.quote())
+                    );
                 }
+                continue 'loop_file;
             }
         }
 
@@ -1182,7 +1353,11 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
                     &mut seen_inodes,
                     &print_tx,
                     None,
-                    Some(stat),
+                    Some(Prefetched {
+                        stat,
+                        dir: None,
+                        _slot: None,
+                    }),
                 ) {
                     Ok(stat) => {
                         print_tx
@@ -1226,7 +1401,10 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
                     translate!("du-error-cannot-access-no-such-file", "path" => path.quote());
 
                 print_tx
-                    .send(Err(USimpleError::new(1, error_msg)))
+                    .send(Err(USimpleError::new(
+                        ExitCodePolicy::DU.operand,
+                        error_msg,
+                    )))
                     .map_err(|e| USimpleError::new(1, e.to_string()))?;
             }
         }
@@ -1234,13 +1412,27 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
 
     drop(print_tx);
 
-    printing_thread
+    #[cfg(not(target_family = "wasm"))]
+    let printed = printing_thread
         .join()
-        .map_err(|_| USimpleError::new(1, translate!("du-error-printing-thread-panicked")))??;
+        .map_err(|_| USimpleError::new(1, translate!("du-error-printing-thread-panicked")))?;
+    // There are no threads on WASM, so everything is printed once the traversal is done.
+    #[cfg(target_family = "wasm")]
+    let printed = stat_printer.print_stats(&rx);
+    printed.map_err(|e| ExitCodePolicy::DU.classify_io_error(FailureClass::Other, e))?;
 
     Ok(())
 }
 
+/// Only the modification time is known on WASM.
+#[cfg(target_family = "wasm")]
+fn metadata_get_time(md: &Metadata, md_time: MetadataTimeField) -> Option<SystemTime> {
+    match md_time {
+        MetadataTimeField::Modification => md.modified,
+        _ => None,
+    }
+}
+
 // Parse --time-style argument, falling back to environment variable if necessary.
 fn parse_time_style(s: Option<&String>) -> UResult<String> {
     let s = match s {
@@ -1337,6 +1529,12 @@ pub fn uu_app() -> Command {
                 .help(translate!("du-help-total"))
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(options::JSON)
+                .long(options::JSON)
+                .help(translate!("du-help-json"))
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(options::MAX_DEPTH)
                 .short('d')
@@ -1465,6 +1663,18 @@ pub fn uu_app() -> Command {
                 .help(translate!("du-help-exclude-from"))
                 .action(ArgAction::Append),
         )
+        .arg(
+            Arg::new(options::EXCLUDE_IGNORE_CASE)
+                .long(options::EXCLUDE_IGNORE_CASE)
+                .help(translate!("du-help-exclude-ignore-case"))
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::EXCLUDE_GLOBSTAR)
+                .long(options::EXCLUDE_GLOBSTAR)
+                .help(translate!("du-help-exclude-globstar"))
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(options::FILES0_FROM)
                 .long("files0-from")
@@ -0,0 +1,194 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+// spell-checker:ignore EMFILE
+
+//! Reading directories ahead of `safe_du` on several threads.
+//!
+//! Most of the time `du` spends on a large tree goes into reading the
+//! directories and the `lstat` call made for each entry. These do not depend
+//! on each other, so with a pool of more than one thread [`list_dir`] stat's
+//! the entries of a directory in parallel, and [`read_ahead`] reads the next
+//! [`READ_AHEAD`] subdirectories of the directory being summed up while
+//! `safe_du` is still to get to them.
+//!
+//! `safe_du` goes through the entries in the order of a sequential walk, so
+//! hard links are still counted once, where they are met first, and the
+//! sizes, the output and the error messages come out just as they would
+//! without the pool. Only one level is read ahead: a subdirectory reads its
+//! own subdirectories once `safe_du` gets to it, which bounds what is held in
+//! memory and the file descriptors kept open to the depth of the walk.
+//!
+//! The directories read ahead are kept open until `safe_du` gets to them, and
+//! at most half of the file descriptors the process may open are used for
+//! that, so that the walk itself never runs out of them. Past that, or when
+//! the file descriptors run out anyway, a subdirectory is left unread, and
+//! `safe_du` reads it in order instead.
+
+use rayon::prelude::*;
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use uucore::libc;
+use uucore::safe_traversal::{DirFd, SymlinkBehavior};
+
+use super::{FileInfo, Stat, TraversalOptions, is_dir, stat_in_parent};
+
+/// An entry of a directory, with the result of its `lstat`.
+pub(crate) struct ListedEntry {
+    pub(crate) name: OsString,
+    pub(crate) lstat: io::Result<libc::stat>,
+    /// The subdirectory itself, if it was read ahead.
+    pub(crate) subdir: Option<Prefetched>,
+}
+
+/// What was found about a directory before `safe_du` got to it.
+pub(crate) struct Prefetched {
+    pub(crate) stat: io::Result<Stat>,
+    /// The directory, kept open to open its subdirectories, and its
+    /// entries, or the error from opening or reading it. `None` if it is
+    /// still to be read.
+    pub(crate) dir: Option<io::Result<(DirFd, Vec<ListedEntry>)>>,
+    /// Counts the directory against [`fd_budget`] until `safe_du` gets to it.
+    pub(crate) _slot: Option<FdSlot>,
+}
+
+/// How many directories read ahead are open and yet to be reached.
+static OPEN_AHEAD: AtomicUsize = AtomicUsize::new(0);
+
+/// How many directories may be open ahead at once: half the limit on open
+/// file descriptors.
+fn fd_budget() -> usize {
+    static BUDGET: OnceLock<usize> = OnceLock::new();
+    *BUDGET.get_or_init(|| {
+        let mut limit = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        // SAFETY: `limit` is a valid rlimit for getrlimit to fill in.
+        if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &raw mut limit) } != 0 {
+            return 0;
+        }
+        usize::try_from(limit.rlim_cur / 2).unwrap_or(usize::MAX)
+    })
+}
+
+/// A directory open ahead, counted in [`OPEN_AHEAD`] until dropped.
+pub(crate) struct FdSlot(());
+
+impl FdSlot {
+    fn take() -> Option<Self> {
+        OPEN_AHEAD
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |open| {
+                (open < fd_budget()).then_some(open + 1)
+            })
+            .ok()
+            .map(|_| Self(()))
+    }
+}
+
+impl Drop for FdSlot {
+    fn drop(&mut self) {
+        OPEN_AHEAD.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// How many subdirectories [`read_ahead`] reads at once.
+const READ_AHEAD: usize = 32;
+
+/// Read the entries of the directory open as `dir_fd`, stat'ing them in
+/// parallel with a pool of several threads.
+pub(crate) fn list_dir(dir_fd: &DirFd) -> io::Result<Vec<ListedEntry>> {
+    let names = dir_fd.read_dir()?;
+    let list = |name: OsString| ListedEntry {
+        lstat: dir_fd.stat_at(&name, SymlinkBehavior::NoFollow),
+        name,
+        subdir: None,
+    };
+    if rayon::current_num_threads() < 2 {
+        Ok(names.into_iter().map(list).collect())
+    } else {
+        Ok(names.into_par_iter().map(list).collect())
+    }
+}
+
+/// With a pool of several threads, read the first [`READ_AHEAD`]
+/// subdirectories `safe_du` would descend into among `entries` of the
+/// directory open as `dir_fd`, which is at `path`. Returns how many of
+/// `entries` that covers.
+pub(crate) fn read_ahead(
+    dir_fd: &DirFd,
+    path: &Path,
+    dir_inode: Option<FileInfo>,
+    options: &TraversalOptions,
+    entries: &mut [ListedEntry],
+) -> usize {
+    if rayon::current_num_threads() < 2 {
+        return entries.len();
+    }
+
+    let mut subdirs = 0;
+    let covered = entries
+        .iter()
+        .position(|entry| {
+            subdirs += usize::from(will_descend(entry, path, dir_inode, options));
+            subdirs > READ_AHEAD
+        })
+        .unwrap_or(entries.len());
+    entries[..covered]
+        .par_iter_mut()
+        .filter(|entry| entry.subdir.is_none() && will_descend(entry, path, dir_inode, options))
+        .for_each(|entry| {
+            entry.subdir = read_subdir(dir_fd, &path.join(&entry.name), &entry.name);
+        });
+    covered
+}
+
+/// Whether `safe_du` descends into `entry` of the directory at `path`,
+/// save for the hard links it is yet to see.
+fn will_descend(
+    entry: &ListedEntry,
+    path: &Path,
+    dir_inode: Option<FileInfo>,
+    options: &TraversalOptions,
+) -> bool {
+    let Ok(lstat) = &entry.lstat else {
+        return false;
+    };
+    #[allow(clippy::unnecessary_cast)]
+    let other_file_system = options.one_file_system
+        && lstat.st_ino != 0
+        && dir_inode.is_some_and(|inode| inode.dev_id != lstat.st_dev as u64);
+    is_dir(lstat)
+        && !other_file_system
+        && !options
+            .excludes
+            .matches(&path.join(&entry.name).to_string_lossy())
+}
+
+fn read_subdir(parent_fd: &DirFd, path: &Path, name: &OsString) -> Option<Prefetched> {
+    let stat = stat_in_parent(parent_fd, path);
+    let mut slot = None;
+    let dir = match &stat {
+        Ok(stat) if stat.metadata.is_dir() => {
+            slot = Some(FdSlot::take()?);
+            let dir = parent_fd
+                .open_subdir(name, SymlinkBehavior::Follow)
+                .and_then(|dir_fd| list_dir(&dir_fd).map(|entries| (dir_fd, entries)));
+            if matches!(&dir, Err(e) if e.raw_os_error() == Some(libc::EMFILE)) {
+                return None;
+            }
+            Some(dir)
+        }
+        _ => None,
+    };
+    Some(Prefetched {
+        stat,
+        dir,
+        _slot: slot,
+    })
+}
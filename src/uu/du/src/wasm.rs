@@ -0,0 +1,102 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Directory traversal on WASM.
+//!
+//! `std::fs::DirEntry` cannot be constructed by a host, so this walks the
+//! entries reported by [`uucore::wasm_io::read_dir`] instead. Hosts don't
+//! report inodes or devices, so hard links are counted every time they are
+//! seen and `--one-file-system` has no effect.
+
+use rustc_hash::FxHashSet as HashSet;
+use std::sync::mpsc;
+
+use uucore::display::Quotable;
+use uucore::error::{FromIo, UResult};
+use uucore::translate;
+use uucore::wasm_io;
+
+use crate::{FileInfo, Stat, StatPrintInfo, TraversalOptions};
+
+pub(crate) fn du_regular(
+    mut my_stat: Stat,
+    options: &TraversalOptions,
+    depth: usize,
+    seen_inodes: &mut HashSet<FileInfo>,
+    print_tx: &mpsc::Sender<UResult<StatPrintInfo>>,
+    _ancestors: Option<&mut HashSet<FileInfo>>,
+    _symlink_depth: Option<usize>,
+) -> Result<Stat, Box<mpsc::SendError<UResult<StatPrintInfo>>>> {
+    if !my_stat.metadata.is_dir() {
+        return Ok(my_stat);
+    }
+
+    let entries = match wasm_io::read_dir(&my_stat.path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            print_tx.send(Err(e.map_err_context(
+                || translate!("du-error-cannot-read-directory", "path" => my_stat.path.quote()),
+            )))?;
+            return Ok(my_stat);
+        }
+    };
+
+    for entry in entries {
+        let entry_path = my_stat.path.join(&entry.name);
+        let this_stat = match Stat::new(&entry_path, None, options) {
+            Ok(this_stat) => this_stat,
+            Err(e) => {
+                print_tx.send(Err(e.map_err_context(
+                    || translate!("du-error-cannot-access", "path" => entry_path.quote()),
+                )))?;
+                continue;
+            }
+        };
+
+        if options.excludes.matches(&this_stat.path.to_string_lossy()) {
+            if options.verbose {
+                println!(
+                    "{}",
+                    translate!("du-verbose-ignored", "path" => this_stat.path.quote())
+                );
+            }
+            continue;
+        }
+
+        if this_stat.metadata.is_dir() {
+            let this_stat = du_regular(
+                this_stat,
+                options,
+                depth + 1,
+                seen_inodes,
+                print_tx,
+                None,
+                None,
+            )?;
+
+            if !options.separate_dirs {
+                my_stat.size += this_stat.size;
+                my_stat.blocks += this_stat.blocks;
+                my_stat.inodes += this_stat.inodes;
+            }
+            print_tx.send(Ok(StatPrintInfo {
+                stat: this_stat,
+                depth: depth + 1,
+            }))?;
+        } else {
+            my_stat.size += this_stat.size;
+            my_stat.blocks += this_stat.blocks;
+            my_stat.inodes += 1;
+            if options.all {
+                print_tx.send(Ok(StatPrintInfo {
+                    stat: this_stat,
+                    depth: depth + 1,
+                }))?;
+            }
+        }
+    }
+
+    Ok(my_stat)
+}
@@ -22,3 +22,4 @@ echo-help-enable-escapes = enable interpretation of backslash escapes
 echo-help-disable-escapes = disable interpretation of backslash escapes (default)
 
 echo-error-non-utf8 = Non-UTF-8 arguments provided, but this platform does not support them
+echo-error-write-error = write error
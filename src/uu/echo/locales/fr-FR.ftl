@@ -22,3 +22,4 @@ echo-help-enable-escapes = activer l'interprétation des séquences d'échappeme
 echo-help-disable-escapes = désactiver l'interprétation des séquences d'échappement (par défaut)
 
 echo-error-non-utf8 = Arguments non-UTF-8 fournis, mais cette plateforme ne les prend pas en charge
+echo-error-write-error = erreur d'écriture
@@ -7,13 +7,14 @@ use clap::builder::ValueParser;
 use clap::{Arg, ArgAction, Command};
 use std::env;
 use std::ffi::{OsStr, OsString};
-use std::io::Write;
+use std::io::{self, Write};
 #[cfg(not(target_family = "wasm"))]
 use std::io::{StdoutLock, stdout};
+use uucore::clap_localization::print_help_or_version;
+use uucore::error::{FromIo, UError, UResult};
+use uucore::format::{FormatChar, OctalParsing, parse_escape_only};
 #[cfg(target_family = "wasm")]
 use uucore::wasm_io::{WasmStdoutLock as StdoutLock, stdout};
-use uucore::error::UResult;
-use uucore::format::{FormatChar, OctalParsing, parse_escape_only};
 use uucore::{format_usage, os_str_as_bytes};
 
 use uucore::translate;
@@ -159,19 +160,19 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
             (Box::new(args), Options::posixly_correct_default())
         }
     } else if let Some(first_arg) = args.next() {
-        if first_arg == "--help" && args.peek().is_none() {
-            // If POSIXLY_CORRECT is not set and the first argument
-            // is `--help`, GNU coreutils prints the help message.
-            //
-            // Verify this using:
-            //
-            //   POSIXLY_CORRECT=1 echo --help
-            //                     echo --help
-            uu_app().print_help()?;
-            return Ok(());
-        } else if first_arg == "--version" && args.peek().is_none() {
-            write!(stdout(), "{}", uu_app().render_version())?;
-            return Ok(());
+        // If POSIXLY_CORRECT is not set and the only argument is `--help`
+        // or `--version`, GNU coreutils prints the help message or the
+        // version.
+        //
+        // Verify this using:
+        //
+        //   POSIXLY_CORRECT=1 echo --help
+        //                     echo --help
+        if args.peek().is_none() {
+            if let Some(printed) = print_help_or_version(uu_app(), first_arg.as_encoded_bytes()) {
+                printed?;
+                return Ok(());
+            }
         }
 
         // if POSIXLY_CORRECT is not set we filter the flags normally
@@ -181,7 +182,9 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         (Box::new(args), Options::default())
     };
 
-    execute(&mut stdout().lock(), args, options)?;
+    let mut stdout = stdout().lock();
+    execute(&mut stdout, args, options)?;
+    stdout.flush().map_err(write_error)?;
 
     Ok(())
 }
@@ -238,23 +241,29 @@ fn execute(
 
         // Don't print a space before the first argument
         if i > 0 {
-            stdout.write_all(b" ")?;
+            stdout.write_all(b" ").map_err(write_error)?;
         }
 
         if options.escape {
             for item in parse_escape_only(bytes, OctalParsing::ThreeDigits) {
-                if item.write(&mut *stdout)?.is_break() {
+                if item.write(&mut *stdout).map_err(write_error)?.is_break() {
                     return Ok(());
                 }
             }
         } else {
-            stdout.write_all(bytes)?;
+            stdout.write_all(bytes).map_err(write_error)?;
         }
     }
 
     if options.trailing_newline {
-        stdout.write_all(b"\n")?;
+        stdout.write_all(b"\n").map_err(write_error)?;
     }
 
     Ok(())
 }
+
+/// Reports a failure to write to stdout as GNU does, e.g.
+/// `echo: write error: No space left on device`.
+fn write_error(error: io::Error) -> Box<dyn UError> {
+    error.map_err_context(|| translate!("echo-error-write-error"))
+}
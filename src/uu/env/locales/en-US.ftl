@@ -10,7 +10,7 @@ env-help-file = read and set variables from a ".env"-style configuration file (p
 env-help-unset = remove variable from the environment
 env-help-debug = print verbose information for each processing step
 env-help-split-string = process and split S into separate arguments; used to pass multiple arguments on shebang lines
-env-help-argv0 = Override the zeroth argument passed to the command being executed. Without this option a default value of `command` is used.
+env-help-argv0 = Override the zeroth argument passed to the command being executed. Without this option a default value of `command` is used. Ignored with a warning on Windows.
 env-help-ignore-signal = set handling of SIG signal(s) to do nothing
 env-help-default-signal = reset handling of SIG signal(s) to the default action
 env-help-block-signal = block delivery of SIG signal(s) while running COMMAND
@@ -37,10 +37,10 @@ env-error-cannot-unset = cannot unset '{ $name }': Invalid argument
 env-error-cannot-unset-invalid = cannot unset { $name }: Invalid argument
 env-error-must-specify-command-with-chdir = must specify command with --chdir (-C)
 env-error-cannot-change-directory = cannot change directory to { $directory }: { $error }
-env-error-argv0-not-supported = --argv0 is currently not supported on this platform
 env-error-permission-denied = { $program }: Permission denied
 env-error-unknown = unknown error: { $error }
 env-error-failed-set-signal-action = failed to set signal action for signal { $signal }: { $error }
 
 # Warning messages
+env-warning-argv0-ignored = --argv0 is not supported on this platform, ignoring { $argv0 }
 env-warning-no-name-specified = no name specified for value { $value }
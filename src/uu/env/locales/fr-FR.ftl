@@ -10,7 +10,7 @@ env-help-file = lire et définir les variables à partir d'un fichier de configu
 env-help-unset = supprimer la variable de l'environnement
 env-help-debug = afficher des informations détaillées pour chaque étape de traitement
 env-help-split-string = traiter et diviser S en arguments séparés ; utilisé pour passer plusieurs arguments sur les lignes shebang
-env-help-argv0 = Remplacer le zéroième argument passé à la commande en cours d'exécution. Sans cette option, une valeur par défaut de `command` est utilisée.
+env-help-argv0 = Remplacer le zéroième argument passé à la commande en cours d'exécution. Sans cette option, une valeur par défaut de `command` est utilisée. Ignoré avec un avertissement sous Windows.
 env-help-ignore-signal = définir la gestion du/des signal/signaux SIG pour ne rien faire
 env-help-default-signal = réinitialiser la gestion du/des signal/signaux SIG à l'action par défaut
 env-help-block-signal = bloquer la livraison du/des signal/signaux SIG pendant l'exécution de COMMAND
@@ -38,10 +38,10 @@ env-error-cannot-unset = impossible de supprimer '{ $name }' : Argument invalide
 env-error-cannot-unset-invalid = impossible de supprimer { $name } : Argument invalide
 env-error-must-specify-command-with-chdir = doit spécifier une commande avec --chdir (-C)
 env-error-cannot-change-directory = impossible de changer de répertoire vers { $directory } : { $error }
-env-error-argv0-not-supported = --argv0 n'est actuellement pas supporté sur cette plateforme
 env-error-permission-denied = { $program } : Permission refusée
 env-error-unknown = erreur inconnue : { $error }
 env-error-failed-set-signal-action = échec de la définition de l'action du signal pour le signal { $signal } : { $error }
 
 # Messages d'avertissement
+env-warning-argv0-ignored = --argv0 n'est pas supporté sur cette plateforme, { $argv0 } est ignoré
 env-warning-no-name-specified = aucun nom spécifié pour la valeur { $value }
@@ -29,7 +29,6 @@ use nix::unistd::execvp;
 use std::borrow::Cow;
 #[cfg(unix)]
 use std::collections::{BTreeMap, BTreeSet};
-use std::env;
 #[cfg(unix)]
 use std::ffi::CString;
 use std::ffi::{OsStr, OsString};
@@ -40,6 +39,7 @@ use std::io::stderr;
 use std::os::unix::ffi::OsStrExt;
 
 use uucore::display::{Quotable, print_all_env_vars};
+use uucore::env;
 use uucore::error::{ExitCode, UError, UResult, USimpleError, UUsageError};
 use uucore::line_ending::LineEnding;
 #[cfg(unix)]
@@ -659,13 +659,14 @@ impl EnvAppData {
                         // Use ErrorFormatter directly to handle error with shebang message callback
                         let formatter =
                             uucore::clap_localization::ErrorFormatter::new(uucore::util_name());
-                        formatter.print_error_and_exit_with_callback(&e, 125, || {
+                        let code = formatter.print_error_with_callback(&e, 125, || {
                             eprintln!(
                                 "{}: {}",
                                 uucore::util_name(),
                                 translate!("env-error-use-s-shebang")
                             );
                         });
+                        return Err(ExitCode::new(code));
                     }
                 }
             }
@@ -689,7 +690,7 @@ impl EnvAppData {
 
         let mut opts = make_options(&matches)?;
 
-        apply_change_directory(&opts)?;
+        apply_change_directory(&opts, self.do_debug_printing)?;
 
         // NOTE: we manually set and unset the env vars below rather than using Command::env() to more
         //       easily handle the case where no command is given
@@ -764,11 +765,15 @@ impl EnvAppData {
                 }
                 arg0
             }
-            Some(_) => {
-                return Err(USimpleError::new(
-                    2,
-                    translate!("env-error-argv0-not-supported"),
-                ));
+            Some(argv0) => {
+                // The command line of a process starts with its program and
+                // there is no separate zeroth argument to override, so run the
+                // program under its own name rather than not at all.
+                show_warning!(
+                    "{}",
+                    translate!("env-warning-argv0-ignored", "argv0" => argv0.quote())
+                );
+                prog.clone()
             }
         };
 
@@ -978,7 +983,10 @@ fn apply_unset_env_vars(opts: &Options<'_>) -> Result<(), Box<dyn UError>> {
     Ok(())
 }
 
-fn apply_change_directory(opts: &Options<'_>) -> Result<(), Box<dyn UError>> {
+fn apply_change_directory(
+    opts: &Options<'_>,
+    do_debug_printing: bool,
+) -> Result<(), Box<dyn UError>> {
     // GNU env tests this behavior
     if opts.program.is_empty() && opts.running_directory.is_some() {
         return Err(UUsageError::new(
@@ -988,7 +996,10 @@ fn apply_change_directory(opts: &Options<'_>) -> Result<(), Box<dyn UError>> {
     }
 
     if let Some(d) = opts.running_directory {
-        match env::set_current_dir(d) {
+        if do_debug_printing {
+            let _ = writeln!(stderr(), "chdir:    {}", d.quote());
+        }
+        match std::env::set_current_dir(d) {
             Ok(()) => d,
             Err(error) => {
                 return Err(USimpleError::new(
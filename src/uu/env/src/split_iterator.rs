@@ -102,7 +102,7 @@ impl<'a> SplitIterator<'a> {
         let (name, default) = var_parse.parse_variable()?;
 
         let varname_os_str_cow = from_native_int_representation(Cow::Borrowed(name));
-        let value = std::env::var_os(varname_os_str_cow);
+        let value = uucore::env::var_os(varname_os_str_cow);
         match (&value, default) {
             (None, None) => {} // do nothing, just replace it with ""
             (Some(value), _) => {
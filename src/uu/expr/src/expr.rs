@@ -7,6 +7,7 @@ use clap::{Arg, ArgAction, Command};
 use std::io::Write;
 use syntax_tree::{AstNode, is_truthy};
 use thiserror::Error;
+use uucore::clap_localization::print_help_or_version;
 use uucore::os_string_to_vec;
 use uucore::translate;
 use uucore::{
@@ -108,10 +109,11 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         .map(os_string_to_vec)
         .collect::<Result<Vec<_>, _>>()?;
 
-    if args.len() == 1 && args[0] == b"--help" {
-        let _ = uu_app().print_help();
-    } else if args.len() == 1 && args[0] == b"--version" {
-        println!("{} {}", uucore::util_name(), uucore::crate_version!());
+    if let [arg] = &args[..]
+        && let Some(printed) = print_help_or_version(uu_app(), arg)
+    {
+        // Like before, failing to print the help or the version is not an error.
+        let _ = printed;
     } else {
         // The first argument may be "--" and should be be ignored.
         let args = if !args.is_empty() && args[0] == b"--" {
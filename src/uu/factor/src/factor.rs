@@ -7,7 +7,9 @@
 
 use std::collections::BTreeMap;
 use std::io::BufRead;
-use std::io::{self, Write, stdin, stdout};
+use std::io::{self, Write};
+#[cfg(not(target_family = "wasm"))]
+use std::io::{stdin, stdout};
 
 use clap::{Arg, ArgAction, Command};
 use num_bigint::BigUint;
@@ -15,6 +17,8 @@ use num_traits::FromPrimitive;
 use uucore::display::Quotable;
 use uucore::error::{FromIo, UResult, USimpleError, set_exit_code};
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{stdin, stdout};
 use uucore::{format_usage, show_error, show_warning};
 
 mod options {
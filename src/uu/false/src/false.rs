@@ -4,6 +4,7 @@
 // file that was distributed with this source code.
 use clap::{Arg, ArgAction, Command};
 use std::{ffi::OsString, io::Write};
+use uucore::clap_localization::print_help_or_version;
 use uucore::error::{UResult, set_exit_code};
 
 use uucore::translate;
@@ -24,13 +25,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     }
 
     // args[0] is the name of the binary.
-    let error = if args[1] == "--help" {
-        uu_app().print_help()
-    } else if args[1] == "--version" {
-        write!(std::io::stdout(), "{}", uu_app().render_version())
-    } else {
-        Ok(())
-    };
+    let error = print_help_or_version(uu_app(), args[1].as_encoded_bytes()).unwrap_or(Ok(()));
 
     if let Err(print_fail) = error {
         let _ = writeln!(std::io::stderr(), "{}: {print_fail}", uucore::util_name());
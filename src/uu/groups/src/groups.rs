@@ -5,7 +5,9 @@
 
 // spell-checker:ignore (ToDO) passwd
 
-use std::io::{Write, stdout};
+use std::io::Write;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use thiserror::Error;
 use uucore::{
     display::Quotable,
@@ -16,6 +18,8 @@ use uucore::{
 
 use clap::{Arg, ArgAction, Command};
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::stdout;
 
 mod options {
     pub const USERS: &str = "USERNAME";
@@ -55,6 +59,11 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         .unwrap_or_default();
 
     if users.is_empty() {
+        // Without identity hooks there is no current user to report on.
+        #[cfg(target_family = "wasm")]
+        if uucore::wasm_io::uid().is_none() {
+            return Err(GroupsError::GetGroupsFailed.into());
+        }
         let Ok(gids) = get_groups_gnu(None) else {
             return Err(GroupsError::GetGroupsFailed.into());
         };
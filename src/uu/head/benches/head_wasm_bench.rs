@@ -0,0 +1,51 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Output-heavy `head` runs through the WASM I/O layer, with output handed to
+//! the host on every write (`0`) and with the default output buffer. Only
+//! built for WASM targets, e.g.
+//! `cargo bench -p uu_head --target wasm32-wasip2` with a WASI runner set up.
+
+#[cfg(target_family = "wasm")]
+mod wasm {
+    use divan::{Bencher, black_box};
+    use uu_head::uumain;
+    use uucore::benchmark::{run_util_function_wasm_io, text_data};
+    use uucore::wasm_io::DEFAULT_OUTPUT_BUFFER_SIZE;
+
+    /// Benchmark printing most of the input by line count
+    #[divan::bench(args = [0, DEFAULT_OUTPUT_BUFFER_SIZE])]
+    fn head_lines(bencher: Bencher, buffer_size: usize) {
+        let data = text_data::generate_by_lines(100_000, 80).leak();
+
+        bencher.bench(|| {
+            black_box(run_util_function_wasm_io(
+                uumain,
+                &["-n", "90000"],
+                data,
+                buffer_size,
+            ));
+        });
+    }
+
+    /// Benchmark printing all but the last lines, which buffers the tail
+    #[divan::bench(args = [0, DEFAULT_OUTPUT_BUFFER_SIZE])]
+    fn head_all_but_last_lines(bencher: Bencher, buffer_size: usize) {
+        let data = text_data::generate_by_lines(100_000, 80).leak();
+
+        bencher.bench(|| {
+            black_box(run_util_function_wasm_io(
+                uumain,
+                &["-n", "-1000"],
+                data,
+                buffer_size,
+            ));
+        });
+    }
+}
+
+fn main() {
+    divan::main();
+}
@@ -8,6 +8,7 @@
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use memchr::memrchr_iter;
 use std::ffi::OsString;
+#[cfg(not(target_family = "wasm"))]
 use std::fs::File;
 use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
 use std::num::TryFromIntError;
@@ -22,8 +23,8 @@ fn stdout() -> uucore::wasm_io::WasmStdout {
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn stdout() -> std::io::Stdout {
-    std::io::stdout()
+fn stdout() -> io::Stdout {
+    io::stdout()
 }
 
 #[cfg(target_family = "wasm")]
@@ -32,11 +33,12 @@ fn stdin() -> uucore::wasm_io::WasmStdin {
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn stdin() -> std::io::Stdin {
-    std::io::stdin()
+fn stdin() -> io::Stdin {
+    io::stdin()
 }
-use uucore::display::{Quotable, print_verbatim};
-use uucore::error::{FromIo, UError, UResult};
+use uucore::display::{HeaderPrinter, Quotable};
+use uucore::error::{FromIo, UError, UResult, strip_errno};
+use uucore::io::{TagWriteErrors, is_write_error, tag_write_error};
 use uucore::line_ending::LineEnding;
 use uucore::translate;
 use uucore::{format_usage, show};
@@ -62,9 +64,12 @@ use take::take_lines;
 #[derive(Error, Debug)]
 enum HeadError {
     /// Wrapper around `io::Error`
-    #[error("{}", translate!("head-error-reading-file", "name" => name.quote(), "err" => err))]
+    #[error("{}", translate!("head-error-reading-file", "name" => name.quote(), "err" => strip_errno(err)))]
     Io { name: PathBuf, err: io::Error },
 
+    #[error("{}", translate!("head-error-writing-stdout", "err" => strip_errno(.0)))]
+    Write(io::Error),
+
     #[error("{}", translate!("head-error-parse-error", "err" => 0))]
     ParseError(String),
 
@@ -249,12 +254,10 @@ impl HeadOptions {
     }
 }
 
-#[inline]
-fn wrap_in_stdout_error(err: io::Error) -> io::Error {
-    io::Error::new(
-        err.kind(),
-        translate!("head-error-writing-stdout", "err" => err),
-    )
+/// Standard output, tagging its errors so that a failed copy is not blamed
+/// on the input
+fn lock_stdout() -> TagWriteErrors<impl Write> {
+    TagWriteErrors(stdout().lock())
 }
 
 fn read_n_bytes(input: impl Read, n: u64) -> io::Result<u64> {
@@ -262,15 +265,14 @@ fn read_n_bytes(input: impl Read, n: u64) -> io::Result<u64> {
     let mut reader = input.take(n);
 
     // Write those bytes to `stdout`.
-    let stdout = stdout();
-    let mut stdout = stdout.lock();
+    let mut stdout = lock_stdout();
 
-    let bytes_written = io::copy(&mut reader, &mut stdout).map_err(wrap_in_stdout_error)?;
+    let bytes_written = io::copy(&mut reader, &mut stdout)?;
 
     // Make sure we finish writing everything to the target before
     // exiting. Otherwise, when Rust is implicitly flushing, any
     // error will be silently ignored.
-    stdout.flush().map_err(wrap_in_stdout_error)?;
+    stdout.flush()?;
 
     Ok(bytes_written)
 }
@@ -280,16 +282,14 @@ fn read_n_lines(input: &mut impl io::BufRead, n: u64, separator: u8) -> io::Resu
     let mut reader = take_lines(input, n, separator);
 
     // Write those bytes to `stdout`.
-    let stdout = stdout();
-    let stdout = stdout.lock();
-    let mut writer = BufWriter::with_capacity(BUF_SIZE, stdout);
+    let mut writer = BufWriter::with_capacity(BUF_SIZE, lock_stdout());
 
-    let bytes_written = io::copy(&mut reader, &mut writer).map_err(wrap_in_stdout_error)?;
+    let bytes_written = io::copy(&mut reader, &mut writer)?;
 
     // Make sure we finish writing everything to the target before
     // exiting. Otherwise, when Rust is implicitly flushing, any
     // error will be silently ignored.
-    writer.flush().map_err(wrap_in_stdout_error)?;
+    writer.flush()?;
 
     Ok(bytes_written)
 }
@@ -301,38 +301,34 @@ fn catch_too_large_numbers_in_backwards_bytes_or_lines(n: u64) -> Option<usize>
 fn read_but_last_n_bytes(mut input: impl Read, n: u64) -> io::Result<u64> {
     let mut bytes_written: u64 = 0;
     if let Some(n) = catch_too_large_numbers_in_backwards_bytes_or_lines(n) {
-        let stdout = stdout();
-        let mut stdout = stdout.lock();
+        let mut stdout = lock_stdout();
 
-        bytes_written = copy_all_but_n_bytes(&mut input, &mut stdout, n)
-            .map_err(wrap_in_stdout_error)?
+        bytes_written = copy_all_but_n_bytes(&mut input, &mut stdout, n)?
             .try_into()
             .unwrap();
 
         // Make sure we finish writing everything to the target before
         // exiting. Otherwise, when Rust is implicitly flushing, any
         // error will be silently ignored.
-        stdout.flush().map_err(wrap_in_stdout_error)?;
+        stdout.flush()?;
     }
     Ok(bytes_written)
 }
 
 fn read_but_last_n_lines(mut input: impl Read, n: u64, separator: u8) -> io::Result<u64> {
-    let stdout = stdout();
-    let mut stdout = stdout.lock();
+    let mut stdout = lock_stdout();
     if n == 0 {
-        return io::copy(&mut input, &mut stdout).map_err(wrap_in_stdout_error);
+        return io::copy(&mut input, &mut stdout);
     }
     let mut bytes_written: u64 = 0;
     if let Some(n) = catch_too_large_numbers_in_backwards_bytes_or_lines(n) {
-        bytes_written = copy_all_but_n_lines(input, &mut stdout, n, separator)
-            .map_err(wrap_in_stdout_error)?
+        bytes_written = copy_all_but_n_lines(input, &mut stdout, n, separator)?
             .try_into()
             .unwrap();
         // Make sure we finish writing everything to the target before
         // exiting. Otherwise, when Rust is implicitly flushing, any
         // error will be silently ignored.
-        stdout.flush().map_err(wrap_in_stdout_error)?;
+        stdout.flush()?;
     }
     Ok(bytes_written)
 }
@@ -428,6 +424,7 @@ where
     }
 }
 
+#[cfg(not(target_family = "wasm"))]
 fn is_seekable(input: &mut File) -> bool {
     let current_pos = input.stream_position();
     current_pos.is_ok()
@@ -435,6 +432,7 @@ fn is_seekable(input: &mut File) -> bool {
         && input.seek(SeekFrom::Start(current_pos.unwrap())).is_ok()
 }
 
+#[cfg(not(target_family = "wasm"))]
 fn head_backwards_file(input: &mut File, options: &HeadOptions) -> io::Result<u64> {
     let st = input.metadata()?;
     let seekable = is_seekable(input);
@@ -446,6 +444,7 @@ fn head_backwards_file(input: &mut File, options: &HeadOptions) -> io::Result<u6
     }
 }
 
+#[cfg(not(target_family = "wasm"))]
 fn head_backwards_without_seek_file(input: &mut File, options: &HeadOptions) -> io::Result<u64> {
     match options.mode {
         Mode::AllButLastBytes(n) => read_but_last_n_bytes(input, n),
@@ -454,6 +453,7 @@ fn head_backwards_without_seek_file(input: &mut File, options: &HeadOptions) ->
     }
 }
 
+#[cfg(not(target_family = "wasm"))]
 fn head_backwards_on_seekable_file(input: &mut File, options: &HeadOptions) -> io::Result<u64> {
     match options.mode {
         Mode::AllButLastBytes(n) => {
@@ -472,6 +472,35 @@ fn head_backwards_on_seekable_file(input: &mut File, options: &HeadOptions) -> i
     }
 }
 
+/// A file opened through the WASM VFS hooks.
+#[cfg(target_family = "wasm")]
+enum VfsFile {
+    Stream(Box<dyn Read>),
+    Seekable(Box<dyn uucore::wasm_io::ReadSeek>),
+}
+
+/// Like [`head_backwards_on_seekable_file`], for VFS files, which have no
+/// metadata to take the size from.
+#[cfg(target_family = "wasm")]
+fn head_backwards_on_seekable_reader(
+    input: &mut (impl Read + Seek),
+    options: &HeadOptions,
+) -> io::Result<u64> {
+    match options.mode {
+        Mode::AllButLastBytes(n) => {
+            let size = input.seek(SeekFrom::End(0))?;
+            input.rewind()?;
+            read_n_bytes(input, size.saturating_sub(n))
+        }
+        Mode::AllButLastLines(n) => {
+            let found = find_nth_line_from_end(input, n, options.line_ending.into())?;
+            read_n_bytes(input, found)
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
 fn head_file(input: &mut File, options: &HeadOptions) -> io::Result<u64> {
     match options.mode {
         Mode::FirstBytes(n) => read_n_bytes(input, n),
@@ -484,119 +513,144 @@ fn head_file(input: &mut File, options: &HeadOptions) -> io::Result<u64> {
     }
 }
 
+/// Print the header and the requested part of `file`. A file that cannot be
+/// opened is reported here; other errors are returned, and those not marked
+/// by [`tag_write_error`] come from reading `file`.
 #[allow(clippy::cognitive_complexity)]
-fn uu_head(options: &HeadOptions) -> UResult<()> {
-    let mut first = true;
-    for file in &options.files {
-        let res = if file == "-" {
-            if (options.files.len() > 1 && !options.quiet) || options.verbose {
-                if !first {
-                    println!();
-                }
-                println!("{}", translate!("head-header-stdin"));
-            }
-            let stdin = stdin();
-
-            #[cfg(unix)]
-            {
-                let stdin_owned_fd = stdin.as_fd().try_clone_to_owned()?;
-                let mut stdin_file = File::from(stdin_owned_fd);
-                let current_pos = stdin_file.stream_position();
-                if let Ok(current_pos) = current_pos {
-                    // We have a seekable file. Ensure we set the input stream to the
-                    // last byte read so that any tools that parse the remainder of
-                    // the stdin stream read from the correct place.
-
-                    let bytes_read = head_file(&mut stdin_file, options)?;
-                    stdin_file.seek(SeekFrom::Start(current_pos + bytes_read))?;
-                } else {
-                    let _bytes_read = head_file(&mut stdin_file, options)?;
-                }
+fn head_operand(
+    file: &OsString,
+    options: &HeadOptions,
+    headers: &mut HeaderPrinter,
+) -> io::Result<()> {
+    if file == "-" {
+        headers
+            .print(translate!("head-stdin-header"))
+            .map_err(tag_write_error)?;
+        let stdin = stdin();
+
+        #[cfg(unix)]
+        {
+            let stdin_owned_fd = stdin.as_fd().try_clone_to_owned()?;
+            let mut stdin_file = File::from(stdin_owned_fd);
+            let current_pos = stdin_file.stream_position();
+            if let Ok(current_pos) = current_pos {
+                // We have a seekable file. Ensure we set the input stream to the
+                // last byte read so that any tools that parse the remainder of
+                // the stdin stream read from the correct place.
+
+                let bytes_read = head_file(&mut stdin_file, options)?;
+                stdin_file.seek(SeekFrom::Start(current_pos + bytes_read))?;
+            } else {
+                let _bytes_read = head_file(&mut stdin_file, options)?;
             }
+        }
 
-            #[cfg(not(unix))]
-            {
-                let mut stdin = stdin.lock();
+        #[cfg(not(unix))]
+        {
+            let mut stdin = stdin.lock();
 
-                match options.mode {
-                    Mode::FirstBytes(n) => read_n_bytes(&mut stdin, n),
-                    Mode::AllButLastBytes(n) => read_but_last_n_bytes(&mut stdin, n),
-                    Mode::FirstLines(n) => read_n_lines(&mut stdin, n, options.line_ending.into()),
-                    Mode::AllButLastLines(n) => {
-                        read_but_last_n_lines(&mut stdin, n, options.line_ending.into())
-                    }
-                }?;
-            }
+            match options.mode {
+                Mode::FirstBytes(n) => read_n_bytes(&mut stdin, n),
+                Mode::AllButLastBytes(n) => read_but_last_n_bytes(&mut stdin, n),
+                Mode::FirstLines(n) => read_n_lines(&mut stdin, n, options.line_ending.into()),
+                Mode::AllButLastLines(n) => {
+                    read_but_last_n_lines(&mut stdin, n, options.line_ending.into())
+                }
+            }?;
+        }
 
-            Ok(())
-        } else {
-            // On WASM, use VFS file hooks since std::fs::File::open returns errors.
-            // VFS files are non-seekable, so use the streaming read path directly.
-            #[cfg(target_family = "wasm")]
-            {
-                let reader = match uucore::wasm_io::open_file(file) {
-                    Ok(r) => r,
-                    Err(err) => {
-                        show!(err.map_err_context(
-                            || translate!("head-error-cannot-open", "name" => file.quote())
-                        ));
-                        continue;
-                    }
-                };
-                if (options.files.len() > 1 && !options.quiet) || options.verbose {
-                    if !first {
-                        println!();
-                    }
-                    print!("==> ");
-                    print_verbatim(file).unwrap();
-                    println!(" <==");
+        Ok(())
+    } else {
+        // On WASM, use VFS file hooks since std::fs::File::open returns errors.
+        // Only the "all but last" modes benefit from seeking; files are
+        // streamed otherwise, or when the host cannot seek them.
+        #[cfg(target_family = "wasm")]
+        {
+            let seekable = !options.presume_input_pipe
+                && matches!(
+                    options.mode,
+                    Mode::AllButLastBytes(_) | Mode::AllButLastLines(_)
+                )
+                && uucore::wasm_io::can_seek_files();
+            let reader = if seekable {
+                uucore::wasm_io::open_file_seekable(file).map(VfsFile::Seekable)
+            } else {
+                uucore::wasm_io::open_file(file).map(VfsFile::Stream)
+            };
+            let reader = match reader {
+                Ok(r) => r,
+                Err(err) => {
+                    show!(err.map_err_context(
+                        || translate!("head-error-cannot-open", "name" => file.quote())
+                    ));
+                    return Ok(());
                 }
-                let mut buf_reader = io::BufReader::with_capacity(BUF_SIZE, reader);
-                match options.mode {
-                    Mode::FirstBytes(n) => read_n_bytes(&mut buf_reader, n),
-                    Mode::FirstLines(n) => {
-                        read_n_lines(&mut buf_reader, n, options.line_ending.into())
-                    }
-                    Mode::AllButLastBytes(n) => read_but_last_n_bytes(&mut buf_reader, n),
-                    Mode::AllButLastLines(n) => {
-                        read_but_last_n_lines(&mut buf_reader, n, options.line_ending.into())
+            };
+            headers.print(file).map_err(tag_write_error)?;
+            match reader {
+                VfsFile::Seekable(mut reader) => {
+                    head_backwards_on_seekable_reader(&mut reader, options)
+                }
+                VfsFile::Stream(reader) => {
+                    let mut buf_reader = io::BufReader::with_capacity(BUF_SIZE, reader);
+                    match options.mode {
+                        Mode::FirstBytes(n) => read_n_bytes(&mut buf_reader, n),
+                        Mode::FirstLines(n) => {
+                            read_n_lines(&mut buf_reader, n, options.line_ending.into())
+                        }
+                        Mode::AllButLastBytes(n) => read_but_last_n_bytes(&mut buf_reader, n),
+                        Mode::AllButLastLines(n) => {
+                            read_but_last_n_lines(&mut buf_reader, n, options.line_ending.into())
+                        }
                     }
-                }?;
-                Ok(())
+                }
+            }?;
+            Ok(())
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            let mut file_handle = match File::open(file) {
+                Ok(f) => f,
+                Err(err) => {
+                    show!(err.map_err_context(
+                        || translate!("head-error-cannot-open", "name" => file.quote())
+                    ));
+                    return Ok(());
+                }
+            };
+            headers.print(file).map_err(tag_write_error)?;
+            // Like GNU, name the directory in a read error and go on
+            // with the next file.
+            if file_handle.metadata().is_ok_and(|m| m.is_dir()) {
+                return Err(file_handle
+                    .read(&mut [0])
+                    .err()
+                    .unwrap_or_else(|| io::ErrorKind::IsADirectory.into()));
             }
+            head_file(&mut file_handle, options)?;
+            Ok(())
+        }
+    }
+}
 
-            #[cfg(not(target_family = "wasm"))]
-            {
-                let mut file_handle = match File::open(file) {
-                    Ok(f) => f,
-                    Err(err) => {
-                        show!(err.map_err_context(
-                            || translate!("head-error-cannot-open", "name" => file.quote())
-                        ));
-                        continue;
-                    }
+fn uu_head(options: &HeadOptions) -> UResult<()> {
+    let mut headers = HeaderPrinter::for_files(options.files.len(), options.quiet, options.verbose);
+    for file in &options.files {
+        // Like GNU, go on with the next file after a read error, but stop
+        // once standard output is gone.
+        match head_operand(file, options, &mut headers) {
+            Ok(()) => {}
+            Err(err) if is_write_error(&err) => return Err(HeadError::Write(err).into()),
+            Err(err) => {
+                let name = if file == "-" {
+                    "standard input".into()
+                } else {
+                    file.into()
                 };
-                if (options.files.len() > 1 && !options.quiet) || options.verbose {
-                    if !first {
-                        println!();
-                    }
-                    print!("==> ");
-                    print_verbatim(file).unwrap();
-                    println!(" <==");
-                }
-                head_file(&mut file_handle, options)?;
-                Ok(())
+                show!(HeadError::Io { name, err });
             }
-        };
-        if let Err(err) = res {
-            let name = if file == "-" {
-                "standard input".into()
-            } else {
-                file.into()
-            };
-            return Err(HeadError::Io { name, err }.into());
         }
-        first = false;
     }
     // Even though this is returning `Ok`, it is possible that a call
     // to `show!()` and thus a call to `set_exit_code()` has been
@@ -23,6 +23,7 @@ id-error-no-such-user = { $user }: no such user
 id-error-cannot-find-group-name = cannot find name for group ID { $gid }
 id-error-cannot-find-user-name = cannot find name for user ID { $uid }
 id-error-audit-retrieve = couldn't retrieve information
+id-error-no-identity = cannot determine the current user
 
 # Help text for command-line arguments
 id-help-ignore = ignore, for compatibility with other versions
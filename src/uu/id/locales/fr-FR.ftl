@@ -23,6 +23,7 @@ id-error-no-such-user = { $user } : utilisateur inexistant
 id-error-cannot-find-group-name = impossible de trouver le nom pour l'ID de groupe { $gid }
 id-error-cannot-find-user-name = impossible de trouver le nom pour l'ID utilisateur { $uid }
 id-error-audit-retrieve = impossible de récupérer les informations
+id-error-no-identity = impossible de déterminer l'utilisateur courant
 
 # Texte d'aide pour les arguments de ligne de commande
 id-help-ignore = ignore, pour compatibilité avec d'autres versions
@@ -35,18 +35,26 @@
 
 use clap::{Arg, ArgAction, Command};
 use std::ffi::CStr;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use std::io::{self, Write};
 use uucore::display::Quotable;
 use uucore::entries::{self, Group, Locate, Passwd};
 use uucore::error::UResult;
 use uucore::error::{USimpleError, set_exit_code};
 pub use uucore::libc;
+#[cfg(not(target_family = "wasm"))]
 use uucore::libc::{getlogin, uid_t};
 use uucore::line_ending::LineEnding;
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::stdout;
 
+#[cfg(not(target_family = "wasm"))]
 use uucore::process::{getegid, geteuid, getgid, getuid};
 use uucore::{format_usage, show_error};
+#[cfg(target_family = "wasm")]
+use wasm::{getegid, geteuid, getgid, getlogin, getuid, uid_t};
 
 macro_rules! cstr2cow {
     ($v:expr) => {
@@ -125,13 +133,19 @@ struct State {
 #[allow(clippy::cognitive_complexity)]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     let matches = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
-    let mut lock = io::stdout().lock();
+    let mut lock = stdout().lock();
 
     let users: Vec<String> = matches
         .get_many::<String>(options::ARG_USERS)
         .map(|v| v.map(ToString::to_string).collect())
         .unwrap_or_default();
 
+    // Without identity hooks there is no current user to report on.
+    #[cfg(target_family = "wasm")]
+    if users.is_empty() && uucore::wasm_io::uid().is_none() {
+        return Err(USimpleError::new(1, translate!("id-error-no-identity")));
+    }
+
     let mut state = State {
         nflag: matches.get_flag(options::OPT_NAME),
         uflag: matches.get_flag(options::OPT_EFFECTIVE_USER),
@@ -472,7 +486,7 @@ pub fn uu_app() -> Command {
 }
 
 fn pretty(possible_pw: Option<Passwd>) -> io::Result<()> {
-    let mut lock = io::stdout().lock();
+    let mut lock = stdout().lock();
 
     if let Some(p) = possible_pw {
         writeln!(
@@ -543,7 +557,7 @@ fn pline(possible_uid: Option<uid_t>) -> io::Result<()> {
     let pw = Passwd::locate(uid)?;
 
     writeln!(
-        io::stdout().lock(),
+        stdout().lock(),
         "{}:{}:{}:{}:{}:{}:{}:{}:{}:{}",
         pw.name,
         pw.user_passwd.unwrap_or_default(),
@@ -562,14 +576,15 @@ fn pline(possible_uid: Option<uid_t>) -> io::Result<()> {
     target_os = "linux",
     target_os = "android",
     target_os = "openbsd",
-    target_os = "cygwin"
+    target_os = "cygwin",
+    target_family = "wasm"
 ))]
 fn pline(possible_uid: Option<uid_t>) -> io::Result<()> {
     let uid = possible_uid.unwrap_or_else(getuid);
     let pw = Passwd::locate(uid)?;
 
     writeln!(
-        io::stdout().lock(),
+        stdout().lock(),
         "{}:{}:{}:{}:{}:{}:{}",
         pw.name,
         pw.user_passwd.unwrap_or_default(),
@@ -586,7 +601,8 @@ fn pline(possible_uid: Option<uid_t>) -> io::Result<()> {
     target_os = "linux",
     target_os = "android",
     target_os = "openbsd",
-    target_os = "cygwin"
+    target_os = "cygwin",
+    target_family = "wasm"
 ))]
 #[allow(clippy::unnecessary_wraps)]
 fn auditid() -> io::Result<()> {
@@ -597,11 +613,12 @@ fn auditid() -> io::Result<()> {
     target_os = "linux",
     target_os = "android",
     target_os = "openbsd",
-    target_os = "cygwin"
+    target_os = "cygwin",
+    target_family = "wasm"
 )))]
 fn auditid() -> io::Result<()> {
     use std::mem::MaybeUninit;
-    let mut lock = io::stdout().lock();
+    let mut lock = stdout().lock();
 
     let mut auditinfo: MaybeUninit<audit::c_auditinfo_addr_t> = MaybeUninit::uninit();
     let address = auditinfo.as_mut_ptr();
@@ -627,7 +644,7 @@ fn id_print(state: &State, groups: &[u32]) -> io::Result<()> {
     let euid = state.ids.as_ref().unwrap().euid;
     let egid = state.ids.as_ref().unwrap().egid;
 
-    let mut lock = io::stdout().lock();
+    let mut lock = stdout().lock();
 
     write!(
         lock,
@@ -728,7 +745,12 @@ fn id_print(state: &State, groups: &[u32]) -> io::Result<()> {
     Ok(())
 }
 
-#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "openbsd")))]
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "openbsd",
+    target_family = "wasm"
+)))]
 mod audit {
     use super::libc::{c_int, c_uint, dev_t, pid_t, uid_t};
 
@@ -767,3 +789,36 @@ mod audit {
         pub fn getaudit(auditinfo_addr: *mut c_auditinfo_addr_t) -> c_int;
     }
 }
+
+/// On WASM there are no processes with credentials: the user running the
+/// builtin, as reported by the host's identity hooks, is both the real and
+/// the effective user.
+#[cfg(target_family = "wasm")]
+mod wasm {
+    use std::ffi::c_char;
+    use std::ptr;
+    use uucore::wasm_io;
+
+    pub type uid_t = u32;
+
+    pub fn getuid() -> uid_t {
+        wasm_io::uid().unwrap_or_default()
+    }
+
+    pub fn geteuid() -> uid_t {
+        getuid()
+    }
+
+    pub fn getgid() -> u32 {
+        wasm_io::gid().unwrap_or_default()
+    }
+
+    pub fn getegid() -> u32 {
+        getgid()
+    }
+
+    /// There is no login session on WASM.
+    pub fn getlogin() -> *mut c_char {
+        ptr::null_mut()
+    }
+}
@@ -20,15 +20,17 @@ install-help-preserve-context = preserve security context
 install-help-context = set security context of files and directories
 install-help-default-context = set SELinux security context of destination file and each created directory to default type
 install-help-unprivileged = do not require elevated privileges to change the owner, the group, or the file flags of the destination
+install-help-sync = flush each installed file and its directory to the storage device before exiting
 
 # Error messages
-install-error-dir-needs-arg = { $util_name } with -d requires at least one argument.
 install-error-create-dir-failed = cannot create directory { $path }
 install-error-chmod-failed = failed to chmod { $path }
 install-error-chmod-failed-detailed = { $path }: chmod failed with error { $error }
 install-error-chown-failed = failed to chown { $path }: { $error }
-install-error-invalid-target = invalid target { $path }: No such file or directory
-install-error-target-not-dir = target { $path } is not a directory
+install-error-invalid-target = cannot create regular file { $path }: No such file or directory
+install-error-create-file-not-dir = cannot create regular file { $path }: Not a directory
+install-error-target-not-dir = target { $path }: Not a directory
+install-error-target = target { $path }
 install-error-backup-failed = cannot backup { $from } to { $to }
 install-error-install-failed = cannot install { $from } to { $to }: { $error }
 install-error-strip-failed = strip program failed: { $error }
@@ -38,12 +40,16 @@ install-error-invalid-user = invalid user: { $user }
 install-error-invalid-group = invalid group: { $group }
 install-error-omitting-directory = omitting directory { $path }
 install-error-not-a-directory = failed to access { $path }: Not a directory
-install-error-override-directory-failed = cannot overwrite directory { $dir } with non-directory { $file }
+install-error-failed-to-access = failed to access { $path }
+install-error-cannot-stat = cannot stat { $path }
+install-error-override-directory-failed = cannot overwrite directory { $dir } with non-directory
 install-error-same-file = { $file1 } and { $file2 } are the same file
 install-error-extra-operand = extra operand { $operand }
-{ $usage }
 install-error-invalid-mode = Invalid mode string: { $error }
-install-error-mutually-exclusive-target = Options --target-directory and --no-target-directory are mutually exclusive
+install-error-mutually-exclusive-target = cannot combine --target-directory (-t) and --no-target-directory (-T)
+install-error-multiple-target-directories = multiple target directories specified
+install-error-target-directory-with-directories = target directory not allowed when installing a directory
+install-error-strip-with-directories = the strip option may not be used when installing a directory
 install-error-mutually-exclusive-compare-preserve = Options --compare and --preserve-timestamps are mutually exclusive
 install-error-mutually-exclusive-compare-strip = Options --compare and --strip are mutually exclusive
 install-error-missing-file-operand = missing file operand
@@ -54,7 +60,6 @@ install-error-failed-to-remove = Failed to remove existing file { $path }. Error
 install-warning-compare-ignored = the --compare (-C) option is ignored when you specify a mode with non-permission bits
 
 # Verbose output
-install-verbose-creating-directory = creating directory { $path }
 install-verbose-creating-directory-step = install: creating directory { $path }
 install-verbose-removed = removed { $path }
 install-verbose-copy = { $from } -> { $to }
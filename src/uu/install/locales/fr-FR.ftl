@@ -20,15 +20,17 @@ install-help-preserve-context = préserver le contexte de sécurité
 install-help-context = définir le contexte de sécurité des fichiers et répertoires
 install-help-default-context = définir le contexte de sécurité SELinux du fichier de destination et de chaque répertoire créé au type par défaut
 install-help-unprivileged = ne pas nécessiter de privilèges élevés pour changer le propriétaire, le groupe ou les attributs du fichier de destination
+install-help-sync = écrire chaque fichier installé et son répertoire sur le périphérique de stockage avant de terminer
 
 # Messages d'erreur
-install-error-dir-needs-arg = { $util_name } avec -d nécessite au moins un argument.
 install-error-create-dir-failed = échec de la création de { $path }
 install-error-chmod-failed = échec du chmod { $path }
 install-error-chmod-failed-detailed = { $path } : échec du chmod avec l'erreur { $error }
 install-error-chown-failed = échec du chown { $path } : { $error }
-install-error-invalid-target = cible invalide { $path } : Aucun fichier ou répertoire de ce type
-install-error-target-not-dir = la cible { $path } n'est pas un répertoire
+install-error-invalid-target = impossible de créer le fichier régulier { $path } : Aucun fichier ou répertoire de ce type
+install-error-create-file-not-dir = impossible de créer le fichier régulier { $path } : N'est pas un répertoire
+install-error-target-not-dir = cible { $path } : N'est pas un répertoire
+install-error-target = cible { $path }
 install-error-backup-failed = impossible de sauvegarder { $from } vers { $to }
 install-error-install-failed = impossible d'installer { $from } vers { $to }: { $error }
 install-error-strip-failed = échec du programme strip : { $error }
@@ -38,12 +40,16 @@ install-error-invalid-user = utilisateur invalide : { $user }
 install-error-invalid-group = groupe invalide : { $group }
 install-error-omitting-directory = omission du répertoire { $path }
 install-error-not-a-directory = échec de l'accès à { $path } : N'est pas un répertoire
-install-error-override-directory-failed = impossible d'écraser le répertoire { $dir } avec un non-répertoire { $file }
+install-error-failed-to-access = échec de l'accès à { $path }
+install-error-cannot-stat = impossible d'évaluer { $path }
+install-error-override-directory-failed = impossible d'écraser le répertoire { $dir } avec un non-répertoire
 install-error-same-file = { $file1 } et { $file2 } sont le même fichier
 install-error-extra-operand = opérande supplémentaire { $operand }
-  { $usage }
 install-error-invalid-mode = Chaîne de mode invalide : { $error }
-install-error-mutually-exclusive-target = Les options --target-directory et --no-target-directory sont mutuellement exclusives
+install-error-mutually-exclusive-target = impossible de combiner --target-directory (-t) et --no-target-directory (-T)
+install-error-multiple-target-directories = plusieurs répertoires cibles spécifiés
+install-error-target-directory-with-directories = répertoire cible non autorisé lors de l'installation d'un répertoire
+install-error-strip-with-directories = l'option strip ne peut pas être utilisée lors de l'installation d'un répertoire
 install-error-mutually-exclusive-compare-preserve = Les options --compare et --preserve-timestamps sont mutuellement exclusives
 install-error-mutually-exclusive-compare-strip = Les options --compare et --strip sont mutuellement exclusives
 install-error-missing-file-operand = opérande de fichier manquant
@@ -54,7 +60,6 @@ install-error-failed-to-remove = Échec de la suppression du fichier existant {
 install-warning-compare-ignored = l'option --compare (-C) est ignorée quand un mode est indiqué avec des bits non liés à des droits
 
 # Sortie détaillée
-install-verbose-creating-directory = création du répertoire { $path }
 install-verbose-creating-directory-step = install : création du répertoire { $path }
 install-verbose-removed = supprimé { $path }
 install-verbose-copy = { $from } -> { $to }
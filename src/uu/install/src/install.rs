@@ -21,10 +21,10 @@ use std::path::{MAIN_SEPARATOR, Path, PathBuf};
 use std::process;
 use thiserror::Error;
 use uucore::backup_control::{self, BackupMode};
-use uucore::buf_copy::copy_stream;
+use uucore::copy::{self, CopyOptions};
 use uucore::display::Quotable;
 use uucore::entries::{grp2gid, usr2uid};
-use uucore::error::{FromIo, UError, UResult, UUsageError};
+use uucore::error::{FromIo, UError, UResult, USimpleError, UUsageError, strip_errno};
 use uucore::fs::dir_strip_dot_for_creation;
 use uucore::perms::{Verbosity, VerbosityLevel, wrap_chown};
 use uucore::process::{getegid, geteuid};
@@ -50,6 +50,7 @@ const DEFAULT_STRIP_PROGRAM: &str = "strip";
 pub struct Behavior {
     main_function: MainFunction,
     specified_mode: Option<u32>,
+    mode_string: Option<String>,
     backup_mode: BackupMode,
     suffix: String,
     owner_id: Option<u32>,
@@ -66,13 +67,11 @@ pub struct Behavior {
     context: Option<String>,
     default_context: bool,
     unprivileged: bool,
+    sync: bool,
 }
 
 #[derive(Error, Debug)]
 enum InstallError {
-    #[error("{}", translate!("install-error-dir-needs-arg", "util_name" => uucore::util_name()))]
-    DirNeedsArg,
-
     #[error("{}", translate!("install-error-create-dir-failed", "path" => .0.quote()))]
     CreateDirFailed(PathBuf, #[source] std::io::Error),
 
@@ -88,6 +87,9 @@ enum InstallError {
     #[error("{}", translate!("install-error-target-not-dir", "path" => .0.quote()))]
     TargetDirIsntDir(PathBuf),
 
+    #[error("{}", translate!("install-error-create-file-not-dir", "path" => .0.quote()))]
+    CreateFileNotADirectory(PathBuf),
+
     #[error("{}", translate!("install-error-backup-failed", "from" => .0.quote(), "to" => .1.quote()))]
     BackupFailed(PathBuf, PathBuf, #[source] std::io::Error),
 
@@ -112,15 +114,12 @@ enum InstallError {
     #[error("{}", translate!("install-error-not-a-directory", "path" => .0.quote()))]
     NotADirectory(PathBuf),
 
-    #[error("{}", translate!("install-error-override-directory-failed", "dir" => .0.quote(), "file" => .1.quote()))]
-    OverrideDirectoryFailed(PathBuf, PathBuf),
+    #[error("{}", translate!("install-error-override-directory-failed", "dir" => .0.quote()))]
+    OverrideDirectoryFailed(PathBuf),
 
     #[error("{}", translate!("install-error-same-file", "file1" => .0.quote(), "file2" => .1.quote()))]
     SameFile(PathBuf, PathBuf),
 
-    #[error("{}", translate!("install-error-extra-operand", "operand" => .0.quote(), "usage" => .1.clone()))]
-    ExtraOperand(OsString, String),
-
     #[cfg(all(feature = "selinux", any(target_os = "linux", target_os = "android")))]
     #[error("{}", .0)]
     SelinuxContextFailed(String),
@@ -168,6 +167,7 @@ static OPT_PRESERVE_CONTEXT: &str = "preserve-context";
 static OPT_CONTEXT: &str = "context";
 static OPT_DEFAULT_CONTEXT: &str = "default-context";
 static OPT_UNPRIVILEGED: &str = "unprivileged";
+static OPT_SYNC: &str = "sync";
 
 static ARG_FILES: &str = "files";
 
@@ -278,7 +278,8 @@ pub fn uu_app() -> Command {
                 .long(OPT_TARGET_DIRECTORY)
                 .help(translate!("install-help-target-directory"))
                 .value_name("DIRECTORY")
-                .value_hint(clap::ValueHint::DirPath),
+                .value_hint(clap::ValueHint::DirPath)
+                .action(ArgAction::Append),
         )
         .arg(
             Arg::new(OPT_NO_TARGET_DIRECTORY)
@@ -329,6 +330,12 @@ pub fn uu_app() -> Command {
                 .help(translate!("install-help-unprivileged"))
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new(OPT_SYNC)
+                .long(OPT_SYNC)
+                .help(translate!("install-help-sync"))
+                .action(ArgAction::SetTrue),
+        )
 }
 
 /// Determine behavior, given command line arguments.
@@ -362,16 +369,33 @@ fn behavior(matches: &ArgMatches) -> UResult<Behavior> {
     };
 
     let backup_mode = backup_control::determine_backup_mode(matches)?;
-    let target_dir = matches.get_one::<String>(OPT_TARGET_DIRECTORY).cloned();
-    let no_target_dir = matches.get_flag(OPT_NO_TARGET_DIRECTORY);
-    if target_dir.is_some() && no_target_dir {
-        show_error!("{}", translate!("install-error-mutually-exclusive-target"));
-        return Err(1.into());
+    let mut target_dirs = matches
+        .get_many::<String>(OPT_TARGET_DIRECTORY)
+        .unwrap_or_default();
+    let target_dir = target_dirs.next().cloned();
+    if target_dirs.next().is_some() {
+        return Err(USimpleError::new(
+            1,
+            translate!("install-error-multiple-target-directories"),
+        ));
     }
+    let no_target_dir = matches.get_flag(OPT_NO_TARGET_DIRECTORY);
 
     let preserve_timestamps = matches.get_flag(OPT_PRESERVE_TIMESTAMPS);
     let compare = matches.get_flag(OPT_COMPARE);
     let strip = matches.get_flag(OPT_STRIP);
+    if considering_dir && strip {
+        return Err(USimpleError::new(
+            1,
+            translate!("install-error-strip-with-directories"),
+        ));
+    }
+    if considering_dir && target_dir.is_some() {
+        return Err(USimpleError::new(
+            1,
+            translate!("install-error-target-directory-with-directories"),
+        ));
+    }
     if preserve_timestamps && compare {
         show_error!(
             "{}",
@@ -433,6 +457,7 @@ fn behavior(matches: &ArgMatches) -> UResult<Behavior> {
     Ok(Behavior {
         main_function,
         specified_mode,
+        mode_string: matches.get_one::<String>(OPT_MODE).cloned(),
         backup_mode,
         suffix: backup_control::determine_backup_suffix(matches),
         owner_id,
@@ -453,6 +478,7 @@ fn behavior(matches: &ArgMatches) -> UResult<Behavior> {
         context,
         default_context,
         unprivileged,
+        sync: uucore::fs::sync_requested(matches.get_flag(OPT_SYNC)),
     })
 }
 
@@ -465,11 +491,14 @@ fn behavior(matches: &ArgMatches) -> UResult<Behavior> {
 ///
 fn directory(paths: &[OsString], b: &Behavior) -> UResult<()> {
     if paths.is_empty() {
-        Err(InstallError::DirNeedsArg.into())
+        Err(UUsageError::new(
+            1,
+            translate!("install-error-missing-file-operand"),
+        ))
     } else {
         for path in paths.iter().map(Path::new) {
-            // if the path already exist, don't try to create it again
-            if !path.exists() {
+            // if the directory already exist, don't try to create it again
+            if !path.is_dir() {
                 // Special case to match GNU's behavior:
                 // install -d foo/. should work and just create foo/
                 // std::fs::create_dir("foo/."); fails in pure Rust
@@ -484,10 +513,21 @@ fn directory(paths: &[OsString], b: &Behavior) -> UResult<()> {
                 // target directory. All created ancestor directories will have
                 // the default mode. Hence it is safe to use fs::create_dir_all
                 // and then only modify the target's dir mode.
-                if let Err(e) = fs::create_dir_all(path_to_create.as_path())
-                    .map_err_context(|| translate!("install-error-create-dir-failed", "path" => path_to_create.as_path().quote()))
-                {
-                    show!(e);
+                let missing: Vec<_> = path_to_create
+                    .ancestors()
+                    .take_while(|p| !p.as_os_str().is_empty() && !p.is_dir())
+                    .collect();
+                if let Err(e) = fs::create_dir_all(path_to_create.as_path()) {
+                    // Report what errno says, like "File exists" for files in
+                    // the way, rather than the normalized io::Error message.
+                    show!(USimpleError::new(
+                        1,
+                        format!(
+                            "{}: {}",
+                            translate!("install-error-create-dir-failed", "path" => path.quote()),
+                            strip_errno(&e)
+                        )
+                    ));
                     continue;
                 }
 
@@ -499,16 +539,18 @@ fn directory(paths: &[OsString], b: &Behavior) -> UResult<()> {
                 }
 
                 if b.verbose {
-                    writeln!(
-                        stdout(),
-                        "{}",
-                        translate!("install-verbose-creating-directory", "path" => path_to_create.quote())
-                    )?;
+                    for created in missing.iter().rev() {
+                        writeln!(
+                            stdout(),
+                            "{}",
+                            translate!("install-verbose-creating-directory-step", "path" => created.quote())
+                        )?;
+                    }
                 }
             }
 
-            if mode::chmod(path, b.mode()).is_err() {
-                // Error messages are printed by the mode::chmod function!
+            if mode::chmod_dir(path, b.mode(), b.mode_string.as_deref()).is_err() {
+                // Error messages are printed by the mode::chmod_dir function!
                 uucore::error::set_exit_code(1);
                 continue;
             }
@@ -525,6 +567,12 @@ fn directory(paths: &[OsString], b: &Behavior) -> UResult<()> {
                     show_if_err!(set_selinux_security_context(path, context));
                 }
             }
+
+            if b.sync {
+                show_if_err!(
+                    uucore::fs::sync_parent_dir(path).map_err_context(|| path.quote().to_string())
+                );
+            }
         }
         // If the exit code was set, or show! has been called at least once
         // (which sets the exit code as well), function execution will end after
@@ -569,12 +617,17 @@ fn standard(mut paths: Vec<OsString>, b: &Behavior) -> UResult<()> {
             translate!("install-error-missing-file-operand"),
         ));
     }
+    if b.no_target_dir && b.target_dir.is_some() {
+        return Err(USimpleError::new(
+            1,
+            translate!("install-error-mutually-exclusive-target"),
+        ));
+    }
     if b.no_target_dir && paths.len() > 2 {
-        return Err(InstallError::ExtraOperand(
-            paths[2].clone(),
-            format_usage(&translate!("install-usage")),
-        )
-        .into());
+        return Err(UUsageError::new(
+            1,
+            translate!("install-error-extra-operand", "operand" => paths[2].quote()),
+        ));
     }
 
     // get the target from either "-t foo" param or from the last given paths argument
@@ -606,7 +659,7 @@ fn standard(mut paths: Vec<OsString>, b: &Behavior) -> UResult<()> {
         let to_create: Option<&Path> = if b.target_dir.is_some() {
             Some(target.as_path())
         // if source and target are filenames used in combination with -D, create target's parent
-        } else if !(sources.len() > 1 || is_potential_directory_path(&target)) {
+        } else if sources.len() == 1 && !target.is_dir() {
             target.parent()
         } else {
             None
@@ -733,23 +786,47 @@ fn standard(mut paths: Vec<OsString>, b: &Behavior) -> UResult<()> {
         }
     }
 
-    if sources.len() > 1 {
+    if b.target_dir.is_some() {
+        match metadata(&target) {
+            Ok(meta) if !meta.is_dir() => {
+                return Err(InstallError::NotADirectory(target).into());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Err(e.map_err_context(
+                    || translate!("install-error-failed-to-access", "path" => target.quote()),
+                ));
+            }
+        }
+        copy_files_into_dir(sources, &target, b)
+    } else if sources.len() > 1 {
         copy_files_into_dir(sources, &target, b)
     } else {
         let source = sources.first().unwrap();
 
+        metadata(source).map_err_context(
+            || translate!("install-error-cannot-stat", "path" => source.quote()),
+        )?;
+
         if source.is_dir() {
             return Err(InstallError::OmittingDirectory(source.clone()).into());
         }
 
-        if b.no_target_dir && target.is_dir() {
-            return Err(
-                InstallError::OverrideDirectoryFailed(target.clone(), source.clone()).into(),
-            );
+        if target.is_dir() {
+            if b.no_target_dir {
+                return Err(InstallError::OverrideDirectoryFailed(target.clone()).into());
+            }
+            return copy_files_into_dir(sources, &target, b);
         }
 
+        // A trailing slash asks for a directory, which is not there.
         if is_potential_directory_path(&target) {
-            return copy_files_into_dir(sources, &target, b);
+            return match metadata(&target) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.map_err_context(
+                    || translate!("install-error-cannot-stat", "path" => target.quote()),
+                )),
+                _ => Err(InstallError::CreateFileNotADirectory(target).into()),
+            };
         }
 
         if target.is_file() || is_new_file_path(&target) {
@@ -797,14 +874,21 @@ fn standard(mut paths: Vec<OsString>, b: &Behavior) -> UResult<()> {
 /// `target_dir` must be a directory.
 ///
 fn copy_files_into_dir(files: &[PathBuf], target_dir: &Path, b: &Behavior) -> UResult<()> {
-    if !target_dir.is_dir() {
-        return Err(InstallError::TargetDirIsntDir(target_dir.to_path_buf()).into());
+    match metadata(target_dir) {
+        Ok(meta) if !meta.is_dir() => {
+            return Err(InstallError::TargetDirIsntDir(target_dir.to_path_buf()).into());
+        }
+        Ok(_) => {}
+        Err(e) => {
+            return Err(e.map_err_context(
+                || translate!("install-error-target", "path" => target_dir.quote()),
+            ));
+        }
     }
     for sourcepath in files {
-        if let Err(err) = sourcepath
-            .metadata()
-            .map_err_context(|| format!("cannot stat {}", sourcepath.quote()))
-        {
+        if let Err(err) = sourcepath.metadata().map_err_context(
+            || translate!("install-error-cannot-stat", "path" => sourcepath.quote()),
+        ) {
             show!(err);
             continue;
         }
@@ -925,9 +1009,9 @@ fn copy_file_safe(from: &Path, to_parent_fd: &DirFd, to_filename: &std::ffi::OsS
         }
     }
 
-    let mut src = File::open(from)?;
-    let mut dst = to_parent_fd.open_file_at(to_filename)?;
-    copy_stream(&mut src, &mut dst)?;
+    let src = File::open(from)?;
+    let dst = to_parent_fd.open_file_at(to_filename)?;
+    copy::copy_file(&src, &dst, CopyOptions::default())?;
 
     Ok(())
 }
@@ -953,11 +1037,7 @@ fn copy_file(from: &Path, to: &Path) -> UResult<()> {
     }
 
     if to.is_dir() && !from.is_dir() {
-        return Err(InstallError::OverrideDirectoryFailed(
-            to.to_path_buf().clone(),
-            from.to_path_buf().clone(),
-        )
-        .into());
+        return Err(InstallError::OverrideDirectoryFailed(to.to_path_buf()).into());
     }
 
     // Remove existing file (create_new below provides TOCTOU protection)
@@ -970,15 +1050,15 @@ fn copy_file(from: &Path, to: &Path) -> UResult<()> {
         }
     }
 
-    let mut handle = File::open(from)?;
+    let handle = File::open(from)?;
     // create_new provides TOCTOU protection
-    let mut dest = OpenOptions::new()
+    let dest = OpenOptions::new()
         .write(true)
         .create_new(true)
         .mode(0o600)
         .open(to)?;
 
-    copy_stream(&mut handle, &mut dest).map_err(|err| {
+    copy::copy_file(&handle, &dest, CopyOptions::default()).map_err(|err| {
         InstallError::InstallFailed(from.to_path_buf(), to.to_path_buf(), err.to_string())
     })?;
 
@@ -1111,6 +1191,10 @@ fn finalize_installed_file(
         }
     }
 
+    if b.sync {
+        uucore::fs::sync_file_and_parent(to).map_err_context(|| to.quote().to_string())?;
+    }
+
     if b.verbose {
         write!(
             stdout(),
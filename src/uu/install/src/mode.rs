@@ -22,6 +22,26 @@ pub fn chmod(path: &Path, mode: u32) -> Result<(), ()> {
     })
 }
 
+/// chmod a directory created or named by `install -d` on UNIX.
+///
+/// Unlike [`chmod`], an explicit `mode_string` keeps the set-user-ID and
+/// set-group-ID bits the directory has, like the one it inherits from a
+/// set-group-ID parent, unless it names them.  Handles own error printing.
+///
+#[cfg(any(unix, target_os = "redox"))]
+pub fn chmod_dir(path: &Path, mode: u32, mode_string: Option<&str>) -> Result<(), ()> {
+    use uucore::{display::Quotable, show_error};
+    let Some(mode_string) = mode_string else {
+        return chmod(path, mode);
+    };
+    uucore::mode::set_dir_mode(path, 0, mode_string, 0).map_err(|err| {
+        show_error!(
+            "{}",
+            translate!("install-error-chmod-failed-detailed", "path" => path.maybe_quote(), "error" => err)
+        );
+    })
+}
+
 /// chmod a file or directory on Windows.
 ///
 /// Adapted from mkdir.rs.
@@ -31,3 +51,9 @@ pub fn chmod(path: &Path, mode: u32) -> Result<(), ()> {
     // chmod on Windows only sets the readonly flag, which isn't even honored on directories
     Ok(())
 }
+
+/// chmod a directory created or named by `install -d` on Windows.
+#[cfg(windows)]
+pub fn chmod_dir(path: &Path, mode: u32, _mode_string: Option<&str>) -> Result<(), ()> {
+    chmod(path, mode)
+}
@@ -28,7 +28,6 @@ join-error-multi-character-tab = multi-character tab { $value }
 join-error-both-files-stdin = both files cannot be standard input
 join-error-invalid-field-specifier = invalid field specifier: { $spec }
 join-error-invalid-file-number = invalid file number in field spec: { $spec }
-join-error-invalid-file-number-simple = invalid file number: { $value }
 join-error-invalid-field-number = invalid field number: { $value }
 join-error-incompatible-fields = incompatible join fields { $field1 }, { $field2 }
 join-error-not-sorted = { $file }:{ $line_num }: is not sorted: { $content }
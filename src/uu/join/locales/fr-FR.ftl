@@ -28,7 +28,6 @@ join-error-multi-character-tab = tabulation multi-caractères { $value }
 join-error-both-files-stdin = les deux fichiers ne peuvent pas être l'entrée standard
 join-error-invalid-field-specifier = spécificateur de champ invalide : { $spec }
 join-error-invalid-file-number = numéro de fichier invalide dans la spécification de champ : { $spec }
-join-error-invalid-file-number-simple = numéro de fichier invalide : { $value }
 join-error-invalid-field-number = numéro de champ invalide : { $value }
 join-error-incompatible-fields = champs de jointure incompatibles { $field1 }, { $field2 }
 join-error-not-sorted = { $file }:{ $line_num } : n'est pas trié : { $content }
@@ -123,8 +123,10 @@ impl Separator for LineSep {
         vec![(0, haystack.len())]
     }
 
+    /// GNU join separates the fields listed with `-o` by a newline when the
+    /// whole line is the field.
     fn output_separator(&self) -> &[u8] {
-        &[]
+        b"\n"
     }
 }
 
@@ -220,18 +222,22 @@ impl<'a, Sep: Separator> Repr<'a, Sep> {
         !self.format.is_empty()
     }
 
+    /// The bytes to write for `field`: the empty filler stands in for fields
+    /// that are missing or empty, as in GNU join.
+    fn field_or_empty<'b>(&'b self, field: Option<&'b [u8]>) -> &'b [u8] {
+        match field {
+            Some(field) if !field.is_empty() => field,
+            _ => self.empty,
+        }
+    }
+
     /// Write the field or empty filler if the field is not set.
     fn write_field(
         &self,
         writer: &mut impl Write,
         field: Option<&[u8]>,
     ) -> Result<(), std::io::Error> {
-        let value = match field {
-            Some(field) => field,
-            None => self.empty,
-        };
-
-        writer.write_all(value)
+        writer.write_all(self.field_or_empty(field))
     }
 
     /// Write each field except the one at the index.
@@ -244,7 +250,7 @@ impl<'a, Sep: Separator> Repr<'a, Sep> {
         for i in 0..line.field_ranges.len() {
             if i != index {
                 writer.write_all(self.separator.output_separator())?;
-                writer.write_all(line.get_field(i).unwrap())?;
+                self.write_field(writer, line.get_field(i))?;
             }
         }
         Ok(())
@@ -259,13 +265,7 @@ impl<'a, Sep: Separator> Repr<'a, Sep> {
             if i > 0 {
                 writer.write_all(self.separator.output_separator())?;
             }
-
-            let field = match f(&self.format[i]) {
-                Some(value) => value,
-                None => self.empty,
-            };
-
-            writer.write_all(field)?;
+            self.write_field(writer, f(&self.format[i]))?;
         }
         Ok(())
     }
@@ -471,14 +471,18 @@ impl<'a> State<'a> {
             self.write_first_line(writer, repr)?;
         }
 
-        self.reset_next_line(input)?;
+        self.reset_next_line(writer, input)?;
         Ok(())
     }
 
     /// Keep reading line sequence until the key does not change, return
     /// the first line whose key differs.
-    fn extend<Sep: Separator>(&mut self, input: &Input<Sep>) -> UResult<Option<Line>> {
-        while let Some(line) = self.next_line(input)? {
+    fn extend<Sep: Separator>(
+        &mut self,
+        writer: &mut impl Write,
+        input: &Input<Sep>,
+    ) -> UResult<Option<Line>> {
+        while let Some(line) = self.next_line(writer, input)? {
             let diff = input.compare(self.get_current_key(), line.get_field(self.key));
 
             if diff == Ordering::Equal {
@@ -568,8 +572,12 @@ impl<'a> State<'a> {
         Ok(())
     }
 
-    fn reset_next_line<Sep: Separator>(&mut self, input: &Input<Sep>) -> Result<(), JoinError> {
-        let line = self.next_line(input)?;
+    fn reset_next_line<Sep: Separator>(
+        &mut self,
+        writer: &mut impl Write,
+        input: &Input<Sep>,
+    ) -> Result<(), JoinError> {
+        let line = self.next_line(writer, input)?;
         self.reset(line);
         Ok(())
     }
@@ -604,13 +612,13 @@ impl<'a> State<'a> {
                 self.write_first_line(writer, repr)?;
             }
 
-            let mut next_line = self.next_line(input)?;
+            let mut next_line = self.next_line(writer, input)?;
             while let Some(line) = &next_line {
                 if self.print_unpaired {
                     self.write_line(writer, line, repr)?;
                 }
                 self.reset(next_line);
-                next_line = self.next_line(input)?;
+                next_line = self.next_line(writer, input)?;
             }
         }
 
@@ -632,8 +640,13 @@ impl<'a> State<'a> {
         }
     }
 
-    /// Get the next line with the order check.
-    fn next_line<Sep: Separator>(&mut self, input: &Input<Sep>) -> Result<Option<Line>, JoinError> {
+    /// Get the next line with the order check. `writer` is flushed before
+    /// a disorder warning, so that it follows the lines joined so far.
+    fn next_line<Sep: Separator>(
+        &mut self,
+        writer: &mut impl Write,
+        input: &Input<Sep>,
+    ) -> Result<Option<Line>, JoinError> {
         if let Some(line) = self.read_line(&input.separator)? {
             if input.check_order == CheckOrder::Disabled {
                 return Ok(Some(line));
@@ -650,6 +663,7 @@ impl<'a> State<'a> {
                 if input.check_order == CheckOrder::Enabled {
                     return Err(JoinError::UnorderedInput(err_msg));
                 }
+                writer.flush()?;
                 eprintln!("{}: {err_msg}", uucore::execution_phrase());
                 self.has_failed = true;
             }
@@ -793,17 +807,19 @@ fn parse_settings(matches: &clap::ArgMatches) -> UResult<Settings> {
     if let Some(value_os) = matches.get_one::<OsString>("t") {
         settings.separator = parse_separator(value_os)?;
     }
-    if let Some(format) = matches.get_one::<String>("o") {
+    // Like GNU, the lists of repeated -o add up, and take precedence over
+    // -o auto.
+    let mut autoformat = false;
+    for format in matches.get_many::<String>("o").unwrap_or_default() {
         if format == "auto" {
-            settings.autoformat = true;
+            autoformat = true;
         } else {
-            let mut specs = vec![];
             for part in format.split([' ', ',', '\t']) {
-                specs.push(Spec::parse(part)?);
+                settings.format.push(Spec::parse(part)?);
             }
-            settings.format = specs;
         }
     }
+    settings.autoformat = autoformat && settings.format.is_empty();
 
     if let Some(empty) = matches.get_one::<String>("e") {
         settings.empty = empty.as_bytes().to_vec();
@@ -874,7 +890,6 @@ pub fn uu_app() -> Command {
                 .short('a')
                 .action(ArgAction::Append)
                 .num_args(1)
-                .value_parser(["1", "2"])
                 .value_name("FILENUM")
                 .help(translate!("join-help-a")),
         )
@@ -883,7 +898,6 @@ pub fn uu_app() -> Command {
                 .short('v')
                 .action(ArgAction::Append)
                 .num_args(1)
-                .value_parser(["1", "2"])
                 .value_name("FILENUM")
                 .help(translate!("join-help-v")),
         )
@@ -909,6 +923,7 @@ pub fn uu_app() -> Command {
         .arg(
             Arg::new("o")
                 .short('o')
+                .action(ArgAction::Append)
                 .value_name("FORMAT")
                 .help(translate!("join-help-o")),
         )
@@ -1059,14 +1074,14 @@ fn exec<Sep: Separator>(
                 state2.has_unpaired = true;
             }
             Ordering::Equal => {
-                let next_line1 = match state1.extend(&input) {
+                let next_line1 = match state1.extend(&mut writer, &input) {
                     Ok(line) => line,
                     Err(e) => {
                         writer.flush()?;
                         return Err(e);
                     }
                 };
-                let next_line2 = match state2.extend(&input) {
+                let next_line2 = match state2.extend(&mut writer, &input) {
                     Ok(line) => line,
                     Err(e) => {
                         writer.flush()?;
@@ -1140,12 +1155,13 @@ fn parse_field_number(value: &str) -> UResult<usize> {
 }
 
 fn parse_file_number(value: &str) -> UResult<FileNum> {
-    match value {
-        "1" => Ok(FileNum::File1),
-        "2" => Ok(FileNum::File2),
-        value => Err(USimpleError::new(
+    match value.parse::<i64>() {
+        Ok(1) => Ok(FileNum::File1),
+        Ok(2) => Ok(FileNum::File2),
+        // GNU calls it a field number too.
+        _ => Err(USimpleError::new(
             1,
-            translate!("join-error-invalid-file-number-simple", "value" => value.quote()),
+            translate!("join-error-invalid-field-number", "value" => value.quote()),
         )),
     }
 }
@@ -6,7 +6,9 @@
 // spell-checker:ignore (ToDO) srcpath targetpath EEXIST
 
 use clap::{Arg, ArgAction, Command};
-use std::io::{Write, stdout};
+use std::io::Write;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use uucore::display::Quotable;
 use uucore::error::{FromIo, UError, UResult};
 use uucore::fs::{make_path_relative_to, paths_refer_to_same_file};
@@ -17,6 +19,8 @@ use std::borrow::Cow;
 use std::collections::HashSet;
 use std::ffi::OsString;
 use std::fs;
+#[cfg(not(target_family = "wasm"))]
+use std::fs::{remove_file, rename};
 use thiserror::Error;
 
 #[cfg(any(unix, target_os = "redox"))]
@@ -26,6 +30,8 @@ use std::os::windows::fs::{symlink_dir, symlink_file};
 use std::path::{Path, PathBuf};
 use uucore::backup_control::{self, BackupMode};
 use uucore::fs::{MissingHandling, ResolveMode, canonicalize};
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{self, remove_file, rename, stdout};
 
 pub struct Settings {
     overwrite: OverwriteMode,
@@ -259,7 +265,7 @@ fn exec(files: &[PathBuf], settings: &Settings) -> UResult<()> {
             return link_files_in_dir(files, &PathBuf::from("."), settings);
         }
         let last_file = &PathBuf::from(files.last().unwrap());
-        if files.len() > 2 || last_file.is_dir() {
+        if files.len() > 2 || is_dir(last_file) {
             // 3rd form: create links in the last argument.
             return link_files_in_dir(&files[0..files.len() - 1], last_file, settings);
         }
@@ -284,7 +290,7 @@ fn exec(files: &[PathBuf], settings: &Settings) -> UResult<()> {
 
 #[allow(clippy::cognitive_complexity)]
 fn link_files_in_dir(files: &[PathBuf], target_dir: &Path, settings: &Settings) -> UResult<()> {
-    if !target_dir.is_dir() {
+    if !is_dir(target_dir) {
         return Err(LnError::TargetIsNotADirectory(target_dir.to_owned()).into());
     }
     // remember the linked destinations for further usage
@@ -294,12 +300,12 @@ fn link_files_in_dir(files: &[PathBuf], target_dir: &Path, settings: &Settings)
     for srcpath in files {
         let targetpath = if settings.no_dereference
             && matches!(settings.overwrite, OverwriteMode::Force)
-            && target_dir.is_symlink()
+            && is_symlink(target_dir)
         {
             // In that case, we don't want to do link resolution
             // We need to clean the target
-            if target_dir.is_file() {
-                if let Err(e) = fs::remove_file(target_dir) {
+            if is_file(target_dir) {
+                if let Err(e) = remove_file(target_dir) {
                     show_error!(
                         "{}",
                         translate!("ln-error-could-not-update", "target" => target_dir.quote(), "error" => e)
@@ -380,7 +386,7 @@ fn link(src: &Path, dst: &Path, settings: &Settings) -> UResult<()> {
         src.into()
     };
 
-    if dst.is_symlink() || dst.exists() {
+    if is_symlink(dst) || exists(dst) {
         backup_path = match settings.backup {
             BackupMode::None => None,
             BackupMode::Simple => Some(simple_backup_path(dst, &settings.suffix)),
@@ -394,7 +400,7 @@ fn link(src: &Path, dst: &Path, settings: &Settings) -> UResult<()> {
             }
         }
         if let Some(ref p) = backup_path {
-            fs::rename(dst, p)
+            rename(dst, p)
                 .map_err_context(|| translate!("ln-cannot-backup", "file" => dst.quote()))?;
         }
         match settings.overwrite {
@@ -404,11 +410,11 @@ fn link(src: &Path, dst: &Path, settings: &Settings) -> UResult<()> {
                     return Err(LnError::SomeLinksFailed.into());
                 }
 
-                if fs::remove_file(dst).is_ok() {}
+                if remove_file(dst).is_ok() {}
                 // In case of error, don't do anything
             }
             OverwriteMode::Force => {
-                if !dst.is_symlink() && paths_refer_to_same_file(src, dst, true) {
+                if !is_symlink(dst) && paths_refer_to_same_file(src, dst, true) {
                     // Even in force overwrite mode, verify we are not targeting the same entry and return a SameFile error if so
                     let same_entry = match (
                         canonicalize(src, MissingHandling::Missing, ResolveMode::Physical),
@@ -421,7 +427,7 @@ fn link(src: &Path, dst: &Path, settings: &Settings) -> UResult<()> {
                         return Err(LnError::SameFile(src.to_owned(), dst.to_owned()).into());
                     }
                 }
-                if fs::remove_file(dst).is_ok() {}
+                if remove_file(dst).is_ok() {}
                 // In case of error, don't do anything
             }
         }
@@ -432,11 +438,11 @@ fn link(src: &Path, dst: &Path, settings: &Settings) -> UResult<()> {
     } else {
         // Cannot create hard link to a directory directly
         // We can however create hard link to a symlink that points to a directory, so long as -L is not passed
-        if src.is_dir() && (!src.is_symlink() || settings.logical) {
+        if is_dir(src) && (!is_symlink(src) || settings.logical) {
             return Err(LnError::FailedToCreateHardLinkDir(source.to_path_buf()).into());
         }
 
-        let p = if settings.logical && source.is_symlink() {
+        let p = if settings.logical && is_symlink(&source) {
             // if we want to have an hard link,
             // source is a symlink and -L is passed
             // we want to resolve the symlink to create the hardlink
@@ -475,7 +481,7 @@ fn numbered_backup_path(path: &Path) -> PathBuf {
     let mut i: u64 = 1;
     loop {
         let new_path = simple_backup_path(path, &OsString::from(format!(".~{i}~")));
-        if !new_path.exists() {
+        if !exists(&new_path) {
             return new_path;
         }
         i += 1;
@@ -484,7 +490,7 @@ fn numbered_backup_path(path: &Path) -> PathBuf {
 
 fn existing_backup_path(path: &Path, suffix: &OsString) -> PathBuf {
     let test_path = simple_backup_path(path, &OsString::from(".~1~"));
-    if test_path.exists() {
+    if exists(&test_path) {
         return numbered_backup_path(path);
     }
     simple_backup_path(path, suffix)
@@ -498,3 +504,51 @@ pub fn symlink<P1: AsRef<Path>, P2: AsRef<Path>>(src: P1, dst: P2) -> std::io::R
         symlink_file(src, dst)
     }
 }
+
+/// Symbolic links are created in the host VFS on WASM.
+#[cfg(target_family = "wasm")]
+pub fn symlink<P1: AsRef<Path>, P2: AsRef<Path>>(src: P1, dst: P2) -> std::io::Result<()> {
+    wasm_io::symlink(src, dst)
+}
+
+// On WASM, files are only reachable through the host VFS hooks.
+
+#[cfg(not(target_family = "wasm"))]
+fn is_dir(path: &Path) -> bool {
+    path.is_dir()
+}
+
+#[cfg(target_family = "wasm")]
+fn is_dir(path: &Path) -> bool {
+    wasm_io::metadata(path).is_ok_and(|md| md.is_dir())
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn is_file(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(target_family = "wasm")]
+fn is_file(path: &Path) -> bool {
+    wasm_io::metadata(path).is_ok_and(|md| md.is_file())
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn is_symlink(path: &Path) -> bool {
+    path.is_symlink()
+}
+
+#[cfg(target_family = "wasm")]
+fn is_symlink(path: &Path) -> bool {
+    wasm_io::symlink_metadata(path).is_ok_and(|md| md.is_symlink())
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn exists(path: &Path) -> bool {
+    path.exists()
+}
+
+#[cfg(target_family = "wasm")]
+fn exists(path: &Path) -> bool {
+    wasm_io::metadata(path).is_ok()
+}
@@ -6,11 +6,17 @@
 // spell-checker:ignore (ToDO) getlogin userlogin
 
 use clap::Command;
+#[cfg(not(target_family = "wasm"))]
 use std::ffi::CStr;
-use std::io::{Write, stdout};
+use std::io::Write;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::stdout;
 use uucore::{error::UResult, show_error};
 
+#[cfg(not(target_family = "wasm"))]
 fn get_userlogin() -> Option<String> {
     unsafe {
         let login: *const libc::c_char = libc::getlogin();
@@ -22,6 +28,13 @@ fn get_userlogin() -> Option<String> {
     }
 }
 
+/// On WASM there is no login session; the user running the builtin, as
+/// reported by the host's identity hooks, is the one logged in.
+#[cfg(target_family = "wasm")]
+fn get_userlogin() -> Option<String> {
+    uucore::wasm_io::uid().and_then(uucore::wasm_io::user_name)
+}
+
 #[uucore::main]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     let _ = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
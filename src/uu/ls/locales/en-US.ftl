@@ -44,6 +44,7 @@ ls-help-list-entries-commas = List entries separated by commas.
 ls-help-list-entries-nul = List entries separated by ASCII NUL characters.
 ls-help-generate-dired-output = generate output designed for Emacs' dired (Directory Editor) mode
 ls-help-hyperlink-filenames = hyperlink file names WHEN
+ls-help-json = print one JSON object per entry instead of columns (JSON Lines)
 ls-help-list-one-file-per-line = List one file per line.
 ls-help-long-format-no-group = Long format without group information.
   Identical to --format=long with --no-group.
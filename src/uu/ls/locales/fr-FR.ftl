@@ -44,6 +44,7 @@ ls-help-list-entries-commas = Lister les entrées séparées par des virgules.
 ls-help-list-entries-nul = Lister les entrées séparées par des caractères NUL ASCII.
 ls-help-generate-dired-output = générer une sortie conçue pour le mode dired (Directory Editor) d'Emacs
 ls-help-hyperlink-filenames = créer des hyperliens pour les noms de fichiers QUAND
+ls-help-json = afficher un objet JSON par entrée au lieu de colonnes (JSON Lines)
 ls-help-list-one-file-per-line = Lister un fichier par ligne.
 ls-help-long-format-no-group = Format long sans informations de groupe.
   Identique à --format=long avec --no-group.
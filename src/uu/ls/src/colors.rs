@@ -68,6 +68,17 @@ impl<'a> StyleManager<'a> {
         path: Option<&PathData>,
         name: OsString,
         wrap: bool,
+    ) -> OsString {
+        let raw_style = path.and_then(|path| self.raw_indicator_style_for_path(path));
+        self.apply_style_with_raw(new_style, raw_style, name, wrap)
+    }
+
+    fn apply_style_with_raw(
+        &mut self,
+        new_style: Option<&Style>,
+        raw_style: Option<RawIndicatorStyle>,
+        name: OsString,
+        wrap: bool,
     ) -> OsString {
         let mut style_code = String::new();
         let mut force_suffix_reset: bool = false;
@@ -79,24 +90,22 @@ impl<'a> StyleManager<'a> {
             }
         }
 
-        if let Some(path) = path {
-            // Fast-path: apply LS_COLORS raw SGR codes verbatim,
-            // bypassing LsColors fallbacks so the entry from LS_COLORS
-            // is honored exactly as specified.
-            match self.raw_indicator_style_for_path(path) {
-                Some(RawIndicatorStyle::Empty) => {
-                    // An explicit empty entry (e.g. "or=") disables coloring and
-                    // bypasses fallbacks, matching GNU ls behavior.
-                    return self.apply_empty_style(name, wrap);
-                }
-                Some(RawIndicatorStyle::Code(indicator)) => {
-                    self.append_raw_style_code_for_indicator(indicator, &mut style_code);
-                    applied_raw_code = true;
-                    self.current_style = None;
-                    force_suffix_reset = true;
-                }
-                None => {}
+        // Fast-path: apply LS_COLORS raw SGR codes verbatim,
+        // bypassing LsColors fallbacks so the entry from LS_COLORS
+        // is honored exactly as specified.
+        match raw_style {
+            Some(RawIndicatorStyle::Empty) => {
+                // An explicit empty entry (e.g. "or=") disables coloring and
+                // bypasses fallbacks, matching GNU ls behavior.
+                return self.apply_empty_style(name, wrap);
             }
+            Some(RawIndicatorStyle::Code(indicator)) => {
+                self.append_raw_style_code_for_indicator(indicator, &mut style_code);
+                applied_raw_code = true;
+                self.current_style = None;
+                force_suffix_reset = true;
+            }
+            None => {}
         }
 
         if !applied_raw_code {
@@ -121,7 +130,7 @@ impl<'a> StyleManager<'a> {
         let indicator = self.indicator_for_raw_code(path)?;
         let should_skip = indicator == Indicator::SymbolicLink
             && self.ln_color_from_target
-            && path.path().exists();
+            && path.target_metadata().is_some();
 
         if should_skip {
             return None;
@@ -248,8 +257,33 @@ impl<'a> StyleManager<'a> {
         name: OsString,
         wrap: bool,
     ) -> OsString {
-        let style = self.colors.style_for(path);
-        self.apply_style(style, Some(path), name, wrap)
+        let raw_style = self.raw_indicator_style_for_path(path);
+        // A raw code wins anyway, so don't ask LsColors, which may stat the
+        // target of a symlink once more.
+        let style = if raw_style.is_none() {
+            self.style_for(path)
+        } else {
+            None
+        };
+        self.apply_style_with_raw(style, raw_style, name, wrap)
+    }
+
+    /// Like [`LsColors::style_for`], but answers its check for orphaned
+    /// symlinks from the target metadata cached in `path`.
+    fn style_for(&self, path: &PathData) -> Option<&'a Style> {
+        if self
+            .colors
+            .has_explicit_style_for(Indicator::OrphanedSymbolicLink)
+            && path.file_type().is_some_and(fs::FileType::is_symlink)
+        {
+            let indicator = if path.target_metadata().is_some() {
+                Indicator::SymbolicLink
+            } else {
+                Indicator::OrphanedSymbolicLink
+            };
+            return self.colors.style_for_indicator(indicator);
+        }
+        self.colors.style_for(path)
     }
 
     pub(crate) fn apply_indicator_style(
@@ -323,21 +357,17 @@ impl<'a> StyleManager<'a> {
         if path.must_dereference && path.metadata().is_none() {
             return None;
         }
-        let mut target = path.path().read_link().ok()?;
-        if target.is_relative() {
-            if let Some(parent) = path.path().parent() {
-                target = parent.join(target);
-            }
-        }
 
-        match fs::metadata(&target) {
-            Ok(metadata) => {
+        match path.target_metadata() {
+            // Like GNU ls, take the file type from the target but match
+            // extensions against the name of the link itself.
+            Some(metadata) => {
                 let style = self
                     .colors
-                    .style_for_path_with_metadata(&target, Some(&metadata));
+                    .style_for_path_with_metadata(path.path(), Some(metadata));
                 Some(self.apply_style(style, None, name, wrap))
             }
-            Err(_) => {
+            None => {
                 if self.has_indicator_style(Indicator::OrphanedSymbolicLink) {
                     Some(self.apply_orphan_link_style(name, wrap))
                 } else {
@@ -352,9 +382,7 @@ impl<'a> StyleManager<'a> {
             return None;
         }
 
-        let mut existence_cache: Option<bool> = None;
-        let mut entry_exists =
-            || -> bool { *existence_cache.get_or_insert_with(|| path.path().exists()) };
+        let mut entry_exists = || path.target_metadata().is_some();
 
         let Some(file_type) = path.file_type() else {
             if self.has_indicator_style(Indicator::MissingFile) && !entry_exists() {
@@ -367,10 +395,6 @@ impl<'a> StyleManager<'a> {
             return self.indicator_for_symlink(&mut entry_exists);
         }
 
-        if self.has_indicator_style(Indicator::MissingFile) && !entry_exists() {
-            return Some(Indicator::MissingFile);
-        }
-
         if file_type.is_file() {
             self.indicator_for_file(path)
         } else if file_type.is_dir() {
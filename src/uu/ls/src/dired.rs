@@ -34,9 +34,9 @@
 /// Overall, the module ensures each entry in the DIRED output has the correct
 /// byte position, considering additional lines or padding affecting positions.
 ///
-use crate::Config;
+use crate::{Config, Stdout};
 use std::fmt;
-use std::io::{BufWriter, Stdout, Write};
+use std::io::{BufWriter, Write};
 use uucore::error::UResult;
 
 #[derive(Debug, Clone, PartialEq)]
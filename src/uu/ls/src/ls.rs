@@ -21,11 +21,12 @@ use std::{
     ffi::{OsStr, OsString},
     fmt::Write as _,
     fs::{self, DirEntry, FileType, Metadata, ReadDir},
-    io::{BufWriter, ErrorKind, IsTerminal, Stdout, Write, stdout},
+    io::{BufWriter, ErrorKind, IsTerminal, Write},
     iter,
     num::IntErrorKind,
     ops::RangeInclusive,
     path::{Path, PathBuf},
+    sync::OnceLock,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -36,6 +37,8 @@ use clap::{
 };
 use glob::{MatchOptions, Pattern};
 use lscolors::{Colorable, LsColors};
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use term_grid::{DEFAULT_SEPARATOR_SIZE, Direction, Filling, Grid, GridOptions, SPACES_IN_TAB};
 use thiserror::Error;
 
@@ -60,11 +63,13 @@ use uucore::libc::{S_IXGRP, S_IXOTH, S_IXUSR};
 use uucore::libc::{dev_t, major, minor};
 use uucore::{
     display::Quotable,
-    error::{UError, UResult, set_exit_code},
+    error::{ExitCodePolicy, FailureClass, UError, UResult, set_exit_code},
     format::human::{SizeFormat, human_readable},
+    format::machine::{Record, file_type_name},
     format_usage,
     fs::FileInformation,
     fs::display_permissions,
+    fs::resolve_relative,
     fsext::{MetadataTimeField, metadata_get_time},
     line_ending::LineEnding,
     os_str_as_bytes_lossy,
@@ -78,11 +83,25 @@ use uucore::{
     version_cmp::version_cmp,
 };
 
+#[cfg(not(target_family = "wasm"))]
+use uucore::{display::raw_stdout, io::TagWriteErrors};
+/// Standard output, whose errors tell a failure to write the listing apart.
+#[cfg(not(target_family = "wasm"))]
+type Stdout = TagWriteErrors<uucore::display::RawStdout>;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{WasmStdout as Stdout, stdout};
+
 mod dired;
 use dired::{DiredOutput, is_dired_arg_present};
 mod colors;
+#[cfg(not(target_family = "wasm"))]
+mod prefetch;
+#[cfg(target_family = "wasm")]
+mod wasm;
 use crate::options::QUOTING_STYLE;
 use colors::{LsColorsParseError, StyleManager, color_name, validate_ls_colors_env};
+#[cfg(target_family = "wasm")]
+pub use wasm::list;
 
 pub mod options {
     pub mod format {
@@ -169,6 +188,7 @@ pub mod options {
     pub static ZERO: &str = "zero";
     pub static DIRED: &str = "dired";
     pub static HYPERLINK: &str = "hyperlink";
+    pub static JSON: &str = "json";
 }
 
 const DEFAULT_TERM_WIDTH: u16 = 80;
@@ -217,16 +237,15 @@ enum LsError {
 
 impl UError for LsError {
     fn code(&self) -> i32 {
-        match self {
-            Self::InvalidLineWidth(_) => 2,
-            Self::IOError(_) => 1,
-            Self::IOErrorContext(_, _, false) => 1,
-            Self::IOErrorContext(_, _, true) => 2,
-            Self::BlockSizeParseError(_) => 2,
-            Self::DiredAndZeroAreIncompatible => 2,
-            Self::AlreadyListedError(_) => 2,
-            Self::TimeStyleParseError(_) => 2,
-        }
+        ExitCodePolicy::LS.code(match self {
+            Self::InvalidLineWidth(_)
+            | Self::BlockSizeParseError(_)
+            | Self::DiredAndZeroAreIncompatible
+            | Self::TimeStyleParseError(_) => FailureClass::Usage,
+            Self::IOError(_) | Self::IOErrorContext(_, _, false) => FailureClass::Nested,
+            Self::IOErrorContext(_, _, true) => FailureClass::Operand,
+            Self::AlreadyListedError(_) => FailureClass::Other,
+        })
     }
 }
 
@@ -341,12 +360,6 @@ enum IndicatorStyle {
     Classify,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum LocaleQuoting {
-    Single,
-    Double,
-}
-
 pub struct Config {
     // Dir and vdir needs access to this field
     pub format: Format,
@@ -367,10 +380,11 @@ pub struct Config {
     file_size_block_size: u64,
     #[allow(dead_code)]
     block_size: u64, // is never read on Windows
+    file_size_unit: String,
+    block_size_unit: String,
     width: u16,
     // Dir and vdir needs access to this field
     pub quoting_style: QuotingStyle,
-    locale_quoting: Option<LocaleQuoting>,
     indicator_style: IndicatorStyle,
     time_format_recent: String,        // Time format for recent dates
     time_format_older: Option<String>, // Time format for older dates (optional, if not present, time_format_recent is used)
@@ -384,6 +398,7 @@ pub struct Config {
     dired: bool,
     hyperlink: bool,
     tab_size: usize,
+    json: bool,
 }
 
 // Fields that can be removed or added to the long format
@@ -391,7 +406,7 @@ struct LongFormat {
     author: bool,
     group: bool,
     owner: bool,
-    #[cfg(unix)]
+    #[cfg(any(unix, target_family = "wasm"))]
     numeric_uid_gid: bool,
 }
 
@@ -568,31 +583,25 @@ fn extract_time(options: &clap::ArgMatches) -> MetadataTimeField {
     }
 }
 
-/// Some env variables can be passed
-/// For now, we are only verifying if empty or not and known for `TERM`
+/// Whether the environment allows colors. As with GNU, a non-empty
+/// `LS_COLORS` or `COLORTERM` always does, and otherwise `TERM` must match one
+/// of the `TERM` patterns of the dircolors database. An unset or empty `TERM`
+/// is accepted too, unless `COLORTERM` is set to an empty value.
 fn is_color_compatible_term() -> bool {
-    let is_term_set = std::env::var("TERM").is_ok();
-    let is_colorterm_set = std::env::var("COLORTERM").is_ok();
-
-    let term = std::env::var("TERM").unwrap_or_default();
-    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
-
-    // Search function in the TERM struct to manage the wildcards
-    let term_matches = |term: &str| -> bool {
-        uucore::colors::TERMS.iter().any(|&pattern| {
-            term == pattern
-                || (pattern.ends_with('*') && term.starts_with(&pattern[..pattern.len() - 1]))
-        })
-    };
-
-    if is_term_set && term.is_empty() && is_colorterm_set && colorterm.is_empty() {
-        return false;
-    }
-
-    if !term.is_empty() && !term_matches(&term) {
-        return false;
+    let is_set_and_non_empty = |var: &str| std::env::var_os(var).is_some_and(|val| !val.is_empty());
+    if is_set_and_non_empty("LS_COLORS") || is_set_and_non_empty("COLORTERM") {
+        return true;
+    }
+
+    let term = std::env::var("TERM").ok();
+    let colorterm = std::env::var_os("COLORTERM");
+    match term.as_deref() {
+        Some("") if colorterm.is_some() => false,
+        Some("") | None => true,
+        Some(term) => uucore::colors::TERMS.iter().any(|&pattern| {
+            parse_glob::from_str(pattern).is_ok_and(|pattern| pattern.matches(term))
+        }),
     }
-    true
 }
 
 /// Extracts the color option to use based on the options provided.
@@ -670,62 +679,8 @@ fn extract_hyperlink(options: &clap::ArgMatches) -> bool {
 /// # Returns
 ///
 /// * An option with None if the style string is invalid, or a `QuotingStyle` wrapped in `Some`.
-struct QuotingStyleSpec {
-    style: QuotingStyle,
-    fixed_control: bool,
-    locale: Option<LocaleQuoting>,
-}
-
-impl QuotingStyleSpec {
-    fn new(style: QuotingStyle) -> Self {
-        Self {
-            style,
-            fixed_control: false,
-            locale: None,
-        }
-    }
-
-    fn with_locale(style: QuotingStyle, locale: LocaleQuoting) -> Self {
-        Self {
-            style,
-            fixed_control: true,
-            locale: Some(locale),
-        }
-    }
-}
-
-fn match_quoting_style_name(
-    style: &str,
-    show_control: bool,
-) -> Option<(QuotingStyle, Option<LocaleQuoting>)> {
-    let spec = match style {
-        "literal" => QuotingStyleSpec::new(QuotingStyle::Literal {
-            show_control: false,
-        }),
-        "shell" => QuotingStyleSpec::new(QuotingStyle::SHELL),
-        "shell-always" => QuotingStyleSpec::new(QuotingStyle::SHELL_QUOTE),
-        "shell-escape" => QuotingStyleSpec::new(QuotingStyle::SHELL_ESCAPE),
-        "shell-escape-always" => QuotingStyleSpec::new(QuotingStyle::SHELL_ESCAPE_QUOTE),
-        "c" => QuotingStyleSpec::new(QuotingStyle::C_DOUBLE),
-        "escape" => QuotingStyleSpec::new(QuotingStyle::C_NO_QUOTES),
-        "locale" => QuotingStyleSpec {
-            style: QuotingStyle::Literal {
-                show_control: false,
-            },
-            fixed_control: true,
-            locale: Some(LocaleQuoting::Single),
-        },
-        "clocale" => QuotingStyleSpec::with_locale(QuotingStyle::C_DOUBLE, LocaleQuoting::Double),
-        _ => return None,
-    };
-
-    let style = if spec.fixed_control {
-        spec.style
-    } else {
-        spec.style.show_control(show_control)
-    };
-
-    Some((style, spec.locale))
+fn match_quoting_style_name(style: &str, show_control: bool) -> Option<QuotingStyle> {
+    QuotingStyle::from_name(style).map(|style| style.show_control(show_control))
 }
 
 /// Extracts the quoting style to use based on the options provided.
@@ -740,30 +695,27 @@ fn match_quoting_style_name(
 /// # Returns
 ///
 /// A [`QuotingStyle`] variant representing the quoting style to use.
-fn extract_quoting_style(
-    options: &clap::ArgMatches,
-    show_control: bool,
-) -> (QuotingStyle, Option<LocaleQuoting>) {
+fn extract_quoting_style(options: &clap::ArgMatches, show_control: bool) -> QuotingStyle {
     let opt_quoting_style = options.get_one::<String>(QUOTING_STYLE);
 
     if let Some(style) = opt_quoting_style {
         match match_quoting_style_name(style, show_control) {
-            Some(pair) => pair,
+            Some(style) => style,
             None => unreachable!("Should have been caught by Clap"),
         }
     } else if options.get_flag(options::quoting::LITERAL) {
-        (QuotingStyle::Literal { show_control }, None)
+        QuotingStyle::Literal { show_control }
     } else if options.get_flag(options::quoting::ESCAPE) {
-        (QuotingStyle::C_NO_QUOTES, None)
+        QuotingStyle::C_NO_QUOTES
     } else if options.get_flag(options::quoting::C) {
-        (QuotingStyle::C_DOUBLE, None)
+        QuotingStyle::C_DOUBLE
     } else if options.get_flag(options::DIRED) {
-        (QuotingStyle::Literal { show_control }, None)
+        QuotingStyle::Literal { show_control }
     } else {
         // If set, the QUOTING_STYLE environment variable specifies a default style.
         if let Ok(style) = std::env::var("QUOTING_STYLE") {
             match match_quoting_style_name(style.as_str(), show_control) {
-                Some(pair) => return pair,
+                Some(style) => return style,
                 None => eprintln!(
                     "{}",
                     translate!("ls-invalid-quoting-style", "program" => std::env::args().next().unwrap_or_else(|| "ls".to_string()), "style" => style.clone())
@@ -774,9 +726,9 @@ fn extract_quoting_style(
         // By default, `ls` uses Shell escape quoting style when writing to a terminal file
         // descriptor and Literal otherwise.
         if stdout().is_terminal() {
-            (QuotingStyle::SHELL_ESCAPE.show_control(show_control), None)
+            QuotingStyle::SHELL_ESCAPE.show_control(show_control)
         } else {
-            (QuotingStyle::Literal { show_control }, None)
+            QuotingStyle::Literal { show_control }
         }
     }
 }
@@ -918,8 +870,11 @@ impl Config {
 
         let sort = extract_sort(options);
         let time = extract_time(options);
-        let mut needs_color = extract_color(options);
-        let hyperlink = extract_hyperlink(options);
+        // `--json` records have no room for colors, hyperlinks or dired
+        // coordinates.
+        let json = options.get_flag(options::JSON);
+        let mut needs_color = extract_color(options) && !json;
+        let hyperlink = extract_hyperlink(options) && !json;
 
         let opt_block_size = options.get_one::<String>(options::size::BLOCK_SIZE);
         let opt_si = opt_block_size.is_some()
@@ -949,6 +904,7 @@ impl Config {
         let env_var_ls_block_size = std::env::var_os("LS_BLOCK_SIZE");
         let env_var_posixly_correct = std::env::var_os("POSIXLY_CORRECT");
         let mut is_env_var_blocksize = false;
+        let mut unit = String::new();
 
         let raw_block_size = if let Some(opt_block_size) = opt_block_size {
             OsString::from(opt_block_size)
@@ -965,7 +921,9 @@ impl Config {
 
         let (file_size_block_size, block_size) = if !opt_si && !opt_hr && !raw_block_size.is_empty()
         {
-            if let Ok(size) = parse_size_non_zero_u64(&raw_block_size.to_string_lossy()) {
+            let raw_block_size = raw_block_size.to_string_lossy();
+            if let Ok(size) = parse_size_non_zero_u64(&raw_block_size) {
+                unit = block_size_unit(&raw_block_size);
                 match (is_env_var_blocksize, opt_kb) {
                     (true, true) => (DEFAULT_FILE_SIZE_BLOCK_SIZE, DEFAULT_BLOCK_SIZE),
                     (true, false) => (DEFAULT_FILE_SIZE_BLOCK_SIZE, size),
@@ -1004,19 +962,30 @@ impl Config {
         } else {
             (DEFAULT_FILE_SIZE_BLOCK_SIZE, DEFAULT_BLOCK_SIZE)
         };
+        // The unit only goes where the block size it came with is used.
+        let file_size_unit = if is_env_var_blocksize {
+            String::new()
+        } else {
+            unit.clone()
+        };
+        let block_size_unit = if opt_kb && opt_block_size.is_none() {
+            String::new()
+        } else {
+            unit
+        };
 
         let long = {
             let author = options.get_flag(options::AUTHOR);
             let group = !options.get_flag(options::NO_GROUP)
                 && !options.get_flag(options::format::LONG_NO_GROUP);
             let owner = !options.get_flag(options::format::LONG_NO_OWNER);
-            #[cfg(unix)]
+            #[cfg(any(unix, target_family = "wasm"))]
             let numeric_uid_gid = options.get_flag(options::format::LONG_NUMERIC_UID_GID);
             LongFormat {
                 author,
                 group,
                 owner,
-                #[cfg(unix)]
+                #[cfg(any(unix, target_family = "wasm"))]
                 numeric_uid_gid,
             }
         };
@@ -1031,10 +1000,10 @@ impl Config {
             !stdout().is_terminal()
         };
 
-        let (mut quoting_style, mut locale_quoting) = extract_quoting_style(options, show_control);
+        let mut quoting_style = extract_quoting_style(options, show_control);
         let indicator_style = extract_indicator_style(options);
         // Only parse the value to "--time-style" if it will become relevant.
-        let dired = options.get_flag(options::DIRED);
+        let dired = options.get_flag(options::DIRED) && !json;
         let (time_format_recent, time_format_older) = if format == Format::Long || dired {
             parse_time_style(options)?
         } else {
@@ -1152,7 +1121,6 @@ impl Config {
                 .unwrap_or(0)
         {
             quoting_style = QuotingStyle::Literal { show_control };
-            locale_quoting = None;
         }
 
         if needs_color {
@@ -1196,6 +1164,7 @@ impl Config {
         } else if options.get_flag(options::DIRECTORY)
             || indicator_style == IndicatorStyle::Classify
             || format == Format::Long
+            || json
         {
             Dereference::None
         } else {
@@ -1230,9 +1199,10 @@ impl Config {
             alloc_size: options.get_flag(options::size::ALLOCATION_SIZE),
             file_size_block_size,
             block_size,
+            file_size_unit,
+            block_size_unit,
             width,
             quoting_style,
-            locale_quoting,
             indicator_style,
             time_format_recent,
             time_format_older,
@@ -1246,13 +1216,18 @@ impl Config {
             dired,
             hyperlink,
             tab_size,
+            json,
         })
     }
 }
 
 #[uucore::main]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
-    let matches = uucore::clap_localization::handle_clap_result_with_exit_code(uu_app(), args, 2)?;
+    let matches = uucore::clap_localization::handle_clap_result_with_exit_code(
+        uu_app(),
+        args,
+        ExitCodePolicy::LS.usage,
+    )?;
 
     let config = Config::from(&matches)?;
 
@@ -1260,7 +1235,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         .get_many::<OsString>(options::PATHS)
         .map_or_else(|| vec![Path::new(".")], |v| v.map(Path::new).collect());
 
-    list(locs, &config)
+    list(locs, &config).map_err(|e| ExitCodePolicy::LS.classify_io_error(FailureClass::Other, e))
 }
 
 pub fn uu_app() -> Command {
@@ -1397,6 +1372,12 @@ pub fn uu_app() -> Command {
             .value_name("WHEN")
             .overrides_with(options::DIRED),
     )
+    .arg(
+        Arg::new(options::JSON)
+            .long(options::JSON)
+            .help(translate!("ls-help-json"))
+            .action(ArgAction::SetTrue),
+    )
     // The next four arguments do not override with the other format
     // options, see the comment in Config::from for the reason.
     // Ideally, they would use Arg::override_with, with their own name
@@ -1929,10 +1910,15 @@ struct PathData {
     // Result<MetaData> got from symlink_metadata() or metadata() based on config
     md: OnceCell<Option<Metadata>>,
     ft: OnceCell<Option<FileType>>,
+    // Result<MetaData> got from metadata(), i.e. of what a symlink points to,
+    // only looked up when coloring needs it
+    target_md: OnceCell<Option<Metadata>>,
     // can be used to avoid reading the filetype. Can be also called d_type:
     // https://www.gnu.org/software/libc/manual/html_node/Directory-Entries.html
     de: RefCell<Option<Box<DirEntry>>>,
     security_context: OnceCell<Box<str>>,
+    // whether the file has an access control list, for the "+" of -l
+    acl: OnceCell<bool>,
     // Name of the file - will be empty for . or ..
     display_name: OsString,
     // PathBuf that all above data corresponds to
@@ -1984,6 +1970,7 @@ impl PathData {
         let ft: OnceCell<Option<FileType>> = OnceCell::new();
         let md: OnceCell<Option<Metadata>> = OnceCell::new();
         let security_context: OnceCell<Box<str>> = OnceCell::new();
+        let acl: OnceCell<bool> = OnceCell::new();
 
         let de: RefCell<Option<Box<DirEntry>>> = if let Some(de) = dir_entry {
             if must_dereference {
@@ -2005,8 +1992,10 @@ impl PathData {
         Self {
             md,
             ft,
+            target_md: OnceCell::new(),
             de,
             security_context,
+            acl,
             display_name,
             p_buf,
             must_dereference,
@@ -2026,7 +2015,7 @@ impl PathData {
                 match get_metadata_with_deref_opt(self.path(), self.must_dereference) {
                     Err(err) => {
                         // FIXME: A bit tricky to propagate the result here
-                        let mut out: std::io::StdoutLock<'static> = stdout().lock();
+                        let mut out = stdout().lock();
                         let _ = out.flush();
                         let errno = err.raw_os_error().unwrap_or(1i32);
                         // a bad fd will throw an error when dereferenced,
@@ -2057,6 +2046,17 @@ impl PathData {
             .as_ref()
     }
 
+    /// The metadata of the file this entry resolves to, following symlinks.
+    /// `None` if that file does not exist, e.g. for a broken symlink.
+    fn target_metadata(&self) -> Option<&Metadata> {
+        if self.must_dereference {
+            return self.metadata();
+        }
+        self.target_md
+            .get_or_init(|| fs::metadata(self.path()).ok())
+            .as_ref()
+    }
+
     fn is_dangling_link(&self) -> bool {
         // deref enabled, self is real dir entry, self has metadata associated with link, but not with target
         self.must_dereference && self.file_type().is_none() && self.metadata().is_none()
@@ -2073,6 +2073,17 @@ impl PathData {
             .get_or_init(|| get_security_context(&self.p_buf, self.must_dereference, config).into())
     }
 
+    #[cfg(all(unix, not(any(target_os = "android", target_os = "macos"))))]
+    fn has_acl(&self) -> bool {
+        *self.acl.get_or_init(|| has_acl(self.path()))
+    }
+
+    // TODO: See how Mac should work here
+    #[cfg(any(not(unix), target_os = "android", target_os = "macos"))]
+    fn has_acl(&self) -> bool {
+        false
+    }
+
     fn path(&self) -> &Path {
         &self.p_buf
     }
@@ -2113,7 +2124,8 @@ fn show_dir_name(
     out: &mut BufWriter<Stdout>,
     config: &Config,
 ) -> std::io::Result<()> {
-    let escaped_name = escape_dir_name_with_locale(path_data.path().as_os_str(), config);
+    let escaped_name =
+        locale_aware_escape_dir_name(path_data.path().as_os_str(), config.quoting_style);
 
     let name = if config.hyperlink && !config.dired {
         create_hyperlink(&escaped_name, path_data)
@@ -2125,70 +2137,6 @@ fn show_dir_name(
     write!(out, ":")
 }
 
-fn escape_with_locale<F>(name: &OsStr, config: &Config, fallback: F) -> OsString
-where
-    F: FnOnce(&OsStr, QuotingStyle) -> OsString,
-{
-    if let Some(locale) = config.locale_quoting {
-        locale_quote(name, locale)
-    } else {
-        fallback(name, config.quoting_style)
-    }
-}
-
-fn escape_dir_name_with_locale(name: &OsStr, config: &Config) -> OsString {
-    escape_with_locale(name, config, locale_aware_escape_dir_name)
-}
-
-fn escape_name_with_locale(name: &OsStr, config: &Config) -> OsString {
-    escape_with_locale(name, config, locale_aware_escape_name)
-}
-
-fn locale_quote(name: &OsStr, style: LocaleQuoting) -> OsString {
-    let bytes = os_str_as_bytes_lossy(name);
-    let mut quoted = String::new();
-    match style {
-        LocaleQuoting::Single => quoted.push('\''),
-        LocaleQuoting::Double => quoted.push('"'),
-    }
-    for &byte in bytes.as_ref() {
-        push_locale_byte(&mut quoted, byte, style);
-    }
-    match style {
-        LocaleQuoting::Single => quoted.push('\''),
-        LocaleQuoting::Double => quoted.push('"'),
-    }
-    OsString::from(quoted)
-}
-
-fn push_locale_byte(buf: &mut String, byte: u8, style: LocaleQuoting) {
-    match (style, byte) {
-        (LocaleQuoting::Single, b'\'') => buf.push_str("'\\''"),
-        (LocaleQuoting::Double, b'"') => buf.push_str("\\\""),
-        (_, b'\\') => buf.push_str("\\\\"),
-        _ => push_basic_escape(buf, byte),
-    }
-}
-
-fn push_basic_escape(buf: &mut String, byte: u8) {
-    match byte {
-        b'\x07' => buf.push_str("\\a"),
-        b'\x08' => buf.push_str("\\b"),
-        b'\t' => buf.push_str("\\t"),
-        b'\n' => buf.push_str("\\n"),
-        b'\x0b' => buf.push_str("\\v"),
-        b'\x0c' => buf.push_str("\\f"),
-        b'\r' => buf.push_str("\\r"),
-        b'\x1b' => buf.push_str("\\e"),
-        b'"' => buf.push('"'),
-        b'\'' => buf.push('\''),
-        b if (0x20..=0x7e).contains(&b) => buf.push(b as char),
-        _ => {
-            let _ = write!(buf, "\\{byte:03o}");
-        }
-    }
-}
-
 // A struct to encapsulate state that is passed around from `list` functions.
 struct ListState<'a> {
     out: BufWriter<Stdout>,
@@ -2205,6 +2153,7 @@ struct ListState<'a> {
     recent_time_range: RangeInclusive<SystemTime>,
 }
 
+#[cfg(not(target_family = "wasm"))]
 #[allow(clippy::cognitive_complexity)]
 pub fn list(locs: Vec<&Path>, config: &Config) -> UResult<()> {
     let mut files = Vec::<PathData>::new();
@@ -2213,7 +2162,7 @@ pub fn list(locs: Vec<&Path>, config: &Config) -> UResult<()> {
     let initial_locs_len = locs.len();
 
     let mut state = ListState {
-        out: BufWriter::new(stdout()),
+        out: BufWriter::new(TagWriteErrors(raw_stdout())),
         style_manager: config.color.as_ref().map(StyleManager::new),
         #[cfg(unix)]
         uid_cache: FxHashMap::default(),
@@ -2285,7 +2234,7 @@ pub fn list(locs: Vec<&Path>, config: &Config) -> UResult<()> {
         };
 
         // Print dir heading - name... 'total' comes after error display
-        if initial_locs_len > 1 || config.recursive {
+        if (initial_locs_len > 1 || config.recursive) && !config.json {
             let needs_blank_line = !(pos.eq(&0usize) && files.is_empty());
             if needs_blank_line {
                 writeln!(state.out)?;
@@ -2323,21 +2272,31 @@ pub fn list(locs: Vec<&Path>, config: &Config) -> UResult<()> {
     if config.dired && !config.hyperlink {
         dired::print_dired_output(config, &dired, &mut state.out)?;
     }
+    state.out.flush()?;
     Ok(())
 }
 
 fn sort_entries(entries: &mut [PathData], config: &Config) {
+    // Like GNU, files with the same time or size are sorted by name. A time
+    // that is not known, e.g. a birth time the file system does not record,
+    // sorts as the oldest.
     match config.sort {
-        Sort::Time => entries.sort_by_key(|k| {
-            Reverse(
-                k.metadata()
-                    .and_then(|md| metadata_get_time(md, config.time))
-                    .unwrap_or(UNIX_EPOCH),
+        Sort::Time => entries.sort_by_cached_key(|k| {
+            (
+                Reverse(
+                    k.metadata()
+                        .and_then(|md| metadata_get_time(md, config.time))
+                        .unwrap_or(UNIX_EPOCH),
+                ),
+                k.display_name().to_os_string(),
             )
         }),
-        Sort::Size => {
-            entries.sort_by_key(|k| Reverse(k.metadata().map_or(0, Metadata::len)));
-        }
+        Sort::Size => entries.sort_by(|a, b| {
+            let size = |k: &PathData| k.metadata().map_or(0, Metadata::len);
+            size(b)
+                .cmp(&size(a))
+                .then_with(|| a.display_name().cmp(b.display_name()))
+        }),
         // The default sort in GNU ls is case insensitive
         Sort::Name => entries.sort_by(|a, b| a.display_name().cmp(b.display_name())),
         Sort::Version => entries.sort_by(|a, b| {
@@ -2413,6 +2372,11 @@ fn should_display(entry: &DirEntry, config: &Config) -> bool {
     }
 
     // check if it is among ignore_patterns
+    !is_ignored(&entry.file_name(), config)
+}
+
+/// Check whether `file_name` matches one of the `--ignore`/`--hide` patterns.
+fn is_ignored(file_name: &OsStr, config: &Config) -> bool {
     let options = MatchOptions {
         // setting require_literal_leading_dot to match behavior in GNU ls
         require_literal_leading_dot: true,
@@ -2420,7 +2384,6 @@ fn should_display(entry: &DirEntry, config: &Config) -> bool {
         case_sensitive: true,
     };
 
-    let file_name = entry.file_name();
     // If the decoding fails, still match best we can
     // FIXME: use OsStrings or Paths once we have a glob crate that supports it:
     // https://github.com/rust-lang/glob/issues/23
@@ -2432,7 +2395,7 @@ fn should_display(entry: &DirEntry, config: &Config) -> bool {
         None => file_name.to_string_lossy(),
     };
 
-    !config
+    config
         .ignore_patterns
         .iter()
         .any(|p| p.matches_with(&file_name, options))
@@ -2487,10 +2450,13 @@ fn enter_directory(
         }
     }
 
+    #[cfg(not(target_family = "wasm"))]
+    prefetch::prefetch(&entries, config);
+
     sort_entries(&mut entries, config);
 
     // Print total after any error display
-    if config.format == Format::Long || config.alloc_size {
+    if (config.format == Format::Long || config.alloc_size) && !config.json {
         let total = return_total(&entries, config, &mut state.out)?;
         write!(state.out, "{}", total.as_str())?;
         if config.dired {
@@ -2524,6 +2490,12 @@ fn enter_directory(
                     if listed_ancestors
                         .insert(FileInformation::from_path(e.path(), e.must_dereference)?)
                     {
+                        if config.json {
+                            enter_directory(e, rd, config, state, listed_ancestors, dired)?;
+                            listed_ancestors
+                                .remove(&FileInformation::from_path(e.path(), e.must_dereference)?);
+                            continue;
+                        }
                         // when listing several directories in recursive mode, we show
                         // "dirname:" at the beginning of the file list
                         writeln!(state.out)?;
@@ -2579,8 +2551,8 @@ fn display_dir_entry_size(
         };
         (
             display_symlink_count(md).len(),
-            display_uname(md, config, state).len(),
-            display_group(md, config, state).len(),
+            display_width(display_uname(md, config, state)),
+            display_width(display_group(md, config, state)),
             size_len,
             major_len,
             minor_len,
@@ -2590,8 +2562,18 @@ fn display_dir_entry_size(
     }
 }
 
+/// The number of terminal columns taken up by `string` once rendered.
+///
+/// Column widths must be computed with this rather than `str::len`, so that
+/// non-ASCII owner names and security contexts, as well as any ANSI escape
+/// sequences, do not throw off the alignment.
+fn display_width(string: &str) -> usize {
+    ansi_width(string)
+}
+
 // A simple, performant, ExtendPad trait to add a string to a Vec<u8>, padding with spaces
 // on the left or right, without making additional copies, or using formatting functions.
+// `count` is a width in terminal columns, see `display_width`.
 trait ExtendPad {
     fn extend_pad_left(&mut self, string: &str, count: usize);
     fn extend_pad_right(&mut self, string: &str, count: usize);
@@ -2599,16 +2581,18 @@ trait ExtendPad {
 
 impl ExtendPad for Vec<u8> {
     fn extend_pad_left(&mut self, string: &str, count: usize) {
-        if string.len() < count {
-            self.extend(iter::repeat_n(b' ', count - string.len()));
+        let width = display_width(string);
+        if width < count {
+            self.extend(iter::repeat_n(b' ', count - width));
         }
         self.extend(string.as_bytes());
     }
 
     fn extend_pad_right(&mut self, string: &str, count: usize) {
         self.extend(string.as_bytes());
-        if string.len() < count {
-            self.extend(iter::repeat_n(b' ', count - string.len()));
+        let width = display_width(string);
+        if width < count {
+            self.extend(iter::repeat_n(b' ', count - width));
         }
     }
 }
@@ -2616,7 +2600,10 @@ impl ExtendPad for Vec<u8> {
 // TODO: Consider converting callers to use ExtendPad instead, as it avoids
 // additional copies.
 fn pad_left(string: &str, count: usize) -> String {
-    format!("{string:>count$}")
+    let mut padded = Vec::with_capacity(count.max(string.len()));
+    padded.extend_pad_left(string, count);
+    // Only ASCII spaces were added to valid UTF-8.
+    String::from_utf8(padded).unwrap()
 }
 
 fn return_total(
@@ -2624,19 +2611,21 @@ fn return_total(
     config: &Config,
     out: &mut BufWriter<Stdout>,
 ) -> UResult<String> {
+    // Like GNU, add up the allocated space first and round once, so that
+    // many small files do not each lose a fraction of a block.
     let mut total_size = 0;
     for item in items {
         total_size += item
             .metadata()
             .as_ref()
-            .map_or(0, |md| get_block_size(md, config));
+            .map_or(0, |md| get_allocated_size(md));
     }
     if config.dired {
         dired::indent(out)?;
     }
     Ok(format!(
         "{}{}",
-        translate!("ls-total", "size" => display_size(total_size, config)),
+        translate!("ls-total", "size" => display_blocks(scale_allocated_size(total_size, config), config)),
         config.line_ending
     ))
 }
@@ -2661,7 +2650,7 @@ fn display_additional_leading_info(
 
     if config.alloc_size {
         let s = if let Some(md) = item.metadata() {
-            display_size(get_block_size(md, config), config)
+            display_blocks(get_block_size(md, config), config)
         } else {
             "?".to_owned()
         };
@@ -2687,8 +2676,16 @@ fn display_items(
     // Display the SELinux security context or '?' if none is found. When used with the `-l`
     // option, print the security context to the left of the size column.
 
+    if config.json {
+        for item in items {
+            let record = json_record(item, config, state);
+            writeln!(state.out, "{record}")?;
+        }
+        return Ok(());
+    }
+
     let quoted = items.iter().any(|item| {
-        let name = escape_name_with_locale(item.display_name(), config);
+        let name = locale_aware_escape_name(item.display_name(), config.quoting_style);
         os_str_starts_with(&name, b"'")
     });
 
@@ -2713,7 +2710,7 @@ fn display_items(
         let mut longest_context_len = 1;
         let prefix_context = if config.context {
             for item in items {
-                let context_len = item.security_context(config).len();
+                let context_len = display_width(item.security_context(config));
                 longest_context_len = context_len.max(longest_context_len);
             }
             Some(longest_context_len)
@@ -2757,58 +2754,130 @@ fn display_items(
             names_vec.push(cell.displayed);
         }
 
-        let mut names = names_vec.into_iter();
+        write_names(names_vec.into_iter(), quoted, config, &mut state.out)?;
+    }
 
-        match config.format {
-            Format::Columns => {
-                display_grid(
-                    names,
-                    config.width,
-                    Direction::TopToBottom,
-                    &mut state.out,
-                    quoted,
-                    config.tab_size,
-                )?;
-            }
-            Format::Across => {
-                display_grid(
-                    names,
-                    config.width,
-                    Direction::LeftToRight,
-                    &mut state.out,
-                    quoted,
-                    config.tab_size,
-                )?;
+    Ok(())
+}
+
+/// The `--json` record of `item`. The owner and group are the names `-l`
+/// would show.
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
+fn json_record(item: &PathData, config: &Config, state: &mut ListState) -> Record {
+    let mut record = Record::new();
+    record
+        .os_string("name", item.display_name())
+        .os_string("path", item.path().as_os_str())
+        .optional_string("type", item.file_type().copied().map(file_type_name));
+    let Some(md) = item.metadata() else {
+        for key in [
+            "size",
+            "mode",
+            "permissions",
+            "nlink",
+            "inode",
+            "uid",
+            "gid",
+            "owner",
+            "group",
+            "mtime",
+            "target",
+        ] {
+            record.null(key);
+        }
+        return record;
+    };
+    record.unsigned("size", md.len());
+    #[cfg(unix)]
+    record
+        .unsigned("mode", u64::from(md.mode() & 0o7777))
+        .string("permissions", &display_permissions(md, true))
+        .unsigned("nlink", md.nlink())
+        .unsigned("inode", md.ino())
+        .unsigned("uid", u64::from(md.uid()))
+        .unsigned("gid", u64::from(md.gid()))
+        .string("owner", display_uname(md, config, state))
+        .string("group", display_group(md, config, state));
+    #[cfg(not(unix))]
+    record
+        .null("mode")
+        .string("permissions", &display_permissions(md, true))
+        .null("nlink")
+        .null("inode")
+        .null("uid")
+        .null("gid")
+        .string("owner", display_uname(md, config, state))
+        .string("group", display_group(md, config, state));
+    record.time("mtime", md.modified().ok());
+    let target = md
+        .is_symlink()
+        .then(|| fs::read_link(item.path()).ok())
+        .flatten();
+    match target {
+        Some(target) => record.os_string("target", target.as_os_str()),
+        None => record.null("target"),
+    };
+    record
+}
+
+/// Write already formatted names in one of the short (non-long) formats.
+fn write_names(
+    mut names: impl Iterator<Item = OsString>,
+    quoted: bool,
+    config: &Config,
+    out: &mut BufWriter<Stdout>,
+) -> UResult<()> {
+    match config.format {
+        Format::Columns => {
+            display_grid(
+                names,
+                config.width,
+                Direction::TopToBottom,
+                out,
+                quoted,
+                config.tab_size,
+                config.line_ending,
+            )?;
+        }
+        Format::Across => {
+            display_grid(
+                names,
+                config.width,
+                Direction::LeftToRight,
+                out,
+                quoted,
+                config.tab_size,
+                config.line_ending,
+            )?;
+        }
+        Format::Commas => {
+            let mut current_col = 0;
+            if let Some(name) = names.next() {
+                write_os_str(out, &name)?;
+                current_col = ansi_width(&name.to_string_lossy()) as u16 + 2;
             }
-            Format::Commas => {
-                let mut current_col = 0;
-                if let Some(name) = names.next() {
-                    write_os_str(&mut state.out, &name)?;
-                    current_col = ansi_width(&name.to_string_lossy()) as u16 + 2;
-                }
-                for name in names {
-                    let name_width = ansi_width(&name.to_string_lossy()) as u16;
-                    // If the width is 0 we print one single line
-                    if config.width != 0 && current_col + name_width + 1 > config.width {
-                        current_col = name_width + 2;
-                        writeln!(state.out, ",")?;
-                    } else {
-                        current_col += name_width + 2;
-                        write!(state.out, ", ")?;
-                    }
-                    write_os_str(&mut state.out, &name)?;
-                }
-                // Current col is never zero again if names have been printed.
-                // So we print a newline.
-                if current_col > 0 {
-                    write!(state.out, "{}", config.line_ending)?;
+            for name in names {
+                let name_width = ansi_width(&name.to_string_lossy()) as u16;
+                // If the width is 0 we print one single line
+                if config.width != 0 && current_col + name_width + 1 > config.width {
+                    current_col = name_width + 2;
+                    writeln!(out, ",")?;
+                } else {
+                    current_col += name_width + 2;
+                    write!(out, ", ")?;
                 }
+                write_os_str(out, &name)?;
             }
-            _ => {
-                for name in names {
-                    write_os_str(&mut state.out, &name)?;
-                    write!(state.out, "{}", config.line_ending)?;
-                }
+            // Current col is never zero again if names have been printed.
+            // So we print a newline.
+            if current_col > 0 {
+                write!(out, "{}", config.line_ending)?;
+            }
+        }
+        _ => {
+            for name in names {
+                write_os_str(out, &name)?;
+                write!(out, "{}", config.line_ending)?;
             }
         }
     }
@@ -2816,21 +2885,22 @@ fn display_items(
     Ok(())
 }
 
-#[allow(unused_variables)]
 fn get_block_size(md: &Metadata, config: &Config) -> u64 {
+    scale_allocated_size(get_allocated_size(md), config)
+}
+
+/// The space allocated to a file in bytes, which is what `-s` and the
+/// `total` line count
+fn get_allocated_size(md: &Metadata) -> u64 {
     /* GNU ls will display sizes in terms of block size
        md.len() will differ from this value when the file has some holes
     */
     #[cfg(unix)]
     {
-        let raw_blocks = if md.file_type().is_char_device() || md.file_type().is_block_device() {
+        if md.file_type().is_char_device() || md.file_type().is_block_device() {
             0u64
         } else {
             md.blocks() * 512
-        };
-        match config.size_format {
-            SizeFormat::Binary | SizeFormat::Decimal => raw_blocks,
-            SizeFormat::Bytes => raw_blocks / config.block_size,
         }
     }
     #[cfg(not(unix))]
@@ -2840,6 +2910,23 @@ fn get_block_size(md: &Metadata, config: &Config) -> u64 {
     }
 }
 
+/// Convert an allocated size in bytes to the unit it is displayed in,
+/// rounding partial blocks up as GNU does
+#[allow(unused_variables)]
+fn scale_allocated_size(size: u64, config: &Config) -> u64 {
+    #[cfg(unix)]
+    {
+        match config.size_format {
+            SizeFormat::Binary | SizeFormat::Decimal => size,
+            SizeFormat::Bytes => size.div_ceil(config.block_size),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        size
+    }
+}
+
 fn display_grid(
     names: impl Iterator<Item = OsString>,
     width: u16,
@@ -2847,6 +2934,7 @@ fn display_grid(
     out: &mut BufWriter<Stdout>,
     quoted: bool,
     tab_size: usize,
+    line_ending: LineEnding,
 ) -> UResult<()> {
     if width == 0 {
         // If the width is 0 we print one single line
@@ -2859,7 +2947,7 @@ fn display_grid(
             write_os_str(out, &name)?;
         }
         if printed_something {
-            writeln!(out)?;
+            write!(out, "{line_ending}")?;
         }
     } else {
         let names: Vec<_> = if quoted {
@@ -2895,6 +2983,16 @@ fn display_grid(
             .into_iter()
             .map(|s| s.to_string_lossy().into_owned())
             .collect();
+        // With --zero, names are not quoted and may contain newlines, which
+        // must be told apart from the ones the grid ends its rows with.
+        let newlines: Vec<_> = if line_ending == LineEnding::Newline {
+            Vec::new()
+        } else {
+            names
+                .iter()
+                .map(|name| name.bytes().filter(|&b| b == b'\n').count())
+                .collect()
+        };
 
         // Since tab_size=0 means no \t, use Spaces separator for optimization.
         let filling = match tab_size {
@@ -2913,8 +3011,59 @@ fn display_grid(
                 width: width as usize,
             },
         );
-        write!(out, "{grid}")?;
+        if line_ending == LineEnding::Newline {
+            write!(out, "{grid}")?;
+        } else {
+            write_grid_rows(
+                &grid.to_string(),
+                &newlines,
+                &grid,
+                direction,
+                line_ending,
+                out,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a grid rendered as `text`, ending its rows with `line_ending`
+/// instead of a newline. `newlines` counts the newlines in each name.
+fn write_grid_rows(
+    text: &str,
+    newlines: &[usize],
+    grid: &Grid<String>,
+    direction: Direction,
+    line_ending: LineEnding,
+    out: &mut BufWriter<Stdout>,
+) -> UResult<()> {
+    let rows = grid.row_count();
+    let columns = grid.column_widths().len();
+    let mut newlines_in_row = vec![0; rows];
+    for (i, count) in newlines.iter().enumerate() {
+        let row = match direction {
+            Direction::LeftToRight => i / columns,
+            Direction::TopToBottom => i % rows,
+        };
+        newlines_in_row[row] += count;
     }
+
+    let mut rest = text.as_bytes();
+    for skip in newlines_in_row {
+        let Some(end) = rest
+            .iter()
+            .enumerate()
+            .filter(|&(_, &b)| b == b'\n')
+            .nth(skip)
+            .map(|(i, _)| i)
+        else {
+            break;
+        };
+        out.write_all(&rest[..end])?;
+        write!(out, "{line_ending}")?;
+        rest = &rest[end + 1..];
+    }
+    out.write_all(rest)?;
     Ok(())
 }
 
@@ -2980,11 +3129,7 @@ fn display_item_long(
         output_display.extend(b"  ");
     }
     if let Some(md) = item.metadata() {
-        #[cfg(any(not(unix), target_os = "android", target_os = "macos"))]
-        // TODO: See how Mac should work here
-        let is_acl_set = false;
-        #[cfg(all(unix, not(any(target_os = "android", target_os = "macos"))))]
-        let is_acl_set = has_acl(item.path());
+        let is_acl_set = item.has_acl();
         output_display.extend(display_permissions(md, true).as_bytes());
         if item.security_context(config).len() > 1 {
             // GNU `ls` uses a "." character to indicate a file with a security context,
@@ -3247,7 +3392,21 @@ fn display_date(
     out: &mut Vec<u8>,
 ) -> UResult<()> {
     let Some(time) = metadata_get_time(metadata, config.time) else {
-        out.extend(b"???");
+        // Like GNU, a time that is not known, such as a birth time the file
+        // system does not record, is shown as a "?" as wide as a timestamp.
+        let mut epoch = Vec::new();
+        let fmt = config
+            .time_format_older
+            .as_ref()
+            .unwrap_or(&config.time_format_recent);
+        format_system_time(
+            &mut epoch,
+            UNIX_EPOCH,
+            fmt,
+            FormatSystemTimeFallback::Integer,
+        )?;
+        let width = display_width(&String::from_utf8_lossy(&epoch));
+        write!(out, "{:>width$}", "?")?;
         return Ok(());
     };
 
@@ -3295,13 +3454,41 @@ fn display_len_or_rdev(metadata: &Metadata, config: &Config) -> SizeOrDeviceId {
         let r = metadata.len() % config.file_size_block_size;
         if r == 0 { d } else { d + 1 }
     };
-    SizeOrDeviceId::Size(display_size(len_adjusted, config))
+    SizeOrDeviceId::Size(display_file_size(len_adjusted, config))
 }
 
 fn display_size(size: u64, config: &Config) -> String {
     human_readable(size, config.size_format)
 }
 
+/// Display a file size already divided by the file size block size
+fn display_file_size(size: u64, config: &Config) -> String {
+    display_size(size, config) + &config.file_size_unit
+}
+
+/// Display an allocated size already divided by the block size
+fn display_blocks(size: u64, config: &Config) -> String {
+    display_size(size, config) + &config.block_size_unit
+}
+
+/// The unit GNU prints after each size when the block size is given as a
+/// bare unit, e.g. `K` for `K` or `kB` for `KB`; empty when it has a number
+fn block_size_unit(spec: &str) -> String {
+    let mut chars = spec.chars();
+    match chars.next() {
+        Some(c) if !c.is_ascii_digit() => {
+            let rest = chars.as_str();
+            // A plain `B` suffix means powers of 1000, written with a small k.
+            if rest == "B" && c.eq_ignore_ascii_case(&'k') {
+                "kB".to_owned()
+            } else {
+                format!("{}{rest}", c.to_ascii_uppercase())
+            }
+        }
+        _ => String::new(),
+    }
+}
+
 #[cfg(unix)]
 fn file_is_executable(md: &Metadata) -> bool {
     // Mode always returns u32, but the flags might not be, based on the platform
@@ -3364,17 +3551,18 @@ fn display_item_name(
     current_column: LazyCell<usize, Box<dyn FnOnce() -> usize + '_>>,
 ) -> DisplayItemName {
     // This is our return value. We start by `&path.display_name` and modify it along the way.
-    let mut name = escape_name_with_locale(path.display_name(), config);
+    let mut name = locale_aware_escape_name(path.display_name(), config.quoting_style);
 
     let is_wrap =
         |namelen: usize| config.width != 0 && *current_column + namelen > config.width.into();
 
+    // Measure the name before the hyperlink escapes, which take no room.
+    let len = name.len();
     if config.hyperlink {
         name = create_hyperlink(&name, path);
     }
 
     if let Some(style_manager) = &mut state.style_manager {
-        let len = name.len();
         name = color_name(name, path, style_manager, None, is_wrap(len));
     }
 
@@ -3423,11 +3611,24 @@ fn display_item_name(
             Ok(target_path) => {
                 name.push(" -> ");
 
+                // Like GNU ls, the target links to the same file as the
+                // symlink itself.
+                let link_target = |escaped_target: OsString| {
+                    if config.hyperlink {
+                        create_hyperlink(&escaped_target, path)
+                    } else {
+                        escaped_target
+                    }
+                };
+
                 // We might as well color the symlink output after the arrow.
                 // This makes extra system calls, but provides important information that
                 // people run `ls -l --color` are very interested in.
                 if let Some(style_manager) = &mut state.style_manager {
-                    let escaped_target = escape_name_with_locale(target_path.as_os_str(), config);
+                    let escaped_target = link_target(locale_aware_escape_name(
+                        target_path.as_os_str(),
+                        config.quoting_style,
+                    ));
                     // We get the absolute path to be able to construct PathData with valid Metadata.
                     // This is because relative symlinks will fail to get_metadata.
                     let mut absolute_target = target_path.clone();
@@ -3436,6 +3637,8 @@ fn display_item_name(
                             absolute_target = parent.join(absolute_target);
                         }
                     }
+                    let absolute_target =
+                        resolve_relative(&absolute_target).unwrap_or(absolute_target);
 
                     match fs::canonicalize(&absolute_target) {
                         Ok(resolved_target) => {
@@ -3483,7 +3686,10 @@ fn display_item_name(
                 } else {
                     // If no coloring is required, we just use target as is.
                     // Apply the right quoting
-                    name.push(escape_name_with_locale(target_path.as_os_str(), config));
+                    name.push(link_target(locale_aware_escape_name(
+                        target_path.as_os_str(),
+                        config.quoting_style,
+                    )));
                 }
             }
             Err(err) => {
@@ -3518,11 +3724,55 @@ fn display_item_name(
     }
 }
 
+/// The host name put in `file://` URIs, looked up once per run.
+fn hyperlink_hostname() -> &'static str {
+    static HOSTNAME: OnceLock<String> = OnceLock::new();
+    HOSTNAME.get_or_init(|| {
+        // There is no host name to report on WASM.
+        #[cfg(target_family = "wasm")]
+        let hostname = OsString::new();
+        #[cfg(not(target_family = "wasm"))]
+        let hostname = hostname::get().unwrap_or_default();
+        hostname.to_string_lossy().into_owned()
+    })
+}
+
+/// Resolve `path` to an absolute path without symlinks, like GNU's
+/// `canonicalize_filename_mode (name, CAN_MISSING)`: the components that
+/// do not exist, e.g. the target of a broken symlink, are kept as they are.
+fn absolute_name(path: &Path) -> PathBuf {
+    // Give up on symlink loops like the kernel does.
+    const MAX_SYMLINKS: usize = 40;
+
+    let mut path = resolve_relative(path).unwrap_or_else(|_| path.to_path_buf());
+    for _ in 0..MAX_SYMLINKS {
+        if let Ok(resolved) = fs::canonicalize(&path) {
+            return resolved;
+        }
+        match path.read_link() {
+            Ok(target) => {
+                path = match path.parent() {
+                    Some(parent) => parent.join(target),
+                    None => target,
+                };
+            }
+            Err(_) => break,
+        }
+    }
+
+    // The last component does not exist, so resolve what leads up to it.
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(file_name)) if !parent.as_os_str().is_empty() => {
+            absolute_name(parent).join(file_name)
+        }
+        _ => std::path::absolute(&path).unwrap_or(path),
+    }
+}
+
 fn create_hyperlink(name: &OsStr, path: &PathData) -> OsString {
-    let hostname = hostname::get().unwrap_or_else(|_| OsString::from(""));
-    let hostname = hostname.to_string_lossy();
+    let hostname = hyperlink_hostname();
 
-    let absolute_path = fs::canonicalize(path.path()).unwrap_or_default();
+    let absolute_path = absolute_name(path.path());
 
     // Get bytes for URL encoding in a cross-platform way
     let absolute_path_bytes = os_str_as_bytes_lossy(absolute_path.as_os_str());
@@ -3571,6 +3821,20 @@ fn display_inode(metadata: &Metadata) -> String {
     get_inode(metadata)
 }
 
+/// Why [`read_security_context`] could not read a security context.
+enum SecurityContextError {
+    /// The symlink to dereference is broken.
+    Dereference(std::io::Error),
+    /// The security module failed to report a context.
+    #[cfg(all(feature = "selinux", any(target_os = "linux", target_os = "android")))]
+    Lookup,
+    /// The context is not valid UTF-8; it comes with its lossy conversion.
+    #[cfg(all(feature = "selinux", any(target_os = "linux", target_os = "android")))]
+    NotUtf8(String, std::string::FromUtf8Error),
+}
+
+static SUBSTITUTE_STRING: &str = "?";
+
 /// This returns the `SELinux` security context as UTF8 `String`.
 /// In the long term this should be changed to [`OsStr`], see discussions at #2621/#2656
 fn get_security_context<'a>(
@@ -3578,59 +3842,78 @@ fn get_security_context<'a>(
     must_dereference: bool,
     config: &'a Config,
 ) -> Cow<'a, str> {
-    static SUBSTITUTE_STRING: &str = "?";
-
-    // If we must dereference, ensure that the symlink is actually valid even if the system
-    // does not support SELinux.
-    // Conforms to the GNU coreutils where a dangling symlink results in exit code 1.
-    if must_dereference {
-        if let Err(err) = get_metadata_with_deref_opt(path, must_dereference) {
-            // The Path couldn't be dereferenced, so return early and set exit code 1
-            // to indicate a minor error
+    match read_security_context(path, must_dereference, config) {
+        Ok(context) => context,
+        Err(SecurityContextError::Dereference(err)) => {
+            // The Path couldn't be dereferenced, so set exit code 1 to
+            // indicate a minor error.
             // Only show error when context display is requested to avoid duplicate messages
             if config.context {
                 show!(LsError::IOErrorContext(path.to_path_buf(), err, false));
             }
-            return Cow::Borrowed(SUBSTITUTE_STRING);
+            Cow::Borrowed(SUBSTITUTE_STRING)
+        }
+        #[cfg(all(feature = "selinux", any(target_os = "linux", target_os = "android")))]
+        Err(SecurityContextError::Lookup) => {
+            // TODO: show the actual reason why it failed
+            show_warning!(
+                "{}",
+                translate!(
+                    "ls-warning-failed-to-get-security-context",
+                    "path" => path.quote().to_string()
+                )
+            );
+            Cow::Borrowed(SUBSTITUTE_STRING)
+        }
+        #[cfg(all(feature = "selinux", any(target_os = "linux", target_os = "android")))]
+        Err(SecurityContextError::NotUtf8(lossy, e)) => {
+            show_warning!(
+                "{}",
+                translate!(
+                    "ls-warning-getting-security-context",
+                    "path" => path.quote().to_string(),
+                    "error" => e.to_string()
+                )
+            );
+            Cow::Owned(lossy)
         }
     }
+}
+
+/// Read the security context of `path` without reporting anything, so that
+/// it can be done ahead of time and on any thread.
+fn read_security_context(
+    path: &Path,
+    must_dereference: bool,
+    config: &Config,
+) -> Result<Cow<'static, str>, SecurityContextError> {
+    // If we must dereference, ensure that the symlink is actually valid even if the system
+    // does not support SELinux.
+    // Conforms to the GNU coreutils where a dangling symlink results in exit code 1.
+    if must_dereference {
+        get_metadata_with_deref_opt(path, must_dereference)
+            .map_err(SecurityContextError::Dereference)?;
+    }
 
     #[cfg(all(feature = "selinux", any(target_os = "linux", target_os = "android")))]
     if config.selinux_supported {
-        match selinux::SecurityContext::of_path(path, must_dereference, false) {
-            Err(_r) => {
-                // TODO: show the actual reason why it failed
-                show_warning!(
-                    "{}",
-                    translate!(
-                        "ls-warning-failed-to-get-security-context",
-                        "path" => path.quote().to_string()
-                    )
-                );
-                return Cow::Borrowed(SUBSTITUTE_STRING);
-            }
-            Ok(None) => return Cow::Borrowed(SUBSTITUTE_STRING),
+        return match selinux::SecurityContext::of_path(path, must_dereference, false) {
+            Err(_r) => Err(SecurityContextError::Lookup),
+            Ok(None) => Ok(Cow::Borrowed(SUBSTITUTE_STRING)),
             Ok(Some(context)) => {
                 let context = context.as_bytes();
 
                 let context = context.strip_suffix(&[0]).unwrap_or(context);
 
-                let res: String = String::from_utf8(context.to_vec()).unwrap_or_else(|e| {
-                    show_warning!(
-                        "{}",
-                        translate!(
-                            "ls-warning-getting-security-context",
-                            "path" => path.quote().to_string(),
-                            "error" => e.to_string()
-                        )
-                    );
-
-                    String::from_utf8_lossy(context).to_string()
-                });
-
-                return Cow::Owned(res);
+                match String::from_utf8(context.to_vec()) {
+                    Ok(res) => Ok(Cow::Owned(res)),
+                    Err(e) => Err(SecurityContextError::NotUtf8(
+                        String::from_utf8_lossy(context).to_string(),
+                        e,
+                    )),
+                }
             }
-        }
+        };
     }
 
     #[cfg(all(feature = "smack", target_os = "linux"))]
@@ -3643,11 +3926,17 @@ fn get_security_context<'a>(
             path.to_path_buf()
         };
 
-        return uucore::smack::get_smack_label_for_path(&target_path)
-            .map_or(Cow::Borrowed(SUBSTITUTE_STRING), Cow::Owned);
+        return Ok(uucore::smack::get_smack_label_for_path(&target_path)
+            .map_or(Cow::Borrowed(SUBSTITUTE_STRING), Cow::Owned));
     }
 
-    Cow::Borrowed(SUBSTITUTE_STRING)
+    #[cfg(not(any(
+        all(feature = "selinux", any(target_os = "linux", target_os = "android")),
+        all(feature = "smack", target_os = "linux")
+    )))]
+    let _ = config;
+
+    Ok(Cow::Borrowed(SUBSTITUTE_STRING))
 }
 
 #[cfg(unix)]
@@ -3667,6 +3956,7 @@ fn calculate_padding_collection(
         minor: 1,
         block_size: 1,
     };
+    let mut has_alternate_access = false;
 
     for item in items {
         #[cfg(unix)]
@@ -3681,32 +3971,26 @@ fn calculate_padding_collection(
 
         if config.alloc_size {
             if let Some(md) = item.metadata() {
-                let block_size_len = display_size(get_block_size(md, config), config).len();
+                let block_size_len = display_blocks(get_block_size(md, config), config).len();
                 padding_collections.block_size = block_size_len.max(padding_collections.block_size);
             }
         }
 
         if config.format == Format::Long {
-            let context_len = item.security_context(config).len();
+            let security_context = item.security_context(config);
             let (link_count_len, uname_len, group_len, size_len, major_len, minor_len) =
                 display_dir_entry_size(item, config, state);
             padding_collections.link_count = link_count_len.max(padding_collections.link_count);
             padding_collections.uname = uname_len.max(padding_collections.uname);
             padding_collections.group = group_len.max(padding_collections.group);
             if config.context {
-                padding_collections.context = context_len.max(padding_collections.context);
+                padding_collections.context =
+                    display_width(security_context).max(padding_collections.context);
             }
 
-            // correctly align columns when some files have capabilities/ACLs and others do not
-            {
-                #[cfg(any(not(unix), target_os = "android", target_os = "macos"))]
-                // TODO: See how Mac should work here
-                let is_acl_set = false;
-                #[cfg(all(unix, not(any(target_os = "android", target_os = "macos"))))]
-                let is_acl_set = has_acl(item.display_name());
-                if context_len > 1 || is_acl_set {
-                    padding_collections.link_count += 1;
-                }
+            // the "." or "+" after the permissions takes up a column of its own
+            if !has_alternate_access {
+                has_alternate_access = security_context.len() > 1 || item.has_acl();
             }
 
             if items.len() == 1usize {
@@ -3723,6 +4007,11 @@ fn calculate_padding_collection(
         }
     }
 
+    // correctly align columns when some files have capabilities/ACLs and others do not
+    if has_alternate_access {
+        padding_collections.link_count += 1;
+    }
+
     padding_collections
 }
 
@@ -3744,12 +4033,12 @@ fn calculate_padding_collection(
     for item in items {
         if config.alloc_size {
             if let Some(md) = item.metadata() {
-                let block_size_len = display_size(get_block_size(md, config), config).len();
+                let block_size_len = display_blocks(get_block_size(md, config), config).len();
                 padding_collections.block_size = block_size_len.max(padding_collections.block_size);
             }
         }
 
-        let context_len = item.security_context(config).len();
+        let context_len = display_width(item.security_context(config));
         let (link_count_len, uname_len, group_len, size_len, _major_len, _minor_len) =
             display_dir_entry_size(item, config, state);
         padding_collections.link_count = link_count_len.max(padding_collections.link_count);
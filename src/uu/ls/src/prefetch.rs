@@ -0,0 +1,114 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Gathering what is to be shown about the entries of a large directory on
+//! several threads.
+//!
+//! Listing a directory with hundreds of thousands of entries is dominated by
+//! the `stat` call made for each of them, and with `-l` or `-Z` by the
+//! lookups of access control lists and security contexts. These do not
+//! depend on each other, so [`prefetch`] makes them all at once on a thread
+//! pool and fills the lazily initialized fields of [`PathData`] with the
+//! results, before the entries are sorted and printed as usual.
+//!
+//! Nothing is reported from the pool: a lookup that fails leaves its field
+//! empty, so that it is made again in order and its error shows up where it
+//! would without the prefetch.
+
+use rayon::prelude::*;
+use std::fs::Metadata;
+use std::path::Path;
+
+use super::{Config, Format, IndicatorStyle, PathData, Sort};
+use super::{get_metadata_with_deref_opt, read_security_context};
+
+/// Below this many entries, starting the threads costs more than it saves.
+/// The same goes for a pool of one thread, which would only do the lookups
+/// earlier.
+const MIN_ENTRIES: usize = 256;
+
+/// The results of the lookups for one entry.
+struct Prefetched {
+    metadata: Option<Metadata>,
+    acl: Option<bool>,
+    security_context: Option<Box<str>>,
+}
+
+/// Look up in parallel what `config` needs to know about `entries`.
+pub(crate) fn prefetch(entries: &[PathData], config: &Config) {
+    let long = config.format == Format::Long;
+    let wants_metadata = long
+        || config.json
+        || config.alloc_size
+        || config.color.is_some()
+        || config.indicator_style == IndicatorStyle::Classify
+        || matches!(config.sort, Sort::Time | Sort::Size)
+        || inode(config);
+    // -l shows a "." for files with a security context, so it needs them too
+    let wants_context = long || config.context;
+    if entries.len() < MIN_ENTRIES
+        || rayon::current_num_threads() < 2
+        || !(wants_metadata || wants_context)
+    {
+        return;
+    }
+
+    let jobs: Vec<(&Path, bool)> = entries
+        .iter()
+        .map(|entry| (entry.path(), entry.must_dereference))
+        .collect();
+    let results: Vec<Prefetched> = jobs
+        .into_par_iter()
+        .map(|(path, must_dereference)| Prefetched {
+            metadata: if wants_metadata {
+                get_metadata_with_deref_opt(path, must_dereference).ok()
+            } else {
+                None
+            },
+            acl: long.then(|| acl(path)),
+            security_context: if wants_context {
+                read_security_context(path, must_dereference, config)
+                    .ok()
+                    .map(Into::into)
+            } else {
+                None
+            },
+        })
+        .collect();
+
+    for (entry, result) in entries.iter().zip(results) {
+        // The cells may have been filled while the entry was created, in
+        // which case the values there are just as good.
+        if let Some(metadata) = result.metadata {
+            let _ = entry.md.set(Some(metadata));
+        }
+        if let Some(acl) = result.acl {
+            let _ = entry.acl.set(acl);
+        }
+        if let Some(security_context) = result.security_context {
+            let _ = entry.security_context.set(security_context);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn inode(config: &Config) -> bool {
+    config.inode
+}
+
+#[cfg(not(unix))]
+fn inode(_config: &Config) -> bool {
+    false
+}
+
+#[cfg(all(unix, not(any(target_os = "android", target_os = "macos"))))]
+fn acl(path: &Path) -> bool {
+    uucore::fsxattr::has_acl(path)
+}
+
+#[cfg(any(not(unix), target_os = "android", target_os = "macos"))]
+fn acl(_path: &Path) -> bool {
+    false
+}
@@ -0,0 +1,433 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Directory listing on WASM.
+//!
+//! `std::fs` cannot enumerate directories on `wasm32-unknown-unknown`, and
+//! `std::fs::Metadata` cannot be constructed by a host, so this lists the
+//! entries reported by [`uucore::wasm_io::read_dir`] and describes them with
+//! [`uucore::wasm_io::metadata`] instead of going through
+//! [`PathData`](crate::PathData).
+
+use std::cmp::Reverse;
+use std::ffi::OsString;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use uucore::entries;
+use uucore::error::UResult;
+use uucore::format::machine::{Record, wasm_file_type_name};
+use uucore::fsext::MetadataTimeField;
+use uucore::os_str_as_bytes_lossy;
+use uucore::quoting_style::{locale_aware_escape_dir_name, locale_aware_escape_name};
+use uucore::show;
+use uucore::time::{FormatSystemTimeFallback, format_system_time};
+use uucore::translate;
+use uucore::version_cmp::version_cmp;
+use uucore::wasm_io::{self, DirEntryInfo, WasmMetadata};
+
+use crate::{
+    Config, Dereference, Files, Format, IndicatorStyle, LsError, Sort, Stdout, display_file_size,
+    display_size, is_ignored, os_str_starts_with, stdout, write_names, write_os_str,
+};
+
+/// An entry to display, either from the command line or from a listing.
+struct WasmEntry {
+    display_name: OsString,
+    path: PathBuf,
+    is_dir: bool,
+    is_symlink: bool,
+    metadata: Option<WasmMetadata>,
+}
+
+impl WasmEntry {
+    fn from_dir_entry(dir: &Path, entry: DirEntryInfo) -> Self {
+        Self {
+            path: dir.join(&entry.name),
+            display_name: entry.name,
+            is_dir: entry.is_dir,
+            is_symlink: entry.is_symlink,
+            metadata: None,
+        }
+    }
+
+    fn dot(path: &Path, name: &str) -> Self {
+        Self {
+            display_name: name.into(),
+            path: path.to_path_buf(),
+            is_dir: true,
+            is_symlink: false,
+            metadata: None,
+        }
+    }
+
+    fn modified(&self, config: &Config) -> Option<SystemTime> {
+        // Only the modification time is known on WASM.
+        match config.time {
+            MetadataTimeField::Modification => self.metadata.as_ref()?.modified,
+            _ => None,
+        }
+    }
+}
+
+pub fn list(locs: Vec<&Path>, config: &Config) -> UResult<()> {
+    let mut out = BufWriter::new(stdout());
+    let mut files = Vec::new();
+    let mut dirs = Vec::new();
+    let initial_locs_len = locs.len();
+
+    for loc in locs {
+        let entry = WasmEntry {
+            display_name: loc.as_os_str().to_os_string(),
+            path: loc.to_path_buf(),
+            is_dir: false,
+            is_symlink: wasm_io::symlink_metadata(loc).is_ok_and(|md| md.is_symlink()),
+            metadata: None,
+        };
+        // Like elsewhere, `-l` shows a symbolic link to a directory itself
+        // rather than the directory's contents.
+        let show_link = entry.is_symlink && matches!(config.dereference, Dereference::None);
+        match wasm_io::read_dir(loc) {
+            Ok(listing) if !(config.directory || show_link) => dirs.push((entry, listing)),
+            Ok(_) => files.push(WasmEntry {
+                is_dir: true,
+                ..entry
+            }),
+            // A dangling symbolic link is still listed.
+            Err(_) if entry.is_symlink || wasm_io::file_exists(loc) => files.push(entry),
+            Err(err) => show!(LsError::IOErrorContext(loc.to_path_buf(), err, true)),
+        }
+    }
+
+    load_metadata(&mut files, config);
+    sort_entries(&mut files, config);
+    dirs.sort_by(|(a, _), (b, _)| compare_entries(a, b, config));
+    if config.reverse {
+        dirs.reverse();
+    }
+
+    display_entries(&files, config, false, &mut out)?;
+
+    let mut first = files.is_empty();
+    for (dir, listing) in dirs {
+        let show_heading = initial_locs_len > 1 || config.recursive;
+        enter_directory(
+            &dir.path,
+            listing,
+            show_heading,
+            &mut first,
+            config,
+            &mut out,
+        )?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn enter_directory(
+    path: &Path,
+    listing: Vec<DirEntryInfo>,
+    show_heading: bool,
+    first: &mut bool,
+    config: &Config,
+    out: &mut BufWriter<Stdout>,
+) -> UResult<()> {
+    if show_heading && !config.json {
+        if !*first {
+            writeln!(out)?;
+        }
+        write_os_str(
+            out,
+            &locale_aware_escape_dir_name(path.as_os_str(), config.quoting_style),
+        )?;
+        writeln!(out, ":")?;
+    }
+    *first = false;
+
+    let mut entries = if config.files == Files::All {
+        vec![
+            WasmEntry::dot(path, "."),
+            WasmEntry::dot(&path.join(".."), ".."),
+        ]
+    } else {
+        Vec::new()
+    };
+    entries.extend(
+        listing
+            .into_iter()
+            .filter(|entry| should_display(entry, config))
+            .map(|entry| WasmEntry::from_dir_entry(path, entry)),
+    );
+    out.flush()?;
+    load_metadata(&mut entries, config);
+    sort_entries(&mut entries, config);
+    display_entries(&entries, config, true, out)?;
+
+    if config.recursive {
+        for entry in entries
+            .iter()
+            .skip(if config.files == Files::All { 2 } else { 0 })
+            .filter(|entry| entry.is_dir && !entry.is_symlink)
+        {
+            match wasm_io::read_dir(&entry.path) {
+                Ok(listing) => enter_directory(&entry.path, listing, true, first, config, out)?,
+                Err(err) => {
+                    out.flush()?;
+                    show!(LsError::IOErrorContext(entry.path.clone(), err, false));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn should_display(entry: &DirEntryInfo, config: &Config) -> bool {
+    let hidden = os_str_as_bytes_lossy(&entry.name).starts_with(b".");
+    !(config.files == Files::Normal && hidden) && !is_ignored(&entry.name, config)
+}
+
+/// Metadata is only queried when the output depends on it.
+fn load_metadata(entries: &mut [WasmEntry], config: &Config) {
+    if config.format != Format::Long
+        && !config.json
+        && !matches!(config.sort, Sort::Size | Sort::Time)
+    {
+        return;
+    }
+    for entry in entries {
+        let metadata = if matches!(config.dereference, Dereference::All) {
+            wasm_io::metadata(&entry.path)
+        } else {
+            wasm_io::symlink_metadata(&entry.path)
+        };
+        match metadata {
+            Ok(metadata) => entry.metadata = Some(metadata),
+            Err(err) => show!(LsError::IOErrorContext(entry.path.clone(), err, false)),
+        }
+    }
+}
+
+fn compare_entries(a: &WasmEntry, b: &WasmEntry, config: &Config) -> std::cmp::Ordering {
+    match config.sort {
+        Sort::Size => {
+            let len = |entry: &WasmEntry| entry.metadata.as_ref().map_or(0, WasmMetadata::len);
+            Reverse(len(a))
+                .cmp(&Reverse(len(b)))
+                .then(a.display_name.cmp(&b.display_name))
+        }
+        Sort::Time => {
+            let time = |entry: &WasmEntry| entry.modified(config).unwrap_or(UNIX_EPOCH);
+            Reverse(time(a))
+                .cmp(&Reverse(time(b)))
+                .then(a.display_name.cmp(&b.display_name))
+        }
+        Sort::Version => version_cmp(
+            os_str_as_bytes_lossy(a.path.as_os_str()).as_ref(),
+            os_str_as_bytes_lossy(b.path.as_os_str()).as_ref(),
+        ),
+        Sort::Extension => a
+            .path
+            .extension()
+            .cmp(&b.path.extension())
+            .then(a.path.file_stem().cmp(&b.path.file_stem())),
+        Sort::Width => a
+            .display_name
+            .len()
+            .cmp(&b.display_name.len())
+            .then(a.display_name.cmp(&b.display_name)),
+        _ => a.display_name.cmp(&b.display_name),
+    }
+}
+
+fn sort_entries(entries: &mut [WasmEntry], config: &Config) {
+    if config.sort != Sort::None {
+        entries.sort_by(|a, b| compare_entries(a, b, config));
+    }
+    if config.reverse {
+        entries.reverse();
+    }
+}
+
+fn indicator(entry: &WasmEntry, config: &Config) -> Option<char> {
+    match config.indicator_style {
+        IndicatorStyle::None => None,
+        _ if entry.is_dir && !entry.is_symlink => Some('/'),
+        IndicatorStyle::FileType | IndicatorStyle::Classify if entry.is_symlink => Some('@'),
+        _ => None,
+    }
+}
+
+fn display_name(entry: &WasmEntry, config: &Config) -> OsString {
+    let mut name = locale_aware_escape_name(&entry.display_name, config.quoting_style);
+    if let Some(c) = indicator(entry, config) {
+        name.push(c.to_string());
+    }
+    name
+}
+
+fn display_entries(
+    entries: &[WasmEntry],
+    config: &Config,
+    show_total: bool,
+    out: &mut BufWriter<Stdout>,
+) -> UResult<()> {
+    if config.json {
+        return display_json(entries, out);
+    }
+    if config.format == Format::Long {
+        return display_long(entries, config, show_total, out);
+    }
+    let names: Vec<OsString> = entries
+        .iter()
+        .map(|entry| display_name(entry, config))
+        .collect();
+    let quoted = names.iter().any(|name| os_str_starts_with(name, b"'"));
+    write_names(names.into_iter(), quoted, config, out)
+}
+
+/// Every file in the host's VFS belongs to the user running the builtin, as
+/// reported by the identity hooks. Without them, and for hard links, which do
+/// not exist on WASM, the columns use the same placeholders as other
+/// platforms without them.
+fn display_long(
+    entries: &[WasmEntry],
+    config: &Config,
+    show_total: bool,
+    out: &mut BufWriter<Stdout>,
+) -> UResult<()> {
+    if show_total {
+        let total: u64 = entries
+            .iter()
+            .filter_map(|entry| entry.metadata.as_ref())
+            .map(WasmMetadata::len)
+            .sum();
+        write!(
+            out,
+            "{}{}",
+            translate!("ls-total", "size" => display_size(total, config)),
+            config.line_ending
+        )?;
+    }
+
+    let sizes: Vec<String> = entries
+        .iter()
+        .map(|entry| match &entry.metadata {
+            Some(md) => display_file_size(md.len().div_ceil(config.file_size_block_size), config),
+            None => "?".to_string(),
+        })
+        .collect();
+    let size_width = sizes.iter().map(String::len).max().unwrap_or(0);
+
+    // Without a clock hook the current time is unknown, so the "recent"
+    // format is used for all dates.
+    let recent_time_range =
+        wasm_io::now().map(|now| (now - Duration::new(31_556_952 / 2, 0))..=now);
+
+    let owner = display_id(wasm_io::uid(), entries::uid2usr, config, "somebody");
+    let group = display_id(wasm_io::gid(), entries::gid2grp, config, "somegroup");
+
+    for (entry, size) in entries.iter().zip(sizes) {
+        let mut line = Vec::with_capacity(128);
+        match &entry.metadata {
+            Some(md) => line.extend(md.display_permissions(true).as_bytes()),
+            None => line.extend(b"??????????"),
+        }
+        line.extend(b" 1");
+        if config.long.owner || config.long.author {
+            write!(line, " {owner}")?;
+        }
+        if config.long.group {
+            write!(line, " {group}")?;
+        }
+        write!(line, " {size:>size_width$} ")?;
+        match entry.modified(config) {
+            Some(time) => {
+                let fmt = match (&config.time_format_older, &recent_time_range) {
+                    (Some(older), Some(recent)) if !recent.contains(&time) => older,
+                    _ => &config.time_format_recent,
+                };
+                format_system_time(&mut line, time, fmt, FormatSystemTimeFallback::Integer)?;
+            }
+            None => line.extend(b"???"),
+        }
+        line.push(b' ');
+        write_os_str(&mut line, &display_name(entry, config))?;
+        if entry
+            .metadata
+            .as_ref()
+            .is_some_and(WasmMetadata::is_symlink)
+        {
+            match wasm_io::read_link(&entry.path) {
+                Ok(target) => {
+                    line.extend(b" -> ");
+                    write_os_str(
+                        &mut line,
+                        &locale_aware_escape_name(target.as_os_str(), config.quoting_style),
+                    )?;
+                }
+                Err(err) => show!(LsError::IOErrorContext(entry.path.clone(), err, false)),
+            }
+        }
+        line.extend(config.line_ending.to_string().as_bytes());
+        out.write_all(&line)?;
+    }
+    Ok(())
+}
+
+/// The `--json` records of `entries`, with the same keys as on other
+/// platforms. Hosts report neither hard links nor inodes.
+fn display_json(entries: &[WasmEntry], out: &mut BufWriter<Stdout>) -> UResult<()> {
+    let uid = wasm_io::uid();
+    let gid = wasm_io::gid();
+    let owner = uid.map(|id| entries::uid2usr(id).unwrap_or_else(|_| id.to_string()));
+    let group = gid.map(|id| entries::gid2grp(id).unwrap_or_else(|_| id.to_string()));
+
+    for entry in entries {
+        let md = entry.metadata.as_ref();
+        let mut record = Record::new();
+        record
+            .os_string("name", &entry.display_name)
+            .os_string("path", entry.path.as_os_str())
+            .optional_string("type", md.map(|md| wasm_file_type_name(md.file_type)))
+            .optional_unsigned("size", md.map(WasmMetadata::len))
+            .optional_unsigned("mode", md.map(|md| u64::from(md.mode & 0o7777)))
+            .optional_string(
+                "permissions",
+                md.map(|md| md.display_permissions(true)).as_deref(),
+            )
+            .null("nlink")
+            .null("inode")
+            .optional_unsigned("uid", uid.map(u64::from))
+            .optional_unsigned("gid", gid.map(u64::from))
+            .optional_string("owner", owner.as_deref())
+            .optional_string("group", group.as_deref())
+            .time("mtime", md.and_then(|md| md.modified));
+        let target = md
+            .filter(|md| md.is_symlink())
+            .and_then(|_| wasm_io::read_link(&entry.path).ok());
+        match target {
+            Some(target) => record.os_string("target", target.as_os_str()),
+            None => record.null("target"),
+        };
+        writeln!(out, "{record}")?;
+    }
+    Ok(())
+}
+
+/// The text of an owner or group column for `id`, or `placeholder` when the
+/// host did not report an identity.
+fn display_id(
+    id: Option<u32>,
+    name: impl Fn(u32) -> io::Result<String>,
+    config: &Config,
+    placeholder: &str,
+) -> String {
+    match id {
+        Some(id) if config.long.numeric_uid_gid => id.to_string(),
+        Some(id) => name(id).unwrap_or_else(|_| id.to_string()),
+        None => placeholder.to_string(),
+    }
+}
@@ -9,7 +9,9 @@ use clap::builder::ValueParser;
 use clap::parser::ValuesRef;
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use std::ffi::OsString;
-use std::io::{Write, stdout};
+use std::io::Write;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use std::path::{Path, PathBuf};
 #[cfg(all(unix, target_os = "linux"))]
 use uucore::error::FromIo;
@@ -18,6 +20,8 @@ use uucore::translate;
 
 #[cfg(not(windows))]
 use uucore::mode;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::stdout;
 use uucore::{display::Quotable, fs::dir_strip_dot_for_creation};
 use uucore::{format_usage, show_if_err};
 
@@ -40,6 +44,10 @@ pub struct Config<'a> {
     /// File permissions (octal).
     pub mode: u32,
 
+    /// The `-m` mode, applied again once a directory is created for the
+    /// special bits that `mode` alone cannot give it.
+    pub mode_string: Option<&'a String>,
+
     /// Print message for each created directory.
     pub verbose: bool,
 
@@ -92,6 +100,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
             let config = Config {
                 recursive,
                 mode,
+                mode_string: matches.get_one::<String>(options::MODE),
                 verbose,
                 set_security_context: set_security_context || context.is_some(),
                 context,
@@ -210,7 +219,7 @@ fn chmod(path: &Path, mode: u32) -> UResult<()> {
 // Create a directory at the given path.
 // Uses iterative approach instead of recursion to avoid stack overflow with deep nesting.
 fn create_dir(path: &Path, is_parent: bool, config: &Config) -> UResult<()> {
-    let path_exists = path.exists();
+    let path_exists = exists(path);
     if path_exists && !config.recursive {
         return Err(USimpleError::new(
             1,
@@ -240,7 +249,7 @@ fn create_dir(path: &Path, is_parent: bool, config: &Config) -> UResult<()> {
         // Second pass: create directories from root to leaf
         // Only create those that don't exist
         for dir in dirs_to_create.iter().rev() {
-            if !dir.exists() {
+            if !exists(dir) {
                 create_single_dir(dir, true, config)?;
             }
         }
@@ -288,16 +297,46 @@ fn create_dir_with_mode(path: &Path, mode: u32) -> std::io::Result<()> {
     std::fs::DirBuilder::new().mode(mode).create(path)
 }
 
-#[cfg(not(unix))]
+#[cfg(windows)]
 fn create_dir_with_mode(path: &Path, _mode: u32) -> std::io::Result<()> {
     std::fs::create_dir(path)
 }
 
+/// Create a directory in the host VFS. The VFS decides the permissions.
+#[cfg(target_family = "wasm")]
+fn create_dir_with_mode(path: &Path, _mode: u32) -> std::io::Result<()> {
+    uucore::wasm_io::create_dir(path)
+}
+
+/// Whether `path` exists, looking in the host VFS on WASM.
+fn exists(path: &Path) -> bool {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        path.exists()
+    }
+    #[cfg(target_family = "wasm")]
+    {
+        uucore::wasm_io::metadata(path).is_ok()
+    }
+}
+
+/// Whether `path` is a directory, looking in the host VFS on WASM.
+fn is_dir(path: &Path) -> bool {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        path.is_dir()
+    }
+    #[cfg(target_family = "wasm")]
+    {
+        uucore::wasm_io::metadata(path).is_ok_and(|md| md.is_dir())
+    }
+}
+
 // Helper function to create a single directory with appropriate permissions
 // `is_parent` argument is not used on windows
 #[allow(unused_variables)]
 fn create_single_dir(path: &Path, is_parent: bool, config: &Config) -> UResult<()> {
-    let path_exists = path.exists();
+    let path_exists = exists(path);
 
     // Calculate the mode to use for directory creation
     #[cfg(unix)]
@@ -332,6 +371,15 @@ fn create_single_dir(path: &Path, is_parent: bool, config: &Config) -> UResult<(
                 }
             }
 
+            // Set the set-user-ID and set-group-ID bits of an explicit mode,
+            // or clear the inherited set-group-ID bit if the mode says so.
+            #[cfg(unix)]
+            if let Some(m) = config.mode_string.filter(|_| !is_parent) {
+                mode::set_dir_mode(path, DEFAULT_PERM, m, mode::get_umask()).map_err_context(
+                    || translate!("mkdir-error-cannot-set-permissions", "path" => path.quote()),
+                )?;
+            }
+
             // Apply SELinux context if requested
             #[cfg(feature = "selinux")]
             if config.set_security_context && uucore::selinux::is_selinux_enabled() {
@@ -352,7 +400,7 @@ fn create_single_dir(path: &Path, is_parent: bool, config: &Config) -> UResult<(
             Ok(())
         }
 
-        Err(_) if path.is_dir() => {
+        Err(_) if is_dir(path) => {
             // Directory already exists - check if this is a logical directory creation
             // (i.e., not just a parent reference like "test_dir/..")
             let ends_with_parent_dir = matches!(
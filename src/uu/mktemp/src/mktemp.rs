@@ -367,10 +367,6 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     let matches = match uu_app().try_get_matches_from(&args) {
         Ok(m) => m,
         Err(e) => {
-            use uucore::clap_localization::handle_clap_error_with_exit_code;
-            if e.kind() == clap::error::ErrorKind::UnknownArgument {
-                handle_clap_error_with_exit_code(e, 1);
-            }
             if e.kind() == clap::error::ErrorKind::TooManyValues
                 && e.context().any(|(kind, val)| {
                     kind == clap::error::ContextKind::InvalidArg
@@ -382,7 +378,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
                     translate!("mktemp-error-too-many-templates"),
                 ));
             }
-            return Err(e.into());
+            return Err(uucore::clap_localization::clap_error_to_uerror(e, 1));
         }
     };
 
@@ -508,7 +504,7 @@ fn dry_exec(tmpdir: &Path, prefix: &str, rand: usize, suffix: &str) -> PathBuf {
 
     // Randomize.
     let bytes = &mut buf[prefix.len()..prefix.len() + rand];
-    rand::rng().fill(bytes);
+    uucore::rand_source::rng().fill(bytes);
     for byte in bytes {
         *byte = match *byte % 62 {
             v @ 0..=9 => v + b'0',
@@ -541,7 +537,7 @@ fn make_temp_dir(dir: &Path, prefix: &str, rand: usize, suffix: &str) -> UResult
     // The directory is created with these permission at creation time, using mkdir(3) syscall.
     // This is not relevant on Windows systems. See: https://docs.rs/tempfile/latest/tempfile/#security
     // `fs` is not imported on Windows anyways.
-    #[cfg(not(windows))]
+    #[cfg(unix)]
     builder.permissions(fs::Permissions::from_mode(0o700));
 
     match builder.tempdir_in(dir) {
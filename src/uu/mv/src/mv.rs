@@ -8,11 +8,13 @@
 mod error;
 #[cfg(unix)]
 mod hardlink;
+#[cfg(target_family = "wasm")]
+mod wasm;
 
 use clap::builder::ValueParser;
 use clap::error::ErrorKind;
 use clap::{Arg, ArgAction, ArgMatches, Command};
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use indicatif::ProgressStyle;
 
 #[cfg(all(unix, not(any(target_os = "macos", target_os = "redox"))))]
 use std::collections::HashMap;
@@ -35,6 +37,7 @@ use crate::hardlink::{
     with_optional_hardlink_context,
 };
 use uucore::backup_control::{self, source_is_target_backup};
+use uucore::copy::{self, CopyOptions};
 use uucore::display::Quotable;
 use uucore::error::{FromIo, UResult, USimpleError, UUsageError, set_exit_code};
 #[cfg(unix)]
@@ -47,6 +50,7 @@ use uucore::fs::{
 };
 #[cfg(all(unix, not(any(target_os = "macos", target_os = "redox"))))]
 use uucore::fsxattr;
+use uucore::progress::{self, MultiProgress, ProgressBar};
 #[cfg(all(feature = "selinux", any(target_os = "linux", target_os = "android")))]
 use uucore::selinux::set_selinux_security_context;
 use uucore::translate;
@@ -169,7 +173,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
             ErrorKind::TooFewValues,
             translate!("mv-error-insufficient-arguments", "arg_files" => ARG_FILES),
         );
-        uucore::clap_localization::handle_clap_error_with_exit_code(err, 1);
+        return Err(uucore::clap_localization::clap_error_to_uerror(err, 1));
     }
 
     let overwrite_mode = determine_overwrite_mode(&matches);
@@ -194,7 +198,11 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         .map(OsString::from);
 
     if let Some(ref maybe_dir) = target_dir {
-        if !Path::new(&maybe_dir).is_dir() {
+        #[cfg(not(target_family = "wasm"))]
+        let is_dir = Path::new(&maybe_dir).is_dir();
+        #[cfg(target_family = "wasm")]
+        let is_dir = uucore::wasm_io::metadata(maybe_dir).is_ok_and(|md| md.is_dir());
+        if !is_dir {
             return Err(MvError::TargetNotADirectory(maybe_dir.quote().to_string()).into());
         }
     }
@@ -364,6 +372,7 @@ fn parse_paths(files: &[OsString], opts: &Options) -> Vec<PathBuf> {
     }
 }
 
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
 fn handle_two_paths(source: &Path, target: &Path, opts: &Options) -> UResult<()> {
     if opts.backup == BackupMode::Simple && source_is_target_backup(source, target, &opts.suffix) {
         return Err(io::Error::new(
@@ -407,11 +416,12 @@ fn handle_two_paths(source: &Path, target: &Path, opts: &Options) -> UResult<()>
                 #[cfg(not(unix))]
                 let hardlink_params = (None, None);
 
+                let display_manager = opts.progress_bar.then(progress::multi_bar);
                 rename(
                     source,
                     target,
                     opts,
-                    None,
+                    display_manager.as_ref(),
                     hardlink_params.0,
                     hardlink_params.1,
                 )
@@ -449,11 +459,12 @@ fn handle_two_paths(source: &Path, target: &Path, opts: &Options) -> UResult<()>
         #[cfg(not(unix))]
         let hardlink_params = (None, None);
 
+        let display_manager = opts.progress_bar.then(progress::multi_bar);
         rename(
             source,
             target,
             opts,
-            None,
+            display_manager.as_ref(),
             hardlink_params.0,
             hardlink_params.1,
         )
@@ -543,6 +554,7 @@ fn assert_not_same_file(
     Ok(())
 }
 
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
 fn handle_multiple_paths(paths: &[PathBuf], opts: &Options) -> UResult<()> {
     if opts.no_target_dir {
         return Err(UUsageError::new(
@@ -559,6 +571,7 @@ fn handle_multiple_paths(paths: &[PathBuf], opts: &Options) -> UResult<()> {
 /// Execute the mv command. This moves 'source' to 'target', where
 /// 'target' is a directory. If 'target' does not exist, and source is a single
 /// file or directory, then 'source' will be renamed to 'target'.
+#[cfg(not(target_family = "wasm"))]
 pub fn mv(files: &[OsString], opts: &Options) -> UResult<()> {
     let paths = parse_paths(files, opts);
 
@@ -572,7 +585,14 @@ pub fn mv(files: &[OsString], opts: &Options) -> UResult<()> {
     }
 }
 
+/// Execute the mv command on WASM, moving files within the host VFS.
+#[cfg(target_family = "wasm")]
+pub fn mv(files: &[OsString], opts: &Options) -> UResult<()> {
+    wasm::mv(&parse_paths(files, opts), opts)
+}
+
 #[allow(clippy::cognitive_complexity)]
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
 fn move_files_into_dir(files: &[PathBuf], target_dir: &Path, options: &Options) -> UResult<()> {
     // remember the moved destinations for further usage
     let mut moved_destinations: HashSet<PathBuf> = HashSet::with_capacity(files.len());
@@ -596,7 +616,7 @@ fn move_files_into_dir(files: &[PathBuf], target_dir: &Path, options: &Options)
         return Err(MvError::NotADirectory(target_dir.quote().to_string()).into());
     }
 
-    let display_manager = options.progress_bar.then(MultiProgress::new);
+    let display_manager = options.progress_bar.then(progress::multi_bar);
 
     let count_progress = if let Some(ref display_manager) = display_manager {
         if files.len() > 1 {
@@ -865,12 +885,20 @@ fn rename_with_fallback(
                 with_optional_hardlink_context(
                     hardlink_tracker,
                     hardlink_scanner,
-                    |tracker, scanner| rename_file_fallback(from, to, Some(tracker), Some(scanner)),
+                    |tracker, scanner| {
+                        rename_file_fallback(
+                            from,
+                            to,
+                            display_manager,
+                            Some(tracker),
+                            Some(scanner),
+                        )
+                    },
                 )
             }
             #[cfg(not(unix))]
             {
-                rename_file_fallback(from, to)
+                rename_file_fallback(from, to, display_manager)
             }
         }
     })
@@ -958,10 +986,7 @@ fn rename_dir_fallback(
 
     let progress_bar = match (display_manager, total_size) {
         (Some(display_manager), Some(total_size)) => {
-            let template = "{msg}: [{elapsed_precise}] {wide_bar} {bytes:>7}/{total_bytes:7}";
-            let style = ProgressStyle::with_template(template).unwrap();
-            let bar = ProgressBar::new(total_size).with_style(style);
-            Some(display_manager.add(bar))
+            Some(display_manager.add(progress::bytes_bar(total_size)))
         }
         (_, _) => None,
     };
@@ -1145,7 +1170,7 @@ fn copy_file_with_hardlinks_helper(
         make_fifo(to)?;
     } else {
         // Copy a regular file.
-        fs::copy(from, to)?;
+        copy::copy_path(from, to, CopyOptions::default())?;
         // Copy xattrs, ignoring ENOTSUP errors (filesystem doesn't support xattrs)
         #[cfg(all(unix, not(any(target_os = "macos", target_os = "redox"))))]
         {
@@ -1159,6 +1184,7 @@ fn copy_file_with_hardlinks_helper(
 fn rename_file_fallback(
     from: &Path,
     to: &Path,
+    display_manager: Option<&MultiProgress>,
     #[cfg(unix)] hardlink_tracker: Option<&mut HardlinkTracker>,
     #[cfg(unix)] hardlink_scanner: Option<&HardlinkGroupScanner>,
 ) -> io::Result<()> {
@@ -1190,9 +1216,22 @@ fn rename_file_fallback(
         }
     }
 
-    // Regular file copy
-    fs::copy(from, to)
-        .map_err(|err| io::Error::new(err.kind(), translate!("mv-error-permission-denied")))?;
+    // Regular file copy, with a bar for the bytes copied if asked for
+    let progress_bar = display_manager.map(|display_manager| {
+        let total_size = from.metadata().map_or(0, |metadata| metadata.len());
+        display_manager.add(progress::bytes_bar(total_size))
+    });
+    copy::copy_path_with_progress(from, to, CopyOptions::default(), &mut |bytes| {
+        if let Some(pb) = &progress_bar {
+            pb.inc(bytes);
+        }
+    })
+    .map_err(|err| io::Error::new(err.kind(), translate!("mv-error-permission-denied")))?;
+    // Every file moved across file systems gets its own bar; don't leave
+    // them all on the screen once they are done.
+    if let Some(pb) = progress_bar {
+        pb.finish_and_clear();
+    }
 
     // Copy xattrs, ignoring ENOTSUP errors (filesystem doesn't support xattrs)
     #[cfg(all(unix, not(any(target_os = "macos", target_os = "redox"))))]
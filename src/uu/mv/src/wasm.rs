@@ -0,0 +1,195 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+// spell-checker:ignore sourcepath targetpath
+
+//! Moving files on WASM.
+//!
+//! Every move is a single [`uucore::wasm_io::rename`] call into the host
+//! VFS, so there is no copy-and-remove fallback, no hard link tracking and
+//! no progress bar. Overwrite, update and backup handling follow the native
+//! implementation.
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use uucore::backup_control::{self, BackupMode, source_is_target_backup};
+use uucore::display::Quotable;
+use uucore::error::{FromIo, UResult, USimpleError, UUsageError, set_exit_code};
+use uucore::fs::{is_same_file, path_ends_with_terminator, resolve_relative};
+use uucore::translate;
+use uucore::update_control::UpdateMode;
+use uucore::wasm_io::{self, WasmMetadata};
+use uucore::{prompt_yes, show};
+
+use crate::error::MvError;
+use crate::{Options, OverwriteMode};
+
+pub(crate) fn mv(paths: &[PathBuf], opts: &Options) -> UResult<()> {
+    if let Some(ref name) = opts.target_dir {
+        return move_files_into_dir(paths, Path::new(name), opts);
+    }
+
+    if paths.len() == 2 {
+        let (source, target) = (&paths[0], &paths[1]);
+        if is_dir(target) && !opts.no_target_dir {
+            return move_files_into_dir(&paths[..1], target, opts);
+        }
+        return move_file(source, target, opts);
+    }
+
+    if opts.no_target_dir {
+        return Err(UUsageError::new(
+            1,
+            translate!("mv-error-extra-operand", "operand" => paths.last().unwrap().quote()),
+        ));
+    }
+    let (target_dir, sources) = paths.split_last().unwrap();
+    move_files_into_dir(sources, target_dir, opts)
+}
+
+fn move_files_into_dir(files: &[PathBuf], target_dir: &Path, opts: &Options) -> UResult<()> {
+    if !is_dir(target_dir) {
+        return Err(MvError::NotADirectory(target_dir.quote().to_string()).into());
+    }
+
+    let mut moved_destinations = HashSet::with_capacity(files.len());
+    for sourcepath in files {
+        let Some(name) = sourcepath.file_name() else {
+            show!(MvError::NoSuchFile(sourcepath.quote().to_string()));
+            continue;
+        };
+        let targetpath = target_dir.join(name);
+
+        if moved_destinations.contains(&targetpath) && opts.backup != BackupMode::Numbered {
+            show!(USimpleError::new(
+                1,
+                translate!("mv-error-will-not-overwrite-just-created", "target" => targetpath.quote(), "source" => sourcepath.quote()),
+            ));
+            continue;
+        }
+
+        if let Err(e) = move_file(sourcepath, &targetpath, opts) {
+            show!(e);
+        }
+        moved_destinations.insert(targetpath);
+    }
+    Ok(())
+}
+
+fn move_file(source: &Path, target: &Path, opts: &Options) -> UResult<()> {
+    if opts.backup == BackupMode::Simple && source_is_target_backup(source, target, &opts.suffix) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            translate!("mv-error-backup-might-destroy-source", "target" => target.quote(), "source" => source.quote()),
+        )
+        .into());
+    }
+
+    let Ok(source_md) = wasm_io::symlink_metadata(source) else {
+        return Err(if path_ends_with_terminator(source) {
+            MvError::CannotStatNotADirectory(source.quote().to_string()).into()
+        } else {
+            MvError::NoSuchFile(source.quote().to_string()).into()
+        });
+    };
+    let target_md = wasm_io::metadata(target).ok();
+
+    let (source_abs, target_abs) = (resolve_relative(source)?, resolve_relative(target)?);
+    if source_abs == target_abs || is_same_file(source, target) {
+        return Err(
+            MvError::SameFile(source.quote().to_string(), target.quote().to_string()).into(),
+        );
+    }
+    if source_md.is_dir() && target_abs.starts_with(&source_abs) {
+        return Err(MvError::SelfTargetSubdirectory(
+            source.quote().to_string(),
+            target.quote().to_string(),
+        )
+        .into());
+    }
+
+    match &target_md {
+        Some(md) if md.is_dir() && !source_md.is_dir() => {
+            return Err(MvError::NonDirectoryToDirectory(
+                source.quote().to_string(),
+                target.quote().to_string(),
+            )
+            .into());
+        }
+        Some(md) if !md.is_dir() && source_md.is_dir() && opts.no_target_dir => {
+            return Err(MvError::DirectoryToNonDirectory(target.quote().to_string()).into());
+        }
+        _ => {}
+    }
+
+    rename(source, target, &source_md, target_md.as_ref(), opts).map_err_context(|| {
+        translate!("mv-error-cannot-move", "source" => source.quote(), "target" => target.quote())
+    })
+}
+
+fn rename(
+    from: &Path,
+    to: &Path,
+    from_md: &WasmMetadata,
+    to_md: Option<&WasmMetadata>,
+    opts: &Options,
+) -> io::Result<()> {
+    let mut backup_path = None;
+
+    if let Some(to_md) = to_md {
+        match opts.update {
+            UpdateMode::None => return Ok(()),
+            UpdateMode::NoneFail => {
+                return Err(io::Error::other(
+                    translate!("mv-error-not-replacing", "target" => to.quote()),
+                ));
+            }
+            UpdateMode::IfOlder if from_md.modified <= to_md.modified => return Ok(()),
+            _ => {}
+        }
+
+        match opts.overwrite {
+            OverwriteMode::NoClobber => return Ok(()),
+            OverwriteMode::Interactive => {
+                if !prompt_yes!(
+                    "{}",
+                    translate!("mv-prompt-overwrite", "target" => to.quote())
+                ) {
+                    set_exit_code(1);
+                    return Ok(());
+                }
+            }
+            OverwriteMode::Force | OverwriteMode::Default => {}
+        }
+
+        backup_path = backup_control::get_backup_path(opts.backup, to, &opts.suffix);
+        if let Some(ref backup_path) = backup_path {
+            wasm_io::rename(to, backup_path)?;
+        } else if to_md.is_dir() {
+            if !wasm_io::read_dir(to)?.is_empty() {
+                return Err(io::Error::other(translate!("mv-error-directory-not-empty")));
+            }
+            wasm_io::remove_dir(to)?;
+        }
+    }
+
+    wasm_io::rename(from, to)?;
+
+    if opts.verbose {
+        let message = if let Some(path) = backup_path {
+            translate!("mv-verbose-renamed-with-backup", "from" => from.quote(), "to" => to.quote(), "backup" => path.quote())
+        } else {
+            translate!("mv-verbose-renamed", "from" => from.quote(), "to" => to.quote())
+        };
+        let _ = writeln!(wasm_io::stdout(), "{message}");
+    }
+    Ok(())
+}
+
+fn is_dir(path: &Path) -> bool {
+    wasm_io::metadata(path).is_ok_and(|md| md.is_dir())
+}
@@ -250,21 +250,20 @@ fn remove_suffix(i: f64, s: Option<Suffix>, u: Unit) -> Result<f64> {
     }
 }
 
-fn transform_from(s: &str, opts: &TransformOptions, max_whitespace: usize) -> Result<f64> {
+/// Parses a number, removing its suffix and applying `--from-unit`.
+///
+/// Also returns the precision that is kept when the number is printed without
+/// `--to` or `--format`: the count of digits after the dot, or zero if the
+/// number had a suffix, as the suffix sets the precision instead.
+fn transform_from(s: &str, opts: &TransformOptions, max_whitespace: usize) -> Result<(f64, usize)> {
     let (i, suffix) = parse_suffix(s, opts.from, max_whitespace)
         .map_err(|original| detailed_error_message(s, opts.from).unwrap_or(original))?;
-    let i = i * (opts.from_unit as f64);
-
-    remove_suffix(i, suffix, opts.from).map(|n| {
-        // GNU numfmt doesn't round values if no --from argument is provided by the user
-        if opts.from == Unit::None {
-            if n == -0.0 { 0.0 } else { n }
-        } else if n < 0.0 {
-            -n.abs().ceil()
-        } else {
-            n.ceil()
-        }
-    })
+    let precision = match suffix {
+        Some(_) => 0,
+        None => parse_implicit_precision(s),
+    };
+
+    remove_suffix(i, suffix, opts.from).map(|n| (n * (opts.from_unit as f64), precision))
 }
 
 /// Divide numerator by denominator, with rounding.
@@ -298,24 +297,40 @@ pub fn div_round(n: f64, d: f64, method: RoundMethod) -> f64 {
     let v = n / d;
 
     if v.abs() < 10.0 {
-        method.round(10.0 * v) / 10.0
+        round_with_precision(v, method, 1)
     } else {
-        method.round(v)
+        round_with_precision(v, method, 0)
     }
 }
 
 /// Rounds to the specified number of decimal points.
+///
+/// The scaled number is first cut to 15 significant digits, which drops the
+/// error of its binary representation: 9.95 is stored as 9.9499999..., and
+/// must still be 9.95 when rounded down to two decimals.
+///
+/// A result of zero is always positive, so that e.g. -0.04 rounded up to one
+/// decimal is printed as "0.0", like GNU numfmt does.
 fn round_with_precision(n: f64, method: RoundMethod, precision: usize) -> f64 {
     let p = 10.0_f64.powf(precision as f64);
+    let scaled = p * n;
+    let scaled = format!("{scaled:.14e}").parse().unwrap_or(scaled);
+    let v = method.round(scaled) / p;
 
-    method.round(p * n) / p
+    if v == 0.0 { 0.0 } else { v }
 }
 
+/// Scales `n` down to the largest unit of `u` it reaches, and rounds it.
+///
+/// Without a precision from `--format`, numbers under 10 keep one decimal and
+/// larger ones are rounded to an integer. With one, the number is rounded to
+/// that many decimals, but never to more than the digits the scaling removed:
+/// 1234 with a precision of 5 is rounded to 1.234 (and printed as 1.23400k).
 fn consider_suffix(
     n: f64,
     u: Unit,
     round_method: RoundMethod,
-    precision: usize,
+    precision: Option<usize>,
 ) -> Result<(f64, Option<Suffix>)> {
     use crate::units::RawSuffix::{E, G, K, M, P, Q, R, T, Y, Z};
 
@@ -330,7 +345,7 @@ fn consider_suffix(
     };
 
     let i = match abs_n {
-        _ if abs_n <= bases[1] - 1.0 => return Ok((n, None)),
+        _ if abs_n < bases[1] => 0,
         _ if abs_n < bases[2] => 1,
         _ if abs_n < bases[3] => 2,
         _ if abs_n < bases[4] => 3,
@@ -344,46 +359,59 @@ fn consider_suffix(
         _ => return Err(translate!("numfmt-error-number-too-big")),
     };
 
-    let v = if precision > 0 {
-        round_with_precision(n / bases[i], round_method, precision)
-    } else {
-        div_round(n, bases[i], round_method)
+    let v = match precision {
+        Some(p) => round_with_precision(n / bases[i], round_method, p.min(3 * i)),
+        None => div_round(n, bases[i], round_method),
     };
 
     // check if rounding pushed us into the next base
-    if v.abs() >= bases[1] {
-        Ok((v / bases[1], Some((suffixes[i], with_i))))
+    let (v, i) = if v.abs() >= bases[1] {
+        (v / bases[1], i + 1)
     } else {
-        Ok((v, Some((suffixes[i - 1], with_i))))
+        (v, i)
+    };
+
+    match i {
+        0 => Ok((v, None)),
+        _ if i > suffixes.len() => Err(translate!("numfmt-error-number-too-big")),
+        _ => Ok((v, Some((suffixes[i - 1], with_i)))),
     }
 }
 
+/// Formats a number read by [`transform_from`], rounding it with
+/// `round_method`.
+///
+/// Without `--to`, the number is rounded to `precision` decimals, or to the
+/// precision it was read with. With it, a scaled number under 10 is printed
+/// with one decimal, and every other number without any, unless `precision`
+/// says otherwise.
 fn transform_to(
-    s: f64,
+    (n, implicit_precision): (f64, usize),
     opts: &TransformOptions,
     round_method: RoundMethod,
-    precision: usize,
+    precision: Option<usize>,
     unit_separator: &str,
 ) -> Result<String> {
-    let (i2, s) = consider_suffix(s, opts.to, round_method, precision)?;
-    let i2 = i2 / (opts.to_unit as f64);
+    let n = n / (opts.to_unit as f64);
+
+    if opts.to == Unit::None {
+        let precision = precision.unwrap_or(implicit_precision);
+        return Ok(format!(
+            "{:.precision$}",
+            round_with_precision(n, round_method, precision),
+        ));
+    }
+
+    let (v, s) = consider_suffix(n, opts.to, round_method, precision)?;
     Ok(match s {
-        None => {
+        Some(s) => {
+            let precision = precision.unwrap_or(usize::from(v != 0.0 && v.abs() < 10.0));
             format!(
-                "{:.precision$}",
-                round_with_precision(i2, round_method, precision),
-            )
-        }
-        Some(s) if precision > 0 => {
-            format!(
-                "{i2:.precision$}{unit_separator}{}",
+                "{v:.precision$}{unit_separator}{}",
                 DisplayableSuffix(s, opts.to),
             )
         }
-        Some(s) if i2.abs() < 10.0 => {
-            format!("{i2:.1}{unit_separator}{}", DisplayableSuffix(s, opts.to))
-        }
-        Some(s) => format!("{i2:.0}{unit_separator}{}", DisplayableSuffix(s, opts.to)),
+        None => format!("{v:.0$}", precision.unwrap_or(0)),
     })
 }
 
@@ -398,14 +426,6 @@ fn format_string(
         None => source,
     };
 
-    let precision = if let Some(p) = options.format.precision {
-        p
-    } else if options.transform.from == Unit::None && options.transform.to == Unit::None {
-        parse_implicit_precision(source_without_suffix)
-    } else {
-        0
-    };
-
     let number = transform_to(
         transform_from(
             source_without_suffix,
@@ -414,7 +434,7 @@ fn format_string(
         )?,
         &options.transform,
         options.round,
-        precision,
+        options.format.precision,
         &options.unit_separator,
     )?;
 
@@ -763,7 +783,7 @@ mod tests {
         use crate::options::RoundMethod;
         use crate::units::Unit;
 
-        let result = consider_suffix(1e27, Unit::Si, RoundMethod::FromZero, 0);
+        let result = consider_suffix(1e27, Unit::Si, RoundMethod::FromZero, None);
         assert!(result.is_ok());
         let (value, suffix) = result.unwrap();
         assert!(suffix.is_some());
@@ -771,7 +791,7 @@ mod tests {
         assert_eq!(raw_suffix as i32, RawSuffix::R as i32);
         assert_eq!(value, 1.0);
 
-        let result = consider_suffix(1e30, Unit::Si, RoundMethod::FromZero, 0);
+        let result = consider_suffix(1e30, Unit::Si, RoundMethod::FromZero, None);
         assert!(result.is_ok());
         let (value, suffix) = result.unwrap();
         assert!(suffix.is_some());
@@ -779,7 +799,7 @@ mod tests {
         assert_eq!(raw_suffix as i32, RawSuffix::Q as i32);
         assert_eq!(value, 1.0);
 
-        let result = consider_suffix(5e30, Unit::Si, RoundMethod::FromZero, 0);
+        let result = consider_suffix(5e30, Unit::Si, RoundMethod::FromZero, None);
         assert!(result.is_ok());
         let (value, suffix) = result.unwrap();
         assert!(suffix.is_some());
@@ -4,10 +4,13 @@
 // file that was distributed with this source code.
 // spell-checker:ignore (ToDO) multifile curr fnames fname xfrd fillloop mockstream
 
+use std::cmp;
 use std::fs::File;
-use std::io;
+use std::io::{self, Read, Seek, SeekFrom};
 #[cfg(unix)]
 use std::os::fd::{AsRawFd, FromRawFd};
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
 
 use uucore::display::Quotable;
 use uucore::show_error;
@@ -16,14 +19,36 @@ pub enum InputSource<'a> {
     FileName(&'a str),
     Stdin,
     #[allow(dead_code)]
-    Stream(Box<dyn io::Read>),
+    Stream(Box<dyn Read>),
+}
+
+/// When bytes must be skipped by reading them, they are read into a buffer
+/// of this size.
+const SKIP_BUFFER_SIZE: usize = 16 * 1024;
+
+/// The input currently being read.
+enum CurrentInput {
+    /// A file, or stdin if it can be used as one, which can be skipped
+    /// through without reading it.
+    File(File),
+    Stream(Box<dyn Read>),
+}
+
+impl Read for CurrentInput {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::File(f) => f.read(buf),
+            Self::Stream(s) => s.read(buf),
+        }
+    }
 }
 
 // MultifileReader - concatenate all our input, file or stdin.
 pub struct MultifileReader<'a> {
     ni: Vec<InputSource<'a>>,
-    curr_file: Option<Box<dyn io::Read>>,
+    curr_file: Option<CurrentInput>,
     any_err: bool,
+    any_opened: bool,
 }
 
 pub trait HasError {
@@ -36,6 +61,7 @@ impl MultifileReader<'_> {
             ni: fnames,
             curr_file: None, // normally this means done; call next_file()
             any_err: false,
+            any_opened: false,
         };
         mf.next_file();
         mf
@@ -61,7 +87,7 @@ impl MultifileReader<'_> {
                     {
                         let stdin_raw_fd = stdin.as_raw_fd();
                         let stdin_file = unsafe { File::from_raw_fd(stdin_raw_fd) };
-                        self.curr_file = Some(Box::new(stdin_file));
+                        self.curr_file = Some(CurrentInput::File(stdin_file));
                     }
 
                     // For non-unix platforms we don't have GNU compatibility requirements, so
@@ -70,8 +96,9 @@ impl MultifileReader<'_> {
                     // doesn't seem worth worrying about at this time.
                     #[cfg(not(unix))]
                     {
-                        self.curr_file = Some(Box::new(stdin));
+                        self.curr_file = Some(CurrentInput::Stream(Box::new(stdin)));
                     }
+                    self.any_opened = true;
                     break;
                 }
                 InputSource::FileName(fname) => {
@@ -79,7 +106,8 @@ impl MultifileReader<'_> {
                         Ok(f) => {
                             // No need to wrap `f` in a BufReader - buffered reading is taken care
                             // of elsewhere.
-                            self.curr_file = Some(Box::new(f));
+                            self.curr_file = Some(CurrentInput::File(f));
+                            self.any_opened = true;
                             break;
                         }
                         Err(e) => {
@@ -99,15 +127,78 @@ impl MultifileReader<'_> {
                     }
                 }
                 InputSource::Stream(s) => {
-                    self.curr_file = Some(s);
+                    self.curr_file = Some(CurrentInput::Stream(s));
+                    self.any_opened = true;
                     break;
                 }
             }
         }
     }
+
+    /// Whether any of the inputs could be opened.
+    pub fn any_opened(&self) -> bool {
+        self.any_opened
+    }
+
+    /// Skip the first `n` bytes of the combined input, returning how many
+    /// of them were missing when it ran out.
+    ///
+    /// As in GNU od, a regular file that ends within the bytes to skip is
+    /// passed over without reading it, and one that goes on past them is
+    /// seeked through. Other inputs, like pipes, are read and the bytes
+    /// discarded.
+    pub fn skip(&mut self, mut n: u64) -> u64 {
+        let mut buf = vec![0; cmp::min(n, SKIP_BUFFER_SIZE as u64) as usize];
+        while n > 0 {
+            let Some(curr_file) = &mut self.curr_file else {
+                break;
+            };
+            if let CurrentInput::File(f) = curr_file {
+                if let Some(left) = bytes_left(f) {
+                    if left < n {
+                        n -= left;
+                        self.next_file();
+                        continue;
+                    }
+                    if f.seek(SeekFrom::Current(n as i64)).is_ok() {
+                        return 0;
+                    }
+                }
+            }
+            let len = cmp::min(n, buf.len() as u64) as usize;
+            match curr_file.read(&mut buf[..len]) {
+                Ok(0) => self.next_file(),
+                Ok(read) => n -= read as u64,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    show_error!("I/O: {e}");
+                    self.any_err = true;
+                    self.next_file();
+                }
+            }
+        }
+        n
+    }
+}
+
+/// The number of bytes from the current position to the end of `f`, if it
+/// is a regular file whose size can be relied on. Like GNU od, files no
+/// larger than their block size are not trusted, as files under `/proc`
+/// are reported as empty or as one block whatever their contents.
+fn bytes_left(f: &mut File) -> Option<u64> {
+    let metadata = f.metadata().ok()?;
+    #[cfg(unix)]
+    let block_size = metadata.blksize();
+    #[cfg(not(unix))]
+    let block_size = 512;
+    if !metadata.is_file() || metadata.len() <= block_size {
+        return None;
+    }
+    let position = f.stream_position().ok()?;
+    Some(metadata.len().saturating_sub(position))
 }
 
-impl io::Read for MultifileReader<'_> {
+impl Read for MultifileReader<'_> {
     /// Fill buf with bytes read from the list of files
     /// Returns `Ok(<number of bytes read>)`
     /// Handles io errors itself, thus always returns OK
@@ -238,4 +329,25 @@ mod tests {
         assert_eq!(sut.read(v.as_mut()).unwrap(), 3);
         assert_eq!(v, [0x42, 0x43, 0x44, 0x64, 0x41]); // last two bytes are not overwritten
     }
+
+    #[test]
+    fn test_multi_file_reader_skip() {
+        let inputs = vec![
+            InputSource::Stream(Box::new(Cursor::new(&b"abc"[..]))),
+            InputSource::Stream(Box::new(Cursor::new(&b"defg"[..]))),
+        ];
+        let mut v = [0; 5];
+
+        let mut sut = MultifileReader::new(inputs);
+
+        assert_eq!(sut.skip(4), 0);
+        assert_eq!(sut.read(v.as_mut()).unwrap(), 3);
+        assert_eq!(&v[..3], b"efg");
+
+        let inputs = vec![InputSource::Stream(Box::new(Cursor::new(&b"abc"[..])))];
+        let mut sut = MultifileReader::new(inputs);
+        // the bytes that could not be skipped are returned
+        assert_eq!(sut.skip(5), 2);
+        assert_eq!(sut.read(v.as_mut()).unwrap(), 0);
+    }
 }
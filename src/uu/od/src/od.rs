@@ -133,37 +133,33 @@ impl OdOptions {
         let formats = parse_format_flags(args).map_err(|e| USimpleError::new(1, e))?;
 
         let mut line_bytes = match matches.get_one::<String>(options::WIDTH) {
-            None => 16,
-            Some(s) => {
-                if matches.value_source(options::WIDTH) == Some(ValueSource::CommandLine) {
-                    let width_display = option_display_name(args, options::WIDTH, Some('w'));
-                    let parsed = parse_number_of_bytes(s).map_err(|e| {
-                        USimpleError::new(1, format_error_message(&e, s, &width_display))
-                    })?;
-                    if parsed == 0 {
-                        return Err(USimpleError::new(
-                            1,
-                            translate!(
-                                "od-error-invalid-argument",
-                                "option" => width_display.clone(),
-                                "value" => s.quote()
-                            ),
-                        ));
-                    }
-                    usize::try_from(parsed).map_err(|_| {
-                        USimpleError::new(
-                            1,
-                            translate!(
-                                "od-error-argument-too-large",
-                                "option" => width_display.clone(),
-                                "value" => s.quote()
-                            ),
-                        )
-                    })?
-                } else {
-                    16
+            Some(s) if matches.value_source(options::WIDTH) == Some(ValueSource::CommandLine) => {
+                let width_display = option_display_name(args, options::WIDTH, Some('w'));
+                let parsed = parse_number_of_bytes(s).map_err(|e| {
+                    USimpleError::new(1, format_error_message(&e, s, &width_display))
+                })?;
+                if parsed == 0 {
+                    return Err(USimpleError::new(
+                        1,
+                        translate!(
+                            "od-error-invalid-argument",
+                            "option" => width_display.clone(),
+                            "value" => s.quote()
+                        ),
+                    ));
                 }
+                usize::try_from(parsed).map_err(|_| {
+                    USimpleError::new(
+                        1,
+                        translate!(
+                            "od-error-argument-too-large",
+                            "option" => width_display.clone(),
+                            "value" => s.quote()
+                        ),
+                    )
+                })?
             }
+            _ => 16,
         };
 
         let min_bytes = formats.iter().fold(1, |max, next| {
@@ -246,7 +242,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     let clap_matches = uucore::clap_localization::handle_clap_result(clap_opts, &args)?;
 
     let od_options = OdOptions::new(&clap_matches, &args)?;
-    let mut out = std::io::stdout().lock();
+    let mut out = uucore::display::raw_stdout();
 
     // Check if we're in strings mode
     if let Some(min_length) = od_options.string_min_length {
@@ -266,7 +262,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
             &od_options.input_strings,
             od_options.skip_bytes,
             od_options.read_bytes,
-        );
+        )?;
         let mut input_decoder = InputDecoder::new(
             &mut input,
             od_options.line_bytes,
@@ -519,9 +515,7 @@ where
                 let length = memory_decoder.length();
 
                 if length == 0 {
-                    if !input_decoder.has_error() {
-                        input_offset.print_final_offset(out)?;
-                    }
+                    input_offset.print_final_offset(out)?;
                     break;
                 }
 
@@ -585,20 +579,7 @@ fn extract_strings_from_input(
     radix: Radix,
     out: &mut impl IoWrite,
 ) -> UResult<()> {
-    let inputs = map_input_strings(input_strings);
-    let mut mf = MultifileReader::new(inputs);
-
-    // Apply skip_bytes by reading and discarding
-    let mut skipped = 0u64;
-    while skipped < skip_bytes {
-        let to_skip = cmp::min(8192, skip_bytes - skipped);
-        let mut skip_buf = vec![0u8; to_skip as usize];
-        match mf.read(&mut skip_buf) {
-            Ok(0) => break, // EOF reached
-            Ok(n) => skipped += n as u64,
-            Err(_) => break,
-        }
-    }
+    let mut mf = open_input(input_strings, skip_bytes)?;
 
     // Helper function to format and print a string
     let mut print_string = |offset: u64, string: &[u8]| -> std::io::Result<()> {
@@ -760,6 +741,21 @@ fn map_input_strings(input_strings: &[String]) -> Vec<InputSource<'_>> {
         .collect()
 }
 
+/// Opens the combined input and skips its first `skip_bytes` bytes.
+///
+/// Fails if none of the inputs can be opened, once their errors have been
+/// shown, or if there are fewer than `skip_bytes` bytes in all.
+fn open_input(input_strings: &[String], skip_bytes: u64) -> UResult<MultifileReader<'_>> {
+    let mut mf = MultifileReader::new(map_input_strings(input_strings));
+    if !mf.any_opened() {
+        return Err(1.into());
+    }
+    if mf.skip(skip_bytes) > 0 {
+        return Err(USimpleError::new(1, translate!("od-error-skip-past-end")));
+    }
+    Ok(mf)
+}
+
 /// returns a reader implementing `PeekRead + Read + HasError` providing the combined input
 ///
 /// `skip_bytes` is the number of bytes skipped from the input
@@ -768,17 +764,16 @@ fn open_input_peek_reader(
     input_strings: &[String],
     skip_bytes: u64,
     read_bytes: Option<u64>,
-) -> PeekReader<BufReader<PartialReader<MultifileReader<'_>>>> {
+) -> UResult<PeekReader<BufReader<PartialReader<MultifileReader<'_>>>>> {
     // should return  "impl PeekRead + Read + HasError" when supported in (stable) rust
-    let inputs = map_input_strings(input_strings);
-    let mf = MultifileReader::new(inputs);
-    let pr = PartialReader::new(mf, skip_bytes, read_bytes);
+    let mf = open_input(input_strings, skip_bytes)?;
+    let pr = PartialReader::new(mf, 0, read_bytes);
     // Add a BufReader over the top of the PartialReader. This will have the
     // effect of generating buffered reads to files/stdin, but since these reads
     // go through MultifileReader (which limits the maximum number of bytes read)
     // we won't ever read more bytes than were specified with the `-N` flag.
     let buf_pr = BufReader::new(pr);
-    PeekReader::new(buf_pr)
+    Ok(PeekReader::new(buf_pr))
 }
 
 impl<R: HasError> HasError for BufReader<R> {
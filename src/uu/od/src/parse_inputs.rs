@@ -9,7 +9,7 @@ use uucore::translate;
 /// POSIX ERANGE value, used for overflow error messages.
 /// Defined here because `ERANGE` is not available on all targets (e.g. WASM).
 #[cfg(not(target_family = "wasm"))]
-use ERANGE;
+use libc::ERANGE;
 #[cfg(target_family = "wasm")]
 const ERANGE: i32 = 34;
 
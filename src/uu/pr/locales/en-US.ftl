@@ -100,5 +100,6 @@ pr-error-socket-not-supported = pr: cannot open {$file}, Operation not supported
 pr-error-no-such-file = pr: cannot open {$file}, No such file or directory
 pr-error-column-merge-conflict = cannot specify number of columns when printing in parallel
 pr-error-across-merge-conflict = cannot specify both printing across and printing in parallel
+pr-error-page-width-too-narrow = page width too narrow
 pr-error-invalid-pages-range = invalid --pages argument '{$start}:{$end}'
 pr-error-invalid-expand-tab-argument ='-e' extra characters or invalid number in the argument: ‘{$arg}’
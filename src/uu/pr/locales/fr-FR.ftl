@@ -99,5 +99,6 @@ pr-error-socket-not-supported = pr : impossible d'ouvrir {$file}, Opération non
 pr-error-no-such-file = pr : impossible d'ouvrir {$file}, Aucun fichier ou répertoire de ce type
 pr-error-column-merge-conflict = impossible de spécifier le nombre de colonnes lors de l'impression en parallèle
 pr-error-across-merge-conflict = impossible de spécifier à la fois l'impression transversale et l'impression en parallèle
+pr-error-page-width-too-narrow = largeur de page trop étroite
 pr-error-invalid-pages-range = argument --pages invalide '{$start}:{$end}'
 pr-error-invalid-expand-tab-argument = Caractères supplémentaires ou nombre invalide dans l'argument de '-e': '{$arg}'
@@ -28,9 +28,10 @@ const LINES_PER_PAGE_FOR_FORM_FEED: usize = 63;
 const HEADER_LINES_PER_PAGE: usize = 5;
 const TRAILER_LINES_PER_PAGE: usize = 5;
 const FILE_STDIN: &str = "-";
-const DEFAULT_COLUMN_WIDTH: usize = 72;
-const DEFAULT_COLUMN_WIDTH_WITH_S_OPTION: usize = 512;
-const DEFAULT_COLUMN_SEPARATOR: &char = &TAB;
+const DEFAULT_PAGE_WIDTH: usize = 72;
+const DEFAULT_COLUMN_SEPARATOR: &str = " ";
+const DEFAULT_JOIN_LINES_SEPARATOR: &str = "\t";
+const TAB_WIDTH: usize = 8;
 const FF: u8 = 0x0C_u8;
 const NL: u8 = b'\n';
 
@@ -76,11 +77,18 @@ struct OutputOptions {
     page_separator_char: String,
     column_mode_options: Option<ColumnModeOptions>,
     merge_files_print: Option<usize>,
-    offset_spaces: String,
+    /// Number of spaces printed before each output line
+    margin: usize,
     form_feed_used: bool,
+    /// Print lines whole, without aligning or truncating columns
     join_lines: bool,
     col_sep_for_printing: String,
-    line_width: Option<usize>,
+    /// Width of an output line, not counting the margin
+    page_width: usize,
+    /// Width of one text column, including its line numbers unless merging
+    column_width: usize,
+    /// Cut lines that do not fit their column
+    truncate_lines: bool,
     expand_tabs: Option<ExpandTabsOptions>,
 }
 
@@ -127,9 +135,7 @@ impl FileLine {
 }
 
 struct ColumnModeOptions {
-    width: usize,
     columns: usize,
-    column_separator: String,
     across_mode: bool,
 }
 
@@ -155,6 +161,18 @@ impl Default for ExpandTabsOptions {
     }
 }
 
+impl NumberingMode {
+    /// Number of columns taken by a line number and its separator, with a
+    /// tab separator counted up to the next tab stop.
+    fn total_width(&self) -> usize {
+        if self.separator == "\t" {
+            self.width + TAB_WIDTH - self.width % TAB_WIDTH
+        } else {
+            self.width + self.separator.chars().count()
+        }
+    }
+}
+
 impl Default for NumberingMode {
     fn default() -> Self {
         Self {
@@ -333,7 +351,7 @@ pub fn uu_app() -> Command {
                 .help(translate!("pr-help-column-string-separator"))
                 .value_name("string")
                 .num_args(0..=1)
-                .default_missing_value(" "),
+                .default_missing_value(""),
         )
         .arg(
             Arg::new(options::MERGE)
@@ -738,32 +756,6 @@ fn build_options(
 
     let across_mode = matches.get_flag(options::ACROSS);
 
-    let column_separator = match matches.get_one::<String>(options::COLUMN_STRING_SEPARATOR) {
-        Some(x) => Some(x),
-        None => matches.get_one::<String>(options::COLUMN_CHAR_SEPARATOR),
-    }
-    .map_or_else(|| DEFAULT_COLUMN_SEPARATOR.to_string(), ToString::to_string);
-
-    let default_column_width = if matches.contains_id(options::COLUMN_WIDTH)
-        && matches.contains_id(options::COLUMN_CHAR_SEPARATOR)
-    {
-        DEFAULT_COLUMN_WIDTH_WITH_S_OPTION
-    } else {
-        DEFAULT_COLUMN_WIDTH
-    };
-
-    let column_width =
-        parse_usize(matches, options::COLUMN_WIDTH).unwrap_or(Ok(default_column_width))?;
-
-    let page_width = if matches.get_flag(options::JOIN_LINES) {
-        None
-    } else {
-        match parse_usize(matches, options::PAGE_WIDTH) {
-            Some(res) => Some(res?),
-            None => None,
-        }
-    };
-
     let re_col = Regex::new(r"\s*-(\d+)\s*").unwrap();
 
     let res = re_col.captures(free_args).map(|i| {
@@ -788,37 +780,64 @@ fn build_options(
 
     let column_mode_options = column_option_value.map(|columns| ColumnModeOptions {
         columns,
-        width: column_width,
-        column_separator,
         across_mode,
     });
 
-    let offset_spaces = " ".repeat(parse_usize(matches, options::INDENT).unwrap_or(Ok(0))?);
-    let join_lines = matches.get_flag(options::JOIN_LINES);
+    let margin = parse_usize(matches, options::INDENT).unwrap_or(Ok(0))?;
+
+    let columns_to_print =
+        merge_files_print.unwrap_or_else(|| column_mode_options.as_ref().map_or(1, |i| i.columns));
+
+    let width_given =
+        matches.contains_id(options::COLUMN_WIDTH) || matches.contains_id(options::PAGE_WIDTH);
 
-    let col_sep_for_printing = column_mode_options.as_ref().map_or_else(
+    // Without a width, `-s` keeps the columns of multi-column output
+    // unaligned, like `-J`.
+    let join_lines = matches.get_flag(options::JOIN_LINES)
+        || (columns_to_print > 1
+            && matches.contains_id(options::COLUMN_CHAR_SEPARATOR)
+            && !width_given);
+
+    let col_sep_for_printing = match matches.get_one::<String>(options::COLUMN_STRING_SEPARATOR) {
+        Some(x) => Some(x),
+        None => matches.get_one::<String>(options::COLUMN_CHAR_SEPARATOR),
+    }
+    .map_or_else(
         || {
-            merge_files_print
-                .map(|_k| DEFAULT_COLUMN_SEPARATOR.to_string())
-                .unwrap_or_default()
+            if matches.get_flag(options::JOIN_LINES) {
+                DEFAULT_JOIN_LINES_SEPARATOR
+            } else {
+                DEFAULT_COLUMN_SEPARATOR
+            }
+            .to_string()
         },
-        |i| i.column_separator.clone(),
+        ToString::to_string,
     );
 
-    let columns_to_print =
-        merge_files_print.unwrap_or_else(|| column_mode_options.as_ref().map_or(1, |i| i.columns));
+    // -W sets the page width for single-column output too, -w only for
+    // multi-column output; both set the width the header is centered in.
+    let page_width = match parse_usize(matches, options::PAGE_WIDTH) {
+        Some(res) => res?,
+        None => parse_usize(matches, options::COLUMN_WIDTH).unwrap_or(Ok(DEFAULT_PAGE_WIDTH))?,
+    };
 
-    let line_width = if join_lines {
-        None
-    } else if columns_to_print > 1 {
-        Some(
-            column_mode_options
-                .as_ref()
-                .map_or(DEFAULT_COLUMN_WIDTH, |i| i.width),
-        )
-    } else {
-        page_width
+    let truncate_lines =
+        !join_lines && (columns_to_print > 1 || matches.contains_id(options::PAGE_WIDTH));
+
+    // When merging, the line number is printed once at the start of each
+    // line, so it takes its share of the page width before the columns do.
+    let number_width = match (&number, merge_files_print) {
+        (Some(number), Some(_)) => number.total_width(),
+        _ => 0,
     };
+    let separators_width = (columns_to_print - 1) * col_sep_for_printing.chars().count();
+    let column_width =
+        page_width.saturating_sub(number_width + separators_width) / columns_to_print;
+    if column_width == 0 {
+        return Err(PrError::EncounteredErrors {
+            msg: translate!("pr-error-page-width-too-narrow"),
+        });
+    }
 
     Ok(OutputOptions {
         number,
@@ -834,11 +853,13 @@ fn build_options(
         page_separator_char,
         column_mode_options,
         merge_files_print,
-        offset_spaces,
+        margin,
         form_feed_used,
         join_lines,
         col_sep_for_printing,
-        line_width,
+        page_width,
+        column_width,
+        truncate_lines,
         expand_tabs,
     })
 }
@@ -1178,9 +1199,20 @@ fn to_table_short_file(
     columns: usize,
     lines: &[FileLine],
 ) -> Vec<Vec<Option<&FileLine>>> {
-    let num_rows = lines.len() / columns;
+    // Balance the columns, with the leftmost ones taking a line more than
+    // the others if the lines do not divide evenly.
+    let (shortest, longer_columns) = (lines.len() / columns, lines.len() % columns);
+    let num_rows = lines.len().div_ceil(columns);
     let mut table: Vec<Vec<_>> = (0..num_rows)
-        .map(|i| (0..columns).map(|j| lines.get(num_rows * j + i)).collect())
+        .map(|i| {
+            (0..columns)
+                .map(|j| {
+                    let length = shortest + usize::from(j < longer_columns);
+                    let start = shortest * j + j.min(longer_columns);
+                    (i < length).then(|| &lines[start + i])
+                })
+                .collect()
+        })
         .collect();
     // Fill the rest with Nones.
     for _ in num_rows..content_lines_per_page {
@@ -1206,7 +1238,6 @@ fn write_columns(
     let columns = options
         .merge_files_print
         .unwrap_or_else(|| get_columns(options));
-    let line_width = options.line_width;
     let feed_line_present = options.form_feed_used;
     let mut not_found_break = false;
 
@@ -1239,37 +1270,20 @@ fn write_columns(
     // cells, where each row will be printed as a single line in the
     // output.
     let merge = options.merge_files_print.is_some();
-    let table = if !merge && (lines.len() < (content_lines_per_page * columns)) {
-        to_table_short_file(content_lines_per_page, columns, lines)
-    } else if across_mode {
+    let table = if across_mode {
         to_table_across(content_lines_per_page, columns, lines)
+    } else if !merge && (lines.len() < (content_lines_per_page * columns)) {
+        to_table_short_file(content_lines_per_page, columns, lines)
     } else if merge {
         to_table_merged(content_lines_per_page, columns, filled_lines)
     } else {
         to_table(content_lines_per_page, columns, lines)
     };
 
-    let blank_line = FileLine::default();
     for row in table {
-        let indexes = row.len();
-        for (i, cell) in row.iter().enumerate() {
-            if cell.is_none() && options.merge_files_print.is_some() {
-                out.write_all(
-                    get_line_for_printing(options, &blank_line, columns, i, line_width, indexes)
-                        .as_bytes(),
-                )?;
-            } else if cell.is_none() {
-                not_found_break = true;
-                break;
-            } else if cell.is_some() {
-                let file_line = cell.unwrap();
-
-                out.write_all(
-                    get_line_for_printing(options, file_line, columns, i, line_width, indexes)
-                        .as_bytes(),
-                )?;
-            }
-        }
+        let (line, not_found) = format_row(options, &row);
+        out.write_all(line.as_bytes())?;
+        not_found_break |= not_found;
         if not_found_break && feed_line_present {
             break;
         }
@@ -1279,63 +1293,204 @@ fn write_columns(
     Ok(())
 }
 
-fn get_line_for_printing(
-    options: &OutputOptions,
-    file_line: &FileLine,
-    columns: usize,
-    index: usize,
-    line_width: Option<usize>,
-    indexes: usize,
-) -> String {
-    let blank_line = String::new();
-    let formatted_line_number = get_formatted_line_number(options, file_line.line_number, index);
+/// A line of output being assembled, keeping track of its display width.
+///
+/// In multi-column output, white space is written with tabs where that is
+/// shorter, like GNU pr does, and white space at the end of the line is
+/// dropped.
+struct OutputLine {
+    buf: String,
+    position: usize,
+    pending_spaces: usize,
+    tabify: bool,
+}
 
-    let mut complete_line = format!("{formatted_line_number}{}", file_line.line_content);
+impl OutputLine {
+    fn new(tabify: bool) -> Self {
+        Self {
+            buf: String::new(),
+            position: 0,
+            pending_spaces: 0,
+            tabify,
+        }
+    }
 
-    let offset_spaces = &options.offset_spaces;
+    fn push_spaces(&mut self, count: usize) {
+        if self.tabify {
+            self.pending_spaces += count;
+        } else {
+            self.buf.extend(std::iter::repeat_n(' ', count));
+            self.position += count;
+        }
+    }
 
-    let tab_count = complete_line.chars().filter(|i| i == &TAB).count();
+    /// Add spaces up to the given display position, if it is further right.
+    fn pad_to(&mut self, position: usize) {
+        let current = self.position + self.pending_spaces;
+        if position > current {
+            self.push_spaces(position - current);
+        }
+    }
 
-    let display_length = complete_line.len() + (tab_count * 7);
+    fn flush_spaces(&mut self) {
+        let goal = self.position + self.pending_spaces;
+        while goal - self.position > 1 {
+            let next_tab_stop = (self.position / TAB_WIDTH + 1) * TAB_WIDTH;
+            if next_tab_stop > goal {
+                break;
+            }
+            self.buf.push(TAB);
+            self.position = next_tab_stop;
+        }
+        self.buf
+            .extend(std::iter::repeat_n(' ', goal - self.position));
+        self.position = goal;
+        self.pending_spaces = 0;
+    }
 
-    let sep = if (index + 1) != indexes && !options.join_lines {
-        &options.col_sep_for_printing
-    } else {
-        &blank_line
-    };
+    fn push_char(&mut self, c: char) {
+        if c == ' ' {
+            self.push_spaces(1);
+            return;
+        }
+        self.flush_spaces();
+        self.buf.push(c);
+        if c == TAB && !self.tabify {
+            self.position = (self.position / TAB_WIDTH + 1) * TAB_WIDTH;
+        } else {
+            self.position += 1;
+        }
+    }
 
-    format!(
-        "{offset_spaces}{}{sep}",
-        line_width
-            .map(|i| {
-                let min_width = (i - (columns - 1)) / columns;
-                if display_length < min_width {
-                    for _i in 0..(min_width - display_length) {
-                        complete_line.push(' ');
-                    }
-                }
+    /// Add a column separator. Its white space is written right away, so
+    /// that the next column starts where it should even if it is empty.
+    fn push_separator(&mut self, separator: &str) {
+        for c in separator.chars() {
+            self.push_char(c);
+        }
+        if self.pending_spaces > 0 {
+            self.flush_spaces();
+        }
+    }
+}
 
-                complete_line.chars().take(min_width).collect()
-            })
-            .unwrap_or(complete_line),
-    )
+/// Format one row of the page table as a line of output.
+///
+/// Also returns whether the row ended at a missing cell.
+fn format_row(options: &OutputOptions, row: &[Option<&FileLine>]) -> (String, bool) {
+    let merge = options.merge_files_print.is_some();
+    let column_mode = row.len() > 1;
+    let sep = &options.col_sep_for_printing;
+    let sep_width = sep.chars().count();
+
+    if row.iter().all(Option::is_none) {
+        // The input has ended, or all files have when merging.
+        return (String::new(), !merge);
+    }
+
+    let mut line = OutputLine::new(column_mode);
+    line.push_spaces(options.margin);
+    let mut columns_start = options.margin;
+
+    // When merging, the line number is printed once, before the columns.
+    if let (true, Some(number)) = (merge, &options.number) {
+        let line_number = row.iter().flatten().next().map_or(0, |l| l.line_number);
+        push_line_number(&mut line, number, line_number, true);
+        columns_start += number.total_width();
+    }
+
+    for (i, cell) in row.iter().enumerate() {
+        let file_line = match cell {
+            Some(file_line) => Some(*file_line),
+            None if merge => None,
+            None => return (line.buf, true),
+        };
+        if i > 0 {
+            if !options.join_lines {
+                let column_start = columns_start + i * (options.column_width + sep_width);
+                line.pad_to(column_start - sep_width);
+            }
+            line.push_separator(sep);
+        }
+        let Some(file_line) = file_line else {
+            continue;
+        };
+
+        let mut input_position = 0;
+        if let (false, Some(number)) = (merge, &options.number) {
+            push_line_number(&mut line, number, file_line.line_number, column_mode);
+            input_position = number.total_width();
+        }
+        let limit = options.truncate_lines.then_some(if column_mode {
+            options.column_width
+        } else {
+            options.page_width
+        });
+        push_line_content(
+            &mut line,
+            &file_line.line_content,
+            input_position,
+            limit,
+            column_mode && !options.join_lines,
+        );
+    }
+
+    (line.buf, false)
+}
+
+/// Add a line number, right-aligned and cut to its last `width` digits,
+/// followed by its separator.
+///
+/// In multi-column output, a tab separator is expanded so that the number
+/// keeps its width in every column.
+fn push_line_number(
+    line: &mut OutputLine,
+    number: &NumberingMode,
+    line_number: usize,
+    column_mode: bool,
+) {
+    let width = number.width;
+    let line_str = line_number.to_string();
+    let digits = &line_str[line_str.len().saturating_sub(width)..];
+    for c in format!("{digits:>width$}").chars() {
+        line.push_char(c);
+    }
+    if column_mode && number.separator == "\t" {
+        line.push_spaces(number.total_width() - width);
+    } else {
+        for c in number.separator.chars() {
+            line.push_char(c);
+        }
+    }
 }
 
-fn get_formatted_line_number(opts: &OutputOptions, line_number: usize, index: usize) -> String {
-    let should_show_line_number =
-        opts.number.is_some() && (opts.merge_files_print.is_none() || index == 0);
-    if should_show_line_number && line_number != 0 {
-        let line_str = line_number.to_string();
-        let num_opt = opts.number.as_ref().unwrap();
-        let width = num_opt.width;
-        let separator = &num_opt.separator;
-        if line_str.len() >= width {
-            format!("{:>width$}{separator}", &line_str[line_str.len() - width..],)
+/// Add the content of an input line, starting at `input_position` within
+/// its column and cut at `limit`, though never before its first character
+/// unless that is a tab.
+///
+/// With `expand_tabs`, tabs are expanded relative to the column.
+fn push_line_content(
+    line: &mut OutputLine,
+    content: &str,
+    mut input_position: usize,
+    limit: Option<usize>,
+    expand_tabs: bool,
+) {
+    for (i, c) in content.chars().enumerate() {
+        let width = if c == TAB {
+            TAB_WIDTH - input_position % TAB_WIDTH
         } else {
-            format!("{line_str:>width$}{separator}")
+            1
+        };
+        if (i > 0 || c == TAB) && limit.is_some_and(|limit| input_position + width > limit) {
+            break;
         }
-    } else {
-        String::new()
+        if c == TAB && expand_tabs {
+            line.push_spaces(width);
+        } else {
+            line.push_char(c);
+        }
+        input_position += width;
     }
 }
 
@@ -1354,8 +1509,7 @@ fn header_content(options: &OutputOptions, page: usize) -> Vec<String> {
     let filename = &options.header;
     let page_part = format!("{} {page}", translate!("pr-page"));
 
-    // Use the line width if available, otherwise use default of 72
-    let total_width = options.line_width.unwrap_or(DEFAULT_COLUMN_WIDTH);
+    let total_width = options.page_width;
 
     let date_len = date_part.chars().count();
     let filename_len = filename.chars().count();
@@ -1376,10 +1530,12 @@ fn header_content(options: &OutputOptions, page: usize) -> Vec<String> {
         format!("{date_part} {filename} {page_part}")
     };
 
+    // Like GNU pr, indent the first line of the header too.
+    let margin = " ".repeat(options.margin);
     vec![
+        margin.clone(),
         String::new(),
-        String::new(),
-        header_line,
+        format!("{margin}{header_line}"),
         String::new(),
         String::new(),
     ]
@@ -3,12 +3,12 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-use std::env;
 use std::io::Write;
 
 use clap::{Arg, ArgAction, Command};
 
 use uucore::display::{OsWrite, print_all_env_vars};
+use uucore::env;
 use uucore::error::UResult;
 use uucore::line_ending::LineEnding;
 use uucore::{format_usage, translate};
@@ -250,6 +250,7 @@ printf-after-help = templating de chaîne anonyme de base :
 
 # Messages d'erreur
 printf-error-missing-operand = opérande manquant
+printf-error-write-error = erreur d'écriture
 printf-warning-ignoring-excess-arguments = arguments excédentaires ignorés, en commençant par { $arg }
 printf-help-version = Afficher les informations de version
 printf-help-help = Afficher cette aide
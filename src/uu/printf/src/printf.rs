@@ -4,13 +4,14 @@
 // file that was distributed with this source code.
 use clap::{Arg, ArgAction, Command};
 use std::ffi::OsString;
+use std::io::Write;
 #[cfg(not(target_family = "wasm"))]
 use std::io::stdout;
 #[cfg(target_family = "wasm")]
 use uucore::wasm_io::stdout;
 use std::ops::ControlFlow;
 use uucore::display::Quotable;
-use uucore::error::{UResult, UUsageError};
+use uucore::error::{FromIo, UResult, UUsageError};
 use uucore::format::{FormatArgument, FormatArguments, FormatItem, parse_spec_and_escape};
 use uucore::translate;
 use uucore::{format_usage, os_str_as_bytes, show_warning};
@@ -25,23 +26,46 @@ mod options {
 
 #[uucore::main]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
-    let matches = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
+    // The operands are not scanned for options, so that `printf '%s\n' --help`
+    // prints `--help`: like GNU printf, only a sole `--help` or `--version`
+    // is an option, and only a `--` before FORMAT ends the options.
+    let args: Vec<OsString> = args.collect();
+    if args.len() == 2 && (args[1] == "--help" || args[1] == "--version") {
+        uucore::clap_localization::handle_clap_result(uu_app(), args)?;
+        return Ok(());
+    }
+    let operands = match args.get(1) {
+        Some(arg) if arg == "--" => &args[2..],
+        _ => args.get(1..).unwrap_or_default(),
+    };
 
-    let format = matches
-        .get_one::<OsString>(options::FORMAT)
-        .ok_or_else(|| UUsageError::new(1, translate!("printf-error-missing-operand")))?;
+    let Some((format, arguments)) = operands.split_first() else {
+        return Err(UUsageError::new(
+            1,
+            translate!("printf-error-missing-operand"),
+        ));
+    };
     let format = os_str_as_bytes(format)?;
 
-    let values: Vec<_> = match matches.get_many::<OsString>(options::ARGUMENT) {
-        Some(s) => s
-            .map(|os_string| FormatArgument::Unparsed(os_string.to_owned()))
-            .collect(),
-        None => vec![],
-    };
+    let values: Vec<_> = arguments
+        .iter()
+        .map(|os_string| FormatArgument::Unparsed(os_string.to_owned()))
+        .collect();
+
+    let result = print_formatted(format, &values);
+    // Output is line buffered, so a trailing partial line only reaches
+    // stdout here; report a failure the same way as one during formatting.
+    stdout()
+        .flush()
+        .map_err_context(|| translate!("printf-error-write-error"))?;
+    result
+}
 
+/// Writes `format` to stdout, reusing it until all of `values` are consumed.
+fn print_formatted(format: &[u8], values: &[FormatArgument]) -> UResult<()> {
     let mut format_seen = false;
     // Parse and process the format string
-    let mut args = FormatArguments::new(&values);
+    let mut args = FormatArguments::new(values);
     for item in parse_spec_and_escape(format) {
         if let Ok(FormatItem::Spec(_)) = item {
             format_seen = true;
@@ -19,18 +19,24 @@ const OPT_PHYSICAL: &str = "physical";
 
 fn physical_path() -> io::Result<PathBuf> {
     // std::env::current_dir() is a thin wrapper around libc::getcwd().
+    #[cfg(not(target_family = "wasm"))]
     let path = env::current_dir()?;
 
+    // On WASM there is no process working directory, the host keeps track
+    // of it and resolves it already.
+    #[cfg(target_family = "wasm")]
+    let path = uucore::wasm_io::current_dir()?;
+
     // On Unix, getcwd() must return the physical path:
     // https://pubs.opengroup.org/onlinepubs/9699919799/functions/getcwd.html
-    #[cfg(unix)]
+    #[cfg(any(unix, target_family = "wasm"))]
     {
         Ok(path)
     }
 
     // On Windows we have to resolve it.
     // On other systems we also resolve it, just in case.
-    #[cfg(not(unix))]
+    #[cfg(not(any(unix, target_family = "wasm")))]
     {
         path.canonicalize()
     }
@@ -43,6 +49,12 @@ fn logical_path() -> io::Result<PathBuf> {
         env::current_dir()
     }
 
+    // The host's working directory is the one its shell shows.
+    #[cfg(target_family = "wasm")]
+    {
+        uucore::wasm_io::current_dir()
+    }
+
     // If we're not on Windows we do things Unix-style.
     //
     // Typical Unix-like kernels don't actually keep track of the logical working
@@ -54,7 +66,7 @@ fn logical_path() -> io::Result<PathBuf> {
     // reasonable, and if not then we fall back to the physical path.
     //
     // POSIX: https://pubs.opengroup.org/onlinepubs/9699919799/utilities/pwd.html
-    #[cfg(not(windows))]
+    #[cfg(not(any(windows, target_family = "wasm")))]
     {
         use std::path::Path;
         fn looks_reasonable(path: &Path) -> bool {
@@ -115,7 +127,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     // We should get c in this case instead of a/b at the end of the path
     let cwd = if matches.get_flag(OPT_PHYSICAL) {
         physical_path()
-    } else if matches.get_flag(OPT_LOGICAL) || env::var("POSIXLY_CORRECT").is_ok() {
+    } else if matches.get_flag(OPT_LOGICAL) || uucore::env::var("POSIXLY_CORRECT").is_ok() {
         logical_path()
     } else {
         physical_path()
@@ -8,17 +8,28 @@
 use clap::{Arg, ArgAction, Command};
 use std::env;
 use std::ffi::OsString;
-use std::fs;
-use std::io::{Write, stdout};
+#[cfg(not(target_family = "wasm"))]
+use std::fs::read_link;
+use std::io::Write;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use std::path::{Path, PathBuf};
 use uucore::display::Quotable;
 use uucore::error::{FromIo, UResult, UUsageError};
 use uucore::fs::{MissingHandling, ResolveMode, canonicalize};
+#[cfg(any(not(target_family = "wasm"), target_os = "wasi"))]
 use uucore::libc::EINVAL;
 use uucore::line_ending::LineEnding;
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{read_link, stdout};
 use uucore::{format_usage, show_error};
 
+/// POSIX EINVAL value, defined here because `libc` has no constants on
+/// `wasm32-unknown-unknown`.
+#[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
+const EINVAL: i32 = 22;
+
 const OPT_CANONICALIZE: &str = "canonicalize";
 const OPT_CANONICALIZE_MISSING: &str = "canonicalize-missing";
 const OPT_CANONICALIZE_EXISTING: &str = "canonicalize-existing";
@@ -83,7 +94,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
 
     for p in &files {
         let path_result = if res_mode == ResolveMode::None {
-            fs::read_link(p)
+            read_link(p)
         } else {
             canonicalize(p, can_mode, res_mode)
         };
@@ -9,13 +9,17 @@ use clap::{
     Arg, ArgAction, ArgMatches, Command,
     builder::{TypedValueParser, ValueParserFactory},
 };
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use std::{
     ffi::{OsStr, OsString},
-    io::{Write, stdout},
+    io::Write,
     path::{Path, PathBuf},
 };
 use uucore::fs::make_path_relative_to;
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::stdout;
 use uucore::{
     display::{Quotable, print_verbatim},
     error::{FromIo, UResult},
@@ -269,9 +273,14 @@ fn canonicalize_relative(
     resolve: ResolveMode,
 ) -> std::io::Result<PathBuf> {
     let abs = canonicalize(r, can_mode, resolve)?;
+    #[cfg(not(target_family = "wasm"))]
     if can_mode == MissingHandling::Existing && !abs.is_dir() {
         abs.read_dir()?; // raise not a directory error
     }
+    #[cfg(target_family = "wasm")]
+    if can_mode == MissingHandling::Existing && !uucore::wasm_io::metadata(&abs)?.is_dir() {
+        uucore::wasm_io::read_dir(&abs)?; // raise not a directory error
+    }
     Ok(abs)
 }
 
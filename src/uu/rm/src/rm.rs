@@ -9,7 +9,10 @@ use clap::builder::{PossibleValue, ValueParser};
 use clap::{Arg, ArgAction, Command, parser::ValueSource};
 use indicatif::{ProgressBar, ProgressStyle};
 use std::ffi::{OsStr, OsString};
+#[cfg(not(target_family = "wasm"))]
 use std::fs::{self, Metadata};
+#[cfg(target_family = "wasm")]
+use std::io::Write;
 use std::io::{self, IsTerminal, stdin};
 use std::ops::BitOr;
 #[cfg(unix)]
@@ -26,8 +29,12 @@ use uucore::translate;
 use uucore::{format_usage, os_str_as_bytes, prompt_yes, show_error};
 
 mod platform;
+#[cfg(target_family = "wasm")]
+mod wasm;
 #[cfg(all(unix, not(target_os = "redox")))]
 use platform::{safe_remove_dir_recursive, safe_remove_empty_dir, safe_remove_file};
+#[cfg(target_family = "wasm")]
+use wasm::{self as fs, Metadata};
 
 #[derive(Debug, Error)]
 enum RmError {
@@ -54,20 +61,23 @@ impl UError for RmError {}
 /// Helper function to print verbose message for removed file
 fn verbose_removed_file(path: &Path, options: &Options) {
     if options.verbose {
-        println!(
-            "{}",
-            translate!("rm-verbose-removed", "file" => uucore::fs::normalize_path(path).quote())
-        );
+        let message =
+            translate!("rm-verbose-removed", "file" => uucore::fs::normalize_path(path).quote());
+        #[cfg(not(target_family = "wasm"))]
+        println!("{message}");
+        #[cfg(target_family = "wasm")]
+        let _ = writeln!(uucore::wasm_io::stdout(), "{message}");
     }
 }
 
 /// Helper function to print verbose message for removed directory
 fn verbose_removed_directory(path: &Path, options: &Options) {
     if options.verbose {
-        println!(
-            "{}",
-            translate!("rm-verbose-removed-directory", "file" => uucore::fs::normalize_path(path).quote())
-        );
+        let message = translate!("rm-verbose-removed-directory", "file" => uucore::fs::normalize_path(path).quote());
+        #[cfg(not(target_family = "wasm"))]
+        println!("{message}");
+        #[cfg(target_family = "wasm")]
+        let _ = writeln!(uucore::wasm_io::stdout(), "{message}");
     }
 }
 
@@ -498,7 +508,7 @@ pub fn remove(files: &[&OsStr], options: &Options) -> bool {
             continue;
         }
 
-        had_err = match file.symlink_metadata() {
+        had_err = match fs::symlink_metadata(file) {
             Ok(metadata) => {
                 // Create progress bar on first successful file metadata read
                 if options.progress && progress_bar.is_none() {
@@ -592,7 +602,7 @@ fn remove_dir_recursive(
     // a directory and we don't want to recurse. In particular, this
     // avoids an infinite recursion in the case of a link to the current
     // directory, like `ln -s . link`.
-    if !path.is_dir() || path.is_symlink() {
+    if !fs::symlink_metadata(path).is_ok_and(|md| md.is_dir()) {
         return remove_file(path, options, progress_bar);
     }
 
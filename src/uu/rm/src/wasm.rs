@@ -0,0 +1,69 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Filesystem access for rm on WASM.
+//!
+//! This mirrors the subset of `std::fs` that rm uses, so that the removal
+//! logic can stay shared. Lookups go through the metadata and directory
+//! hooks of [`uucore::wasm_io`], and removals go through its mutation hooks.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub(crate) use uucore::wasm_io::{
+    WasmMetadata as Metadata, metadata, remove_dir, remove_file, symlink_metadata,
+};
+
+/// An entry returned by [`read_dir`].
+pub(crate) struct DirEntry {
+    path: PathBuf,
+    is_dir: bool,
+}
+
+impl DirEntry {
+    pub(crate) fn path(&self) -> PathBuf {
+        self.path.clone()
+    }
+
+    pub(crate) fn file_type(&self) -> io::Result<FileType> {
+        Ok(FileType {
+            is_dir: self.is_dir,
+        })
+    }
+}
+
+/// The type of a [`DirEntry`]. Like `std::fs::FileType`, symbolic links are
+/// not followed.
+pub(crate) struct FileType {
+    is_dir: bool,
+}
+
+impl FileType {
+    pub(crate) fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+}
+
+pub(crate) fn read_dir(path: &Path) -> io::Result<impl Iterator<Item = io::Result<DirEntry>>> {
+    let entries = uucore::wasm_io::read_dir(path)?;
+    Ok(entries.into_iter().map(move |entry| {
+        Ok(DirEntry {
+            path: path.join(&entry.name),
+            is_dir: entry.is_dir && !entry.is_symlink,
+        })
+    }))
+}
+
+pub(crate) fn remove_dir_all(path: &Path) -> io::Result<()> {
+    for entry in read_dir(path)? {
+        let entry = entry?;
+        if entry.is_dir {
+            remove_dir_all(&entry.path)?;
+        } else {
+            remove_file(&entry.path)?;
+        }
+    }
+    remove_dir(path)
+}
@@ -8,12 +8,17 @@
 use clap::builder::ValueParser;
 use clap::{Arg, ArgAction, Command};
 use std::ffi::OsString;
+#[cfg(not(target_family = "wasm"))]
 use std::fs::{read_dir, remove_dir};
 use std::io;
+#[cfg(target_family = "wasm")]
+use std::io::Write;
 use std::path::Path;
 use uucore::display::Quotable;
 use uucore::error::{UResult, set_exit_code, strip_errno};
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{read_dir, remove_dir};
 
 use uucore::{format_usage, show_error, util_name};
 
@@ -112,10 +117,11 @@ fn remove(mut path: &Path, opts: Opts) -> Result<(), Error<'_>> {
 
 fn remove_single(path: &Path, opts: Opts) -> Result<(), Error<'_>> {
     if opts.verbose {
-        println!(
-            "{}",
-            translate!("rmdir-verbose-removing-directory", "util_name" => util_name(), "path" => path.quote())
-        );
+        let message = translate!("rmdir-verbose-removing-directory", "util_name" => util_name(), "path" => path.quote());
+        #[cfg(not(target_family = "wasm"))]
+        println!("{message}");
+        #[cfg(target_family = "wasm")]
+        let _ = writeln!(uucore::wasm_io::stdout(), "{message}");
     }
     remove_dir(path).map_err(|error| Error { error, path })
 }
@@ -130,7 +136,7 @@ fn strip_trailing_slashes_from_path(path: &[u8]) -> &[u8] {
 }
 
 // POSIX: https://pubs.opengroup.org/onlinepubs/009696799/functions/rmdir.html
-#[cfg(not(windows))]
+#[cfg(not(any(windows, target_family = "wasm")))]
 const NOT_EMPTY_CODES: &[i32] = &[libc::ENOTEMPTY, libc::EEXIST];
 
 // 145 is ERROR_DIR_NOT_EMPTY, determined experimentally.
@@ -141,7 +147,7 @@ const NOT_EMPTY_CODES: &[i32] = &[145];
 // not empty.
 // This is a subset of the error codes listed in rmdir(2) from the Linux man-pages
 // project. Maybe other systems have additional codes that apply?
-#[cfg(not(windows))]
+#[cfg(not(any(windows, target_family = "wasm")))]
 const PERHAPS_EMPTY_CODES: &[i32] = &[libc::EACCES, libc::EBUSY, libc::EPERM, libc::EROFS];
 
 // Probably incomplete, I can't find a list of possible errors for
@@ -151,6 +157,7 @@ const PERHAPS_EMPTY_CODES: &[i32] = &[
     5, // ERROR_ACCESS_DENIED, found experimentally.
 ];
 
+#[cfg(not(target_family = "wasm"))]
 fn dir_not_empty(error: &io::Error, path: &Path) -> bool {
     if let Some(code) = error.raw_os_error() {
         if NOT_EMPTY_CODES.contains(&code) {
@@ -170,6 +177,14 @@ fn dir_not_empty(error: &io::Error, path: &Path) -> bool {
     false
 }
 
+// Errors from the host VFS carry no OS error code, so rely on the error kind
+// and fall back to checking whether the directory still has entries.
+#[cfg(target_family = "wasm")]
+fn dir_not_empty(error: &io::Error, path: &Path) -> bool {
+    error.kind() == io::ErrorKind::DirectoryNotEmpty
+        || read_dir(path).is_ok_and(|entries| !entries.is_empty())
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Opts {
     ignore: bool,
@@ -55,6 +55,7 @@ shred-exact-help = do not round file sizes up to the next full block;
                    this is the default for non-regular files
 shred-zero-help = add a final overwrite with zeros to hide shredding
 shred-random-source-help = take random bytes from FILE
+shred-sync-help = flush the file, and its directory once it is removed, to the storage device
 
 # Verbose messages
 shred-removing = {$file}: removing
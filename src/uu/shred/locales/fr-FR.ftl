@@ -54,6 +54,7 @@ shred-exact-help = ne pas arrondir les tailles de fichier au bloc complet suivan
                    c'est la valeur par défaut pour les fichiers non réguliers
 shred-zero-help = ajouter un écrasement final avec des zéros pour cacher la destruction
 shred-random-source-help = prendre des octets aléatoires du FICHIER
+shred-sync-help = écrire le fichier, et son répertoire une fois supprimé, sur le périphérique de stockage
 
 # Messages verbeux
 shred-removing = {$file} : suppression
@@ -20,6 +20,7 @@ use uucore::display::Quotable;
 use uucore::error::{FromIo, UResult, USimpleError, UUsageError};
 use uucore::parser::parse_size::parse_size_u64;
 use uucore::parser::shortcut_value_parser::ShortcutValueParser;
+use uucore::rand_source;
 use uucore::translate;
 use uucore::{format_usage, show_error, show_if_err};
 
@@ -34,6 +35,7 @@ pub mod options {
     pub const EXACT: &str = "exact";
     pub const ZERO: &str = "zero";
     pub const RANDOM_SOURCE: &str = "random-source";
+    pub const SYNC: &str = "sync";
 
     pub mod remove {
         pub const UNLINK: &str = "unlink";
@@ -189,7 +191,7 @@ impl BytesWriter {
         match pass {
             PassType::Random => match random_source {
                 None => Ok(Self::Random {
-                    rng: StdRng::from_os_rng(),
+                    rng: StdRng::from_rng(&mut rand_source::rng()),
                     buffer: [0; BLOCK_SIZE],
                 }),
                 Some(file_cell) => {
@@ -296,10 +298,11 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     let size_arg = matches
         .get_one::<String>(options::SIZE)
         .map(ToOwned::to_owned);
-    let size = get_size(size_arg);
+    let size = get_size(size_arg)?;
     let exact = matches.get_flag(options::EXACT) || size.is_some();
     let zero = matches.get_flag(options::ZERO);
     let verbose = matches.get_flag(options::VERBOSE);
+    let sync = uucore::fs::sync_requested(matches.get_flag(options::SYNC));
 
     for path_str in matches.get_many::<OsString>(options::FILE).unwrap() {
         show_if_err!(wipe_file(
@@ -312,6 +315,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
             random_source.as_ref(),
             verbose,
             force,
+            sync,
         ));
     }
     Ok(())
@@ -396,6 +400,12 @@ pub fn uu_app() -> Command {
                 .value_hint(clap::ValueHint::FilePath)
                 .action(ArgAction::Set),
         )
+        .arg(
+            Arg::new(options::SYNC)
+                .long(options::SYNC)
+                .help(translate!("shred-sync-help"))
+                .action(ArgAction::SetTrue),
+        )
         // Positional arguments
         .arg(
             Arg::new(options::FILE)
@@ -405,21 +415,17 @@ pub fn uu_app() -> Command {
         )
 }
 
-fn get_size(size_str_opt: Option<String>) -> Option<u64> {
+fn get_size(size_str_opt: Option<String>) -> UResult<Option<u64>> {
     size_str_opt
-        .as_ref()
-        .and_then(|size| parse_size_u64(size.as_str()).ok())
-        .or_else(|| {
-            if let Some(size) = size_str_opt {
-                show_error!(
-                    "{}",
-                    translate!("shred-invalid-file-size", "size" => size.quote())
-                );
-                // TODO: replace with our error management
-                std::process::exit(1);
-            }
-            None
+        .map(|size| {
+            parse_size_u64(size.as_str()).map_err(|_| {
+                USimpleError::new(
+                    1,
+                    translate!("shred-invalid-file-size", "size" => size.quote()),
+                )
+            })
         })
+        .transpose()
 }
 
 fn pass_name(pass_type: &PassType) -> String {
@@ -590,8 +596,7 @@ fn create_standard_pass_sequence(num_passes: usize) -> Vec<PassType> {
     }
 
     // For standard sequence, use system randomness for shuffling
-    let mut rng = StdRng::from_os_rng();
-    sequence[1..].shuffle(&mut rng);
+    sequence[1..].shuffle(&mut rand_source::rng());
 
     // Final pass is always random
     sequence.push(PassType::Random);
@@ -625,6 +630,7 @@ fn wipe_file(
     random_source: Option<&RefCell<File>>,
     verbose: bool,
     force: bool,
+    sync: bool,
 ) -> UResult<()> {
     // Get these potential errors out of the way first
     let path = Path::new(path_str);
@@ -718,11 +724,21 @@ fn wipe_file(
         )?;
     }
 
-    if remove_method != RemoveMethod::None {
-        do_remove(path, path_str, verbose, remove_method).map_err_context(
-            || translate!("shred-failed-to-remove-file", "file" => path.maybe_quote()),
+    // Each pass has only flushed the data; with --sync, the metadata too.
+    if sync {
+        file.sync_all().map_err_context(
+            || translate!("shred-file-write-pass-failed", "file" => path.maybe_quote()),
         )?;
     }
+
+    if remove_method != RemoveMethod::None {
+        do_remove(path, path_str, verbose, remove_method)?;
+        if sync {
+            uucore::fs::sync_parent_dir(path).map_err_context(
+                || translate!("shred-failed-to-remove-file", "file" => path.maybe_quote()),
+            )?;
+        }
+    }
     Ok(())
 }
 
@@ -772,8 +788,8 @@ fn do_pass(
 }
 
 /// Repeatedly renames the file with strings of decreasing length (most likely all 0s)
-/// Return the path of the file after its last renaming or None in case of an error
-fn wipe_name(orig_path: &Path, verbose: bool, remove_method: RemoveMethod) -> PathBuf {
+/// Return the path of the file after its last renaming
+fn wipe_name(orig_path: &Path, verbose: bool, remove_method: RemoveMethod) -> UResult<PathBuf> {
     let file_name_len = orig_path.file_name().unwrap().len();
 
     let mut last_path = PathBuf::from(orig_path);
@@ -813,15 +829,13 @@ fn wipe_name(orig_path: &Path, verbose: bool, remove_method: RemoveMethod) -> Pa
                 }
                 Err(e) => {
                     let msg = translate!("shred-couldnt-rename", "file" => last_path.maybe_quote(), "new_name" => new_path.quote(), "error" => e);
-                    show_error!("{msg}");
-                    // TODO: replace with our error management
-                    std::process::exit(1);
+                    return Err(USimpleError::new(1, msg));
                 }
             }
         }
     }
 
-    last_path
+    Ok(last_path)
 }
 
 fn do_remove(
@@ -829,7 +843,7 @@ fn do_remove(
     orig_filename: &OsString,
     verbose: bool,
     remove_method: RemoveMethod,
-) -> Result<(), io::Error> {
+) -> UResult<()> {
     if verbose {
         show_error!(
             "{}",
@@ -840,10 +854,12 @@ fn do_remove(
     let remove_path = if remove_method == RemoveMethod::Unlink {
         path.with_file_name(orig_filename)
     } else {
-        wipe_name(path, verbose, remove_method)
+        wipe_name(path, verbose, remove_method)?
     };
 
-    fs::remove_file(remove_path)?;
+    fs::remove_file(remove_path).map_err_context(
+        || translate!("shred-failed-to-remove-file", "file" => path.maybe_quote()),
+    )?;
 
     if verbose {
         show_error!(
@@ -110,9 +110,6 @@ impl<R: BufRead> RandomSourceAdapter<R> {
 
     pub fn shuffle<'a, T>(&mut self, vals: &'a mut [T], amount: usize) -> UResult<&'a mut [T]> {
         // Fisher-Yates shuffle.
-        // TODO: GNU does something different if amount <= vals.len() and the input is stdin.
-        // The order changes completely and depends on --head-count.
-        // No clue what they might do differently and why.
         let amount = amount.min(vals.len());
         for idx in 0..amount {
             let other_idx = self.generate_at_most((vals.len() - idx - 1) as u64)? as usize + idx;
@@ -5,24 +5,36 @@
 
 // spell-checker:ignore (ToDO) cmdline evec nonrepeating seps shufable rvec fdata
 
+#[cfg(target_family = "wasm")]
+use std::cell::RefCell;
 use std::ffi::{OsStr, OsString};
+#[cfg(not(target_family = "wasm"))]
 use std::fs::File;
-use std::io::{self, BufReader, BufWriter, Read, Write, stdin, stdout};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+#[cfg(not(target_family = "wasm"))]
+use std::io::{stdin, stdout};
 use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
+#[cfg(target_family = "wasm")]
+use std::rc::Rc;
 use std::str::FromStr;
 
 use clap::{Arg, ArgAction, Command, builder::ValueParser};
-use rand::rngs::ThreadRng;
 use rand::{
     Rng,
     seq::{IndexedRandom, SliceRandom},
 };
+#[cfg(not(target_family = "wasm"))]
+use tempfile::TempPath;
 
 use uucore::display::{OsWrite, Quotable};
 use uucore::error::{FromIo, UResult, USimpleError, UUsageError};
 use uucore::format_usage;
+use uucore::fs::is_same_file;
+use uucore::rand_source::{self, RandSource};
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{WriteMode, stdin, stdout};
 
 mod compat_random_source;
 mod nonrepeating_iterator;
@@ -40,6 +52,15 @@ enum Mode {
 
 const BUF_SIZE: usize = 64 * 1024;
 
+/// Inputs of up to this many bytes are read whole even with `--head-count`.
+const RESERVOIR_MIN_INPUT: u64 = 8 * 1024 * 1024;
+
+/// An open input file. On WASM this is a reader from the VFS file hooks.
+#[cfg(not(target_family = "wasm"))]
+type InputFile = File;
+#[cfg(target_family = "wasm")]
+type InputFile = Box<dyn Read>;
+
 struct Options {
     head_count: u64,
     output: Option<PathBuf>,
@@ -123,29 +144,44 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         },
     };
 
+    // If the output file is also the input file, write to a temporary file
+    // and only replace the output file once everything has been written.
+    let in_place = match (&mode, &options.output) {
+        (Mode::Default(input), Some(output)) => {
+            input.as_os_str() != "-" && is_same_file(input, output)
+        }
+        _ => false,
+    };
+    let mut pending = None;
+
     let mut output = BufWriter::with_capacity(
         BUF_SIZE,
         match options.output {
             None => Box::new(stdout()) as Box<dyn OsWrite>,
-            Some(ref s) => {
-                let file = File::create(s).map_err_context(
+            Some(ref s) if in_place => {
+                let in_place = InPlaceOutput::new(s).map_err_context(
                     || translate!("shuf-error-failed-to-open-for-writing", "file" => s.quote()),
                 )?;
-                Box::new(file) as Box<dyn OsWrite>
+                let writer = in_place.writer()?;
+                pending = Some(in_place);
+                writer
             }
+            Some(ref s) => create_file(s).map_err_context(
+                || translate!("shuf-error-failed-to-open-for-writing", "file" => s.quote()),
+            )?,
         },
     );
 
     if options.head_count == 0 {
         // In this case we do want to touch the output file but we can quit immediately.
-        return Ok(());
+        return persist_output(pending);
     }
 
     let mut rng = match options.random_source {
-        RandomSource::None => WrappedRng::Default(rand::rng()),
+        RandomSource::None => WrappedRng::Default(rand_source::rng()),
         RandomSource::Seed(ref seed) => WrappedRng::Seed(SeededRng::new(seed)),
         RandomSource::File(ref r) => {
-            let file = File::open(r).map_err_context(
+            let file = open_file(r).map_err_context(
                 || translate!("shuf-error-failed-to-open-random-source", "file" => r.quote()),
             )?;
             let file = BufReader::new(file);
@@ -161,6 +197,11 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         Mode::InputRange(mut range) => {
             shuf_exec(&mut range, &options, &mut rng, &mut output)?;
         }
+        Mode::Default(filename) if use_reservoir(&filename, &options, &rng) => {
+            let lines = read_input_reservoir(&filename, &options, &mut rng)?;
+            let mut items: Vec<&[u8]> = lines.iter().map(Vec::as_slice).collect();
+            shuf_exec(&mut items, &options, &mut rng, &mut output)?;
+        }
         Mode::Default(filename) => {
             let fdata = read_input_file(&filename)?;
             let mut items = split_seps(&fdata, options.sep);
@@ -168,6 +209,124 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         }
     }
 
+    persist_output(pending)
+}
+
+/// Open `path` for reading.
+fn open_file(path: &Path) -> io::Result<InputFile> {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        File::open(path)
+    }
+    #[cfg(target_family = "wasm")]
+    {
+        uucore::wasm_io::open_file(path)
+    }
+}
+
+/// Create `path`, or truncate it if it exists, for writing.
+fn create_file(path: &Path) -> io::Result<Box<dyn OsWrite>> {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        Ok(Box::new(File::create(path)?))
+    }
+    #[cfg(target_family = "wasm")]
+    {
+        Ok(Box::new(uucore::wasm_io::open_file_write(
+            path,
+            WriteMode::Truncate,
+        )?))
+    }
+}
+
+/// A temporary file that is written instead of an output file which is also
+/// the input, and renamed over it once complete.
+#[cfg(not(target_family = "wasm"))]
+struct InPlaceOutput {
+    file: File,
+    temp_path: TempPath,
+    target: PathBuf,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl InPlaceOutput {
+    fn new(target: &Path) -> io::Result<Self> {
+        let dir = match target.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let temp = tempfile::Builder::new().prefix(".shuf").tempfile_in(dir)?;
+        std::fs::set_permissions(temp.path(), std::fs::metadata(target)?.permissions())?;
+        let (file, temp_path) = temp.into_parts();
+        Ok(Self {
+            file,
+            temp_path,
+            target: target.to_owned(),
+        })
+    }
+
+    fn writer(&self) -> io::Result<Box<dyn OsWrite>> {
+        Ok(Box::new(self.file.try_clone()?))
+    }
+
+    fn persist(self) -> io::Result<()> {
+        self.file.sync_all()?;
+        self.temp_path.persist(&self.target).map_err(|e| e.error)
+    }
+}
+
+/// The output collected in memory instead when it is also the input, as the
+/// VFS has no temporary files, and written over it once complete.
+#[cfg(target_family = "wasm")]
+struct InPlaceOutput {
+    data: Rc<RefCell<Vec<u8>>>,
+    target: PathBuf,
+}
+
+#[cfg(target_family = "wasm")]
+impl InPlaceOutput {
+    fn new(target: &Path) -> io::Result<Self> {
+        Ok(Self {
+            data: Rc::default(),
+            target: target.to_owned(),
+        })
+    }
+
+    fn writer(&self) -> io::Result<Box<dyn OsWrite>> {
+        Ok(Box::new(SharedBuffer(self.data.clone())))
+    }
+
+    fn persist(self) -> io::Result<()> {
+        let mut file = uucore::wasm_io::open_file_write(&self.target, WriteMode::Truncate)?;
+        file.write_all(&self.data.borrow())?;
+        file.flush()
+    }
+}
+
+/// A writer appending to the buffer of an [`InPlaceOutput`].
+#[cfg(target_family = "wasm")]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+#[cfg(target_family = "wasm")]
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl OsWrite for SharedBuffer {}
+
+fn persist_output(pending: Option<InPlaceOutput>) -> UResult<()> {
+    if let Some(in_place) = pending {
+        in_place
+            .persist()
+            .map_err_context(|| translate!("shuf-error-write-failed"))?;
+    }
     Ok(())
 }
 
@@ -263,7 +422,113 @@ fn read_input_file(filename: &Path) -> UResult<Vec<u8>> {
             .map_err_context(|| translate!("shuf-error-read-error"))?;
         Ok(data)
     } else {
-        std::fs::read(filename).map_err_context(|| filename.maybe_quote().to_string())
+        let mut data = Vec::new();
+        open_file(filename)
+            .and_then(|mut file| file.read_to_end(&mut data))
+            .map_err_context(|| filename.maybe_quote().to_string())?;
+        Ok(data)
+    }
+}
+
+/// Whether to sample the input while reading it rather than read it whole.
+///
+/// Like GNU, this is done when only `--head-count` lines are output and the
+/// input is larger than [`RESERVOIR_MIN_INPUT`] or of unknown size, such as a
+/// pipe. `--random-seed` always reads the input whole, so that it keeps
+/// giving the same output for the same input however it is read.
+fn use_reservoir(filename: &Path, opts: &Options, rng: &WrappedRng) -> bool {
+    !opts.repeat
+        && opts.head_count != u64::MAX
+        && !matches!(rng, WrappedRng::Seed(_))
+        && input_size(filename).is_none_or(|size| size > RESERVOIR_MIN_INPUT)
+}
+
+/// The number of bytes left to read from `filename` if it is a regular file.
+#[cfg(not(target_family = "wasm"))]
+fn input_size(filename: &Path) -> Option<u64> {
+    if filename.as_os_str() != "-" {
+        let metadata = std::fs::metadata(filename).ok()?;
+        return metadata.is_file().then_some(metadata.len());
+    }
+    #[cfg(unix)]
+    {
+        use std::io::Seek;
+        use std::mem::ManuallyDrop;
+        use std::os::fd::{AsRawFd, FromRawFd};
+        // Safety: the file is only used to query stdin, and ManuallyDrop
+        // keeps it from closing the descriptor.
+        let mut file = ManuallyDrop::new(unsafe { File::from_raw_fd(stdin().as_raw_fd()) });
+        let metadata = file.metadata().ok()?;
+        let offset = file.stream_position().ok()?;
+        metadata
+            .is_file()
+            .then(|| metadata.len().saturating_sub(offset))
+    }
+    // A piped stdin may look like a file on Windows, so never trust it.
+    #[cfg(not(unix))]
+    None
+}
+
+/// The size of the inputs is not known on WASM, where they come from the host.
+#[cfg(target_family = "wasm")]
+fn input_size(_filename: &Path) -> Option<u64> {
+    None
+}
+
+/// Read a uniform random sample of at most `--head-count` lines, holding
+/// no more than that in memory, the way GNU does: once the sample is full,
+/// each further line replaces a random line of it with a probability of
+/// the sample size over the number of lines read so far.
+fn read_input_reservoir(
+    filename: &Path,
+    opts: &Options,
+    rng: &mut WrappedRng,
+) -> UResult<Vec<Vec<u8>>> {
+    let context = || {
+        if filename.as_os_str() == "-" {
+            translate!("shuf-error-read-error")
+        } else {
+            filename.maybe_quote().to_string()
+        }
+    };
+    let input: Box<dyn Read> = if filename.as_os_str() == "-" {
+        Box::new(stdin())
+    } else {
+        Box::new(open_file(filename).map_err_context(context)?)
+    };
+    let mut input = BufReader::with_capacity(BUF_SIZE, input);
+    // Read the next line into `line`, without its separator.
+    let mut read_line = |line: &mut Vec<u8>| -> UResult<bool> {
+        line.clear();
+        if input.read_until(opts.sep, line).map_err_context(context)? == 0 {
+            return Ok(false);
+        }
+        if line.last() == Some(&opts.sep) {
+            line.pop();
+        }
+        Ok(true)
+    };
+
+    let mut reservoir = Vec::new();
+    let mut line = Vec::new();
+    while (reservoir.len() as u64) < opts.head_count {
+        if !read_line(&mut line)? {
+            return Ok(reservoir);
+        }
+        reservoir.push(std::mem::take(&mut line));
+    }
+    let mut lines_read = opts.head_count;
+    loop {
+        // GNU picks the fate of a line before trying to read it, so this
+        // draws one more number than there are lines.
+        let idx = rng.choose_from_range(0..=lines_read)?;
+        if !read_line(&mut line)? {
+            return Ok(reservoir);
+        }
+        if idx < opts.head_count {
+            std::mem::swap(&mut reservoir[idx as usize], &mut line);
+        }
+        lines_read += 1;
     }
 }
 
@@ -408,10 +673,24 @@ fn shuf_exec(
                 translate!("shuf-error-no-lines-to-repeat"),
             ));
         }
-        for _ in 0..opts.head_count {
+        // Sample with replacement straight from the loaded input, one line at
+        // a time. Without -n there is no bound: like GNU we keep going until
+        // the write fails, which on a closed pipe normally means SIGPIPE.
+        let unbounded = opts.head_count == u64::MAX;
+        let mut written = 0;
+        while unbounded || written < opts.head_count {
+            // There is no SIGPIPE on WASM or inside another program, so
+            // the host may stop an endless stream through cancellation.
+            if uucore::cancel::is_cancelled() {
+                output.flush().map_err(handle_write_error)?;
+                return Err(uucore::error::ExitCode::new(
+                    uucore::cancel::INTERRUPTED_EXIT_CODE,
+                ));
+            }
             let r = input.choose(rng)?;
             r.write_all_to(output).map_err(handle_write_error)?;
             output.write_all(&sep).map_err(handle_write_error)?;
+            written += 1;
         }
     } else {
         let shuffled = input.partial_shuffle(rng, opts.head_count)?;
@@ -442,9 +721,9 @@ fn parse_range(input_range: &str) -> Result<RangeInclusive<u64>, String> {
 }
 
 enum WrappedRng {
-    Default(ThreadRng),
+    Default(RandSource),
     Seed(SeededRng),
-    File(RandomSourceAdapter<BufReader<File>>),
+    File(RandomSourceAdapter<BufReader<InputFile>>),
 }
 
 impl WrappedRng {
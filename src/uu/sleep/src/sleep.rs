@@ -70,6 +70,34 @@ fn sleep(args: &[&str]) -> UResult<()> {
     if arg_error {
         return Err(UUsageError::new(1, ""));
     }
-    thread::sleep(sleep_dur);
+    sleep_cancellable(sleep_dur)
+}
+
+/// Sleep in short slices so that a host running us in-process can stop us,
+/// see [`uucore::cancel`]. Without a host that could, sleep in one go.
+fn sleep_cancellable(duration: Duration) -> UResult<()> {
+    const SLICE: Duration = Duration::from_millis(50);
+    if !uucore::cancel::is_cancellable() {
+        thread::sleep(duration);
+        return Ok(());
+    }
+    // Measure against a deadline where there is a clock, so that waking up
+    // for every slice does not add up to a noticeably longer sleep.
+    #[cfg(any(not(target_family = "wasm"), target_os = "wasi"))]
+    let deadline = std::time::Instant::now().checked_add(duration);
+    let mut remaining = duration;
+    while !remaining.is_zero() {
+        uucore::cancel::check()?;
+        let slice = remaining.min(SLICE);
+        thread::sleep(slice);
+        #[cfg(any(not(target_family = "wasm"), target_os = "wasi"))]
+        if let Some(deadline) = deadline {
+            remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        }
+        #[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
+        {
+            remaining -= slice;
+        }
+    }
     Ok(())
 }
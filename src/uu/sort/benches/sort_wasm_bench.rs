@@ -0,0 +1,32 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Output-heavy `sort` runs through the WASM I/O layer, with output handed to
+//! the host on every write (`0`) and with the default output buffer. Only
+//! built for WASM targets, e.g.
+//! `cargo bench -p uu_sort --bench sort_wasm_bench --target wasm32-wasip2`
+//! with a WASI runner set up.
+
+#[cfg(target_family = "wasm")]
+mod wasm {
+    use divan::{Bencher, black_box};
+    use uu_sort::uumain;
+    use uucore::benchmark::{run_util_function_wasm_io, text_data};
+    use uucore::wasm_io::DEFAULT_OUTPUT_BUFFER_SIZE;
+
+    /// Benchmark sorting ASCII lines from stdin
+    #[divan::bench(args = [0, DEFAULT_OUTPUT_BUFFER_SIZE])]
+    fn sort_stdin(bencher: Bencher, buffer_size: usize) {
+        let data = text_data::generate_ascii_data(100_000).leak();
+
+        bencher.bench(|| {
+            black_box(run_util_function_wasm_io(uumain, &[], data, buffer_size));
+        });
+    }
+}
+
+fn main() {
+    divan::main();
+}
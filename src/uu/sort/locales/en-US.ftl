@@ -17,13 +17,14 @@ sort-cannot-read = cannot read: {$path}: {$error}
 sort-open-tmp-file-failed = failed to open temporary file: {$error}
 sort-compress-prog-execution-failed = could not run compress program '{$prog}': {$error}
 sort-compress-prog-terminated-abnormally = {$prog} terminated abnormally
-sort-cannot-create-tmp-file = cannot create temporary file in {$path}:
+sort-cannot-create-tmp-file = cannot create temporary file in {$path}: {$error}
 sort-file-operands-combined = extra operand {$file}
     file operands cannot be combined with --files0-from
     Try '{$help} --help' for more information.
 sort-multiple-output-files = multiple output files specified
 sort-minus-in-stdin = when reading file names from standard input, no file name of '-' allowed
 sort-no-input-from = no input from {$file}
+sort-cannot-read-file-names = cannot read file names from {$file}
 sort-invalid-zero-length-filename = {$file}:{$line_num}: invalid zero-length file name
 sort-options-incompatible = options '-{$opt1}{$opt2}' are incompatible
 sort-invalid-key = invalid key {$key}
@@ -53,6 +54,7 @@ sort-failed-fetch-rlimit = Failed to fetch rlimit
 sort-invalid-suffix-in-option-arg = invalid suffix in --{$option} argument {$arg}
 sort-invalid-option-arg = invalid --{$option} argument {$arg}
 sort-option-arg-too-large = --{$option} argument {$arg} too large
+sort-parallel-zero = number in parallel must be nonzero
 sort-error-disorder = {$file}:{$line_number}: disorder: {$line}
 sort-error-buffer-size-too-big = Buffer size {$size} does not fit in address space
 sort-error-no-match-for-key = ^ no match for key
@@ -67,7 +69,8 @@ sort-warning-sort-rule = text ordering performed using ‘{$locale}’ sorting r
 sort-warning-key-zero-width = key {$key} has zero width and will be ignored
 sort-warning-key-numeric-spans-fields = key {$key} is numeric and spans multiple fields
 sort-warning-leading-blanks-significant = leading blanks are significant in key {$key}; consider also specifying 'b'
-sort-warning-numbers-use-decimal-point = numbers use '.' as a decimal point in this locale
+sort-warning-numbers-use-decimal-point = numbers use '{$decimal}' as a decimal point in this locale
+sort-note-numbers-use-decimal-point = note numbers use '{$decimal}' as a decimal point in this locale
 sort-warning-options-ignored = options '-{$options}' are ignored
 sort-warning-option-ignored = option '-{$option}' is ignored
 sort-warning-option-reverse-last-resort = option '-r' only applies to last-resort comparison
@@ -103,7 +106,7 @@ sort-help-separator = custom separator for -k
 sort-help-zero-terminated = line delimiter is NUL, not newline
 sort-help-parallel = change the number of threads running concurrently to NUM_THREADS
 sort-help-buf-size = sets the maximum SIZE of each segment in number of sorted items
-sort-help-tmp-dir = use DIR for temporaries, not $TMPDIR or /tmp
+sort-help-tmp-dir = use DIR for temporaries, not $TMPDIR or /tmp; multiple options specify multiple directories
 sort-help-compress-prog = compress temporary files with PROG, decompress with PROG -d; PROG has to take input from stdin and output to stdout
 sort-help-batch-size = Merge at most N_MERGE inputs at once.
 sort-help-files0-from = read input from the files specified by NUL-terminated NUL_FILE
@@ -17,13 +17,14 @@ sort-cannot-read = impossible de lire : {$path} : {$error}
 sort-open-tmp-file-failed = échec d'ouverture du fichier temporaire : {$error}
 sort-compress-prog-execution-failed = impossible d'exécuter le programme de compression '{$prog}' : {$error}
 sort-compress-prog-terminated-abnormally = {$prog} s'est terminé anormalement
-sort-cannot-create-tmp-file = impossible de créer un fichier temporaire dans {$path} :
+sort-cannot-create-tmp-file = impossible de créer un fichier temporaire dans {$path} : {$error}
 sort-file-operands-combined = opérande supplémentaire {$file}
     les opérandes de fichier ne peuvent pas être combinées avec --files0-from
     Essayez '{$help} --help' pour plus d'informations.
 sort-multiple-output-files = plusieurs fichiers de sortie spécifiés
 sort-minus-in-stdin = lors de la lecture des noms de fichiers depuis l'entrée standard, aucun nom de fichier '-' n'est autorisé
 sort-no-input-from = aucune entrée depuis {$file}
+sort-cannot-read-file-names = impossible de lire les noms de fichiers depuis {$file}
 sort-invalid-zero-length-filename = {$file}:{$line_num} : nom de fichier de longueur zéro invalide
 sort-options-incompatible = les options '-{$opt1}{$opt2}' sont incompatibles
 sort-invalid-key = clé invalide {$key}
@@ -53,6 +54,7 @@ sort-failed-fetch-rlimit = Échec de récupération de rlimit
 sort-invalid-suffix-in-option-arg = suffixe invalide dans l'argument --{$option} {$arg}
 sort-invalid-option-arg = argument --{$option} invalide {$arg}
 sort-option-arg-too-large = argument --{$option} {$arg} trop grand
+sort-parallel-zero = le nombre de threads parallèles doit être non nul
 sort-error-disorder = {$file}:{$line_number}: désordre : {$line}
 sort-error-buffer-size-too-big = La taille du tampon {$size} ne rentre pas dans l'espace d'adressage
 sort-error-no-match-for-key = ^ aucune correspondance pour la clé
@@ -86,7 +88,7 @@ sort-help-separator = séparateur personnalisé pour -k
 sort-help-zero-terminated = le délimiteur de ligne est NUL, pas nouvelle ligne
 sort-help-parallel = change le nombre de threads s'exécutant simultanément vers NUM_THREADS
 sort-help-buf-size = définit la TAILLE maximale de chaque segment en nombre d'éléments triés
-sort-help-tmp-dir = utilise RÉP pour les temporaires, pas $TMPDIR ou /tmp
+sort-help-tmp-dir = utilise RÉP pour les temporaires, pas $TMPDIR ou /tmp ; plusieurs options spécifient plusieurs répertoires
 sort-help-compress-prog = compresse les fichiers temporaires avec PROG, décompresse avec PROG -d ; PROG doit prendre l'entrée depuis stdin et sortir vers stdout
 sort-help-batch-size = Fusionne au maximum N_MERGE entrées à la fois.
 sort-help-files0-from = lit l'entrée depuis les fichiers spécifiés par FICHIER_NUL terminé par NUL
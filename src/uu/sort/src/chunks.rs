@@ -15,16 +15,18 @@ use std::{
     sync::mpsc::SyncSender,
 };
 
-use memchr::memchr_iter;
+use memchr::{memchr, memchr_iter};
+use rayon::prelude::*;
 use self_cell::self_cell;
 use uucore::error::{UResult, USimpleError};
+use uucore::key_cmp::{Month, NumInfo};
 
-use crate::{
-    GeneralBigDecimalParseResult, GlobalSettings, Line, SortMode, numeric_str_cmp::NumInfo,
-};
+use crate::{GeneralBigDecimalParseResult, GlobalSettings, Line, SortMode};
 
 const MAX_TOKEN_BUFFER_BYTES: usize = 4 * 1024 * 1024;
 const MAX_TOKEN_BUFFER_ELEMS: usize = MAX_TOKEN_BUFFER_BYTES / size_of::<Range<usize>>();
+/// The smallest part of a chunk whose keys are extracted on a thread of its own.
+const MIN_PARALLEL_SEGMENT_BYTES: usize = 256 * 1024;
 
 self_cell!(
     /// The chunk that is passed around between threads.
@@ -46,14 +48,26 @@ pub struct ChunkContents<'a> {
     pub line_count_hint: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct LineData<'a> {
     pub selections: Vec<&'a [u8]>,
     pub num_infos: Vec<NumInfo>,
     pub parsed_floats: Vec<GeneralBigDecimalParseResult>,
+    pub months: Vec<Month>,
     pub line_num_floats: Vec<Option<f64>>,
 }
 
+impl LineData<'_> {
+    /// Move the data of the lines in `other` behind the lines already here.
+    fn append(&mut self, mut other: Self) {
+        self.selections.append(&mut other.selections);
+        self.num_infos.append(&mut other.num_infos);
+        self.parsed_floats.append(&mut other.parsed_floats);
+        self.months.append(&mut other.months);
+        self.line_num_floats.append(&mut other.line_num_floats);
+    }
+}
+
 impl Chunk {
     /// Destroy this chunk and return its components to be reused.
     pub fn recycle(mut self) -> RecycledChunk {
@@ -62,6 +76,7 @@ impl Chunk {
             contents.line_data.selections.clear();
             contents.line_data.num_infos.clear();
             contents.line_data.parsed_floats.clear();
+            contents.line_data.months.clear();
             contents.line_data.line_num_floats.clear();
             contents.token_buffer.clear();
             let lines = unsafe {
@@ -85,6 +100,7 @@ impl Chunk {
                 selections,
                 std::mem::take(&mut contents.line_data.num_infos),
                 std::mem::take(&mut contents.line_data.parsed_floats),
+                std::mem::take(&mut contents.line_data.months),
                 std::mem::take(&mut contents.line_data.line_num_floats),
                 std::mem::take(&mut contents.token_buffer),
                 contents.line_count_hint,
@@ -95,9 +111,10 @@ impl Chunk {
             selections: recycled_contents.1,
             num_infos: recycled_contents.2,
             parsed_floats: recycled_contents.3,
-            line_num_floats: recycled_contents.4,
-            token_buffer: recycled_contents.5,
-            line_count_hint: recycled_contents.6,
+            months: recycled_contents.4,
+            line_num_floats: recycled_contents.5,
+            token_buffer: recycled_contents.6,
+            line_count_hint: recycled_contents.7,
             buffer: self.into_owner(),
         }
     }
@@ -116,6 +133,7 @@ pub struct RecycledChunk {
     selections: Vec<&'static [u8]>,
     num_infos: Vec<NumInfo>,
     parsed_floats: Vec<GeneralBigDecimalParseResult>,
+    months: Vec<Month>,
     line_num_floats: Vec<Option<f64>>,
     token_buffer: Vec<Range<usize>>,
     line_count_hint: usize,
@@ -129,6 +147,7 @@ impl RecycledChunk {
             selections: Vec::new(),
             num_infos: Vec::new(),
             parsed_floats: Vec::new(),
+            months: Vec::new(),
             line_num_floats: Vec::new(),
             token_buffer: Vec::new(),
             line_count_hint: 0,
@@ -174,6 +193,7 @@ pub fn read<T: Read>(
         selections,
         num_infos,
         parsed_floats,
+        months,
         line_num_floats,
         mut token_buffer,
         mut line_count_hint,
@@ -211,6 +231,7 @@ pub fn read<T: Read>(
                 selections,
                 num_infos,
                 parsed_floats,
+                months,
                 line_num_floats,
             };
             parse_lines(
@@ -250,6 +271,7 @@ pub(crate) fn parse_lines<'a>(
     assert!(line_data.selections.is_empty());
     assert!(line_data.num_infos.is_empty());
     assert!(line_data.parsed_floats.is_empty());
+    assert!(line_data.months.is_empty());
     assert!(line_data.line_num_floats.is_empty());
     token_buffer.clear();
     if token_buffer.capacity() > MAX_TOKEN_BUFFER_ELEMS {
@@ -286,9 +308,86 @@ pub(crate) fn parse_lines<'a>(
             .parsed_floats
             .reserve(estimated.saturating_mul(settings.precomputed.floats_per_line));
     }
+    if settings.precomputed.months_per_line > 0 {
+        line_data
+            .months
+            .reserve(estimated.saturating_mul(settings.precomputed.months_per_line));
+    }
     if settings.mode == SortMode::Numeric {
         line_data.line_num_floats.reserve(estimated);
     }
+    let segments = split_segments(read, separator, settings);
+    if segments.len() > 1 {
+        // Extract the keys of each segment on its own thread, then append
+        // the results in order. Each line stores the same number of entries
+        // in every `LineData` vector, so only the line indices need shifting.
+        let parsed: Vec<_> = segments
+            .into_par_iter()
+            .map(|segment| {
+                let mut lines = Vec::new();
+                let mut line_data = LineData::default();
+                let mut token_buffer = Vec::new();
+                parse_segment(
+                    segment,
+                    &mut lines,
+                    &mut line_data,
+                    &mut token_buffer,
+                    separator,
+                    settings,
+                );
+                (lines, line_data)
+            })
+            .collect();
+        for (segment_lines, segment_data) in parsed {
+            let offset = lines.len();
+            lines.extend(segment_lines.into_iter().map(|mut line| {
+                line.index += offset;
+                line
+            }));
+            line_data.append(segment_data);
+        }
+    } else {
+        parse_segment(read, lines, line_data, token_buffer, separator, settings);
+    }
+    *line_count_hint = exact_line_count.unwrap_or(lines.len());
+}
+
+/// Split `read` into at most one segment per thread of `--parallel`, each
+/// made of whole lines, so that their keys can be extracted concurrently.
+///
+/// Chunks are not split when there are no keys to extract, or when they are
+/// too small for the threads to pay off.
+fn split_segments<'a>(read: &'a [u8], separator: u8, settings: &GlobalSettings) -> Vec<&'a [u8]> {
+    let count = settings
+        .threads
+        .min(read.len() / MIN_PARALLEL_SEGMENT_BYTES)
+        .max(1);
+    if count == 1 || !settings.needs_line_data() {
+        return vec![read];
+    }
+    let mut segments = Vec::with_capacity(count);
+    let mut start = 0;
+    for i in 1..count {
+        let target = (read.len() * i / count).max(start);
+        let Some(end) = memchr(separator, &read[target..]).map(|idx| target + idx) else {
+            break;
+        };
+        segments.push(&read[start..end]);
+        start = end + 1;
+    }
+    segments.push(&read[start..]);
+    segments
+}
+
+/// Split `read` into `Line`s numbered from 0, and add them to `lines`.
+fn parse_segment<'a>(
+    read: &'a [u8],
+    lines: &mut Vec<Line<'a>>,
+    line_data: &mut LineData<'a>,
+    token_buffer: &mut Vec<Range<usize>>,
+    separator: u8,
+    settings: &GlobalSettings,
+) {
     let mut start = 0usize;
     let mut index = 0usize;
     for sep_idx in memchr_iter(separator, read) {
@@ -299,7 +398,6 @@ pub(crate) fn parse_lines<'a>(
     }
     let line = &read[start..];
     lines.push(Line::create(line, index, line_data, token_buffer, settings));
-    *line_count_hint = exact_line_count.unwrap_or(index + 1);
 }
 
 /// Read from `file` into `buffer`.
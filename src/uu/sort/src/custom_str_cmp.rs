@@ -6,14 +6,84 @@
 //! Custom string comparisons.
 //!
 //! The goal is to compare strings without transforming them first (i.e. not allocating new strings)
+//! Only the keys of the modes that compare them as a whole, like `-V`, are transformed, by
+//! [`custom_str_transform`].
+//!
+//! A character is a single byte, as in GNU sort, unless the locale uses UTF-8:
+//! then it is a UTF-8 sequence, and every byte that does not start a valid
+//! sequence is a character of its own. This applies to the character
+//! positions of `-k` and to the `d` and `i` flags.
 
+use std::borrow::Cow;
 use std::cmp::Ordering;
 
+use uucore::i18n::{UEncoding, get_locale_encoding};
+
+/// Whether characters are UTF-8 sequences rather than single bytes.
+pub fn multibyte_locale() -> bool {
+    get_locale_encoding() == UEncoding::Utf8
+}
+
+/// The length of the character at the start of `s` when characters are
+/// UTF-8 sequences, or 0 if `s` is empty.
+pub fn utf8_char_len(s: &[u8]) -> usize {
+    let len = match s.first() {
+        None => return 0,
+        Some(0xC2..=0xDF) => 2,
+        Some(0xE0..=0xEF) => 3,
+        Some(0xF0..=0xF4) => 4,
+        Some(_) => return 1,
+    };
+    if s.get(..len).is_some_and(|c| std::str::from_utf8(c).is_ok()) {
+        len
+    } else {
+        1
+    }
+}
+
+/// Split `s` into the characters of a UTF-8 locale.
+fn utf8_chars(mut s: &[u8]) -> impl Iterator<Item = &[u8]> {
+    std::iter::from_fn(move || {
+        if s.is_empty() {
+            return None;
+        }
+        let (c, rest) = s.split_at(utf8_char_len(s));
+        s = rest;
+        Some(c)
+    })
+}
+
+/// Whether `c` is a blank: blanks separate fields and are skipped by `-b`,
+/// and `-d` keeps them. These are the blanks of GNU sort in the C locale.
+pub fn is_blank(c: u8) -> bool {
+    matches!(c, b' ' | b'\t' | b'\n')
+}
+
 fn filter_char(c: u8, ignore_non_printing: bool, ignore_non_dictionary: bool) -> bool {
-    if ignore_non_dictionary && !(c.is_ascii_alphanumeric() || c.is_ascii_whitespace()) {
+    if ignore_non_dictionary && !(c.is_ascii_alphanumeric() || is_blank(c)) {
         return false;
     }
-    if ignore_non_printing && (c.is_ascii_control() || !c.is_ascii()) {
+    if ignore_non_printing && !(c.is_ascii_graphic() || c == b' ') {
+        return false;
+    }
+    true
+}
+
+/// Like [`filter_char`], for a character of a UTF-8 locale. Invalid bytes
+/// are neither alphanumeric nor printable.
+fn filter_utf8_char(c: &[u8], ignore_non_printing: bool, ignore_non_dictionary: bool) -> bool {
+    if let [c] = c {
+        if c.is_ascii() {
+            return filter_char(*c, ignore_non_printing, ignore_non_dictionary);
+        }
+    }
+    let Some(c) = std::str::from_utf8(c).ok().and_then(|c| c.chars().next()) else {
+        return !(ignore_non_printing || ignore_non_dictionary);
+    };
+    if ignore_non_dictionary && !(c.is_alphanumeric() || c.is_whitespace()) {
+        return false;
+    }
+    if ignore_non_printing && c.is_control() {
         return false;
     }
     true
@@ -38,6 +108,15 @@ pub fn custom_str_cmp(
         // There are no custom settings. Fall back to the default strcmp, which is faster.
         return a.cmp(b);
     }
+    if multibyte_locale() {
+        return custom_utf8_cmp(
+            a,
+            b,
+            ignore_non_printing,
+            ignore_non_dictionary,
+            ignore_case,
+        );
+    }
     let mut a_chars = a
         .iter()
         .filter(|&&c| filter_char(c, ignore_non_printing, ignore_non_dictionary));
@@ -60,3 +139,62 @@ pub fn custom_str_cmp(
         }
     }
 }
+
+/// [`custom_str_cmp`] for the characters of a UTF-8 locale. Only ASCII
+/// letters are folded by `ignore_case`.
+fn custom_utf8_cmp(
+    a: &[u8],
+    b: &[u8],
+    ignore_non_printing: bool,
+    ignore_non_dictionary: bool,
+    ignore_case: bool,
+) -> Ordering {
+    let mut a_chars =
+        utf8_chars(a).filter(|c| filter_utf8_char(c, ignore_non_printing, ignore_non_dictionary));
+    let mut b_chars =
+        utf8_chars(b).filter(|c| filter_utf8_char(c, ignore_non_printing, ignore_non_dictionary));
+    loop {
+        let ordering = match (a_chars.next(), b_chars.next()) {
+            (None, None) => return Ordering::Equal,
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (Some([a_char]), Some([b_char])) => cmp_chars(*a_char, *b_char, ignore_case),
+            (Some(a_char), Some(b_char)) => a_char.cmp(b_char),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// The bytes [`custom_str_cmp`] compares `s` by: those of the characters that
+/// are not ignored, with ASCII letters in upper case if `ignore_case` is set.
+/// `s` is borrowed as it is when nothing is to be ignored or folded.
+pub fn custom_str_transform(
+    s: &[u8],
+    ignore_non_printing: bool,
+    ignore_non_dictionary: bool,
+    ignore_case: bool,
+) -> Cow<'_, [u8]> {
+    if !(ignore_case || ignore_non_dictionary || ignore_non_printing) {
+        return Cow::Borrowed(s);
+    }
+    let mut transformed = Vec::with_capacity(s.len());
+    if multibyte_locale() {
+        for c in utf8_chars(s)
+            .filter(|c| filter_utf8_char(c, ignore_non_printing, ignore_non_dictionary))
+        {
+            transformed.extend_from_slice(c);
+        }
+    } else {
+        transformed.extend(
+            s.iter()
+                .copied()
+                .filter(|&c| filter_char(c, ignore_non_printing, ignore_non_dictionary)),
+        );
+    }
+    if ignore_case {
+        transformed.make_ascii_uppercase();
+    }
+    Cow::Owned(transformed)
+}
@@ -24,14 +24,20 @@ use std::sync::mpsc::{Receiver, SyncSender};
 use std::thread;
 
 use itertools::Itertools;
+#[cfg(not(target_family = "wasm"))]
+use uucore::display::Quotable;
 use uucore::error::UResult;
 #[cfg(not(target_family = "wasm"))]
-use uucore::error::strip_errno;
+use uucore::error::{FromIo, strip_errno};
+#[cfg(not(target_family = "wasm"))]
+use uucore::translate;
 
 use crate::Output;
 #[cfg(not(target_family = "wasm"))]
 use crate::chunks::RecycledChunk;
 #[cfg(not(target_family = "wasm"))]
+use crate::merge;
+#[cfg(not(target_family = "wasm"))]
 use crate::merge::WriteableCompressedTmpFile;
 #[cfg(not(target_family = "wasm"))]
 use crate::merge::WriteablePlainTmpFile;
@@ -43,8 +49,6 @@ use crate::{
     chunks::{self, Chunk},
     compare_by, sort_by,
 };
-#[cfg(not(target_family = "wasm"))]
-use crate::merge;
 use crate::{Line, print_sorted};
 
 // Note: update `test_sort::test_start_buffer` if this size is changed
@@ -65,6 +69,7 @@ pub fn ext_sort(
     // Read all input into a single buffer
     let mut all_data = Vec::new();
     for file_result in files {
+        uucore::cancel::check()?;
         let mut file = file_result?;
         let prev_len = all_data.len();
         file.read_to_end(&mut all_data)?;
@@ -85,6 +90,7 @@ pub fn ext_sort(
             selections: Vec::new(),
             num_infos: Vec::new(),
             parsed_floats: Vec::new(),
+            months: Vec::new(),
             line_num_floats: Vec::new(),
         };
         let mut token_buffer = Vec::new();
@@ -116,8 +122,7 @@ pub fn ext_sort(
     if settings.unique {
         print_sorted(
             chunk.lines().iter().dedup_by(|a, b| {
-                compare_by(a, b, settings, chunk.line_data(), chunk.line_data())
-                    == Ordering::Equal
+                compare_by(a, b, settings, chunk.line_data(), chunk.line_data()) == Ordering::Equal
             }),
             settings,
             output,
@@ -353,6 +358,7 @@ fn read_write_loop<I: WriteableTmpFile>(
     let mut sender_option = Some(sender);
     let mut tmp_files = vec![];
     loop {
+        uucore::cancel::check()?;
         let Ok(chunk) = receiver.recv() else {
             return Ok(ReadResult::WroteChunksToFile { tmp_files });
         };
@@ -394,15 +400,19 @@ fn write<I: WriteableTmpFile>(
     compress_prog: Option<&str>,
     separator: u8,
 ) -> UResult<I::Closed> {
+    let path = file.1.clone();
     let mut tmp_file = I::create(file, compress_prog)?;
-    write_lines(chunk.lines(), tmp_file.as_write(), separator);
+    write_lines(chunk.lines(), tmp_file.as_write(), separator).map_err_context(
+        || translate!("sort-error-write-failed", "output" => path.maybe_quote()),
+    )?;
     tmp_file.finished_writing()
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn write_lines<T: Write>(lines: &[Line], writer: &mut T, separator: u8) {
+fn write_lines<T: Write>(lines: &[Line], writer: &mut T, separator: u8) -> std::io::Result<()> {
     for s in lines {
-        writer.write_all(s.line).unwrap();
-        writer.write_all(&[separator]).unwrap();
+        writer.write_all(s.line)?;
+        writer.write_all(&[separator])?;
     }
+    Ok(())
 }
@@ -25,10 +25,13 @@ use std::{
 };
 
 use compare::Compare;
+use uucore::display::Quotable;
 use uucore::error::{FromIo, UResult};
+use uucore::fs::is_same_file;
+use uucore::translate;
 
 use crate::{
-    GlobalSettings, Output, SortError,
+    CANCEL_CHECK_INTERVAL, GlobalSettings, Output, SortError,
     chunks::{self, Chunk, RecycledChunk},
     compare_by, current_open_fd_count, fd_soft_limit, open,
     tmp_dir::TmpDirWrapper,
@@ -42,19 +45,17 @@ fn replace_output_file_in_input_files(
     tmp_dir: &mut TmpDirWrapper,
 ) -> UResult<()> {
     let mut copy: Option<PathBuf> = None;
-    if let Some(Ok(output_path)) = output.map(|path| Path::new(path).canonicalize()) {
+    if let Some(output_path) = output.map(Path::new) {
         for file in files {
-            if let Ok(file_path) = Path::new(file).canonicalize() {
-                if file_path == output_path {
-                    if let Some(copy) = &copy {
-                        *file = copy.clone().into_os_string();
-                    } else {
-                        let (_file, copy_path) = tmp_dir.next_file()?;
-                        fs::copy(file_path, &copy_path)
-                            .map_err(|error| SortError::OpenTmpFileFailed { error })?;
-                        *file = copy_path.clone().into_os_string();
-                        copy = Some(copy_path);
-                    }
+            if is_same_file(&*file, output_path) {
+                if let Some(copy) = &copy {
+                    *file = copy.clone().into_os_string();
+                } else {
+                    let (_file, copy_path) = tmp_dir.next_file()?;
+                    fs::copy(&*file, &copy_path)
+                        .map_err(|error| SortError::OpenTmpFileFailed { error })?;
+                    *file = copy_path.clone().into_os_string();
+                    copy = Some(copy_path);
                 }
             }
         }
@@ -303,15 +304,22 @@ struct FileMerger<'a> {
 impl FileMerger<'_> {
     /// Write the merged contents to the output file.
     fn write_all(self, settings: &GlobalSettings, output: Output) -> UResult<()> {
-        let mut out = output.into_write();
-        self.write_all_to(settings, &mut out)
+        let mut out = output.into_write()?;
+        self.write_all_to(settings, &mut out)?;
+        out.finish().map_err_context(|| "write failed".into())
     }
 
     fn write_all_to(mut self, settings: &GlobalSettings, out: &mut impl Write) -> UResult<()> {
+        let mut written = 0usize;
         while self
             .write_next(settings, out)
             .map_err_context(|| "write failed".into())?
-        {}
+        {
+            written += 1;
+            if written.is_multiple_of(CANCEL_CHECK_INTERVAL) {
+                uucore::cancel::check()?;
+            }
+        }
         drop(self.request_sender);
         self.reader_join_handle.join().unwrap()
     }
@@ -458,7 +466,10 @@ impl WriteableTmpFile for WriteablePlainTmpFile {
         })
     }
 
-    fn finished_writing(self) -> UResult<Self::Closed> {
+    fn finished_writing(mut self) -> UResult<Self::Closed> {
+        self.file.flush().map_err_context(
+            || translate!("sort-error-write-failed", "output" => self.path.maybe_quote()),
+        )?;
         Ok(ClosedPlainTmpFile { path: self.path })
     }
 
@@ -530,9 +541,13 @@ impl WriteableTmpFile for WriteableCompressedTmpFile {
         })
     }
 
-    fn finished_writing(self) -> UResult<Self::Closed> {
+    fn finished_writing(mut self) -> UResult<Self::Closed> {
+        let flushed = self.child_stdin.flush();
         drop(self.child_stdin);
         check_child_success(self.child, &self.compress_prog)?;
+        flushed.map_err_context(
+            || translate!("sort-error-write-failed", "output" => self.path.maybe_quote()),
+        )?;
         Ok(ClosedCompressedTmpFile {
             path: self.path,
             compress_prog: self.compress_prog,
@@ -15,7 +15,6 @@ mod chunks;
 mod custom_str_cmp;
 mod ext_sort;
 mod merge;
-mod numeric_str_cmp;
 mod tmp_dir;
 
 use ahash::AHashMap;
@@ -23,21 +22,22 @@ use bigdecimal::BigDecimal;
 use chunks::LineData;
 use clap::builder::ValueParser;
 use clap::{Arg, ArgAction, ArgMatches, Command};
-use custom_str_cmp::custom_str_cmp;
+use custom_str_cmp::{
+    custom_str_cmp, custom_str_transform, is_blank, multibyte_locale, utf8_char_len,
+};
 use ext_sort::ext_sort;
-use numeric_str_cmp::{NumInfo, NumInfoParseSettings, human_numeric_str_cmp, numeric_str_cmp};
 use rand::{Rng, rng};
 use rayon::prelude::*;
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::env;
 use std::ffi::{OsStr, OsString};
+#[cfg(not(target_family = "wasm"))]
 use std::fs::{File, OpenOptions};
 use std::hash::{BuildHasher, Hash, Hasher};
-use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::io::{BufReader, BufWriter, Read, Write};
 #[cfg(not(target_family = "wasm"))]
 use std::io::{stdin, stdout};
-#[cfg(target_family = "wasm")]
-use uucore::wasm_io::{stdin, stdout};
 use std::num::{IntErrorKind, NonZero};
 use std::ops::Range;
 #[cfg(unix)]
@@ -47,12 +47,16 @@ use std::path::PathBuf;
 use std::str::Utf8Error;
 use thiserror::Error;
 use uucore::display::Quotable;
+use uucore::error::{ExitCodePolicy, FailureClass, UError, UResult, USimpleError, UUsageError};
 use uucore::error::{FromIo, strip_errno};
-use uucore::error::{UError, UResult, USimpleError, UUsageError};
 use uucore::extendedbigdecimal::ExtendedBigDecimal;
+use uucore::files0::files0;
 #[cfg(feature = "i18n-collator")]
 use uucore::i18n::collator::locale_cmp;
 use uucore::i18n::decimal::locale_decimal_separator;
+use uucore::key_cmp::{
+    Month, NumInfo, NumInfoParseSettings, human_numeric_str_cmp, month_parse, numeric_str_cmp,
+};
 use uucore::line_ending::LineEnding;
 use uucore::parser::num_parser::{ExtendedParser, ExtendedParserError};
 use uucore::parser::parse_size::{ParseSizeError, Parser};
@@ -61,6 +65,8 @@ use uucore::posix::{MODERN, TRADITIONAL};
 use uucore::show_error;
 use uucore::translate;
 use uucore::version_cmp::version_cmp;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{stdin, stdout};
 use uucore::{format_usage, i18n};
 
 use crate::buffer_hint::automatic_buffer_size;
@@ -147,6 +153,10 @@ const MIN_AUTOMATIC_BUF_SIZE: usize = 512 * 1024; // 512 KiB
 const FALLBACK_AUTOMATIC_BUF_SIZE: usize = 32 * 1024 * 1024; // 32 MiB
 const MAX_AUTOMATIC_BUF_SIZE: usize = 1024 * 1024 * 1024; // 1 GiB
 
+// Output loops poll `uucore::cancel` once per this many lines, which keeps
+// the check out of the way of the per-line work.
+const CANCEL_CHECK_INTERVAL: usize = 4096;
+
 #[derive(Debug, Error)]
 pub enum SortError {
     #[error("{}", format_disorder(.file, .line_number, .line, .silent))]
@@ -178,8 +188,11 @@ pub enum SortError {
     #[error("{}", translate!("sort-compress-prog-terminated-abnormally", "prog" => .prog.quote()))]
     CompressProgTerminatedAbnormally { prog: String },
 
-    #[error("{}", translate!("sort-cannot-create-tmp-file", "path" => format!("{}", .path.quote())))]
-    TmpFileCreationFailed { path: PathBuf },
+    #[error("{}", translate!("sort-cannot-create-tmp-file", "path" => format!("{}", .path.quote()), "error" => strip_errno(.error)))]
+    TmpFileCreationFailed {
+        path: PathBuf,
+        error: std::io::Error,
+    },
 
     #[error("{}", translate!("sort-file-operands-combined", "file" => format!("{}", .file.quote()), "help" => uucore::execution_phrase()))]
     FileOperandsCombined { file: PathBuf },
@@ -193,6 +206,9 @@ pub enum SortError {
     #[error("{}", translate!("sort-minus-in-stdin"))]
     MinusInStdIn,
 
+    #[error("{}", translate!("sort-cannot-read-file-names", "file" => format!("{}", .file.quote())))]
+    Files0ReadFailed { file: PathBuf },
+
     #[error("{}", translate!("sort-no-input-from", "file" => format!("{}", .file.quote())))]
     EmptyInputFile { file: PathBuf },
 
@@ -204,7 +220,7 @@ impl UError for SortError {
     fn code(&self) -> i32 {
         match self {
             Self::Disorder { .. } => 1,
-            _ => 2,
+            _ => ExitCodePolicy::SORT.other,
         }
     }
 }
@@ -235,40 +251,104 @@ fn count_non_null_bytes(bytes: &[u8]) -> usize {
     bytes.iter().filter(|&&c| c != b'\0').count()
 }
 
+/// The open output file. On WASM this is a writer from the VFS write hooks.
+#[cfg(not(target_family = "wasm"))]
+type OutputFile = File;
+#[cfg(target_family = "wasm")]
+type OutputFile = Box<dyn Write>;
+
 pub struct Output {
-    file: Option<(OsString, File)>,
+    file: Option<(OsString, OutputFile)>,
+    /// Whether the output file is also one of the inputs. The output is then
+    /// written to a temporary file that replaces the output file once complete.
+    #[cfg(not(target_family = "wasm"))]
+    in_place: bool,
 }
 
 impl Output {
-    fn new(name: Option<impl AsRef<OsStr>>) -> UResult<Self> {
+    fn new(name: Option<impl AsRef<OsStr>>, inputs: &[OsString]) -> UResult<Self> {
+        #[cfg(not(target_family = "wasm"))]
+        let in_place = name.as_ref().is_some_and(|name| {
+            let output = Path::new(name.as_ref());
+            inputs
+                .iter()
+                .any(|input| input != STDIN_FILE && uucore::fs::is_same_file(input, output))
+        });
+        #[cfg(target_family = "wasm")]
+        let _ = inputs;
+
         let file = if let Some(name) = name {
             let path = Path::new(name.as_ref());
             // This is different from `File::create()` because we don't truncate the output yet.
             // This allows using the output file as an input file.
+            #[cfg(not(target_family = "wasm"))]
             #[allow(clippy::suspicious_open_options)]
-            let file = OpenOptions::new()
-                .write(true)
-                .create(true)
-                .open(path)
-                .map_err(|e| SortError::OpenFailed {
-                    path: path.to_owned(),
-                    error: e,
-                })?;
+            let file = OpenOptions::new().write(true).create(true).open(path);
+            #[cfg(target_family = "wasm")]
+            let file = uucore::wasm_io::open_file_write(path, uucore::wasm_io::WriteMode::Create);
+            let file = file.map_err(|e| SortError::OpenFailed {
+                path: path.to_owned(),
+                error: e,
+            })?;
             Some((name.as_ref().to_owned(), file))
         } else {
             None
         };
-        Ok(Self { file })
+        Ok(Self {
+            file,
+            #[cfg(not(target_family = "wasm"))]
+            in_place,
+        })
     }
 
-    fn into_write(self) -> BufWriter<Box<dyn Write>> {
-        BufWriter::new(match self.file {
+    fn into_write(self) -> UResult<OutputWriter> {
+        #[cfg(not(target_family = "wasm"))]
+        if let (true, Some((name, _file))) = (self.in_place, &self.file) {
+            let path = Path::new(name);
+            let (file, temp_path) =
+                create_in_place_temp_file(path).map_err(|error| SortError::OpenFailed {
+                    path: path.to_owned(),
+                    error,
+                })?;
+            let sync = file.try_clone().map_err(|error| SortError::OpenFailed {
+                path: path.to_owned(),
+                error,
+            })?;
+            return Ok(OutputWriter {
+                writer: BufWriter::new(Box::new(file)),
+                pending: Some(PendingRename {
+                    file: sync,
+                    temp_path,
+                    target: path.to_owned(),
+                }),
+            });
+        }
+
+        let writer: BufWriter<Box<dyn Write>> = BufWriter::new(match self.file {
+            #[cfg(not(target_family = "wasm"))]
             Some((_name, file)) => {
                 // truncate the file
                 let _ = file.set_len(0);
                 Box::new(file)
             }
+            // VFS writers cannot be truncated in place, so reopen the file
+            // now that all input has been read.
+            #[cfg(target_family = "wasm")]
+            Some((name, file)) => {
+                drop(file);
+                let path = Path::new(&name);
+                uucore::wasm_io::open_file_write(path, uucore::wasm_io::WriteMode::Truncate)
+                    .map_err(|e| SortError::OpenFailed {
+                        path: path.to_owned(),
+                        error: e,
+                    })?
+            }
             None => Box::new(stdout()),
+        });
+        Ok(OutputWriter {
+            writer,
+            #[cfg(not(target_family = "wasm"))]
+            pending: None,
         })
     }
 
@@ -280,6 +360,66 @@ impl Output {
     }
 }
 
+/// The writer returned by [`Output::into_write`]. [`OutputWriter::finish`]
+/// must be called once everything has been written.
+pub struct OutputWriter {
+    writer: BufWriter<Box<dyn Write>>,
+    #[cfg(not(target_family = "wasm"))]
+    pending: Option<PendingRename>,
+}
+
+/// A temporary file that still has to replace the output file.
+#[cfg(not(target_family = "wasm"))]
+struct PendingRename {
+    file: File,
+    temp_path: tempfile::TempPath,
+    target: PathBuf,
+}
+
+impl OutputWriter {
+    /// Flush the output. When sorting in place, this also syncs the temporary
+    /// file and atomically renames it over the output file.
+    fn finish(mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(pending) = self.pending.take() {
+            pending.file.sync_all()?;
+            pending
+                .temp_path
+                .persist(&pending.target)
+                .map_err(|e| e.error)?;
+        }
+        Ok(())
+    }
+}
+
+impl Write for OutputWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.writer.write_all(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Create a temporary file in the directory of `path` with the same
+/// permissions, so that it can later be renamed over `path`.
+#[cfg(not(target_family = "wasm"))]
+fn create_in_place_temp_file(path: &Path) -> std::io::Result<(File, tempfile::TempPath)> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let temp = tempfile::Builder::new().prefix(".sort").tempfile_in(dir)?;
+    std::fs::set_permissions(temp.path(), std::fs::metadata(path)?.permissions())?;
+    Ok(temp.into_parts())
+}
+
 #[derive(Clone)]
 pub struct GlobalSettings {
     mode: SortMode,
@@ -297,8 +437,11 @@ pub struct GlobalSettings {
     salt: Option<[u8; 16]>,
     random_source: Option<PathBuf>,
     selectors: Vec<FieldSelector>,
+    /// Whether `selectors` come from `-k` options rather than standing for the whole line.
+    keys_specified: bool,
     separator: Option<u8>,
-    threads: String,
+    /// The number of threads that parse and sort lines, from `--parallel`.
+    threads: usize,
     line_ending: LineEnding,
     buffer_size: usize,
     buffer_size_is_explicit: bool,
@@ -340,6 +483,7 @@ struct Precomputed {
     needs_tokens: bool,
     num_infos_per_line: usize,
     floats_per_line: usize,
+    months_per_line: usize,
     selections_per_line: usize,
     fast_lexicographic: bool,
     fast_ascii_insensitive: bool,
@@ -348,6 +492,17 @@ struct Precomputed {
 }
 
 impl GlobalSettings {
+    /// Whether lines need more than their bytes to be compared, i.e. keys or
+    /// numbers that are extracted into `LineData` when the line is read.
+    fn needs_line_data(&self) -> bool {
+        self.precomputed.needs_tokens
+            || self.precomputed.selections_per_line > 0
+            || self.precomputed.num_infos_per_line > 0
+            || self.precomputed.floats_per_line > 0
+            || self.precomputed.months_per_line > 0
+            || self.mode == SortMode::Numeric
+    }
+
     /// Parse a SIZE string into a number of bytes.
     /// A size string comprises an integer and an optional unit.
     /// The unit may be k, K, m, M, g, G, t, T, P, E, Z, Y (powers of 1024), or b which is 1.
@@ -388,6 +543,11 @@ impl GlobalSettings {
             .iter()
             .filter(|s| matches!(s.settings.mode, SortMode::GeneralNumeric))
             .count();
+        self.precomputed.months_per_line = self
+            .selectors
+            .iter()
+            .filter(|s| matches!(s.settings.mode, SortMode::Month))
+            .count();
 
         let uses_numeric = self
             .selectors
@@ -427,6 +587,7 @@ impl GlobalSettings {
                     && !selector.settings.dictionary_order
                     && !selector.settings.ignore_non_printing
                     && !selector.settings.ignore_blanks
+                    && selector.settings.reverse == self.reverse
             }
     }
 
@@ -446,6 +607,7 @@ impl GlobalSettings {
                     && !selector.settings.dictionary_order
                     && !selector.settings.ignore_non_printing
                     && !selector.settings.ignore_blanks
+                    && selector.settings.reverse == self.reverse
             }
     }
 }
@@ -468,8 +630,9 @@ impl Default for GlobalSettings {
             salt: None,
             random_source: None,
             selectors: vec![],
+            keys_specified: false,
             separator: None,
-            threads: String::new(),
+            threads: std::thread::available_parallelism().map_or(1, NonZero::get),
             line_ending: LineEnding::Newline,
             buffer_size: FALLBACK_AUTOMATIC_BUF_SIZE,
             buffer_size_is_explicit: false,
@@ -510,6 +673,24 @@ impl Default for KeySettings {
     }
 }
 
+impl KeySettings {
+    /// The key as the modes that compare it as a whole see it, like GNU:
+    /// without the characters `-d` or `-i` ignore, and in upper case with
+    /// `-f`. The default mode ignores and folds as it compares instead.
+    ///
+    /// Only `-f` goes with the numeric modes, so the transformed key has its
+    /// number at the same offsets as the key, and its unit possibly in
+    /// another case.
+    fn transform<'a>(&self, key: &'a [u8]) -> Cow<'a, [u8]> {
+        custom_str_transform(
+            key,
+            self.ignore_non_printing,
+            self.dictionary_order,
+            self.ignore_case,
+        )
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct ModeFlags {
     numeric: bool,
@@ -627,6 +808,7 @@ fn incompatible_options_error(opts: &str) -> Box<dyn UError> {
 enum Selection<'a> {
     AsBigDecimal(GeneralBigDecimalParseResult),
     WithNumInfo(&'a [u8], NumInfo),
+    WithMonth(&'a [u8], Month),
     Str(&'a [u8]),
 }
 
@@ -650,12 +832,7 @@ impl<'a> Line<'a> {
         token_buffer: &mut Vec<Field>,
         settings: &GlobalSettings,
     ) -> Self {
-        let needs_line_data = settings.precomputed.needs_tokens
-            || settings.precomputed.selections_per_line > 0
-            || settings.precomputed.num_infos_per_line > 0
-            || settings.precomputed.floats_per_line > 0
-            || settings.mode == SortMode::Numeric;
-        if !needs_line_data {
+        if !settings.needs_line_data() {
             return Self { line, index };
         }
         token_buffer.clear();
@@ -687,6 +864,12 @@ impl<'a> Line<'a> {
                     line_data.num_infos.push(num_info);
                     line_data.selections.push(str);
                 }
+                Selection::WithMonth(str, month) => {
+                    line_data.months.push(month);
+                    if selector.needs_selection {
+                        line_data.selections.push(str);
+                    }
+                }
                 Selection::Str(str) => {
                     if selector.needs_selection {
                         line_data.selections.push(str);
@@ -756,12 +939,12 @@ impl<'a> Line<'a> {
                     if num_range == (0..0) {
                         // This was not a valid number.
                         // Report no match at the first non-whitespace character.
-                        let leading_whitespace = self.line[selection.clone()]
+                        let leading_whitespace = self.line[initial_selection.clone()]
                             .iter()
                             .position(|c| !c.is_ascii_whitespace())
-                            .unwrap_or(0);
-                        selection.start += leading_whitespace;
-                        selection.end += leading_whitespace;
+                            .unwrap_or(initial_selection.len());
+                        selection.start = initial_selection.start + leading_whitespace;
+                        selection.end = selection.start;
                     } else {
                         // include a trailing si unit
                         if selector.settings.mode == SortMode::HumanNumeric {
@@ -838,18 +1021,14 @@ impl<'a> Line<'a> {
             }
         }
 
-        if settings.mode != SortMode::Random
-            && !settings.stable
+        if !settings.stable
             && !settings.unique
             && (settings.dictionary_order
                 || settings.ignore_leading_blanks
                 || settings.ignore_case
                 || settings.ignore_non_printing
                 || settings.mode != SortMode::Default
-                || settings
-                    .selectors
-                    .last()
-                    .is_none_or(|selector| selector != &FieldSelector::default()))
+                || settings.keys_specified)
         {
             // A last resort comparator is in use, underline the whole line.
             if self.line.is_empty() {
@@ -895,7 +1074,7 @@ fn tokenize_default(
     // pretend that there was whitespace in front of the line
     let mut previous_was_whitespace = true;
     for (idx, char) in line.iter().enumerate() {
-        let is_whitespace = char.is_ascii_whitespace();
+        let is_whitespace = is_blank(*char);
         let treat_as_separator = if is_whitespace {
             if blank_thousands_sep && *char == b' ' {
                 !is_blank_thousands_sep(line, idx, allow_unit_after_blank)
@@ -1216,9 +1395,9 @@ impl FieldSelector {
         };
         let mut range_str = &line[self.get_range(line, tokens)];
         if self.settings.mode == SortMode::Numeric || self.settings.mode == SortMode::HumanNumeric {
-            // Parse NumInfo for this number.
+            // Parse NumInfo for this number, whose unit `-f` may turn into another.
             let (info, num_range) = NumInfo::parse(
-                range_str,
+                &self.settings.transform(range_str),
                 &numeric_locale.num_info_settings(self.settings.mode == SortMode::HumanNumeric),
             );
             // Shorten the range to what we need to pass to numeric_str_cmp later.
@@ -1227,10 +1406,13 @@ impl FieldSelector {
         } else if self.settings.mode == SortMode::GeneralNumeric {
             // Parse this number as BigDecimal, as this is the requirement for general numeric sorting.
             let decimal_pt = locale_decimal_pt();
+            let key = self.settings.transform(range_str);
             Selection::AsBigDecimal(general_bd_parse(
-                &range_str[get_leading_gen(range_str, decimal_pt)],
+                &key[get_leading_gen(&key, decimal_pt)],
                 decimal_pt,
             ))
+        } else if self.settings.mode == SortMode::Month {
+            Selection::WithMonth(range_str, month_parse(range_str))
         } else {
             // This is not a numeric sort, so we don't need a NumCache.
             Selection::Str(range_str)
@@ -1279,16 +1461,20 @@ impl FieldSelector {
                 if position.ignore_blanks {
                     idx += line[idx..]
                         .iter()
-                        .enumerate()
-                        .find(|(_, c)| !c.is_ascii_whitespace())
-                        .map_or(line[idx..].len(), |(idx, _)| idx);
+                        .position(|c| !is_blank(*c))
+                        .unwrap_or(line[idx..].len());
                 }
                 // apply the character index
-                idx += line[idx..]
-                    .iter()
-                    .enumerate()
-                    .nth(position.char - 1)
-                    .map_or(line[idx..].len(), |(idx, _)| idx);
+                if multibyte_locale() {
+                    for _ in 1..position.char {
+                        if idx >= line.len() {
+                            break;
+                        }
+                        idx += utf8_char_len(&line[idx..]);
+                    }
+                } else {
+                    idx = idx.saturating_add(position.char - 1).min(line.len());
+                }
                 if idx >= line.len() {
                     Resolution::TooHigh
                 } else {
@@ -1302,10 +1488,13 @@ impl FieldSelector {
                 let to = self.to.as_ref().map(|to| resolve_index(line, tokens, to));
 
                 let mut range = match to {
-                    Some(Resolution::StartOfChar(mut to)) => {
+                    Some(Resolution::StartOfChar(to)) => {
                         // We need to include the character at `to`.
-                        to += 1;
-                        from..to
+                        if multibyte_locale() {
+                            from..to + utf8_char_len(&line[to..])
+                        } else {
+                            from..to + 1
+                        }
                     }
                     Some(Resolution::EndOfChar(to)) => from..to,
                     // If `to` was not given or the match would be after the end of the line,
@@ -1484,9 +1673,12 @@ struct LegacyKeyWarning {
 
 impl LegacyKeyWarning {
     fn legacy_key_display(&self) -> String {
-        match self.to_field {
-            Some(to) => format!("+{} -{to}", self.from_field),
-            None => format!("+{}", self.from_field),
+        // Like GNU, show the end as the field it reaches into, so that
+        // `+1.2 -2.3` is reported as `+1 -3`.
+        match (self.to_field, self.to_char) {
+            (Some(to), Some(0) | None) => format!("+{} -{to}", self.from_field),
+            (Some(to), Some(_)) => format!("+{} -{}", self.from_field, to.saturating_add(1)),
+            (None, _) => format!("+{}", self.from_field),
         }
     }
 
@@ -1772,7 +1964,7 @@ fn default_merge_batch_size() -> usize {
 }
 
 fn locale_failed_to_set() -> bool {
-    matches!(env::var("LC_ALL").ok().as_deref(), Some("missing"))
+    matches!(uucore::env::var("LC_ALL").ok().as_deref(), Some("missing"))
 }
 
 fn key_zero_width(selector: &FieldSelector) -> bool {
@@ -1801,10 +1993,28 @@ fn key_spans_multiple_fields(selector: &FieldSelector) -> bool {
     }
 }
 
-fn key_leading_blanks_significant(selector: &FieldSelector) -> bool {
-    selector.settings.mode == SortMode::Default
-        && !selector.from.ignore_blanks
-        && !selector.settings.ignore_blanks
+/// Whether blanks at the start of the key's fields take part in comparisons,
+/// which GNU sort warns about, because fields include their leading blanks
+/// when the default field separator is used.
+fn key_leading_blanks_significant(selector: &FieldSelector, settings: &GlobalSettings) -> bool {
+    // Numbers and months are looked for after skipping blanks anyway.
+    let implicit_skip = matches!(
+        selector.settings.mode,
+        SortMode::Numeric | SortMode::HumanNumeric | SortMode::GeneralNumeric | SortMode::Month
+    );
+    // A key like -k1.2,1.5 takes characters at fixed offsets of the line.
+    let line_offset = selector
+        .to
+        .as_ref()
+        .is_some_and(|to| to.field == 1 && to.char != 0);
+    let end_blanks_significant = selector
+        .to
+        .as_ref()
+        .is_some_and(|to| to.char != 0 && !to.ignore_blanks);
+    settings.separator.is_none()
+        && !line_offset
+        && ((!selector.from.ignore_blanks && (!implicit_skip || selector.from.char > 1))
+            || end_blanks_significant)
 }
 
 fn emit_debug_warnings(
@@ -1828,6 +2038,8 @@ fn emit_debug_warnings(
         show_error!("{}", translate!("sort-warning-simple-byte-comparison"));
     }
 
+    let mut basic_numeric_span = false;
+    let mut general_numeric_span = false;
     for (idx, selector) in settings.selectors.iter().enumerate() {
         let key_index = idx + 1;
         if let Some(legacy) = legacy_warnings
@@ -1852,22 +2064,61 @@ fn emit_debug_warnings(
             continue;
         }
 
-        if flags.keys_specified && key_spans_multiple_fields(selector) {
+        if flags.keys_specified && key_leading_blanks_significant(selector, settings) {
             show_error!(
                 "{}",
                 translate!(
-                    "sort-warning-key-numeric-spans-fields",
+                    "sort-warning-leading-blanks-significant",
                     "key" => key_index
                 )
             );
-        } else if flags.keys_specified && key_leading_blanks_significant(selector) {
+        }
+        if flags.keys_specified && key_spans_multiple_fields(selector) {
             show_error!(
                 "{}",
                 translate!(
-                    "sort-warning-leading-blanks-significant",
+                    "sort-warning-key-numeric-spans-fields",
                     "key" => key_index
                 )
             );
+            if selector.settings.mode == SortMode::GeneralNumeric {
+                general_numeric_span = true;
+            } else {
+                basic_numeric_span = true;
+            }
+        }
+    }
+
+    // Separators inside a numeric key that spans fields are read as part of
+    // the number.
+    let decimal_pt = settings.numeric_locale.decimal_pt.unwrap_or(DECIMAL_PT);
+    let mut decimal_pt_warned = false;
+    if let Some(sep) = settings.separator {
+        let sep_display = char::from(sep).to_string();
+        if basic_numeric_span && settings.numeric_locale.thousands_sep == Some(sep) {
+            show_error!(
+                "{}",
+                translate!("sort-warning-separator-grouping", "sep" => sep_display.clone())
+            );
+        }
+        if basic_numeric_span || general_numeric_span {
+            if sep == decimal_pt {
+                show_error!(
+                    "{}",
+                    translate!("sort-warning-separator-decimal", "sep" => sep_display)
+                );
+                decimal_pt_warned = true;
+            } else if sep == b'-' {
+                show_error!(
+                    "{}",
+                    translate!("sort-warning-separator-minus", "sep" => sep_display)
+                );
+            } else if general_numeric_span && sep == b'+' {
+                show_error!(
+                    "{}",
+                    translate!("sort-warning-separator-plus", "sep" => sep_display)
+                );
+            }
         }
     }
 
@@ -1877,37 +2128,17 @@ fn emit_debug_warnings(
             SortMode::Numeric | SortMode::HumanNumeric | SortMode::GeneralNumeric
         )
     });
-
-    let mut suppress_decimal_warning = false;
-    if numeric_used {
-        if let Some(sep) = settings.separator {
-            match sep {
-                b'.' => {
-                    show_error!(
-                        "{}",
-                        translate!("sort-warning-separator-decimal", "sep" => ".")
-                    );
-                    suppress_decimal_warning = true;
-                }
-                b'-' => {
-                    show_error!(
-                        "{}",
-                        translate!("sort-warning-separator-minus", "sep" => "-")
-                    );
-                }
-                b'+' => {
-                    show_error!(
-                        "{}",
-                        translate!("sort-warning-separator-plus", "sep" => "+")
-                    );
-                }
-                _ => {}
-            }
-        }
-
-        if !suppress_decimal_warning {
-            show_error!("{}", translate!("sort-warning-numbers-use-decimal-point"));
-        }
+    if numeric_used && !decimal_pt_warned {
+        // Only a note, unless the decimal point also separates the fields.
+        let key = if settings.separator == Some(decimal_pt) {
+            "sort-warning-numbers-use-decimal-point"
+        } else {
+            "sort-note-numbers-use-decimal-point"
+        };
+        show_error!(
+            "{}",
+            translate!(key, "decimal" => char::from(decimal_pt).to_string())
+        );
     }
 
     let uses_reverse = settings
@@ -1939,8 +2170,7 @@ fn emit_debug_warnings(
     };
 
     let reverse_unused = flags.reverse && !uses_reverse;
-    let last_resort_active =
-        settings.mode != SortMode::Random && !settings.stable && !settings.unique;
+    let last_resort_active = !settings.stable && !settings.unique;
     let reverse_ignored = reverse_unused && !last_resort_active;
     let reverse_last_resort_warning = reverse_unused && last_resort_active;
 
@@ -1997,8 +2227,13 @@ fn emit_debug_warnings(
 }
 
 #[uucore::main]
-#[allow(clippy::cognitive_complexity)]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
+    // A plain I/O error, e.g. failing to write the output, is a failure like any other.
+    sort(args).map_err(|e| ExitCodePolicy::SORT.classify_io_error(FailureClass::Other, e))
+}
+
+#[allow(clippy::cognitive_complexity)]
+fn sort(args: impl uucore::Args) -> UResult<()> {
     let mut settings = GlobalSettings {
         numeric_locale: detect_numeric_locale(),
         ..Default::default()
@@ -2008,8 +2243,11 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     if !legacy_warnings.is_empty() {
         index_legacy_warnings(&processed_args, &mut legacy_warnings);
     }
-    let matches =
-        uucore::clap_localization::handle_clap_result_with_exit_code(uu_app(), processed_args, 2)?;
+    let matches = uucore::clap_localization::handle_clap_result_with_exit_code(
+        uu_app(),
+        processed_args,
+        ExitCodePolicy::SORT.usage,
+    )?;
 
     // Prevent -o/--output to be specified multiple times
     if let Some(mut outputs) = matches.get_many::<OsString>(options::OUTPUT) {
@@ -2041,28 +2279,21 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
 
         // sort errors with "cannot open: [...]" instead of "cannot read: [...]" here
         let reader = open_with_open_failed_error(&files0_from)?;
-        let buf_reader = BufReader::new(reader);
-        for (line_num, line) in buf_reader.split(b'\0').flatten().enumerate() {
-            let f = std::str::from_utf8(&line)
-                .expect("Could not parse string from zero terminated input.");
-            match f {
-                STDIN_FILE => {
-                    return Err(SortError::MinusInStdIn.into());
-                }
-                "" => {
-                    return Err(SortError::ZeroLengthFileName {
-                        file: files0_from,
-                        line_num: line_num + 1,
-                    }
-                    .into());
+        for (line_num, name) in files0(BufReader::new(reader)).enumerate() {
+            let Ok(name) = name else {
+                return Err(SortError::Files0ReadFailed { file: files0_from }.into());
+            };
+            if name == STDIN_FILE {
+                return Err(SortError::MinusInStdIn.into());
+            }
+            if name.is_empty() {
+                return Err(SortError::ZeroLengthFileName {
+                    file: files0_from,
+                    line_num: line_num + 1,
                 }
-                _ => {}
+                .into());
             }
-
-            files.push(OsString::from(
-                std::str::from_utf8(&line)
-                    .expect("Could not parse string from zero terminated input."),
-            ));
+            files.push(name);
         }
         if files.is_empty() {
             return Err(SortError::EmptyInputFile { file: files0_from }.into());
@@ -2119,19 +2350,12 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     settings.dictionary_order = dictionary_order;
     settings.ignore_non_printing = ignore_non_printing;
     settings.ignore_case = ignore_case;
-    if matches.contains_id(options::PARALLEL) {
-        // "0" is default - threads = num of cores
-        settings.threads = matches
-            .get_one::<String>(options::PARALLEL)
-            .map_or_else(|| "0".to_string(), String::from);
-        let num_threads = match settings.threads.parse::<usize>() {
-            Ok(0) | Err(_) => std::thread::available_parallelism().map_or(1, NonZero::get),
-            Ok(n) => n,
-        };
-        let _ = rayon::ThreadPoolBuilder::new()
-            .num_threads(num_threads)
-            .build_global();
+    if let Some(threads) = matches.get_one::<String>(options::PARALLEL) {
+        settings.threads = parse_thread_count(threads)?;
     }
+    let _ = rayon::ThreadPoolBuilder::new()
+        .num_threads(settings.threads)
+        .build_global();
 
     if let Some(size_str) = matches.get_one::<String>(options::BUF_SIZE) {
         settings.buffer_size = GlobalSettings::parse_byte_count(size_str).map_err(|e| {
@@ -2143,22 +2367,22 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         settings.buffer_size_is_explicit = false;
     }
 
-    let mut tmp_dir = TmpDirWrapper::new(
-        matches
-            .get_one::<String>(options::TMP_DIR)
-            .map_or_else(
-                || {
-                    // env::temp_dir() panics on wasm32-unknown-unknown.
-                    // Use "/tmp" as a fallback; the path is never actually created
-                    // unless external sorting is triggered (which requires filesystem I/O).
-                    #[cfg(target_family = "wasm")]
-                    { std::path::PathBuf::from("/tmp") }
-                    #[cfg(not(target_family = "wasm"))]
-                    { env::temp_dir() }
-                },
-                PathBuf::from,
-            ),
-    );
+    let mut tmp_dir = TmpDirWrapper::new(matches.get_many::<String>(options::TMP_DIR).map_or_else(
+        || {
+            // env::temp_dir() panics on wasm32-unknown-unknown.
+            // Use "/tmp" as a fallback; the path is never actually created
+            // unless external sorting is triggered (which requires filesystem I/O).
+            #[cfg(target_family = "wasm")]
+            {
+                vec![std::path::PathBuf::from("/tmp")]
+            }
+            #[cfg(not(target_family = "wasm"))]
+            {
+                vec![env::temp_dir()]
+            }
+        },
+        |dirs| dirs.map(PathBuf::from).collect(),
+    ));
 
     settings.compress_prog = matches
         .get_one::<String>(options::COMPRESS_PROG)
@@ -2282,6 +2506,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     }
 
     if let Some(values) = matches.get_many::<String>(options::KEY) {
+        settings.keys_specified = true;
         for value in values {
             let selector = FieldSelector::parse(value, &settings)?;
             settings.selectors.push(selector);
@@ -2325,7 +2550,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         open(file)?;
     }
 
-    let output = Output::new(matches.get_one::<OsString>(options::OUTPUT))?;
+    let output = Output::new(matches.get_one::<OsString>(options::OUTPUT), &files)?;
 
     if settings.debug {
         let global_flags = GlobalOptionFlags::from_matches(&matches);
@@ -2553,7 +2778,8 @@ pub fn uu_app() -> Command {
             .long(options::TMP_DIR)
             .help(translate!("sort-help-tmp-dir"))
             .value_name("DIR")
-            .value_hint(clap::ValueHint::DirPath),
+            .value_hint(clap::ValueHint::DirPath)
+            .action(ArgAction::Append),
     )
     .arg(
         Arg::new(options::COMPRESS_PROG)
@@ -2613,6 +2839,28 @@ fn exec(
     }
 }
 
+/// Parse the argument of `--parallel`.
+///
+/// As in GNU sort, counts too large to represent are not an error; they fall
+/// back to the number of available processors.
+fn parse_thread_count(arg: &str) -> UResult<usize> {
+    match arg.parse::<usize>() {
+        Ok(0) => Err(USimpleError::new(2, translate!("sort-parallel-zero"))),
+        Ok(n) => Ok(n),
+        Err(e) if *e.kind() == IntErrorKind::PosOverflow => {
+            Ok(std::thread::available_parallelism().map_or(1, NonZero::get))
+        }
+        Err(_) => Err(USimpleError::new(
+            2,
+            translate!(
+                "sort-invalid-option-arg",
+                "option" => options::PARALLEL,
+                "arg" => arg.quote()
+            ),
+        )),
+    }
+}
+
 fn sort_by<'a>(unsorted: &mut Vec<Line<'a>>, settings: &GlobalSettings, line_data: &LineData<'a>) {
     if settings.stable || settings.unique {
         unsorted.par_sort_by(|a, b| compare_by(a, b, settings, line_data, line_data));
@@ -2621,6 +2869,10 @@ fn sort_by<'a>(unsorted: &mut Vec<Line<'a>>, settings: &GlobalSettings, line_dat
     }
 }
 
+/// Compare two lines key by key: each key is extracted from its line,
+/// transformed by its flags where its mode compares it whole, compared by its
+/// mode and reversed by its own `-r`. The first key that differs decides,
+/// otherwise the whole lines do unless `-s` or `-u` is given.
 fn compare_by<'a>(
     a: &Line<'a>,
     b: &Line<'a>,
@@ -2651,6 +2903,7 @@ fn compare_by<'a>(
     let mut selection_index = 0;
     let mut num_info_index = 0;
     let mut parsed_float_index = 0;
+    let mut month_index = 0;
 
     if let (Some(Some(a_f64)), Some(Some(b_f64))) = (
         a_line_data.line_num_floats.get(a.index),
@@ -2688,19 +2941,13 @@ fn compare_by<'a>(
 
         let cmp: Ordering = match settings.mode {
             SortMode::Random => {
-                // check if the two strings are equal
-                if custom_str_cmp(
-                    a_str,
-                    b_str,
-                    settings.ignore_non_printing,
-                    settings.dictionary_order,
-                    settings.ignore_case,
-                ) == Ordering::Equal
-                {
+                // Hash the keys as they compare, so that equal keys share a hash
+                let (a_key, b_key) = (settings.transform(a_str), settings.transform(b_str));
+                if a_key == b_key {
                     Ordering::Equal
                 } else {
                     // Only if they are not equal compare by the hash
-                    random_shuffle(a_str, b_str, &global_settings.salt.unwrap())
+                    random_shuffle(&a_key, &b_key, &global_settings.salt.unwrap())
                 }
             }
             SortMode::Numeric => {
@@ -2717,7 +2964,10 @@ fn compare_by<'a>(
                 let b_num_info = &b_line_data.num_infos
                     [b.index * global_settings.precomputed.num_infos_per_line + num_info_index];
                 num_info_index += 1;
-                human_numeric_str_cmp((a_str, a_num_info), (b_str, b_num_info))
+                human_numeric_str_cmp(
+                    (&settings.transform(a_str), a_num_info),
+                    (&settings.transform(b_str), b_num_info),
+                )
             }
             SortMode::GeneralNumeric => {
                 let a_float = &a_line_data.parsed_floats
@@ -2727,8 +2977,17 @@ fn compare_by<'a>(
                 parsed_float_index += 1;
                 general_numeric_compare(a_float, b_float)
             }
-            SortMode::Month => month_compare(a_str, b_str),
-            SortMode::Version => version_cmp(a_str, b_str),
+            SortMode::Month => {
+                let a_month = a_line_data.months
+                    [a.index * global_settings.precomputed.months_per_line + month_index];
+                let b_month = b_line_data.months
+                    [b.index * global_settings.precomputed.months_per_line + month_index];
+                month_index += 1;
+                a_month.cmp(&b_month)
+            }
+            SortMode::Version => {
+                version_cmp(&settings.transform(a_str), &settings.transform(b_str))
+            }
             SortMode::Default => {
                 // Use locale-aware comparison if feature is enabled and no custom flags are set
                 #[cfg(feature = "i18n-collator")]
@@ -2766,10 +3025,7 @@ fn compare_by<'a>(
     }
 
     // Call "last resort compare" if all selectors returned Equal
-    let cmp = if global_settings.mode == SortMode::Random
-        || global_settings.stable
-        || global_settings.unique
-    {
+    let cmp = if global_settings.stable || global_settings.unique {
         Ordering::Equal
     } else {
         a.line.cmp(b.line)
@@ -2997,51 +3253,6 @@ fn random_shuffle(a: &[u8], b: &[u8], salt: &[u8]) -> Ordering {
     da.cmp(&db)
 }
 
-#[derive(Eq, Ord, PartialEq, PartialOrd, Clone, Copy)]
-enum Month {
-    Unknown,
-    January,
-    February,
-    March,
-    April,
-    May,
-    June,
-    July,
-    August,
-    September,
-    October,
-    November,
-    December,
-}
-
-/// Parse the beginning string into a Month, returning [`Month::Unknown`] on errors.
-fn month_parse(line: &[u8]) -> Month {
-    let line = line.trim_ascii_start();
-
-    match line.get(..3).map(<[u8]>::to_ascii_uppercase).as_deref() {
-        Some(b"JAN") => Month::January,
-        Some(b"FEB") => Month::February,
-        Some(b"MAR") => Month::March,
-        Some(b"APR") => Month::April,
-        Some(b"MAY") => Month::May,
-        Some(b"JUN") => Month::June,
-        Some(b"JUL") => Month::July,
-        Some(b"AUG") => Month::August,
-        Some(b"SEP") => Month::September,
-        Some(b"OCT") => Month::October,
-        Some(b"NOV") => Month::November,
-        Some(b"DEC") => Month::December,
-        _ => Month::Unknown,
-    }
-}
-
-fn month_compare(a: &[u8], b: &[u8]) -> Ordering {
-    let ma = month_parse(a);
-    let mb = month_parse(b);
-
-    ma.cmp(&mb)
-}
-
 fn print_sorted<'a, T: Iterator<Item = &'a Line<'a>>>(
     iter: T,
     settings: &GlobalSettings,
@@ -3053,11 +3264,14 @@ fn print_sorted<'a, T: Iterator<Item = &'a Line<'a>>>(
         .to_owned();
     let ctx = || translate!("sort-error-write-failed", "output" => output_name.maybe_quote());
 
-    let mut writer = output.into_write();
-    for line in iter {
+    let mut writer = output.into_write()?;
+    for (i, line) in iter.enumerate() {
+        if i.is_multiple_of(CANCEL_CHECK_INTERVAL) {
+            uucore::cancel::check()?;
+        }
         line.print(&mut writer, settings).map_err_context(ctx)?;
     }
-    writer.flush().map_err_context(ctx)?;
+    writer.finish().map_err_context(ctx)?;
     Ok(())
 }
 
@@ -3165,13 +3379,6 @@ mod tests {
         assert_eq!(Ordering::Equal, random_shuffle(a, b, &c));
     }
 
-    #[test]
-    fn test_month_compare() {
-        let a = b"JaN";
-        let b = b"OCt";
-
-        assert_eq!(Ordering::Less, month_compare(a, b));
-    }
     #[test]
     fn test_version_compare() {
         let a = b"1.2.3-alpha2";
@@ -17,15 +17,16 @@ use uucore::{
 
 use crate::{SortError, current_open_fd_count, fd_soft_limit};
 
-/// A wrapper around [`TempDir`] that may only exist once in a process.
+/// A wrapper around [`TempDir`]s that may only exist once in a process.
 ///
-/// `TmpDirWrapper` handles the allocation of new temporary files in this temporary directory and
-/// deleting the whole directory when `SIGINT` is received. Creating a second `TmpDirWrapper` will
+/// `TmpDirWrapper` handles the allocation of new temporary files in these temporary directories and
+/// deleting the whole directories when `SIGINT` is received. Creating a second `TmpDirWrapper` will
 /// fail because `ctrlc::set_handler()` fails when there's already a handler.
-/// The directory is only created once the first file is requested.
+/// Like GNU sort, files are spread over the parent directories in turn (one per `-T` option),
+/// and the directory in each parent is only created once its first file is requested.
 pub struct TmpDirWrapper {
-    temp_dir: Option<TempDir>,
-    parent_path: PathBuf,
+    temp_dirs: Vec<TempDir>,
+    parent_paths: Vec<PathBuf>,
     size: usize,
     lock: Arc<Mutex<()>>,
 }
@@ -33,7 +34,7 @@ pub struct TmpDirWrapper {
 #[derive(Default, Clone)]
 struct HandlerRegistration {
     lock: Option<Arc<Mutex<()>>>,
-    path: Option<PathBuf>,
+    paths: Vec<PathBuf>,
 }
 
 // Lazily create the global HandlerRegistration so all TmpDirWrapper instances and the
@@ -59,6 +60,12 @@ fn ensure_signal_handler_installed(state: Arc<Mutex<HandlerRegistration>>) -> UR
     // Guard to ensure the SIGINT handler is registered once per process and reused.
     static HANDLER_INSTALLED: AtomicBool = AtomicBool::new(false);
 
+    // The handler exits the process, which an embedding host must never see. Signals are
+    // the host's business then, and the temp directories are still removed when dropped.
+    if uucore::error::EMBEDDED {
+        return Ok(());
+    }
+
     if HANDLER_INSTALLED
         .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
         .is_err()
@@ -69,14 +76,14 @@ fn ensure_signal_handler_installed(state: Arc<Mutex<HandlerRegistration>>) -> UR
     let handler_state = state.clone();
     if let Err(e) = ctrlc::set_handler(move || {
         // Load the latest lock/path snapshot so the handler cleans the active temp dir.
-        let (lock, path) = {
+        let (lock, paths) = {
             let state = handler_state.lock().unwrap();
-            (state.lock.clone(), state.path.clone())
+            (state.lock.clone(), state.paths.clone())
         };
 
         if let Some(lock) = lock {
             let _guard = lock.lock().unwrap();
-            if let Some(path) = path {
+            for path in paths {
                 if let Err(e) = remove_tmp_dir(&path) {
                     show_error!(
                         "{}",
@@ -107,33 +114,41 @@ fn ensure_signal_handler_installed(_state: Arc<Mutex<HandlerRegistration>>) -> U
 }
 
 impl TmpDirWrapper {
-    pub fn new(path: PathBuf) -> Self {
+    pub fn new(parent_paths: Vec<PathBuf>) -> Self {
+        assert!(!parent_paths.is_empty());
         Self {
-            parent_path: path,
+            parent_paths,
             size: 0,
-            temp_dir: None,
+            temp_dirs: Vec::new(),
             lock: Arc::default(),
         }
     }
 
+    /// Create the temporary directory in the next parent directory that does not have one yet.
     fn init_tmp_dir(&mut self) -> UResult<()> {
-        assert!(self.temp_dir.is_none());
-        assert_eq!(self.size, 0);
-        self.temp_dir = Some(
-            tempfile::Builder::new()
-                .prefix("uutils_sort")
-                .tempdir_in(&self.parent_path)
-                .map_err(|_| SortError::TmpFileCreationFailed {
-                    path: self.parent_path.clone(),
-                })?,
-        );
-
-        let path = self.temp_dir.as_ref().unwrap().path().to_owned();
+        let parent_path = &self.parent_paths[self.temp_dirs.len()];
+        let temp_dir = tempfile::Builder::new()
+            .prefix("uutils_sort")
+            .tempdir_in(parent_path)
+            .map_err(|error| SortError::TmpFileCreationFailed {
+                path: parent_path.clone(),
+                error,
+            })?;
+
+        let path = temp_dir.path().to_owned();
+        self.temp_dirs.push(temp_dir);
         let state = HANDLER_STATE.clone();
         {
             let mut guard = state.lock().unwrap();
-            guard.lock = Some(self.lock.clone());
-            guard.path = Some(path);
+            if !guard
+                .lock
+                .as_ref()
+                .is_some_and(|current| Arc::ptr_eq(current, &self.lock))
+            {
+                guard.lock = Some(self.lock.clone());
+                guard.paths.clear();
+            }
+            guard.paths.push(path);
         }
 
         if should_install_signal_handler() {
@@ -143,14 +158,15 @@ impl TmpDirWrapper {
     }
 
     pub fn next_file(&mut self) -> UResult<(File, PathBuf)> {
-        if self.temp_dir.is_none() {
+        let index = self.size % self.parent_paths.len();
+        if self.temp_dirs.len() <= index {
             self.init_tmp_dir()?;
         }
 
         let _lock = self.lock.lock().unwrap();
         let file_name = self.size.to_string();
         self.size += 1;
-        let path = self.temp_dir.as_ref().unwrap().path().join(file_name);
+        let path = self.temp_dirs[index].path().join(file_name);
         Ok((
             File::create(&path).map_err(|error| SortError::OpenTmpFileFailed { error })?,
             path,
@@ -174,7 +190,7 @@ impl Drop for TmpDirWrapper {
             .is_some_and(|current| Arc::ptr_eq(current, &self.lock))
         {
             guard.lock = None;
-            guard.path = None;
+            guard.paths.clear();
         }
     }
 }
@@ -24,6 +24,7 @@ split-error-multiple-separator-characters = multiple separator characters specif
 split-error-filter-with-kth-chunk = --filter does not process a chunk extracted to stdout
 split-error-invalid-io-block-size = invalid IO block size: { $size }
 split-error-not-supported = --filter is currently not supported in this platform
+split-error-preserve-mode-not-supported = --preserve-mode is not supported on this platform
 split-error-invalid-number-of-chunks = invalid number of chunks: { $chunks }
 split-error-invalid-chunk-number = invalid chunk number: { $chunk }
 split-error-invalid-number-of-lines = invalid number of lines: { $error }
@@ -33,6 +34,7 @@ split-error-overflow = Overflow
 split-error-output-file-suffixes-exhausted = output file suffixes exhausted
 split-error-numerical-suffix-start-too-large = numerical suffix start value is too large for the suffix length
 split-error-cannot-open-for-reading = cannot open { $file } for reading
+split-error-cannot-read-mode = cannot read the permissions of { $file }
 split-error-would-overwrite-input = { $file } would overwrite input; aborting
 split-error-cannot-determine-input-size = { $input }: cannot determine input size
 split-error-cannot-determine-file-size = { $input }: cannot determine file size
@@ -40,6 +42,7 @@ split-error-cannot-read-from-input = { $input }: cannot read from input : { $err
 split-error-input-output-error = input/output error
 split-error-unable-to-open-file = unable to open { $file }; aborting
 split-error-unable-to-reopen-file = unable to re-open { $file }; aborting
+split-error-unable-to-set-mode = unable to set the permissions of { $file }; aborting
 split-error-file-descriptor-limit = at file descriptor limit, but no file descriptor left to close. Closed { $count } writers before.
 split-error-shell-process-returned = Shell process returned { $code }
 split-error-shell-process-terminated = Shell process terminated by signal
@@ -60,3 +63,4 @@ split-help-hex-suffixes = same as -x, but allow setting the start value
 split-help-suffix-length = generate suffixes of length N (default 2)
 split-help-verbose = print a diagnostic just before each output file is opened
 split-help-separator = use SEP instead of newline as the record separator; '\\0' (zero) specifies the NUL character
+split-help-preserve-mode = give the output files the permissions of the input
@@ -24,6 +24,7 @@ split-error-multiple-separator-characters = plusieurs caractères de séparateur
 split-error-filter-with-kth-chunk = --filter ne traite pas un chunk extrait vers stdout
 split-error-invalid-io-block-size = taille de bloc IO invalide : { $size }
 split-error-not-supported = --filter n'est actuellement pas supporté sur cette plateforme
+split-error-preserve-mode-not-supported = --preserve-mode n'est pas supporté sur cette plateforme
 split-error-invalid-number-of-chunks = nombre de chunks invalide : { $chunks }
 split-error-invalid-chunk-number = numéro de chunk invalide : { $chunk }
 split-error-invalid-number-of-lines = nombre de lignes invalide : { $error }
@@ -33,6 +34,7 @@ split-error-overflow = Débordement
 split-error-output-file-suffixes-exhausted = suffixes de fichiers de sortie épuisés
 split-error-numerical-suffix-start-too-large = la valeur de départ du suffixe numérique est trop grande pour la longueur du suffixe
 split-error-cannot-open-for-reading = impossible d'ouvrir { $file } en lecture
+split-error-cannot-read-mode = impossible de lire les permissions de { $file }
 split-error-would-overwrite-input = { $file } écraserait l'entrée ; abandon
 split-error-cannot-determine-input-size = { $input } : impossible de déterminer la taille de l'entrée
 split-error-cannot-determine-file-size = { $input } : impossible de déterminer la taille du fichier
@@ -40,6 +42,7 @@ split-error-cannot-read-from-input = { $input } : impossible de lire depuis l'en
 split-error-input-output-error = erreur d'entrée/sortie
 split-error-unable-to-open-file = impossible d'ouvrir { $file } ; abandon
 split-error-unable-to-reopen-file = impossible de rouvrir { $file } ; abandon
+split-error-unable-to-set-mode = impossible de définir les permissions de { $file } ; abandon
 split-error-file-descriptor-limit = limite de descripteurs de fichiers atteinte, mais aucun descripteur de fichier à fermer. { $count } écrivains fermés auparavant.
 split-error-shell-process-returned = Le processus shell a retourné { $code }
 split-error-shell-process-terminated = Le processus shell a été terminé par un signal
@@ -59,3 +62,4 @@ split-help-hex-suffixes = identique à -x, mais permet de définir la valeur de
 split-help-suffix-length = générer des suffixes de longueur N (par défaut 2)
 split-help-verbose = afficher un diagnostic juste avant l'ouverture de chaque fichier de sortie
 split-help-separator = utiliser SEP au lieu de nouvelle ligne comme séparateur d'enregistrement ; '\\0' (zéro) spécifie le caractère NUL
+split-help-preserve-mode = donner aux fichiers de sortie les permissions de l'entrée
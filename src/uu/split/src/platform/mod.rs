@@ -3,6 +3,8 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 #[cfg(unix)]
+pub use self::unix::input_mode;
+#[cfg(unix)]
 pub use self::unix::instantiate_current_writer;
 #[cfg(unix)]
 pub use self::unix::paths_refer_to_same_file;
@@ -12,8 +14,16 @@ pub use self::windows::instantiate_current_writer;
 #[cfg(windows)]
 pub use self::windows::paths_refer_to_same_file;
 
+#[cfg(target_family = "wasm")]
+pub use self::wasm::instantiate_current_writer;
+#[cfg(target_family = "wasm")]
+pub use self::wasm::paths_refer_to_same_file;
+
 #[cfg(unix)]
 mod unix;
 
 #[cfg(windows)]
 mod windows;
+
+#[cfg(target_family = "wasm")]
+mod wasm;
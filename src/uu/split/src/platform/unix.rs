@@ -4,8 +4,11 @@
 // file that was distributed with this source code.
 use std::env;
 use std::ffi::OsStr;
+use std::fs::{File, Permissions};
 use std::io::{BufWriter, Error, Result};
 use std::io::{ErrorKind, Write};
+use std::os::fd::AsFd;
+use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 use std::process::{Child, Command, Stdio};
 use uucore::error::USimpleError;
@@ -125,16 +128,19 @@ impl Drop for FilterWriter {
 }
 
 /// Instantiate either a file writer or a "write to shell process's stdin" writer
+///
+/// A newly created file is given the permission bits in `mode`, if any
 pub fn instantiate_current_writer(
     filter: Option<&str>,
     filename: &str,
     is_new: bool,
+    mode: Option<u32>,
 ) -> Result<BufWriter<Box<dyn Write>>> {
     match filter {
         None => {
             let file = if is_new {
                 // create new file
-                std::fs::OpenOptions::new()
+                let file = std::fs::OpenOptions::new()
                     .write(true)
                     .create(true)
                     .truncate(true)
@@ -146,7 +152,17 @@ pub fn instantiate_current_writer(
                         _ => Error::other(
                             translate!("split-error-unable-to-open-file", "file" => filename),
                         ),
-                    })?
+                    })?;
+                if let Some(mode) = mode {
+                    // the umask does not apply here, the input's bits are copied as they are
+                    file.set_permissions(Permissions::from_mode(mode))
+                        .map_err(|_| {
+                            Error::other(
+                                translate!("split-error-unable-to-set-mode", "file" => filename),
+                            )
+                        })?;
+                }
+                file
             } else {
                 // re-open file that we previously created to append to it
                 std::fs::OpenOptions::new()
@@ -167,6 +183,16 @@ pub fn instantiate_current_writer(
     }
 }
 
+/// Get the permission bits of the input, for `--preserve-mode`
+pub fn input_mode(input: &OsStr) -> Result<u32> {
+    let metadata = if input == "-" {
+        File::from(std::io::stdin().as_fd().try_clone_to_owned()?).metadata()?
+    } else {
+        std::fs::metadata(input)?
+    };
+    Ok(metadata.permissions().mode() & 0o7777)
+}
+
 pub fn paths_refer_to_same_file(p1: &OsStr, p2: &OsStr) -> bool {
     // We have to take symlinks and relative paths into account.
     let p1 = if p1 == "-" {
@@ -0,0 +1,40 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use std::ffi::OsStr;
+use std::io::{BufWriter, Error, Result, Write};
+use std::path::Path;
+use uucore::translate;
+use uucore::wasm_io::{WriteMode, open_file_write};
+
+/// Get a file writer backed by the host VFS
+///
+/// There is no shell to run a filter in on WASM, so like the windows
+/// version this _always_ returns a file writer
+///
+/// `--preserve-mode` is refused on this platform, so `_mode` is always `None`
+pub fn instantiate_current_writer(
+    _filter: Option<&str>,
+    filename: &str,
+    is_new: bool,
+    _mode: Option<u32>,
+) -> Result<BufWriter<Box<dyn Write>>> {
+    let file = if is_new {
+        // create new file
+        open_file_write(Path::new(&filename), WriteMode::Truncate).map_err(|_| {
+            Error::other(translate!("split-error-unable-to-open-file", "file" => filename))
+        })?
+    } else {
+        // re-open file that we previously created to append to it
+        open_file_write(Path::new(&filename), WriteMode::Append).map_err(|_| {
+            Error::other(translate!("split-error-unable-to-reopen-file", "file" => filename))
+        })?
+    };
+    Ok(BufWriter::new(file))
+}
+
+pub fn paths_refer_to_same_file(p1: &OsStr, p2: &OsStr) -> bool {
+    // The VFS does not expose file identities, so only compare the paths
+    p1 != "-" && Path::new(p1) == Path::new(p2)
+}
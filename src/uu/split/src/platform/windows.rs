@@ -13,10 +13,13 @@ use uucore::translate;
 ///
 /// Unlike the unix version of this function, this _always_ returns
 /// a file writer
+///
+/// `--preserve-mode` is refused on this platform, so `_mode` is always `None`
 pub fn instantiate_current_writer(
     _filter: Option<&str>,
     filename: &str,
     is_new: bool,
+    _mode: Option<u32>,
 ) -> Result<BufWriter<Box<dyn Write>>> {
     let file = if is_new {
         // create new file
@@ -15,14 +15,21 @@ use crate::strategy::{NumberType, Strategy, StrategyError};
 use clap::{Arg, ArgAction, ArgMatches, Command, ValueHint, parser::ValueSource};
 use std::env;
 use std::ffi::OsString;
+#[cfg(not(target_family = "wasm"))]
 use std::fs::{File, metadata};
 use std::io;
-use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write, stdin};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+#[cfg(not(target_family = "wasm"))]
+use std::io::{stdin, stdout};
 use std::path::Path;
 use thiserror::Error;
 use uucore::display::Quotable;
 use uucore::error::{FromIo, UIoError, UResult, USimpleError, UUsageError};
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{
+    metadata, open_file as open_input, open_file_seekable as open_input_seekable, stdin, stdout,
+};
 
 use uucore::parser::parse_size::parse_size_u64;
 
@@ -42,6 +49,7 @@ static OPT_HEX_SUFFIXES_SHORT: &str = "-x";
 static OPT_SUFFIX_LENGTH: &str = "suffix-length";
 static OPT_VERBOSE: &str = "verbose";
 static OPT_SEPARATOR: &str = "separator";
+static OPT_PRESERVE_MODE: &str = "preserve-mode";
 static OPT_ELIDE_EMPTY_FILES: &str = "elide-empty-files";
 static OPT_IO_BLKSIZE: &str = "-io-blksize";
 
@@ -54,7 +62,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     let matches = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
 
     match Settings::from(&matches, obs_lines.as_deref()) {
-        Ok(settings) => {
+        Ok(mut settings) => {
             // When using --filter, we write to a child process's stdin which may
             // close early. Disable SIGPIPE so we get EPIPE errors instead of
             // being terminated, allowing graceful handling of broken pipes.
@@ -62,7 +70,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
             if settings.filter.is_some() {
                 let _ = uucore::signals::disable_pipe_errors();
             }
-            split(&settings)
+            split(&mut settings)
         }
         Err(e) if e.requires_usage() => Err(UUsageError::new(1, format!("{e}"))),
         Err(e) => Err(USimpleError::new(1, format!("{e}"))),
@@ -375,6 +383,12 @@ pub fn uu_app() -> Command {
                 .action(ArgAction::Append)
                 .help(translate!("split-help-separator")),
         )
+        .arg(
+            Arg::new(OPT_PRESERVE_MODE)
+                .long(OPT_PRESERVE_MODE)
+                .help(translate!("split-help-preserve-mode"))
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(OPT_IO_BLKSIZE)
                 .long("io-blksize")
@@ -418,6 +432,12 @@ struct Settings {
     /// created.
     elide_empty_files: bool,
     io_blksize: Option<u64>,
+
+    /// Whether to give each new output file the permissions of the input.
+    preserve_mode: bool,
+
+    /// The permission bits of the input, once read with `--preserve-mode`.
+    input_mode: Option<u32>,
 }
 
 #[derive(Debug, Error)]
@@ -454,6 +474,11 @@ enum SettingsError {
     #[cfg(windows)]
     #[error("{}", translate!("split-error-not-supported"))]
     NotSupported,
+
+    /// The `--preserve-mode` option needs unix permissions.
+    #[cfg(not(unix))]
+    #[error("{}", translate!("split-error-preserve-mode-not-supported"))]
+    PreserveModeNotSupported,
 }
 
 impl SettingsError {
@@ -512,6 +537,8 @@ impl Settings {
             separator,
             elide_empty_files: matches.get_flag(OPT_ELIDE_EMPTY_FILES),
             io_blksize,
+            preserve_mode: matches.get_flag(OPT_PRESERVE_MODE),
+            input_mode: None,
         };
 
         #[cfg(windows)]
@@ -520,6 +547,11 @@ impl Settings {
             return Err(SettingsError::NotSupported);
         }
 
+        #[cfg(not(unix))]
+        if result.preserve_mode {
+            return Err(SettingsError::PreserveModeNotSupported);
+        }
+
         // Return an error if `--filter` option is used with any of the
         // Kth chunk sub-strategies of `--number` option
         // As those are writing to stdout of `split` and cannot write to filter command child process
@@ -543,13 +575,25 @@ impl Settings {
         filename: &str,
         is_new: bool,
     ) -> io::Result<BufWriter<Box<dyn Write>>> {
+        if is_new && self.verbose {
+            if self.filter.is_some() {
+                println!("executing with FILE={}", filename.maybe_quote());
+            } else {
+                println!("creating file {}", filename.quote());
+            }
+        }
         if platform::paths_refer_to_same_file(&self.input, filename.as_ref()) {
             return Err(io::Error::other(
                 translate!("split-error-would-overwrite-input", "file" => filename.quote()),
             ));
         }
 
-        platform::instantiate_current_writer(self.filter.as_deref(), filename, is_new)
+        platform::instantiate_current_writer(
+            self.filter.as_deref(),
+            filename,
+            is_new,
+            self.input_mode,
+        )
     }
 }
 
@@ -661,7 +705,10 @@ where
             // which report filesystem metadata size that does not match
             // their actual content size
             // Attempt direct `seek()` for the end of a file
+            #[cfg(not(target_family = "wasm"))]
             let mut tmp_fd = File::open(Path::new(input))?;
+            #[cfg(target_family = "wasm")]
+            let mut tmp_fd = open_input_seekable(Path::new(input))?;
             let end = tmp_fd.seek(SeekFrom::End(0))?;
             if end > 0 {
                 Ok(end)
@@ -721,9 +768,6 @@ impl<'a> ByteChunkWriter<'a> {
         let filename = filename_iterator.next().ok_or_else(|| {
             USimpleError::new(1, translate!("split-error-output-file-suffixes-exhausted"))
         })?;
-        if settings.verbose {
-            println!("creating file {}", filename.quote());
-        }
         let inner = settings.instantiate_current_writer(&filename, true)?;
         Ok(ByteChunkWriter {
             settings,
@@ -759,9 +803,6 @@ impl Write for ByteChunkWriter<'_> {
                 let filename = self.filename_iterator.next().ok_or_else(|| {
                     io::Error::other(translate!("split-error-output-file-suffixes-exhausted"))
                 })?;
-                if self.settings.verbose {
-                    println!("creating file {}", filename.quote());
-                }
                 self.inner = self.settings.instantiate_current_writer(&filename, true)?;
             }
 
@@ -860,9 +901,6 @@ impl<'a> LineChunkWriter<'a> {
         let filename = filename_iterator.next().ok_or_else(|| {
             io::Error::other(translate!("split-error-output-file-suffixes-exhausted"))
         })?;
-        if settings.verbose {
-            println!("creating file {}", filename.quote());
-        }
         settings.instantiate_current_writer(&filename, true)
     }
 }
@@ -1139,7 +1177,7 @@ where
     }
 
     // In Kth chunk of N mode - we will write to stdout instead of to a file.
-    let mut stdout_writer = io::stdout().lock();
+    let mut stdout_writer = stdout().lock();
     // In N chunks mode - we will write to `num_chunks` files
     let mut out_files: OutFiles = OutFiles::new();
 
@@ -1263,7 +1301,7 @@ where
     }
 
     // In Kth chunk of N mode - we will write to stdout instead of to a file.
-    let mut stdout_writer = io::stdout().lock();
+    let mut stdout_writer = stdout().lock();
     // In N chunks mode - we will write to `num_chunks` files
     let mut out_files: OutFiles = OutFiles::new();
 
@@ -1317,7 +1355,9 @@ where
         let num_line_bytes = bytes.len() as u64;
         num_bytes_written += num_line_bytes;
         let mut skipped = -1;
-        while num_bytes_should_be_written <= num_bytes_written {
+        // The last chunk takes whatever is left, which keeps this from running
+        // away when there are fewer bytes than chunks.
+        while chunk_number < num_chunks && num_bytes_should_be_written <= num_bytes_written {
             num_bytes_should_be_written +=
                 chunk_size_base + (chunk_size_reminder > chunk_number) as u64;
             chunk_number += 1;
@@ -1380,7 +1420,7 @@ where
     R: BufRead,
 {
     // In Kth chunk of N mode - we will write to stdout instead of to a file.
-    let mut stdout_writer = io::stdout().lock();
+    let mut stdout_writer = stdout().lock();
     // In N chunks mode - we will write to `num_chunks` files
     let mut out_files: OutFiles = OutFiles::new();
 
@@ -1488,9 +1528,6 @@ where
                 let filename = filename_iterator.next().ok_or_else(|| {
                     USimpleError::new(1, translate!("split-error-output-file-suffixes-exhausted"))
                 })?;
-                if settings.verbose {
-                    println!("creating file {}", filename.quote());
-                }
                 writer = settings.instantiate_current_writer(&filename, true)?;
                 remaining = chunk_size;
             }
@@ -1535,15 +1572,27 @@ where
 }
 
 #[allow(clippy::cognitive_complexity)]
-fn split(settings: &Settings) -> UResult<()> {
+fn split(settings: &mut Settings) -> UResult<()> {
     let r_box = if settings.input == "-" {
         Box::new(stdin()) as Box<dyn Read>
     } else {
-        let r = File::open(Path::new(&settings.input)).map_err_context(
+        #[cfg(not(target_family = "wasm"))]
+        let r = File::open(Path::new(&settings.input));
+        #[cfg(target_family = "wasm")]
+        let r = open_input(Path::new(&settings.input));
+        let r = r.map_err_context(
             || translate!("split-error-cannot-open-for-reading", "file" => settings.input.quote()),
         )?;
         Box::new(r) as Box<dyn Read>
     };
+    #[cfg(unix)]
+    if settings.preserve_mode {
+        let mode = platform::input_mode(&settings.input).map_err_context(
+            || translate!("split-error-cannot-read-mode", "file" => settings.input.quote()),
+        )?;
+        settings.input_mode = Some(mode);
+    }
+    let settings = &*settings;
     let mut reader = if let Some(c) = settings.io_blksize {
         BufReader::with_capacity(c.try_into().unwrap(), r_box)
     } else {
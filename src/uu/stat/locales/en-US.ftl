@@ -55,7 +55,7 @@ stat-after-help = Valid format sequences for files (without `--file-system`):
 
 ## Error messages
 
-stat-error-invalid-quoting-style = Invalid quoting style: {$style}
+stat-error-invalid-quoting-style = ignoring invalid value of environment variable QUOTING_STYLE: {$style}
 stat-error-missing-operand = missing operand
   Try 'stat --help' for more information.
 stat-error-invalid-directive = {$directive}: invalid directive
@@ -81,6 +81,7 @@ stat-help-format = use the specified FORMAT instead of the default;
 stat-help-printf = like --format, but interpret backslash escapes,
   and do not output a mandatory trailing newline;
   if you want a newline, include \n in FORMAT
+stat-help-json = print one JSON object per file instead of the formatted text (JSON Lines)
 
 ## Word translations
 
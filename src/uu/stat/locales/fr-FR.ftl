@@ -62,6 +62,7 @@ stat-help-format = utiliser le FORMAT spécifié au lieu du défaut ;
 stat-help-printf = comme --format, mais interpréter les séquences d'échappement avec barre oblique inverse,
   et ne pas afficher une nouvelle ligne finale obligatoire ;
   si vous voulez une nouvelle ligne, incluez \n dans FORMAT
+stat-help-json = afficher un objet JSON par fichier au lieu du texte formaté (JSON Lines)
 
 ## Traductions de mots
 
@@ -91,7 +92,7 @@ stat-word-birth = Créé
 
 ## Messages d'erreur
 
-stat-error-invalid-quoting-style = Style de guillemets invalide : {$style}
+stat-error-invalid-quoting-style = valeur invalide ignorée pour la variable d'environnement QUOTING_STYLE : {$style}
 stat-error-missing-operand = opérande manquant
   Essayez 'stat --help' pour plus d'informations.
 stat-error-invalid-directive = {$directive} : directive invalide
@@ -9,30 +9,50 @@ use uucore::translate;
 
 use clap::builder::ValueParser;
 use uucore::display::Quotable;
+#[cfg(not(target_family = "wasm"))]
+use uucore::entries;
+use uucore::format::machine::Record;
+#[cfg(not(target_family = "wasm"))]
+use uucore::format::machine::file_type_name;
+#[cfg(not(target_family = "wasm"))]
 use uucore::fs::{display_permissions, major, minor};
+use uucore::fsext::read_fs_list;
+#[cfg(not(target_family = "wasm"))]
 use uucore::fsext::{
-    FsMeta, MetadataTimeField, StatFs, metadata_get_time, pretty_filetype, pretty_fstype,
-    read_fs_list, statfs,
+    FsMeta, MetadataTimeField, StatFs, metadata_get_time, pretty_filetype, pretty_fstype, statfs,
 };
+#[cfg(not(target_family = "wasm"))]
 use uucore::libc::mode_t;
-use uucore::{entries, format_usage, show_error, show_warning};
+use uucore::quoting_style::{QuotingStyle, locale_aware_escape_name};
+use uucore::{format_usage, show_error, show_warning};
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use std::borrow::Cow;
 use std::cell::OnceCell;
+use std::env;
 use std::ffi::{OsStr, OsString};
-use std::fs::{FileType, Metadata};
+#[cfg(not(target_family = "wasm"))]
+use std::fs::{self, FileType, Metadata, read_link};
 use std::io::Write;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
+#[cfg(not(target_family = "wasm"))]
 use std::os::unix::fs::{FileTypeExt, MetadataExt};
 use std::path::Path;
-use std::{env, fs};
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{read_link, stdout};
 
 use thiserror::Error;
-use uucore::time::{FormatSystemTimeFallback, format_system_time, system_time_to_sec};
+#[cfg(not(target_family = "wasm"))]
+use uucore::time::system_time_to_sec;
+use uucore::time::{FormatSystemTimeFallback, format_system_time};
+
+#[cfg(target_family = "wasm")]
+mod wasm;
 
 #[derive(Debug, Error)]
 enum StatError {
-    #[error("{}", translate!("stat-error-invalid-quoting-style", "style" => style.clone()))]
+    #[error("{}", translate!("stat-error-invalid-quoting-style", "style" => style.quote()))]
     InvalidQuotingStyle { style: String },
     #[error("{}", translate!("stat-error-missing-operand"))]
     MissingOperand,
@@ -60,6 +80,7 @@ mod options {
     pub const FORMAT: &str = "format";
     pub const PRINTF: &str = "printf";
     pub const TERSE: &str = "terse";
+    pub const JSON: &str = "json";
     pub const FILES: &str = "files";
 }
 
@@ -107,17 +128,19 @@ enum Padding {
 /// fill characters.
 fn pad_and_print(result: &str, left: bool, width: usize, padding: Padding) {
     match (left, padding) {
-        (false, Padding::Zero) => print!("{result:0>width$}"),
-        (false, Padding::Space) => print!("{result:>width$}"),
-        (true, Padding::Zero) => print!("{result:0<width$}"),
-        (true, Padding::Space) => print!("{result:<width$}"),
+        (false, Padding::Zero) => write!(stdout(), "{result:0>width$}"),
+        (false, Padding::Space) => write!(stdout(), "{result:>width$}"),
+        (true, Padding::Zero) => write!(stdout(), "{result:0<width$}"),
+        (true, Padding::Space) => write!(stdout(), "{result:<width$}"),
     }
+    .unwrap();
 }
 
 /// Pads and prints raw bytes (Unix-specific) or falls back to string printing
 ///
 /// On Unix systems, this preserves non-UTF8 data by printing raw bytes
 /// On other platforms, falls back to lossy string conversion
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
 fn pad_and_print_bytes<W: Write>(
     mut writer: W,
     bytes: &[u8],
@@ -153,6 +176,7 @@ fn pad_and_print_bytes<W: Write>(
 /// write padding based on a writer W and n size
 /// writer is genric to be any buffer like: `std::io::stdout`
 /// n is the calculated padding size
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
 fn write_padding<W: Write>(writer: &mut W, n: usize) -> Result<(), std::io::Error> {
     for _ in 0..n {
         writer.write_all(b" ")?;
@@ -172,31 +196,6 @@ pub enum OutputType<'a> {
     Unknown,
 }
 
-#[derive(Default)]
-enum QuotingStyle {
-    Locale,
-    Shell,
-    #[default]
-    ShellEscapeAlways,
-    Quote,
-}
-
-impl std::str::FromStr for QuotingStyle {
-    type Err = StatError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "locale" => Ok(Self::Locale),
-            "shell" => Ok(Self::Shell),
-            "shell-escape-always" => Ok(Self::ShellEscapeAlways),
-            // The others aren't exposed to the user
-            _ => Err(StatError::InvalidQuotingStyle {
-                style: s.to_string(),
-            }),
-        }
-    }
-}
-
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum Precision {
     NotSpecified,
@@ -307,10 +306,13 @@ struct Stater {
     show_fs: bool,
     from_user: bool,
     files: Vec<OsString>,
+    json: bool,
     mount_list: OnceCell<Option<Vec<OsString>>>,
     mount_list_needed: bool,
     default_tokens: Vec<Token>,
+    #[cfg_attr(target_family = "wasm", allow(dead_code))]
     default_dev_tokens: Vec<Token>,
+    quoting_style: OnceCell<Option<QuotingStyle>>,
 }
 
 /// Prints a formatted output based on the provided output type, flags, width, and precision.
@@ -369,7 +371,7 @@ fn print_it(output: &OutputType, flags: Flags, width: usize, precision: Precisio
         OutputType::Float(num) => {
             print_float(*num, flags, width, precision, padding_char);
         }
-        OutputType::Unknown => print!("?"),
+        OutputType::Unknown => write_raw_byte(b'?'),
     }
 }
 
@@ -424,7 +426,7 @@ fn print_os_str(s: &OsString, flags: Flags, width: usize, precision: Precision)
 
         let bytes = s.as_bytes();
 
-        if pad_and_print_bytes(std::io::stdout(), bytes, flags.left, width, precision).is_err() {
+        if pad_and_print_bytes(stdout(), bytes, flags.left, width, precision).is_err() {
             // if an error occurred while trying to print bytes fall back to normal lossy string so it can be printed
             let fallback_string = s.to_string_lossy();
             print_str(&fallback_string, flags, width, precision);
@@ -437,57 +439,36 @@ fn print_os_str(s: &OsString, flags: Flags, width: usize, precision: Precision)
     }
 }
 
-fn quote_file_name(file_name: &str, quoting_style: &QuotingStyle) -> String {
-    match quoting_style {
-        QuotingStyle::Locale | QuotingStyle::Shell => {
-            let escaped = file_name.replace('\'', r"\'");
-            format!("'{escaped}'")
-        }
-        QuotingStyle::ShellEscapeAlways => {
-            let quote = if file_name.contains('\'') { '"' } else { '\'' };
-            format!("{quote}{file_name}{quote}")
-        }
-        QuotingStyle::Quote => file_name.to_string(),
+/// Quote a file name for `%N` with `style`, or print it as is for the
+/// default format, where GNU leaves it unquoted.
+fn quote_file_name(file_name: &OsStr, style: Option<QuotingStyle>) -> OsString {
+    match style {
+        Some(style) => locale_aware_escape_name(file_name, style),
+        None => file_name.to_owned(),
     }
 }
 
+/// The `%N` name of `file`, followed by `-> target` if it is a symbolic link.
 fn get_quoted_file_name(
-    display_name: &str,
+    display_name: &OsStr,
     file: &OsString,
-    file_type: FileType,
-    from_user: bool,
-) -> Result<String, i32> {
-    let quoting_style = env::var("QUOTING_STYLE")
-        .ok()
-        .and_then(|style| style.parse().ok())
-        .unwrap_or_default();
-
-    if file_type.is_symlink() {
-        let quoted_display_name = quote_file_name(display_name, &quoting_style);
-        match fs::read_link(file) {
-            Ok(dst) => {
-                let quoted_dst = quote_file_name(&dst.to_string_lossy(), &quoting_style);
-                Ok(format!("{quoted_display_name} -> {quoted_dst}"))
-            }
-            Err(e) => {
-                show_error!("{e}");
-                Err(1)
-            }
-        }
-    } else {
-        let style = if from_user {
-            quoting_style
-        } else {
-            QuotingStyle::Quote
-        };
-        Ok(quote_file_name(display_name, &style))
+    is_symlink: bool,
+    style: Option<QuotingStyle>,
+) -> std::io::Result<OsString> {
+    let mut quoted = quote_file_name(display_name, style);
+    if is_symlink {
+        let dst = read_link(file)?;
+        quoted.push(" -> ");
+        quoted.push(quote_file_name(dst.as_os_str(), style));
     }
+    Ok(quoted)
 }
 
+#[cfg(not(target_family = "wasm"))]
 fn process_token_filesystem(t: &Token, meta: &StatFs, display_name: &str) {
     match *t {
         Token::Byte(byte) => write_raw_byte(byte),
-        Token::Char(c) => print!("{c}"),
+        Token::Char(c) => write_char(c),
         Token::Directive {
             flag,
             width,
@@ -696,8 +677,75 @@ fn print_unsigned_hex(
     pad_and_print(&s, flags.left, width, padding_char);
 }
 
+fn print_record(record: &Record) {
+    stdout()
+        .write_all(format!("{record}\n").as_bytes())
+        .unwrap();
+}
+
+/// The `--json` record of a file. Unlike `%U` and `%G`, unknown owner and
+/// group names are `null`.
+#[cfg(not(target_family = "wasm"))]
+fn json_file_record(meta: &Metadata, name: &OsStr, file: &OsStr) -> Record {
+    let mut record = Record::new();
+    record
+        .os_string("name", name)
+        .string("type", file_type_name(meta.file_type()))
+        .unsigned("size", meta.len())
+        .unsigned("blocks", meta.blocks())
+        .unsigned("io_block", meta.blksize())
+        .unsigned("device", meta.dev())
+        .unsigned("rdev", meta.rdev())
+        .unsigned("inode", meta.ino())
+        .unsigned("nlink", meta.nlink())
+        .unsigned("mode", u64::from(meta.mode() & 0o7777))
+        .string("permissions", &display_permissions(meta, true))
+        .unsigned("uid", u64::from(meta.uid()))
+        .unsigned("gid", u64::from(meta.gid()))
+        .optional_string("owner", entries::uid2usr(meta.uid()).ok().as_deref())
+        .optional_string("group", entries::gid2grp(meta.gid()).ok().as_deref())
+        .time("atime", metadata_get_time(meta, MetadataTimeField::Access))
+        .time(
+            "mtime",
+            metadata_get_time(meta, MetadataTimeField::Modification),
+        )
+        .time("ctime", metadata_get_time(meta, MetadataTimeField::Change))
+        .time("birth", metadata_get_time(meta, MetadataTimeField::Birth));
+    let target = meta.is_symlink().then(|| read_link(file).ok()).flatten();
+    match target {
+        Some(target) => record.os_string("target", target.as_os_str()),
+        None => record.null("target"),
+    };
+    record
+}
+
+/// The `--json` record of a file system, for `--file-system`.
+#[cfg(not(target_family = "wasm"))]
+fn json_filesystem_record(meta: &StatFs, name: &OsStr) -> Record {
+    let mut record = Record::new();
+    record
+        .os_string("name", name)
+        .unsigned("id", meta.fsid())
+        .string("type", &pretty_fstype(meta.fs_type()))
+        .unsigned("namelen", meta.namelen())
+        .unsigned("io_block", meta.io_size())
+        .signed("block_size", meta.block_size())
+        .unsigned("blocks", meta.total_blocks())
+        .unsigned("blocks_free", meta.free_blocks())
+        .unsigned("blocks_available", meta.avail_blocks())
+        .unsigned("inodes", meta.total_file_nodes())
+        .unsigned("inodes_free", meta.free_file_nodes());
+    record
+}
+
 fn write_raw_byte(byte: u8) {
-    std::io::stdout().write_all(&[byte]).unwrap();
+    stdout().write_all(&[byte]).unwrap();
+}
+
+fn write_char(c: char) {
+    stdout()
+        .write_all(c.encode_utf8(&mut [0; 4]).as_bytes())
+        .unwrap();
 }
 
 impl Stater {
@@ -976,10 +1024,34 @@ impl Stater {
             show_fs,
             from_user: !format_str.is_empty(),
             files,
+            json: matches.get_flag(options::JSON),
             mount_list: OnceCell::new(),
             mount_list_needed,
             default_tokens,
             default_dev_tokens,
+            quoting_style: OnceCell::new(),
+        })
+    }
+
+    /// The style `%N` quotes names with: the one named by `QUOTING_STYLE`
+    /// for a user format, or none at all for the default format, like GNU.
+    fn quoting_style(&self) -> Option<QuotingStyle> {
+        *self.quoting_style.get_or_init(|| {
+            if !self.from_user {
+                return None;
+            }
+            let style = env::var("QUOTING_STYLE").ok().and_then(|style| {
+                let parsed = QuotingStyle::from_name(&style);
+                if parsed.is_none() {
+                    show_error!("{}", StatError::InvalidQuotingStyle { style });
+                }
+                parsed
+            });
+            Some(
+                style
+                    .unwrap_or(QuotingStyle::SHELL_ESCAPE_QUOTE)
+                    .show_control(true),
+            )
         })
     }
 
@@ -1009,6 +1081,7 @@ impl Stater {
             .find(|root| path.starts_with(root))
     }
 
+    #[cfg(not(target_family = "wasm"))]
     fn exec(&self) -> i32 {
         let mut stdin_is_fifo = false;
         if cfg!(unix) {
@@ -1024,6 +1097,7 @@ impl Stater {
         ret
     }
 
+    #[cfg(not(target_family = "wasm"))]
     #[allow(clippy::too_many_arguments)]
     fn process_token_files(
         &self,
@@ -1032,13 +1106,12 @@ impl Stater {
         display_name: &str,
         file: &OsString,
         file_type: FileType,
-        from_user: bool,
         #[cfg(feature = "selinux")] follow_symbolic_links: bool,
         #[cfg(not(feature = "selinux"))] _: bool,
     ) -> Result<(), i32> {
         match *t {
             Token::Byte(byte) => write_raw_byte(byte),
-            Token::Char(c) => print!("{c}"),
+            Token::Char(c) => write_char(c),
 
             Token::Directive {
                 flag,
@@ -1046,6 +1119,7 @@ impl Stater {
                 precision,
                 format,
             } => {
+                let quoted_name;
                 let output = match format {
                     // access rights in octal
                     'a' => OutputType::UnsignedOct(0o7777 & meta.mode()),
@@ -1118,9 +1192,17 @@ impl Stater {
                     'n' => OutputType::Str(display_name.to_string()),
                     // quoted file name with dereference if symbolic link
                     'N' => {
-                        let file_name =
-                            get_quoted_file_name(display_name, file, file_type, from_user)?;
-                        OutputType::Str(file_name)
+                        quoted_name = get_quoted_file_name(
+                            display_name.as_ref(),
+                            file,
+                            file_type.is_symlink(),
+                            self.quoting_style(),
+                        )
+                        .map_err(|e| {
+                            show_error!("{e}");
+                            1
+                        })?;
+                        OutputType::OsStr(&quoted_name)
                     }
                     // optimal I/O transfer size hint
                     'o' => OutputType::Unsigned(meta.blksize()),
@@ -1186,7 +1268,9 @@ impl Stater {
         Ok(())
     }
 
+    #[cfg(not(target_family = "wasm"))]
     fn do_stat(&self, file: &OsStr, stdin_is_fifo: bool) -> i32 {
+        let name = file;
         let display_name = file.to_string_lossy();
         let file = if cfg!(unix) && display_name == "-" {
             if self.show_fs {
@@ -1203,6 +1287,9 @@ impl Stater {
         };
         if self.show_fs {
             match statfs(&file) {
+                Ok(meta) if self.json => {
+                    print_record(&json_filesystem_record(&meta, name));
+                }
                 Ok(meta) => {
                     let tokens = &self.default_tokens;
 
@@ -1230,6 +1317,9 @@ impl Stater {
                 fs::symlink_metadata(&file)
             };
             match result {
+                Ok(meta) if self.json => {
+                    print_record(&json_file_record(&meta, name, &file));
+                }
                 Ok(meta) => {
                     let file_type = meta.file_type();
                     let tokens = if self.from_user
@@ -1247,7 +1337,6 @@ impl Stater {
                             &display_name,
                             &file,
                             file_type,
-                            self.from_user,
                             follow_symbolic_links,
                         ) {
                             return code;
@@ -1392,6 +1481,13 @@ pub fn uu_app() -> Command {
                 .value_name("FORMAT")
                 .help(translate!("stat-help-printf")),
         )
+        .arg(
+            Arg::new(options::JSON)
+                .long(options::JSON)
+                .help(translate!("stat-help-json"))
+                .conflicts_with_all([options::FORMAT, options::PRINTF, options::TERSE])
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new(options::FILES)
                 .action(ArgAction::Append)
@@ -1402,8 +1498,13 @@ pub fn uu_app() -> Command {
 
 const PRETTY_DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S.%N %z";
 
+#[cfg(not(target_family = "wasm"))]
 fn pretty_time(meta: &Metadata, md_time_field: MetadataTimeField) -> String {
-    if let Some(time) = metadata_get_time(meta, md_time_field) {
+    format_time(metadata_get_time(meta, md_time_field))
+}
+
+fn format_time(time: Option<std::time::SystemTime>) -> String {
+    if let Some(time) = time {
         let mut tmp = Vec::new();
         if format_system_time(
             &mut tmp,
@@ -1422,6 +1523,8 @@ fn pretty_time(meta: &Metadata, md_time_field: MetadataTimeField) -> String {
 #[cfg(test)]
 mod tests {
     use crate::{pad_and_print_bytes, quote_file_name, write_padding};
+    use std::ffi::OsStr;
+    use uucore::quoting_style::QuotingStyle;
 
     use super::{Flags, Precision, ScanUtil, Stater, Token, group_num, precision_trunc};
 
@@ -1575,16 +1678,21 @@ mod tests {
 
     #[test]
     fn test_quote_file_name() {
-        let file_name = "nice' file";
+        let style = Some(QuotingStyle::SHELL_ESCAPE_QUOTE.show_control(true));
         assert_eq!(
-            quote_file_name(file_name, &crate::QuotingStyle::ShellEscapeAlways),
+            quote_file_name(OsStr::new("nice' file"), style),
             "\"nice' file\""
         );
-
-        let file_name = "nice\" file";
         assert_eq!(
-            quote_file_name(file_name, &crate::QuotingStyle::ShellEscapeAlways),
+            quote_file_name(OsStr::new("nice\" file"), style),
             "\'nice\" file\'"
         );
+        assert_eq!(
+            quote_file_name(OsStr::new("new\nline"), style),
+            "'new'$'\\n''line'"
+        );
+
+        // the default format prints names as they are
+        assert_eq!(quote_file_name(OsStr::new("new\nline"), None), "new\nline");
     }
 }
@@ -0,0 +1,232 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! File status on WASM.
+//!
+//! `std::fs::Metadata` cannot be constructed by a host, so this describes
+//! files with [`uucore::wasm_io::metadata`] instead. Hosts don't report
+//! owners, devices, inodes or file systems, so those directives print
+//! placeholders and `--file-system` is not supported.
+
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::io;
+
+use uucore::display::Quotable;
+use uucore::entries;
+use uucore::format::machine::{Record, wasm_file_type_name};
+use uucore::show_error;
+use uucore::translate;
+use uucore::wasm_io::{self, WasmFileType, WasmMetadata};
+
+use crate::{
+    OutputType, StatError, Stater, Token, format_time, get_quoted_file_name, print_it,
+    print_record, quote_file_name, write_char, write_raw_byte,
+};
+
+/// The file type bits of a raw mode, as reported by `%f`.
+fn file_type_bits(file_type: WasmFileType) -> u32 {
+    match file_type {
+        WasmFileType::File => 0o100_000,
+        WasmFileType::Dir => 0o040_000,
+        WasmFileType::Symlink => 0o120_000,
+        WasmFileType::Other => 0,
+    }
+}
+
+fn pretty_filetype(meta: &WasmMetadata) -> String {
+    match meta.file_type {
+        WasmFileType::File if meta.is_empty() => "regular empty file",
+        WasmFileType::File => "regular file",
+        WasmFileType::Dir => "directory",
+        WasmFileType::Symlink => "symbolic link",
+        WasmFileType::Other => "weird file",
+    }
+    .to_owned()
+}
+
+/// The `--json` record of a file, with the same keys as on other platforms
+/// and `null` for what hosts don't report.
+fn json_record(meta: &WasmMetadata, display_name: &str, file: &OsStr) -> Record {
+    let uid = wasm_io::uid();
+    let gid = wasm_io::gid();
+    let mut record = Record::new();
+    record
+        .string("name", display_name)
+        .string("type", wasm_file_type_name(meta.file_type))
+        .unsigned("size", meta.len())
+        .unsigned("blocks", meta.len().div_ceil(512))
+        .unsigned("io_block", 512)
+        .null("device")
+        .null("rdev")
+        .null("inode")
+        .unsigned("nlink", 1)
+        .unsigned("mode", u64::from(meta.mode & 0o7777))
+        .string("permissions", &meta.display_permissions(true))
+        .optional_unsigned("uid", uid.map(u64::from))
+        .optional_unsigned("gid", gid.map(u64::from))
+        .optional_string(
+            "owner",
+            uid.and_then(|id| entries::uid2usr(id).ok()).as_deref(),
+        )
+        .optional_string(
+            "group",
+            gid.and_then(|id| entries::gid2grp(id).ok()).as_deref(),
+        )
+        .null("atime")
+        .time("mtime", meta.modified)
+        .null("ctime")
+        .null("birth");
+    let target = meta
+        .is_symlink()
+        .then(|| wasm_io::read_link(file).ok())
+        .flatten();
+    match target {
+        Some(target) => record.os_string("target", target.as_os_str()),
+        None => record.null("target"),
+    };
+    record
+}
+
+impl Stater {
+    pub(crate) fn exec(&self) -> i32 {
+        let mut ret = 0;
+        for f in &self.files {
+            ret |= self.do_stat(f);
+        }
+        ret
+    }
+
+    fn process_token_files(
+        &self,
+        t: &Token,
+        meta: &WasmMetadata,
+        display_name: &str,
+        file: &OsString,
+    ) {
+        match *t {
+            Token::Byte(byte) => write_raw_byte(byte),
+            Token::Char(c) => write_char(c),
+
+            Token::Directive {
+                flag,
+                width,
+                precision,
+                format,
+            } => {
+                let quoted_name;
+                let output = match format {
+                    // access rights in octal
+                    'a' => OutputType::UnsignedOct(0o7777 & meta.mode),
+                    // access rights in human readable form
+                    'A' => OutputType::Str(meta.display_permissions(true)),
+                    // number of blocks allocated (see %B)
+                    'b' => OutputType::Unsigned(meta.len().div_ceil(512)),
+                    // the size in bytes of each block reported by %b
+                    'B' | 'o' => OutputType::Unsigned(512),
+                    // SELinux security context string
+                    'C' => OutputType::Str(translate!("stat-selinux-unsupported-os")),
+                    // raw mode in hex
+                    'f' => OutputType::UnsignedHex(u64::from(
+                        file_type_bits(meta.file_type) | meta.mode,
+                    )),
+                    // file type
+                    'F' => OutputType::Str(pretty_filetype(meta)),
+                    // user and group names of owner
+                    'G' | 'U' => OutputType::Str("UNKNOWN".to_owned()),
+                    // number of hard links
+                    'h' => OutputType::Unsigned(1),
+                    // mount point
+                    'm' => match self.find_mount_point(file) {
+                        Some(s) => OutputType::OsStr(s),
+                        None => OutputType::Str(String::new()),
+                    },
+                    // file name
+                    'n' => OutputType::Str(display_name.to_string()),
+                    // quoted file name
+                    'N' => {
+                        let style = self.quoting_style();
+                        quoted_name = get_quoted_file_name(
+                            display_name.as_ref(),
+                            file,
+                            meta.is_symlink(),
+                            style,
+                        )
+                        .unwrap_or_else(|e| {
+                            show_error!("{e}");
+                            quote_file_name(display_name.as_ref(), style)
+                        });
+                        OutputType::OsStr(&quoted_name)
+                    }
+                    // total size, in bytes
+                    's' => OutputType::Integer(meta.len() as i64),
+                    // time of last data modification, human-readable
+                    'y' => OutputType::Str(format_time(meta.modified)),
+                    // time of last data modification, seconds since Epoch
+                    'Y' => {
+                        let (sec, nsec) = meta
+                            .modified
+                            .map_or((0, 0), uucore::time::system_time_to_sec);
+                        OutputType::Float(sec as f64 + nsec as f64 / 1_000_000_000.0)
+                    }
+                    // times of file birth, last access and last status change
+                    'w' | 'x' | 'z' => OutputType::Str(format_time(None)),
+                    'W' | 'X' | 'Z' => OutputType::Integer(0),
+                    // device numbers, owner IDs and inode number
+                    'd' | 'D' | 'g' | 'i' | 'r' | 'R' | 't' | 'T' | 'u' => OutputType::Unsigned(0),
+                    _ => OutputType::Unknown,
+                };
+                print_it(&output, flag, width, precision);
+            }
+        }
+    }
+
+    fn do_stat(&self, file: &OsStr) -> i32 {
+        let display_name = file.to_string_lossy();
+        if self.show_fs && display_name == "-" {
+            show_error!("{}", StatError::StdinFilesystemMode);
+            return 1;
+        }
+        if self.show_fs {
+            show_error!(
+                "{}",
+                StatError::CannotReadFilesystemInfo {
+                    file: display_name.quote().to_string(),
+                    error: io::Error::from(io::ErrorKind::Unsupported).to_string(),
+                }
+            );
+            return 1;
+        }
+
+        let result = if self.follow {
+            wasm_io::metadata(file)
+        } else {
+            wasm_io::symlink_metadata(file)
+        };
+        match result {
+            Ok(meta) if self.json => {
+                print_record(&json_record(&meta, &display_name, file));
+                0
+            }
+            Ok(meta) => {
+                let file = OsString::from(file);
+                for t in &self.default_tokens {
+                    self.process_token_files(t, &meta, &display_name, &file);
+                }
+                0
+            }
+            Err(e) => {
+                show_error!(
+                    "{}",
+                    StatError::CannotStat {
+                        file: display_name.quote().to_string(),
+                        error: e.to_string()
+                    }
+                );
+                1
+            }
+        }
+    }
+}
@@ -207,7 +207,7 @@ fn set_buffer(stream: *mut FILE, value: &str) {
     if res != 0 {
         eprintln!("could not set buffering of {} to mode {mode}", unsafe {
             fileno(stream)
-        },);
+        });
     }
 }
 
@@ -0,0 +1,21 @@
+strings-about = Print the sequences of printable characters in files.
+
+  With no FILE, or when FILE is -, read standard input.
+strings-usage = strings [OPTION]... [FILE]...
+
+# Help messages
+strings-help-all = scan the whole file (the default)
+strings-help-bytes = print sequences of at least MIN characters (default 4)
+strings-help-print-file-name = print the name of the file before each string
+strings-help-radix = print the offset of each string in base RADIX
+strings-help-octal = alias for --radix=o
+strings-help-encoding = select the character size and endianness (s and S for 7 and 8 bits, b and l for 16 bits, B and L for 32 bits)
+strings-help-unicode = how to treat UTF-8 multibyte characters
+strings-help-include-all-whitespace = include all whitespace characters in strings
+strings-help-output-separator = separate strings with SEP instead of a newline
+
+# Output
+strings-standard-input = {"{"}standard input{"}"}
+
+# Error messages
+strings-error-invalid-minimum-length = invalid minimum string length { $length }
@@ -0,0 +1,21 @@
+strings-about = Afficher les séquences de caractères imprimables des fichiers.
+
+  Sans FICHIER, ou quand FICHIER est -, lire l'entrée standard.
+strings-usage = strings [OPTION]... [FICHIER]...
+
+# Messages d'aide
+strings-help-all = analyser le fichier entier (par défaut)
+strings-help-bytes = afficher les séquences d'au moins MIN caractères (4 par défaut)
+strings-help-print-file-name = afficher le nom du fichier avant chaque chaîne
+strings-help-radix = afficher la position de chaque chaîne en base RADIX
+strings-help-octal = alias pour --radix=o
+strings-help-encoding = choisir la taille et le boutisme des caractères (s et S pour 7 et 8 bits, b et l pour 16 bits, B et L pour 32 bits)
+strings-help-unicode = traitement des caractères multi-octets UTF-8
+strings-help-include-all-whitespace = inclure tous les caractères d'espacement dans les chaînes
+strings-help-output-separator = séparer les chaînes par SEP au lieu d'un saut de ligne
+
+# Sortie
+strings-standard-input = {"{"}entrée standard{"}"}
+
+# Messages d'erreur
+strings-error-invalid-minimum-length = longueur minimale de chaîne invalide { $length }
@@ -0,0 +1,459 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+// spell-checker:ignore (ToDO) endianness
+
+use clap::{Arg, ArgAction, Command};
+use std::ffi::{OsStr, OsString};
+#[cfg(not(target_family = "wasm"))]
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Bytes, Read, Write};
+#[cfg(not(target_family = "wasm"))]
+use std::io::{stdin, stdout};
+use std::iter::Peekable;
+use uucore::display::{OsWrite, Quotable};
+use uucore::error::{FromIo, UResult, USimpleError};
+use uucore::parser::shortcut_value_parser::ShortcutValueParser;
+use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{stdin, stdout};
+
+use uucore::{format_usage, show};
+
+mod options {
+    pub const ALL: &str = "all";
+    pub const BYTES: &str = "bytes";
+    pub const PRINT_FILE_NAME: &str = "print-file-name";
+    pub const RADIX: &str = "radix";
+    pub const OCTAL: &str = "octal";
+    pub const ENCODING: &str = "encoding";
+    pub const UNICODE: &str = "unicode";
+    pub const INCLUDE_ALL_WHITESPACE: &str = "include-all-whitespace";
+    pub const OUTPUT_SEPARATOR: &str = "output-separator";
+    pub const FILE: &str = "file";
+}
+
+/// The base in which the offsets of strings are printed.
+#[derive(Clone, Copy)]
+enum Radix {
+    Octal,
+    Decimal,
+    Hexadecimal,
+}
+
+/// The size and byte order of the characters to look for.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    /// 7-bit ASCII
+    SevenBit,
+    /// Any single byte, so that bytes above 127 are printable too
+    EightBit,
+    BigEndian16,
+    LittleEndian16,
+    BigEndian32,
+    LittleEndian32,
+}
+
+impl Encoding {
+    fn from_letter(letter: &str) -> Self {
+        match letter {
+            "S" => Self::EightBit,
+            "b" => Self::BigEndian16,
+            "l" => Self::LittleEndian16,
+            "B" => Self::BigEndian32,
+            "L" => Self::LittleEndian32,
+            _ => Self::SevenBit,
+        }
+    }
+
+    fn width(self) -> usize {
+        match self {
+            Self::SevenBit | Self::EightBit => 1,
+            Self::BigEndian16 | Self::LittleEndian16 => 2,
+            Self::BigEndian32 | Self::LittleEndian32 => 4,
+        }
+    }
+
+    fn is_big_endian(self) -> bool {
+        matches!(self, Self::BigEndian16 | Self::BigEndian32)
+    }
+}
+
+struct Settings {
+    min_length: usize,
+    print_file_name: bool,
+    radix: Option<Radix>,
+    encoding: Encoding,
+    /// Treat valid UTF-8 multibyte sequences as printable characters.
+    utf8: bool,
+    include_all_whitespace: bool,
+    separator: OsString,
+}
+
+impl Settings {
+    fn is_graphic(&self, c: u32) -> bool {
+        let Ok(byte) = u8::try_from(c) else {
+            return false;
+        };
+        byte == b'\t'
+            || (b' '..=b'~').contains(&byte)
+            || (self.encoding == Encoding::EightBit && byte > 127)
+            || (self.include_all_whitespace && (byte.is_ascii_whitespace() || byte == b'\x0b'))
+    }
+}
+
+/// Reads characters from an input and keeps track of the offset.
+struct Scanner<'a, R: BufRead> {
+    bytes: Peekable<Bytes<R>>,
+    offset: u64,
+    settings: &'a Settings,
+}
+
+impl<'a, R: BufRead> Scanner<'a, R> {
+    fn new(reader: R, settings: &'a Settings) -> Self {
+        Self {
+            bytes: reader.bytes().peekable(),
+            offset: 0,
+            settings,
+        }
+    }
+
+    fn next_byte(&mut self) -> std::io::Result<Option<u8>> {
+        let byte = self.bytes.next().transpose()?;
+        if byte.is_some() {
+            self.offset += 1;
+        }
+        Ok(byte)
+    }
+
+    /// Consumes the next byte if it continues a UTF-8 sequence.
+    fn next_continuation_byte(&mut self) -> std::io::Result<Option<u8>> {
+        match self.bytes.peek() {
+            Some(Ok(byte)) if (0x80..0xc0).contains(byte) => self.next_byte(),
+            _ => Ok(None),
+        }
+    }
+
+    /// Reads the next character and appends it to `string` if it is printable.
+    ///
+    /// Returns the number of characters appended, where 0 means that the
+    /// character ends the current string, or `None` at the end of the input.
+    /// An incomplete character at the end of the input is ignored.
+    fn read_char(&mut self, string: &mut Vec<u8>) -> std::io::Result<Option<usize>> {
+        let Some(first) = self.next_byte()? else {
+            return Ok(None);
+        };
+        if self.settings.utf8 && first > 127 {
+            return self.read_utf8(first, string).map(Some);
+        }
+
+        let mut c = u32::from(first);
+        for i in 1..self.settings.encoding.width() {
+            let Some(byte) = self.next_byte()? else {
+                return Ok(None);
+            };
+            if self.settings.encoding.is_big_endian() {
+                c = (c << 8) | u32::from(byte);
+            } else {
+                c |= u32::from(byte) << (8 * i);
+            }
+        }
+        if self.settings.is_graphic(c) {
+            string.push(c as u8);
+            Ok(Some(1))
+        } else {
+            Ok(Some(0))
+        }
+    }
+
+    /// Reads the rest of a UTF-8 sequence starting with `first`.
+    ///
+    /// Bytes that don't form a valid, printable character are treated as
+    /// single bytes.
+    fn read_utf8(&mut self, first: u8, string: &mut Vec<u8>) -> std::io::Result<usize> {
+        let len = match first {
+            0xc2..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf4 => 4,
+            _ => 1,
+        };
+        let mut sequence = vec![first];
+        while sequence.len() < len {
+            match self.next_continuation_byte()? {
+                Some(byte) => sequence.push(byte),
+                None => break,
+            }
+        }
+
+        let printable = std::str::from_utf8(&sequence)
+            .ok()
+            .and_then(|s| s.chars().next())
+            .is_some_and(|c| !c.is_control());
+        if printable {
+            string.extend(&sequence);
+            Ok(1)
+        } else if sequence
+            .iter()
+            .all(|&byte| self.settings.is_graphic(byte.into()))
+        {
+            string.extend(&sequence);
+            Ok(sequence.len())
+        } else {
+            Ok(0)
+        }
+    }
+}
+
+fn print_string(
+    out: &mut impl OsWrite,
+    name: &OsStr,
+    offset: u64,
+    string: &[u8],
+    settings: &Settings,
+) -> UResult<()> {
+    if settings.print_file_name {
+        out.write_all_os(name)?;
+        out.write_all(b": ")?;
+    }
+    match settings.radix {
+        Some(Radix::Octal) => write!(out, "{offset:7o} ")?,
+        Some(Radix::Decimal) => write!(out, "{offset:7} ")?,
+        Some(Radix::Hexadecimal) => write!(out, "{offset:7x} ")?,
+        None => {}
+    }
+    out.write_all(string)?;
+    out.write_all_os(&settings.separator)?;
+    Ok(())
+}
+
+/// Prints the strings of at least `settings.min_length` printable characters
+/// found in `input`.
+fn print_strings(
+    input: impl Read,
+    name: &OsStr,
+    settings: &Settings,
+    out: &mut impl OsWrite,
+) -> UResult<()> {
+    let mut scanner = Scanner::new(BufReader::new(input), settings);
+    let mut string = Vec::new();
+    let mut len = 0;
+    let mut start = 0;
+    loop {
+        let offset = scanner.offset;
+        let read = scanner
+            .read_char(&mut string)
+            .map_err_context(|| name.maybe_quote().to_string())?;
+        if let Some(n @ 1..) = read {
+            if len == 0 {
+                start = offset;
+            }
+            len += n;
+            continue;
+        }
+        if len >= settings.min_length {
+            print_string(out, name, start, &string, settings)?;
+        }
+        string.clear();
+        len = 0;
+        if read.is_none() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn open(name: &OsStr) -> UResult<Box<dyn Read>> {
+    if name == "-" {
+        return Ok(Box::new(stdin()));
+    }
+    #[cfg(not(target_family = "wasm"))]
+    let file = File::open(name);
+    #[cfg(target_family = "wasm")]
+    let file = uucore::wasm_io::open_file(name);
+    match file {
+        Ok(file) => Ok(Box::new(file)),
+        Err(err) => Err(err.map_err_context(|| name.maybe_quote().to_string())),
+    }
+}
+
+/// Rewrites the obsolete `-NUM` form of the minimum length into `--bytes=NUM`.
+fn handle_obsolete(args: impl uucore::Args) -> Vec<OsString> {
+    let mut end_of_options = false;
+    args.map(|arg| {
+        if end_of_options {
+            return arg;
+        }
+        if arg == "--" {
+            end_of_options = true;
+        }
+        match arg.to_str().and_then(|s| s.strip_prefix('-')) {
+            Some(digits) if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) => {
+                format!("--{}={digits}", options::BYTES).into()
+            }
+            _ => arg,
+        }
+    })
+    .collect()
+}
+
+#[uucore::main]
+pub fn uumain(args: impl uucore::Args) -> UResult<()> {
+    let matches = uucore::clap_localization::handle_clap_result(uu_app(), handle_obsolete(args))?;
+
+    let min_length = match matches.get_one::<String>(options::BYTES) {
+        Some(s) => match s.parse::<usize>() {
+            Ok(n) if n > 0 => n,
+            _ => {
+                return Err(USimpleError::new(
+                    1,
+                    translate!("strings-error-invalid-minimum-length", "length" => s),
+                ));
+            }
+        },
+        None => 4,
+    };
+
+    let radix = if matches.get_flag(options::OCTAL) {
+        Some(Radix::Octal)
+    } else {
+        matches
+            .get_one::<String>(options::RADIX)
+            .map(|radix| match radix.as_str() {
+                "o" => Radix::Octal,
+                "d" => Radix::Decimal,
+                _ => Radix::Hexadecimal,
+            })
+    };
+
+    let encoding = matches
+        .get_one::<String>(options::ENCODING)
+        .map_or(Encoding::SevenBit, |e| Encoding::from_letter(e));
+
+    let settings = Settings {
+        min_length,
+        print_file_name: matches.get_flag(options::PRINT_FILE_NAME),
+        radix,
+        encoding,
+        utf8: encoding.width() == 1
+            && matches
+                .get_one::<String>(options::UNICODE)
+                .is_some_and(|mode| mode == "locale"),
+        include_all_whitespace: matches.get_flag(options::INCLUDE_ALL_WHITESPACE),
+        separator: matches
+            .get_one::<OsString>(options::OUTPUT_SEPARATOR)
+            .cloned()
+            .unwrap_or_else(|| "\n".into()),
+    };
+
+    let files: Vec<OsString> = match matches.get_many::<OsString>(options::FILE) {
+        Some(v) => v.cloned().collect(),
+        None => vec![OsString::from("-")],
+    };
+
+    let mut out = BufWriter::new(stdout().lock());
+    for file in &files {
+        let input = match open(file) {
+            Ok(input) => input,
+            Err(err) => {
+                show!(err);
+                continue;
+            }
+        };
+        let name = if file == "-" {
+            translate!("strings-standard-input").into()
+        } else {
+            file.clone()
+        };
+        if let Err(err) = print_strings(input, &name, &settings, &mut out) {
+            out.flush()?;
+            show!(err);
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+pub fn uu_app() -> Command {
+    Command::new(uucore::util_name())
+        .version(uucore::crate_version!())
+        .help_template(uucore::localized_help_template(uucore::util_name()))
+        .override_usage(format_usage(&translate!("strings-usage")))
+        .about(translate!("strings-about"))
+        .infer_long_args(true)
+        .arg(
+            Arg::new(options::ALL)
+                .short('a')
+                .long(options::ALL)
+                .help(translate!("strings-help-all"))
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::BYTES)
+                .short('n')
+                .long(options::BYTES)
+                .value_name("MIN")
+                .help(translate!("strings-help-bytes")),
+        )
+        .arg(
+            Arg::new(options::PRINT_FILE_NAME)
+                .short('f')
+                .long(options::PRINT_FILE_NAME)
+                .help(translate!("strings-help-print-file-name"))
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::RADIX)
+                .short('t')
+                .long(options::RADIX)
+                .value_name("RADIX")
+                .value_parser(["o", "d", "x"])
+                .help(translate!("strings-help-radix")),
+        )
+        .arg(
+            Arg::new(options::OCTAL)
+                .short('o')
+                .help(translate!("strings-help-octal"))
+                .overrides_with(options::RADIX)
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::ENCODING)
+                .short('e')
+                .long(options::ENCODING)
+                .value_name("ENCODING")
+                .value_parser(["s", "S", "b", "l", "B", "L"])
+                .help(translate!("strings-help-encoding")),
+        )
+        .arg(
+            Arg::new(options::UNICODE)
+                .short('U')
+                .long(options::UNICODE)
+                .value_name("MODE")
+                .value_parser(ShortcutValueParser::new(["default", "invalid", "locale"]))
+                .help(translate!("strings-help-unicode")),
+        )
+        .arg(
+            Arg::new(options::INCLUDE_ALL_WHITESPACE)
+                .short('w')
+                .long(options::INCLUDE_ALL_WHITESPACE)
+                .help(translate!("strings-help-include-all-whitespace"))
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new(options::OUTPUT_SEPARATOR)
+                .short('s')
+                .long(options::OUTPUT_SEPARATOR)
+                .value_name("SEP")
+                .value_parser(clap::value_parser!(OsString))
+                .help(translate!("strings-help-output-separator")),
+        )
+        .arg(
+            Arg::new(options::FILE)
+                .action(ArgAction::Append)
+                .hide(true)
+                .value_hint(clap::ValueHint::FilePath)
+                .value_parser(clap::value_parser!(OsString)),
+        )
+}
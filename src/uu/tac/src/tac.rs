@@ -11,12 +11,8 @@ use clap::{Arg, ArgAction, Command};
 use memchr::memmem;
 use memmap2::Mmap;
 use std::ffi::OsString;
-use std::io::{BufWriter, Read, Write, stdin, stdout};
-use std::{
-    fs::{File, read},
-    io::copy,
-    path::Path,
-};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write, stdin, stdout};
+use std::{fs::File, io::copy, path::Path};
 #[cfg(unix)]
 use uucore::error::set_exit_code;
 use uucore::error::{UError, UResult};
@@ -104,21 +100,18 @@ pub fn uu_app() -> Command {
 /// `before` is `false`, then each match of this pattern is interpreted
 /// as the end of a line.
 ///
-/// This function writes each line in `data` to [`std::io::Stdout`] in
-/// reverse.
+/// This function writes each line in `data` to `out` in reverse.
 ///
 /// # Errors
 ///
-/// If there is a problem writing to `stdout`, then this function
+/// If there is a problem writing to `out`, then this function
 /// returns [`std::io::Error`].
-fn buffer_tac_regex(
+fn buffer_tac_regex<W: Write>(
     data: &[u8],
+    out: &mut W,
     pattern: &regex::bytes::Regex,
     before: bool,
 ) -> std::io::Result<()> {
-    let out = stdout();
-    let mut out = BufWriter::new(out.lock());
-
     // The index of the line separator for the current line.
     //
     // As we scan through the `data` from right to left, we update this
@@ -173,7 +166,7 @@ fn buffer_tac_regex(
 
 /// Write lines from `data` to stdout in reverse.
 ///
-/// This function writes to [`stdout`] each line appearing in `data`,
+/// This function writes to `out` each line appearing in `data`,
 /// starting with the last line and ending with the first line. The
 /// `separator` parameter defines what characters to use as a line
 /// separator.
@@ -183,10 +176,12 @@ fn buffer_tac_regex(
 /// If `before` is `true`, then this function assumes that the
 /// `separator` appears at the beginning of each line, as in
 /// `"/abc/def"`.
-fn buffer_tac(data: &[u8], before: bool, separator: &str) -> std::io::Result<()> {
-    let out = stdout();
-    let mut out = BufWriter::new(out.lock());
-
+fn buffer_tac<W: Write>(
+    data: &[u8],
+    out: &mut W,
+    before: bool,
+    separator: &str,
+) -> std::io::Result<()> {
     // The number of bytes in the line separator.
     let slen = separator.len();
 
@@ -223,6 +218,79 @@ fn buffer_tac(data: &[u8], before: bool, separator: &str) -> std::io::Result<()>
     Ok(())
 }
 
+/// The number of bytes [`file_tac`] reads at a time.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Write lines from a file that could not be memory-mapped to `out` in
+/// reverse, like [`buffer_tac`].
+///
+/// The file is read backwards, `chunk_size` bytes at a time, so that only
+/// the chunk being scanned and the line it ends in are held in memory.
+///
+/// # Errors
+///
+/// A failure to read `input` is reported as a [`TacError::ReadError`] for
+/// `filename`, and a failure to write as a [`TacError::WriteError`].
+fn file_tac<R: Read + Seek, W: Write>(
+    input: &mut R,
+    out: &mut W,
+    filename: &OsString,
+    before: bool,
+    separator: &str,
+    chunk_size: usize,
+) -> Result<(), TacError> {
+    let read_error = |e| TacError::ReadError(filename.clone(), e);
+    let slen = separator.len();
+
+    // The bytes of the file from offset `pos` that have not been written
+    // yet are `buf[start..]`.
+    let mut pos = input.seek(SeekFrom::End(0)).map_err(read_error)?;
+    let mut buf = Vec::new();
+    let mut start = 0;
+
+    // Separators do not overlap, so each one must end at or before the
+    // offset of the one found last.
+    let mut search_end = pos;
+
+    while pos > 0 {
+        let len = chunk_size.min(pos.try_into().unwrap_or(usize::MAX));
+
+        // Make room for the chunk in front of the bytes not written yet,
+        // growing the buffer geometrically while a line does not fit.
+        if start < len {
+            let pending = buf.len() - start;
+            let size = (2 * pending).max(pending + len);
+            let mut grown = vec![0; size];
+            grown[size - pending..].copy_from_slice(&buf[start..]);
+            start = size - pending;
+            buf = grown;
+        }
+        start -= len;
+        pos -= len as u64;
+        input.seek(SeekFrom::Start(pos)).map_err(read_error)?;
+        input
+            .read_exact(&mut buf[start..start + len])
+            .map_err(read_error)?;
+
+        // Only separators starting in this chunk are new, the others were
+        // found while scanning the chunks after it.
+        let scan_len = (search_end - pos).min((len + slen - 1) as u64) as usize;
+        let mut following_line_start = buf.len();
+        for i in memmem::rfind_iter(&buf[start..start + scan_len], separator) {
+            search_end = pos + i as u64;
+            let i = start + i;
+            let line_start = if before { i } else { i + slen };
+            out.write_all(&buf[line_start..following_line_start])
+                .map_err(TacError::WriteError)?;
+            following_line_start = line_start;
+        }
+        buf.truncate(following_line_start);
+    }
+
+    out.write_all(&buf[start..]).map_err(TacError::WriteError)?;
+    out.flush().map_err(TacError::WriteError)
+}
+
 /// Make the regex flavor compatible with `regex` crate
 ///
 /// Concretely:
@@ -324,10 +392,7 @@ fn tac(filenames: &[OsString], before: bool, regex: bool, separator: &str) -> UR
     };
 
     for filename in filenames {
-        let mmap;
-        let buf;
-
-        let data: &[u8] = if filename == "-" {
+        let input = if filename == "-" {
             #[cfg(unix)]
             if uucore::signals::stdin_was_closed() {
                 let e: Box<dyn UError> = TacError::ReadError(
@@ -339,21 +404,11 @@ fn tac(filenames: &[OsString], before: bool, regex: bool, separator: &str) -> UR
                 set_exit_code(1);
                 continue;
             }
-            if let Some(mmap1) = try_mmap_stdin() {
-                mmap = mmap1;
-                &mmap
+            if let Some(mmap) = try_mmap_stdin() {
+                Input::Mmap(mmap)
             } else {
-                // Copy stdin to a temp file (respects TMPDIR), then mmap it.
-                // Falls back to Vec buffer if temp file creation fails (e.g., bad TMPDIR).
-                match buffer_stdin() {
-                    Ok(StdinData::Mmap(mmap1)) => {
-                        mmap = mmap1;
-                        &mmap
-                    }
-                    Ok(StdinData::Vec(buf1)) => {
-                        buf = buf1;
-                        &buf
-                    }
+                match buffer_stream(stdin()) {
+                    Ok(input) => input,
                     Err(e) => {
                         show!(TacError::ReadError(OsString::from("stdin"), e));
                         continue;
@@ -375,81 +430,118 @@ fn tac(filenames: &[OsString], before: bool, regex: bool, separator: &str) -> UR
                 continue;
             }
 
-            if let Some(mmap1) = try_mmap_path(path) {
-                mmap = mmap1;
-                &mmap
-            } else {
-                match read(path) {
-                    Ok(buf1) => {
-                        buf = buf1;
-                        &buf
-                    }
-                    Err(e) => {
-                        let e: Box<dyn UError> = TacError::ReadError(filename.clone(), e).into();
-                        show!(e);
-                        continue;
-                    }
+            match open_file(path) {
+                Ok(input) => input,
+                Err(e) => {
+                    let e: Box<dyn UError> = TacError::ReadError(filename.clone(), e).into();
+                    show!(e);
+                    continue;
                 }
             }
         };
 
         // Select the appropriate `tac` algorithm based on whether the
         // separator is given as a regular expression or a fixed string.
-        let result = match maybe_pattern {
-            Some(ref pattern) => buffer_tac_regex(data, pattern, before),
-            None => buffer_tac(data, before, separator),
+        let out = stdout();
+        let mut out = BufWriter::new(out.lock());
+        let tac_data = |data: &[u8], out: &mut BufWriter<_>| {
+            match maybe_pattern {
+                Some(ref pattern) => buffer_tac_regex(data, out, pattern, before),
+                None => buffer_tac(data, out, before, separator),
+            }
+            .map_err(TacError::WriteError)
+        };
+        let result = match input {
+            Input::Mmap(mmap) => tac_data(&mmap, &mut out),
+            Input::Vec(buf) => tac_data(&buf, &mut out),
+            Input::File(mut file) if maybe_pattern.is_none() => {
+                file_tac(&mut file, &mut out, filename, before, separator, CHUNK_SIZE)
+            }
+            Input::File(mut file) => {
+                // A regular expression is matched against the whole input
+                let mut buf = Vec::new();
+                match file.rewind().and_then(|()| file.read_to_end(&mut buf)) {
+                    Ok(_) => tac_data(&buf, &mut out),
+                    Err(e) => Err(TacError::ReadError(filename.clone(), e)),
+                }
+            }
         };
 
         // If there is any error in writing the output, terminate immediately.
-        if let Err(e) = result {
-            return Err(TacError::WriteError(e).into());
+        match result {
+            Err(e @ TacError::ReadError(..)) => show!(e),
+            Err(e) => return Err(e.into()),
+            Ok(()) => {}
         }
     }
     Ok(())
 }
 
+/// The contents of a file or of stdin.
+enum Input {
+    Mmap(Mmap),
+    Vec(Vec<u8>),
+    /// A file that cannot be memory-mapped, read in chunks by [`file_tac`].
+    File(File),
+}
+
+#[cfg(not(target_family = "wasm"))]
 fn try_mmap_stdin() -> Option<Mmap> {
     // SAFETY: If the file is truncated while we map it, SIGBUS will be raised
     // and our process will be terminated, thus preventing access of invalid memory.
     let mmap = unsafe { Mmap::map(&stdin()).ok()? };
     // On Windows, mmap on a pipe handle can "succeed" but return 0 bytes
     // (the file size of a pipe is reported as 0). When that happens, return
-    // None so we fall through to buffer_stdin() which reads the pipe properly.
+    // None so we fall through to buffer_stream() which reads the pipe properly.
     if mmap.is_empty() { None } else { Some(mmap) }
 }
 
-enum StdinData {
-    Mmap(Mmap),
-    Vec(Vec<u8>),
+/// Stdin cannot be memory-mapped on WASM, so it is always buffered.
+#[cfg(target_family = "wasm")]
+fn try_mmap_stdin() -> Option<Mmap> {
+    None
 }
 
-/// Copy stdin to a temp file, then memory-map it.
+/// Copy a stream such as a pipe to a temp file, then memory-map it, or read
+/// it back in chunks if it cannot be mapped.
 /// Falls back to reading directly into memory if temp file creation fails.
-fn buffer_stdin() -> std::io::Result<StdinData> {
+fn buffer_stream(mut stream: impl Read) -> std::io::Result<Input> {
     // Try to create a temp file (respects TMPDIR)
     if let Ok(mut tmp) = tempfile::tempfile() {
-        // Temp file created - copy stdin to it, then read back
-        copy(&mut stdin(), &mut tmp)?;
+        // Temp file created - copy the stream to it, then read back
+        copy(&mut stream, &mut tmp)?;
         // SAFETY: If the file is truncated while we map it, SIGBUS will be raised
         // and our process will be terminated, thus preventing access of invalid memory.
-        let mmap = unsafe { Mmap::map(&tmp)? };
-        Ok(StdinData::Mmap(mmap))
+        match unsafe { Mmap::map(&tmp) } {
+            Ok(mmap) => Ok(Input::Mmap(mmap)),
+            Err(_) => Ok(Input::File(tmp)),
+        }
     } else {
         // Fall back to reading directly into memory (e.g., bad TMPDIR)
         let mut buf = Vec::new();
-        stdin().read_to_end(&mut buf)?;
-        Ok(StdinData::Vec(buf))
+        stream.read_to_end(&mut buf)?;
+        Ok(Input::Vec(buf))
     }
 }
 
-fn try_mmap_path(path: &Path) -> Option<Mmap> {
-    let file = File::open(path).ok()?;
+/// Open a file for reading in reverse.
+///
+/// Regular files are memory-mapped, or read in chunks from the end if that
+/// fails. Other files, such as FIFOs or files in `/proc` that report a size
+/// of zero, are buffered like stdin.
+fn open_file(path: &Path) -> std::io::Result<Input> {
+    let file = File::open(path)?;
+    let meta = file.metadata()?;
+    if !meta.is_file() || meta.len() == 0 {
+        return buffer_stream(file);
+    }
 
     // SAFETY: If the file is truncated while we map it, SIGBUS will be raised
     // and our process will be terminated, thus preventing access of invalid memory.
-    let mmap = unsafe { Mmap::map(&file).ok()? };
-
-    Some(mmap)
+    match unsafe { Mmap::map(&file) } {
+        Ok(mmap) => Ok(Input::Mmap(mmap)),
+        Err(_) => Ok(Input::File(file)),
+    }
 }
 
 #[cfg(test)]
@@ -536,3 +628,68 @@ mod tests_hybrid_flavor {
         assert_eq!(translate_regex_flavor(r"\^"), r"\^");
     }
 }
+
+#[cfg(test)]
+mod tests_file_tac {
+    use super::{buffer_tac, file_tac};
+    use std::ffi::OsString;
+    use std::io::Cursor;
+
+    /// Check that reading in chunks of every size gives the same output as
+    /// reading everything at once.
+    fn check(data: &[u8], separator: &str) {
+        for before in [false, true] {
+            let mut expected = Vec::new();
+            buffer_tac(data, &mut expected, before, separator).unwrap();
+            for chunk_size in 1..=data.len() + 1 {
+                let mut out = Vec::new();
+                file_tac(
+                    &mut Cursor::new(data),
+                    &mut out,
+                    &OsString::from("test"),
+                    before,
+                    separator,
+                    chunk_size,
+                )
+                .unwrap();
+                assert_eq!(
+                    out, expected,
+                    "chunk size {chunk_size}, before {before}, data {data:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lines() {
+        check(b"", "\n");
+        check(b"\n", "\n");
+        check(b"a", "\n");
+        check(b"a\nbb\n\nccc\n", "\n");
+        check(b"\na\nbb\nccc", "\n");
+    }
+
+    #[test]
+    fn test_multi_char_separator() {
+        check(b"a<>b<>c<>", "<>");
+        check(b"<>a<>b<><>c", "<>");
+        check(b"a<><b<>>c", "<>");
+    }
+
+    #[test]
+    fn test_overlapping_separator() {
+        check(b"aaaaaaa", "aa");
+        check(b"baaabaaaab", "aa");
+        check(b"abababa", "aba");
+    }
+
+    #[test]
+    fn test_long_lines() {
+        let mut data = vec![b'x'; 100];
+        data.push(b'\n');
+        data.extend_from_slice(b"y\nzz");
+        data.extend(std::iter::repeat_n(b'w', 50));
+        check(&data, "\n");
+        check(&data, "zw");
+    }
+}
@@ -9,7 +9,7 @@ tail-help-bytes = Number of bytes to print
 tail-help-debug = indicate which --follow implementation is used
 tail-help-follow = Print the file as it grows
 tail-help-lines = Number of lines to print
-tail-help-pid = With -f, terminate after process ID, PID dies
+tail-help-pid = With -f, terminate after process ID, PID dies; can be repeated to wait for several processes
 tail-help-quiet = Never output headers giving file names
 tail-help-sleep-interval = Number of seconds to sleep between polling the file when running with -f
 tail-help-max-unchanged-stats = Reopen a FILE which has not changed size after N (default 5) iterations to see if it has been unlinked or renamed (this is the usual case of rotated log files); This option is meaningful only when polling (i.e., with --use-polling) and when --follow=name
@@ -25,6 +25,7 @@ tail-help-polling-windows = Disable 'ReadDirectoryChanges' support and use polli
 tail-error-cannot-follow-stdin-by-name = cannot follow { $stdin } by name
 tail-error-cannot-open-no-such-file = cannot open '{ $file }' for reading: { $error }
 tail-error-reading-file = error reading '{ $file }': { $error }
+tail-error-writing-stdout = error writing 'standard output': { $error }
 tail-error-cannot-follow-file-type = { $file }: cannot follow end of this type of file{ $msg }
 tail-error-cannot-open-for-reading = cannot open '{ $file }' for reading
 tail-error-cannot-fstat = cannot fstat { $file }: { $error }
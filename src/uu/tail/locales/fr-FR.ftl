@@ -8,7 +8,7 @@ tail-usage = tail [DRAPEAU]... [FICHIER]...
 tail-help-bytes = Nombre d'octets à afficher
 tail-help-follow = Afficher le fichier au fur et à mesure de sa croissance
 tail-help-lines = Nombre de lignes à afficher
-tail-help-pid = Avec -f, terminer après que l'ID de processus, PID meure
+tail-help-pid = Avec -f, terminer après que l'ID de processus, PID meure ; peut être répété pour attendre plusieurs processus
 tail-help-quiet = Ne jamais afficher d'en-têtes donnant les noms de fichiers
 tail-help-sleep-interval = Nombre de secondes à attendre entre les sondages du fichier lors de l'exécution avec -f
 tail-help-max-unchanged-stats = Rouvrir un FICHIER qui n'a pas changé de taille après N (par défaut 5) itérations pour voir s'il a été supprimé ou renommé (c'est le cas habituel des fichiers journaux pivotés) ; Cette option n'a de sens que lors du sondage (c'est-à-dire avec --use-polling) et quand --follow=name
@@ -24,6 +24,7 @@ tail-help-polling-windows = Désactiver le support 'ReadDirectoryChanges' et uti
 tail-error-cannot-follow-stdin-by-name = impossible de suivre { $stdin } par nom
 tail-error-cannot-open-no-such-file = impossible d'ouvrir '{ $file }' en lecture : { $error }
 tail-error-reading-file = erreur de lecture de '{ $file }' : { $error }
+tail-error-writing-stdout = erreur lors de l'écriture sur 'sortie standard' : { $error }
 tail-error-cannot-follow-file-type = { $file } : impossible de suivre la fin de ce type de fichier{ $msg }
 tail-error-cannot-open-for-reading = impossible d'ouvrir '{ $file }' en lecture
 tail-error-cannot-fstat = impossible de faire fstat { $file } : { $error }
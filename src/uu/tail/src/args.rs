@@ -12,6 +12,8 @@ use same_file::Handle;
 use std::ffi::OsString;
 use std::io::IsTerminal;
 use std::time::Duration;
+use uucore::clap_localization::clap_error_to_uerror;
+use uucore::display::HeaderPrinter;
 use uucore::error::{UResult, USimpleError, UUsageError};
 use uucore::parser::parse_signed_num::{SignPrefix, parse_signed_num_max};
 use uucore::parser::parse_size::ParseSizeError;
@@ -134,7 +136,8 @@ pub struct Settings {
     pub follow: Option<FollowMode>,
     pub max_unchanged_stats: u32,
     pub mode: FilterMode,
-    pub pid: platform::Pid,
+    /// The processes of `--pid`, after whose death `--follow` ends
+    pub pids: Vec<platform::Pid>,
     pub retry: bool,
     pub sleep_sec: Duration,
     pub use_polling: bool,
@@ -152,7 +155,7 @@ impl Default for Settings {
             sleep_sec: Duration::from_secs_f32(1.0),
             follow: Option::default(),
             mode: FilterMode::default(),
-            pid: Default::default(),
+            pids: Vec::new(),
             retry: Default::default(),
             use_polling: Default::default(),
             verbose: Default::default(),
@@ -251,7 +254,7 @@ impl Settings {
             }
         }
 
-        if let Some(pid_str) = matches.get_one::<String>(options::PID) {
+        for pid_str in matches.get_many::<String>(options::PID).unwrap_or_default() {
             match pid_str.parse() {
                 Ok(pid) => {
                     // NOTE: on unix platform::Pid is i32, on windows platform::Pid is u32
@@ -264,7 +267,9 @@ impl Settings {
                         ));
                     }
 
-                    settings.pid = pid;
+                    if pid != 0 {
+                        settings.pids.push(pid);
+                    }
                 }
                 Err(e) => {
                     return Err(USimpleError::new(
@@ -279,9 +284,12 @@ impl Settings {
             .get_many::<OsString>(options::ARG_FILES)
             .map_or_else(|| vec![Input::default()], |v| v.map(Input::from).collect());
 
-        settings.verbose = (matches.get_flag(options::verbosity::VERBOSE)
-            || settings.inputs.len() > 1)
-            && !matches.get_flag(options::verbosity::QUIET);
+        settings.verbose = HeaderPrinter::for_files(
+            settings.inputs.len(),
+            matches.get_flag(options::verbosity::QUIET),
+            matches.get_flag(options::verbosity::VERBOSE),
+        )
+        .enabled();
 
         Ok(settings)
     }
@@ -309,10 +317,10 @@ impl Settings {
             }
         }
 
-        if self.pid != 0 {
+        if let Some(&pid) = self.pids.first() {
             if self.follow.is_none() {
                 show_warning!("{}", translate!("tail-warning-pid-ignored"));
-            } else if !platform::supports_pid_checks(self.pid) {
+            } else if !platform::supports_pid_checks(pid) {
                 show_warning!("{}", translate!("tail-warning-pid-not-supported"));
             }
         }
@@ -322,7 +330,7 @@ impl Settings {
         // as `tty` (but no otherwise blocking stdin), then we print a warning that `--follow`
         // cannot be applied under these circumstances and is therefore ineffective.
         if self.follow.is_some() && self.has_stdin() {
-            let blocking_stdin = self.pid == 0
+            let blocking_stdin = self.pids.is_empty()
                 && self.follow == Some(FollowMode::Descriptor)
                 && self.num_inputs() == 1
                 && Handle::stdin().is_ok_and(|handle| {
@@ -402,17 +410,19 @@ fn parse_num(src: &str) -> Result<Signum, ParseSizeError> {
 pub fn parse_args(args: impl uucore::Args) -> UResult<Settings> {
     let args_vec: Vec<OsString> = args.collect();
     let clap_args = uu_app().try_get_matches_from(args_vec.clone());
+    // clap errors are only reported once it is clear that the obsolete syntax doesn't apply.
     let clap_result = match clap_args {
         Ok(matches) => Ok(Settings::from(&matches)?),
-        Err(err) => Err(err.into()),
+        Err(err) => Err(err),
     };
+    let report = |err| clap_error_to_uerror(err, 1);
 
     // clap isn't able to handle obsolete syntax.
     // therefore, we want to check further for obsolete arguments.
     // argv[0] is always present, argv[1] might be obsolete arguments
     // argv[2] might contain an input file, argv[3] isn't allowed in obsolete mode
     if args_vec.len() != 2 && args_vec.len() != 3 {
-        return clap_result;
+        return clap_result.map_err(report);
     }
 
     // At this point, there are a few possible cases:
@@ -432,11 +442,11 @@ pub fn parse_args(args: impl uucore::Args) -> UResult<Settings> {
     // checking whether clap succeeded or the first argument starts with '+'.
     let possible_obsolete_args = &args_vec[1];
     if clap_result.is_ok() && !possible_obsolete_args.to_string_lossy().starts_with('+') {
-        return clap_result;
+        return clap_result.map_err(report);
     }
     match parse_obsolete(possible_obsolete_args, args_vec.get(2))? {
         Some(settings) => Ok(settings),
-        None => clap_result,
+        None => clap_result.map_err(report),
     }
 }
 
@@ -488,7 +498,7 @@ pub fn uu_app() -> Command {
                 .long(options::PID)
                 .value_name("PID")
                 .help(translate!("tail-help-pid"))
-                .overrides_with(options::PID),
+                .action(ArgAction::Append),
         )
         .arg(
             Arg::new(options::verbosity::QUIET)
@@ -11,9 +11,7 @@
 // spell-checker:ignore (ToDO) filehandle BUFSIZ
 
 use std::collections::VecDeque;
-use std::fs::File;
-use std::io::{BufRead, Read, Seek, SeekFrom, Write};
-use uucore::error::UResult;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
 
 /// When reading files in reverse in `bounded_tail`, this is the size of each
 /// block read at a time.
@@ -26,14 +24,14 @@ pub const BLOCK_SIZE: u64 = 1 << 16;
 /// value here is set to this value, too.
 pub const BUFFER_SIZE: usize = 8192;
 
-/// An iterator over a file in non-overlapping chunks from the end of the file.
+/// An iterator over a seekable file in non-overlapping chunks from the end of the file.
 ///
 /// Each chunk is a [`Vec`]<[`u8`]> of size [`BLOCK_SIZE`] (except
 /// possibly the last chunk, which might be smaller). Each call to
 /// [`ReverseChunks::next`] will seek backwards through the given file.
-pub struct ReverseChunks<'a> {
+pub struct ReverseChunks<'a, R: Read + Seek> {
     /// The file to iterate over, by blocks, from the end to the beginning.
-    file: &'a File,
+    file: &'a mut R,
 
     /// The total number of bytes in the file.
     size: u64,
@@ -45,8 +43,8 @@ pub struct ReverseChunks<'a> {
     block_idx: usize,
 }
 
-impl<'a> ReverseChunks<'a> {
-    pub fn new(file: &'a mut File) -> Self {
+impl<'a, R: Read + Seek> ReverseChunks<'a, R> {
+    pub fn new(file: &'a mut R) -> Self {
         let current = if cfg!(unix) {
             file.stream_position().unwrap()
         } else {
@@ -64,7 +62,7 @@ impl<'a> ReverseChunks<'a> {
     }
 }
 
-impl Iterator for ReverseChunks<'_> {
+impl<R: Read + Seek> Iterator for ReverseChunks<'_, R> {
     type Item = Vec<u8>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -208,9 +206,9 @@ impl BytesChunk {
 
     /// Fills `self.buffer` with maximal [`BUFFER_SIZE`] number of bytes, draining the reader by
     /// that number of bytes. If EOF is reached (so 0 bytes are read), it returns
-    /// [`UResult<None>`]; otherwise, it returns [`UResult<Some(bytes)>`], where bytes is the
+    /// [`io::Result<None>`]; otherwise, it returns [`io::Result<Some(bytes)>`], where bytes is the
     /// number of bytes read from the source.
-    pub fn fill(&mut self, filehandle: &mut impl BufRead) -> UResult<Option<usize>> {
+    pub fn fill(&mut self, filehandle: &mut impl BufRead) -> io::Result<Option<usize>> {
         let num_bytes = filehandle.read(&mut self.buffer)?;
         self.bytes = num_bytes;
         if num_bytes == 0 {
@@ -285,7 +283,7 @@ impl BytesChunkBuffer {
     /// let mut chunks = BytesChunkBuffer::new(num_print);
     /// chunks.fill(&mut reader).unwrap();
     /// ```
-    pub fn fill(&mut self, reader: &mut impl BufRead) -> UResult<()> {
+    pub fn fill(&mut self, reader: &mut impl BufRead) -> io::Result<()> {
         let mut chunk = Box::new(BytesChunk::new());
 
         // fill chunks with all bytes from reader and reuse already instantiated chunks if possible
@@ -319,7 +317,7 @@ impl BytesChunkBuffer {
         Ok(())
     }
 
-    pub fn print(&self, writer: &mut impl Write) -> UResult<()> {
+    pub fn print(&self, writer: &mut impl Write) -> io::Result<()> {
         for chunk in &self.chunks {
             writer.write_all(chunk.get_buffer())?;
         }
@@ -458,7 +456,7 @@ impl LinesChunk {
     /// that number of bytes. This function works like the [`BytesChunk::fill`] function besides
     /// that this function also counts and stores the number of lines encountered while reading from
     /// the `filehandle`.
-    pub fn fill(&mut self, filehandle: &mut impl BufRead) -> UResult<Option<usize>> {
+    pub fn fill(&mut self, filehandle: &mut impl BufRead) -> io::Result<Option<usize>> {
         match self.chunk.fill(filehandle)? {
             None => {
                 self.lines = 0;
@@ -514,7 +512,7 @@ impl LinesChunk {
     ///
     /// * `writer`: must implement [`Write`]
     /// * `offset`: An offset in number of lines.
-    pub fn write_lines(&self, writer: &mut impl Write, offset: usize) -> UResult<()> {
+    pub fn write_lines(&self, writer: &mut impl Write, offset: usize) -> io::Result<()> {
         self.write_bytes(writer, self.calculate_bytes_offset_from(offset))
     }
 
@@ -524,7 +522,7 @@ impl LinesChunk {
     ///
     /// * `writer`: must implement [`Write`]
     /// * `offset`: An offset in number of bytes.
-    pub fn write_bytes(&self, writer: &mut impl Write, offset: usize) -> UResult<()> {
+    pub fn write_bytes(&self, writer: &mut impl Write, offset: usize) -> io::Result<()> {
         writer.write_all(self.get_buffer_with(offset))?;
         Ok(())
     }
@@ -562,7 +560,7 @@ impl LinesChunkBuffer {
     /// in sum exactly `self.num_print` lines stored in all chunks. The method returns an iterator
     /// over these chunks. If there are no chunks, for example because the piped stdin contained no
     /// lines, or `num_print = 0` then `iterator.next` will return None.
-    pub fn fill(&mut self, reader: &mut impl BufRead) -> UResult<()> {
+    pub fn fill(&mut self, reader: &mut impl BufRead) -> io::Result<()> {
         let mut chunk = Box::new(LinesChunk::new(self.delimiter));
 
         while chunk.fill(reader)?.is_some() {
@@ -617,7 +615,7 @@ impl LinesChunkBuffer {
         Ok(())
     }
 
-    pub fn write(&self, mut writer: impl Write) -> UResult<()> {
+    pub fn write(&self, mut writer: impl Write) -> io::Result<()> {
         for chunk in &self.chunks {
             chunk.write_bytes(&mut writer, 0)?;
         }
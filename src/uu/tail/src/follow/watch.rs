@@ -15,8 +15,9 @@ use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, channel};
 use uucore::display::Quotable;
 use uucore::error::{UResult, USimpleError, set_exit_code};
+use uucore::fs::resolve_relative;
 #[cfg(target_os = "linux")]
-use uucore::signals::ensure_stdout_not_broken;
+use uucore::signals::{ensure_stdout_not_broken, wait_stdout_broken};
 use uucore::translate;
 
 use uucore::show_error;
@@ -99,7 +100,12 @@ pub struct Observer {
     pub orphans: Vec<PathBuf>,
     pub files: FileHandling,
 
-    pub pid: platform::Pid,
+    pub pids: Vec<platform::Pid>,
+
+    /// Files tailed through the VFS hooks, which `platform::follow` polls
+    /// instead of watching them.
+    #[cfg(target_family = "wasm")]
+    pub vfs_files: Vec<platform::VfsFile>,
 }
 
 impl Observer {
@@ -108,12 +114,11 @@ impl Observer {
         follow: Option<FollowMode>,
         use_polling: bool,
         files: FileHandling,
-        pid: platform::Pid,
+        pids: &[platform::Pid],
     ) -> Self {
-        let pid = if platform::supports_pid_checks(pid) {
-            pid
-        } else {
-            0
+        let pids = match pids.first() {
+            Some(&pid) if platform::supports_pid_checks(pid) => pids.to_vec(),
+            _ => Vec::new(),
         };
 
         Self {
@@ -123,7 +128,9 @@ impl Observer {
             watcher_rx: None,
             orphans: Vec::new(),
             files,
-            pid,
+            pids,
+            #[cfg(target_family = "wasm")]
+            vfs_files: Vec::new(),
         }
     }
 
@@ -133,7 +140,7 @@ impl Observer {
             settings.follow,
             settings.use_polling,
             FileHandling::from(settings),
-            settings.pid,
+            &settings.pids,
         )
     }
 
@@ -145,11 +152,7 @@ impl Observer {
         update_last: bool,
     ) -> UResult<()> {
         if self.follow.is_some() {
-            let path = if path.is_relative() {
-                std::env::current_dir()?.join(path)
-            } else {
-                path.to_owned()
-            };
+            let path = resolve_relative(path)?;
             let metadata = path.metadata().ok();
             self.files.insert(
                 &path,
@@ -168,18 +171,37 @@ impl Observer {
         update_last: bool,
     ) -> UResult<()> {
         if self.retry && self.follow.is_some() {
+            #[cfg(target_family = "wasm")]
+            self.add_vfs_path(path, display_name);
             return self.add_path(path, display_name, None, update_last);
         }
 
         Ok(())
     }
 
+    #[cfg(target_family = "wasm")]
+    pub fn add_vfs_path(&mut self, path: &Path, display_name: &str) {
+        if self.follow.is_some() {
+            self.vfs_files
+                .push(platform::VfsFile::new(path, display_name));
+        }
+    }
+
     pub fn start(&mut self, settings: &Settings) -> UResult<()> {
         if settings.follow.is_none() {
             return Ok(());
         }
 
+        if cfg!(target_family = "wasm") {
+            // Without threads there is no way to run a `PollWatcher`;
+            // `platform::follow` polls the VFS itself.
+            self.use_polling = true;
+            return Ok(());
+        }
+
         let (tx, rx) = channel();
+        #[cfg(target_os = "linux")]
+        monitor_stdout(tx.clone());
 
         /*
         Watcher is implemented per platform using the best implementation available on that
@@ -265,10 +287,7 @@ impl Observer {
                         if !path.is_file() {
                             continue;
                         }
-                        let mut path = path.clone();
-                        if path.is_relative() {
-                            path = std::env::current_dir()?.join(path);
-                        }
+                        let path = resolve_relative(path)?;
 
                         if path.is_tailable() {
                             // Add existing regular files to `Watcher` (InotifyWatcher).
@@ -312,6 +331,15 @@ impl Observer {
                 if let Ok(new_md) = event_path.metadata() {
                     let is_tailable = new_md.is_tailable();
                     let pd = self.files.get(event_path);
+                    // Following a descriptor, a file that has since taken the name
+                    // of the one we have open is none of our business, like in GNU.
+                    // The metadata is gone if the name went away after we last read.
+                    if self.follow_descriptor()
+                        && pd.reader.is_some()
+                        && pd.metadata.as_ref().is_none_or(|md| !md.file_id_eq(&new_md))
+                    {
+                        return Ok(paths);
+                    }
                     if let Some(old_md) = &pd.metadata {
                         if is_tailable {
                             // We resume tracking from the start of the file,
@@ -436,39 +464,30 @@ impl Observer {
                     */
                 }
             }
-            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
-                /*
-                NOTE: For `tail -f a`, keep tracking additions to b after `mv a b`
-                (gnu/tests/tail-2/descriptor-vs-rename.sh)
-                NOTE: The File/BufReader doesn't need to be updated.
-                However, we need to update our `files.map`.
-                This can only be done for inotify, because this EventKind does not
-                trigger for the PollWatcher.
-                BUG: As a result, there's a bug if polling is used:
-                $ tail -f file_a ---disable-inotify
-                $ mv file_a file_b
-                $ echo A >> file_b
-                $ echo A >> file_a
-                The last append to file_a is printed, however this shouldn't be because
-                after the "mv" tail should only follow "file_b".
-                TODO: [2022-05; jhscheer] add test for this bug
-                */
-
-                if self.follow_descriptor() {
-                    let new_path = event.paths.last().unwrap();
-                    paths.push(new_path.clone());
-
-                    let new_data = PathData::from_other_with_path(self.files.remove(event_path), new_path);
-                    self.files.insert(
-                        new_path,
-                        new_data,
-                        self.files.get_last().unwrap() == event_path
-                    );
-
-                    // Unwatch old path and watch new path
-                    let _ = self.watcher_rx.as_mut().unwrap().unwatch(event_path);
-                    self.watcher_rx.as_mut().unwrap().watch_with_parent(new_path)?;
-                }
+            /*
+            NOTE: For `tail -f a`, keep tracking additions to b after `mv a b`
+            (gnu/tests/tail-2/descriptor-vs-rename.sh)
+            NOTE: The File/BufReader doesn't need to be updated.
+            However, we need to update our `files.map`.
+            This can only be done for inotify, because this EventKind does not
+            trigger for the PollWatcher. When polling, the reader keeps being read
+            under the old name, and a new "file_a" is ignored as any other file
+            taking the name of the one we follow.
+            */
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if self.follow_descriptor() => {
+                let new_path = event.paths.last().unwrap();
+                paths.push(new_path.clone());
+
+                let new_data = PathData::from_other_with_path(self.files.remove(event_path), new_path);
+                self.files.insert(
+                    new_path,
+                    new_data,
+                    self.files.get_last().unwrap() == event_path
+                );
+
+                // Unwatch old path and watch new path
+                let _ = self.watcher_rx.as_mut().unwrap().unwatch(event_path);
+                self.watcher_rx.as_mut().unwrap().watch_with_parent(new_path)?;
             }
             _ => {}
         }
@@ -478,11 +497,17 @@ impl Observer {
 
 #[allow(clippy::cognitive_complexity)]
 pub fn follow(mut observer: Observer, settings: &Settings) -> UResult<()> {
+    // `Observer::start` does not create a watcher on WASM.
+    #[cfg(target_family = "wasm")]
+    if observer.watcher_rx.is_none() {
+        return platform::follow(std::mem::take(&mut observer.vfs_files), settings);
+    }
+
     if observer.files.no_files_remaining(settings) && !observer.files.only_stdin_remaining() {
         return Err(USimpleError::new(1, translate!("tail-no-files-remaining")));
     }
 
-    let mut process = platform::ProcessChecker::new(observer.pid);
+    let mut process = platform::ProcessChecker::new(&observer.pids);
 
     let mut timeout_counter = 0;
 
@@ -490,10 +515,14 @@ pub fn follow(mut observer: Observer, settings: &Settings) -> UResult<()> {
     loop {
         let mut _read_some = false;
 
+        // A host running us in-process, e.g. on WASM where there are no
+        // signals, stops us through cancellation instead.
+        uucore::cancel::check()?;
+
         // If `--pid=p`, tail checks whether process p
         // is alive at least every `--sleep-interval=N` seconds
-        if settings.follow.is_some() && observer.pid != 0 && process.is_dead() {
-            // p is dead, tail will also terminate
+        if settings.follow.is_some() && !observer.pids.is_empty() && process.is_dead() {
+            // p and any other `--pid` are dead, tail will also terminate
             break;
         }
 
@@ -594,6 +623,11 @@ pub fn follow(mut observer: Observer, settings: &Settings) -> UResult<()> {
                     }
                 }
             }
+            #[cfg(target_os = "linux")]
+            Ok(Err(notify::Error {
+                kind: notify::ErrorKind::Io(ref e),
+                ..
+            })) if e.kind() == std::io::ErrorKind::BrokenPipe => return die_pipe(),
             Ok(Err(notify::Error {
                 kind: notify::ErrorKind::MaxFilesWatch,
                 ..
@@ -614,7 +648,7 @@ pub fn follow(mut observer: Observer, settings: &Settings) -> UResult<()> {
                 // Check if stdout pipe is still open
                 #[cfg(target_os = "linux")]
                 if let Ok(false) = ensure_stdout_not_broken() {
-                    return Ok(());
+                    return die_pipe();
                 }
             }
             Err(e) => {
@@ -654,3 +688,24 @@ pub fn follow(mut observer: Observer, settings: &Settings) -> UResult<()> {
 
     Ok(())
 }
+
+/// Wake up the follow loop as soon as the reader of a pipe on stdout goes
+/// away, rather than at the next write, like GNU. Nothing may be written for
+/// a long time while waiting for files to change.
+#[cfg(target_os = "linux")]
+fn monitor_stdout(tx: mpsc::Sender<Result<notify::Event, notify::Error>>) {
+    std::thread::spawn(move || {
+        if let Ok(true) = wait_stdout_broken() {
+            let broken = std::io::Error::from(std::io::ErrorKind::BrokenPipe);
+            let _ = tx.send(Err(notify::Error::io(broken)));
+        }
+    });
+}
+
+/// Exit as a write to the broken pipe on stdout would have: by SIGPIPE, or
+/// with status 1 if it is ignored.
+#[cfg(target_os = "linux")]
+fn die_pipe() -> UResult<()> {
+    let _ = nix::sys::signal::raise(nix::sys::signal::Signal::SIGPIPE);
+    Err(1.into())
+}
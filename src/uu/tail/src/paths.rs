@@ -112,34 +112,6 @@ impl Default for Input {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy)]
-pub struct HeaderPrinter {
-    verbose: bool,
-    first_header: bool,
-}
-
-impl HeaderPrinter {
-    pub fn new(verbose: bool, first_header: bool) -> Self {
-        Self {
-            verbose,
-            first_header,
-        }
-    }
-
-    pub fn print_input(&mut self, input: &Input) {
-        self.print(input.display_name.as_str());
-    }
-
-    pub fn print(&mut self, string: &str) {
-        if self.verbose {
-            println!(
-                "{}==> {string} <==",
-                if self.first_header { "" } else { "\n" },
-            );
-            self.first_header = false;
-        }
-    }
-}
 pub trait FileExtTail {
     #[allow(clippy::wrong_self_convention)]
     fn is_seekable(&mut self, current_offset: u64) -> bool;
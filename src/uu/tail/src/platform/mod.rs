@@ -15,7 +15,7 @@ pub use self::unix::{
 pub use self::windows::{Pid, ProcessChecker, supports_pid_checks};
 
 #[cfg(target_family = "wasm")]
-pub use self::wasm::{Pid, ProcessChecker, supports_pid_checks};
+pub use self::wasm::{Pid, ProcessChecker, VfsFile, follow, supports_pid_checks};
 
 #[cfg(unix)]
 mod unix;
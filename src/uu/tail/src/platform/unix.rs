@@ -11,18 +11,22 @@ use std::io::Error;
 pub type Pid = libc::pid_t;
 
 pub struct ProcessChecker {
-    pid: Pid,
+    pids: Vec<Pid>,
 }
 
 impl ProcessChecker {
-    pub fn new(process_id: Pid) -> Self {
-        Self { pid: process_id }
+    pub fn new(process_ids: &[Pid]) -> Self {
+        Self {
+            pids: process_ids.to_vec(),
+        }
     }
 
-    // Borrowing mutably to be aligned with Windows implementation
+    /// Whether all the processes are dead, forgetting those that are
     #[allow(clippy::wrong_self_convention)]
     pub fn is_dead(&mut self) -> bool {
-        unsafe { libc::kill(self.pid, 0) != 0 && get_errno() != libc::EPERM }
+        self.pids
+            .retain(|&pid| unsafe { libc::kill(pid, 0) == 0 || get_errno() == libc::EPERM });
+        self.pids.is_empty()
     }
 }
 
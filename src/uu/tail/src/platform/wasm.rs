@@ -3,18 +3,32 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-// WASM platform stubs for tail's process-checking functionality.
-// Process monitoring is not available on WASM.
+// WASM platform support for tail.
+// Process monitoring is not available on WASM: WASI (including preview 2)
+// has no notion of other processes, so there is no pid to watch.
+// There is no inotify either, so `--follow` polls the size of each file
+// through the VFS hooks instead of using `notify`.
+
+use crate::args::Settings;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use uucore::cancel::{INTERRUPTED_EXIT_CODE, is_cancelled};
+use uucore::display::{HeaderPrinter, Quotable};
+use uucore::error::{ExitCode, FromIo, UResult, USimpleError};
+use uucore::wasm_io::{self, WasmMetadata};
+use uucore::{show_error, translate};
 
 pub type Pid = u32;
 
 pub struct ProcessChecker {
-    _pid: Pid,
+    _pids: Vec<Pid>,
 }
 
 impl ProcessChecker {
-    pub fn new(process_id: Pid) -> Self {
-        Self { _pid: process_id }
+    pub fn new(process_ids: &[Pid]) -> Self {
+        Self {
+            _pids: process_ids.to_vec(),
+        }
     }
 
     #[allow(clippy::wrong_self_convention)]
@@ -31,3 +45,109 @@ impl Drop for ProcessChecker {
 pub fn supports_pid_checks(_pid: Pid) -> bool {
     false
 }
+
+/// A file followed through the VFS hooks, along with how much of it has
+/// been printed so far.
+pub struct VfsFile {
+    path: PathBuf,
+    display_name: String,
+    offset: u64,
+    missing: bool,
+}
+
+impl VfsFile {
+    /// Start following `path` after its current end, i.e. right after the
+    /// initial tail. A file that does not exist (yet) is printed from the
+    /// start once it appears.
+    pub fn new(path: &Path, display_name: &str) -> Self {
+        let metadata = wasm_io::metadata(path).ok().filter(WasmMetadata::is_file);
+        Self {
+            path: path.to_owned(),
+            display_name: display_name.to_owned(),
+            offset: metadata.as_ref().map_or(0, WasmMetadata::len),
+            missing: metadata.is_none(),
+        }
+    }
+
+    /// Copy whatever was appended since the last poll to `writer`, returning
+    /// the number of bytes copied.
+    fn copy_appended(&mut self, len: u64, writer: &mut impl Write) -> io::Result<u64> {
+        let copied = if wasm_io::can_seek_files() {
+            let mut reader = wasm_io::open_file_seekable(&self.path)?;
+            reader.seek(SeekFrom::Start(self.offset))?;
+            io::copy(&mut reader.take(len - self.offset), writer)?
+        } else {
+            let mut reader = wasm_io::open_file(&self.path)?;
+            io::copy(&mut (&mut reader).take(self.offset), &mut io::sink())?;
+            io::copy(&mut reader.take(len - self.offset), writer)?
+        };
+        self.offset += copied;
+        Ok(copied)
+    }
+}
+
+/// Follow `files` until the host cancels us, printing appended data to the
+/// overridden stdout.
+///
+/// Each iteration re-stats every file through the metadata hook and waits
+/// for the next one with [`wasm_io::tick`], so the host decides how often we
+/// poll. A file that shrank is considered truncated and printed from the
+/// start, like GNU tail does. Renames cannot be detected this way, so
+/// `--follow=name` and `--follow=descriptor` behave the same.
+pub fn follow(mut files: Vec<VfsFile>, settings: &Settings) -> UResult<()> {
+    if files.is_empty() {
+        return Err(USimpleError::new(1, translate!("tail-no-files-remaining")));
+    }
+
+    let mut header_printer = HeaderPrinter::new(settings.verbose, false);
+    // The last file was printed last by the initial tail.
+    let mut last = files.len() - 1;
+    let mut stdout = wasm_io::stdout();
+
+    loop {
+        if is_cancelled() {
+            stdout.flush()?;
+            return Err(ExitCode::new(INTERRUPTED_EXIT_CODE));
+        }
+
+        for (index, file) in files.iter_mut().enumerate() {
+            let Some(metadata) = wasm_io::metadata(&file.path)
+                .ok()
+                .filter(WasmMetadata::is_file)
+            else {
+                // The file may come back; print all of it when it does.
+                file.missing = true;
+                continue;
+            };
+            let len = metadata.len();
+            if file.missing {
+                show_error!(
+                    "{}",
+                    translate!("tail-status-has-appeared-following-new-file", "file" => file.display_name.quote())
+                );
+                file.missing = false;
+                file.offset = 0;
+            } else if len < file.offset {
+                show_error!(
+                    "{}",
+                    translate!("tail-status-file-truncated", "file" => file.display_name.clone())
+                );
+                file.offset = 0;
+            }
+            if len == file.offset {
+                continue;
+            }
+
+            if index != last {
+                header_printer.print(&file.display_name)?;
+                last = index;
+            }
+            file.copy_appended(len, &mut stdout).map_err_context(|| {
+                translate!("tail-error-cannot-open-for-reading", "file" => file.display_name.clone())
+            })?;
+        }
+        stdout.flush()?;
+
+        wasm_io::tick(settings.sleep_sec);
+    }
+}
@@ -12,38 +12,44 @@ use windows_sys::core::BOOL;
 pub type Pid = u32;
 
 pub struct ProcessChecker {
-    dead: bool,
-    handle: HANDLE,
+    /// The handles of the processes still alive; those that cannot be
+    /// opened are taken for dead
+    handles: Vec<HANDLE>,
 }
 
 impl ProcessChecker {
-    pub fn new(process_id: Pid) -> Self {
+    pub fn new(process_ids: &[Pid]) -> Self {
         #[allow(non_snake_case)]
         let FALSE: BOOL = 0;
-        let h = unsafe { OpenProcess(PROCESS_SYNCHRONIZE, FALSE, process_id) };
-        Self {
-            dead: h.is_null(),
-            handle: h,
-        }
+        let handles = process_ids
+            .iter()
+            .map(|&process_id| unsafe { OpenProcess(PROCESS_SYNCHRONIZE, FALSE, process_id) })
+            .filter(|h| !h.is_null())
+            .collect();
+        Self { handles }
     }
 
+    /// Whether all the processes are dead, closing the handles of those that are
     #[allow(clippy::wrong_self_convention)]
     pub fn is_dead(&mut self) -> bool {
-        if !self.dead {
-            self.dead = unsafe {
-                let status = WaitForSingleObject(self.handle, 0);
-                status == WAIT_OBJECT_0 || status == WAIT_FAILED
+        self.handles.retain(|&handle| unsafe {
+            let status = WaitForSingleObject(handle, 0);
+            let dead = status == WAIT_OBJECT_0 || status == WAIT_FAILED;
+            if dead {
+                CloseHandle(handle);
             }
-        }
-
-        self.dead
+            !dead
+        });
+        self.handles.is_empty()
     }
 }
 
 impl Drop for ProcessChecker {
     fn drop(&mut self) {
-        unsafe {
-            CloseHandle(self.handle);
+        for &handle in &self.handles {
+            unsafe {
+                CloseHandle(handle);
+            }
         }
     }
 }
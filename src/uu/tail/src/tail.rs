@@ -26,19 +26,19 @@ use args::{FilterMode, Settings, Signum, parse_args};
 use chunks::ReverseChunks;
 use follow::Observer;
 use memchr::{memchr_iter, memrchr_iter};
-use paths::{FileExtTail, HeaderPrinter, Input, InputKind};
+use paths::{FileExtTail, Input, InputKind};
 use same_file::Handle;
-use std::cmp::Ordering;
 use std::fs::File;
 use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
 #[cfg(not(target_family = "wasm"))]
 use std::io::{stdin, stdout};
-#[cfg(target_family = "wasm")]
-use uucore::wasm_io::{stdin, stdout};
 use std::path::{Path, PathBuf};
-use uucore::display::Quotable;
-use uucore::error::{FromIo, UResult, USimpleError, set_exit_code};
+use uucore::display::{HeaderPrinter, Quotable};
+use uucore::error::{FromIo, UResult, USimpleError, set_exit_code, strip_errno};
+use uucore::io::{TagWriteErrors, is_write_error};
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{stdin, stdout};
 
 use uucore::{show, show_error};
 
@@ -105,7 +105,7 @@ fn uu_tail(settings: &Settings) -> UResult<()> {
         the input file is not a FIFO, pipe, or regular file, it is unspecified whether or
         not the -f option shall be ignored.
         */
-        if !settings.has_only_stdin() || settings.pid != 0 {
+        if !settings.has_only_stdin() || !settings.pids.is_empty() {
             follow::follow(observer, settings)?;
         }
     }
@@ -121,17 +121,25 @@ fn tail_file(
     observer: &mut Observer,
     offset: u64,
 ) -> UResult<()> {
-    if !path.exists() {
+    #[cfg(not(target_family = "wasm"))]
+    let (exists, is_dir) = (path.exists(), path.is_dir());
+    #[cfg(target_family = "wasm")]
+    let (exists, is_dir) = (
+        uucore::wasm_io::file_exists(path),
+        uucore::wasm_io::metadata(path).is_ok_and(|md| md.is_dir()),
+    );
+
+    if !exists {
         set_exit_code(1);
         show_error!(
             "{}",
             translate!("tail-error-cannot-open-no-such-file", "file" => input.display_name.clone(), "error" => translate!("tail-no-such-file-or-directory"))
         );
         observer.add_bad_path(path, input.display_name.as_str(), false)?;
-    } else if path.is_dir() {
+    } else if is_dir {
         set_exit_code(1);
 
-        header_printer.print_input(input);
+        header_printer.print(&input.display_name)?;
         let err_msg = translate!("tail-is-a-directory");
 
         show_error!(
@@ -154,9 +162,12 @@ fn tail_file(
             return Ok(());
         }
         observer.add_bad_path(path, input.display_name.as_str(), false)?;
+    } else if cfg!(target_family = "wasm") {
+        #[cfg(target_family = "wasm")]
+        tail_vfs_file(settings, header_printer, input, path, observer)?;
     } else {
         #[cfg(unix)]
-        let open_result = open_file(path, settings.pid != 0);
+        let open_result = open_file(path, !settings.pids.is_empty());
         #[cfg(not(unix))]
         let open_result = File::open(path);
 
@@ -164,19 +175,20 @@ fn tail_file(
             Ok(mut file) => {
                 let st = file.metadata()?;
                 let blksize_limit = uucore::fs::sane_blksize::sane_blksize_from_metadata(&st);
-                header_printer.print_input(input);
+                header_printer.print(&input.display_name)?;
                 let mut reader;
-                if !settings.presume_input_pipe
+                let result = if !settings.presume_input_pipe
                     && file.is_seekable(if input.is_stdin() { offset } else { 0 })
                     && (!st.is_file() || st.len() > blksize_limit)
                 {
-                    bounded_tail(&mut file, settings);
+                    let result = bounded_tail(&mut file, settings);
                     reader = BufReader::new(file);
+                    result
                 } else {
                     reader = BufReader::new(file);
-                    unbounded_tail(&mut reader, settings)?;
-                }
-                if input.is_tailable() {
+                    unbounded_tail(&mut reader, settings)
+                };
+                if check_read_result(result, input)? && input.is_tailable() {
                     observer.add_path(
                         path,
                         input.display_name.as_str(),
@@ -205,6 +217,35 @@ fn tail_file(
     Ok(())
 }
 
+/// Tails a file through the WASM VFS hooks, where `std::fs::File` is not
+/// available.
+///
+/// When the host can open files as seekable, the file is read from the end
+/// like a regular file; otherwise it is streamed. With `--follow`, the file
+/// is then polled by `platform::follow`.
+#[cfg(target_family = "wasm")]
+fn tail_vfs_file(
+    settings: &Settings,
+    header_printer: &mut HeaderPrinter,
+    input: &Input,
+    path: &Path,
+    observer: &mut Observer,
+) -> UResult<()> {
+    let context =
+        || translate!("tail-error-cannot-open-for-reading", "file" => input.display_name.clone());
+    if !settings.presume_input_pipe && uucore::wasm_io::can_seek_files() {
+        let mut file = uucore::wasm_io::open_file_seekable(path).map_err_context(context)?;
+        header_printer.print(&input.display_name)?;
+        check_read_result(bounded_tail(&mut file, settings), input)?;
+    } else {
+        let file = uucore::wasm_io::open_file(path).map_err_context(context)?;
+        header_printer.print(&input.display_name)?;
+        check_read_result(unbounded_tail(&mut BufReader::new(file), settings), input)?;
+    }
+    observer.add_vfs_path(path, input.display_name.as_str());
+    Ok(())
+}
+
 /// Opens a file, using non-blocking mode for FIFOs when `use_nonblock_for_fifo` is true.
 ///
 /// When opening a FIFO with `--pid`, we need to use O_NONBLOCK so that:
@@ -303,7 +344,7 @@ fn tail_stdin(
         )?;
     } else {
         // pipe
-        header_printer.print_input(input);
+        header_printer.print(&input.display_name)?;
         if paths::stdin_is_bad_fd() {
             set_exit_code(1);
             show_error!(
@@ -318,13 +359,34 @@ fn tail_stdin(
             }
         } else {
             let mut reader = BufReader::new(stdin());
-            unbounded_tail(&mut reader, settings)?;
+            check_read_result(unbounded_tail(&mut reader, settings), input)?;
         }
     }
 
     Ok(())
 }
 
+/// Report a failure to read `input` and go on with the next file, as GNU
+/// does, returning whether reading succeeded. Failing to write to standard
+/// output ends `tail`.
+fn check_read_result(result: io::Result<()>, input: &Input) -> UResult<bool> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(err) if is_write_error(&err) => Err(USimpleError::new(
+            1,
+            translate!("tail-error-writing-stdout", "error" => strip_errno(&err)),
+        )),
+        Err(err) => {
+            set_exit_code(1);
+            show_error!(
+                "{}",
+                translate!("tail-error-reading-file", "file" => input.display_name.clone(), "error" => strip_errno(&err))
+            );
+            Ok(false)
+        }
+    }
+}
+
 /// Find the index after the given number of instances of a given byte.
 ///
 /// This function reads through a given reader until `num_delimiters`
@@ -413,7 +475,7 @@ fn forwards_thru_file(
 /// Iterate over bytes in the file, in reverse, until we find the
 /// `num_delimiters` instance of `delimiter`. The `file` is left seek'd to the
 /// position just after that delimiter.
-fn backwards_thru_file(file: &mut File, num_delimiters: u64, delimiter: u8) {
+fn backwards_thru_file(file: &mut (impl Read + Seek), num_delimiters: u64, delimiter: u8) {
     if num_delimiters == 0 {
         file.seek(SeekFrom::End(0)).unwrap();
         return;
@@ -422,7 +484,8 @@ fn backwards_thru_file(file: &mut File, num_delimiters: u64, delimiter: u8) {
     // so far (reading from the end of the file toward the beginning).
     let mut counter = 0;
     let mut first_slice = true;
-    for slice in ReverseChunks::new(file) {
+    let mut found = None;
+    'chunks: for slice in ReverseChunks::new(file) {
         // Iterate over each byte in the slice in reverse order.
         let mut iter = memrchr_iter(delimiter, &slice);
 
@@ -445,15 +508,18 @@ fn backwards_thru_file(file: &mut File, num_delimiters: u64, delimiter: u8) {
             if counter >= num_delimiters {
                 // We should never over-count - assert that.
                 assert_eq!(counter, num_delimiters);
-                // After each iteration of the outer loop, the
-                // cursor in the file is at the *beginning* of the
-                // block, so seeking forward by `i + 1` bytes puts
-                // us right after the found delimiter.
-                file.seek(SeekFrom::Current((i + 1) as i64)).unwrap();
-                return;
+                found = Some(i);
+                break 'chunks;
             }
         }
     }
+    if let Some(i) = found {
+        // After each iteration of the outer loop, the
+        // cursor in the file is at the *beginning* of the
+        // block, so seeking forward by `i + 1` bytes puts
+        // us right after the found delimiter.
+        file.seek(SeekFrom::Current((i + 1) as i64)).unwrap();
+    }
 }
 
 /// When tail'ing a file, we do not need to read the whole file from start to
@@ -461,7 +527,10 @@ fn backwards_thru_file(file: &mut File, num_delimiters: u64, delimiter: u8) {
 /// end of the file, and then read the file "backwards" in blocks of size
 /// `BLOCK_SIZE` until we find the location of the first line/byte. This ends up
 /// being a nice performance win for very large files.
-fn bounded_tail(file: &mut File, settings: &Settings) {
+///
+/// With `+N`, the file is tailed from where it was left open, e.g. by a
+/// `head` run before on the same standard input, and not from its start.
+fn bounded_tail(file: &mut (impl Read + Seek), settings: &Settings) -> io::Result<()> {
     debug_assert!(!settings.presume_input_pipe);
     let mut limit = None;
 
@@ -471,34 +540,40 @@ fn bounded_tail(file: &mut File, settings: &Settings) {
             backwards_thru_file(file, *count, *delimiter);
         }
         FilterMode::Lines(Signum::Positive(count), delimiter) if count > &1 => {
-            let i = forwards_thru_file(file, *count - 1, *delimiter).unwrap();
-            file.seek(SeekFrom::Start(i as u64)).unwrap();
+            let start = file.stream_position()?;
+            let i = forwards_thru_file(file, *count - 1, *delimiter)?;
+            file.seek(SeekFrom::Start(start + i as u64))?;
         }
         FilterMode::Lines(Signum::MinusZero, _) => {
-            file.seek(SeekFrom::End(0)).unwrap();
+            file.seek(SeekFrom::End(0))?;
         }
         FilterMode::Bytes(Signum::Negative(count)) => {
             if file.seek(SeekFrom::End(-(*count as i64))).is_err() {
-                file.seek(SeekFrom::Start(0)).unwrap();
+                file.seek(SeekFrom::Start(0))?;
             }
             limit = Some(*count);
         }
         FilterMode::Bytes(Signum::Positive(count)) if count > &1 => {
             // GNU `tail` seems to index bytes and lines starting at 1, not
             // at 0. It seems to treat `+0` and `+1` as the same thing.
-            file.seek(SeekFrom::Start(*count - 1)).unwrap();
+            // Past what a seek can reach, there is nothing left to print.
+            let start = file.stream_position()?;
+            let target = start.saturating_add(*count - 1);
+            if target > i64::MAX as u64 || file.seek(SeekFrom::Start(target)).is_err() {
+                file.seek(SeekFrom::End(0))?;
+            }
         }
         FilterMode::Bytes(Signum::MinusZero) => {
-            file.seek(SeekFrom::End(0)).unwrap();
+            file.seek(SeekFrom::End(0))?;
         }
         _ => {}
     }
 
-    print_target_section(file, limit);
+    print_target_section(file, limit)
 }
 
-fn unbounded_tail<T: Read>(reader: &mut BufReader<T>, settings: &Settings) -> UResult<()> {
-    let mut writer = BufWriter::new(stdout().lock());
+fn unbounded_tail<T: Read>(reader: &mut BufReader<T>, settings: &Settings) -> io::Result<()> {
+    let mut writer = BufWriter::new(TagWriteErrors(stdout().lock()));
     match &settings.mode {
         FilterMode::Lines(Signum::Negative(count), sep) => {
             let mut chunks = chunks::LinesChunkBuffer::new(*sep, *count);
@@ -538,27 +613,12 @@ fn unbounded_tail<T: Read>(reader: &mut BufReader<T>, settings: &Settings) -> UR
             io::copy(reader, &mut writer)?;
         }
         FilterMode::Bytes(Signum::Positive(count)) => {
-            let mut num_skip = *count - 1;
-            let mut chunk = chunks::BytesChunk::new();
-            loop {
-                if let Some(bytes) = chunk.fill(reader)? {
-                    let bytes: u64 = bytes as u64;
-                    match bytes.cmp(&num_skip) {
-                        Ordering::Less => num_skip -= bytes,
-                        Ordering::Equal => {
-                            break;
-                        }
-                        Ordering::Greater => {
-                            writer.write_all(chunk.get_buffer_with(num_skip as usize))?;
-                            break;
-                        }
-                    }
-                } else {
-                    return Ok(());
-                }
+            // Skip through the buffer of `reader` itself, without copying
+            // what is skipped anywhere.
+            let num_skip = *count - 1;
+            if io::copy(&mut reader.by_ref().take(num_skip), &mut io::sink())? == num_skip {
+                io::copy(reader, &mut writer)?;
             }
-
-            io::copy(reader, &mut writer)?;
         }
         _ => {}
     }
@@ -575,26 +635,26 @@ fn unbounded_tail<T: Read>(reader: &mut BufReader<T>, settings: &Settings) -> UR
     Ok(())
 }
 
-fn print_target_section<R>(file: &mut R, limit: Option<u64>)
+fn print_target_section<R>(file: &mut R, limit: Option<u64>) -> io::Result<()>
 where
     R: Read + ?Sized,
 {
     // Print the target section of the file.
-    let stdout = stdout();
-    let mut stdout = stdout.lock();
+    let mut stdout = TagWriteErrors(stdout().lock());
     if let Some(limit) = limit {
         let mut reader = file.take(limit);
-        io::copy(&mut reader, &mut stdout).unwrap();
+        io::copy(&mut reader, &mut stdout)?;
     } else {
-        io::copy(file, &mut stdout).unwrap();
+        io::copy(file, &mut stdout)?;
     }
+    stdout.flush()
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::forwards_thru_file;
-    use std::io::Cursor;
+    use crate::{backwards_thru_file, forwards_thru_file};
+    use std::io::{Cursor, Seek};
 
     #[test]
     fn test_forwards_thru_file_zero() {
@@ -617,4 +677,26 @@ mod tests {
         let i = forwards_thru_file(&mut reader, 2, b'\n').unwrap();
         assert_eq!(i, 2);
     }
+
+    #[test]
+    fn test_backwards_thru_file_zero() {
+        let mut reader = Cursor::new("a\nb\n");
+        backwards_thru_file(&mut reader, 0, b'\n');
+        assert_eq!(reader.stream_position().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_backwards_thru_file_basic() {
+        //                   01 23 45 67 89
+        let mut reader = Cursor::new("a\nb\nc\nd\ne\n");
+        backwards_thru_file(&mut reader, 2, b'\n');
+        assert_eq!(reader.stream_position().unwrap(), 6);
+    }
+
+    #[test]
+    fn test_backwards_thru_file_past_start() {
+        let mut reader = Cursor::new("x\ny\n");
+        backwards_thru_file(&mut reader, 5, b'\n');
+        assert_eq!(reader.stream_position().unwrap(), 0);
+    }
 }
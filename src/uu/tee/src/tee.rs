@@ -5,12 +5,14 @@
 
 use clap::{Arg, ArgAction, Command, builder::PossibleValue};
 use std::ffi::OsString;
+#[cfg(not(target_family = "wasm"))]
 use std::fs::OpenOptions;
 use std::io::{Error, ErrorKind, Read, Result, Write, stderr, stdin, stdout};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uucore::display::Quotable;
 use uucore::error::UResult;
 use uucore::format_usage;
+use uucore::fs::is_same_file;
 use uucore::parser::shortcut_value_parser::ShortcutValueParser;
 use uucore::translate;
 
@@ -167,12 +169,23 @@ fn tee(options: &Options) -> Result<()> {
             disable_pipe_errors().map_err(|_| Error::from(ErrorKind::Other))?;
         }
     }
-    let mut writers: Vec<NamedWriter> = options
-        .files
-        .iter()
-        .filter_map(|file| open(file, options.append, options.output_error.as_ref()))
-        .collect::<Result<Vec<NamedWriter>>>()?;
-    let had_open_errors = writers.len() != options.files.len();
+    // When truncating, an operand naming a file that is already open (such
+    // as `tee f ./f` or a hard link) is written through the first writer:
+    // two truncating writers for one file would clobber each other.
+    let mut opened: Vec<&OsString> = Vec::new();
+    let mut duplicates = 0;
+    let mut writers: Vec<NamedWriter> = Vec::new();
+    for file in &options.files {
+        if !options.append && opened.iter().any(|prev| is_same_file(prev, file)) {
+            duplicates += 1;
+            continue;
+        }
+        if let Some(writer) = open(file, options.append, options.output_error.as_ref()) {
+            writers.push(writer?);
+            opened.push(file);
+        }
+    }
+    let had_open_errors = writers.len() + duplicates != options.files.len();
 
     writers.insert(
         0,
@@ -259,15 +272,9 @@ fn open(
     output_error: Option<&OutputErrorMode>,
 ) -> Option<Result<NamedWriter>> {
     let path = PathBuf::from(name);
-    let mut options = OpenOptions::new();
-    let mode = if append {
-        options.append(true)
-    } else {
-        options.truncate(true)
-    };
-    match mode.write(true).create(true).open(path.as_path()) {
-        Ok(file) => Some(Ok(NamedWriter {
-            inner: Box::new(file),
+    match open_writer(&path, append) {
+        Ok(inner) => Some(Ok(NamedWriter {
+            inner,
             name: name.clone(),
         })),
         Err(f) => {
@@ -280,6 +287,29 @@ fn open(
     }
 }
 
+/// On WASM, use the VFS write hooks since `std::fs` cannot create files.
+#[cfg(target_family = "wasm")]
+fn open_writer(path: &Path, append: bool) -> Result<Box<dyn Write>> {
+    use uucore::wasm_io::{WriteMode, open_file_write};
+    let mode = if append {
+        WriteMode::Append
+    } else {
+        WriteMode::Truncate
+    };
+    open_file_write(path, mode)
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn open_writer(path: &Path, append: bool) -> Result<Box<dyn Write>> {
+    let mut options = OpenOptions::new();
+    let mode = if append {
+        options.append(true)
+    } else {
+        options.truncate(true)
+    };
+    Ok(Box::new(mode.write(true).create(true).open(path)?))
+}
+
 struct MultiWriter {
     writers: Vec<NamedWriter>,
     output_error_mode: Option<OutputErrorMode>,
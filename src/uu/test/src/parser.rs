@@ -120,7 +120,7 @@ impl std::fmt::Display for Symbol {
 ///   TERM → str OP str
 ///   TERM → str | 𝜖
 ///   OP → STRINGOP | INTOP | FILEOP
-///   STRINGOP → = | == | !=
+///   STRINGOP → = | == | != | < | >
 ///   INTOP → -eq | -ge | -gt | -le | -lt | -ne
 ///   FILEOP → -ef | -nt | -ot
 ///   STRLEN → -n | -z
@@ -131,6 +131,8 @@ impl std::fmt::Display for Symbol {
 #[derive(Debug)]
 struct Parser {
     tokens: Peekable<std::vec::IntoIter<OsString>>,
+    /// The number of tokens in the whole expression.
+    len: usize,
     pub stack: Vec<Symbol>,
 }
 
@@ -138,6 +140,7 @@ impl Parser {
     /// Construct a new Parser from a `Vec<OsString>` of tokens.
     fn new(tokens: Vec<OsString>) -> Self {
         Self {
+            len: tokens.len(),
             tokens: tokens.into_iter().peekable(),
             stack: vec![],
         }
@@ -199,11 +202,25 @@ impl Parser {
                 }
             }
             Symbol::None => self.stack.push(symbol),
+            Symbol::Literal(s) if looks_like_unary_op(&s) && !self.is_sole_operand() => {
+                // `-x = y` still compares `-x` as a string
+                if !matches!(self.peek(), Symbol::Op(_)) {
+                    return Err(ParseError::UnaryOperatorExpected(s.quote().to_string()));
+                }
+                self.literal(Symbol::Literal(s))?;
+            }
             literal => self.literal(literal)?,
         }
         Ok(())
     }
 
+    /// Whether the token just taken is the only operand of the expression,
+    /// as in `-x` or `! -x`, where POSIX has it tested as a string whatever
+    /// it looks like.
+    fn is_sole_operand(&mut self) -> bool {
+        self.len <= 2 && self.peek() == Symbol::None
+    }
+
     /// Parse a (possibly) parenthesized expression.
     ///
     /// test has no reserved keywords, so "(" will be interpreted as a literal
@@ -435,6 +452,13 @@ impl Parser {
     }
 }
 
+/// Whether `s` has the shape of a unary operator, a dash and one other byte,
+/// without being one that test knows, e.g. `-l` or `--`. As in GNU test, such
+/// a token is an error where a unary operator could stand.
+fn looks_like_unary_op(s: &OsStr) -> bool {
+    matches!(s.as_encoded_bytes(), [b'-', _])
+}
+
 /// Parse the token stream `args`, returning a `Symbol` stack representing the
 /// operations to perform in postfix order.
 pub fn parse(args: Vec<OsString>) -> ParseResult<Vec<Symbol>> {
@@ -11,6 +11,7 @@ mod parser;
 use clap::Command;
 use error::{ParseError, ParseResult};
 use parser::{Operator, Symbol, UnaryOperator, parse};
+use std::cmp::Ordering;
 use std::ffi::{OsStr, OsString};
 use std::fs;
 #[cfg(unix)]
@@ -18,7 +19,10 @@ use std::os::unix::fs::MetadataExt;
 use uucore::display::Quotable;
 use uucore::error::{UResult, USimpleError};
 use uucore::format_usage;
-#[cfg(not(windows))]
+#[cfg(feature = "i18n-collator")]
+use uucore::i18n::collator::{init_locale_collation, locale_cmp};
+use uucore::os_str_as_bytes_lossy;
+#[cfg(unix)]
 use uucore::process::{getegid, geteuid};
 
 use uucore::translate;
@@ -96,8 +100,8 @@ fn eval(stack: &mut Vec<Symbol>) -> ParseResult<bool> {
             let a = pop_literal!();
             match op.to_string_lossy().as_ref() {
                 "!=" => Ok(a != b),
-                "<" => Ok(a < b),
-                ">" => Ok(a > b),
+                "<" => Ok(compare_strings(&a, &b) == Ordering::Less),
+                ">" => Ok(compare_strings(&a, &b) == Ordering::Greater),
                 _ => Ok(a == b),
             }
         }
@@ -175,6 +179,19 @@ fn eval(stack: &mut Vec<Symbol>) -> ParseResult<bool> {
     }
 }
 
+/// Compare strings for `<` and `>`, in the collation order of the current
+/// locale when built with the `i18n-collator` feature, byte by byte otherwise.
+fn compare_strings(a: &OsStr, b: &OsStr) -> Ordering {
+    let (a, b) = (os_str_as_bytes_lossy(a), os_str_as_bytes_lossy(b));
+    #[cfg(feature = "i18n-collator")]
+    {
+        init_locale_collation();
+        locale_cmp(&a, &b)
+    }
+    #[cfg(not(feature = "i18n-collator"))]
+    a.cmp(&b)
+}
+
 /// Operations to compare integers
 /// `a` is the left hand side
 /// `b` is the left hand side
@@ -234,7 +251,18 @@ fn isatty(fd: &OsStr) -> ParseResult<bool> {
         .map(str::trim)
         .and_then(|s| s.parse().ok())
         .ok_or_else(|| ParseError::InvalidInteger(fd.quote().to_string()))
-        .map(|i| unsafe { libc::isatty(i) == 1 })
+        .map(fd_is_terminal)
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn fd_is_terminal(fd: i32) -> bool {
+    unsafe { libc::isatty(fd) == 1 }
+}
+
+/// There are no terminals on WASM.
+#[cfg(target_family = "wasm")]
+fn fd_is_terminal(_fd: i32) -> bool {
+    false
 }
 
 #[derive(Eq, PartialEq)]
@@ -259,7 +287,7 @@ enum PathCondition {
     Executable,
 }
 
-#[cfg(not(windows))]
+#[cfg(unix)]
 fn path(path: &OsStr, condition: &PathCondition) -> bool {
     use std::fs::Metadata;
     use std::os::unix::fs::FileTypeExt;
@@ -350,6 +378,46 @@ fn path(path: &OsStr, condition: &PathCondition) -> bool {
     }
 }
 
+/// WASM has no users, groups or special files, so the running user is
+/// treated as the owner of every file.
+#[cfg(target_family = "wasm")]
+fn path(path: &OsStr, condition: &PathCondition) -> bool {
+    use uucore::wasm_io::{metadata, symlink_metadata};
+
+    const S_ISUID: u32 = 0o4000;
+    const S_ISGID: u32 = 0o2000;
+    const S_ISVTX: u32 = 0o1000;
+
+    let metadata = if condition == &PathCondition::SymLink {
+        symlink_metadata(path)
+    } else {
+        metadata(path)
+    };
+
+    let Ok(metadata) = metadata else {
+        return false;
+    };
+
+    match condition {
+        PathCondition::BlockSpecial
+        | PathCondition::CharacterSpecial
+        | PathCondition::Fifo
+        | PathCondition::Socket => false,
+        PathCondition::Directory => metadata.is_dir(),
+        PathCondition::Exists | PathCondition::GroupOwns | PathCondition::UserOwns => true,
+        PathCondition::ExistsModifiedLastRead => false,
+        PathCondition::Regular => metadata.is_file(),
+        PathCondition::GroupIdFlag => metadata.mode & S_ISGID != 0,
+        PathCondition::SymLink => metadata.is_symlink(),
+        PathCondition::Sticky => metadata.mode & S_ISVTX != 0,
+        PathCondition::Readable => metadata.mode & 0o400 != 0,
+        PathCondition::NonEmpty => metadata.len() > 0,
+        PathCondition::UserIdFlag => metadata.mode & S_ISUID != 0,
+        PathCondition::Writable => metadata.mode & 0o200 != 0,
+        PathCondition::Executable => metadata.mode & 0o100 != 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
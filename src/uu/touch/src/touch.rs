@@ -26,6 +26,7 @@ use uucore::error::{FromIo, UResult, USimpleError};
 #[cfg(target_os = "linux")]
 use uucore::libc;
 use uucore::parser::shortcut_value_parser::ShortcutValueParser;
+use uucore::time::{local_time_zone, local_time_zone_at};
 use uucore::translate;
 use uucore::{format_usage, show};
 
@@ -131,7 +132,7 @@ fn timestamp_to_filetime(ts: Timestamp) -> FileTime {
 
 fn filetime_to_zoned(ft: &FileTime) -> Option<Zoned> {
     let ts = Timestamp::new(ft.unix_seconds(), ft.nanoseconds() as i32).ok()?;
-    Some(Zoned::new(ts, TimeZone::system()))
+    Some(Zoned::new(ts, local_time_zone_at(ts)))
 }
 
 /// Whether all characters in the string are digits.
@@ -382,12 +383,12 @@ pub fn touch(files: &[InputFile], opts: &Options) -> Result<(), TouchError> {
                 if opts.date.is_none() {
                     now = FileTime::from_unix_time(0, libc::UTIME_NOW as u32);
                 } else {
-                    now = timestamp_to_filetime(Timestamp::now());
+                    now = timestamp_to_filetime(uucore::time::now());
                 }
             }
             #[cfg(not(target_os = "linux"))]
             {
-                now = timestamp_to_filetime(Timestamp::now());
+                now = timestamp_to_filetime(uucore::time::now());
             }
             (now, now)
         }
@@ -651,7 +652,7 @@ fn parse_date(ref_zoned: Zoned, s: &str) -> Result<FileTime, TouchError> {
     if let Ok(filetime) = strtime::parse(format::ISO_8601, s)
         .and_then(|tm| tm.to_date())
         .and_then(|date| {
-            TimeZone::system()
+            local_time_zone()
                 .to_ambiguous_zoned(date.to_datetime(Time::midnight()))
                 .unambiguous()
         })
@@ -704,7 +705,7 @@ fn prepend_century(s: &str) -> UResult<String> {
 fn parse_timestamp(s: &str) -> UResult<FileTime> {
     use format::{YYYYMMDDHHMM, YYYYMMDDHHMM_DOT_SS};
 
-    let current_year = || Timestamp::now().to_zoned(TimeZone::system()).year();
+    let current_year = || uucore::time::now().to_zoned(local_time_zone()).year();
 
     let (format, ts) = match s.chars().count() {
         15 => (YYYYMMDDHHMM_DOT_SS, s.to_owned()),
@@ -742,7 +743,7 @@ fn parse_timestamp(s: &str) -> UResult<FileTime> {
     // Due to daylight saving time switch, local time can jump from 1:59 AM to
     // 3:00 AM, in which case any time between 2:00 AM and 2:59 AM is not valid.
     // Jiff's `to_ambiguous_zoned(...).unambiguous()` handles this case.
-    let local = TimeZone::system()
+    let local = local_time_zone()
         .to_ambiguous_zoned(dt)
         .unambiguous()
         .map_err(|_| {
@@ -770,6 +771,10 @@ fn pathbuf_from_stdout() -> Result<PathBuf, TouchError> {
     {
         Ok(PathBuf::from("/proc/self/fd/1"))
     }
+    #[cfg(target_family = "wasm")]
+    {
+        Ok(PathBuf::from("/dev/stdout"))
+    }
     #[cfg(windows)]
     {
         use std::os::windows::prelude::AsRawHandle;
@@ -9,19 +9,30 @@ use rustc_hash::FxHashMap;
 use std::collections::VecDeque;
 use std::collections::hash_map::Entry;
 use std::ffi::OsString;
+#[cfg(not(target_family = "wasm"))]
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, Write};
+#[cfg(not(target_family = "wasm"))]
+use std::io::{stdin, stdout};
 use string_interner::StringInterner;
 use string_interner::backend::BucketBackend;
 use thiserror::Error;
 use uucore::display::Quotable;
 use uucore::error::{UError, UResult, USimpleError};
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::{stdin, stdout};
 use uucore::{format_usage, show, translate};
 
 // short types for switching interning behavior on the fly.
 type Sym = string_interner::symbol::SymbolUsize;
 type Interner = StringInterner<BucketBackend<Sym>, rustc_hash::FxBuildHasher>;
 
+/// The open input file. On WASM this is a reader from the VFS file hooks.
+#[cfg(not(target_family = "wasm"))]
+type InputFile = File;
+#[cfg(target_family = "wasm")]
+type InputFile = Box<dyn io::Read>;
+
 mod options {
     pub const FILE: &str = "file";
 }
@@ -54,11 +65,11 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
             ));
         }
     };
-    let file: File;
+    let file: InputFile;
     // Create the directed graph from pairs of tokens in the input data.
     let mut g = Graph::new(input.to_string_lossy().to_string());
     if input == "-" {
-        process_input(io::stdin().lock(), &mut g)?;
+        process_input(stdin().lock(), &mut g)?;
     } else {
         // Windows reports a permission denied error when trying to read a directory.
         // So we check manually beforehand. On other systems, we avoid this extra check for performance.
@@ -73,7 +84,11 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
 
             file = File::open(path)?;
         }
-        #[cfg(not(windows))]
+        #[cfg(target_family = "wasm")]
+        {
+            file = uucore::wasm_io::open_file(input)?;
+        }
+        #[cfg(not(any(windows, target_family = "wasm")))]
         {
             file = File::open(input)?;
 
@@ -82,7 +97,6 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
                 target_os = "linux",
                 target_os = "android",
                 target_os = "fuchsia",
-                target_os = "wasi",
                 target_env = "uclibc",
                 target_os = "freebsd",
             ))]
@@ -103,7 +117,7 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         process_input(reader, &mut g)?;
     }
 
-    g.run_tsort();
+    g.run_tsort(&mut stdout().lock())?;
     Ok(())
 }
 
@@ -277,7 +291,7 @@ impl Graph {
     }
 
     /// Implementation of algorithm T from TAOCP (Don. Knuth), vol. 1.
-    fn run_tsort(&mut self) {
+    fn run_tsort(&mut self, out: &mut impl Write) -> io::Result<()> {
         let mut independent_nodes_queue: VecDeque<Sym> = self
             .nodes
             .iter()
@@ -297,7 +311,7 @@ impl Graph {
 
         while !self.nodes.is_empty() {
             let v = self.find_next_node(&mut independent_nodes_queue);
-            println!("{}", self.get_node_name(v));
+            writeln!(out, "{}", self.get_node_name(v))?;
             if let Some(node_to_process) = self.nodes.remove(&v) {
                 for successor_name in node_to_process.successor_tokens.into_iter().rev() {
                     // we reverse to match GNU tsort order
@@ -312,6 +326,7 @@ impl Graph {
                 }
             }
         }
+        Ok(())
     }
     pub fn indegree(&self, sym: Sym) -> Option<usize> {
         self.nodes.get(&sym).map(|data| data.predecessor_count)
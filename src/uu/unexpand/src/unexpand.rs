@@ -3,15 +3,14 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-// spell-checker:ignore (ToDO) nums aflag uflag scol prevtab amode ctype cwidth nbytes lastcol pctype Preprocess
+// spell-checker:ignore (ToDO) nums aflag Preprocess
 
 use clap::{Arg, ArgAction, Command};
 use std::ffi::OsString;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Stdout, Write, stdin, stdout};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Stdout, Write, stdin, stdout};
 use std::num::IntErrorKind;
 use std::path::Path;
-use std::str::from_utf8;
 use thiserror::Error;
 use uucore::display::Quotable;
 use uucore::error::{FromIo, UError, UResult, USimpleError, set_exit_code};
@@ -154,7 +153,6 @@ struct Options {
     files: Vec<OsString>,
     tab_config: TabConfig,
     aflag: bool,
-    uflag: bool,
 }
 
 impl Options {
@@ -170,7 +168,6 @@ impl Options {
 
         let aflag = (matches.get_flag(options::ALL) || matches.contains_id(options::TABS))
             && !matches.get_flag(options::FIRST_ONLY);
-        let uflag = !matches.get_flag(options::NO_UTF8);
 
         let files = match matches.get_many::<OsString>(options::FILE) {
             Some(v) => v.cloned().collect(),
@@ -181,7 +178,6 @@ impl Options {
             files,
             tab_config,
             aflag,
-            uflag,
         })
     }
 }
@@ -344,276 +340,190 @@ fn next_tabstop(tab_config: &TabConfig, col: usize) -> Option<usize> {
     }
 }
 
-fn write_tabs(
-    output: &mut BufWriter<Stdout>,
-    tab_config: &TabConfig,
-    print_state: &mut PrintState,
-    amode: bool,
-) -> UResult<()> {
-    // This conditional establishes the following:
-    // We never turn a single space before a non-blank into
-    // a tab, unless it's at the start of the line.
-    let ai = print_state.leading || amode;
-    if (ai && print_state.pctype != CharType::Tab && print_state.col > print_state.scol + 1)
-        || (print_state.col > print_state.scol
-            && (print_state.leading || ai && print_state.pctype == CharType::Tab))
-    {
-        while let Some(nts) = next_tabstop(tab_config, print_state.scol) {
-            if print_state.col < print_state.scol + nts {
-                break;
-            }
-
-            output.write_all(b"\t")?;
-            print_state.scol += nts;
-        }
-    }
-
-    while print_state.col > print_state.scol {
-        output.write_all(b" ")?;
-        print_state.scol += 1;
-    }
-    Ok(())
+/// The column of the first tab stop after `column`, or `None` past the last
+/// one, as GNU's `get_next_tab_column`.
+fn next_tab_column(tab_config: &TabConfig, column: usize) -> Option<usize> {
+    next_tabstop(tab_config, column).map(|distance| column + distance)
 }
 
-#[derive(PartialEq, Eq, Debug)]
-enum CharType {
-    Backspace,
-    Space,
-    Tab,
-    Other,
+/// The conversion state of the current line.
+///
+/// This is the state machine of GNU unexpand. Blanks are held back in
+/// `pending` until it is known whether they reach a tab stop: a run of
+/// blanks that ends on a stop is replaced by a tab, while blanks followed by
+/// anything else are written as they are. As POSIX wants, a single space
+/// just before a tab stop is kept a space, unless more blanks follow it.
+///
+/// The state is carried over from one buffer, and from one file, to the
+/// next, so a line is converted the same however its input is split.
+struct LineState {
+    /// Whether blanks are still converted: only until the first non-blank,
+    /// unless all blanks are, and never past the last tab stop.
+    convert: bool,
+    /// The column of the input, counting one per byte.
+    column: usize,
+    /// Whether the pending blanks are a single one just before a tab stop,
+    /// possibly followed by more blanks after it.
+    one_blank_before_tab_stop: bool,
+    /// Whether the previous byte was a blank. Starts out true, so that a
+    /// blank at the start of a line can become a tab on its own.
+    prev_blank: bool,
+    /// Blanks that may still be replaced by a tab.
+    pending: Vec<u8>,
 }
 
-fn next_char_info(uflag: bool, buf: &[u8], byte: usize) -> (CharType, usize, usize) {
-    let (ctype, cwidth, nbytes) = if uflag {
-        let nbytes = char::from(buf[byte]).len_utf8();
-
-        if byte + nbytes > buf.len() {
-            // make sure we don't overrun the buffer because of invalid UTF-8
-            (CharType::Other, 1, 1)
-        } else if let Ok(t) = from_utf8(&buf[byte..byte + nbytes]) {
-            // Now that we think it's UTF-8, figure out what kind of char it is
-            match t.chars().next() {
-                Some(' ') => (CharType::Space, 0, 1),
-                Some('\t') => (CharType::Tab, 0, 1),
-                Some('\x08') => (CharType::Backspace, 0, 1),
-                Some(_) => (CharType::Other, nbytes, nbytes),
-                None => {
-                    // invalid char snuck past the utf8_validation_iterator somehow???
-                    (CharType::Other, 1, 1)
-                }
-            }
-        } else {
-            // otherwise, it's not valid
-            (CharType::Other, 1, 1) // implicit assumption: non-UTF8 char has display width 1
+impl LineState {
+    fn new() -> Self {
+        Self {
+            convert: true,
+            column: 0,
+            one_blank_before_tab_stop: false,
+            prev_blank: true,
+            pending: Vec::new(),
         }
-    } else {
-        (
-            match buf[byte] {
-                // always take exactly 1 byte in strict ASCII mode
-                0x20 => CharType::Space,
-                0x09 => CharType::Tab,
-                0x08 => CharType::Backspace,
-                _ => CharType::Other,
-            },
-            1,
-            1,
-        )
-    };
-
-    (ctype, cwidth, nbytes)
-}
-
-// This struct is used to store the current state of printing the input buf.
-// Things that need to be tracked are
-// - are we in the leading whitespaces before any other chars of this line
-// - what columns have already been printed
-// - how many whitespaces have been found but not yet been printed
-// - what type was the last Char
-struct PrintState {
-    col: usize,
-    scol: usize,
-    leading: bool,
-    pctype: CharType,
-}
-
-impl PrintState {
-    // reinitializes the PrintState struct to beginning of line values
-    fn new_line(&mut self) {
-        self.col = 0;
-        self.scol = 0;
-        self.leading = true;
-        self.pctype = CharType::Other;
     }
-}
 
-#[allow(clippy::cognitive_complexity)]
-fn unexpand_buf(
-    buf: &[u8],
-    output: &mut BufWriter<Stdout>,
-    options: &Options,
-    lastcol: usize,
-    tab_config: &TabConfig,
-    print_state: &mut PrintState,
-) -> UResult<()> {
-    // We can only fast forward if we don't need to calculate col/scol
-    if let Some(b'\n') = buf.last() {
-        // Fast path: if we're not converting all spaces (-a flag not set)
-        // and the line doesn't start with spaces, just write it directly
-        if !options.aflag
-            && !buf.is_empty()
-            && ((buf[0] != b' ' && buf[0] != b'\t') || !print_state.leading)
-        {
-            write_tabs(output, tab_config, print_state, options.aflag)?;
-            print_state.scol = print_state.col;
-            print_state.col += buf.len();
-            output.write_all(buf)?;
-            return Ok(());
-        }
+    /// Start over at the beginning of a line. Nothing is pending there: the
+    /// newline has flushed it.
+    fn new_line(&mut self) {
+        self.convert = true;
+        self.column = 0;
+        self.prev_blank = true;
     }
 
-    let mut byte = 0; // offset into the buffer
-
-    // We can only fast forward if we don't need to calculate col/scol
-    if let Some(b'\n') = buf.last() {
-        // Fast path for leading spaces in non-UTF8 mode: count consecutive spaces/tabs at start
-        if !options.uflag && !options.aflag && print_state.leading {
-            // In default mode (not -a), we only convert leading spaces
-            // So we can batch process them and then copy the rest
-            while byte < buf.len() {
-                match buf[byte] {
-                    b' ' => {
-                        print_state.col += 1;
-                        byte += 1;
-                    }
-                    b'\t' => {
-                        print_state.col += next_tabstop(tab_config, print_state.col).unwrap_or(1);
-                        byte += 1;
-                        print_state.pctype = CharType::Tab;
-                    }
-                    _ => break,
+    /// Convert `buf` and write the result, holding back the blanks at its
+    /// end whose fate depends on what comes next.
+    fn unexpand_buf(
+        &mut self,
+        mut buf: &[u8],
+        output: &mut impl Write,
+        options: &Options,
+    ) -> std::io::Result<()> {
+        while !buf.is_empty() {
+            if !self.convert {
+                // nothing changes until the end of the line
+                let end = buf.iter().position(|&b| b == b'\n');
+                let (rest_of_line, rest) = buf.split_at(end.map_or(buf.len(), |end| end + 1));
+                output.write_all(rest_of_line)?;
+                if end.is_some() {
+                    self.new_line();
                 }
+                buf = rest;
+                continue;
             }
 
-            // If we found spaces/tabs, write them as tabs
-            if byte > 0 {
-                write_tabs(output, tab_config, print_state, options.aflag)?;
-            }
-
-            // Write the rest of the line directly (no more tab conversion needed)
-            if byte < buf.len() {
-                print_state.leading = false;
-                output.write_all(&buf[byte..])?;
+            let c = buf[0];
+            buf = &buf[1..];
+            if let Some(c) = self.convert_byte(c, options) {
+                self.flush_pending(output)?;
+                output.write_all(&[c])?;
+                if c == b'\n' {
+                    self.new_line();
+                }
             }
-            return Ok(());
         }
+        Ok(())
     }
 
-    while byte < buf.len() {
-        // when we have a finite number of columns, never convert past the last column
-        if lastcol > 0 && print_state.col >= lastcol {
-            write_tabs(output, tab_config, print_state, true)?;
-            output.write_all(&buf[byte..])?;
-            print_state.scol = print_state.col;
-            break;
-        }
-
-        // figure out how big the next char is, if it's UTF-8
-        let (ctype, cwidth, nbytes) = next_char_info(options.uflag, buf, byte);
-
-        // now figure out how many columns this char takes up, and maybe print it
-        let tabs_buffered = print_state.leading || options.aflag;
-        match ctype {
-            CharType::Space | CharType::Tab => {
-                // compute next col, but only write space or tab chars if not buffering
-                print_state.col += if ctype == CharType::Space {
-                    1
-                } else {
-                    next_tabstop(tab_config, print_state.col).unwrap_or(1)
-                };
-
-                if !tabs_buffered {
-                    output.write_all(&buf[byte..byte + nbytes])?;
-                    print_state.scol = print_state.col; // now printed up to this column
+    /// Update the state for `c`. Returns the byte to write after the pending
+    /// blanks, or `None` if `c` became pending itself.
+    fn convert_byte(&mut self, c: u8, options: &Options) -> Option<u8> {
+        let mut c = c;
+        let blank = c == b' ' || c == b'\t';
+        if blank {
+            match next_tab_column(&options.tab_config, self.column) {
+                None => self.convert = false,
+                Some(next_tab_column) => {
+                    if c == b'\t' {
+                        self.column = next_tab_column;
+                        if let Some(first) = self.pending.first_mut() {
+                            *first = b'\t';
+                        }
+                    } else {
+                        self.column += 1;
+                        if !(self.prev_blank && self.column == next_tab_column) {
+                            // It is not yet known whether the pending blanks
+                            // will be replaced by tabs.
+                            if self.column == next_tab_column {
+                                self.one_blank_before_tab_stop = true;
+                            }
+                            self.pending.push(c);
+                            self.prev_blank = true;
+                            return None;
+                        }
+                        // The blanks reach a tab stop: replace them by a tab,
+                        // or two if a single blank reached the previous one.
+                        if let Some(first) = self.pending.first_mut() {
+                            *first = b'\t';
+                        }
+                        c = b'\t';
+                    }
+                    // Discard the pending blanks, unless it was a single one
+                    // just before the previous tab stop.
+                    self.pending
+                        .truncate(usize::from(self.one_blank_before_tab_stop));
                 }
             }
-            CharType::Other | CharType::Backspace => {
-                // always
-                write_tabs(output, tab_config, print_state, options.aflag)?;
-                print_state.leading = false; // no longer at the start of a line
-                print_state.col = if ctype == CharType::Other {
-                    // use computed width
-                    print_state.col + cwidth
-                } else if print_state.col > 0 {
-                    // Backspace case, but only if col > 0
-                    print_state.col - 1
-                } else {
-                    0
-                };
-                output.write_all(&buf[byte..byte + nbytes])?;
-                print_state.scol = print_state.col; // we've now printed up to this column
-            }
+        } else if c == b'\x08' {
+            // go back one column
+            self.column = self.column.saturating_sub(1);
+        } else {
+            self.column += 1;
         }
 
-        byte += nbytes; // move on to next char
-        print_state.pctype = ctype; // save the previous type
+        self.prev_blank = blank;
+        self.convert &= options.aflag || blank;
+        Some(c)
     }
 
-    Ok(())
+    /// Write the blanks held back, now that a non-blank follows them.
+    fn flush_pending(&mut self, output: &mut impl Write) -> std::io::Result<()> {
+        if !self.pending.is_empty() {
+            if self.pending.len() > 1 && self.one_blank_before_tab_stop {
+                self.pending[0] = b'\t';
+            }
+            output.write_all(&self.pending)?;
+            self.pending.clear();
+            self.one_blank_before_tab_stop = false;
+        }
+        Ok(())
+    }
 }
 
 fn unexpand_file(
     file: &OsString,
     output: &mut BufWriter<Stdout>,
     options: &Options,
-    lastcol: usize,
-    tab_config: &TabConfig,
+    state: &mut LineState,
 ) -> UResult<()> {
     let mut buf = [0u8; 4096];
     let mut input = open(file)?;
-    let mut print_state = PrintState {
-        col: 0,
-        scol: 0,
-        leading: true,
-        pctype: CharType::Other,
-    };
 
     loop {
         match input.read(&mut buf) {
             Ok(0) => break,
-            Ok(n) => {
-                for line in buf[..n].split_inclusive(|b| *b == b'\n') {
-                    unexpand_buf(line, output, options, lastcol, tab_config, &mut print_state)?;
-                    if let Some(b'\n') = line.last() {
-                        print_state.new_line();
-                    }
-                }
-            }
+            Ok(n) => state.unexpand_buf(&buf[..n], output, options)?,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
             Err(e) => return Err(e.map_err_context(|| file.maybe_quote().to_string())),
         }
     }
-    // write out anything remaining
-    write_tabs(output, tab_config, &mut print_state, options.aflag)?;
     Ok(())
 }
 
 fn unexpand(options: &Options) -> UResult<()> {
     let mut output = BufWriter::new(stdout());
-    let tab_config = &options.tab_config;
-    let lastcol = if tab_config.tabstops.len() > 1
-        && tab_config.increment_size.is_none()
-        && tab_config.extend_size.is_none()
-    {
-        *tab_config.tabstops.last().unwrap()
-    } else {
-        0
-    };
+    // Like GNU, a line that is not finished at the end of a file goes on in
+    // the next one.
+    let mut state = LineState::new();
 
     for file in &options.files {
-        if let Err(e) = unexpand_file(file, &mut output, options, lastcol, tab_config) {
+        if let Err(e) = unexpand_file(file, &mut output, options, &mut state) {
             show!(e);
             set_exit_code(1);
         }
     }
+    // write out the blanks left at the end of the input
+    state.flush_pending(&mut output)?;
     output.flush()?;
     Ok(())
 }
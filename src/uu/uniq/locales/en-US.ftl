@@ -23,20 +23,9 @@ uniq-error-write-line-terminator = Could not write line terminator
 uniq-error-write-error = write error
 uniq-error-read-error = read error
 uniq-error-invalid-argument = Invalid argument for { $opt_name }: { $arg }
-uniq-error-try-help = Try 'uniq --help' for more information.
-uniq-error-group-mutually-exclusive = --group is mutually exclusive with -c/-d/-D/-u
-uniq-error-group-badoption = invalid argument 'badoption' for '--group'
-  Valid arguments are:
-    - 'prepend'
-    - 'append'
-    - 'separate'
-    - 'both'
 
-uniq-error-all-repeated-badoption = invalid argument 'badoption' for '--all-repeated'
-  Valid arguments are:
-    - 'none'
-    - 'prepend'
-    - 'separate'
+uniq-error-group-mutually-exclusive = --group is mutually exclusive with -c/-d/-D/-u
+  Try 'uniq --help' for more information.
 
 uniq-error-counts-and-repeated-meaningless = printing all duplicated lines and repeat counts is meaningless
   Try 'uniq --help' for more information.
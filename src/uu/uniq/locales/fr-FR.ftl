@@ -22,20 +22,9 @@ uniq-error-write-line-terminator = Impossible d'écrire le terminateur de ligne
 uniq-error-write-error = erreur d'écriture
 uniq-error-read-error = erreur de lecture
 uniq-error-invalid-argument = Argument invalide pour { $opt_name } : { $arg }
-uniq-error-try-help = Essayez 'uniq --help' pour plus d'informations.
-uniq-error-group-mutually-exclusive = --group est mutuellement exclusif avec -c/-d/-D/-u
-uniq-error-group-badoption = argument invalide 'badoption' pour '--group'
-  Arguments valides :
-    - 'prepend'
-    - 'append'
-    - 'separate'
-    - 'both'
 
-uniq-error-all-repeated-badoption = argument invalide 'badoption' pour '--all-repeated'
-  Arguments valides :
-    - 'none'
-    - 'prepend'
-    - 'separate'
+uniq-error-group-mutually-exclusive = --group est mutuellement exclusif avec -c/-d/-D/-u
+  Essayez 'uniq --help' pour plus d'informations.
 
 uniq-error-counts-and-repeated-meaningless = afficher toutes les lignes dupliquées et les nombres de répétitions n'a pas de sens
   Essayez 'uniq --help' pour plus d'informations.
@@ -3,10 +3,7 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 // spell-checker:ignore badoption
-use clap::{
-    Arg, ArgAction, ArgMatches, Command, builder::ValueParser, error::ContextKind, error::Error,
-    error::ErrorKind,
-};
+use clap::{Arg, ArgAction, ArgMatches, Command, builder::ValueParser};
 use std::ffi::{OsStr, OsString};
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Write};
@@ -16,7 +13,7 @@ use std::io::{stdin, stdout};
 use uucore::wasm_io::{stdin, stdout};
 use std::num::IntErrorKind;
 use uucore::display::Quotable;
-use uucore::error::{FromIo, UError, UResult, USimpleError};
+use uucore::error::{FromIo, UResult, USimpleError};
 use uucore::format_usage;
 use uucore::parser::shortcut_value_parser::ShortcutValueParser;
 use uucore::posix::{OBSOLETE, posix_version};
@@ -594,61 +591,11 @@ fn handle_extract_obs_skip_chars(
     }
 }
 
-/// Maps Clap errors to [`USimpleError`] and overrides 3 specific ones
-/// to meet requirements of GNU tests for `uniq`.
-/// Unfortunately these overrides are necessary, since several GNU tests
-/// for `uniq` hardcode and require the exact wording of the error message
-/// and it is not compatible with how Clap formats and displays those error messages.
-fn map_clap_errors(clap_error: Error) -> Box<dyn UError> {
-    let footer = translate!("uniq-error-try-help");
-    let override_arg_conflict = translate!("uniq-error-group-mutually-exclusive") + "\n" + &footer;
-    let override_group_badoption = translate!("uniq-error-group-badoption") + "\n" + &footer;
-    let override_all_repeated_badoption =
-        translate!("uniq-error-all-repeated-badoption") + "\n" + &footer;
-
-    let error_message = match clap_error.kind() {
-        ErrorKind::ArgumentConflict => override_arg_conflict,
-        ErrorKind::InvalidValue
-            if clap_error
-                .get(ContextKind::InvalidValue)
-                .is_some_and(|v| v.to_string() == "badoption")
-                && clap_error
-                    .get(ContextKind::InvalidArg)
-                    .is_some_and(|v| v.to_string().starts_with("--group")) =>
-        {
-            override_group_badoption
-        }
-        ErrorKind::InvalidValue
-            if clap_error
-                .get(ContextKind::InvalidValue)
-                .is_some_and(|v| v.to_string() == "badoption")
-                && clap_error
-                    .get(ContextKind::InvalidArg)
-                    .is_some_and(|v| v.to_string().starts_with("--all-repeated")) =>
-        {
-            override_all_repeated_badoption
-        }
-        _ => return clap_error.into(),
-    };
-    USimpleError::new(1, error_message)
-}
-
 #[uucore::main]
 pub fn uumain(args: impl uucore::Args) -> UResult<()> {
     let (args, skip_fields_old, skip_chars_old) = handle_obsolete(args);
 
-    let matches = match uu_app().try_get_matches_from(args) {
-        Ok(matches) => matches,
-        Err(clap_error) => {
-            if clap_error.exit_code() == 0 {
-                // Let caller handle help/version
-                return Err(map_clap_errors(clap_error));
-            }
-            // Use ErrorFormatter directly to handle error
-            let formatter = uucore::clap_localization::ErrorFormatter::new(uucore::util_name());
-            formatter.print_error_and_exit_with_callback(&clap_error, 1, || {});
-        }
-    };
+    let matches = uucore::clap_localization::handle_clap_result(uu_app(), args)?;
 
     let files = matches.get_many::<OsString>(ARG_FILES);
 
@@ -675,6 +622,16 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         zero_terminated: matches.get_flag(options::ZERO_TERMINATED),
     };
 
+    // Checked here rather than by clap, for the message GNU gives
+    if matches.contains_id(options::GROUP)
+        && (uniq.show_counts || uniq.repeats_only || uniq.uniques_only)
+    {
+        return Err(USimpleError::new(
+            1,
+            translate!("uniq-error-group-mutually-exclusive"),
+        ));
+    }
+
     if uniq.show_counts && uniq.all_repeated {
         return Err(USimpleError::new(
             1,
@@ -717,13 +674,7 @@ pub fn uu_app() -> Command {
                 .value_name("group-method")
                 .num_args(0..=1)
                 .default_missing_value("separate")
-                .require_equals(true)
-                .conflicts_with_all([
-                    options::REPEATED,
-                    options::ALL_REPEATED,
-                    options::UNIQUE,
-                    options::COUNT,
-                ]),
+                .require_equals(true),
         )
         .arg(
             Arg::new(options::CHECK_CHARS)
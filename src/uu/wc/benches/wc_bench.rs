@@ -97,6 +97,19 @@ fn wc_words_large_line_count(bencher: Bencher, num_lines: usize) {
     });
 }
 
+/// Benchmark word counting on text mixing ASCII with multi-byte characters
+#[divan::bench(args = [100_000])]
+fn wc_words_mixed_large_line_count(bencher: Bencher, num_lines: usize) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let data = text_data::generate_mixed_data(num_lines);
+    let file_path = create_test_file(&data, temp_dir.path());
+    let file_path_str = file_path.to_str().unwrap();
+
+    bencher.bench(|| {
+        black_box(run_util_function(uumain, &["-w", file_path_str]));
+    });
+}
+
 /// Benchmark default wc (lines, words, bytes) on large line counts
 #[divan::bench(args = [100_000])]
 fn wc_default_large_line_count(bencher: Bencher, num_lines: usize) {
@@ -262,3 +262,67 @@ pub(crate) fn count_bytes_chars_and_lines_fast<
         }
     }
 }
+
+/// Whether an ASCII byte separates words.
+///
+/// `char::is_whitespace` and the POSIX set used under `POSIXLY_CORRECT`
+/// differ only outside ASCII.
+#[inline]
+const fn is_ascii_space(byte: u8) -> bool {
+    matches!(byte, b'\t'..=b'\r' | b' ')
+}
+
+/// Counts the words that start in `text`, carrying `in_word` over from the
+/// previous chunk.
+///
+/// Runs of ASCII are scanned 64 bytes at a time: a mask of the bytes that are
+/// not spaces is built without branching, and every set bit whose lower
+/// neighbour is clear starts a word. Blocks containing other characters are
+/// classified one character at a time, decoding only the few that can be
+/// spaces.
+pub(crate) fn count_words(text: &str, in_word: &mut bool, posixly_correct: bool) -> usize {
+    const BLOCK: usize = 64;
+    let bytes = text.as_bytes();
+    let mut words = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if let Some(block) = bytes.get(i..i + BLOCK).filter(|block| block.is_ascii()) {
+            let mask = block.iter().enumerate().fold(0_u64, |mask, (j, &byte)| {
+                mask | (u64::from(!is_ascii_space(byte)) << j)
+            });
+            let starts = mask & !((mask << 1) | u64::from(*in_word));
+            words += starts.count_ones() as usize;
+            *in_word = mask >> (BLOCK - 1) == 1;
+            i += BLOCK;
+            continue;
+        }
+        let end = (i + BLOCK).min(bytes.len());
+        while i < end {
+            let byte = bytes[i];
+            let is_space = if byte.is_ascii() {
+                i += 1;
+                is_ascii_space(byte)
+            } else {
+                // `text` is valid UTF-8, so the lead byte gives the length,
+                // and every non-ASCII space starts with one of these bytes.
+                let len = match byte {
+                    0x80..0xe0 => 2,
+                    0xe0..0xf0 => 3,
+                    _ => 4,
+                };
+                let may_be_space = !posixly_correct && matches!(byte, 0xc2 | 0xe1..=0xe3);
+                let is_space = may_be_space && text[i..i + len].chars().all(char::is_whitespace);
+                i += len;
+                is_space
+            };
+            if is_space {
+                *in_word = false;
+            } else if !*in_word {
+                // This also counts control characters! (As of GNU coreutils 9.5)
+                *in_word = true;
+                words += 1;
+            }
+        }
+    }
+    words
+}
@@ -32,8 +32,8 @@ fn stdout() -> uucore::wasm_io::WasmStdout {
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn stdout() -> std::io::Stdout {
-    std::io::stdout()
+fn stdout() -> io::Stdout {
+    io::stdout()
 }
 
 #[cfg(target_family = "wasm")]
@@ -42,8 +42,8 @@ fn wc_stdin() -> uucore::wasm_io::WasmStdin {
 }
 
 #[cfg(not(target_family = "wasm"))]
-fn wc_stdin() -> std::io::Stdin {
-    std::io::stdin()
+fn wc_stdin() -> io::Stdin {
+    io::stdin()
 }
 
 use clap::{Arg, ArgAction, ArgMatches, Command, builder::ValueParser};
@@ -54,6 +54,7 @@ use uucore::{display::Quotable, translate};
 
 use uucore::{
     error::{FromIo, UError, UResult},
+    files0::files0,
     format_usage,
     hardware::{HardwareFeature, HasHardwareFeatures as _, SimdPolicy},
     parser::shortcut_value_parser::ShortcutValueParser,
@@ -62,7 +63,7 @@ use uucore::{
 };
 
 use crate::{
-    count_fast::{count_bytes_chars_and_lines_fast, count_bytes_fast},
+    count_fast::{count_bytes_chars_and_lines_fast, count_bytes_fast, count_words},
     countable::WordCountable,
     word_count::WordCount,
 };
@@ -605,7 +606,32 @@ fn process_chunk<
     current_len: &mut usize,
     in_word: &mut bool,
     posixly_correct: bool,
+    simd_allowed: bool,
 ) {
+    if !SHOW_MAX_LINE_LENGTH {
+        // Without -L nothing depends on the position within a line, so each
+        // count is taken over the whole chunk with a vectorized scan.
+        if SHOW_WORDS {
+            total.words += count_words(text, in_word, posixly_correct);
+        }
+        if SHOW_LINES {
+            total.lines += if simd_allowed {
+                bytecount::count(text.as_bytes(), b'\n')
+            } else {
+                bytecount::naive_count(text.as_bytes(), b'\n')
+            };
+        }
+        if SHOW_CHARS {
+            total.chars += if simd_allowed {
+                bytecount::num_chars(text.as_bytes())
+            } else {
+                bytecount::naive_num_chars(text.as_bytes())
+            };
+        }
+        total.bytes += text.len();
+        return;
+    }
+
     for ch in text.chars() {
         if SHOW_WORDS {
             let is_space = if posixly_correct {
@@ -681,6 +707,7 @@ fn word_count_from_reader_specialized<
     let mut in_word = false;
     let mut current_len = 0;
     let posixly_correct = env::var_os("POSIXLY_CORRECT").is_some();
+    let simd_allowed = wc_simd_allowed(SimdPolicy::detect());
     while let Some(chunk) = reader.next_strict() {
         match chunk {
             Ok(text) => {
@@ -690,6 +717,7 @@ fn word_count_from_reader_specialized<
                     &mut current_len,
                     &mut in_word,
                     posixly_correct,
+                    simd_allowed,
                 );
             }
             Err(e) => {
@@ -833,39 +861,12 @@ fn files0_iter<'a>(
     r: impl io::Read + 'static,
     err_path: OsString,
 ) -> impl Iterator<Item = InputIterItem<'a>> {
-    use std::io::BufRead;
-    let mut i = Some(
-        io::BufReader::new(r)
-            .split(b'\0')
-            .map(move |res| match res {
-                Ok(p) if p == STDIN_REPR.as_bytes() => Ok(Input::Stdin(StdinKind::Explicit)),
-                Ok(p) => {
-                    // On Unix systems, OsStrings are just strings of bytes, not necessarily UTF-8.
-                    #[cfg(unix)]
-                    {
-                        use std::os::unix::ffi::OsStringExt;
-                        Ok(Input::Path(PathBuf::from(OsString::from_vec(p)).into()))
-                    }
-
-                    // ...Windows does not, we must go through Strings.
-                    #[cfg(not(unix))]
-                    {
-                        let s = String::from_utf8(p).map_err(io::Error::other)?;
-                        Ok(Input::Path(PathBuf::from(s).into()))
-                    }
-                }
-                Err(e) => Err(e.map_err_context(
-                    || translate!("wc-error-read-error", "path" => escape_name_wrapper(&err_path)),
-                ) as Box<dyn UError>),
-            }),
-    );
-    // Loop until there is an error; yield that error and then nothing else.
-    iter::from_fn(move || {
-        let next = i.as_mut().and_then(Iterator::next);
-        if matches!(next, Some(Err(_)) | None) {
-            i = None;
-        }
-        next
+    files0(io::BufReader::new(r)).map(move |res| match res {
+        Ok(p) if p == STDIN_REPR => Ok(Input::Stdin(StdinKind::Explicit)),
+        Ok(p) => Ok(Input::Path(PathBuf::from(p).into())),
+        Err(e) => Err(e.map_err_context(
+            || translate!("wc-error-read-error", "path" => escape_name_wrapper(&err_path)),
+        ) as Box<dyn UError>),
     })
 }
 
@@ -1009,14 +1010,17 @@ fn wc(inputs: &Inputs, settings: &Settings) -> UResult<()> {
             }
         };
 
-        // Store any I/O error from reading to print AFTER stats (matches GNU wc behavior)
-        let (word_count, deferred_error) = match word_count_from_input(&input, settings) {
-            CountResult::Success(word_count) => (word_count, None),
-            CountResult::Interrupted(word_count, err) => (
-                word_count,
-                Some(err.map_err_context(|| input.path_display())),
-            ),
+        // A read error is reported as soon as it happens, after the lines
+        // printed so far and before the counts read until then, like GNU.
+        let word_count = match word_count_from_input(&input, settings) {
+            CountResult::Success(word_count) => word_count,
+            CountResult::Interrupted(word_count, err) => {
+                let _ = stdout().flush();
+                show!(err.map_err_context(|| input.path_display()));
+                word_count
+            }
             CountResult::Failure(err) => {
+                let _ = stdout().flush();
                 show!(err.map_err_context(|| input.path_display()));
                 continue;
             }
@@ -1030,11 +1034,6 @@ fn wc(inputs: &Inputs, settings: &Settings) -> UResult<()> {
                 show!(err.map_err_context(|| translate!("wc-error-failed-to-print-result", "title" => title.to_string_lossy())));
             }
         }
-        // Print deferred error after stats to match GNU wc output order
-        if let Some(err) = deferred_error {
-            let _ = stdout().flush();
-            show!(err);
-        }
     }
 
     if settings.total_when.is_total_row_visible(num_inputs) {
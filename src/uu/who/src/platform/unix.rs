@@ -12,6 +12,7 @@ use uucore::display::Quotable;
 use uucore::error::{FromIo, UResult};
 use uucore::libc::{S_IWGRP, STDIN_FILENO, ttyname};
 use uucore::translate;
+use uucore::uptime::{get_container_boot_time, get_kernel_boot_time};
 
 use uucore::utmpx::{self, UtmpxRecord, time};
 
@@ -161,6 +162,10 @@ fn idle_string<'a>(when: i64, boottime: i64) -> Cow<'a, str> {
 }
 
 fn time_string(ut: &UtmpxRecord) -> String {
+    format_time(ut.login_time())
+}
+
+fn format_time(time: time::OffsetDateTime) -> String {
     let lc_time = std::env::var("LC_ALL")
         .or_else(|_| std::env::var("LC_TIME"))
         .or_else(|_| std::env::var("LANG"))
@@ -174,7 +179,18 @@ fn time_string(ut: &UtmpxRecord) -> String {
         // "%Y-%m-%d %H:%M"
         time::format_description::parse("[year]-[month]-[day] [hour]:[minute]").unwrap()
     };
-    ut.login_time().format(&time_format).unwrap()
+    time.format(&time_format).unwrap()
+}
+
+/// The start of the container we run in, or else the boot time as known by
+/// the kernel, in the local time zone.
+fn kernel_boot_time() -> Option<time::OffsetDateTime> {
+    #[allow(clippy::unnecessary_cast)]
+    let boot_time = get_container_boot_time().or_else(get_kernel_boot_time)? as i64;
+    let boot_time = time::OffsetDateTime::from_unix_timestamp(boot_time).ok()?;
+    let local_offset = time::OffsetDateTime::now_local()
+        .map_or_else(|_| time::UtcOffset::UTC, time::OffsetDateTime::offset);
+    Some(boot_time.to_offset(local_offset))
 }
 
 #[inline]
@@ -224,7 +240,9 @@ impl Who {
                 String::new()
             };
 
+            let mut boot_time_seen = false;
             for ut in records {
+                boot_time_seen |= ut.record_type() == utmpx::BOOT_TIME;
                 if !self.my_line_only || cur_tty == ut.tty_device() {
                     if self.need_users && ut.is_user_process() {
                         self.print_user(&ut)?;
@@ -235,7 +253,9 @@ impl Who {
                                     self.print_runlevel(&ut);
                                 }
                             }
-                            utmpx::BOOT_TIME if self.need_boottime => self.print_boottime(&ut),
+                            utmpx::BOOT_TIME if self.need_boottime => {
+                                self.print_boottime(&time_string(&ut));
+                            }
                             utmpx::NEW_TIME if self.need_clockchange => self.print_clockchange(&ut),
                             utmpx::INIT_PROCESS if self.need_initspawn => self.print_initspawn(&ut),
                             utmpx::LOGIN_PROCESS if self.need_login => self.print_login(&ut),
@@ -244,8 +264,15 @@ impl Who {
                         }
                     }
                 }
+            }
 
-                if ut.record_type() == utmpx::BOOT_TIME {}
+            // Containers usually have no BOOT_TIME record, so ask the kernel
+            // or the container's PID 1 instead, unless a file was given
+            // explicitly.
+            if self.need_boottime && !boot_time_seen && !self.my_line_only && self.args.len() != 1 {
+                if let Some(boot_time) = kernel_boot_time() {
+                    self.print_boottime(&format_time(boot_time));
+                }
             }
         }
         Ok(())
@@ -336,12 +363,12 @@ impl Who {
     }
 
     #[inline]
-    fn print_boottime(&self, ut: &UtmpxRecord) {
+    fn print_boottime(&self, time: &str) {
         self.print_line(
             "",
             ' ',
             &translate!("who-system-boot"),
-            &time_string(ut),
+            time,
             "",
             "",
             "",
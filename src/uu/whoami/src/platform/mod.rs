@@ -11,8 +11,14 @@ pub use self::unix::get_username;
 #[cfg(windows)]
 pub use self::windows::get_username;
 
+#[cfg(target_family = "wasm")]
+pub use self::wasm::get_username;
+
 #[cfg(unix)]
 mod unix;
 
 #[cfg(windows)]
 mod windows;
+
+#[cfg(target_family = "wasm")]
+mod wasm;
@@ -0,0 +1,17 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+use std::ffi::OsString;
+use std::io;
+
+use uucore::entries::uid2usr;
+use uucore::wasm_io;
+
+pub fn get_username() -> io::Result<OsString> {
+    // The user running the builtin is only known through the host's
+    // identity hooks.
+    let uid = wasm_io::uid().ok_or_else(|| io::Error::from(io::ErrorKind::NotFound))?;
+    uid2usr(uid).map(Into::into)
+}
@@ -8,10 +8,15 @@
 use clap::{Arg, ArgAction, Command, builder::ValueParser};
 use std::error::Error;
 use std::ffi::OsString;
+#[cfg(not(target_family = "wasm"))]
+use std::io::stdout;
 use std::io::{self, Write};
-use uucore::error::{UResult, USimpleError};
+use uucore::cancel::{INTERRUPTED_EXIT_CODE, is_cancelled};
+use uucore::error::{ExitCode, UResult, USimpleError, strip_errno};
 use uucore::format_usage;
 use uucore::translate;
+#[cfg(target_family = "wasm")]
+use uucore::wasm_io::stdout;
 
 // it's possible that using a smaller or larger buffer might provide better performance on some
 // systems, but honestly this is good enough
@@ -30,9 +35,12 @@ pub fn uumain(args: impl uucore::Args) -> UResult<()> {
         // On Windows, silently handle broken pipe since there's no SIGPIPE
         #[cfg(windows)]
         Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::Interrupted && is_cancelled() => {
+            Err(ExitCode::new(INTERRUPTED_EXIT_CODE))
+        }
         Err(err) => Err(USimpleError::new(
             1,
-            translate!("yes-error-standard-output", "error" => err),
+            translate!("yes-error-standard-output", "error" => strip_errno(&err)),
         )),
     }
 }
@@ -67,10 +75,14 @@ fn args_into_buffer<'a>(
     {
         #[cfg(unix)]
         use std::os::unix::ffi::OsStrExt;
+
+        #[cfg(unix)]
+        let as_bytes = |a: &'a OsString| a.as_bytes();
+        // `std::os::wasi` is unstable on preview 2, but the encoded bytes are the raw bytes
         #[cfg(target_os = "wasi")]
-        use std::os::wasi::ffi::OsStrExt;
+        let as_bytes = |a: &'a OsString| a.as_encoded_bytes();
 
-        for part in itertools::intersperse(i.map(|a| a.as_bytes()), b" ") {
+        for part in itertools::intersperse(i.map(as_bytes), b" ") {
             buf.extend_from_slice(part);
         }
     }
@@ -112,11 +124,16 @@ fn prepare_buffer(buf: &mut Vec<u8>) {
 }
 
 pub fn exec(bytes: &[u8]) -> io::Result<()> {
-    let stdout = io::stdout();
+    let stdout = stdout();
     let mut stdout = stdout.lock();
 
     loop {
         stdout.write_all(bytes)?;
+        // `write_all` retries on `Interrupted`, so this can only come from
+        // the host asking us to stop.
+        if is_cancelled() {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
     }
 }
 
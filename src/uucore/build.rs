@@ -11,6 +11,17 @@ use std::path::{Path, PathBuf};
 pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let out_dir = env::var("OUT_DIR")?;
 
+    // Build details reported by `--version`, see `uucore::version`
+    println!("cargo:rustc-env=UUCORE_TARGET={}", env::var("TARGET")?);
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort_unstable();
+    println!("cargo:rustc-env=UUCORE_FEATURES={}", features.join(","));
+
     let mut embedded_file = File::create(Path::new(&out_dir).join("embedded_locales.rs"))?;
     writeln!(embedded_file, "// Generated at compile time - do not edit")?;
     writeln!(
@@ -29,6 +29,7 @@ error-io = I/O error
 error-permission-denied = Permission denied
 error-file-not-found = No such file or directory
 error-invalid-argument = Invalid argument
+error-write = write error
 error-is-a-directory = { $file }: Is a directory
 
 # Common actions
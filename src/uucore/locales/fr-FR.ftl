@@ -29,6 +29,7 @@ error-io = Erreur E/S
 error-permission-denied = Permission refusée
 error-file-not-found = Aucun fichier ou répertoire de ce type
 error-invalid-argument = Argument invalide
+error-write = erreur d'écriture
 error-is-a-directory = { $file }: Est un répertoire
 
 # Actions communes
@@ -16,12 +16,16 @@ pub mod buf_copy;
 pub mod checksum;
 #[cfg(feature = "colors")]
 pub mod colors;
+#[cfg(feature = "copy")]
+pub mod copy;
 #[cfg(feature = "encoding")]
 pub mod encoding;
 #[cfg(feature = "extendedbigdecimal")]
 pub mod extendedbigdecimal;
 #[cfg(feature = "fast-inc")]
 pub mod fast_inc;
+#[cfg(feature = "files0")]
+pub mod files0;
 #[cfg(feature = "format")]
 pub mod format;
 #[cfg(feature = "fs")]
@@ -30,6 +34,8 @@ pub mod fs;
 pub mod fsext;
 #[cfg(feature = "i18n-common")]
 pub mod i18n;
+#[cfg(feature = "key-cmp")]
+pub mod key_cmp;
 #[cfg(feature = "lines")]
 pub mod lines;
 #[cfg(any(
@@ -39,8 +45,12 @@ pub mod lines;
     feature = "parser-glob"
 ))]
 pub mod parser;
+#[cfg(feature = "progress")]
+pub mod progress;
 #[cfg(feature = "quoting-style")]
 pub mod quoting_style;
+#[cfg(feature = "rand-source")]
+pub mod rand_source;
 #[cfg(feature = "ranges")]
 pub mod ranges;
 #[cfg(feature = "ringbuffer")]
@@ -66,7 +76,7 @@ pub mod mode;
 pub mod wasm_io;
 
 // ** unix-only
-#[cfg(all(unix, feature = "entries"))]
+#[cfg(all(any(unix, target_family = "wasm"), feature = "entries"))]
 pub mod entries;
 #[cfg(all(unix, feature = "perms"))]
 pub mod perms;
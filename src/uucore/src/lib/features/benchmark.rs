@@ -50,6 +50,51 @@ pub fn setup_test_file(data: &[u8]) -> PathBuf {
     file_path
 }
 
+/// Run a utility function the way a WASM host does, inside
+/// [`crate::wasm_io::with_wasm_io`] with `input` as stdin
+/// Returns the exit code and the number of writes that reached the host
+#[cfg(target_family = "wasm")]
+pub fn run_util_function_wasm_io<F>(
+    util_func: F,
+    args: &[&str],
+    input: &'static [u8],
+    output_buffer_size: usize,
+) -> (i32, usize)
+where
+    F: FnOnce(std::vec::IntoIter<std::ffi::OsString>) -> i32,
+{
+    use crate::wasm_io;
+    use std::cell::Cell;
+    use std::io;
+    use std::rc::Rc;
+
+    /// Stands in for the host's output stream, counting the calls into it
+    struct HostSink(Rc<Cell<usize>>);
+
+    impl Write for HostSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.set(self.0.get() + 1);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let host_writes = Rc::new(Cell::new(0));
+    let code = wasm_io::with_wasm_io(
+        Box::new(input),
+        Box::new(HostSink(host_writes.clone())),
+        Box::new(HostSink(host_writes.clone())),
+        || {
+            wasm_io::set_output_buffer_size(output_buffer_size);
+            run_util_function(util_func, args)
+        },
+    );
+    (code, host_writes.get())
+}
+
 /// Generate test data with different characteristics for text processing utilities
 pub mod text_data {
     /// Generate test data with a specific size in MB and average line length
@@ -5,7 +5,7 @@
 
 // spell-checker:ignore bitlen
 
-use std::ffi::{OsStr, OsString};
+use std::ffi::OsStr;
 use std::fs::File;
 use std::io::{self, BufReader, Read, Write};
 use std::path::Path;
@@ -80,15 +80,26 @@ impl OutputFormat {
     }
 
     /// Find the correct output format for cksum.
-    pub fn from_cksum(algo: AlgoKind, tag: bool, binary: bool, raw: bool, base64: bool) -> Self {
+    ///
+    /// cksum has a bunch of legacy behavior around `--tag`, `--untagged`,
+    /// `--binary` and `--text`, which depends on the order they appear in
+    /// `args`, the utility's command line.
+    pub fn from_cksum<S: AsRef<OsStr>>(
+        algo: AlgoKind,
+        args: impl Iterator<Item = S>,
+        raw: bool,
+        base64: bool,
+    ) -> UResult<Self> {
+        let (tag, binary) = cksum_tag_and_binary(args)?;
+
         // Raw output format takes precedence over anything else.
         if raw {
-            return Self::Raw;
+            return Ok(Self::Raw);
         }
 
         // Then, if the algo is legacy, takes precedence over the rest
         if algo.is_legacy() {
-            return Self::Legacy;
+            return Ok(Self::Legacy);
         }
 
         let digest_format = if base64 {
@@ -99,14 +110,14 @@ impl OutputFormat {
 
         // After that, decide between tagged and untagged output
         if tag {
-            Self::Tagged(digest_format)
+            Ok(Self::Tagged(digest_format))
         } else {
             let reading_mode = if binary {
                 ReadingMode::Binary
             } else {
                 ReadingMode::Text
             };
-            Self::Untagged(digest_format, reading_mode)
+            Ok(Self::Untagged(digest_format, reading_mode))
         }
     }
 
@@ -115,19 +126,20 @@ impl OutputFormat {
     ///
     /// Since standalone utils can't use the Raw or Legacy output format, it is
     /// decided only using the --tag, --binary and --text arguments.
-    pub fn from_standalone(args: impl Iterator<Item = OsString>) -> UResult<Self> {
+    pub fn from_standalone<S: AsRef<OsStr>>(args: impl Iterator<Item = S>) -> UResult<Self> {
         let mut text = true;
         let mut tag = false;
 
         for arg in args {
+            let arg = arg.as_ref();
             if arg == "--" {
                 break;
-            } else if arg == "--tag" {
+            } else if is_long_option(arg, "tag") {
                 tag = true;
                 text = false;
-            } else if arg == "--binary" || arg == "-b" {
+            } else if is_long_option(arg, "binary") || arg == "-b" {
                 text = false;
-            } else if arg == "--text" || arg == "-t" {
+            } else if is_long_option(arg, "text") || arg == "-t" {
                 // Finding a `--text` after `--tag` is an error.
                 if tag {
                     return Err(ChecksumError::TextAfterTag.into());
@@ -151,6 +163,52 @@ impl OutputFormat {
     }
 }
 
+/// Returns whether cksum should use the tagged output format, and whether it
+/// should use the binary flag in the untagged case.
+fn cksum_tag_and_binary<S: AsRef<OsStr>>(args: impl Iterator<Item = S>) -> UResult<(bool, bool)> {
+    let mut tag = true;
+    let mut binary = false;
+    let mut text = false;
+
+    // --binary, --tag and --untagged are tight together: none of them
+    // conflicts with each other but --tag will reset "binary" and "text" and
+    // set "tag".
+
+    for arg in args {
+        let arg = arg.as_ref();
+        if arg == "--" {
+            break;
+        } else if arg == "-b" || is_long_option(arg, "binary") {
+            text = false;
+            binary = true;
+        } else if arg == "-t" || is_long_option(arg, "text") {
+            text = true;
+            binary = false;
+        } else if is_long_option(arg, "tag") {
+            tag = true;
+            binary = false;
+            text = false;
+        } else if is_long_option(arg, "untagged") {
+            tag = false;
+        }
+    }
+
+    // Specifying --text without ever mentioning --untagged fails.
+    if text && tag {
+        return Err(ChecksumError::TextWithoutUntagged.into());
+    }
+
+    Ok((tag, binary))
+}
+
+/// Whether `arg` is the long option `name`, or an abbreviation of it. clap
+/// already rejected the ambiguous ones.
+fn is_long_option(arg: &OsStr, name: &str) -> bool {
+    arg.to_str()
+        .and_then(|arg| arg.strip_prefix("--"))
+        .is_some_and(|prefix| !prefix.is_empty() && name.starts_with(prefix))
+}
+
 fn print_legacy_checksum(
     options: &ChecksumComputeOptions,
     filename: &OsStr,
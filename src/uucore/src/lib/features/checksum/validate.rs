@@ -7,10 +7,12 @@
 
 use crate::util_name;
 
+use std::collections::VecDeque;
 use std::ffi::OsStr;
 use std::fmt::Display;
 use std::fs::File;
 use std::io::{self, BufReader, Read, Write, stderr, stdin};
+use std::sync::mpsc::{Receiver, channel};
 
 use os_display::Quotable;
 
@@ -70,6 +72,8 @@ pub struct ChecksumValidateOptions {
     pub ignore_missing: bool,
     pub strict: bool,
     pub verbose: ChecksumVerbose,
+    /// Stop at the first line that makes the check fail.
+    pub fail_fast: bool,
 }
 
 /// This structure holds the count of checksum test lines' outcomes.
@@ -126,6 +130,36 @@ impl From<ChecksumError> for LineCheckError {
     }
 }
 
+/// What checking a line prints, kept until the lines before it are printed,
+/// so that the output follows the checksum file even when the files are
+/// read in parallel.
+#[derive(Default)]
+struct LineReport {
+    warnings: Vec<String>,
+    errors: Vec<Box<dyn UError>>,
+    stdout: Vec<u8>,
+}
+
+impl LineReport {
+    fn print(self) {
+        for warning in self.warnings {
+            let _ = writeln!(stderr(), "{}: {warning}", util_name());
+        }
+        for err in self.errors {
+            show!(err);
+        }
+        let _ = io::stdout().write_all(&self.stdout);
+    }
+}
+
+/// The outcome of checking a line, and what to print for it.
+type LineOutcome = (Result<(), LineCheckError>, LineReport);
+
+/// Lines whose file is being digested, per thread of the pool. Their
+/// outcomes wait in memory until the lines before them are printed.
+#[cfg(not(target_family = "wasm"))]
+const LINES_IN_FLIGHT_PER_THREAD: usize = 4;
+
 /// Represents an error that was encountered when processing a checksum file.
 enum FileCheckError {
     /// a generic UError was encountered in sub-functions
@@ -533,23 +567,24 @@ fn get_raw_expected_digest(checksum: &str, byte_len_hint: Option<usize>) -> Opti
 fn get_file_to_check(
     filename: &OsStr,
     opts: ChecksumValidateOptions,
+    report: &mut LineReport,
 ) -> Result<Box<dyn Read>, LineCheckError> {
     let filename_bytes = os_str_as_bytes(filename).map_err(|e| LineCheckError::UError(e.into()))?;
 
     if filename == "-" {
         Ok(Box::new(stdin())) // Use stdin if "-" is specified in the checksum file
     } else {
-        let failed_open = || {
+        let failed_open = |report: &mut LineReport| {
             write_file_report(
-                io::stdout(),
+                &mut report.stdout,
                 filename_bytes,
                 FileChecksumResult::CantOpen,
                 "",
                 opts.verbose,
             );
         };
-        let print_error = |err: io::Error| {
-            show!(err.map_err_context(|| {
+        let print_error = |report: &mut LineReport, err: io::Error| {
+            report.errors.push(err.map_err_context(|| {
                 locale_aware_escape_name(filename, QuotingStyle::SHELL_ESCAPE)
                     // This is non destructive thanks to the escaping
                     .to_string_lossy()
@@ -562,12 +597,12 @@ fn get_file_to_check(
                     .map_err(|_| LineCheckError::CantOpenFile)?
                     .is_dir()
                 {
-                    print_error(io::Error::new(
-                        io::ErrorKind::IsADirectory,
-                        "Is a directory",
-                    ));
+                    print_error(
+                        report,
+                        io::Error::new(io::ErrorKind::IsADirectory, "Is a directory"),
+                    );
                     // also regarded as a failed open
-                    failed_open();
+                    failed_open(report);
                     Err(LineCheckError::FileIsDirectory)
                 } else {
                     Ok(Box::new(f))
@@ -576,8 +611,8 @@ fn get_file_to_check(
             Err(err) => {
                 if !opts.ignore_missing {
                     // yes, we have both stderr and stdout here
-                    print_error(err);
-                    failed_open();
+                    print_error(report, err);
+                    failed_open(report);
                 }
                 // we could not open the file but we want to continue
                 Err(LineCheckError::FileNotFound)
@@ -663,6 +698,37 @@ fn identify_algo_name_and_length(
     Ok((line_algo, bytes))
 }
 
+/// The file named by a checksum line and the digest it should have. Reading
+/// and digesting the file is the slow part of checking a line, so it is done
+/// apart from parsing the line, possibly on another thread.
+struct CheckJob {
+    filename: Vec<u8>,
+    expected_checksum: Vec<u8>,
+    algo: SizedAlgoKind,
+}
+
+impl CheckJob {
+    /// Compute the digest of the file and compare it with the expected one.
+    fn run(self, opts: ChecksumValidateOptions) -> LineOutcome {
+        let mut report = LineReport::default();
+        let result = compute_and_check_digest_from_file(
+            &self.filename,
+            &self.expected_checksum,
+            self.algo,
+            opts,
+            &mut report,
+        );
+        (result, report)
+    }
+
+    /// Whether the file is standard input, which only the thread reading the
+    /// checksum file may read, in case that is standard input too.
+    #[cfg(not(target_family = "wasm"))]
+    fn reads_stdin(&self) -> bool {
+        self.filename == b"-"
+    }
+}
+
 /// Given a filename and an algorithm, compute the digest and compare it with
 /// the expected one.
 fn compute_and_check_digest_from_file(
@@ -670,12 +736,13 @@ fn compute_and_check_digest_from_file(
     expected_checksum: &[u8],
     algo: SizedAlgoKind,
     opts: ChecksumValidateOptions,
+    report: &mut LineReport,
 ) -> Result<(), LineCheckError> {
     let (filename_to_check_unescaped, prefix) = unescape_filename(filename);
     let real_filename_to_check = os_str_from_bytes(&filename_to_check_unescaped)?;
 
     // Open the input file
-    let file_to_check = get_file_to_check(&real_filename_to_check, opts)?;
+    let file_to_check = get_file_to_check(&real_filename_to_check, opts, report)?;
     let mut file_reader = BufReader::new(file_to_check);
 
     // Read the file and calculate the checksum
@@ -687,14 +754,14 @@ fn compute_and_check_digest_from_file(
         match digest_reader(&mut digest, &mut file_reader, ReadingMode::Text) {
             Ok(result) => result,
             Err(err) => {
-                show!(err.map_err_context(|| {
+                report.errors.push(err.map_err_context(|| {
                     locale_aware_escape_name(&real_filename_to_check, QuotingStyle::SHELL_ESCAPE)
                         .to_string_lossy()
                         .to_string()
                 }));
 
                 write_file_report(
-                    io::stdout(),
+                    &mut report.stdout,
                     filename,
                     FileChecksumResult::CantOpen,
                     prefix,
@@ -711,7 +778,7 @@ fn compute_and_check_digest_from_file(
         DigestOutput::U16(n) => n.to_be_bytes() == expected_checksum,
     };
     write_file_report(
-        io::stdout(),
+        &mut report.stdout,
         filename,
         FileChecksumResult::from_bool(checksum_correct),
         prefix,
@@ -729,9 +796,8 @@ fn compute_and_check_digest_from_file(
 fn process_algo_based_line(
     line_info: &LineInfo,
     cli_algo_kind: Option<AlgoKind>,
-    opts: ChecksumValidateOptions,
     last_algo: &mut Option<String>,
-) -> Result<(), LineCheckError> {
+) -> Result<CheckJob, LineCheckError> {
     let filename_to_check = line_info.filename.as_slice();
 
     let (algo_kind, algo_byte_len) =
@@ -749,7 +815,11 @@ fn process_algo_based_line(
 
     let algo = SizedAlgoKind::from_unsized(algo_kind, algo_byte_len)?;
 
-    compute_and_check_digest_from_file(filename_to_check, &expected_checksum, algo, opts)
+    Ok(CheckJob {
+        filename: filename_to_check.to_vec(),
+        expected_checksum,
+        algo,
+    })
 }
 
 /// Check a digest checksum with non-algo based pre-treatment.
@@ -758,8 +828,7 @@ fn process_non_algo_based_line(
     line_info: &LineInfo,
     cli_algo_kind: AlgoKind,
     cli_algo_length: Option<usize>,
-    opts: ChecksumValidateOptions,
-) -> Result<(), LineCheckError> {
+) -> Result<CheckJob, LineCheckError> {
     let mut filename_to_check = line_info.filename.as_slice();
     if filename_to_check.starts_with(b"*")
         && line_number == 0
@@ -785,24 +854,25 @@ fn process_non_algo_based_line(
 
     let algo = SizedAlgoKind::from_unsized(algo_kind, algo_byte_len)?;
 
-    compute_and_check_digest_from_file(filename_to_check, &expected_checksum, algo, opts)
+    Ok(CheckJob {
+        filename: filename_to_check.to_vec(),
+        expected_checksum,
+        algo,
+    })
 }
 
-/// Parses a checksum line, detect the algorithm to use, read the file and produce
-/// its digest, and compare it to the expected value.
+/// Parses a checksum line and detects the algorithm to use.
 ///
-/// Returns `Ok(bool)` if the comparison happened, bool indicates if the digest
-/// matched the expected.
-/// If the comparison didn't happen, return a `LineChecksumError`.
+/// Returns the file to digest and the digest it should have.
+/// If there is nothing to compare, return a `LineChecksumError`.
 fn process_checksum_line(
     line: &OsStr,
     i: usize,
     cli_algo_name: Option<AlgoKind>,
     cli_algo_length: Option<usize>,
-    opts: ChecksumValidateOptions,
     cached_line_format: &mut Option<LineFormat>,
     last_algo: &mut Option<String>,
-) -> Result<(), LineCheckError> {
+) -> Result<CheckJob, LineCheckError> {
     let line_bytes = os_str_as_bytes(line).map_err(|e| LineCheckError::UError(Box::new(e)))?;
 
     // Early return on empty or commented lines.
@@ -817,25 +887,184 @@ fn process_checksum_line(
     };
 
     if line_info.format == LineFormat::AlgoBased {
-        process_algo_based_line(&line_info, cli_algo_name, opts, last_algo)
+        process_algo_based_line(&line_info, cli_algo_name, last_algo)
     } else if let Some(cli_algo) = cli_algo_name {
         // If we match a non-algo based parser, we expect a cli argument
         // to give us the algorithm to use
-        process_non_algo_based_line(i, &line_info, cli_algo, cli_algo_length, opts)
+        process_non_algo_based_line(i, &line_info, cli_algo, cli_algo_length)
     } else {
         // We have no clue of what algorithm to use
         Err(LineCheckError::ImproperlyFormatted)
     }
 }
 
+/// The state of checking the lines of one checksum file.
+struct FileCheck<'a> {
+    filename_input: &'a OsStr,
+    cli_algo_kind: Option<AlgoKind>,
+    cli_algo_length: Option<usize>,
+    opts: ChecksumValidateOptions,
+    res: ChecksumResult,
+    // cached_line_format is used to ensure that several non algo-based checksum line
+    // will use the same parser.
+    cached_line_format: Option<LineFormat>,
+    // last_algo caches the algorithm used in the last line to print a warning
+    // message for the current line if improperly formatted.
+    // Behavior tested in gnu_cksum_c::test_warn
+    last_algo: Option<String>,
+    /// Whether `--fail-fast` stopped the check before the last line.
+    stopped: bool,
+}
+
+impl FileCheck<'_> {
+    /// Parses line `i`, and returns the file to digest for it, or its outcome
+    /// if there is none.
+    fn parse_line(&mut self, line: &OsStr, i: usize) -> Result<CheckJob, LineOutcome> {
+        let result = process_checksum_line(
+            line,
+            i,
+            self.cli_algo_kind,
+            self.cli_algo_length,
+            &mut self.cached_line_format,
+            &mut self.last_algo,
+        );
+        result.map_err(|err| {
+            let mut report = LineReport::default();
+            if matches!(err, LineCheckError::ImproperlyFormatted)
+                && self.opts.verbose.at_least_warning()
+            {
+                let algo = if let Some(algo_name_input) = self.cli_algo_kind {
+                    algo_name_input.to_uppercase()
+                } else if let Some(algo) = &self.last_algo {
+                    algo.as_str()
+                } else {
+                    "Unknown algorithm"
+                };
+                report.warnings.push(translate!("checksum-error-algo-bad-format", "file" => self.filename_input.maybe_quote(), "line" => i + 1, "algo" => algo));
+            }
+            (Err(err), report)
+        })
+    }
+
+    /// Prints the outcome of a line and counts it. Returns whether to go on
+    /// with the next lines.
+    fn record(&mut self, (line_result, report): LineOutcome) -> Result<bool, FileCheckError> {
+        report.print();
+
+        // Match a first time to elude critical UErrors, and increment the total
+        // in all cases except on skipped.
+        use LineCheckError::*;
+        match line_result {
+            Err(UError(e)) => return Err(e.into()),
+            Err(Skipped) => (),
+            _ => self.res.total += 1,
+        }
+
+        // Match a second time to update the right field of `res`, and see
+        // whether the line makes the check fail.
+        let res = &mut self.res;
+        let failed = match line_result {
+            Ok(()) => {
+                res.correct += 1;
+                false
+            }
+            Err(DigestMismatch) => {
+                res.failed_cksum += 1;
+                true
+            }
+            Err(ImproperlyFormatted) => {
+                res.bad_format += 1;
+                self.opts.strict
+            }
+            Err(CantOpenFile | FileIsDirectory) => {
+                res.failed_open_file += 1;
+                true
+            }
+            Err(FileNotFound) if !self.opts.ignore_missing => {
+                res.failed_open_file += 1;
+                true
+            }
+            _ => false,
+        };
+
+        if failed && self.opts.fail_fast {
+            self.stopped = true;
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Checks the lines of the checksum file. `start` either runs the job of
+    /// line `i` and returns its outcome, or has it run elsewhere and sent to
+    /// `done` as `(i, outcome)`.
+    fn check_lines<R: Read>(
+        &mut self,
+        reader: BufReader<R>,
+        mut start: impl FnMut(usize, CheckJob) -> Option<LineOutcome>,
+        done: &Receiver<(usize, LineOutcome)>,
+        max_in_flight: usize,
+    ) -> Result<(), FileCheckError> {
+        // The outcomes of the lines from `first` on, which are not printed yet.
+        let mut pending = VecDeque::new();
+        let mut first = 0;
+
+        for (i, line_res) in read_os_string_lines(reader).enumerate() {
+            let line = line_res.map_err(|e| {
+                USimpleError::new(
+                    UIoError::from(e).code(),
+                    format!("{}: read error", self.filename_input.maybe_quote()),
+                )
+            })?;
+
+            pending.push_back(match self.parse_line(&line, i) {
+                Ok(job) => start(i, job),
+                Err(outcome) => Some(outcome),
+            });
+            if !self.print_ready(&mut pending, &mut first, done, max_in_flight)? {
+                return Ok(());
+            }
+        }
+        self.print_ready(&mut pending, &mut first, done, 1)?;
+        Ok(())
+    }
+
+    /// Prints the outcomes at the front of `pending`, waiting for more while
+    /// `max_pending` or more lines are not printed. Returns whether to go on.
+    fn print_ready(
+        &mut self,
+        pending: &mut VecDeque<Option<LineOutcome>>,
+        first: &mut usize,
+        done: &Receiver<(usize, LineOutcome)>,
+        max_pending: usize,
+    ) -> Result<bool, FileCheckError> {
+        loop {
+            for (i, outcome) in done.try_iter() {
+                pending[i - *first] = Some(outcome);
+            }
+            while let Some(Some(_)) = pending.front() {
+                let outcome = pending.pop_front().flatten().unwrap();
+                *first += 1;
+                if !self.record(outcome)? {
+                    return Ok(false);
+                }
+            }
+            if pending.len() < max_pending {
+                return Ok(true);
+            }
+            // The sender is kept by the caller, so this only waits.
+            if let Ok((i, outcome)) = done.recv() {
+                pending[i - *first] = Some(outcome);
+            }
+        }
+    }
+}
+
 fn process_checksum_file(
     filename_input: &OsStr,
     cli_algo_kind: Option<AlgoKind>,
     cli_algo_length: Option<usize>,
     opts: ChecksumValidateOptions,
 ) -> Result<(), FileCheckError> {
-    let mut res = ChecksumResult::default();
-
     let input_is_stdin = filename_input == OsStr::new("-");
 
     let file: Box<dyn Read> = if input_is_stdin {
@@ -854,68 +1083,26 @@ fn process_checksum_file(
 
     let reader = BufReader::new(file);
 
-    // cached_line_format is used to ensure that several non algo-based checksum line
-    // will use the same parser.
-    let mut cached_line_format = None;
-    // last_algo caches the algorithm used in the last line to print a warning
-    // message for the current line if improperly formatted.
-    // Behavior tested in gnu_cksum_c::test_warn
-    let mut last_algo = None;
-
-    for (i, line_res) in read_os_string_lines(reader).enumerate() {
-        let line = line_res.map_err(|e| {
-            USimpleError::new(
-                UIoError::from(e).code(),
-                format!("{}: read error", filename_input.maybe_quote()),
-            )
-        })?;
-
-        let line_result = process_checksum_line(
-            &line,
-            i,
-            cli_algo_kind,
-            cli_algo_length,
-            opts,
-            &mut cached_line_format,
-            &mut last_algo,
-        );
-
-        // Match a first time to elude critical UErrors, and increment the total
-        // in all cases except on skipped.
-        use LineCheckError::*;
-        match line_result {
-            Err(UError(e)) => return Err(e.into()),
-            Err(Skipped) => (),
-            _ => res.total += 1,
-        }
-
-        // Match a second time to update the right field of `res`.
-        match line_result {
-            Ok(()) => res.correct += 1,
-            Err(DigestMismatch) => res.failed_cksum += 1,
-            Err(ImproperlyFormatted) => {
-                res.bad_format += 1;
+    let mut check = FileCheck {
+        filename_input,
+        cli_algo_kind,
+        cli_algo_length,
+        opts,
+        res: ChecksumResult::default(),
+        cached_line_format: None,
+        last_algo: None,
+        stopped: false,
+    };
+    check_lines_with_pool(&mut check, reader)?;
+    let res = check.res;
 
-                if opts.verbose.at_least_warning() {
-                    let algo = if let Some(algo_name_input) = cli_algo_kind {
-                        algo_name_input.to_uppercase()
-                    } else if let Some(algo) = &last_algo {
-                        algo.as_str()
-                    } else {
-                        "Unknown algorithm"
-                    };
-                    let _ = writeln!(
-                        stderr(),
-                        "{}: {}",
-                        util_name(),
-                        translate!("checksum-error-algo-bad-format", "file" => filename_input.maybe_quote(), "line" => i + 1, "algo" => algo)
-                    );
-                }
-            }
-            Err(CantOpenFile | FileIsDirectory) => res.failed_open_file += 1,
-            Err(FileNotFound) if !opts.ignore_missing => res.failed_open_file += 1,
-            _ => (),
+    // --fail-fast stops at a line that makes the check fail; summing up the
+    // lines that were not checked would be misleading.
+    if check.stopped {
+        if opts.verbose.over_status() {
+            print_cksum_report(&res);
         }
+        return Err(FileCheckError::Failed);
     }
 
     let filename_display = || {
@@ -967,6 +1154,54 @@ fn process_checksum_file(
     Ok(())
 }
 
+/// Checks the lines of a checksum file, digesting the files they name on the
+/// rayon pool when it has several threads.
+#[cfg(not(target_family = "wasm"))]
+fn check_lines_with_pool<R: Read>(
+    check: &mut FileCheck,
+    reader: BufReader<R>,
+) -> Result<(), FileCheckError> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let opts = check.opts;
+    let (tx, rx) = channel();
+    let threads = rayon::current_num_threads();
+    if threads < 2 {
+        return check.check_lines(reader, |_, job| Some(job.run(opts)), &rx, 1);
+    }
+
+    // Set once the outcomes of the jobs not started yet are no longer needed.
+    let cancelled = AtomicBool::new(false);
+    rayon::in_place_scope(|scope| {
+        let start = |i, job: CheckJob| {
+            if job.reads_stdin() {
+                return Some(job.run(opts));
+            }
+            let tx = tx.clone();
+            let cancelled = &cancelled;
+            scope.spawn(move |_| {
+                if !cancelled.load(Ordering::Relaxed) {
+                    let _ = tx.send((i, job.run(opts)));
+                }
+            });
+            None
+        };
+        let result = check.check_lines(reader, start, &rx, threads * LINES_IN_FLIGHT_PER_THREAD);
+        cancelled.store(true, Ordering::Relaxed);
+        result
+    })
+}
+
+#[cfg(target_family = "wasm")]
+fn check_lines_with_pool<R: Read>(
+    check: &mut FileCheck,
+    reader: BufReader<R>,
+) -> Result<(), FileCheckError> {
+    let opts = check.opts;
+    let (_tx, rx) = channel();
+    check.check_lines(reader, |_, job| Some(job.run(opts)), &rx, 1)
+}
+
 /// Do the checksum validation (can be strict or not)
 pub fn perform_checksum_validation<'a, I>(
     files: I,
@@ -987,6 +1222,9 @@ where
             Err(Failed | CantOpenChecksumFile) => failed = true,
             Ok(_) => (),
         }
+        if failed && opts.fail_fast {
+            break;
+        }
     }
 
     if failed {
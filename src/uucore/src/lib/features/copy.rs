@@ -0,0 +1,566 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+// spell-checker:ignore ficlone reflink sendfile lseek ENXIO EXDEV ETXTBSY
+
+//! Copying the contents of one file to another.
+//!
+//! [`copy_file`] uses the cheapest method the platform and the files allow,
+//! falling back to the next one when a method isn't supported:
+//!
+//! 1. a reflink (`FICLONE` on Linux), which shares the data instead of
+//!    copying it, unless [`Reflink::Never`] is asked for;
+//! 2. `copy_file_range` (Linux), which copies inside the kernel and lets the
+//!    file system offload the copy;
+//! 3. `sendfile` (Linux and Android);
+//! 4. reads and writes through a buffer.
+//!
+//! Holes in sparse sources are kept as requested by [`Sparse`], and
+//! [`copy_file_with_progress`] reports the bytes copied as it goes.
+//!
+//! This is the copy engine shared by `cp`, `mv` and `install`. Sources that
+//! are not regular files, e.g. character devices, are copied with reads and
+//! writes; for pipes, [`crate::buf_copy`] is usually faster.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, ErrorKind, Read, Write};
+#[cfg(unix)]
+use std::os::unix::fs::{FileExt, MetadataExt, OpenOptionsExt, PermissionsExt};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+/// The largest amount of data copied at once, which bounds the memory used
+/// for buffers and how often progress is reported.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// The buffer size for copies done with reads and writes.
+const BUFFER_SIZE: usize = 128 * 1024;
+
+/// Whether to share the data of the source instead of copying it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Reflink {
+    /// Always copy the data.
+    Never,
+    /// Share the data if the file system supports it, and copy it otherwise.
+    #[default]
+    Auto,
+    /// Share the data, and fail if that isn't possible.
+    Always,
+}
+
+/// How to treat holes in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sparse {
+    /// Write every byte, filling holes with zeros.
+    Never,
+    /// Keep the holes of sources that look sparse.
+    #[default]
+    Auto,
+    /// Turn every block of zeros into a hole, whether the source has holes or
+    /// not.
+    Always,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CopyOptions {
+    pub reflink: Reflink,
+    pub sparse: Sparse,
+}
+
+/// The method that ended up copying the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Reflink,
+    CopyFileRange,
+    Sendfile,
+    /// Reads and writes, also used to skip holes or blocks of zeros.
+    ReadWrite,
+}
+
+/// The result of a successful copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Copied {
+    /// The number of bytes of the source that were copied, holes included.
+    pub bytes: u64,
+    pub method: Method,
+}
+
+/// Copy the contents of `src` into `dst`.
+///
+/// Both files are used from their current position, which is expected to be
+/// their start, and `dst` is expected to be empty. `src` doesn't need to be a
+/// regular file, but only regular files get the faster methods and hole
+/// handling.
+pub fn copy_file(src: &File, dst: &File, options: CopyOptions) -> io::Result<Copied> {
+    copy_file_with_progress(src, dst, options, &mut |_| {})
+}
+
+/// Like [`copy_file`], calling `progress` with the number of bytes copied by
+/// each step, e.g. to advance a progress bar.
+pub fn copy_file_with_progress(
+    src: &File,
+    dst: &File,
+    options: CopyOptions,
+    progress: &mut dyn FnMut(u64),
+) -> io::Result<Copied> {
+    let metadata = src.metadata()?;
+
+    if options.reflink != Reflink::Never {
+        match reflink(src, dst) {
+            Ok(()) => {
+                progress(metadata.len());
+                return Ok(Copied {
+                    bytes: metadata.len(),
+                    method: Method::Reflink,
+                });
+            }
+            Err(err) if options.reflink == Reflink::Always => return Err(err),
+            Err(_) => {}
+        }
+    }
+
+    if metadata.is_file() {
+        #[cfg(unix)]
+        match options.sparse {
+            Sparse::Always => return copy_zeros_as_holes(src, dst, &metadata, progress),
+            Sparse::Auto if looks_sparse(&metadata) => {
+                return copy_data_regions(src, dst, &metadata, progress);
+            }
+            _ => {}
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(bytes) = copy_with_copy_file_range(src, dst, progress)? {
+            return Ok(Copied {
+                bytes,
+                method: Method::CopyFileRange,
+            });
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if let Some(bytes) = copy_with_sendfile(src, dst, progress)? {
+            return Ok(Copied {
+                bytes,
+                method: Method::Sendfile,
+            });
+        }
+    }
+
+    Ok(Copied {
+        bytes: copy_with_read_write(src, dst, progress)?,
+        method: Method::ReadWrite,
+    })
+}
+
+/// Copy the file at `from` to `to` in the manner of [`std::fs::copy`]: `to`
+/// is created or truncated and gets the permissions of `from`, which must be
+/// a regular file (or a symbolic link to one).
+pub fn copy_path(from: &Path, to: &Path, options: CopyOptions) -> io::Result<Copied> {
+    copy_path_with_progress(from, to, options, &mut |_| {})
+}
+
+/// Like [`copy_path`], calling `progress` as [`copy_file_with_progress`]
+/// does.
+pub fn copy_path_with_progress(
+    from: &Path,
+    to: &Path,
+    options: CopyOptions,
+    progress: &mut dyn FnMut(u64),
+) -> io::Result<Copied> {
+    let src = File::open(from)?;
+    let metadata = src.metadata()?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "the source path is neither a regular file nor a symlink to a regular file",
+        ));
+    }
+
+    let permissions = metadata.permissions();
+    let mut open_options = OpenOptions::new();
+    open_options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    open_options.mode(permissions.mode());
+    let dst = open_options.open(to)?;
+    if dst.metadata()?.is_file() {
+        dst.set_permissions(permissions)?;
+    }
+
+    copy_file_with_progress(&src, &dst, options, progress)
+}
+
+/// Make `dst` share the data of `src` without copying it, if the file system
+/// supports that.
+pub fn reflink(src: &File, dst: &File) -> io::Result<()> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        // SAFETY: both descriptors are open for as long as the borrows live.
+        if unsafe { libc::ioctl(dst.as_raw_fd(), libc::FICLONE, src.as_raw_fd()) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+        let _ = (src, dst);
+        Err(ErrorKind::Unsupported.into())
+    }
+}
+
+/// Whether a regular file has fewer blocks allocated than its size needs,
+/// the sign of holes (or of a virtual file).
+#[cfg(unix)]
+fn looks_sparse(metadata: &std::fs::Metadata) -> bool {
+    metadata.blocks() < metadata.size() / 512
+}
+
+/// Whether `err` says that a copy system call can't be used for these files,
+/// as opposed to the copy having failed.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn is_unsupported(err: &io::Error) -> bool {
+    matches!(
+        err.raw_os_error(),
+        Some(
+            libc::ENOSYS
+                | libc::EXDEV
+                | libc::EINVAL
+                | libc::EOPNOTSUPP
+                | libc::EPERM
+                | libc::EBADF
+                | libc::ETXTBSY
+        )
+    )
+}
+
+/// Run `step`, a wrapper around a copy system call, until the end of `src`.
+///
+/// Returns `None` if nothing could be copied this way, so that the caller
+/// falls back to another method. This includes files that report no data to
+/// these calls although they have some, like those in `/proc`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn copy_with_syscall(
+    progress: &mut dyn FnMut(u64),
+    mut step: impl FnMut() -> isize,
+) -> io::Result<Option<u64>> {
+    let mut total = 0;
+    loop {
+        match step() {
+            0 if total == 0 => return Ok(None),
+            0 => return Ok(Some(total)),
+            n if n < 0 => {
+                let err = io::Error::last_os_error();
+                match err.kind() {
+                    ErrorKind::Interrupted => {}
+                    _ if total == 0 && is_unsupported(&err) => return Ok(None),
+                    _ => return Err(err),
+                }
+            }
+            n => {
+                total += n as u64;
+                progress(n as u64);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn copy_with_copy_file_range(
+    src: &File,
+    dst: &File,
+    progress: &mut dyn FnMut(u64),
+) -> io::Result<Option<u64>> {
+    let (src_fd, dst_fd) = (src.as_raw_fd(), dst.as_raw_fd());
+    copy_with_syscall(progress, || {
+        // SAFETY: null offsets make the call use and advance the file
+        // positions, and both descriptors are open.
+        unsafe {
+            libc::copy_file_range(
+                src_fd,
+                std::ptr::null_mut(),
+                dst_fd,
+                std::ptr::null_mut(),
+                CHUNK_SIZE,
+                0,
+            )
+        }
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn copy_with_sendfile(
+    src: &File,
+    dst: &File,
+    progress: &mut dyn FnMut(u64),
+) -> io::Result<Option<u64>> {
+    let (src_fd, dst_fd) = (src.as_raw_fd(), dst.as_raw_fd());
+    copy_with_syscall(progress, || {
+        // SAFETY: a null offset makes the call use and advance the position
+        // of `src`, and both descriptors are open.
+        unsafe { libc::sendfile(dst_fd, src_fd, std::ptr::null_mut(), CHUNK_SIZE) }
+    })
+}
+
+fn copy_with_read_write(
+    mut src: &File,
+    mut dst: &File,
+    progress: &mut dyn FnMut(u64),
+) -> io::Result<u64> {
+    let mut buf = vec![0; BUFFER_SIZE];
+    let mut total = 0;
+    loop {
+        let n = match src.read(&mut buf) {
+            Ok(0) => return Ok(total),
+            Ok(n) => n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        dst.write_all(&buf[..n])?;
+        total += n as u64;
+        progress(n as u64);
+    }
+}
+
+/// Copy `src` block by block, leaving a hole in `dst` wherever a block holds
+/// only zeros.
+#[cfg(unix)]
+fn copy_zeros_as_holes(
+    src: &File,
+    dst: &File,
+    metadata: &std::fs::Metadata,
+    progress: &mut dyn FnMut(u64),
+) -> io::Result<Copied> {
+    let size = metadata.size();
+    dst.set_len(size)?;
+
+    let block_size = dst.metadata()?.blksize().max(512) as usize;
+    let mut buf = vec![0; block_size];
+    let mut offset = 0;
+    while offset < size {
+        let n = match src.read_at(&mut buf, offset) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        if buf[..n].iter().any(|&b| b != 0) {
+            dst.write_all_at(&buf[..n], offset)?;
+        }
+        offset += n as u64;
+        progress(n as u64);
+    }
+
+    Ok(Copied {
+        bytes: size,
+        method: Method::ReadWrite,
+    })
+}
+
+/// Copy only the data regions of `src`, found with `SEEK_DATA` and
+/// `SEEK_HOLE`, so that its holes stay holes in `dst`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn copy_data_regions(
+    src: &File,
+    dst: &File,
+    metadata: &std::fs::Metadata,
+    progress: &mut dyn FnMut(u64),
+) -> io::Result<Copied> {
+    let size = metadata.size();
+    let src_fd = src.as_raw_fd();
+
+    // SAFETY: `src_fd` is open; the call only reads the file layout.
+    let first = unsafe { libc::lseek(src_fd, 0, libc::SEEK_DATA) };
+    if first < 0 {
+        let err = io::Error::last_os_error();
+        match err.raw_os_error() {
+            // No data at all.
+            Some(libc::ENXIO) => {
+                dst.set_len(size)?;
+                progress(size);
+                return Ok(Copied {
+                    bytes: size,
+                    method: Method::ReadWrite,
+                });
+            }
+            // The file system can't tell where the holes are.
+            Some(libc::EINVAL) => return copy_zeros_as_holes(src, dst, metadata, progress),
+            _ => return Err(err),
+        }
+    }
+
+    dst.set_len(size)?;
+    let mut buf = vec![0; (size as usize).min(CHUNK_SIZE)];
+    let mut data = first;
+    let mut copied = 0;
+    loop {
+        // SAFETY: as above.
+        let hole = unsafe { libc::lseek(src_fd, data, libc::SEEK_HOLE) };
+        if hole < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut offset = data as u64;
+        while offset < hole as u64 {
+            let len = ((hole as u64 - offset) as usize).min(buf.len());
+            src.read_exact_at(&mut buf[..len], offset)?;
+            dst.write_all_at(&buf[..len], offset)?;
+            offset += len as u64;
+            progress(offset - copied);
+            copied = offset;
+        }
+
+        // SAFETY: as above.
+        data = unsafe { libc::lseek(src_fd, hole, libc::SEEK_DATA) };
+        if data < 0 {
+            let err = io::Error::last_os_error();
+            if err.raw_os_error() == Some(libc::ENXIO) {
+                break;
+            }
+            return Err(err);
+        }
+    }
+    if copied < size {
+        progress(size - copied);
+    }
+
+    Ok(Copied {
+        bytes: size,
+        method: Method::ReadWrite,
+    })
+}
+
+/// Without `SEEK_DATA`, holes can only be found by looking for zeros.
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+fn copy_data_regions(
+    src: &File,
+    dst: &File,
+    metadata: &std::fs::Metadata,
+    progress: &mut dyn FnMut(u64),
+) -> io::Result<Copied> {
+    copy_zeros_as_holes(src, dst, metadata, progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Seek, SeekFrom};
+    use tempfile::tempdir;
+
+    fn copy_to_new(data: &[u8], options: CopyOptions) -> (Vec<u8>, Copied, u64) {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("src"), data).unwrap();
+        let src = File::open(dir.path().join("src")).unwrap();
+        let mut dst = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(dir.path().join("dst"))
+            .unwrap();
+
+        let mut reported = 0;
+        let copied = copy_file_with_progress(&src, &dst, options, &mut |n| reported += n).unwrap();
+
+        let mut contents = Vec::new();
+        dst.seek(SeekFrom::Start(0)).unwrap();
+        dst.read_to_end(&mut contents).unwrap();
+        (contents, copied, reported)
+    }
+
+    #[test]
+    fn test_copy_file() {
+        let data: Vec<u8> = (0..300_000u32).map(|i| i as u8).collect();
+        for reflink in [Reflink::Never, Reflink::Auto] {
+            for sparse in [Sparse::Never, Sparse::Auto, Sparse::Always] {
+                let options = CopyOptions { reflink, sparse };
+                let (contents, copied, reported) = copy_to_new(&data, options);
+                assert_eq!(contents, data, "{options:?}");
+                assert_eq!(copied.bytes, data.len() as u64);
+                assert_eq!(reported, data.len() as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_copy_empty_file() {
+        let (contents, copied, _) = copy_to_new(b"", CopyOptions::default());
+        assert!(contents.is_empty());
+        assert_eq!(copied.bytes, 0);
+    }
+
+    #[test]
+    fn test_sparse_never_reads_and_writes_without_reflink() {
+        let options = CopyOptions {
+            reflink: Reflink::Never,
+            sparse: Sparse::Never,
+        };
+        let (_, copied, _) = copy_to_new(b"data", options);
+        assert_ne!(copied.method, Method::Reflink);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_sparse_always_makes_holes() {
+        let mut data = vec![0; 1024 * 1024];
+        data.extend_from_slice(b"end");
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("src"), &data).unwrap();
+        let src = File::open(dir.path().join("src")).unwrap();
+        let dst = File::create(dir.path().join("dst")).unwrap();
+
+        let options = CopyOptions {
+            reflink: Reflink::Never,
+            sparse: Sparse::Always,
+        };
+        copy_file(&src, &dst, options).unwrap();
+
+        assert_eq!(std::fs::read(dir.path().join("dst")).unwrap(), data);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_sparse_auto_keeps_holes() {
+        let dir = tempdir().unwrap();
+        let src = File::create(dir.path().join("src")).unwrap();
+        src.write_all_at(b"start", 0).unwrap();
+        src.write_all_at(b"end", 8 * 1024 * 1024).unwrap();
+        drop(src);
+        let src = File::open(dir.path().join("src")).unwrap();
+        let dst = File::create(dir.path().join("dst")).unwrap();
+
+        copy_file(&src, &dst, CopyOptions::default()).unwrap();
+
+        assert_eq!(
+            std::fs::read(dir.path().join("dst")).unwrap(),
+            std::fs::read(dir.path().join("src")).unwrap()
+        );
+        if looks_sparse(&src.metadata().unwrap()) {
+            assert!(looks_sparse(&dst.metadata().unwrap()));
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_copy_path_sets_permissions() {
+        let dir = tempdir().unwrap();
+        let (from, to) = (dir.path().join("from"), dir.path().join("to"));
+        std::fs::write(&from, b"abc").unwrap();
+        std::fs::set_permissions(&from, std::fs::Permissions::from_mode(0o640)).unwrap();
+
+        let copied = copy_path(&from, &to, CopyOptions::default()).unwrap();
+
+        assert_eq!(copied.bytes, 3);
+        assert_eq!(std::fs::read(&to).unwrap(), b"abc");
+        assert_eq!(to.metadata().unwrap().permissions().mode() & 0o777, 0o640);
+    }
+
+    #[test]
+    fn test_copy_path_rejects_directories() {
+        let dir = tempdir().unwrap();
+        let err =
+            copy_path(dir.path(), &dir.path().join("to"), CopyOptions::default()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
@@ -7,6 +7,9 @@
 
 //! Get password/group file entry
 //!
+//! On WASM there is no password or group database; lookups go through the
+//! identity hooks installed with [`crate::wasm_io::set_identity_hooks`].
+//!
 //! # Examples:
 //!
 //! ```
@@ -34,17 +37,34 @@
 
 #[cfg(any(target_os = "freebsd", target_vendor = "apple"))]
 use libc::time_t;
+#[cfg(unix)]
 use libc::{c_char, c_int, gid_t, uid_t};
+#[cfg(unix)]
 use libc::{getgrgid, getgrnam, getgroups};
+#[cfg(unix)]
 use libc::{getpwnam, getpwuid, group, passwd};
 
+#[cfg(unix)]
 use std::ffi::{CStr, CString};
 use std::io::Error as IOError;
 use std::io::ErrorKind;
 use std::io::Result as IOResult;
+#[cfg(unix)]
 use std::ptr;
+#[cfg(unix)]
 use std::sync::Mutex;
 
+#[cfg(target_family = "wasm")]
+use crate::wasm_io;
+
+#[cfg(target_family = "wasm")]
+#[allow(non_camel_case_types)]
+type uid_t = u32;
+#[cfg(target_family = "wasm")]
+#[allow(non_camel_case_types)]
+type gid_t = u32;
+
+#[cfg(unix)]
 unsafe extern "C" {
     /// From: `<https://man7.org/linux/man-pages/man3/getgrouplist.3.html>`
     /// > The getgrouplist() function scans the group database to obtain
@@ -64,6 +84,7 @@ unsafe extern "C" {
 /// > supplementary group IDs for the process is returned.  This allows
 /// > the caller to determine the size of a dynamically allocated list
 /// > to be used in a further call to getgroups().
+#[cfg(unix)]
 pub fn get_groups() -> IOResult<Vec<gid_t>> {
     let mut groups = Vec::new();
     loop {
@@ -93,6 +114,19 @@ pub fn get_groups() -> IOResult<Vec<gid_t>> {
     }
 }
 
+/// On WASM, these are the primary and other groups of the user running the
+/// builtin, as reported by the host.
+#[cfg(target_family = "wasm")]
+pub fn get_groups() -> IOResult<Vec<gid_t>> {
+    let mut groups: Vec<gid_t> = wasm_io::gid().into_iter().collect();
+    for gid in wasm_io::groups() {
+        if !groups.contains(&gid) {
+            groups.push(gid);
+        }
+    }
+    Ok(groups)
+}
+
 /// The list of group IDs returned from GNU's `groups` and GNU's `id --groups`
 /// starts with the effective group ID (egid).
 /// This is a wrapper for `get_groups()` to mimic this behavior.
@@ -122,7 +156,19 @@ pub fn get_groups_gnu(arg_id: Option<u32>) -> IOResult<Vec<gid_t>> {
     Ok(sort_groups(groups, egid))
 }
 
-#[cfg(all(unix, not(target_os = "redox"), feature = "process"))]
+#[cfg(target_family = "wasm")]
+pub fn get_groups_gnu(arg_id: Option<u32>) -> IOResult<Vec<gid_t>> {
+    let groups = get_groups()?;
+    Ok(match arg_id.or_else(wasm_io::gid) {
+        Some(egid) => sort_groups(groups, egid),
+        None => groups,
+    })
+}
+
+#[cfg(any(
+    all(unix, not(target_os = "redox"), feature = "process"),
+    target_family = "wasm"
+))]
 fn sort_groups(mut groups: Vec<gid_t>, egid: gid_t) -> Vec<gid_t> {
     if let Some(index) = groups.iter().position(|&x| x == egid) {
         groups[..=index].rotate_right(1);
@@ -165,6 +211,7 @@ pub struct Passwd {
 /// ptr must point to a valid C string.
 ///
 /// Returns None if ptr is null.
+#[cfg(unix)]
 fn cstr2string(ptr: *const c_char) -> Option<String> {
     if ptr.is_null() {
         None
@@ -173,6 +220,7 @@ fn cstr2string(ptr: *const c_char) -> Option<String> {
     }
 }
 
+#[cfg(unix)]
 impl Passwd {
     /// # Safety
     /// All the pointed-to strings must be valid and not change while
@@ -246,6 +294,20 @@ impl Passwd {
     }
 }
 
+#[cfg(target_family = "wasm")]
+impl Passwd {
+    /// The ids of the groups the user belongs to. On WASM, the host only
+    /// reports the other groups of the user running the builtin; any other
+    /// user is only in their primary group.
+    pub fn belongs_to(&self) -> Vec<gid_t> {
+        if wasm_io::uid() == Some(self.uid) {
+            get_groups().unwrap_or_default()
+        } else {
+            vec![self.gid]
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Group {
     /// AKA group.gr_name
@@ -254,6 +316,7 @@ pub struct Group {
     pub gid: gid_t,
 }
 
+#[cfg(unix)]
 impl Group {
     /// # Safety
     /// gr_name must be valid and not change while
@@ -281,8 +344,10 @@ pub trait Locate<K> {
 // to, so we must copy all the data we want before releasing the lock.
 // (Technically we must also ensure that the raw functions aren't being called
 // anywhere else in the program.)
+#[cfg(unix)]
 static PW_LOCK: Mutex<()> = Mutex::new(());
 
+#[cfg(unix)]
 macro_rules! f {
     ($fnam:ident, $fid:ident, $t:ident, $st:ident) => {
         impl Locate<$t> for $st {
@@ -340,9 +405,64 @@ macro_rules! f {
     };
 }
 
+#[cfg(unix)]
 f!(getpwnam, getpwuid, uid_t, Passwd);
+#[cfg(unix)]
 f!(getgrnam, getgrgid, gid_t, Group);
 
+#[cfg(target_family = "wasm")]
+impl Locate<uid_t> for Passwd {
+    fn locate(uid: uid_t) -> IOResult<Self> {
+        let name = wasm_io::user_name(uid)
+            .ok_or_else(|| IOError::new(ErrorKind::NotFound, format!("No such id: {uid}")))?;
+        // The host only reports the primary group of the current user, so
+        // every user is assumed to share it.
+        Ok(Self {
+            name,
+            uid,
+            gid: wasm_io::gid().unwrap_or_default(),
+            user_info: None,
+            user_shell: None,
+            user_dir: None,
+            user_passwd: None,
+        })
+    }
+}
+
+#[cfg(target_family = "wasm")]
+impl Locate<gid_t> for Group {
+    fn locate(gid: gid_t) -> IOResult<Self> {
+        let name = wasm_io::group_name(gid)
+            .ok_or_else(|| IOError::new(ErrorKind::NotFound, format!("No such id: {gid}")))?;
+        Ok(Self { name, gid })
+    }
+}
+
+// The hooks only map ids to names, so the only names that can be looked up
+// are those of the current user and their primary group. Other entries can
+// still be found by their numeric id.
+#[cfg(target_family = "wasm")]
+macro_rules! locate_by_name {
+    ($st:ident, $t:ident, $current:path, $name:path) => {
+        impl<'a> Locate<&'a str> for $st {
+            fn locate(k: &'a str) -> IOResult<Self> {
+                match $current() {
+                    Some(id) if $name(id).as_deref() == Some(k) => Self::locate(id),
+                    _ => match k.parse::<$t>() {
+                        Ok(id) => Self::locate(id),
+                        Err(_) => Err(IOError::new(ErrorKind::NotFound, format!("Not found: {k}"))),
+                    },
+                }
+            }
+        }
+    };
+}
+
+#[cfg(target_family = "wasm")]
+locate_by_name!(Passwd, uid_t, wasm_io::uid, wasm_io::user_name);
+#[cfg(target_family = "wasm")]
+locate_by_name!(Group, gid_t, wasm_io::gid, wasm_io::group_name);
+
 #[inline]
 pub fn uid2usr(id: uid_t) -> IOResult<String> {
     Passwd::locate(id).map(|p| p.name)
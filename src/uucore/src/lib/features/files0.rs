@@ -0,0 +1,121 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Read the NUL-separated file lists given to `--files0-from`.
+//!
+//! `du`, `sort` and `wc` accept `--files0-from=F`, where `F` holds the names
+//! of the files to process, each followed by a NUL byte, as written by
+//! `find -print0`. Reading the names from a file rather than the command
+//! line means there is no limit on how many can be given.
+//!
+//! [`files0`] yields the names one at a time, so a utility that does not need
+//! them all up front can process a list of any length in constant memory.
+//! Checks for empty names and for `-` are left to the caller, since the
+//! utilities report them differently.
+
+use std::ffi::OsString;
+use std::io::{self, BufRead};
+#[cfg(unix)]
+use std::os::unix::ffi::OsStringExt;
+
+/// Returns an iterator over the NUL-separated names read from `reader`.
+///
+/// The final name does not need a terminating NUL. Empty names are yielded
+/// as they are. The iterator ends after the first read error, which it
+/// yields. On platforms other than unix, a name that is not valid UTF-8 is
+/// an [`io::ErrorKind::InvalidData`] error.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::io::Cursor;
+///
+/// let mut names = files0(Cursor::new(b"a\0\0b c\0")).map(Result::unwrap);
+///
+/// assert_eq!(names.next(), Some("a".into()));
+/// assert_eq!(names.next(), Some("".into()));
+/// assert_eq!(names.next(), Some("b c".into()));
+/// assert_eq!(names.next(), None);
+/// ```
+pub fn files0<R: BufRead>(reader: R) -> Files0<R> {
+    Files0 {
+        reader: Some(reader),
+    }
+}
+
+/// An iterator over the names in a `--files0-from` list.
+///
+/// This struct is created by [`files0`]; see its documentation for details.
+pub struct Files0<R> {
+    reader: Option<R>,
+}
+
+impl<R: BufRead> Iterator for Files0<R> {
+    type Item = io::Result<OsString>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let reader = self.reader.as_mut()?;
+        let mut name = Vec::new();
+        let result = match reader.read_until(b'\0', &mut name) {
+            Ok(0) => None,
+            Ok(_) => {
+                if name.last() == Some(&b'\0') {
+                    name.pop();
+                }
+                #[cfg(unix)]
+                let name = Ok(OsString::from_vec(name));
+                #[cfg(not(unix))]
+                let name = String::from_utf8(name)
+                    .map(OsString::from)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+                Some(name)
+            }
+            Err(e) => Some(Err(e)),
+        };
+        if !matches!(result, Some(Ok(_))) {
+            self.reader = None;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::files0;
+    use std::io::{self, BufReader, Cursor, Read};
+
+    fn names(input: &[u8]) -> Vec<String> {
+        files0(Cursor::new(input))
+            .map(|name| name.unwrap().into_string().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_terminated_and_unterminated() {
+        assert_eq!(names(b"a\0b\0"), ["a", "b"]);
+        assert_eq!(names(b"a\0b"), ["a", "b"]);
+        assert!(names(b"").is_empty());
+    }
+
+    #[test]
+    fn test_empty_names_are_kept() {
+        assert_eq!(names(b"\0"), [""]);
+        assert_eq!(names(b"a\0\0b\n\0"), ["a", "", "b\n"]);
+    }
+
+    #[test]
+    fn test_stops_after_error() {
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken"))
+            }
+        }
+        let mut it = files0(BufReader::new(Cursor::new(b"a\0").chain(Failing)));
+        assert_eq!(it.next().unwrap().unwrap(), "a");
+        assert!(it.next().unwrap().is_err());
+        assert!(it.next().is_none());
+    }
+}
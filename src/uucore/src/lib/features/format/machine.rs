@@ -0,0 +1,269 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Machine-readable output
+//!
+//! Utilities with a `--json` flag print one JSON object per entry, each on
+//! its own line (JSON Lines), instead of their columnar text. The keys of a
+//! utility's records don't change with the options that only affect the
+//! human-oriented layout, and values that are not known on the current
+//! platform are `null` rather than left out.
+//!
+//! JSON strings cannot hold arbitrary bytes, so the bytes of a file name that
+//! are not valid UTF-8 are written as the lone surrogates `\udc80` to
+//! `\udcff`, like Python's `surrogateescape` error handler does. Unlike
+//! `\u0080` to `\u00ff`, these can't be mistaken for characters of a valid
+//! name, and the original bytes can be recovered from them.
+
+use std::ffi::OsStr;
+use std::fmt::{self, Display, Write as _};
+use std::fs::FileType;
+#[cfg(unix)]
+use std::os::unix::fs::FileTypeExt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A JSON object under construction, with its fields in insertion order.
+///
+/// ```
+/// use uucore::format::machine::Record;
+///
+/// let mut record = Record::new();
+/// record.string("path", "a\"b").unsigned("size", 3).null("owner");
+/// assert_eq!(record.to_string(), r#"{"path":"a\"b","size":3,"owner":null}"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    buf: String,
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Record {
+    pub fn new() -> Self {
+        Self {
+            buf: String::from("{"),
+        }
+    }
+
+    fn key(&mut self, key: &str) -> &mut String {
+        if self.buf.len() > 1 {
+            self.buf.push(',');
+        }
+        push_string(&mut self.buf, key);
+        self.buf.push(':');
+        &mut self.buf
+    }
+
+    pub fn string(&mut self, key: &str, value: &str) -> &mut Self {
+        push_string(self.key(key), value);
+        self
+    }
+
+    /// Add a file name or other OS string, escaping the bytes that are not
+    /// valid UTF-8 as described in the [module documentation](self).
+    pub fn os_string(&mut self, key: &str, value: &OsStr) -> &mut Self {
+        let buf = self.key(key);
+        buf.push('"');
+        for chunk in crate::os_str_as_bytes_lossy(value).utf8_chunks() {
+            push_escaped(buf, chunk.valid());
+            for byte in chunk.invalid() {
+                let _ = write!(buf, "\\udc{byte:02x}");
+            }
+        }
+        buf.push('"');
+        self
+    }
+
+    pub fn unsigned(&mut self, key: &str, value: u64) -> &mut Self {
+        let _ = write!(self.key(key), "{value}");
+        self
+    }
+
+    pub fn signed(&mut self, key: &str, value: i64) -> &mut Self {
+        let _ = write!(self.key(key), "{value}");
+        self
+    }
+
+    pub fn bool(&mut self, key: &str, value: bool) -> &mut Self {
+        self.key(key).push_str(if value { "true" } else { "false" });
+        self
+    }
+
+    pub fn null(&mut self, key: &str) -> &mut Self {
+        self.key(key).push_str("null");
+        self
+    }
+
+    /// Add `value` as a string, or `null` if there is none.
+    pub fn optional_string(&mut self, key: &str, value: Option<&str>) -> &mut Self {
+        match value {
+            Some(value) => self.string(key, value),
+            None => self.null(key),
+        }
+    }
+
+    /// Add `value` as an unsigned number, or `null` if there is none.
+    pub fn optional_unsigned(&mut self, key: &str, value: Option<u64>) -> &mut Self {
+        match value {
+            Some(value) => self.unsigned(key, value),
+            None => self.null(key),
+        }
+    }
+
+    /// Add a timestamp as whole seconds since the Unix epoch, or `null` if
+    /// it is not known.
+    pub fn time(&mut self, key: &str, value: Option<SystemTime>) -> &mut Self {
+        match value {
+            Some(time) => self.signed(key, seconds_since_epoch(time)),
+            None => self.null(key),
+        }
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}}}", self.buf)
+    }
+}
+
+/// Whole seconds since the Unix epoch, rounded down for times before it.
+fn seconds_since_epoch(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(err) => {
+            let before = err.duration();
+            -(before.as_secs() as i64) - i64::from(before.subsec_nanos() > 0)
+        }
+    }
+}
+
+/// Append `value` to `buf` as a quoted JSON string.
+fn push_string(buf: &mut String, value: &str) {
+    buf.push('"');
+    push_escaped(buf, value);
+    buf.push('"');
+}
+
+/// Append `value` to `buf` with the characters JSON strings can't hold escaped.
+fn push_escaped(buf: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(buf, "\\u{:04x}", c as u32);
+            }
+            c => buf.push(c),
+        }
+    }
+}
+
+/// The name used for a file type in records, e.g. `"directory"`.
+pub fn file_type_name(file_type: FileType) -> &'static str {
+    #[cfg(unix)]
+    {
+        if file_type.is_block_device() {
+            return "block-device";
+        } else if file_type.is_char_device() {
+            return "character-device";
+        } else if file_type.is_fifo() {
+            return "fifo";
+        } else if file_type.is_socket() {
+            return "socket";
+        }
+    }
+    if file_type.is_symlink() {
+        "symlink"
+    } else if file_type.is_dir() {
+        "directory"
+    } else if file_type.is_file() {
+        "file"
+    } else {
+        "unknown"
+    }
+}
+
+/// The name used for a host-reported file type in records, matching
+/// [`file_type_name`].
+#[cfg(target_family = "wasm")]
+pub fn wasm_file_type_name(file_type: crate::wasm_io::WasmFileType) -> &'static str {
+    use crate::wasm_io::WasmFileType;
+    match file_type {
+        WasmFileType::File => "file",
+        WasmFileType::Dir => "directory",
+        WasmFileType::Symlink => "symlink",
+        WasmFileType::Other => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_record() {
+        assert_eq!(Record::new().to_string(), "{}");
+    }
+
+    #[test]
+    fn test_fields_in_order() {
+        let mut record = Record::new();
+        record
+            .string("name", "a")
+            .unsigned("size", 42)
+            .signed("offset", -1)
+            .bool("big", false)
+            .optional_string("owner", None)
+            .optional_unsigned("uid", Some(0));
+        assert_eq!(
+            record.to_string(),
+            r#"{"name":"a","size":42,"offset":-1,"big":false,"owner":null,"uid":0}"#
+        );
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let mut record = Record::new();
+        record.string("s", "\"\\\n\t\u{1}é");
+        assert_eq!(record.to_string(), r#"{"s":"\"\\\n\t\u0001é"}"#);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_non_utf8_os_string() {
+        use std::os::unix::ffi::OsStrExt;
+        let mut record = Record::new();
+        record
+            .os_string("s", OsStr::from_bytes(b"a\xff\"\xc3b"))
+            .os_string("t", OsStr::new("\u{ff}"));
+        assert_eq!(record.to_string(), r#"{"s":"a\udcff\"\udcc3b","t":"ÿ"}"#);
+    }
+
+    #[test]
+    fn test_time() {
+        let mut record = Record::new();
+        record
+            .time(
+                "t",
+                Some(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(5)),
+            )
+            .time("u", None);
+        assert_eq!(record.to_string(), r#"{"t":5,"u":null}"#);
+
+        let mut record = Record::new();
+        record.time(
+            "t",
+            Some(UNIX_EPOCH - std::time::Duration::from_millis(1500)),
+        );
+        assert_eq!(record.to_string(), r#"{"t":-2}"#);
+    }
+}
@@ -22,11 +22,12 @@ use std::env;
 use std::ffi::CString;
 use std::ffi::{OsStr, OsString};
 use std::fs;
+#[cfg(not(target_family = "wasm"))]
 use std::fs::read_dir;
 use std::hash::Hash;
 use std::io::Stdin;
 use std::io::{Error, ErrorKind, Result as IOResult};
-#[cfg(unix)]
+#[cfg(any(unix, target_os = "wasi"))]
 use std::os::fd::AsFd;
 #[cfg(unix)]
 use std::os::unix::fs::MetadataExt;
@@ -34,6 +35,9 @@ use std::path::{Component, MAIN_SEPARATOR, Path, PathBuf};
 #[cfg(target_os = "windows")]
 use winapi_util::AsHandleRef;
 
+#[cfg(target_family = "wasm")]
+use crate::wasm_io::read_dir;
+
 /// Used to check if the `mode` has its `perm` bit set.
 ///
 /// This macro expands to `mode & perm != 0`.
@@ -49,7 +53,8 @@ macro_rules! has {
 pub struct FileInformation(
     #[cfg(unix)] nix::sys::stat::FileStat,
     #[cfg(windows)] winapi_util::file::Information,
-    #[cfg(target_family = "wasm")] fs::Metadata,
+    #[cfg(target_os = "wasi")] libc::stat,
+    #[cfg(all(target_family = "wasm", not(target_os = "wasi")))] fs::Metadata,
 );
 
 impl FileInformation {
@@ -67,8 +72,21 @@ impl FileInformation {
         Ok(Self(info))
     }
 
+    /// Get information from a currently open file
+    #[cfg(target_os = "wasi")]
+    pub fn from_file(file: &impl AsFd) -> IOResult<Self> {
+        use std::os::fd::AsRawFd;
+        let mut stat = std::mem::MaybeUninit::<libc::stat>::uninit();
+        // SAFETY: the descriptor is borrowed for the duration of the call, and
+        // `stat` is only read once `fstat` has filled it in.
+        if unsafe { libc::fstat(file.as_fd().as_raw_fd(), stat.as_mut_ptr()) } != 0 {
+            return Err(Error::last_os_error());
+        }
+        Ok(Self(unsafe { stat.assume_init() }))
+    }
+
     /// Get information from a currently open file (WASM stub using metadata)
-    #[cfg(target_family = "wasm")]
+    #[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
     pub fn from_file(_file: &impl std::io::Read) -> IOResult<Self> {
         Err(Error::new(
             ErrorKind::Unsupported,
@@ -105,7 +123,26 @@ impl FileInformation {
             let file = open_options.read(true).open(path.as_ref())?;
             Self::from_file(&file)
         }
-        #[cfg(target_family = "wasm")]
+        #[cfg(target_os = "wasi")]
+        {
+            let path = std::ffi::CString::new(path.as_ref().as_os_str().as_encoded_bytes())
+                .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+            let mut stat = std::mem::MaybeUninit::<libc::stat>::uninit();
+            // SAFETY: `path` is a valid C string, and `stat` is only read once
+            // the call has filled it in.
+            let ret = unsafe {
+                if dereference {
+                    libc::stat(path.as_ptr(), stat.as_mut_ptr())
+                } else {
+                    libc::lstat(path.as_ptr(), stat.as_mut_ptr())
+                }
+            };
+            if ret != 0 {
+                return Err(Error::last_os_error());
+            }
+            Ok(Self(unsafe { stat.assume_init() }))
+        }
+        #[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
         {
             let md = if dereference {
                 fs::metadata(path.as_ref())
@@ -126,7 +163,11 @@ impl FileInformation {
         {
             self.0.file_size()
         }
-        #[cfg(target_family = "wasm")]
+        #[cfg(target_os = "wasi")]
+        {
+            self.0.st_size.try_into().unwrap_or(0)
+        }
+        #[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
         {
             self.0.len()
         }
@@ -180,10 +221,17 @@ impl FileInformation {
         return self.0.st_nlink.try_into().unwrap();
         #[cfg(windows)]
         return self.0.number_of_links();
-        #[cfg(target_family = "wasm")]
+        #[cfg(target_os = "wasi")]
+        return self.0.st_nlink;
+        #[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
         return 1; // WASM has no hard link concept
     }
 
+    #[cfg(target_os = "wasi")]
+    pub fn inode(&self) -> u64 {
+        self.0.st_ino
+    }
+
     #[cfg(unix)]
     pub fn inode(&self) -> u64 {
         #[cfg(all(not(any(target_os = "netbsd")), target_pointer_width = "64"))]
@@ -193,7 +241,7 @@ impl FileInformation {
     }
 }
 
-#[cfg(unix)]
+#[cfg(any(unix, target_os = "wasi"))]
 impl PartialEq for FileInformation {
     fn eq(&self, other: &Self) -> bool {
         self.0.st_dev == other.0.st_dev && self.0.st_ino == other.0.st_ino
@@ -208,7 +256,7 @@ impl PartialEq for FileInformation {
     }
 }
 
-#[cfg(target_family = "wasm")]
+#[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
 impl PartialEq for FileInformation {
     fn eq(&self, _other: &Self) -> bool {
         // WASM has no inode/device concepts; conservative: never equal
@@ -220,7 +268,7 @@ impl Eq for FileInformation {}
 
 impl Hash for FileInformation {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        #[cfg(unix)]
+        #[cfg(any(unix, target_os = "wasi"))]
         {
             self.0.st_dev.hash(state);
             self.0.st_ino.hash(state);
@@ -230,7 +278,7 @@ impl Hash for FileInformation {
             self.0.volume_serial_number().hash(state);
             self.0.file_index().hash(state);
         }
-        #[cfg(target_family = "wasm")]
+        #[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
         {
             // No unique file identity on WASM; hash the file size as fallback
             self.0.len().hash(state);
@@ -308,15 +356,59 @@ pub fn normalize_path(path: &Path) -> PathBuf {
     ret
 }
 
+/// Return the current working directory.
+///
+/// On WASM this is the directory reported by the host through
+/// `wasm_io::set_cwd_hook`; elsewhere it is [`std::env::current_dir`].
+pub fn current_dir() -> IOResult<PathBuf> {
+    #[cfg(target_family = "wasm")]
+    {
+        crate::wasm_io::current_dir()
+    }
+    #[cfg(not(target_family = "wasm"))]
+    {
+        env::current_dir()
+    }
+}
+
+/// Make a relative path absolute by joining it to the current working directory.
+///
+/// Absolute paths are returned unchanged. Utilities should use this instead
+/// of joining with [`std::env::current_dir`] so that the host's working
+/// directory is honored on WASM.
+pub fn resolve_relative<P: AsRef<Path>>(path: P) -> IOResult<PathBuf> {
+    let path = path.as_ref();
+    if path.is_absolute() {
+        return Ok(path.to_path_buf());
+    }
+    Ok(current_dir()?.join(path))
+}
+
 fn resolve_symlink<P: AsRef<Path>>(path: P) -> IOResult<Option<PathBuf>> {
-    let result = if fs::symlink_metadata(&path)?.file_type().is_symlink() {
-        Some(fs::read_link(&path)?)
+    #[cfg(not(target_family = "wasm"))]
+    use fs::{read_link, symlink_metadata};
+    // On WASM, symbolic links live in the host VFS.
+    #[cfg(target_family = "wasm")]
+    use crate::wasm_io::{read_link, symlink_metadata};
+
+    let result = if symlink_metadata(&path)?.is_symlink() {
+        Some(read_link(&path)?)
     } else {
         None
     };
     Ok(result)
 }
 
+#[cfg(not(target_family = "wasm"))]
+fn path_exists(path: &Path) -> bool {
+    path.exists()
+}
+
+#[cfg(target_family = "wasm")]
+fn path_exists(path: &Path) -> bool {
+    crate::wasm_io::metadata(path).is_ok()
+}
+
 enum OwningComponent {
     Prefix(OsString),
     RootDir,
@@ -390,6 +482,9 @@ pub fn canonicalize<P: AsRef<Path>>(
         };
     let original = if original.is_absolute() {
         original.to_path_buf()
+    } else if cfg!(target_family = "wasm") {
+        // The host's working directory can't be canonicalized through `std::fs`.
+        resolve_relative(original)?
     } else {
         let current_dir = env::current_dir()?;
         dunce::canonicalize(current_dir)?.join(original)
@@ -428,8 +523,13 @@ pub fn canonicalize<P: AsRef<Path>>(
                 if followed_symlinks < SYMLINKS_TO_LOOK_FOR_LOOPS {
                     followed_symlinks += 1;
                 } else {
+                    #[cfg(not(target_family = "wasm"))]
                     let file_info =
                         FileInformation::from_path(result.parent().unwrap(), false).unwrap();
+                    // The host VFS has no inode numbers, so identify the
+                    // directory by its (already resolved) path instead.
+                    #[cfg(target_family = "wasm")]
+                    let file_info = result.parent().unwrap().to_path_buf();
                     let mut path_to_follow = PathBuf::new();
                     for part in &parts {
                         path_to_follow.push(part.as_os_str());
@@ -443,12 +543,11 @@ pub fn canonicalize<P: AsRef<Path>>(
                 }
                 result.pop();
             }
-            Err(e) => {
+            Err(e)
                 if miss_mode == MissingHandling::Existing
-                    || (miss_mode == MissingHandling::Normal && !parts.is_empty())
-                {
-                    return Err(e);
-                }
+                    || (miss_mode == MissingHandling::Normal && !parts.is_empty()) =>
+            {
+                return Err(e);
             }
             _ => {}
         }
@@ -461,7 +560,7 @@ pub fn canonicalize<P: AsRef<Path>>(
             }
         }
         MissingHandling::Normal => {
-            if result.exists() {
+            if path_exists(&result) {
                 if has_to_be_directory {
                     read_dir(&result)?;
                 }
@@ -619,6 +718,54 @@ pub fn infos_refer_to_same_file(
     false
 }
 
+/// Checks if `p1` and `p2` refer to the same file, following symbolic links.
+///
+/// Files are compared by device and inode number on Unix and WASI and by volume
+/// serial number and file index on Windows, so hard links and bind mounts of a
+/// file are recognized as well. The host VFS on `wasm32-unknown-unknown` reports
+/// neither, so there the canonicalized paths are compared instead.
+/// If either file cannot be accessed, returns false.
+pub fn is_same_file<P: AsRef<Path>, Q: AsRef<Path>>(p1: P, p2: Q) -> bool {
+    #[cfg(any(not(target_family = "wasm"), target_os = "wasi"))]
+    {
+        infos_refer_to_same_file(
+            FileInformation::from_path(p1, true),
+            FileInformation::from_path(p2, true),
+        )
+    }
+    #[cfg(all(target_family = "wasm", not(target_os = "wasi")))]
+    {
+        let resolve = |p: &Path| {
+            crate::wasm_io::metadata(p)?;
+            canonicalize(p, MissingHandling::Normal, ResolveMode::Physical)
+        };
+        match (resolve(p1.as_ref()), resolve(p2.as_ref())) {
+            (Ok(p1), Ok(p2)) => p1 == p2,
+            _ => false,
+        }
+    }
+}
+
+/// Checks if the open files `f1` and `f2` are the same file, in the same way
+/// as [`is_same_file`].
+#[cfg(any(unix, target_os = "wasi"))]
+pub fn are_same_open_file(f1: &impl AsFd, f2: &impl AsFd) -> bool {
+    infos_refer_to_same_file(
+        FileInformation::from_file(f1),
+        FileInformation::from_file(f2),
+    )
+}
+
+/// Checks if the open files `f1` and `f2` are the same file, in the same way
+/// as [`is_same_file`].
+#[cfg(target_os = "windows")]
+pub fn are_same_open_file(f1: &impl AsHandleRef, f2: &impl AsHandleRef) -> bool {
+    infos_refer_to_same_file(
+        FileInformation::from_file(f1),
+        FileInformation::from_file(f2),
+    )
+}
+
 /// Converts absolute `path` to be relative to absolute `to` path.
 pub fn make_path_relative_to<P1: AsRef<Path>, P2: AsRef<Path>>(path: P1, to: P2) -> PathBuf {
     let path = path.as_ref();
@@ -898,6 +1045,57 @@ pub fn make_fifo(path: &Path) -> std::io::Result<()> {
     }
 }
 
+/// The environment variable that makes the utilities writing files behave as
+/// if `--sync` was given, e.g. `UUTILS_SYNC=1 make install`.
+pub const SYNC_ENV: &str = "UUTILS_SYNC";
+
+/// Whether files should be flushed to the storage device before the utility
+/// reports success: `flag` is the state of its `--sync` option, and any
+/// non-empty value of [`SYNC_ENV`] turns it on as well.
+pub fn sync_requested(flag: bool) -> bool {
+    flag || env::var_os(SYNC_ENV).is_some_and(|value| !value.is_empty())
+}
+
+/// Flush the data and metadata of the file at `path` to the storage device,
+/// then the directory holding it, so that its name survives a crash as well.
+///
+/// Only regular files are flushed themselves; for anything else, e.g. a
+/// symbolic link or a FIFO, opening it could follow it or block, so only the
+/// directory is.
+pub fn sync_file_and_parent(path: &Path) -> IOResult<()> {
+    if fs::symlink_metadata(path)?.is_file() {
+        // Windows only flushes handles opened for writing.
+        let file = if cfg!(windows) {
+            fs::OpenOptions::new().write(true).open(path)?
+        } else {
+            fs::File::open(path)?
+        };
+        file.sync_all()?;
+    }
+    sync_parent_dir(path)
+}
+
+/// Flush the directory holding `path` to the storage device, so that
+/// entries just created, renamed or removed in it survive a crash.
+///
+/// Directories cannot be flushed on Windows, where this does nothing.
+#[cfg_attr(not(unix), expect(clippy::unnecessary_wraps))]
+pub fn sync_parent_dir(path: &Path) -> IOResult<()> {
+    #[cfg(unix)]
+    {
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        fs::File::open(parent)?.sync_all()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(())
+    }
+}
+
 // Redox's libc appears not to include the following utilities
 
 #[cfg(target_os = "redox")]
@@ -1012,6 +1210,14 @@ mod tests {
         }
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_relative() {
+        let cwd = env::current_dir().unwrap();
+        assert_eq!(resolve_relative("a/b").unwrap(), cwd.join("a/b"));
+        assert_eq!(resolve_relative("/a/b").unwrap(), PathBuf::from("/a/b"));
+    }
+
     #[cfg(unix)]
     #[test]
     fn test_display_permissions() {
@@ -1143,6 +1349,24 @@ mod tests {
         assert!(are_hardlinks_to_same_file(path1, &path2));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_is_same_file() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("file");
+        let other = dir.path().join("other");
+        fs::write(&file, "a").unwrap();
+        fs::write(&other, "a").unwrap();
+        fs::hard_link(&file, dir.path().join("hardlink")).unwrap();
+        unix::fs::symlink(&file, dir.path().join("symlink")).unwrap();
+
+        assert!(is_same_file(&file, &file));
+        assert!(is_same_file(&file, dir.path().join("hardlink")));
+        assert!(is_same_file(&file, dir.path().join("symlink")));
+        assert!(!is_same_file(&file, &other));
+        assert!(!is_same_file(&file, dir.path().join("missing")));
+    }
+
     #[cfg(unix)]
     #[test]
     fn test_get_file_display() {
@@ -1212,4 +1436,20 @@ mod tests {
         std::thread::spawn(move || assert!(fs::write(&path2, b"foo").is_ok()));
         assert_eq!(fs::read(&path).unwrap(), b"foo");
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sync_file_and_parent() {
+        let tempdir = tempdir().unwrap();
+        let file = tempdir.path().join("file");
+        fs::write(&file, b"foo").unwrap();
+        assert!(sync_file_and_parent(&file).is_ok());
+
+        // A FIFO without a writer is not opened, which would block.
+        let fifo = tempdir.path().join("fifo");
+        make_fifo(&fifo).unwrap();
+        assert!(sync_file_and_parent(&fifo).is_ok());
+
+        assert!(sync_file_and_parent(&tempdir.path().join("missing")).is_err());
+    }
 }
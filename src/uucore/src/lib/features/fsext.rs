@@ -34,11 +34,15 @@ use std::os::unix::ffi::OsStrExt;
 #[cfg(windows)]
 use std::os::windows::ffi::OsStrExt;
 #[cfg(windows)]
+use std::os::windows::ffi::OsStringExt;
+#[cfg(windows)]
 use windows_sys::Win32::{
-    Foundation::{ERROR_NO_MORE_FILES, INVALID_HANDLE_VALUE},
+    Foundation::{ERROR_MORE_DATA, ERROR_NO_MORE_FILES, INVALID_HANDLE_VALUE, NO_ERROR},
+    NetworkManagement::WNet::WNetGetConnectionW,
     Storage::FileSystem::{
-        FindFirstVolumeW, FindNextVolumeW, FindVolumeClose, GetDiskFreeSpaceW, GetDriveTypeW,
-        GetVolumeInformationW, GetVolumePathNamesForVolumeNameW, QueryDosDeviceW,
+        FindFirstVolumeW, FindNextVolumeW, FindVolumeClose, GetDiskFreeSpaceExW, GetDiskFreeSpaceW,
+        GetDriveTypeW, GetLogicalDrives, GetVolumeInformationW, GetVolumePathNameW,
+        GetVolumePathNamesForVolumeNameW, QueryDosDeviceW,
     },
     System::WindowsProgramming::DRIVE_REMOTE,
 };
@@ -58,6 +62,80 @@ fn to_nul_terminated_wide_string(s: impl AsRef<OsStr>) -> Vec<u16> {
         .collect::<Vec<u16>>()
 }
 
+/// Split a list of strings, each ended by a nul and the whole list by
+/// another, as returned by `GetVolumePathNamesForVolumeNameW`.
+#[cfg(windows)]
+fn split_multi_string(buf: &[u16]) -> Vec<OsString> {
+    buf.split(|&c| c == 0)
+        .take_while(|s| !s.is_empty())
+        .map(OsString::from_wide)
+        .collect()
+}
+
+/// The paths at which the volume `volume_name` (`\\?\Volume{GUID}\`) is
+/// mounted: drive letters like `C:\` as well as folders on other volumes,
+/// as for a virtual disk mounted into a folder.
+#[cfg(windows)]
+fn volume_mount_points(volume_name: &str) -> Vec<OsString> {
+    let volume_name = to_nul_terminated_wide_string(volume_name);
+    let mut buf = vec![0u16; MAX_PATH];
+    loop {
+        let mut len = 0;
+        let success = unsafe {
+            GetVolumePathNamesForVolumeNameW(
+                volume_name.as_ptr(),
+                buf.as_mut_ptr(),
+                buf.len() as u32,
+                &raw mut len,
+            )
+        };
+        if success != 0 {
+            return split_multi_string(&buf);
+        }
+        let err = IOError::last_os_error();
+        if err.raw_os_error() != Some(ERROR_MORE_DATA as i32) || len as usize <= buf.len() {
+            return Vec::new();
+        }
+        buf.resize(len as usize, 0);
+    }
+}
+
+/// The name of the file system at `root`, a mount point or a volume name,
+/// such as "NTFS", or `None` if it can't be read.
+#[cfg(windows)]
+fn volume_fs_type(root: impl AsRef<OsStr>) -> Option<String> {
+    let mut fs_type_buf = [0u16; MAX_PATH];
+    let success = unsafe {
+        let root = to_nul_terminated_wide_string(root);
+        GetVolumeInformationW(
+            root.as_ptr(),
+            ptr::null_mut(),
+            0,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            fs_type_buf.as_mut_ptr(),
+            fs_type_buf.len() as u32,
+        )
+    };
+    (success != 0).then(|| LPWSTR2String(&fs_type_buf))
+}
+
+/// The mount point of the volume that `path` is on, e.g. `C:\` for
+/// `C:\Windows`, or `\\server\share\` for a file on a network share.
+#[cfg(windows)]
+pub fn volume_path_name(path: &Path) -> std::io::Result<OsString> {
+    let mut buf = [0u16; MAX_PATH];
+    let success = unsafe {
+        let path = to_nul_terminated_wide_string(path);
+        GetVolumePathNameW(path.as_ptr(), buf.as_mut_ptr(), buf.len() as u32)
+    };
+    if success == 0 {
+        return Err(IOError::last_os_error());
+    }
+    Ok(split_multi_string(&buf).into_iter().next().unwrap_or_default())
+}
+
 #[cfg(unix)]
 use libc::{
     S_IFBLK, S_IFCHR, S_IFDIR, S_IFIFO, S_IFLNK, S_IFMT, S_IFREG, S_IFSOCK, mode_t, strerror,
@@ -267,8 +345,10 @@ impl MountInfo {
         })
     }
 
+    /// The entries for the local volume `volume_name`, one for each of the
+    /// paths it is mounted at, or a single dummy one if it is not mounted.
     #[cfg(windows)]
-    fn new(mut volume_name: String) -> Option<Self> {
+    fn from_volume(mut volume_name: String) -> Vec<Self> {
         let mut dev_name_buf = [0u16; MAX_PATH];
         volume_name.pop();
         unsafe {
@@ -285,59 +365,73 @@ impl MountInfo {
         };
         volume_name.push('\\');
         let dev_name = LPWSTR2String(&dev_name_buf);
-
-        let mut mount_root_buf = [0u16; MAX_PATH];
-        let success = unsafe {
-            let volume_name = to_nul_terminated_wide_string(&volume_name);
-            GetVolumePathNamesForVolumeNameW(
-                volume_name.as_ptr(),
-                mount_root_buf.as_mut_ptr(),
-                mount_root_buf.len() as u32,
-                ptr::null_mut(),
-            )
-        };
-        if 0 == success {
-            // TODO: support the case when `GetLastError()` returns `ERROR_MORE_DATA`
-            return None;
+        let fs_type = volume_fs_type(&volume_name).unwrap_or_default();
+
+        let mount_points = volume_mount_points(&volume_name);
+        if mount_points.is_empty() {
+            // Only shown with `--all`, and read through the volume name.
+            return vec![Self {
+                dev_id: volume_name,
+                dev_name,
+                fs_type,
+                mount_root: OsString::new(),
+                mount_dir: OsString::new(),
+                mount_option: String::new(),
+                remote: false,
+                dummy: true,
+            }];
         }
-        // TODO: This should probably call `OsString::from_wide`, but unclear if
-        // terminating zeros need to be striped first.
-        let mount_root = LPWSTR2String(&mount_root_buf);
+        mount_points
+            .into_iter()
+            .map(|mount_dir| {
+                let remote = DRIVE_REMOTE
+                    == unsafe {
+                        let mount_dir = to_nul_terminated_wide_string(&mount_dir);
+                        GetDriveTypeW(mount_dir.as_ptr())
+                    };
+                Self {
+                    dev_id: volume_name.clone(),
+                    dev_name: dev_name.clone(),
+                    fs_type: fs_type.clone(),
+                    mount_root: mount_dir.clone(),
+                    mount_dir,
+                    mount_option: String::new(),
+                    remote,
+                    dummy: false,
+                }
+            })
+            .collect()
+    }
 
-        let mut fs_type_buf = [0u16; MAX_PATH];
-        let success = unsafe {
-            let mount_root = to_nul_terminated_wide_string(&mount_root);
-            GetVolumeInformationW(
-                mount_root.as_ptr(),
-                ptr::null_mut(),
-                0,
-                ptr::null_mut(),
-                ptr::null_mut(),
-                ptr::null_mut(),
-                fs_type_buf.as_mut_ptr(),
-                fs_type_buf.len() as u32,
+    /// The entry for the network drive mapped to the drive letter `root`
+    /// (`Z:\`), whose device is the share it is connected to.
+    #[cfg(windows)]
+    fn from_network_drive(root: String) -> Self {
+        let local_name = to_nul_terminated_wide_string(root.trim_end_matches('\\'));
+        let mut remote_name_buf = [0u16; MAX_PATH];
+        let mut len = remote_name_buf.len() as u32;
+        let result = unsafe {
+            WNetGetConnectionW(
+                local_name.as_ptr(),
+                remote_name_buf.as_mut_ptr(),
+                &raw mut len,
             )
         };
-        let fs_type = if 0 == success {
-            None
+        let dev_name = if result == NO_ERROR {
+            LPWSTR2String(&remote_name_buf)
         } else {
-            Some(LPWSTR2String(&fs_type_buf))
+            root.clone()
         };
-        let remote = DRIVE_REMOTE
-            == unsafe {
-                let mount_root = to_nul_terminated_wide_string(&mount_root);
-                GetDriveTypeW(mount_root.as_ptr())
-            };
-        Some(Self {
-            dev_id: volume_name,
+        Self {
+            dev_id: root.clone(),
             dev_name,
-            fs_type: fs_type.unwrap_or_default(),
-            mount_root: mount_root.into(), // TODO: We should figure out how to keep an OsString here.
-            mount_dir: OsString::new(),
+            fs_type: volume_fs_type(&root).unwrap_or_default(),
+            mount_root: root.clone().into(),
+            mount_dir: root.into(),
             mount_option: String::new(),
-            remote,
+            remote: true,
             dummy: false,
-        })
+        }
     }
 }
 
@@ -504,12 +598,10 @@ pub fn read_fs_list() -> UResult<Vec<MountInfo>> {
         let mut mounts = Vec::<MountInfo>::new();
         loop {
             let volume_name = LPWSTR2String(&volume_name_buf);
-            if !volume_name.starts_with("\\\\?\\") || !volume_name.ends_with('\\') {
+            if volume_name.starts_with("\\\\?\\") && volume_name.ends_with('\\') {
+                mounts.extend(MountInfo::from_volume(volume_name));
+            } else {
                 show_warning!("A bad path was skipped: {volume_name}");
-                continue;
-            }
-            if let Some(m) = MountInfo::new(volume_name) {
-                mounts.push(m);
             }
             if 0 == unsafe {
                 FindNextVolumeW(
@@ -520,6 +612,9 @@ pub fn read_fs_list() -> UResult<Vec<MountInfo>> {
             } {
                 let err = IOError::last_os_error();
                 if err.raw_os_error() != Some(ERROR_NO_MORE_FILES as i32) {
+                    unsafe {
+                        FindVolumeClose(find_handle);
+                    }
                     let msg = format!("FindNextVolumeW failed: {err}");
                     return Err(USimpleError::new(EXIT_ERR, msg));
                 }
@@ -529,13 +624,28 @@ pub fn read_fs_list() -> UResult<Vec<MountInfo>> {
         unsafe {
             FindVolumeClose(find_handle);
         }
+
+        // Network drives are not volumes of this machine, so they are found
+        // through the drive letters in use instead.
+        let drives = unsafe { GetLogicalDrives() };
+        for letter in (b'A'..=b'Z').filter(|l| drives & (1 << (l - b'A')) != 0) {
+            let root = format!("{}:\\", letter as char);
+            let drive_type = unsafe {
+                let root = to_nul_terminated_wide_string(&root);
+                GetDriveTypeW(root.as_ptr())
+            };
+            if drive_type == DRIVE_REMOTE {
+                mounts.push(MountInfo::from_network_drive(root));
+            }
+        }
         Ok(mounts)
     }
     #[cfg(any(
         target_os = "aix",
         target_os = "redox",
         target_os = "illumos",
-        target_os = "solaris"
+        target_os = "solaris",
+        target_family = "wasm"
     ))]
     {
         // No method to read mounts, yet
@@ -612,53 +722,58 @@ impl FsUsage {
     }
     #[cfg(windows)]
     pub fn new(path: &Path) -> UResult<Self> {
-        let mut root_path = [0u16; MAX_PATH];
+        let path = to_nul_terminated_wide_string(path);
+
+        let mut bytes_available = 0;
+        let mut total_bytes = 0;
+        let mut free_bytes = 0;
         let success = unsafe {
-            let path = to_nul_terminated_wide_string(path);
-            GetVolumePathNamesForVolumeNameW(
-                //path_utf8.as_ptr(),
+            GetDiskFreeSpaceExW(
                 path.as_ptr(),
-                root_path.as_mut_ptr(),
-                root_path.len() as u32,
-                ptr::null_mut(),
+                &raw mut bytes_available,
+                &raw mut total_bytes,
+                &raw mut free_bytes,
             )
         };
         if 0 == success {
-            let msg = format!(
-                "GetVolumePathNamesForVolumeNameW failed: {}",
-                IOError::last_os_error()
-            );
+            let msg = format!("GetDiskFreeSpaceExW failed: {}", IOError::last_os_error());
             return Err(USimpleError::new(EXIT_ERR, msg));
         }
 
+        // The sizes are counted in clusters, as the size of the file system
+        // blocks, when the file system tells it; some network shares don't.
         let mut sectors_per_cluster = 0;
         let mut bytes_per_sector = 0;
         let mut number_of_free_clusters = 0;
         let mut total_number_of_clusters = 0;
-
-        unsafe {
-            let path = to_nul_terminated_wide_string(path);
+        let success = unsafe {
             GetDiskFreeSpaceW(
                 path.as_ptr(),
                 &raw mut sectors_per_cluster,
                 &raw mut bytes_per_sector,
                 &raw mut number_of_free_clusters,
                 &raw mut total_number_of_clusters,
-            );
-        }
-
+            )
+        };
         let bytes_per_cluster = sectors_per_cluster as u64 * bytes_per_sector as u64;
+        let blocksize = if success != 0 && bytes_per_cluster > 0 {
+            bytes_per_cluster
+        } else {
+            1
+        };
+
         Ok(Self {
             // f_bsize      File system block size.
-            blocksize: bytes_per_cluster,
+            blocksize,
             // f_blocks - Total number of blocks on the file system, in units of f_frsize.
             // frsize =     Fundamental file system block size (fragment size).
-            blocks: total_number_of_clusters as u64,
+            blocks: total_bytes / blocksize,
             //  Total number of free blocks.
-            bfree: number_of_free_clusters as u64,
-            //  Total number of free blocks available to non-privileged processes.
-            bavail: 0,
-            bavail_top_bit_set: ((bytes_per_sector as u64) & (1u64.rotate_right(1))) != 0,
+            bfree: free_bytes / blocksize,
+            //  Total number of free blocks available to non-privileged processes,
+            //  which takes the disk quota of the user into account.
+            bavail: bytes_available / blocksize,
+            bavail_top_bit_set: false,
             // Total number of file nodes (inodes) on the file system.
             files: 0, // Not available on windows
             // Total number of free file nodes (inodes).
@@ -1070,6 +1185,17 @@ pub fn pretty_fstype<'a>(fstype: i64) -> Cow<'a, str> {
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(windows)]
+    fn test_split_multi_string() {
+        let buf: Vec<u16> = "C:\\\0D:\\mnt\\vhd\\\0\0\0".encode_utf16().collect();
+        assert_eq!(
+            split_multi_string(&buf),
+            [OsString::from("C:\\"), OsString::from("D:\\mnt\\vhd\\")]
+        );
+        assert!(split_multi_string(&[0, 0]).is_empty());
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_file_type() {
@@ -32,7 +32,7 @@ fn get_encoding() -> &'static MbEncoding {
     ENCODING.get_or_init(|| {
         let val = ["LC_ALL", "LC_CTYPE", "LANG"]
             .iter()
-            .find_map(|&k| std::env::var(k).ok().filter(|v| !v.is_empty()));
+            .find_map(|&k| crate::env::var(k).ok().filter(|v| !v.is_empty()));
         let s = match val.as_deref() {
             Some(s) if s != "C" && s != "POSIX" => s,
             _ => return MbEncoding::Utf8,
@@ -3,10 +3,15 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-//! Fast comparison for strings representing a base 10 number without precision loss.
+//! Comparison of sort keys that have to be parsed first, i.e. numbers and month names.
 //!
+//! The parsed part of a key is meant to be computed once per line and cached, not on every
+//! comparison: [`NumInfo`] for numeric and human numeric keys, [`Month`] for month keys.
+//! Version keys are compared with [`crate::version_cmp`].
+//!
+//! Numbers are compared as strings, without precision loss.
 //! To be able to short-circuit when comparing, [`NumInfo`] must be passed along with each number
-//! to [`numeric_str_cmp`]. [`NumInfo`] is generally obtained by calling [`NumInfo::parse`] and should be cached.
+//! to [`numeric_str_cmp`]. [`NumInfo`] is generally obtained by calling [`NumInfo::parse`].
 //! It is allowed to arbitrarily modify the exponent afterward, which is equivalent to shifting the decimal point.
 //!
 //! More specifically, exponent can be understood so that the original number is in `(1..10)*10^exponent`.
@@ -273,6 +278,45 @@ pub fn numeric_str_cmp((a, a_info): (&[u8], &NumInfo), (b, b_info): (&[u8], &Num
     }
 }
 
+/// A month name, as compared by `sort -M`. Anything that is not a month sorts first.
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd, Clone, Copy)]
+pub enum Month {
+    Unknown,
+    January,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+/// Parse the beginning string into a Month, returning [`Month::Unknown`] on errors.
+pub fn month_parse(line: &[u8]) -> Month {
+    let line = line.trim_ascii_start();
+
+    match line.get(..3).map(<[u8]>::to_ascii_uppercase).as_deref() {
+        Some(b"JAN") => Month::January,
+        Some(b"FEB") => Month::February,
+        Some(b"MAR") => Month::March,
+        Some(b"APR") => Month::April,
+        Some(b"MAY") => Month::May,
+        Some(b"JUN") => Month::June,
+        Some(b"JUL") => Month::July,
+        Some(b"AUG") => Month::August,
+        Some(b"SEP") => Month::September,
+        Some(b"OCT") => Month::October,
+        Some(b"NOV") => Month::November,
+        Some(b"DEC") => Month::December,
+        _ => Month::Unknown,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -513,4 +557,13 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn month() {
+        assert_eq!(month_parse(b"JaN"), Month::January);
+        assert_eq!(month_parse(b"  dec 1"), Month::December);
+        assert_eq!(month_parse(b"ja"), Month::Unknown);
+        assert!(month_parse(b"JaN") < month_parse(b"OCt"));
+        assert!(month_parse(b"foo") < month_parse(b"jan"));
+    }
 }
@@ -7,7 +7,10 @@
 
 // spell-checker:ignore (vars) fperm srwx
 
+#[cfg(not(target_family = "wasm"))]
 use libc::umask;
+#[cfg(unix)]
+use std::{fs, io, os::unix::fs::PermissionsExt, path::Path};
 
 pub fn parse_numeric(fperm: u32, mut mode: &str, considering_dir: bool) -> Result<u32, String> {
     let (op, pos) = parse_op(mode).map_or_else(|_| (None, 0), |(op, pos)| (Some(op), pos));
@@ -169,6 +172,44 @@ pub fn parse(mode_string: &str, considering_dir: bool, umask: u32) -> Result<u32
     parse_chmod(0, mode_string, considering_dir, umask)
 }
 
+/// The mode `mode_string` gives a directory whose mode is `current`, applied
+/// from `base` like the `-m` of `mkdir` and `install -d`.
+///
+/// The set-user-ID and set-group-ID bits a directory inherits from its parent
+/// are kept unless `mode_string` names them, as with `chmod`: `755` and `=rwx`
+/// keep them, `00755`, `g-s` and `2755` decide them.
+pub fn parse_dir_mode(
+    current: u32,
+    base: u32,
+    mode_string: &str,
+    umask: u32,
+) -> Result<u32, String> {
+    parse_chmod(base | (current & 0o6000), mode_string, true, umask)
+}
+
+/// Give the directory at `path` the mode of [`parse_dir_mode`].
+///
+/// `mkdir(2)` ignores the set-user-ID and set-group-ID bits it is asked for
+/// and hands down the parent's set-group-ID bit instead, so a directory
+/// created with an explicit mode needs this to end up with exactly that mode.
+/// Nothing is changed if the directory already has it.
+#[cfg(unix)]
+pub fn set_dir_mode(path: &Path, base: u32, mode_string: &str, umask: u32) -> io::Result<()> {
+    let current = fs::metadata(path)?.permissions().mode() & 0o7777;
+    let mode = parse_dir_mode(current, base, mode_string, umask).map_err(io::Error::other)?;
+    if mode != current {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    }
+    Ok(())
+}
+
+/// The host VFS has no umask, so report the conventional default.
+#[cfg(target_family = "wasm")]
+pub fn get_umask() -> u32 {
+    0o022
+}
+
+#[cfg(not(target_family = "wasm"))]
 pub fn get_umask() -> u32 {
     // There's no portable way to read the umask without changing it.
     // We have to replace it and then quickly set it back, hopefully before
@@ -200,6 +241,7 @@ mod tests {
 
     use super::parse;
     use super::parse_chmod;
+    use super::parse_dir_mode;
 
     #[test]
     fn test_chmod_symbolic_modes() {
@@ -334,4 +376,24 @@ mod tests {
         // First add user write, then set to 755 (should override)
         assert_eq!(parse("u+w,755", false, 0).unwrap(), 0o755);
     }
+
+    #[test]
+    fn test_parse_dir_mode_inherited_bits() {
+        // The set-group-ID bit from the parent stays unless the mode names it
+        assert_eq!(parse_dir_mode(0o2755, 0o777, "755", 0o022).unwrap(), 0o2755);
+        assert_eq!(
+            parse_dir_mode(0o2700, 0o777, "=rwx", 0o077).unwrap(),
+            0o2700
+        );
+        assert_eq!(
+            parse_dir_mode(0o2755, 0o777, "00755", 0o022).unwrap(),
+            0o755
+        );
+        assert_eq!(parse_dir_mode(0o2777, 0o777, "g-s", 0o022).unwrap(), 0o777);
+        // Special bits that `mkdir(2)` drops are put back
+        assert_eq!(parse_dir_mode(0o755, 0o777, "6755", 0o022).unwrap(), 0o6755);
+        assert_eq!(parse_dir_mode(0o777, 0o777, "u+s", 0o022).unwrap(), 0o4777);
+        // The sticky bit is never inherited
+        assert_eq!(parse_dir_mode(0o1755, 0, "755", 0).unwrap(), 0o755);
+    }
 }
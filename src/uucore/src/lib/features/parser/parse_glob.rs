@@ -4,11 +4,14 @@
 // file that was distributed with this source code.
 //! Parsing a glob Pattern from a string.
 //!
-//! Use the [`from_str`] function to parse a [`Pattern`] from a string.
+//! Use the [`from_str`] function to parse a [`Pattern`] from a string, and
+//! [`PatternSet`] to match paths against a list of patterns, as with
+//! `du --exclude`.
 
-// cSpell:words fnmatch
+// cSpell:words fnmatch globstar
 
-use glob::{Pattern, PatternError};
+use glob::{MatchOptions, Pattern, PatternError};
+use std::path::is_separator;
 
 fn fix_negation(glob: &str) -> String {
     let mut chars = glob.chars().collect::<Vec<_>>();
@@ -56,6 +59,96 @@ pub fn from_str(glob: &str) -> Result<Pattern, PatternError> {
     Pattern::new(&fix_negation(glob))
 }
 
+/// Replace each run of `*` with a single one, so that `**` means the same as
+/// `*` and is not taken by [`Pattern`] for a recursive wildcard.
+fn collapse_stars(glob: &str) -> String {
+    let mut collapsed = String::with_capacity(glob.len());
+    for c in glob.chars() {
+        if !(c == '*' && collapsed.ends_with('*')) {
+            collapsed.push(c);
+        }
+    }
+    collapsed
+}
+
+/// A list of glob patterns that a path is matched against, the way GNU
+/// matches its exclude lists: a pattern matches a path if it matches the
+/// whole path or any trailing run of its components, so `b/c` matches
+/// `a/b/c` as well as `b/c`.
+///
+/// By default, `*` also matches `/` and `**` means the same as `*`, as with
+/// fnmatch. With [`PatternSet::globstar`], `*` stops at `/` and a `**`
+/// component matches any number of directories instead, like the `globstar`
+/// option of bash.
+#[derive(Debug, Default)]
+pub struct PatternSet {
+    patterns: Vec<Pattern>,
+    globstar: bool,
+    ignore_case: bool,
+}
+
+impl PatternSet {
+    /// An empty set, which matches nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether `**` matches any number of directories. This must be set
+    /// before patterns are added.
+    pub fn globstar(mut self, globstar: bool) -> Self {
+        self.globstar = globstar;
+        self
+    }
+
+    /// Whether letters match regardless of their case.
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Parse `glob` with [`from_str`] and add it to the set.
+    pub fn add(&mut self, glob: &str) -> Result<(), PatternError> {
+        let pattern = if self.globstar {
+            from_str(glob)?
+        } else {
+            from_str(&collapse_stars(glob))?
+        };
+        self.patterns.push(pattern);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether any pattern matches `path` or one of its trailing runs of
+    /// components.
+    pub fn matches(&self, path: &str) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let options = MatchOptions {
+            case_sensitive: !self.ignore_case,
+            require_literal_separator: self.globstar,
+            require_literal_leading_dot: false,
+        };
+        let mut rest = path;
+        loop {
+            if self
+                .patterns
+                .iter()
+                .any(|pattern| pattern.matches_with(rest, options))
+            {
+                return true;
+            }
+            match rest.find(is_separator) {
+                Some(i) => rest = &rest[i + 1..],
+                None => return false,
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -66,6 +159,44 @@ mod tests {
         assert_eq!(from_str("[^abc]").unwrap(), Pattern::new("[!abc]").unwrap());
     }
 
+    #[test]
+    fn test_pattern_set() {
+        let mut set = PatternSet::new();
+        assert!(!set.matches("a"));
+        set.add("b/c").unwrap();
+        set.add("*.o").unwrap();
+        set.add("x**y").unwrap();
+        assert!(set.matches("b/c"));
+        assert!(set.matches("a/b/c"));
+        assert!(!set.matches("a/bb/c"));
+        assert!(!set.matches("a/b/c/d"));
+        assert!(set.matches("dir/sub/file.o"));
+        // '*' matches '/' unless globstar is set
+        assert!(set.matches("x/z/y"));
+        assert!(!set.matches("X.O"));
+    }
+
+    #[test]
+    fn test_pattern_set_globstar() {
+        let mut set = PatternSet::new().globstar(true);
+        set.add("a/**/*.o").unwrap();
+        assert!(set.matches("a/x.o"));
+        assert!(set.matches("top/a/b/c/x.o"));
+        assert!(!set.matches("b/x.o"));
+        let mut set = PatternSet::new().globstar(true);
+        set.add("a*").unwrap();
+        assert!(set.matches("dir/abc"));
+        assert!(!set.matches("ab/c"));
+    }
+
+    #[test]
+    fn test_pattern_set_ignore_case() {
+        let mut set = PatternSet::new().ignore_case(true);
+        set.add("*.[a-c]").unwrap();
+        assert!(set.matches("dir/FILE.B"));
+        assert!(!set.matches("dir/file.d"));
+    }
+
     #[test]
     fn test_fix_negation() {
         // Happy/Simple case
@@ -16,6 +16,8 @@ use clap::{Arg, ArgMatches, Command};
 
 use libc::{gid_t, uid_t};
 use options::traverse;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ffi::OsString;
 
 #[cfg(not(target_os = "linux"))]
@@ -75,6 +77,35 @@ fn chown<P: AsRef<Path>>(path: P, uid: uid_t, gid: gid_t, follow: bool) -> IORes
     }
 }
 
+thread_local! {
+    /// The names of the users and groups looked up so far. A recursive
+    /// `chown -v` or `chown -c` prints the same few names for every file, and
+    /// looking one up may mean reading `/etc/passwd` or asking a directory
+    /// service.
+    static USER_NAMES: RefCell<HashMap<uid_t, String>> = RefCell::new(HashMap::new());
+    static GROUP_NAMES: RefCell<HashMap<gid_t, String>> = RefCell::new(HashMap::new());
+}
+
+/// The name of a user, or its id if it has none.
+fn user_name(uid: uid_t) -> String {
+    USER_NAMES.with_borrow_mut(|names| {
+        names
+            .entry(uid)
+            .or_insert_with(|| entries::uid2usr(uid).unwrap_or_else(|_| uid.to_string()))
+            .clone()
+    })
+}
+
+/// The name of a group, or its id if it has none.
+fn group_name(gid: gid_t) -> String {
+    GROUP_NAMES.with_borrow_mut(|names| {
+        names
+            .entry(gid)
+            .or_insert_with(|| entries::gid2grp(gid).unwrap_or_else(|_| gid.to_string()))
+            .clone()
+    })
+}
+
 /// Perform the change of owner on a path
 /// with the various options
 /// and error messages management
@@ -90,8 +121,15 @@ pub fn wrap_chown<P: AsRef<Path>>(
     let dest_gid = dest_gid.unwrap_or_else(|| meta.gid());
     let path = path.as_ref();
     let mut out: String = String::new();
+    let changed = dest_uid != meta.uid() || dest_gid != meta.gid();
 
-    if let Err(e) = chown(path, dest_uid, dest_gid, follow) {
+    // A file that is already owned as requested is left alone
+    let result = if changed {
+        chown(path, dest_uid, dest_gid, follow)
+    } else {
+        Ok(())
+    };
+    if let Err(e) = result {
         match verbosity.level {
             VerbosityLevel::Silent => (),
             level => {
@@ -110,8 +148,8 @@ pub fn wrap_chown<P: AsRef<Path>>(
                         format!(
                             "{out}\nfailed to change group of {} from {} to {}",
                             path.quote(),
-                            entries::gid2grp(gid).unwrap_or_else(|_| gid.to_string()),
-                            entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                            group_name(gid),
+                            group_name(dest_gid)
                         )
                     } else {
                         let uid = meta.uid();
@@ -119,10 +157,10 @@ pub fn wrap_chown<P: AsRef<Path>>(
                         format!(
                             "{out}\nfailed to change ownership of {} from {}:{} to {}:{}",
                             path.quote(),
-                            entries::uid2usr(uid).unwrap_or_else(|_| uid.to_string()),
-                            entries::gid2grp(gid).unwrap_or_else(|_| gid.to_string()),
-                            entries::uid2usr(dest_uid).unwrap_or_else(|_| dest_uid.to_string()),
-                            entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                            user_name(uid),
+                            group_name(gid),
+                            user_name(dest_uid),
+                            group_name(dest_gid)
                         )
                     };
                 }
@@ -131,7 +169,6 @@ pub fn wrap_chown<P: AsRef<Path>>(
         return Err(out);
     }
 
-    let changed = dest_uid != meta.uid() || dest_gid != meta.gid();
     if changed {
         match verbosity.level {
             VerbosityLevel::Changes | VerbosityLevel::Verbose => {
@@ -140,8 +177,8 @@ pub fn wrap_chown<P: AsRef<Path>>(
                     format!(
                         "changed group of {} from {} to {}",
                         path.quote(),
-                        entries::gid2grp(gid).unwrap_or_else(|_| gid.to_string()),
-                        entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                        group_name(gid),
+                        group_name(dest_gid)
                     )
                 } else {
                     let gid = meta.gid();
@@ -149,10 +186,10 @@ pub fn wrap_chown<P: AsRef<Path>>(
                     format!(
                         "changed ownership of {} from {}:{} to {}:{}",
                         path.quote(),
-                        entries::uid2usr(uid).unwrap_or_else(|_| uid.to_string()),
-                        entries::gid2grp(gid).unwrap_or_else(|_| gid.to_string()),
-                        entries::uid2usr(dest_uid).unwrap_or_else(|_| dest_uid.to_string()),
-                        entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                        user_name(uid),
+                        group_name(gid),
+                        user_name(dest_uid),
+                        group_name(dest_gid)
                     )
                 };
             }
@@ -163,14 +200,14 @@ pub fn wrap_chown<P: AsRef<Path>>(
             format!(
                 "group of {} retained as {}",
                 path.quote(),
-                entries::gid2grp(dest_gid).unwrap_or_default()
+                group_name(dest_gid)
             )
         } else {
             format!(
                 "ownership of {} retained as {}:{}",
                 path.quote(),
-                entries::uid2usr(dest_uid).unwrap_or_else(|_| dest_uid.to_string()),
-                entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                user_name(dest_uid),
+                group_name(dest_gid)
             )
         };
     }
@@ -192,6 +229,20 @@ pub enum TraverseSymlinks {
     All,
 }
 
+impl TraverseSymlinks {
+    /// Returns whether a symlink should be followed during a recursive traversal.
+    ///
+    /// `-P` never follows symlinks, `-H` only follows those given on the command
+    /// line, and `-L` follows every symlink encountered.
+    pub fn should_follow(&self, is_command_line_arg: bool) -> bool {
+        match self {
+            Self::None => false,
+            Self::First => is_command_line_arg,
+            Self::All => true,
+        }
+    }
+}
+
 pub struct ChownExecutor {
     pub dest_uid: Option<u32>,
     pub dest_gid: Option<u32>,
@@ -300,7 +351,7 @@ impl ChownExecutor {
 
         if self.recursive
             && self.preserve_root
-            && is_root(path, self.traverse_symlinks != TraverseSymlinks::None)
+            && is_root(path, self.traverse_symlinks.should_follow(true))
         {
             // Fail-fast, do not attempt to recurse.
             return 1;
@@ -385,8 +436,16 @@ impl ChownExecutor {
         let dest_uid = self.dest_uid.unwrap_or_else(|| meta.uid());
         let dest_gid = self.dest_gid.unwrap_or_else(|| meta.gid());
 
-        // Use fchown (safe) to change the directory's ownership
-        if let Err(e) = dir_fd.fchown(self.dest_uid, self.dest_gid) {
+        // Use fchown (safe) to change the directory's ownership, unless it
+        // is already owned as requested. `meta` may describe a symlink to the
+        // directory instead, which says nothing about the directory's owner.
+        let changed = !meta.is_dir() || dest_uid != meta.uid() || dest_gid != meta.gid();
+        let result = if changed {
+            dir_fd.fchown(self.dest_uid, self.dest_gid)
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
             let mut error_msg = format!(
                 "changing {} of {}: {e}",
                 if self.verbosity.groups_only {
@@ -403,8 +462,8 @@ impl ChownExecutor {
                     format!(
                         "{error_msg}\nfailed to change group of {} from {} to {}",
                         path.quote(),
-                        entries::gid2grp(gid).unwrap_or_else(|_| gid.to_string()),
-                        entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                        group_name(gid),
+                        group_name(dest_gid)
                     )
                 } else {
                     let uid = meta.uid();
@@ -412,10 +471,10 @@ impl ChownExecutor {
                     format!(
                         "{error_msg}\nfailed to change ownership of {} from {}:{} to {}:{}",
                         path.quote(),
-                        entries::uid2usr(uid).unwrap_or_else(|_| uid.to_string()),
-                        entries::gid2grp(gid).unwrap_or_else(|_| gid.to_string()),
-                        entries::uid2usr(dest_uid).unwrap_or_else(|_| dest_uid.to_string()),
-                        entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                        user_name(uid),
+                        group_name(gid),
+                        user_name(dest_uid),
+                        group_name(dest_gid)
                     )
                 };
             }
@@ -433,7 +492,7 @@ impl ChownExecutor {
         let root = root.as_ref();
 
         // Don't traverse into symlinks if configured not to
-        if self.traverse_symlinks == TraverseSymlinks::None && root.is_symlink() {
+        if !self.traverse_symlinks.should_follow(true) && root.is_symlink() {
             return 0;
         }
 
@@ -476,7 +535,7 @@ impl ChownExecutor {
             let entry_path = dir_path.join(&entry_name);
 
             // Get metadata for the entry
-            let follow = self.traverse_symlinks == TraverseSymlinks::All;
+            let follow = self.traverse_symlinks.should_follow(false);
 
             let meta = match dir_fd.metadata_at(&entry_name, follow.into()) {
                 Ok(m) => m,
@@ -490,7 +549,7 @@ impl ChownExecutor {
             };
 
             if self.preserve_root
-                && is_root(&entry_path, self.traverse_symlinks == TraverseSymlinks::All)
+                && is_root(&entry_path, self.traverse_symlinks.should_follow(false))
             {
                 *ret = 1;
                 return;
@@ -500,13 +559,21 @@ impl ChownExecutor {
             if self.matched(meta.uid(), meta.gid()) {
                 // Use fchownat for the actual ownership change
                 let follow_symlinks =
-                    self.dereference || self.traverse_symlinks == TraverseSymlinks::All;
+                    self.dereference || self.traverse_symlinks.should_follow(false);
 
                 // Only pass the IDs that should actually be changed
                 let chown_uid = self.dest_uid;
                 let chown_gid = self.dest_gid;
 
-                if let Err(e) =
+                // Skip entries that are already owned as requested, as long
+                // as `meta` describes the file that would be changed
+                let unchanged = follow_symlinks == follow
+                    && chown_uid.is_none_or(|uid| uid == meta.uid())
+                    && chown_gid.is_none_or(|gid| gid == meta.gid());
+
+                if unchanged {
+                    self.report_ownership_change_success(&entry_path, meta.uid(), meta.gid());
+                } else if let Err(e) =
                     dir_fd.chown_at(&entry_name, chown_uid, chown_gid, follow_symlinks.into())
                 {
                     *ret = 1;
@@ -562,13 +629,13 @@ impl ChownExecutor {
         let root = root.as_ref();
 
         // walkdir always dereferences the root directory, so we have to check it ourselves
-        if self.traverse_symlinks == TraverseSymlinks::None && root.is_symlink() {
+        if !self.traverse_symlinks.should_follow(true) && root.is_symlink() {
             return 0;
         }
 
         let mut ret = 0;
         let mut iterator = WalkDir::new(root)
-            .follow_links(self.traverse_symlinks == TraverseSymlinks::All)
+            .follow_links(self.traverse_symlinks.should_follow(false))
             .min_depth(1)
             .into_iter();
         // We can't use a for loop because we need to manipulate the iterator inside the loop.
@@ -605,8 +672,7 @@ impl ChownExecutor {
                 continue;
             };
 
-            if self.preserve_root && is_root(path, self.traverse_symlinks == TraverseSymlinks::All)
-            {
+            if self.preserve_root && is_root(path, self.traverse_symlinks.should_follow(false)) {
                 // Fail-fast, do not recurse further.
                 return 1;
             }
@@ -674,15 +740,9 @@ impl ChownExecutor {
     fn print_verbose_ownership_retained_as(&self, path: &Path, uid: u32, gid: Option<u32>) {
         if self.verbosity.level == VerbosityLevel::Verbose {
             let ownership = match (self.dest_uid, self.dest_gid, gid) {
-                (Some(_), Some(_), Some(gid)) => format!(
-                    "{}:{}",
-                    entries::uid2usr(uid).unwrap_or_else(|_| uid.to_string()),
-                    entries::gid2grp(gid).unwrap_or_else(|_| gid.to_string())
-                ),
-                (None, Some(_), Some(gid)) => {
-                    entries::gid2grp(gid).unwrap_or_else(|_| gid.to_string())
-                }
-                _ => entries::uid2usr(uid).unwrap_or_else(|_| uid.to_string()),
+                (Some(_), Some(_), Some(gid)) => format!("{}:{}", user_name(uid), group_name(gid)),
+                (None, Some(_), Some(gid)) => group_name(gid),
+                _ => user_name(uid),
             };
             if self.verbosity.groups_only {
                 println!("group of {} retained as {ownership}", path.quote());
@@ -724,20 +784,17 @@ impl ChownExecutor {
                         format!(
                             "changed group of {} from {} to {}",
                             path.quote(),
-                            entries::gid2grp(original_gid)
-                                .unwrap_or_else(|_| original_gid.to_string()),
-                            entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                            group_name(original_gid),
+                            group_name(dest_gid)
                         )
                     } else {
                         format!(
                             "changed ownership of {} from {}:{} to {}:{}",
                             path.quote(),
-                            entries::uid2usr(original_uid)
-                                .unwrap_or_else(|_| original_uid.to_string()),
-                            entries::gid2grp(original_gid)
-                                .unwrap_or_else(|_| original_gid.to_string()),
-                            entries::uid2usr(dest_uid).unwrap_or_else(|_| dest_uid.to_string()),
-                            entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                            user_name(original_uid),
+                            group_name(original_gid),
+                            user_name(dest_uid),
+                            group_name(dest_gid)
                         )
                     };
                     show_error!("{output}");
@@ -749,14 +806,14 @@ impl ChownExecutor {
                 format!(
                     "group of {} retained as {}",
                     path.quote(),
-                    entries::gid2grp(dest_gid).unwrap_or_default()
+                    group_name(dest_gid)
                 )
             } else {
                 format!(
                     "ownership of {} retained as {}:{}",
                     path.quote(),
-                    entries::uid2usr(dest_uid).unwrap_or_else(|_| dest_uid.to_string()),
-                    entries::gid2grp(dest_gid).unwrap_or_else(|_| dest_gid.to_string())
+                    user_name(dest_uid),
+                    group_name(dest_gid)
                 )
             };
             show_error!("{output}");
@@ -984,6 +1041,16 @@ mod tests {
     #[cfg(unix)]
     use tempfile::tempdir;
 
+    #[test]
+    fn test_traverse_symlinks_should_follow() {
+        assert!(!TraverseSymlinks::None.should_follow(true));
+        assert!(!TraverseSymlinks::None.should_follow(false));
+        assert!(TraverseSymlinks::First.should_follow(true));
+        assert!(!TraverseSymlinks::First.should_follow(false));
+        assert!(TraverseSymlinks::All.should_follow(true));
+        assert!(TraverseSymlinks::All.should_follow(false));
+    }
+
     #[test]
     fn test_empty_string() {
         let path = PathBuf::new();
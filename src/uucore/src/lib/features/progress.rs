@@ -3,7 +3,25 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-//! Functions for formatting a number as a magnitude and a unit suffix.
+//! Progress reporting for long copies.
+//!
+//! `cp --progress` and `mv --progress` draw a bar from [`bytes_bar`], showing
+//! the bytes copied out of the total, the transfer rate and the estimated
+//! time left. `dd status=progress` prints its own statistics line instead,
+//! as GNU `dd` does, and uses [`to_magnitude_and_suffix`] and
+//! [`bytes_per_second`] for it.
+//!
+//! Bars are drawn on standard error, and only when it is a terminal, so
+//! that redirected output does not fill up with escape sequences.
+
+use std::io::{IsTerminal, stderr};
+use std::time::Duration;
+
+pub use indicatif::{MultiProgress, ProgressBar};
+use indicatif::{ProgressDrawTarget, ProgressStyle};
+
+/// The layout of the bars returned by [`bytes_bar`].
+const BYTES_TEMPLATE: &str = "{msg}: [{elapsed_precise}] {wide_bar} {bytes:>7}/{total_bytes:7} {binary_bytes_per_sec:>11} ETA {eta}";
 
 /// The first ten powers of 1024.
 const IEC_BASES: [u128; 10] = [
@@ -39,7 +57,7 @@ const SI_SUFFIXES: [&str; 9] = ["B", "kB", "MB", "GB", "TB", "PB", "EB", "ZB", "
 
 /// A `SuffixType` determines whether the suffixes are 1000 or 1024 based.
 #[derive(Clone, Copy)]
-pub(crate) enum SuffixType {
+pub enum SuffixType {
     Iec,
     Si,
 }
@@ -61,7 +79,7 @@ impl SuffixType {
 /// Convert a number into a magnitude and a multi-byte unit suffix.
 ///
 /// The returned string has a maximum length of 5 chars, for example: "1.1kB", "999kB", "1MB".
-pub(crate) fn to_magnitude_and_suffix(n: u128, suffix_type: SuffixType) -> String {
+pub fn to_magnitude_and_suffix(n: u128, suffix_type: SuffixType) -> String {
     let (base, suffix) = suffix_type.base_and_suffix(n);
     // TODO To match dd on my machine, we would need to round like
     // this:
@@ -87,10 +105,40 @@ pub(crate) fn to_magnitude_and_suffix(n: u128, suffix_type: SuffixType) -> Strin
     }
 }
 
+/// The average number of bytes transferred per second.
+///
+/// Durations under a millisecond count as one millisecond, so the rate of a
+/// copy that has only just started is not infinite.
+pub fn bytes_per_second(bytes: u128, elapsed: Duration) -> u128 {
+    (1000 * bytes) / elapsed.as_millis().max(1)
+}
+
+/// Where progress bars are drawn: standard error if it is a terminal,
+/// nowhere otherwise.
+pub fn draw_target() -> ProgressDrawTarget {
+    if stderr().is_terminal() {
+        ProgressDrawTarget::stderr()
+    } else {
+        ProgressDrawTarget::hidden()
+    }
+}
+
+/// A bar for copying `total` bytes, labelled with the utility name.
+pub fn bytes_bar(total: u64) -> ProgressBar {
+    ProgressBar::with_draw_target(Some(total), draw_target())
+        .with_style(ProgressStyle::with_template(BYTES_TEMPLATE).unwrap())
+        .with_message(crate::util_name())
+}
+
+/// A container for several bars drawn together, e.g. one per file as well as
+/// a count of the files done.
+pub fn multi_bar() -> MultiProgress {
+    MultiProgress::with_draw_target(draw_target())
+}
+
 #[cfg(test)]
 mod tests {
-
-    use crate::numbers::{SuffixType, to_magnitude_and_suffix};
+    use super::*;
 
     #[test]
     fn test_to_magnitude_and_suffix_powers_of_1024() {
@@ -156,4 +204,20 @@ mod tests {
             "1.0 GB"
         );
     }
+
+    #[test]
+    fn test_bytes_per_second() {
+        assert_eq!(bytes_per_second(1000, Duration::from_secs(2)), 500);
+        assert_eq!(bytes_per_second(3, Duration::from_millis(1500)), 2);
+        // no division by zero right at the start
+        assert_eq!(bytes_per_second(7, Duration::ZERO), 7000);
+    }
+
+    #[test]
+    fn test_bytes_bar() {
+        let bar = bytes_bar(10);
+        bar.inc(4);
+        assert_eq!(bar.length(), Some(10));
+        assert_eq!(bar.position(), 4);
+    }
 }
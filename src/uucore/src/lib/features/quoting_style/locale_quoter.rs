@@ -0,0 +1,75 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+use super::{EscapedChar, Quoter, Quotes};
+use crate::i18n::UEncoding;
+
+/// The quotes used by the `locale` and `clocale` styles in a UTF-8 locale.
+const LEFT_QUOTE: &str = "\u{2018}";
+const RIGHT_QUOTE: &str = "\u{2019}";
+
+/// Escape a name as a C string between the quotation marks of the locale,
+/// like GNU's `locale` and `clocale` quoting styles.
+///
+/// In a UTF-8 locale the name goes between `‘` and `’` and only a literal `’`
+/// needs a backslash, otherwise the ASCII `quotes` are used.
+pub(super) struct LocaleQuoter {
+    /// The ASCII quotes to use outside of a UTF-8 locale.
+    quotes: Quotes,
+
+    /// Whether the locale uses UTF-8, and so `‘` and `’`.
+    unicode: bool,
+
+    dirname: bool,
+
+    buffer: Vec<u8>,
+}
+
+impl LocaleQuoter {
+    pub fn new(quotes: Quotes, encoding: UEncoding, dirname: bool, size_hint: usize) -> Self {
+        let mut quoter = Self {
+            quotes,
+            unicode: encoding == UEncoding::Utf8,
+            dirname,
+            buffer: Vec::with_capacity(size_hint + 2),
+        };
+        quoter.push_quote(LEFT_QUOTE);
+        quoter
+    }
+
+    fn push_quote(&mut self, unicode_quote: &str) {
+        match (self.unicode, self.quotes) {
+            (true, _) => self.buffer.extend_from_slice(unicode_quote.as_bytes()),
+            (false, Quotes::None) => (),
+            (false, Quotes::Single) => self.buffer.push(b'\''),
+            (false, Quotes::Double) => self.buffer.push(b'"'),
+        }
+    }
+}
+
+impl Quoter for LocaleQuoter {
+    fn push_char(&mut self, input: char) {
+        let escaped: String = match input {
+            '\u{2019}' if self.unicode => "\\\u{2019}".to_string(),
+            '\'' | '"' if self.unicode => input.to_string(),
+            _ => EscapedChar::new_c(input, self.quotes, self.dirname)
+                .hide_control()
+                .collect(),
+        };
+        self.buffer.extend_from_slice(escaped.as_bytes());
+    }
+
+    fn push_invalid(&mut self, input: &[u8]) {
+        for b in input {
+            let escaped: String = EscapedChar::new_octal(*b).hide_control().collect();
+            self.buffer.extend_from_slice(escaped.as_bytes());
+        }
+    }
+
+    fn finalize(mut self: Box<Self>) -> Vec<u8> {
+        self.push_quote(RIGHT_QUOTE);
+        self.buffer
+    }
+}
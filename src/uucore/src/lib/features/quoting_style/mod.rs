@@ -11,6 +11,7 @@ use std::fmt;
 use crate::i18n::{self, UEncoding};
 use crate::quoting_style::c_quoter::CQuoter;
 use crate::quoting_style::literal_quoter::LiteralQuoter;
+use crate::quoting_style::locale_quoter::LocaleQuoter;
 use crate::quoting_style::shell_quoter::{EscapedShellQuoter, NonEscapedShellQuoter};
 
 mod escaped_char;
@@ -18,6 +19,7 @@ pub use escaped_char::{EscapeState, EscapedChar};
 
 mod c_quoter;
 mod literal_quoter;
+mod locale_quoter;
 mod shell_quoter;
 
 /// The quoting style to use when escaping a name.
@@ -50,6 +52,14 @@ pub enum QuotingStyle {
         /// Whether to show control and non-unicode characters, or replace them with `?`.
         show_control: bool,
     },
+
+    /// Escape the name as a C string between the quotation marks of the
+    /// locale: `‘` and `’` in a UTF-8 locale, `quotes` otherwise.
+    /// Used in, e.g., `ls --quoting-style=locale`.
+    Locale {
+        /// The type of quotes to use outside of a UTF-8 locale.
+        quotes: Quotes,
+    },
 }
 
 /// Provide sane defaults for quoting styles.
@@ -86,8 +96,35 @@ impl QuotingStyle {
         quotes: Quotes::Double,
     };
 
+    pub const LOCALE: Self = Self::Locale {
+        quotes: Quotes::Single,
+    };
+
+    pub const CLOCALE: Self = Self::Locale {
+        quotes: Quotes::Double,
+    };
+
+    /// Look up a style by the name GNU gives it, as accepted by
+    /// `ls --quoting-style` and the `QUOTING_STYLE` environment variable.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "literal" => Self::Literal {
+                show_control: false,
+            },
+            "shell" => Self::SHELL,
+            "shell-always" => Self::SHELL_QUOTE,
+            "shell-escape" => Self::SHELL_ESCAPE,
+            "shell-escape-always" => Self::SHELL_ESCAPE_QUOTE,
+            "c" => Self::C_DOUBLE,
+            "escape" => Self::C_NO_QUOTES,
+            "locale" => Self::LOCALE,
+            "clocale" => Self::CLOCALE,
+            _ => return None,
+        })
+    }
+
     /// Set the `show_control` field of the quoting style.
-    /// Note: this is a no-op for the `C` variant.
+    /// Note: this is a no-op for the `C` and `Locale` variants.
     pub fn show_control(self, show_control: bool) -> Self {
         use QuotingStyle::*;
         match self {
@@ -101,7 +138,7 @@ impl QuotingStyle {
                 show_control,
             },
             Literal { .. } => Literal { show_control },
-            C { .. } => self,
+            C { .. } | Locale { .. } => self,
         }
     }
 }
@@ -137,7 +174,6 @@ pub enum Quotes {
 
     /// Use double quotes.
     Double,
-    // TODO: Locale
 }
 
 /// Escape a name according to the given quoting style.
@@ -158,6 +194,9 @@ fn escape_name_inner(
     let mut quoter: Box<dyn Quoter> = match style {
         QuotingStyle::Literal { .. } => Box::new(LiteralQuoter::new(name.len())),
         QuotingStyle::C { quotes } => Box::new(CQuoter::new(quotes, dirname, name.len())),
+        QuotingStyle::Locale { quotes } => {
+            Box::new(LocaleQuoter::new(quotes, encoding, dirname, name.len()))
+        }
         QuotingStyle::Shell {
             escape: true,
             always_quote,
@@ -250,6 +289,7 @@ impl fmt::Display for QuotingStyle {
             }
             Self::C { .. } => f.write_str("C"),
             Self::Literal { .. } => f.write_str("literal"),
+            Self::Locale { .. } => f.write_str("locale"),
         }
     }
 }
@@ -287,6 +327,8 @@ mod tests {
             "shell-always-show" => QuotingStyle::SHELL_QUOTE.show_control(true),
             "shell-escape" => QuotingStyle::SHELL_ESCAPE,
             "shell-escape-always" => QuotingStyle::SHELL_ESCAPE_QUOTE,
+            "locale" => QuotingStyle::LOCALE,
+            "clocale" => QuotingStyle::CLOCALE,
             _ => panic!("Invalid name!"),
         }
     }
@@ -1084,4 +1126,64 @@ mod tests {
         assert_eq!(format!("{}", Quotes::Single), "Single");
         assert_eq!(format!("{}", Quotes::Double), "Double");
     }
+
+    #[test]
+    fn test_locale_quoting() {
+        check_names_encoding(
+            UEncoding::Ascii,
+            "it's",
+            &[("'it\\'s'", "locale"), ("\"it's\"", "clocale")],
+        );
+        check_names_encoding(
+            UEncoding::Utf8,
+            "it's",
+            &[
+                ("\u{2018}it's\u{2019}", "locale"),
+                ("\u{2018}it's\u{2019}", "clocale"),
+            ],
+        );
+        check_names_encoding(
+            UEncoding::Utf8,
+            "a\u{2019}b",
+            &[("\u{2018}a\\\u{2019}b\u{2019}", "locale")],
+        );
+        check_names_encoding(
+            UEncoding::Ascii,
+            "new\nline\x1b",
+            &[
+                ("'new\\nline\\033'", "locale"),
+                ("\"new\\nline\\033\"", "clocale"),
+            ],
+        );
+        check_names_encoding(
+            UEncoding::Utf8,
+            "new\nline\x1b",
+            &[("\u{2018}new\\nline\\033\u{2019}", "locale")],
+        );
+    }
+
+    #[test]
+    fn test_locale_quoting_non_ascii() {
+        check_names_encoding(UEncoding::Ascii, "é", &[("'\\303\\251'", "locale")]);
+        check_names_encoding(UEncoding::Utf8, "é", &[("\u{2018}é\u{2019}", "locale")]);
+        check_names_encoding_raw(
+            UEncoding::Utf8,
+            b"\xff",
+            &[(b"\xe2\x80\x98\\377\xe2\x80\x99", "locale")],
+        );
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(
+            QuotingStyle::from_name("locale"),
+            Some(QuotingStyle::LOCALE)
+        );
+        assert_eq!(QuotingStyle::from_name("c"), Some(QuotingStyle::C_DOUBLE));
+        assert_eq!(
+            QuotingStyle::from_name("shell-escape-always"),
+            Some(QuotingStyle::SHELL_ESCAPE_QUOTE)
+        );
+        assert_eq!(QuotingStyle::from_name("bogus"), None);
+    }
 }
@@ -0,0 +1,71 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+//! A source of randomness for utilities that need unpredictable output, such
+//! as shuf, mktemp and shred.
+//!
+//! `wasm32-unknown-unknown` has no entropy source of its own, so on WASM the
+//! bytes come from the host through [`crate::wasm_io::set_random_hook`] when
+//! one is installed. Everywhere else this is `rand`'s thread-local generator.
+
+use rand::RngCore;
+use rand::rngs::ThreadRng;
+
+/// A random number generator that can be used wherever `rand` expects one.
+pub enum RandSource {
+    /// `rand`'s thread-local generator, seeded by the operating system.
+    Thread(ThreadRng),
+    /// Bytes supplied by the host's random hook.
+    #[cfg(target_family = "wasm")]
+    Host,
+}
+
+/// Get the random number generator for the running utility.
+pub fn rng() -> RandSource {
+    #[cfg(target_family = "wasm")]
+    if crate::wasm_io::has_random_hook() {
+        return RandSource::Host;
+    }
+    RandSource::Thread(rand::rng())
+}
+
+impl RngCore for RandSource {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            Self::Thread(rng) => rng.next_u32(),
+            #[cfg(target_family = "wasm")]
+            Self::Host => rand::rand_core::impls::next_u32_via_fill(self),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            Self::Thread(rng) => rng.next_u64(),
+            #[cfg(target_family = "wasm")]
+            Self::Host => rand::rand_core::impls::next_u64_via_fill(self),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            Self::Thread(rng) => rng.fill_bytes(dest),
+            #[cfg(target_family = "wasm")]
+            Self::Host => crate::wasm_io::fill_random(dest),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_rng_produces_varying_output() {
+        let mut rng = rng();
+        let first: [u8; 32] = rng.random();
+        let second: [u8; 32] = rng.random();
+        assert_ne!(first, second);
+    }
+}
@@ -451,6 +451,55 @@ pub fn install_signal_handler(
     Ok(())
 }
 
+/// The signal users send to ask a long-running utility how far it got:
+/// `SIGINFO` where the system has one, which `Ctrl-T` sends from the
+/// terminal, and `SIGUSR1` elsewhere.
+#[cfg(any(
+    target_vendor = "apple",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub const STATUS_SIGNAL: Signal = Signal::SIGINFO;
+/// The signal users send to ask a long-running utility how far it got:
+/// `SIGINFO` where the system has one, which `Ctrl-T` sends from the
+/// terminal, and `SIGUSR1` elsewhere.
+#[cfg(all(
+    unix,
+    not(any(
+        target_vendor = "apple",
+        target_os = "freebsd",
+        target_os = "dragonfly",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))
+))]
+pub const STATUS_SIGNAL: Signal = Signal::SIGUSR1;
+
+#[cfg(unix)]
+static STATUS_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+extern "C" fn status_signal_handler(_: std::os::raw::c_int) {
+    STATUS_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// Starts watching for [`STATUS_SIGNAL`], for [`status_requested`] to report.
+///
+/// The signal no longer terminates the process once this returns `Ok`.
+#[cfg(unix)]
+pub fn watch_status_signal() -> Result<(), Errno> {
+    install_signal_handler(STATUS_SIGNAL, status_signal_handler)
+}
+
+/// Whether [`STATUS_SIGNAL`] arrived since the last call, which the main
+/// loop of a utility calls to know when to print its progress.
+#[cfg(unix)]
+pub fn status_requested() -> bool {
+    STATUS_REQUESTED.swap(false, Ordering::Relaxed)
+}
+
 // Detect closed stdin/stdout before Rust reopens them as /dev/null (see issue #2873)
 #[cfg(unix)]
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -571,8 +620,29 @@ pub const fn sigpipe_was_ignored() -> bool {
 
 #[cfg(target_os = "linux")]
 pub fn ensure_stdout_not_broken() -> std::io::Result<bool> {
+    use nix::poll::PollTimeout;
+
+    // Use ZERO timeout to return immediately - we just want to check the current state.
+    Ok(!stdout_pipe_broken(PollTimeout::ZERO)?)
+}
+
+/// Blocks until the reader of a pipe on stdout goes away, and returns `true`
+/// then. Returns `false` at once if stdout is not a pipe.
+///
+/// This lets a program that mostly waits for input, like `tail -f`, exit as
+/// soon as its output is gone instead of at its next write.
+#[cfg(target_os = "linux")]
+pub fn wait_stdout_broken() -> std::io::Result<bool> {
+    use nix::poll::PollTimeout;
+
+    stdout_pipe_broken(PollTimeout::NONE)
+}
+
+#[cfg(target_os = "linux")]
+fn stdout_pipe_broken(timeout: nix::poll::PollTimeout) -> std::io::Result<bool> {
     use nix::{
-        poll::{PollFd, PollFlags, PollTimeout, poll},
+        errno::Errno,
+        poll::{PollFd, PollFlags, poll},
         sys::stat::{SFlag, fstat},
     };
     use std::io::stdout;
@@ -580,18 +650,24 @@ pub fn ensure_stdout_not_broken() -> std::io::Result<bool> {
 
     let out = stdout();
 
-    // First, check that stdout is a fifo and return true if it's not the case
+    // First, check that stdout is a fifo and return false if it's not the case
     let stat = fstat(out.as_fd())?;
     if !SFlag::from_bits_truncate(stat.st_mode).contains(SFlag::S_IFIFO) {
-        return Ok(true);
+        return Ok(false);
     }
 
-    // POLLRDBAND is the flag used by GNU tee.
+    // POLLRDBAND is the flag used by GNU tee. It is never set for the
+    // writing end of a pipe, so only POLLERR, when the pipe is broken, ends
+    // the wait.
     let mut pfds = [PollFd::new(out.as_fd(), PollFlags::POLLRDBAND)];
 
-    // Then, ensure that the pipe is not broken.
-    // Use ZERO timeout to return immediately - we just want to check the current state.
-    let res = poll(&mut pfds, PollTimeout::ZERO)?;
+    // Then, check whether the pipe is broken.
+    let res = loop {
+        match poll(&mut pfds, timeout) {
+            Err(Errno::EINTR) => {}
+            res => break res?,
+        }
+    };
 
     if res > 0 {
         // poll returned with events ready - check if POLLERR is set (pipe broken)
@@ -602,13 +678,13 @@ pub fn ensure_stdout_not_broken() -> std::io::Result<bool> {
                 true
             }
         });
-        return Ok(!error);
+        return Ok(error);
     }
 
-    // res == 0 means no events ready (timeout reached immediately with ZERO timeout).
+    // res == 0 means no events ready (the timeout was reached).
     // This means the pipe is healthy (not broken).
     // res < 0 would be an error, but nix returns Err in that case.
-    Ok(true)
+    Ok(false)
 }
 
 #[test]
@@ -642,3 +718,12 @@ fn name() {
         assert_eq!(signal_name_by_value(value), Some(*signal));
     }
 }
+
+#[cfg(unix)]
+#[test]
+fn status_signal_is_watched() {
+    watch_status_signal().unwrap();
+    nix::sys::signal::raise(STATUS_SIGNAL).unwrap();
+    assert!(status_requested());
+    assert!(!status_requested());
+}
@@ -7,15 +7,55 @@
 
 //! Set of functions related to time handling
 
-use jiff::Zoned;
 use jiff::fmt::StdIoWrite;
 use jiff::fmt::strtime::{BrokenDownTime, Config};
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, Zoned};
 use std::io::Write;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::error::{UResult, USimpleError};
 use crate::show_error;
 
+/// The current time. On WASM this comes from the host's clock hook, if installed.
+pub fn now() -> Timestamp {
+    #[cfg(target_family = "wasm")]
+    if let Some(now) = crate::wasm_io::now().and_then(|now| Timestamp::try_from(now).ok()) {
+        return now;
+    }
+    Timestamp::now()
+}
+
+/// The local time zone, as selected by `TZ`.
+pub fn local_time_zone() -> TimeZone {
+    local_time_zone_at(now())
+}
+
+/// The local time zone to use for displaying `time`, as selected by `TZ`.
+///
+/// `jiff` reads `TZ` from the process environment, which is empty on WASM,
+/// so there the variable is looked up through [`crate::env`] instead. If it
+/// is unset, the host's UTC offset at `time` is used, if known.
+#[cfg_attr(not(target_family = "wasm"), allow(unused_variables))]
+pub fn local_time_zone_at(time: Timestamp) -> TimeZone {
+    #[cfg(target_family = "wasm")]
+    {
+        if let Ok(tz) = crate::env::var("TZ") {
+            // A leading colon only marks the rest as an implementation-defined name.
+            let name = tz.strip_prefix(':').unwrap_or(&tz);
+            return TimeZone::get(name)
+                .or_else(|_| TimeZone::posix(name))
+                .unwrap_or(TimeZone::UTC);
+        }
+        if let Some(offset) = crate::wasm_io::tz_offset(time.into())
+            .and_then(|secs| jiff::tz::Offset::from_seconds(secs).ok())
+        {
+            return TimeZone::fixed(offset);
+        }
+    }
+    TimeZone::system()
+}
+
 /// Format the given date according to this time format style.
 fn format_zoned<W: Write>(out: &mut W, zoned: Zoned, fmt: &str) -> UResult<()> {
     let tm = BrokenDownTime::from(&zoned);
@@ -56,8 +96,8 @@ pub fn format_system_time<W: Write>(
     fmt: &str,
     mode: FormatSystemTimeFallback,
 ) -> UResult<()> {
-    let zoned: Result<Zoned, _> = time.try_into();
-    if let Ok(zoned) = zoned {
+    if let Ok(timestamp) = Timestamp::try_from(time) {
+        let zoned = timestamp.to_zoned(local_time_zone_at(timestamp));
         format_zoned(out, zoned, fmt)
     } else {
         // Assume that if we cannot build a Zoned element, the timestamp is
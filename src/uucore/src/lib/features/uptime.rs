@@ -3,7 +3,7 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-// spell-checker:ignore gettime BOOTTIME clockid boottime nusers loadavg getloadavg timeval
+// spell-checker:ignore gettime BOOTTIME clockid boottime nusers loadavg getloadavg timeval btime
 
 //! Provides functions to get system uptime, number of users and load average.
 
@@ -136,8 +136,6 @@ pub fn get_uptime(_boot_time: Option<time_t>) -> UResult<i64> {
 #[cfg(unix)]
 #[cfg(not(target_os = "openbsd"))]
 pub fn get_uptime(boot_time: Option<time_t>) -> UResult<i64> {
-    use crate::utmpx::Utmpx;
-    use libc::BOOT_TIME;
     use std::fs::File;
     use std::io::Read;
 
@@ -153,14 +151,8 @@ pub fn get_uptime(boot_time: Option<time_t>) -> UResult<i64> {
         return Ok(uptime);
     }
 
-    // Try provided boot_time or derive from utmpx
-    let derived_boot_time = boot_time.or_else(|| {
-        Utmpx::iter_all_records()
-            .filter(|r| r.record_type() == BOOT_TIME)
-            .map(|r| r.login_time().unix_timestamp())
-            .find(|&ts| ts > 0)
-            .map(|ts| ts as time_t)
-    });
+    // Try provided boot_time or derive it from utmpx and the kernel
+    let derived_boot_time = boot_time.or_else(get_boot_time);
 
     // macOS-specific fallback: use sysctl kern.boottime when utmpx did not provide BOOT_TIME
     //
@@ -197,6 +189,117 @@ pub fn get_uptime(boot_time: Option<time_t>) -> UResult<i64> {
     Err(UptimeError::SystemUptime)?
 }
 
+/// Get the system boot time, in seconds since the Unix epoch.
+///
+/// The sources are tried in this order, the first one that is available wins:
+///
+/// 1. the `BOOT_TIME` record of the utmpx database,
+/// 2. the start time of PID 1, when running in a container (Linux),
+/// 3. the `btime` line of `/proc/stat` (Linux),
+/// 4. the current time minus the `CLOCK_BOOTTIME` clock (Linux).
+///
+/// The second one is [`get_container_boot_time`], the last two are
+/// [`get_kernel_boot_time`].
+#[cfg(unix)]
+#[cfg(not(target_os = "openbsd"))]
+pub fn get_boot_time() -> Option<time_t> {
+    use crate::utmpx::Utmpx;
+    use libc::BOOT_TIME;
+
+    Utmpx::iter_all_records()
+        .filter(|r| r.record_type() == BOOT_TIME)
+        .map(|r| r.login_time().unix_timestamp())
+        .find(|&ts| ts > 0)
+        .map(|ts| ts as time_t)
+        .or_else(get_container_boot_time)
+        .or_else(get_kernel_boot_time)
+}
+
+/// Get the time the container we run in was started, if we run in one.
+///
+/// Containers usually have no `BOOT_TIME` record in utmpx, and the kernel
+/// only knows when the host booted. The closest thing a container has to a
+/// boot is the start of its PID 1, which `/proc/1/stat` gives in clock ticks
+/// since the kernel booted.
+///
+/// A container is recognized by the markers Docker (`/.dockerenv`), Podman
+/// (`/run/.containerenv`), Kubernetes (`KUBERNETES_SERVICE_HOST`) and
+/// systemd-nspawn and friends (`container`) leave behind. Elsewhere, PID 1
+/// is the init system, which starts along with the kernel, so this returns
+/// `None` and [`get_kernel_boot_time`] should be used instead.
+#[cfg(unix)]
+#[cfg(not(target_os = "openbsd"))]
+pub fn get_container_boot_time() -> Option<time_t> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    if std::path::Path::new("/.dockerenv").exists()
+        || std::path::Path::new("/run/.containerenv").exists()
+        || std::env::var_os("KUBERNETES_SERVICE_HOST").is_some()
+        || std::env::var_os("container").is_some()
+    {
+        let start_ticks = std::fs::read_to_string("/proc/1/stat")
+            .ok()
+            .and_then(|stat| parse_proc_pid_stat_starttime(&stat))?;
+        let ticks_per_second = u64::try_from(unsafe { libc::sysconf(libc::_SC_CLK_TCK) })
+            .ok()
+            .filter(|&hz| hz > 0)?;
+        let since_boot = time_t::try_from(start_ticks / ticks_per_second).ok()?;
+        return get_kernel_boot_time()?.checked_add(since_boot);
+    }
+    None
+}
+
+/// Get the boot time as known by the kernel, without looking at utmpx.
+///
+/// In a container, this is when the host booted, which is also what
+/// `/proc/uptime` counts from; see [`get_container_boot_time`] for when the
+/// container started. `/proc/stat` is preferred, and `CLOCK_BOOTTIME` is
+/// used when `/proc` isn't mounted.
+#[cfg(unix)]
+#[cfg(not(target_os = "openbsd"))]
+pub fn get_kernel_boot_time() -> Option<time_t> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        if let Some(btime) = std::fs::read_to_string("/proc/stat")
+            .ok()
+            .and_then(|stat| parse_proc_stat_btime(&stat))
+        {
+            return Some(btime);
+        }
+
+        let mut tp = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let ret = unsafe { libc::clock_gettime(libc::CLOCK_BOOTTIME, &raw mut tp) };
+        if ret == 0 {
+            #[allow(clippy::unnecessary_cast)]
+            let boot_time = Timestamp::now().as_second() - tp.tv_sec as i64;
+            return time_t::try_from(boot_time).ok().filter(|&t| t > 0);
+        }
+    }
+    None
+}
+
+/// Extract the boot time from the `btime` line of `/proc/stat`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn parse_proc_stat_btime(stat: &str) -> Option<time_t> {
+    stat.lines()
+        .find_map(|line| line.strip_prefix("btime "))
+        .and_then(|btime| btime.trim().parse::<time_t>().ok())
+        .filter(|&t| t > 0)
+}
+
+/// Extract the start time, in clock ticks since boot, from the contents of
+/// `/proc/<pid>/stat`.
+///
+/// It is the 22nd field, counting the command name in parentheses as the
+/// 2nd one even though it may contain spaces and parentheses itself.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn parse_proc_pid_stat_starttime(stat: &str) -> Option<u64> {
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(22 - 3)?.parse().ok()
+}
+
 /// The format used to display a FormattedUptime.
 pub enum OutputFormat {
     /// Typical `uptime` output (e.g. 2 days, 3:04).
@@ -491,6 +594,34 @@ mod tests {
         assert_eq!("2 users", format_nusers(2));
     }
 
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_parse_proc_stat_btime() {
+        let stat = "cpu  1 2 3 4\nintr 5\nctxt 6\nbtime 1700000000\nprocesses 7\n";
+        assert_eq!(parse_proc_stat_btime(stat), Some(1_700_000_000));
+        assert_eq!(parse_proc_stat_btime("cpu  1 2 3 4\n"), None);
+        assert_eq!(parse_proc_stat_btime("btime 0\n"), None);
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_kernel_boot_time_is_in_the_past() {
+        let boot_time = get_kernel_boot_time().expect("no boot time");
+        #[allow(clippy::unnecessary_cast)]
+        let boot_time = boot_time as i64;
+        assert!(boot_time <= Timestamp::now().as_second());
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_parse_proc_pid_stat_starttime() {
+        let stat = "1 (init) S 0 1 1 0 -1 4194560 1 2 3 4 5 6 7 8 20 0 1 0 1234 5 6\n";
+        assert_eq!(parse_proc_pid_stat_starttime(stat), Some(1234));
+        let stat = "42 (a) b (c) S 0 1 1 0 -1 4194560 1 2 3 4 5 6 7 8 20 0 1 0 99 5 6\n";
+        assert_eq!(parse_proc_pid_stat_starttime(stat), Some(99));
+        assert_eq!(parse_proc_pid_stat_starttime("1 (init) S 0 1"), None);
+    }
+
     /// Test that sysctl kern.boottime is accessible on macOS and returns valid boot time.
     /// This ensures the fallback mechanism added for issue #3621 works correctly.
     #[test]
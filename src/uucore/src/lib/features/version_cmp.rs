@@ -102,15 +102,18 @@ pub fn version_cmp(mut a: &[u8], mut b: &[u8]) -> Ordering {
         _ => {}
     }
 
-    // Try to strip file extensions
-    let (mut a, mut b) = match (remove_file_ending(a), remove_file_ending(b)) {
-        (a_stripped, b_stripped) if a_stripped == b_stripped => {
-            // If both would be the same after stripping file extensions, don't strip them.
-            (a, b)
+    // Compare without the file extensions first, and with them if that is a
+    // tie, like gnulib's filevercmp.
+    let (a_stripped, b_stripped) = (remove_file_ending(a), remove_file_ending(b));
+    match version_rev_cmp(a_stripped, b_stripped) {
+        Ordering::Equal if a_stripped.len() != a.len() || b_stripped.len() != b.len() => {
+            version_rev_cmp(a, b)
         }
-        stripped => stripped,
-    };
+        ord => ord,
+    }
+}
 
+fn version_rev_cmp(mut a: &[u8], mut b: &[u8]) -> Ordering {
     // 1. Compare leading non-numerical part
     // 2. Compare leading numerical part
     // 3. Repeat
@@ -127,7 +130,7 @@ pub fn version_cmp(mut a: &[u8], mut b: &[u8]) -> Ordering {
         }
 
         a = &a[a_numerical_start..];
-        b = &b[a_numerical_start..];
+        b = &b[b_numerical_start..];
 
         let a_numerical_end = a
             .iter()
@@ -351,6 +354,14 @@ mod tests {
             "if both start with a dot it is ignored for the comparison"
         );
 
+        // File extensions
+        assert_eq!(
+            version_cmp(b"02", b"2.tar.gz"),
+            Ordering::Less,
+            "the extensions break a tie of the versions without them"
+        );
+        assert_eq!(version_cmp(b"2.tar.gz", b"02.tar.gz"), Ordering::Equal);
+
         // The following tests are incompatible with GNU as of 2021/06.
         // I think that's because of a bug in GNU, reported as https://lists.gnu.org/archive/html/bug-coreutils/2021-06/msg00045.html
         assert_eq!(
@@ -16,20 +16,67 @@
 //! });
 //! ```
 
-use std::cell::RefCell;
-use std::fs::File;
-use std::io::{self, BufRead, Read, Write};
-use std::path::Path;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::ffi::{OsStr, OsString};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 type FileOpenerFn = Box<dyn Fn(&Path) -> io::Result<Box<dyn Read>>>;
 type FileExistsFn = Box<dyn Fn(&Path) -> bool>;
+type SeekableOpenerFn = Box<dyn Fn(&Path) -> io::Result<Box<dyn ReadSeek>>>;
+type FileWriterFn = Box<dyn Fn(&Path) -> io::Result<Box<dyn Write>>>;
+type ReadDirFn = Box<dyn Fn(&Path) -> io::Result<Vec<DirEntryInfo>>>;
+type MetadataFn = Box<dyn Fn(&Path, bool) -> io::Result<WasmMetadata>>;
+type ReadLinkFn = Box<dyn Fn(&Path) -> io::Result<PathBuf>>;
+type SymlinkFn = Box<dyn Fn(&Path, &Path) -> io::Result<()>>;
+type PathOpFn = Box<dyn Fn(&Path) -> io::Result<()>>;
+type RenameFn = Box<dyn Fn(&Path, &Path) -> io::Result<()>>;
+type CwdFn = Box<dyn Fn() -> io::Result<PathBuf>>;
+type EnvGetFn = Box<dyn Fn(&OsStr) -> Option<OsString>>;
+type EnvSetFn = Box<dyn Fn(&OsStr, Option<&OsStr>)>;
+type EnvIterFn = Box<dyn Fn() -> Vec<(OsString, OsString)>>;
+type ClockFn = Box<dyn Fn() -> SystemTime>;
+type TzOffsetFn = Box<dyn Fn(SystemTime) -> i32>;
+type CancelFn = Box<dyn Fn() -> bool>;
+type TickFn = Box<dyn Fn(Duration)>;
+type IdNameFn = Box<dyn Fn(u32) -> Option<String>>;
+type RandomFn = Box<dyn Fn(&mut [u8])>;
 
 thread_local! {
-    static STDOUT_OVERRIDE: RefCell<Option<Box<dyn Write>>> = RefCell::new(None);
-    static STDERR_OVERRIDE: RefCell<Option<Box<dyn Write>>> = RefCell::new(None);
+    static STDOUT_OVERRIDE: RefCell<Option<HostOutput>> = RefCell::new(None);
+    static STDERR_OVERRIDE: RefCell<Option<HostOutput>> = RefCell::new(None);
+    static OUTPUT_BUFFER_SIZE: Cell<usize> = Cell::new(DEFAULT_OUTPUT_BUFFER_SIZE);
     static STDIN_OVERRIDE: RefCell<Option<Box<dyn Read>>> = RefCell::new(None);
     static FILE_OPENER: RefCell<Option<FileOpenerFn>> = RefCell::new(None);
     static FILE_EXISTS: RefCell<Option<FileExistsFn>> = RefCell::new(None);
+    static SEEKABLE_OPENER: RefCell<Option<SeekableOpenerFn>> = RefCell::new(None);
+    static FILE_CREATE: RefCell<Option<FileWriterFn>> = RefCell::new(None);
+    static FILE_APPEND: RefCell<Option<FileWriterFn>> = RefCell::new(None);
+    static FILE_TRUNCATE: RefCell<Option<FileWriterFn>> = RefCell::new(None);
+    static READ_DIR: RefCell<Option<ReadDirFn>> = RefCell::new(None);
+    static METADATA: RefCell<Option<MetadataFn>> = RefCell::new(None);
+    static READ_LINK: RefCell<Option<ReadLinkFn>> = RefCell::new(None);
+    static SYMLINK: RefCell<Option<SymlinkFn>> = RefCell::new(None);
+    static CREATE_DIR: RefCell<Option<PathOpFn>> = RefCell::new(None);
+    static REMOVE_FILE: RefCell<Option<PathOpFn>> = RefCell::new(None);
+    static REMOVE_DIR: RefCell<Option<PathOpFn>> = RefCell::new(None);
+    static RENAME: RefCell<Option<RenameFn>> = RefCell::new(None);
+    static CWD: RefCell<Option<CwdFn>> = RefCell::new(None);
+    static ENV_GET: RefCell<Option<EnvGetFn>> = RefCell::new(None);
+    static ENV_SET: RefCell<Option<EnvSetFn>> = RefCell::new(None);
+    static ENV_ITER: RefCell<Option<EnvIterFn>> = RefCell::new(None);
+    static CLOCK: RefCell<Option<ClockFn>> = RefCell::new(None);
+    static TZ_OFFSET: RefCell<Option<TzOffsetFn>> = RefCell::new(None);
+    static CANCEL: RefCell<Option<CancelFn>> = RefCell::new(None);
+    static TICK: RefCell<Option<TickFn>> = RefCell::new(None);
+    static IDENTITY: RefCell<Option<(u32, u32)>> = RefCell::new(None);
+    static GROUPS: RefCell<Vec<u32>> = RefCell::new(Vec::new());
+    static USER_NAME: RefCell<Option<IdNameFn>> = RefCell::new(None);
+    static GROUP_NAME: RefCell<Option<IdNameFn>> = RefCell::new(None);
+    static RANDOM: RefCell<Option<RandomFn>> = RefCell::new(None);
 }
 
 /// Install custom stdin/stdout/stderr for the duration of a closure.
@@ -37,6 +84,15 @@ thread_local! {
 /// This is the primary entry point for hosts that want to capture output
 /// from uutils commands on WASM. The overrides are automatically cleaned
 /// up when the closure returns (even on panic).
+///
+/// The value of the closure is returned, so calling a utility's `uumain`
+/// inside it hands its exit code back to the host. Build `uucore` with the
+/// `embedded` feature to make sure nothing on the way calls
+/// [`std::process::exit`], which would abort the whole instance.
+///
+/// Output still buffered when the closure returns (see
+/// [`set_output_buffer_size`]) is handed to `stdout` and `stderr` before
+/// they are dropped.
 pub fn with_wasm_io<F, R>(
     stdin: Box<dyn Read>,
     stdout: Box<dyn Write>,
@@ -47,18 +103,47 @@ where
     F: FnOnce() -> R,
 {
     STDIN_OVERRIDE.with(|s| *s.borrow_mut() = Some(stdin));
-    STDOUT_OVERRIDE.with(|s| *s.borrow_mut() = Some(stdout));
-    STDERR_OVERRIDE.with(|s| *s.borrow_mut() = Some(stderr));
+    STDOUT_OVERRIDE.with(|s| *s.borrow_mut() = Some(HostOutput::new(stdout)));
+    STDERR_OVERRIDE.with(|s| *s.borrow_mut() = Some(HostOutput::new(stderr)));
 
     // Use a guard to ensure cleanup on panic.
     struct CleanupGuard;
     impl Drop for CleanupGuard {
         fn drop(&mut self) {
+            // Errors can't be reported from here; utilities that care about
+            // them flush stdout themselves before returning.
+            flush_pending_output();
             STDIN_OVERRIDE.with(|s| *s.borrow_mut() = None);
             STDOUT_OVERRIDE.with(|s| *s.borrow_mut() = None);
             STDERR_OVERRIDE.with(|s| *s.borrow_mut() = None);
+            OUTPUT_BUFFER_SIZE.with(|s| s.set(DEFAULT_OUTPUT_BUFFER_SIZE));
             FILE_OPENER.with(|s| *s.borrow_mut() = None);
             FILE_EXISTS.with(|s| *s.borrow_mut() = None);
+            SEEKABLE_OPENER.with(|s| *s.borrow_mut() = None);
+            FILE_CREATE.with(|s| *s.borrow_mut() = None);
+            FILE_APPEND.with(|s| *s.borrow_mut() = None);
+            FILE_TRUNCATE.with(|s| *s.borrow_mut() = None);
+            READ_DIR.with(|s| *s.borrow_mut() = None);
+            METADATA.with(|s| *s.borrow_mut() = None);
+            READ_LINK.with(|s| *s.borrow_mut() = None);
+            SYMLINK.with(|s| *s.borrow_mut() = None);
+            CREATE_DIR.with(|s| *s.borrow_mut() = None);
+            REMOVE_FILE.with(|s| *s.borrow_mut() = None);
+            REMOVE_DIR.with(|s| *s.borrow_mut() = None);
+            RENAME.with(|s| *s.borrow_mut() = None);
+            CWD.with(|s| *s.borrow_mut() = None);
+            ENV_GET.with(|s| *s.borrow_mut() = None);
+            ENV_SET.with(|s| *s.borrow_mut() = None);
+            ENV_ITER.with(|s| *s.borrow_mut() = None);
+            CLOCK.with(|s| *s.borrow_mut() = None);
+            TZ_OFFSET.with(|s| *s.borrow_mut() = None);
+            CANCEL.with(|s| *s.borrow_mut() = None);
+            TICK.with(|s| *s.borrow_mut() = None);
+            IDENTITY.with(|s| *s.borrow_mut() = None);
+            GROUPS.with(|s| s.borrow_mut().clear());
+            USER_NAME.with(|s| *s.borrow_mut() = None);
+            GROUP_NAME.with(|s| *s.borrow_mut() = None);
+            RANDOM.with(|s| *s.borrow_mut() = None);
         }
     }
     let _guard = CleanupGuard;
@@ -95,6 +180,8 @@ impl Write for WasmStdout {
 
 /// Lock handle returned by `WasmStdout::lock()`. On WASM this is a
 /// no-op wrapper — there is no real locking since WASM is single-threaded.
+///
+/// Buffered output is handed to the host when the lock is dropped.
 pub struct WasmStdoutLock;
 
 impl Write for WasmStdoutLock {
@@ -107,7 +194,16 @@ impl Write for WasmStdoutLock {
     }
 }
 
+impl Drop for WasmStdoutLock {
+    fn drop(&mut self) {
+        flush_pending_output();
+    }
+}
+
 fn write_stdout(buf: &[u8]) -> io::Result<usize> {
+    // Hand over pending stderr output first so that a host merging both
+    // streams sees them in the order they were written.
+    hand_over_pending(&STDERR_OVERRIDE);
     STDOUT_OVERRIDE.with(|s| {
         let mut borrow = s.borrow_mut();
         if let Some(ref mut writer) = *borrow {
@@ -151,6 +247,8 @@ impl Write for WasmStderr {
 }
 
 /// Lock handle returned by `WasmStderr::lock()`.
+///
+/// Buffered output is handed to the host when the lock is dropped.
 pub struct WasmStderrLock;
 
 impl Write for WasmStderrLock {
@@ -163,7 +261,14 @@ impl Write for WasmStderrLock {
     }
 }
 
+impl Drop for WasmStderrLock {
+    fn drop(&mut self) {
+        flush_pending_output();
+    }
+}
+
 fn write_stderr(buf: &[u8]) -> io::Result<usize> {
+    hand_over_pending(&STDOUT_OVERRIDE);
     STDERR_OVERRIDE.with(|s| {
         let mut borrow = s.borrow_mut();
         if let Some(ref mut writer) = *borrow {
@@ -185,6 +290,83 @@ fn flush_stderr() -> io::Result<()> {
     })
 }
 
+/// Hand whatever is still buffered for either stream to the host.
+fn flush_pending_output() {
+    // At most one of the streams has pending output, since writing to one
+    // of them hands over what is pending for the other.
+    hand_over_pending(&STDOUT_OVERRIDE);
+    hand_over_pending(&STDERR_OVERRIDE);
+}
+
+fn hand_over_pending(stream: &'static std::thread::LocalKey<RefCell<Option<HostOutput>>>) {
+    stream.with(|s| {
+        if let Some(output) = s.borrow_mut().as_mut() {
+            output.hand_over_pending();
+        }
+    });
+}
+
+/// A host-provided output stream, along with the bytes written to it that
+/// have not been handed over yet.
+struct HostOutput {
+    writer: Box<dyn Write>,
+    pending: Vec<u8>,
+    /// An error from handing over pending output outside of a write or
+    /// flush, reported by the next one.
+    error: Option<io::Error>,
+}
+
+impl HostOutput {
+    fn new(writer: Box<dyn Write>) -> Self {
+        Self {
+            writer,
+            pending: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        let limit = OUTPUT_BUFFER_SIZE.with(Cell::get);
+        if self.pending.len() + buf.len() > limit {
+            self.write_pending()?;
+        }
+        // Large writes go straight through rather than being copied first.
+        if buf.len() >= limit {
+            return self.writer.write(buf);
+        }
+        self.pending.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn write_pending(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        // Drop the pending bytes even if the host fails to take them, so
+        // that the error is reported once rather than on every later write.
+        let result = self.writer.write_all(&self.pending);
+        self.pending.clear();
+        result
+    }
+
+    fn hand_over_pending(&mut self) {
+        if let Err(error) = self.write_pending() {
+            self.error.get_or_insert(error);
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.write_pending()?;
+        self.writer.flush()
+    }
+}
+
 /// A stdin wrapper that reads from the thread-local override if set,
 /// or falls back to `std::io::stdin()`.
 pub struct WasmStdin;
@@ -255,6 +437,9 @@ impl BufRead for WasmStdinLock {
 }
 
 fn read_stdin(buf: &mut [u8]) -> io::Result<usize> {
+    // Make sure prompts and any other output written so far reach the host
+    // before we wait for input.
+    flush_pending_output();
     STDIN_OVERRIDE.with(|s| {
         let mut borrow = s.borrow_mut();
         if let Some(ref mut reader) = *borrow {
@@ -280,6 +465,79 @@ pub fn stdin() -> WasmStdin {
     WasmStdin
 }
 
+/// Returns the process's own stdout if the host has not overridden it.
+///
+/// On WASI the standard streams are real file descriptors, which utilities
+/// such as `cat` inspect to detect an input file that is also the output.
+#[cfg(target_os = "wasi")]
+pub fn host_stdout() -> Option<io::Stdout> {
+    STDOUT_OVERRIDE.with(|s| s.borrow().is_none().then(io::stdout))
+}
+
+/// Returns the process's own stdin if the host has not overridden it.
+#[cfg(target_os = "wasi")]
+pub fn host_stdin() -> Option<io::Stdin> {
+    STDIN_OVERRIDE.with(|s| s.borrow().is_none().then(io::stdin))
+}
+
+// ── Output buffering ─────────────────────────────────────────────
+// Every write to the host's stdout and stderr crosses the WASM boundary,
+// which dominates output-heavy builtins like cat that write a line at a
+// time. Small writes are collected and handed over in larger chunks.
+
+/// The number of bytes collected before output is handed to the host, unless
+/// the host sets another size with [`set_output_buffer_size`].
+pub const DEFAULT_OUTPUT_BUFFER_SIZE: usize = 32 * 1024;
+
+/// Set how many bytes of stdout and stderr output are collected before they
+/// are handed to the host. Called by the host (brush-uutils) before executing
+/// a builtin; `0` hands every write over as soon as it is made.
+///
+/// Buffered output is also handed over when it is flushed, when a
+/// [`WasmStdoutLock`] or [`WasmStderrLock`] is dropped, before reading from
+/// stdin, and when [`with_wasm_io`] returns. Writing to one stream hands over
+/// what is pending for the other first, so the two stay in order.
+pub fn set_output_buffer_size(size: usize) {
+    OUTPUT_BUFFER_SIZE.with(|s| s.set(size));
+}
+
+// ── Working directory hook ───────────────────────────────────────
+// Allow hosts to report the shell's working directory so that
+// builtins like pwd and realpath, and relative paths handed to the
+// other hooks, follow it on WASM, where the process has none.
+
+/// Install the working-directory override. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// While it is installed, relative paths are resolved against it before they
+/// are passed to the other hooks, so hosts only ever see absolute paths.
+pub fn set_cwd_hook(cwd: Box<dyn Fn() -> io::Result<PathBuf>>) {
+    CWD.with(|s| *s.borrow_mut() = Some(cwd));
+}
+
+/// Get the current working directory, using the host override if set,
+/// otherwise falling back to `std::env::current_dir`.
+pub fn current_dir() -> io::Result<PathBuf> {
+    CWD.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref cwd_fn) = *borrow {
+            cwd_fn()
+        } else {
+            std::env::current_dir()
+        }
+    })
+}
+
+/// Make `path` absolute against the host's working directory, if one is
+/// installed. Without the override, paths are passed through unchanged.
+fn resolve(path: &Path) -> io::Result<Cow<'_, Path>> {
+    if path.is_absolute() || CWD.with(|cell| cell.borrow().is_none()) {
+        Ok(Cow::Borrowed(path))
+    } else {
+        Ok(Cow::Owned(current_dir()?.join(path)))
+    }
+}
+
 // ── File I/O hooks ───────────────────────────────────────────────
 // Allow hosts to provide a VFS-backed file opener so that builtins
 // like cat, head, sort, etc. can open files by path on WASM.
@@ -297,7 +555,7 @@ pub fn set_file_hooks(
 /// Open a file for reading, using the VFS override if set,
 /// otherwise falling back to `std::fs::File::open`.
 pub fn open_file(path: impl AsRef<Path>) -> io::Result<Box<dyn Read>> {
-    let path = path.as_ref();
+    let path = &resolve(path.as_ref())?;
     FILE_OPENER.with(|cell| {
         let borrow = cell.borrow();
         if let Some(ref opener) = *borrow {
@@ -308,10 +566,23 @@ pub fn open_file(path: impl AsRef<Path>) -> io::Result<Box<dyn Read>> {
     })
 }
 
+/// Open a file through WASI itself, unless the host has installed a file
+/// opener, in which case `None` is returned and [`open_file`] must be used.
+#[cfg(target_os = "wasi")]
+pub fn open_host_file(path: impl AsRef<Path>) -> Option<io::Result<File>> {
+    if FILE_OPENER.with(|cell| cell.borrow().is_some()) {
+        return None;
+    }
+    Some(resolve(path.as_ref()).and_then(File::open))
+}
+
 /// Check if a file exists, using the VFS override if set,
 /// otherwise falling back to `Path::exists`.
 pub fn file_exists(path: impl AsRef<Path>) -> bool {
-    let path = path.as_ref();
+    let path = &match resolve(path.as_ref()) {
+        Ok(path) => path,
+        Err(_) => return false,
+    };
     FILE_EXISTS.with(|cell| {
         let borrow = cell.borrow();
         if let Some(ref exists_fn) = *borrow {
@@ -321,3 +592,662 @@ pub fn file_exists(path: impl AsRef<Path>) -> bool {
         }
     })
 }
+
+/// A reader that can also seek, as returned by [`open_file_seekable`].
+pub trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+/// Install the seekable file-opening override. Called by the host
+/// (brush-uutils) before executing a builtin, in addition to
+/// [`set_file_hooks`], when its VFS supports random access.
+///
+/// This lets builtins like tail and head read files from the end instead
+/// of streaming them whole.
+pub fn set_seekable_opener(opener: Box<dyn Fn(&Path) -> io::Result<Box<dyn ReadSeek>>>) {
+    SEEKABLE_OPENER.with(|s| *s.borrow_mut() = Some(opener));
+}
+
+/// Whether [`open_file_seekable`] can open files: either the host installed
+/// a seekable opener, or no file hooks are installed and `std::fs` is used.
+pub fn can_seek_files() -> bool {
+    SEEKABLE_OPENER.with(|cell| cell.borrow().is_some())
+        || FILE_OPENER.with(|cell| cell.borrow().is_none())
+}
+
+/// Open a file for reading and seeking, using the seekable VFS override if
+/// set, otherwise falling back to `std::fs::File::open`.
+///
+/// # Errors
+///
+/// Returns [`io::ErrorKind::Unsupported`] if only a streaming opener is
+/// installed; check [`can_seek_files`] first.
+pub fn open_file_seekable(path: impl AsRef<Path>) -> io::Result<Box<dyn ReadSeek>> {
+    let path = &resolve(path.as_ref())?;
+    SEEKABLE_OPENER.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref opener) = *borrow {
+            opener(path)
+        } else if can_seek_files() {
+            Ok(Box::new(File::open(path)?) as Box<dyn ReadSeek>)
+        } else {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+    })
+}
+
+// ── File write hooks ─────────────────────────────────────────────
+// Allow hosts to provide VFS-backed writers so that builtins like
+// tee, split, and sort -o can create and write files on WASM.
+
+/// How a file should be opened by [`open_file_write`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Create the file if it is missing, keeping any existing contents.
+    /// Writes start at the beginning of the file.
+    Create,
+    /// Create the file if it is missing and write at its end.
+    Append,
+    /// Create the file if it is missing and discard any existing contents.
+    Truncate,
+}
+
+/// Install file-writing overrides. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// Each hook receives the path to open and returns a writer for it,
+/// following the semantics of the corresponding [`WriteMode`].
+pub fn set_file_write_hooks(
+    create: Box<dyn Fn(&Path) -> io::Result<Box<dyn Write>>>,
+    append: Box<dyn Fn(&Path) -> io::Result<Box<dyn Write>>>,
+    truncate: Box<dyn Fn(&Path) -> io::Result<Box<dyn Write>>>,
+) {
+    FILE_CREATE.with(|s| *s.borrow_mut() = Some(create));
+    FILE_APPEND.with(|s| *s.borrow_mut() = Some(append));
+    FILE_TRUNCATE.with(|s| *s.borrow_mut() = Some(truncate));
+}
+
+/// Open a file for writing, using the VFS override for `mode` if set,
+/// otherwise falling back to `std::fs::OpenOptions`.
+pub fn open_file_write(path: impl AsRef<Path>, mode: WriteMode) -> io::Result<Box<dyn Write>> {
+    let path = &resolve(path.as_ref())?;
+    let hook = match mode {
+        WriteMode::Create => &FILE_CREATE,
+        WriteMode::Append => &FILE_APPEND,
+        WriteMode::Truncate => &FILE_TRUNCATE,
+    };
+    hook.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref writer_fn) = *borrow {
+            return writer_fn(path);
+        }
+        let mut options = OpenOptions::new();
+        options.write(true).create(true);
+        match mode {
+            WriteMode::Create => {}
+            WriteMode::Append => {
+                options.append(true);
+            }
+            WriteMode::Truncate => {
+                options.truncate(true);
+            }
+        }
+        Ok(Box::new(options.open(path)?) as Box<dyn Write>)
+    })
+}
+
+// ── Directory hooks ──────────────────────────────────────────────
+// Allow hosts to enumerate VFS directories so that builtins like
+// ls, dir, and vdir can list them on WASM.
+
+/// A single entry of a directory listing, independent of `std::fs::DirEntry`
+/// so that hosts can construct it from their own VFS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntryInfo {
+    /// The file name of the entry, without any leading path.
+    pub name: OsString,
+    /// Whether the entry is a directory.
+    pub is_dir: bool,
+    /// Whether the entry is a symbolic link.
+    pub is_symlink: bool,
+}
+
+/// Install the directory-listing override. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// The hook returns the entries of the given directory, excluding `.` and `..`.
+pub fn set_dir_hooks(read_dir: Box<dyn Fn(&Path) -> io::Result<Vec<DirEntryInfo>>>) {
+    READ_DIR.with(|s| *s.borrow_mut() = Some(read_dir));
+}
+
+/// List a directory, using the VFS override if set,
+/// otherwise falling back to `std::fs::read_dir`.
+pub fn read_dir(path: impl AsRef<Path>) -> io::Result<Vec<DirEntryInfo>> {
+    let path = &resolve(path.as_ref())?;
+    READ_DIR.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref read_dir_fn) = *borrow {
+            return read_dir_fn(path);
+        }
+        std::fs::read_dir(path)?
+            .map(|entry| {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                Ok(DirEntryInfo {
+                    name: entry.file_name(),
+                    is_dir: file_type.is_dir(),
+                    is_symlink: file_type.is_symlink(),
+                })
+            })
+            .collect()
+    })
+}
+
+// ── Metadata hooks ───────────────────────────────────────────────
+// Allow hosts to report VFS metadata so that builtins like stat, du,
+// ls -l, and test can inspect files on WASM.
+
+/// The type of a file, as reported in [`WasmMetadata`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmFileType {
+    File,
+    Dir,
+    Symlink,
+    Other,
+}
+
+/// File metadata independent of `std::fs::Metadata`, which hosts cannot
+/// construct from their own VFS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmMetadata {
+    /// The size of the file in bytes.
+    pub len: u64,
+    /// The last modification time, if known.
+    pub modified: Option<SystemTime>,
+    /// The type of the file.
+    pub file_type: WasmFileType,
+    /// The permission bits of the file, e.g. `0o644`.
+    pub mode: u32,
+}
+
+impl WasmMetadata {
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.file_type == WasmFileType::File
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.file_type == WasmFileType::Dir
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.file_type == WasmFileType::Symlink
+    }
+
+    /// Render the permissions like `ls -l` does, e.g. `drwxr-xr-x`.
+    pub fn display_permissions(&self, display_file_type: bool) -> String {
+        let mut result = String::with_capacity(10);
+        if display_file_type {
+            result.push(match self.file_type {
+                WasmFileType::File => '-',
+                WasmFileType::Dir => 'd',
+                WasmFileType::Symlink => 'l',
+                WasmFileType::Other => '?',
+            });
+        }
+        for (i, c) in "rwxrwxrwx".chars().enumerate() {
+            result.push(if self.mode & (0o400 >> i) == 0 {
+                '-'
+            } else {
+                c
+            });
+        }
+        result
+    }
+}
+
+impl From<std::fs::Metadata> for WasmMetadata {
+    fn from(metadata: std::fs::Metadata) -> Self {
+        let file_type = metadata.file_type();
+        let file_type = if file_type.is_symlink() {
+            WasmFileType::Symlink
+        } else if file_type.is_dir() {
+            WasmFileType::Dir
+        } else if file_type.is_file() {
+            WasmFileType::File
+        } else {
+            WasmFileType::Other
+        };
+        // Without `PermissionsExt`, only the read-only flag is available.
+        let mode = match (file_type, metadata.permissions().readonly()) {
+            (WasmFileType::Dir, false) => 0o755,
+            (WasmFileType::Dir, true) => 0o555,
+            (_, false) => 0o644,
+            (_, true) => 0o444,
+        };
+        Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+            file_type,
+            mode,
+        }
+    }
+}
+
+/// Install the metadata override. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// The hook receives the path to inspect and whether symbolic links
+/// should be followed.
+pub fn set_metadata_hook(metadata: Box<dyn Fn(&Path, bool) -> io::Result<WasmMetadata>>) {
+    METADATA.with(|s| *s.borrow_mut() = Some(metadata));
+}
+
+fn query_metadata(path: &Path, follow_symlinks: bool) -> io::Result<WasmMetadata> {
+    let path = &resolve(path)?;
+    METADATA.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref metadata_fn) = *borrow {
+            return metadata_fn(path, follow_symlinks);
+        }
+        let metadata = if follow_symlinks {
+            std::fs::metadata(path)?
+        } else {
+            std::fs::symlink_metadata(path)?
+        };
+        Ok(metadata.into())
+    })
+}
+
+/// Query the metadata of a file, following symbolic links, using the VFS
+/// override if set, otherwise falling back to `std::fs::metadata`.
+pub fn metadata(path: impl AsRef<Path>) -> io::Result<WasmMetadata> {
+    query_metadata(path.as_ref(), true)
+}
+
+/// Query the metadata of a file without following symbolic links, using the
+/// VFS override if set, otherwise falling back to `std::fs::symlink_metadata`.
+pub fn symlink_metadata(path: impl AsRef<Path>) -> io::Result<WasmMetadata> {
+    query_metadata(path.as_ref(), false)
+}
+
+/// Install the symbolic link override. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// Together with the metadata hook this lets `uucore::fs::canonicalize`,
+/// and so realpath, resolve symbolic links stored in the host VFS.
+pub fn set_read_link_hook(read_link: Box<dyn Fn(&Path) -> io::Result<PathBuf>>) {
+    READ_LINK.with(|s| *s.borrow_mut() = Some(read_link));
+}
+
+/// Read the target of a symbolic link, using the VFS override if set,
+/// otherwise falling back to `std::fs::read_link`.
+pub fn read_link(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+    let path = &resolve(path.as_ref())?;
+    READ_LINK.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref read_link_fn) = *borrow {
+            read_link_fn(path)
+        } else {
+            std::fs::read_link(path)
+        }
+    })
+}
+
+// ── Symbolic link hooks ──────────────────────────────────────────
+// Allow hosts to create symbolic links in their VFS so that ln -s and
+// cp -s work on WASM, and readlink and ls -l can show where they point.
+
+/// Install the symbolic link overrides. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// `read_link` replaces the hook installed by [`set_read_link_hook`].
+/// `symlink` creates a link at its second path pointing to its first one,
+/// which it must store verbatim like `symlink(2)` does, and fail with
+/// [`io::ErrorKind::AlreadyExists`] if the link path exists. Links are
+/// inspected with [`symlink_metadata`], which the metadata hook serves when
+/// asked not to follow them.
+pub fn set_symlink_hooks(
+    read_link: Box<dyn Fn(&Path) -> io::Result<PathBuf>>,
+    symlink: Box<dyn Fn(&Path, &Path) -> io::Result<()>>,
+) {
+    READ_LINK.with(|s| *s.borrow_mut() = Some(read_link));
+    SYMLINK.with(|s| *s.borrow_mut() = Some(symlink));
+}
+
+/// Create a symbolic link at `link` pointing to `original`, using the VFS
+/// override if set. Without it this fails with
+/// [`io::ErrorKind::Unsupported`], since symbolic links cannot be created
+/// through the stable WASM APIs.
+pub fn symlink(original: impl AsRef<Path>, link: impl AsRef<Path>) -> io::Result<()> {
+    // Only the link is resolved; a relative target stays relative to it.
+    let link = &resolve(link.as_ref())?;
+    SYMLINK.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref symlink_fn) = *borrow {
+            symlink_fn(original.as_ref(), link)
+        } else {
+            Err(io::ErrorKind::Unsupported.into())
+        }
+    })
+}
+
+// ── Filesystem mutation hooks ────────────────────────────────────
+// Allow hosts to modify their VFS so that builtins like mkdir, rm,
+// rmdir, and mv can change it on WASM, not only read it.
+
+/// Install the filesystem mutation overrides. Called by the host
+/// (brush-uutils) before executing a builtin.
+///
+/// `create_dir` creates a single directory, `remove_file` and `remove_dir`
+/// remove a file or an empty directory, and `rename` moves a file or
+/// directory, replacing the destination like `rename(2)` does. Errors should
+/// use the same [`io::ErrorKind`]s as `std::fs`, since callers match on them.
+pub fn set_fs_mutation_hooks(
+    create_dir: Box<dyn Fn(&Path) -> io::Result<()>>,
+    remove_file: Box<dyn Fn(&Path) -> io::Result<()>>,
+    remove_dir: Box<dyn Fn(&Path) -> io::Result<()>>,
+    rename: Box<dyn Fn(&Path, &Path) -> io::Result<()>>,
+) {
+    CREATE_DIR.with(|s| *s.borrow_mut() = Some(create_dir));
+    REMOVE_FILE.with(|s| *s.borrow_mut() = Some(remove_file));
+    REMOVE_DIR.with(|s| *s.borrow_mut() = Some(remove_dir));
+    RENAME.with(|s| *s.borrow_mut() = Some(rename));
+}
+
+fn run_path_op(
+    hook: &'static std::thread::LocalKey<RefCell<Option<PathOpFn>>>,
+    path: &Path,
+    fallback: fn(&Path) -> io::Result<()>,
+) -> io::Result<()> {
+    let path = &resolve(path)?;
+    hook.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref op_fn) = *borrow {
+            op_fn(path)
+        } else {
+            fallback(path)
+        }
+    })
+}
+
+/// Create a directory, using the VFS override if set, otherwise falling
+/// back to `std::fs::create_dir`.
+pub fn create_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    run_path_op(&CREATE_DIR, path.as_ref(), |path| std::fs::create_dir(path))
+}
+
+/// Remove a file, using the VFS override if set, otherwise falling back to
+/// `std::fs::remove_file`.
+pub fn remove_file(path: impl AsRef<Path>) -> io::Result<()> {
+    run_path_op(&REMOVE_FILE, path.as_ref(), |path| {
+        std::fs::remove_file(path)
+    })
+}
+
+/// Remove an empty directory, using the VFS override if set, otherwise
+/// falling back to `std::fs::remove_dir`.
+pub fn remove_dir(path: impl AsRef<Path>) -> io::Result<()> {
+    run_path_op(&REMOVE_DIR, path.as_ref(), |path| std::fs::remove_dir(path))
+}
+
+/// Rename a file or directory, using the VFS override if set, otherwise
+/// falling back to `std::fs::rename`.
+pub fn rename(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<()> {
+    let from = &resolve(from.as_ref())?;
+    let to = &resolve(to.as_ref())?;
+    RENAME.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref rename_fn) = *borrow {
+            rename_fn(from, to)
+        } else {
+            std::fs::rename(from, to)
+        }
+    })
+}
+
+// ── Environment hooks ────────────────────────────────────────────
+// Allow hosts to expose their own variable table so that builtins
+// like env, printenv, date, and sort see the shell's environment on
+// WASM, where the process has none.
+
+/// Install the environment overrides. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// `get` looks up a single variable, `set` assigns a variable or removes it
+/// when the value is `None`, and `iter` lists all variables. Utilities reach
+/// these through [`crate::env`].
+pub fn set_env_hooks(
+    get: Box<dyn Fn(&OsStr) -> Option<OsString>>,
+    set: Box<dyn Fn(&OsStr, Option<&OsStr>)>,
+    iter: Box<dyn Fn() -> Vec<(OsString, OsString)>>,
+) {
+    ENV_GET.with(|s| *s.borrow_mut() = Some(get));
+    ENV_SET.with(|s| *s.borrow_mut() = Some(set));
+    ENV_ITER.with(|s| *s.borrow_mut() = Some(iter));
+}
+
+/// Look up an environment variable, using the host override if set,
+/// otherwise falling back to `std::env::var_os`.
+pub fn var_os(key: &OsStr) -> Option<OsString> {
+    ENV_GET.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref get_fn) = *borrow {
+            get_fn(key)
+        } else {
+            std::env::var_os(key)
+        }
+    })
+}
+
+/// List all environment variables, using the host override if set,
+/// otherwise falling back to `std::env::vars_os`.
+pub fn vars_os() -> Vec<(OsString, OsString)> {
+    ENV_ITER.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref iter_fn) = *borrow {
+            iter_fn()
+        } else {
+            std::env::vars_os().collect()
+        }
+    })
+}
+
+/// Set or, with a `None` value, remove an environment variable, using the
+/// host override if set, otherwise falling back to `std::env`.
+///
+/// # Safety
+///
+/// Without a host override this calls [`std::env::set_var`] or
+/// [`std::env::remove_var`], with the same requirements.
+pub unsafe fn set_var(key: &OsStr, value: Option<&OsStr>) {
+    ENV_SET.with(|cell| {
+        let borrow = cell.borrow();
+        if let Some(ref set_fn) = *borrow {
+            set_fn(key, value);
+        } else if let Some(value) = value {
+            unsafe { std::env::set_var(key, value) };
+        } else {
+            unsafe { std::env::remove_var(key) };
+        }
+    });
+}
+
+// ── Clock hooks ──────────────────────────────────────────────────
+// Allow hosts to provide the current time and the local UTC offset, since
+// WASM runtimes may have neither a system clock nor a TZ database.
+
+/// Install the clock override. Called by the host (brush-uutils) before
+/// executing a builtin.
+pub fn set_clock_hook(clock: Box<dyn Fn() -> SystemTime>) {
+    CLOCK.with(|s| *s.borrow_mut() = Some(clock));
+}
+
+/// Install the time zone override. Called by the host (brush-uutils) before
+/// executing a builtin.
+///
+/// The hook returns the local offset from UTC in seconds (east of UTC being
+/// positive) at the given instant, so that hosts can account for daylight
+/// saving time. It is only consulted when `TZ` is not set.
+pub fn set_tz_offset_hook(tz_offset: Box<dyn Fn(SystemTime) -> i32>) {
+    TZ_OFFSET.with(|s| *s.borrow_mut() = Some(tz_offset));
+}
+
+/// The current time according to the host, or `None` if no clock hook is
+/// installed. `SystemTime::now()` panics on `wasm32-unknown-unknown`, so
+/// there is no fallback here.
+pub fn now() -> Option<SystemTime> {
+    CLOCK.with(|cell| cell.borrow().as_ref().map(|clock_fn| clock_fn()))
+}
+
+/// The host's local offset from UTC in seconds at `time`, or `None` if no
+/// time zone hook is installed.
+pub fn tz_offset(time: SystemTime) -> Option<i32> {
+    TZ_OFFSET.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .map(|tz_offset_fn| tz_offset_fn(time))
+    })
+}
+
+// ── Cancellation hook ────────────────────────────────────────────
+// Allow hosts to interrupt long-running builtins like yes, sleep,
+// tail -f, and dd, which cannot receive SIGINT on WASM.
+
+pub use crate::cancel::INTERRUPTED_EXIT_CODE;
+
+/// Install the cancellation override. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// The hook is polled through [`crate::cancel`] from the main loops of
+/// long-running builtins and should return `true` once the user asked to interrupt the command, after
+/// which the builtin stops and exits with [`INTERRUPTED_EXIT_CODE`].
+pub fn set_cancel_hook(cancel: Box<dyn Fn() -> bool>) {
+    CANCEL.with(|s| *s.borrow_mut() = Some(cancel));
+}
+
+/// Whether the host asked to interrupt the running builtin. Always `false`
+/// when no cancel hook is installed.
+pub fn is_cancelled() -> bool {
+    CANCEL.with(|cell| cell.borrow().as_ref().is_some_and(|cancel_fn| cancel_fn()))
+}
+
+// ── Tick hook ────────────────────────────────────────────────────
+// Allow hosts to schedule the polling loop of tail -f, since there is no
+// inotify on WASM and a host with its own event loop cannot let a builtin
+// block it with `thread::sleep`.
+
+/// Install the tick override. Called by the host (brush-uutils) before
+/// executing a builtin.
+///
+/// The hook is called with the interval a builtin wants to wait between two
+/// polls and should return once the host is ready for the next one, e.g.
+/// after running its own event loop for about that long.
+pub fn set_tick_hook(tick: Box<dyn Fn(Duration)>) {
+    TICK.with(|s| *s.borrow_mut() = Some(tick));
+}
+
+/// Wait for the next poll, through the tick hook if one is installed and
+/// with `thread::sleep` otherwise. Pending output is handed to the host
+/// first so that it shows up while the builtin waits.
+pub fn tick(interval: Duration) {
+    flush_pending_output();
+    TICK.with(|cell| match cell.borrow().as_ref() {
+        Some(tick_fn) => tick_fn(interval),
+        None => std::thread::sleep(interval),
+    });
+}
+
+// ── Identity hooks ───────────────────────────────────────────────
+// Allow hosts to supply the user running the shell and the names of
+// users and groups, so that id, whoami, and the owner columns of ls -l
+// have something to show on WASM, where there is no passwd database.
+
+/// Install the identity overrides. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// `uid` and `gid` are the ids of the user running the builtin, who also
+/// owns every file in the host's VFS, and `groups` the ids of the other
+/// groups they belong to. `name_by_uid` and `name_by_gid` map ids to user
+/// and group names, returning `None` for unknown ids. Utilities reach these
+/// through [`crate::entries`].
+pub fn set_identity_hooks(
+    uid: u32,
+    gid: u32,
+    groups: Vec<u32>,
+    name_by_uid: Box<dyn Fn(u32) -> Option<String>>,
+    name_by_gid: Box<dyn Fn(u32) -> Option<String>>,
+) {
+    IDENTITY.with(|s| *s.borrow_mut() = Some((uid, gid)));
+    GROUPS.with(|s| *s.borrow_mut() = groups);
+    USER_NAME.with(|s| *s.borrow_mut() = Some(name_by_uid));
+    GROUP_NAME.with(|s| *s.borrow_mut() = Some(name_by_gid));
+}
+
+/// The user id of the user running the builtin, or `None` if no identity
+/// hooks are installed.
+pub fn uid() -> Option<u32> {
+    IDENTITY.with(|cell| cell.borrow().map(|(uid, _)| uid))
+}
+
+/// The primary group id of the user running the builtin, or `None` if no
+/// identity hooks are installed.
+pub fn gid() -> Option<u32> {
+    IDENTITY.with(|cell| cell.borrow().map(|(_, gid)| gid))
+}
+
+/// The ids of the groups the user running the builtin belongs to besides
+/// their primary group, empty if no identity hooks are installed.
+pub fn groups() -> Vec<u32> {
+    GROUPS.with(|cell| cell.borrow().clone())
+}
+
+/// The name of the user with the given id, or `None` if it is unknown to
+/// the host or no identity hooks are installed.
+pub fn user_name(uid: u32) -> Option<String> {
+    USER_NAME.with(|cell| cell.borrow().as_ref().and_then(|name_fn| name_fn(uid)))
+}
+
+/// The name of the group with the given id, or `None` if it is unknown to
+/// the host or no identity hooks are installed.
+pub fn group_name(gid: u32) -> Option<String> {
+    GROUP_NAME.with(|cell| cell.borrow().as_ref().and_then(|name_fn| name_fn(gid)))
+}
+
+// ── Randomness hook ──────────────────────────────────────────────
+// Allow hosts to supply entropy (e.g. from the JS crypto API) to shuf,
+// mktemp, and shred, since wasm32-unknown-unknown has no source of its own.
+
+/// Install the randomness override. Called by the host (brush-uutils)
+/// before executing a builtin.
+///
+/// The hook must fill the whole buffer with cryptographically secure random
+/// bytes. Utilities reach it through [`crate::rand_source`].
+pub fn set_random_hook(random: Box<dyn Fn(&mut [u8])>) {
+    RANDOM.with(|s| *s.borrow_mut() = Some(random));
+}
+
+/// Whether the host installed a random hook.
+pub fn has_random_hook() -> bool {
+    RANDOM.with(|cell| cell.borrow().is_some())
+}
+
+/// Fill `dest` with random bytes from the host.
+///
+/// # Panics
+///
+/// Panics if no random hook is installed; check [`has_random_hook`] first.
+pub fn fill_random(dest: &mut [u8]) {
+    RANDOM.with(|cell| {
+        let borrow = cell.borrow();
+        let random_fn = borrow.as_ref().expect("no random hook installed");
+        random_fn(dest);
+    });
+}
@@ -10,7 +10,7 @@
 // * feature-gated external crates (re-shared as public internal modules)
 #[cfg(feature = "libc")]
 pub extern crate libc;
-#[cfg(all(feature = "windows-sys", target_os = "windows"))]
+#[cfg(target_os = "windows")]
 pub extern crate windows_sys;
 
 //## internal modules
@@ -22,8 +22,10 @@ mod mods; // core cross-platform modules
 pub use uucore_procs::*;
 
 // * cross-platform modules
+pub use crate::mods::cancel;
 pub use crate::mods::clap_localization;
 pub use crate::mods::display;
+pub use crate::mods::env;
 pub use crate::mods::error;
 #[cfg(feature = "fs")]
 pub use crate::mods::io;
@@ -32,6 +34,8 @@ pub use crate::mods::locale;
 pub use crate::mods::os;
 pub use crate::mods::panic;
 pub use crate::mods::posix;
+pub use crate::mods::prompt;
+pub use crate::mods::version;
 
 // * feature-gated modules
 #[cfg(feature = "backup-control")]
@@ -44,12 +48,16 @@ pub use crate::features::buf_copy;
 pub use crate::features::checksum;
 #[cfg(feature = "colors")]
 pub use crate::features::colors;
+#[cfg(feature = "copy")]
+pub use crate::features::copy;
 #[cfg(feature = "encoding")]
 pub use crate::features::encoding;
 #[cfg(feature = "extendedbigdecimal")]
 pub use crate::features::extendedbigdecimal;
 #[cfg(feature = "fast-inc")]
 pub use crate::features::fast_inc;
+#[cfg(feature = "files0")]
+pub use crate::features::files0;
 #[cfg(feature = "format")]
 pub use crate::features::format;
 #[cfg(feature = "fs")]
@@ -58,6 +66,8 @@ pub use crate::features::fs;
 pub use crate::features::hardware;
 #[cfg(feature = "i18n-common")]
 pub use crate::features::i18n;
+#[cfg(feature = "key-cmp")]
+pub use crate::features::key_cmp;
 #[cfg(feature = "lines")]
 pub use crate::features::lines;
 #[cfg(any(
@@ -67,8 +77,12 @@ pub use crate::features::lines;
     feature = "parser-glob"
 ))]
 pub use crate::features::parser;
+#[cfg(feature = "progress")]
+pub use crate::features::progress;
 #[cfg(feature = "quoting-style")]
 pub use crate::features::quoting_style;
+#[cfg(feature = "rand-source")]
+pub use crate::features::rand_source;
 #[cfg(feature = "ranges")]
 pub use crate::features::ranges;
 #[cfg(feature = "ringbuffer")]
@@ -91,7 +105,7 @@ pub use crate::features::version_cmp;
 #[cfg(all(not(windows), feature = "mode"))]
 pub use crate::features::mode;
 // ** unix-only
-#[cfg(all(unix, feature = "entries"))]
+#[cfg(all(any(unix, target_family = "wasm"), feature = "entries"))]
 pub use crate::features::entries;
 #[cfg(all(unix, feature = "perms"))]
 pub use crate::features::perms;
@@ -568,6 +582,9 @@ pub fn read_os_string_lines<R: std::io::Read>(
 /// `stderr`. A space is also printed at the end for nice spacing between the prompt and
 /// the user input. Any input starting with `'y'` or `'Y'` is interpreted as `yes`.
 ///
+/// A host running the utility in-process can answer differently, e.g. always
+/// `yes` or through a callback; see [`prompt::with_responder`].
+///
 /// # Examples
 /// ```
 /// use uucore::prompt_yes;
@@ -582,15 +599,7 @@ pub fn read_os_string_lines<R: std::io::Read>(
 #[macro_export]
 macro_rules! prompt_yes(
     ($($args:tt)+) => ({
-        use std::io::Write;
-        eprint!("{}: ", uucore::util_name());
-        eprint!($($args)+);
-        eprint!(" ");
-        let res = std::io::stderr().flush().map_err(|err| {
-            $crate::error::USimpleError::new(1, err.to_string())
-        });
-        uucore::show_if_err!(res);
-        uucore::read_yes()
+        $crate::prompt::ask(format_args!($($args)+))
     })
 );
 
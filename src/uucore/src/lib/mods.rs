@@ -4,8 +4,10 @@
 // file that was distributed with this source code.
 // mods ~ cross-platforms modules (core/bundler file)
 
+pub mod cancel;
 pub mod clap_localization;
 pub mod display;
+pub mod env;
 pub mod error;
 #[cfg(feature = "fs")]
 pub mod io;
@@ -14,3 +16,5 @@ pub mod locale;
 pub mod os;
 pub mod panic;
 pub mod posix;
+pub mod prompt;
+pub mod version;
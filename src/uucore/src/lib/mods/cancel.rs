@@ -0,0 +1,136 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Cooperative cancellation of running utilities.
+//!
+//! A utility running inside another program, e.g. through the multi-call
+//! crate's `registry::run_cancellable`, cannot be stopped with a signal,
+//! either because the platform has none (WASM, Windows) or because the
+//! signal would hit the whole host. Instead, the host hands a [`CancellationToken`] to
+//! [`with_cancellation`] and cancels it from another thread, e.g. when a
+//! timeout expires. Long-running loops poll [`check`] and stop with
+//! [`INTERRUPTED_EXIT_CODE`] once that happened.
+//!
+//! On WASM, the host's cancel hook (`wasm_io::set_cancel_hook`) is polled
+//! as well.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::error::{ExitCode, UResult};
+
+/// The exit status of a utility stopped through cancellation, matching the
+/// status a shell reports for a command killed by SIGINT.
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// A flag shared between a host and the utility it runs. Clones refer to the
+/// same flag, so one can be moved to the thread that decides to cancel.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the utility running with this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with `token` as the cancellation token of the current thread.
+///
+/// The previous token, if any, is restored when `f` returns, even on panic.
+pub fn with_cancellation<F, R>(token: CancellationToken, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct RestoreGuard(Option<CancellationToken>);
+    impl Drop for RestoreGuard {
+        fn drop(&mut self) {
+            CURRENT.with(|cell| *cell.borrow_mut() = self.0.take());
+        }
+    }
+
+    let previous = CURRENT.with(|cell| cell.borrow_mut().replace(token));
+    let _guard = RestoreGuard(previous);
+    f()
+}
+
+/// Whether the running utility can be asked to stop at all, i.e. a token is
+/// installed or the host may cancel through its hook on WASM. Loops that
+/// only wake up to poll [`check`] can skip that when this is `false`.
+pub fn is_cancellable() -> bool {
+    cfg!(target_family = "wasm") || CURRENT.with(|cell| cell.borrow().is_some())
+}
+
+/// Whether the running utility was asked to stop.
+pub fn is_cancelled() -> bool {
+    #[cfg(target_family = "wasm")]
+    if crate::wasm_io::is_cancelled() {
+        return true;
+    }
+    CURRENT.with(|cell| {
+        cell.borrow()
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    })
+}
+
+/// Fail with [`INTERRUPTED_EXIT_CODE`] if the running utility was asked to
+/// stop. Meant to be called once per iteration of a long-running loop.
+pub fn check() -> UResult<()> {
+    if is_cancelled() {
+        Err(ExitCode::new(INTERRUPTED_EXIT_CODE))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_token() {
+        assert!(!is_cancellable());
+        assert!(!is_cancelled());
+        assert!(check().is_ok());
+    }
+
+    #[test]
+    fn test_cancel_from_another_thread() {
+        let token = CancellationToken::new();
+        let remote = token.clone();
+        with_cancellation(token, || {
+            assert!(is_cancellable());
+            assert!(!is_cancelled());
+            std::thread::spawn(move || remote.cancel()).join().unwrap();
+            assert!(is_cancelled());
+            assert_eq!(check().unwrap_err().code(), INTERRUPTED_EXIT_CODE);
+        });
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn test_nested_tokens_are_restored() {
+        let outer = CancellationToken::new();
+        outer.cancel();
+        with_cancellation(outer, || {
+            with_cancellation(CancellationToken::new(), || assert!(!is_cancelled()));
+            assert!(is_cancelled());
+        });
+    }
+}
@@ -11,7 +11,7 @@
 //! instead of parsing error strings, providing a more robust solution.
 //!
 
-use crate::error::{UResult, USimpleError};
+use crate::error::{UError, UResult, USimpleError};
 use crate::locale::translate;
 
 use clap::error::{ContextKind, ErrorKind};
@@ -19,6 +19,7 @@ use clap::{ArgMatches, Command, Error};
 
 use std::error::Error as StdError;
 use std::ffi::OsString;
+use std::io::Write;
 
 /// Color enum for consistent styling
 #[derive(Debug, Clone, Copy)]
@@ -94,23 +95,37 @@ impl<'a> ErrorFormatter<'a> {
     }
 
     /// Print error and exit with the specified code
+    #[cfg(not(feature = "embedded"))]
     fn print_error_and_exit(&self, err: &Error, exit_code: i32) -> ! {
         self.print_error_and_exit_with_callback(err, exit_code, || {})
     }
 
     /// Print error with optional callback before exit
+    ///
+    /// Not available with the `embedded` feature, use
+    /// [`Self::print_error_with_callback`] and return the code instead.
+    #[cfg(not(feature = "embedded"))]
     pub fn print_error_and_exit_with_callback<F>(
         &self,
         err: &Error,
         exit_code: i32,
         callback: F,
     ) -> !
+    where
+        F: FnOnce(),
+    {
+        let code = self.print_error_with_callback(err, exit_code, callback);
+        std::process::exit(code);
+    }
+
+    /// Print error and run the callback, then return the exit code (no exit call)
+    pub fn print_error_with_callback<F>(&self, err: &Error, exit_code: i32, callback: F) -> i32
     where
         F: FnOnce(),
     {
         let code = self.print_error(err, exit_code);
         callback();
-        std::process::exit(code);
+        code
     }
 
     /// Print error and return exit code (no exit call)
@@ -134,6 +149,9 @@ impl<'a> ErrorFormatter<'a> {
     /// Handle help and version display
     fn handle_display_errors(&self, err: &Error) -> i32 {
         print!("{}", err.render());
+        if err.kind() == ErrorKind::DisplayVersion {
+            let _ = crate::version::print_requested_details();
+        }
         0
     }
 
@@ -233,15 +251,7 @@ impl<'a> ErrorFormatter<'a> {
             self.print_simple_error_msg(&err.render().to_string());
         }
 
-        // InvalidValue errors traditionally use exit code 1 for backward compatibility
-        // But if a utility explicitly requests a high exit code (>= 125), respect it
-        // This allows utilities like runcon (125) to override the default while preserving
-        // the standard behavior for utilities using normal error codes (1, 2, etc.)
-        if matches!(err.kind(), ErrorKind::InvalidValue) && exit_code < 125 {
-            1 // Force exit code 1 for InvalidValue unless using special exit codes
-        } else {
-            exit_code // Respect the requested exit code for special cases
-        }
+        clap_error_exit_code(err, exit_code)
     }
 
     /// Handle missing required argument errors
@@ -393,6 +403,37 @@ where
     handle_clap_result_with_exit_code(cmd, itr, 1)
 }
 
+/// Prints the help or the version of `cmd` for a utility that parses its
+/// own arguments, such as `echo` or `true`, and was given `arg` as its only
+/// option.
+///
+/// The output goes to stdout and is the same as a `--help` or `--version`
+/// handled by [`handle_clap_result`]. Returns `None` if `arg` is neither
+/// option, and the write error otherwise, leaving it to the utility to decide
+/// on the exit code.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clap::Command;
+/// use uucore::clap_localization::print_help_or_version;
+///
+/// let cmd = Command::new("myutil");
+/// if let Some(printed) = print_help_or_version(cmd, b"--version") {
+///     printed.expect("write error");
+/// }
+/// ```
+pub fn print_help_or_version(mut cmd: Command, arg: &[u8]) -> Option<std::io::Result<()>> {
+    match arg {
+        b"--help" => Some(cmd.print_help()),
+        b"--version" => {
+            let mut stdout = std::io::stdout();
+            Some(write!(stdout, "{}", crate::version::render(&cmd)).and_then(|()| stdout.flush()))
+        }
+        _ => None,
+    }
+}
+
 /// Handles clap command parsing with a custom exit code for errors.
 ///
 /// Similar to `handle_clap_result` but allows specifying a custom exit code
@@ -434,15 +475,54 @@ where
     I: IntoIterator<Item = T>,
     T: Into<OsString> + Clone,
 {
-    cmd.try_get_matches_from(itr).map_err(|e| {
-        if e.exit_code() == 0 {
-            e.into() // Preserve help/version
-        } else {
-            let formatter = ErrorFormatter::new(crate::util_name());
-            let code = formatter.print_error(&e, exit_code);
-            USimpleError::new(code, "")
-        }
-    })
+    cmd.try_get_matches_from(itr)
+        .map_err(|e| clap_error_to_uerror(e, exit_code))
+}
+
+/// Converts a clap error into a [`UError`] using a custom exit code.
+///
+/// Help and version requests are passed through unchanged, so they are
+/// printed to stdout and exit with code 0. Any other error is printed to
+/// stderr right away with localized formatting, and the returned error only
+/// carries the exit code.
+///
+/// This is the building block of [`handle_clap_result_with_exit_code`]. Use it
+/// directly when a utility has to inspect the parse result before deciding to
+/// report the error, for example to fall back to an obsolete syntax.
+///
+/// # Examples
+///
+/// ```no_run
+/// use clap::Command;
+/// use uucore::clap_localization::clap_error_to_uerror;
+///
+/// let cmd = Command::new("myutil");
+/// let result = cmd
+///     .try_get_matches_from(["myutil", "--invalid"])
+///     .map_err(|e| clap_error_to_uerror(e, 2));
+/// ```
+pub fn clap_error_to_uerror(err: Error, exit_code: i32) -> Box<dyn UError> {
+    if err.exit_code() == 0 {
+        err.into() // Preserve help/version
+    } else {
+        let formatter = ErrorFormatter::new(crate::util_name());
+        let code = formatter.print_error(&err, exit_code);
+        USimpleError::new(code, "")
+    }
+}
+
+/// The exit code [`ErrorFormatter::print_error`] returns for `err` when the
+/// utility asked for `exit_code`, known before anything is printed.
+pub fn clap_error_exit_code(err: &Error, exit_code: i32) -> i32 {
+    match err.kind() {
+        ErrorKind::DisplayHelp | ErrorKind::DisplayVersion => 0,
+        // InvalidValue errors traditionally use exit code 1 for backward compatibility
+        // But if a utility explicitly requests a high exit code (>= 125), respect it
+        // This allows utilities like runcon (125) to override the default while preserving
+        // the standard behavior for utilities using normal error codes (1, 2, etc.)
+        ErrorKind::InvalidValue if exit_code < 125 => 1,
+        _ => exit_code,
+    }
 }
 
 /// Handles a clap error directly with a custom exit code.
@@ -459,6 +539,8 @@ where
 /// # Panics
 ///
 /// This function never returns - it always calls `std::process::exit()`.
+/// It is not available with the `embedded` feature, where the process must
+/// not exit; use [`clap_error_to_uerror`] and return the error instead.
 ///
 /// # Examples
 ///
@@ -472,6 +554,7 @@ where
 ///     Err(e) => handle_clap_error_with_exit_code(e, 1),
 /// }
 /// ```
+#[cfg(not(feature = "embedded"))]
 pub fn handle_clap_error_with_exit_code(err: Error, exit_code: i32) -> ! {
     let formatter = ErrorFormatter::new(crate::util_name());
     formatter.print_error_and_exit(&err, exit_code);
@@ -591,6 +674,60 @@ mod tests {
         assert_eq!(matches.get_one::<String>("output").unwrap(), "out.txt");
     }
 
+    #[test]
+    fn test_handle_clap_result_help_and_version() {
+        for arg in ["--help", "--version"] {
+            let cmd = create_test_command().version("1.0");
+            let err = handle_clap_result(cmd, vec!["test", arg]).unwrap_err();
+            assert_eq!(err.code(), 0);
+        }
+    }
+
+    #[test]
+    fn test_handle_clap_result_with_exit_code_for_usage_error() {
+        let cmd = create_test_command();
+        let err = handle_clap_result_with_exit_code(cmd, vec!["test", "--unknown"], 2).unwrap_err();
+        assert_eq!(err.code(), 2);
+
+        let cmd = create_test_command();
+        let err = handle_clap_result(cmd, vec!["test", "--unknown"]).unwrap_err();
+        assert_eq!(err.code(), 1);
+    }
+
+    #[test]
+    fn test_print_error_with_callback_returns_code() {
+        let err = create_test_command()
+            .try_get_matches_from(vec!["test", "--unknown"])
+            .unwrap_err();
+        let mut called = false;
+        let code = ErrorFormatter::new("test").print_error_with_callback(&err, 125, || {
+            called = true;
+        });
+        assert_eq!(code, 125);
+        assert!(called);
+    }
+
+    #[test]
+    fn test_print_help_or_version_other_arg() {
+        assert!(print_help_or_version(create_test_command(), b"--helpx").is_none());
+        assert!(print_help_or_version(create_test_command(), b"-h").is_none());
+    }
+
+    #[test]
+    fn test_handle_clap_result_double_dash() {
+        let cmd = Command::new("test")
+            .arg(
+                Arg::new("verbose")
+                    .short('v')
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(Arg::new("files").num_args(0..));
+        let matches = handle_clap_result(cmd, vec!["test", "--", "-v", "--help"]).unwrap();
+        assert!(!matches.get_flag("verbose"));
+        let files: Vec<_> = matches.get_many::<String>("files").unwrap().collect();
+        assert_eq!(files, ["-v", "--help"]);
+    }
+
     #[test]
     fn test_configure_localized_command() {
         let cmd = Command::new("test");
@@ -23,17 +23,28 @@
 //! println_verbatim(path)?; // Prints "foo/bar.baz"
 //! # Ok::<(), std::io::Error>(())
 //! ```
+//!
+//! Utilities whose output is arbitrary bytes, like `cat` and `od`, should write
+//! through [`raw_stdout`], which does not alter the bytes even on a Windows console.
 
-use std::env;
+use crate::env;
 use std::ffi::OsStr;
 use std::fmt;
 use std::fs::File;
 use std::io::{self, BufWriter, Stdout, StdoutLock, Write as IoWrite};
 
+#[cfg(unix)]
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
 #[cfg(unix)]
 use std::os::unix::ffi::OsStrExt;
-#[cfg(target_os = "wasi")]
-use std::os::wasi::ffi::OsStrExt;
+#[cfg(windows)]
+use std::os::windows::ffi::OsStrExt;
+#[cfg(windows)]
+use std::os::windows::io::{AsHandle, AsRawHandle, BorrowedHandle, RawHandle};
+#[cfg(windows)]
+use windows_sys::Win32::Storage::FileSystem::WriteFile;
+#[cfg(windows)]
+use windows_sys::Win32::System::Console::{GetConsoleMode, WriteConsoleW};
 
 // These used to be defined here, but they live in their own crate now.
 pub use os_display::{Quotable, Quoted};
@@ -49,7 +60,10 @@ pub use os_display::{Quotable, Quoted};
 /// using low-level library calls and bypassing `io::Write`. This is not a big priority
 /// because broken filenames are much rarer on Windows than on Unix.
 pub fn println_verbatim<S: AsRef<OsStr>>(text: S) -> io::Result<()> {
+    #[cfg(not(target_family = "wasm"))]
     let mut stdout = io::stdout().lock();
+    #[cfg(target_family = "wasm")]
+    let mut stdout = crate::wasm_io::stdout().lock();
     stdout.write_all_os(text.as_ref())?;
     stdout.write_all(b"\n")?;
     Ok(())
@@ -57,15 +71,19 @@ pub fn println_verbatim<S: AsRef<OsStr>>(text: S) -> io::Result<()> {
 
 /// Like `println_verbatim`, without the trailing newline.
 pub fn print_verbatim<S: AsRef<OsStr>>(text: S) -> io::Result<()> {
-    io::stdout().write_all_os(text.as_ref())
+    #[cfg(not(target_family = "wasm"))]
+    let mut stdout = io::stdout();
+    #[cfg(target_family = "wasm")]
+    let mut stdout = crate::wasm_io::stdout();
+    stdout.write_all_os(text.as_ref())
 }
 
 /// [`io::Write`], but for OS strings.
 ///
 /// On Unix this works straightforwardly.
 ///
-/// On Windows this currently returns an error if the OS string is not valid Unicode.
-/// This may in the future change to allow those strings to be written to consoles.
+/// On Windows this returns an error if the OS string is not valid Unicode, unless
+/// the writer is a console: [`RawStdout`] writes those strings to consoles as UTF-16.
 pub trait OsWrite: io::Write {
     /// Write the entire OS string into this writer.
     ///
@@ -76,17 +94,22 @@ pub trait OsWrite: io::Write {
     /// On Windows, if the OS string is not valid Unicode, an error of kind
     /// [`io::ErrorKind::InvalidData`] is returned.
     fn write_all_os(&mut self, buf: &OsStr) -> io::Result<()> {
-        #[cfg(any(unix, target_os = "wasi"))]
+        #[cfg(unix)]
         {
             self.write_all(buf.as_bytes())
         }
 
+        // OS strings are arbitrary bytes on WASI too, but `std::os::wasi` is
+        // unstable on preview 2, so go through the encoded bytes instead.
+        #[cfg(target_os = "wasi")]
+        {
+            self.write_all(buf.as_encoded_bytes())
+        }
+
         #[cfg(not(any(unix, target_os = "wasi")))]
         {
-            // It's possible to write a better OsWrite impl for Windows consoles (e.g. Stdout)
-            // as those are fundamentally 16-bit. If the OS string is invalid then it can be
-            // encoded to 16-bit and written using raw windows_sys calls. But this is quite involved
-            // (see `sys/pal/windows/stdio.rs` in the stdlib) and the value-add is small.
+            // Windows consoles are fundamentally 16-bit, so invalid OS strings can be
+            // written to them as UTF-16, which `RawStdout` does.
             //
             // There's no way to write invalid OS strings to Windows files, as those are 8-bit.
 
@@ -109,9 +132,26 @@ pub trait OsWrite: io::Write {
 impl OsWrite for File {}
 impl OsWrite for Stdout {}
 impl OsWrite for StdoutLock<'_> {}
-// A future smarter Windows implementation can first flush the BufWriter before
-// doing a raw write.
-impl<W: OsWrite> OsWrite for BufWriter<W> {}
+#[cfg(target_family = "wasm")]
+impl OsWrite for crate::wasm_io::WasmStdout {}
+#[cfg(target_family = "wasm")]
+impl OsWrite for crate::wasm_io::WasmStdoutLock {}
+/// Files opened through [`crate::wasm_io::open_file_write`].
+#[cfg(target_family = "wasm")]
+impl OsWrite for Box<dyn io::Write> {}
+impl<W: OsWrite> OsWrite for BufWriter<W> {
+    // The inner writer may be able to write strings that are not valid
+    // Unicode, but only if it gets them directly.
+    #[cfg(windows)]
+    fn write_all_os(&mut self, buf: &OsStr) -> io::Result<()> {
+        if let Some(text) = buf.to_str() {
+            self.write_all(text.as_bytes())
+        } else {
+            self.flush()?;
+            self.get_mut().write_all_os(buf)
+        }
+    }
+}
 
 impl OsWrite for Box<dyn OsWrite> {
     fn write_all_os(&mut self, buf: &OsStr) -> io::Result<()> {
@@ -120,6 +160,207 @@ impl OsWrite for Box<dyn OsWrite> {
     }
 }
 
+/// Lock stdout for writing bytes exactly as given.
+///
+/// Outside of Windows this is just [`io::stdout().lock()`](io::Stdout::lock).
+///
+/// On Windows the standard library writes to a console with `WriteConsoleW`,
+/// which needs UTF-8, and it fails on anything else. The returned writer still
+/// sends valid UTF-8 that way, but writes the bytes of invalid sequences to
+/// the console unchanged with `WriteFile`, as they would be if stdout were
+/// redirected to a file. It also writes OS strings that are not valid Unicode
+/// to the console as UTF-16, without any loss.
+pub fn raw_stdout() -> RawStdout {
+    let inner = io::stdout().lock();
+    #[cfg(windows)]
+    let is_console = {
+        let mut mode = 0;
+        // SAFETY: the handle stays open while `inner` is alive, and `mode`
+        // is a valid place to store the console mode.
+        unsafe { GetConsoleMode(inner.as_raw_handle(), &raw mut mode) != 0 }
+    };
+    RawStdout {
+        inner,
+        #[cfg(windows)]
+        is_console,
+        #[cfg(windows)]
+        pending: Vec::new(),
+    }
+}
+
+/// Locked stdout that never alters the bytes written to it, see [`raw_stdout`].
+pub struct RawStdout {
+    inner: StdoutLock<'static>,
+    #[cfg(windows)]
+    is_console: bool,
+    /// The start of a UTF-8 sequence that was cut off at the end of the last
+    /// write, held back until we know whether it is valid.
+    #[cfg(windows)]
+    pending: Vec<u8>,
+}
+
+#[cfg(windows)]
+impl RawStdout {
+    /// Write bytes to the console, sending the valid UTF-8 through the
+    /// standard library and the rest through [`Self::write_raw`].
+    fn write_console(&mut self, buf: &[u8]) -> io::Result<()> {
+        let joined;
+        let mut rest = if self.pending.is_empty() {
+            buf
+        } else {
+            self.pending.extend_from_slice(buf);
+            joined = std::mem::take(&mut self.pending);
+            &joined[..]
+        };
+        while !rest.is_empty() {
+            let Err(err) = std::str::from_utf8(rest) else {
+                return self.inner.write_all(rest);
+            };
+            let (valid, invalid) = rest.split_at(err.valid_up_to());
+            self.inner.write_all(valid)?;
+            let Some(len) = err.error_len() else {
+                self.pending.extend_from_slice(invalid);
+                break;
+            };
+            self.inner.flush()?;
+            self.write_raw(&invalid[..len])?;
+            rest = &invalid[len..];
+        }
+        Ok(())
+    }
+
+    /// Write bytes to the handle with `WriteFile`, bypassing the standard
+    /// library's console handling.
+    fn write_raw(&mut self, mut buf: &[u8]) -> io::Result<()> {
+        while !buf.is_empty() {
+            let len = u32::try_from(buf.len()).unwrap_or(u32::MAX);
+            let mut written = 0;
+            // SAFETY: `buf` is valid for `len` bytes, and the write is not
+            // overlapped.
+            let ok = unsafe {
+                WriteFile(
+                    self.inner.as_raw_handle(),
+                    buf.as_ptr(),
+                    len,
+                    &raw mut written,
+                    std::ptr::null_mut(),
+                )
+            };
+            if ok == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            buf = &buf[written as usize..];
+        }
+        Ok(())
+    }
+
+    /// Write UTF-16 to the console with `WriteConsoleW`.
+    fn write_wide(&mut self, mut wide: &[u16]) -> io::Result<()> {
+        while !wide.is_empty() {
+            let len = u32::try_from(wide.len()).unwrap_or(u32::MAX);
+            let mut written = 0;
+            // SAFETY: `wide` is valid for `len` code units.
+            let ok = unsafe {
+                WriteConsoleW(
+                    self.inner.as_raw_handle(),
+                    wide.as_ptr(),
+                    len,
+                    &raw mut written,
+                    std::ptr::null(),
+                )
+            };
+            if ok == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            wide = &wide[written as usize..];
+        }
+        Ok(())
+    }
+
+    /// Write out a cut-off UTF-8 sequence that is not going to be completed.
+    fn write_pending(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        self.inner.flush()?;
+        let pending = std::mem::take(&mut self.pending);
+        self.write_raw(&pending)
+    }
+}
+
+impl IoWrite for RawStdout {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        #[cfg(windows)]
+        if self.is_console {
+            self.write_console(buf)?;
+            return Ok(buf.len());
+        }
+        self.inner.write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        #[cfg(windows)]
+        if self.is_console {
+            return self.write_console(buf);
+        }
+        self.inner.write_all(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl OsWrite for RawStdout {
+    #[cfg(windows)]
+    fn write_all_os(&mut self, buf: &OsStr) -> io::Result<()> {
+        match buf.to_str() {
+            Some(text) => self.write_all(text.as_bytes()),
+            None if self.is_console => {
+                self.write_pending()?;
+                self.inner.flush()?;
+                self.write_wide(&buf.encode_wide().collect::<Vec<_>>())
+            }
+            None => self.inner.write_all_os(buf),
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for RawStdout {
+    fn drop(&mut self) {
+        let _ = self.write_pending();
+    }
+}
+
+#[cfg(unix)]
+impl AsFd for RawStdout {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.inner.as_fd()
+    }
+}
+
+#[cfg(unix)]
+impl AsRawFd for RawStdout {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl AsHandle for RawStdout {
+    fn as_handle(&self) -> BorrowedHandle<'_> {
+        self.inner.as_handle()
+    }
+}
+
+#[cfg(windows)]
+impl AsRawHandle for RawStdout {
+    fn as_raw_handle(&self) -> RawHandle {
+        self.inner.as_raw_handle()
+    }
+}
+
 /// Print all environment variables in the format `name=value` with the specified line ending.
 ///
 /// This function handles non-UTF-8 environment variable names and values correctly by using
@@ -134,3 +375,56 @@ pub fn print_all_env_vars<T: fmt::Display>(line_ending: T) -> io::Result<()> {
     }
     Ok(())
 }
+
+/// Prints the `==> name <==` headers that `head` and `tail` put before the
+/// output of each file, with a blank line between a header and the output of
+/// the previous file.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeaderPrinter {
+    enabled: bool,
+    first_header: bool,
+}
+
+impl HeaderPrinter {
+    /// A printer that prints headers if `enabled` is set. `first_header` tells
+    /// whether no header has been printed yet, so the next one needs no blank
+    /// line before it.
+    pub fn new(enabled: bool, first_header: bool) -> Self {
+        Self {
+            enabled,
+            first_header,
+        }
+    }
+
+    /// A printer for `file_count` operands: like GNU, headers are always
+    /// printed with `-v`, never with `-q`, and otherwise only if there is more
+    /// than one operand. Of `-q` and `-v`, whichever comes last wins, which is
+    /// up to the argument parser.
+    pub fn for_files(file_count: usize, quiet: bool, verbose: bool) -> Self {
+        Self::new(verbose || (file_count > 1 && !quiet), true)
+    }
+
+    /// Whether headers are printed at all.
+    pub fn enabled(self) -> bool {
+        self.enabled
+    }
+
+    /// Print the header for `name`, verbatim, if headers are enabled.
+    pub fn print<S: AsRef<OsStr>>(&mut self, name: S) -> io::Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        #[cfg(not(target_family = "wasm"))]
+        let mut stdout = io::stdout().lock();
+        #[cfg(target_family = "wasm")]
+        let mut stdout = crate::wasm_io::stdout().lock();
+        if !self.first_header {
+            stdout.write_all(b"\n")?;
+        }
+        self.first_header = false;
+        stdout.write_all(b"==> ")?;
+        stdout.write_all_os(name.as_ref())?;
+        stdout.write_all(b" <==\n")?;
+        stdout.flush()
+    }
+}
@@ -0,0 +1,88 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Access to environment variables.
+//!
+//! These functions mirror their [`std::env`] counterparts. On WASM they go
+//! through the host's variable table installed with
+//! `wasm_io::set_env_hooks`, so utilities should use them instead of
+//! [`std::env`] for variables the user can set, like `TZ` or `LC_ALL`.
+
+use std::env::VarError;
+use std::ffi::{OsStr, OsString};
+
+/// Fetch the environment variable `key`, see [`std::env::var_os`].
+pub fn var_os<K: AsRef<OsStr>>(key: K) -> Option<OsString> {
+    #[cfg(target_family = "wasm")]
+    return crate::wasm_io::var_os(key.as_ref());
+    #[cfg(not(target_family = "wasm"))]
+    std::env::var_os(key)
+}
+
+/// Fetch the environment variable `key` as a `String`, see [`std::env::var`].
+pub fn var<K: AsRef<OsStr>>(key: K) -> Result<String, VarError> {
+    match var_os(key) {
+        Some(value) => value.into_string().map_err(VarError::NotUnicode),
+        None => Err(VarError::NotPresent),
+    }
+}
+
+/// List all environment variables, see [`std::env::vars_os`].
+pub fn vars_os() -> impl Iterator<Item = (OsString, OsString)> {
+    #[cfg(target_family = "wasm")]
+    return crate::wasm_io::vars_os().into_iter();
+    #[cfg(not(target_family = "wasm"))]
+    std::env::vars_os()
+}
+
+/// Set the environment variable `key` to `value`, see [`std::env::set_var`].
+///
+/// # Safety
+///
+/// Same as [`std::env::set_var`].
+pub unsafe fn set_var<K: AsRef<OsStr>, V: AsRef<OsStr>>(key: K, value: V) {
+    #[cfg(target_family = "wasm")]
+    unsafe {
+        crate::wasm_io::set_var(key.as_ref(), Some(value.as_ref()));
+    }
+    #[cfg(not(target_family = "wasm"))]
+    unsafe {
+        std::env::set_var(key, value);
+    }
+}
+
+/// Remove the environment variable `key`, see [`std::env::remove_var`].
+///
+/// # Safety
+///
+/// Same as [`std::env::remove_var`].
+pub unsafe fn remove_var<K: AsRef<OsStr>>(key: K) {
+    #[cfg(target_family = "wasm")]
+    unsafe {
+        crate::wasm_io::set_var(key.as_ref(), None);
+    }
+    #[cfg(not(target_family = "wasm"))]
+    unsafe {
+        std::env::remove_var(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_var_matches_std() {
+        let key = "UUCORE_ENV_TEST_VAR";
+        unsafe { set_var(key, "value") };
+        assert_eq!(var(key), Ok("value".to_string()));
+        assert_eq!(var_os(key), std::env::var_os(key));
+        assert!(vars_os().any(|(k, v)| k == key && v == "value"));
+
+        unsafe { remove_var(key) };
+        assert_eq!(var(key), Err(VarError::NotPresent));
+        assert_eq!(std::env::var_os(key), None);
+    }
+}
@@ -44,6 +44,24 @@
 //! **Note**: The [`crate::show`] and [`crate::show_if_err`] macros set the exit code of the program using
 //! [`set_exit_code`]. See the documentation on that function for more information.
 //!
+//! # Exit codes
+//! Most GNU utilities exit with `1` for every failure, but some tell failures apart: `ls`
+//! exits with `2` for serious trouble and `1` for minor problems, `sort` with `2` for any
+//! failure because `1` means that `-c` found disorder. [`ExitCodePolicy`] records the exit
+//! code of each [`FailureClass`] for a utility, so that the exit status doesn't depend on
+//! the error type a failure happens to be reported with. In particular a plain [`UIoError`]
+//! always has the code `1`, which [`ExitCodePolicy::classify_io_error`] adjusts.
+//!
+//! # Embedding
+//! With the `embedded` feature, `uucore` is used by a host that runs utilities in its own
+//! process (for example a WASM instance driven through `wasm_io::with_wasm_io`), so the
+//! process must never exit. Every termination has to go through the [`UResult`] returned from
+//! `uumain`, which the [`crate::main`] macro turns into the returned exit code. Helpers that
+//! call [`std::process::exit`] are not available with this feature, and utilities check
+//! [`EMBEDDED`] before installing signal handlers that would. Each call of `uumain`
+//! starts again from exit code `0`, so a code set with [`set_exit_code`] doesn't leak into the
+//! next invocation.
+//!
 //! # Guidelines
 //! * Use error types from `uucore` where possible.
 //! * Add error types to `uucore` if an error appears in multiple utils.
@@ -64,6 +82,10 @@ use std::{
 
 static EXIT_CODE: AtomicI32 = AtomicI32::new(0);
 
+/// Whether `uucore` is built with the `embedded` feature, in which case the
+/// process must never exit (see [Embedding](self#embedding)).
+pub const EMBEDDED: bool = cfg!(feature = "embedded");
+
 /// Get the last exit code set with [`set_exit_code`].
 /// The default value is `0`.
 pub fn get_exit_code() -> i32 {
@@ -679,11 +701,156 @@ impl From<i32> for Box<dyn UError> {
     }
 }
 
+/// A class of failures, which a utility may exit with a code of its own for,
+/// see [`ExitCodePolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// The command line is invalid, e.g. an unknown option or a bad option argument.
+    Usage,
+    /// An operand given on the command line can't be processed.
+    Operand,
+    /// A file found while processing an operand, e.g. in a subdirectory, can't be processed.
+    Nested,
+    /// The output can't be written.
+    Write,
+    /// Any other failure.
+    Other,
+}
+
+/// The exit code a utility uses for each [`FailureClass`], matching GNU.
+///
+/// ```
+/// use uucore::error::{ExitCodePolicy, FailureClass, UError, USimpleError};
+///
+/// assert_eq!(ExitCodePolicy::LS.code(FailureClass::Nested), 1);
+/// assert_eq!(ExitCodePolicy::LS.code(FailureClass::Operand), 2);
+///
+/// let err = ExitCodePolicy::SORT.classify(FailureClass::Other, USimpleError::new(1, "error!"));
+/// assert_eq!(err.code(), 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitCodePolicy {
+    /// The exit code for [`FailureClass::Usage`].
+    pub usage: i32,
+    /// The exit code for [`FailureClass::Operand`].
+    pub operand: i32,
+    /// The exit code for [`FailureClass::Nested`].
+    pub nested: i32,
+    /// The exit code for [`FailureClass::Write`].
+    pub write: i32,
+    /// The exit code for [`FailureClass::Other`].
+    pub other: i32,
+}
+
+impl ExitCodePolicy {
+    /// Exit with `1` for every failure, like most GNU utilities.
+    pub const DEFAULT: Self = Self::uniform(1);
+
+    /// `ls`, `dir` and `vdir`: `2` for serious trouble, such as an operand that can't be
+    /// accessed, and `1` for minor problems, such as a subdirectory that can't be read.
+    pub const LS: Self = Self {
+        nested: 1,
+        ..Self::uniform(2)
+    };
+
+    /// `du`: `1` for every failure.
+    pub const DU: Self = Self::DEFAULT;
+
+    /// `df`: `1` for every failure.
+    pub const DF: Self = Self::DEFAULT;
+
+    /// `sort`: `2` for every failure, `1` only reports disorder found by `-c`.
+    pub const SORT: Self = Self::uniform(2);
+
+    /// A policy with the same exit code for every failure.
+    pub const fn uniform(code: i32) -> Self {
+        Self {
+            usage: code,
+            operand: code,
+            nested: code,
+            write: code,
+            other: code,
+        }
+    }
+
+    /// The exit code for failures of `class`.
+    pub const fn code(self, class: FailureClass) -> i32 {
+        match class {
+            FailureClass::Usage => self.usage,
+            FailureClass::Operand => self.operand,
+            FailureClass::Nested => self.nested,
+            FailureClass::Write => self.write,
+            FailureClass::Other => self.other,
+        }
+    }
+
+    /// Make `err` exit with the code for `class`, keeping its message.
+    pub fn classify(self, class: FailureClass, err: Box<dyn UError>) -> Box<dyn UError> {
+        Box::new(ClassifiedError {
+            code: self.code(class),
+            inner: err,
+        })
+    }
+
+    /// Give a plain [`UIoError`], e.g. from `?` on a [`std::io::Result`], the exit code for
+    /// `class`. Errors from a [`TagWriteErrors`](crate::io::TagWriteErrors) writer are
+    /// reported as `write error: <reason>` with the exit code for [`FailureClass::Write`]
+    /// instead, like GNU does when standard output can't be written. Other errors already
+    /// have the right code and are returned as they are.
+    #[cfg(feature = "fs")]
+    pub fn classify_io_error(self, class: FailureClass, err: Box<dyn UError>) -> Box<dyn UError> {
+        let Some(io_err) = (&*err as &dyn Error).downcast_ref::<UIoError>() else {
+            return err;
+        };
+        if crate::io::is_write_error(&io_err.inner) {
+            let message = format!(
+                "{}: {}",
+                crate::translate!("error-write"),
+                strip_errno(&io_err.inner)
+            );
+            return USimpleError::new(self.code(FailureClass::Write), message);
+        }
+        self.classify(class, err)
+    }
+}
+
+/// An error with the exit code chosen by an [`ExitCodePolicy`].
+#[derive(Debug)]
+struct ClassifiedError {
+    code: i32,
+    inner: Box<dyn UError>,
+}
+
+impl Error for ClassifiedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.inner.source()
+    }
+}
+
+impl Display for ClassifiedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        self.inner.fmt(f)
+    }
+}
+
+impl UError for ClassifiedError {
+    fn code(&self) -> i32 {
+        self.code
+    }
+
+    fn usage(&self) -> bool {
+        self.inner.usage()
+    }
+}
+
 /// A wrapper for `clap::Error` that implements [`UError`]
 ///
 /// Contains a custom error code. When `Display::fmt` is called on this struct
 /// the [`clap::Error`] will be printed _directly to `stdout` or `stderr`_.
 /// This is because `clap` only supports colored output when it prints directly.
+/// Usage errors are printed with the localized formatting of
+/// [`crate::clap_localization::handle_clap_result`], so a clap error that is
+/// returned from `uumain` looks the same as one caught while parsing.
 ///
 /// [`ClapErrorWrapper`] is generally created by calling the
 /// [`UClapError::with_exit_code`] method on [`clap::Error`] or using the [`From`]
@@ -713,18 +880,14 @@ pub trait UClapError<T> {
 
 impl From<clap::Error> for Box<dyn UError> {
     fn from(e: clap::Error) -> Self {
-        Box::new(ClapErrorWrapper {
-            code: 1,
-            error: e,
-            print_failed: Cell::new(false),
-        })
+        Box::new(e.with_exit_code(1))
     }
 }
 
 impl UClapError<ClapErrorWrapper> for clap::Error {
     fn with_exit_code(self, code: i32) -> ClapErrorWrapper {
         ClapErrorWrapper {
-            code,
+            code: crate::clap_localization::clap_error_exit_code(&self, code),
             error: self,
             print_failed: Cell::new(false),
         }
@@ -758,10 +921,22 @@ impl Error for ClapErrorWrapper {}
 // This is abuse of the Display trait
 impl Display for ClapErrorWrapper {
     fn fmt(&self, _f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        if self.error.exit_code() != 0 {
+            let formatter = crate::clap_localization::ErrorFormatter::new(crate::util_name());
+            formatter.print_error(&self.error, self.code);
+            return Ok(());
+        }
         // Check if printing succeeds. For DisplayHelp and DisplayVersion,
         // error.print() writes to stdout, so we need to detect write failures
         // (e.g., when stdout is /dev/full).
-        if let Err(print_fail) = self.error.print() {
+        let printed = self.error.print().and_then(|()| {
+            if self.error.kind() == clap::error::ErrorKind::DisplayVersion {
+                crate::version::print_requested_details()
+            } else {
+                Ok(())
+            }
+        });
+        if let Err(print_fail) = printed {
             // Mark that printing failed so code() can return the appropriate exit code
             self.print_failed.set(true);
             // Try to display this error to stderr, but ignore if that fails too
@@ -778,6 +953,83 @@ impl Display for ClapErrorWrapper {
 
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn test_exit_code_policies() {
+        use super::{ExitCodePolicy, FailureClass::*};
+
+        // The exit codes GNU documents for each utility and class of failure.
+        let matrix = [
+            (ExitCodePolicy::LS, [2, 2, 1, 2, 2]),
+            (ExitCodePolicy::DU, [1, 1, 1, 1, 1]),
+            (ExitCodePolicy::DF, [1, 1, 1, 1, 1]),
+            (ExitCodePolicy::SORT, [2, 2, 2, 2, 2]),
+        ];
+        for (policy, codes) in matrix {
+            for (class, code) in [Usage, Operand, Nested, Write, Other]
+                .into_iter()
+                .zip(codes)
+            {
+                assert_eq!(policy.code(class), code, "{policy:?} {class:?}");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_classify_io_error() {
+        use super::{ExitCodePolicy, FailureClass, UError, USimpleError};
+        use crate::io::tag_write_error;
+        use std::io::{Error, ErrorKind};
+
+        let policy = ExitCodePolicy::LS;
+        let err: Box<dyn UError> = Error::from(ErrorKind::NotFound).into();
+        assert_eq!(
+            policy.classify_io_error(FailureClass::Nested, err).code(),
+            1
+        );
+        let err: Box<dyn UError> = Error::from(ErrorKind::NotFound).into();
+        assert_eq!(
+            policy.classify_io_error(FailureClass::Operand, err).code(),
+            2
+        );
+
+        let err: Box<dyn UError> = tag_write_error(Error::from_raw_os_error(28)).into();
+        let err = ExitCodePolicy::SORT.classify_io_error(FailureClass::Other, err);
+        assert_eq!(err.code(), 2);
+        assert!(err.to_string().ends_with(": No space left on device"));
+
+        // Errors with a code of their own keep it.
+        let err = policy.classify_io_error(FailureClass::Operand, USimpleError::new(3, "x"));
+        assert_eq!(err.code(), 3);
+    }
+
+    #[test]
+    fn test_clap_error_wrapper_code() {
+        use super::{UClapError, UError};
+        use clap::{Arg, Command};
+
+        let cmd = Command::new("test").arg(Arg::new("when").value_parser(["always", "never"]));
+
+        // Usage errors keep their code, known before anything is printed,
+        let err = cmd
+            .clone()
+            .try_get_matches_from(["test", "1", "2"])
+            .with_exit_code(2)
+            .unwrap_err();
+        assert_eq!(err.code(), 2);
+        assert_eq!(err.to_string(), "");
+        assert_eq!(err.code(), 2);
+
+        // except for invalid values, which exit with 1 like in `handle_clap_result`.
+        let err = cmd
+            .try_get_matches_from(["test", "x"])
+            .with_exit_code(2)
+            .unwrap_err();
+        assert_eq!(err.code(), 1);
+        assert_eq!(err.to_string(), "");
+        assert_eq!(err.code(), 1);
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_nix_error_conversion() {
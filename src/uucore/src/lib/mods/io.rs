@@ -13,14 +13,20 @@
 //!
 //! Even though they are distinct classes, they share common functionality.
 //! Access to this common functionality is provided in `OwnedFileDescriptorOrHandle`.
+//!
+//! [`TagWriteErrors`] tells the errors of a writer apart from those of a
+//! reader once a copy from one to the other has failed.
 
+use crate::display::OsWrite;
 #[cfg(unix)]
 use std::os::fd::{AsFd, OwnedFd};
 #[cfg(windows)]
 use std::os::windows::io::{AsHandle, OwnedHandle};
 use std::{
+    error::Error,
+    fmt,
     fs::{File, OpenOptions},
-    io,
+    io::{self, Write},
     path::Path,
     process::Stdio,
 };
@@ -104,3 +110,94 @@ impl From<OwnedFileDescriptorOrHandle> for Stdio {
         value.into_stdio()
     }
 }
+
+/// A writer whose errors are tagged, so that [`is_write_error`] can tell
+/// them from read errors after e.g. [`io::copy`] fails. Utilities use it to
+/// go on with the next file after a read error but stop once their output
+/// is gone, as GNU does.
+pub struct TagWriteErrors<W>(pub W);
+
+impl<W: Write> Write for TagWriteErrors<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf).map_err(tag_write_error)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush().map_err(tag_write_error)
+    }
+}
+
+impl<W: OsWrite> OsWrite for TagWriteErrors<W> {
+    // The inner writer may write strings that are not valid Unicode itself.
+    #[cfg(windows)]
+    fn write_all_os(&mut self, buf: &std::ffi::OsStr) -> io::Result<()> {
+        self.0.write_all_os(buf).map_err(tag_write_error)
+    }
+}
+
+#[derive(Debug)]
+struct WriteError(io::Error);
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for WriteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Tag `err` as a write error. The kind and the message stay the same.
+pub fn tag_write_error(err: io::Error) -> io::Error {
+    if is_write_error(&err) {
+        return err;
+    }
+    io::Error::new(err.kind(), WriteError(err))
+}
+
+/// Whether `err` was tagged by [`tag_write_error`] or came from a
+/// [`TagWriteErrors`] writer.
+pub fn is_write_error(err: &io::Error) -> bool {
+    matches!(err.get_ref(), Some(e) if e.is::<WriteError>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Failing;
+
+    impl io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::InvalidData.into())
+        }
+    }
+
+    impl Write for Failing {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::StorageFull.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_tag_write_errors() {
+        let err = io::copy(&mut Failing, &mut TagWriteErrors(Vec::new())).unwrap_err();
+        assert!(!is_write_error(&err));
+
+        let err = io::copy(&mut &b"x"[..], &mut TagWriteErrors(Failing)).unwrap_err();
+        assert!(is_write_error(&err));
+        assert_eq!(err.kind(), io::ErrorKind::StorageFull);
+        assert_eq!(
+            err.to_string(),
+            io::Error::from(io::ErrorKind::StorageFull).to_string()
+        );
+        assert!(is_write_error(&tag_write_error(err)));
+    }
+}
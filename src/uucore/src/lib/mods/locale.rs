@@ -553,13 +553,18 @@ macro_rules! translate {
             let mut args = fluent::FluentArgs::new();
             $(
                 let value_str = $value.to_string();
-                if let Ok(num_val) = value_str.parse::<i64>() {
-                    args.set($key, num_val);
-                } else if let Ok(float_val) = value_str.parse::<f64>() {
-                    args.set($key, float_val);
-                } else {
+                // Only pass numbers that are written the way fluent writes
+                // them back, so that values like "+0" or "1.50" are kept as
+                // given.
+                match (value_str.parse::<i64>(), value_str.parse::<f64>()) {
+                    (Ok(num_val), _) if num_val.to_string() == value_str => {
+                        args.set($key, num_val);
+                    }
+                    (_, Ok(float_val)) if float_val.to_string() == value_str => {
+                        args.set($key, float_val);
+                    }
                     // Keep as string if not a number
-                    args.set($key, value_str);
+                    _ => args.set($key, value_str),
                 }
             )+
             $crate::locale::get_message_with_args($id, args)
@@ -1086,6 +1091,21 @@ invalid-syntax = This is { $missing
         .unwrap();
     }
 
+    #[test]
+    fn test_macro_keeps_number_like_text() {
+        std::thread::spawn(|| {
+            let temp_dir = create_test_locales_dir();
+            let locale = LanguageIdentifier::from_str("en-US").unwrap();
+            init_test_localization(&locale, temp_dir.path()).unwrap();
+
+            assert_eq!(translate!("welcome", "name" => "+0"), "Welcome, +0!");
+            assert_eq!(translate!("welcome", "name" => "1.50"), "Welcome, 1.50!");
+            assert_eq!(translate!("count-items", "count" => 1), "You have 1 item");
+        })
+        .join()
+        .unwrap();
+    }
+
     #[test]
     fn test_arabic_localization_with_macro() {
         std::thread::spawn(|| {
@@ -0,0 +1,190 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! How interactive prompts are answered.
+//!
+//! `cp -i`, `mv -i`, `ln -i` and `rm -i` ask before overwriting or removing a
+//! file, through [`prompt_yes!`](crate::prompt_yes). By default the question
+//! is written to standard error and the answer is a line read from standard
+//! input. A host running utilities in-process, such as a WASM shell or a test
+//! harness, may have no terminal to ask, so it hands a [`Responder`] to
+//! [`with_responder`] to decide how prompts on the current thread are
+//! answered.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{Read, Write};
+
+/// How the prompts of a utility are answered.
+#[derive(Default)]
+pub enum Responder {
+    /// Read a line from standard input; it is a yes if it starts with `y`
+    /// or `Y`.
+    #[default]
+    Line,
+    /// Read a single byte from standard input, so that the answers to
+    /// several prompts need no newlines between them. The terminal, if any,
+    /// is left in its usual mode, so this is meant for hosts that provide
+    /// standard input themselves.
+    SingleKey,
+    /// Answer every prompt with yes, without reading any input.
+    Yes,
+    /// Answer every prompt with no, without reading any input.
+    No,
+    /// Let a callback answer. It is given the question without the utility
+    /// name in front, and nothing is written to standard error.
+    Callback(Box<dyn FnMut(&str) -> bool>),
+}
+
+impl fmt::Debug for Responder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Line => f.write_str("Line"),
+            Self::SingleKey => f.write_str("SingleKey"),
+            Self::Yes => f.write_str("Yes"),
+            Self::No => f.write_str("No"),
+            Self::Callback(_) => f.write_str("Callback(..)"),
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Responder> = const { RefCell::new(Responder::Line) };
+}
+
+/// Run `f` with prompts on the current thread answered by `responder`.
+///
+/// The previous responder is restored when `f` returns, even on panic.
+pub fn with_responder<F, R>(responder: Responder, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct RestoreGuard(Option<Responder>);
+    impl Drop for RestoreGuard {
+        fn drop(&mut self) {
+            if let Some(previous) = self.0.take() {
+                CURRENT.with(|cell| *cell.borrow_mut() = previous);
+            }
+        }
+    }
+
+    let previous = CURRENT.with(|cell| cell.replace(responder));
+    let _guard = RestoreGuard(Some(previous));
+    f()
+}
+
+/// Ask `question` and return whether the answer is yes.
+///
+/// Unless a [`Responder::Callback`] answers, the question is written to
+/// standard error after the utility name and followed by a space. This is
+/// what [`prompt_yes!`](crate::prompt_yes) expands to.
+pub fn ask(question: fmt::Arguments<'_>) -> bool {
+    // Take the responder out while it runs, so that a callback that asks
+    // something itself goes through the default responder rather than
+    // borrowing this one twice.
+    let mut responder = CURRENT.with(RefCell::take);
+    let answer = match &mut responder {
+        Responder::Callback(callback) => callback(&question.to_string()),
+        Responder::Yes => {
+            write_question(question);
+            true
+        }
+        Responder::No => {
+            write_question(question);
+            false
+        }
+        Responder::Line => {
+            write_question(question);
+            read_line_answer()
+        }
+        Responder::SingleKey => {
+            write_question(question);
+            read_key_answer()
+        }
+    };
+    CURRENT.with(|cell| *cell.borrow_mut() = responder);
+    answer
+}
+
+fn write_question(question: fmt::Arguments<'_>) {
+    #[cfg(target_family = "wasm")]
+    let mut stderr = crate::wasm_io::stderr();
+    #[cfg(not(target_family = "wasm"))]
+    let mut stderr = std::io::stderr().lock();
+    let result =
+        write!(stderr, "{}: {question} ", crate::util_name()).and_then(|()| stderr.flush());
+    drop(stderr);
+    crate::show_if_err!(result.map_err(|err| crate::error::USimpleError::new(1, err.to_string())));
+}
+
+/// Read a line from standard input and check whether it starts with `y` or
+/// `Y`.
+fn read_line_answer() -> bool {
+    #[cfg(not(target_family = "wasm"))]
+    {
+        crate::read_yes()
+    }
+    // The host's stdin has no shared buffer as `std::io::stdin()` has, so
+    // read byte by byte to leave the input after the line for the next prompt.
+    #[cfg(target_family = "wasm")]
+    {
+        let first = read_byte();
+        let mut byte = first;
+        while byte.is_some_and(|b| b != b'\n') {
+            byte = read_byte();
+        }
+        matches!(first, Some(b'y' | b'Y'))
+    }
+}
+
+fn read_key_answer() -> bool {
+    matches!(read_byte(), Some(b'y' | b'Y'))
+}
+
+fn read_byte() -> Option<u8> {
+    #[cfg(target_family = "wasm")]
+    let mut stdin = crate::wasm_io::stdin();
+    #[cfg(not(target_family = "wasm"))]
+    let mut stdin = std::io::stdin().lock();
+    let mut byte = [0];
+    match stdin.read(&mut byte) {
+        Ok(1) => Some(byte[0]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_answers() {
+        with_responder(Responder::Yes, || assert!(ask(format_args!("q?"))));
+        with_responder(Responder::No, || assert!(!ask(format_args!("q?"))));
+    }
+
+    #[test]
+    fn test_callback_sees_question() {
+        let answers = with_responder(
+            Responder::Callback(Box::new(|question| question.ends_with("'a'?"))),
+            || {
+                [
+                    ask(format_args!("remove {}?", "'a'")),
+                    ask(format_args!("remove 'b'?")),
+                ]
+            },
+        );
+        assert_eq!(answers, [true, false]);
+    }
+
+    #[test]
+    fn test_nested_responders_are_restored() {
+        with_responder(Responder::Yes, || {
+            with_responder(Responder::No, || assert!(!ask(format_args!("q?"))));
+            assert!(ask(format_args!("q?")));
+        });
+        CURRENT.with(|cell| assert!(matches!(*cell.borrow(), Responder::Line)));
+    }
+}
@@ -0,0 +1,81 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! `--version` output shared by all the utilities.
+//!
+//! Every utility prints the same version line, e.g. `ls (uutils coreutils) 0.6.0`.
+//! When [`DETAILS_ENV`] is set to a non-empty value, the line is followed by a blank
+//! line and a machine-readable block of `key=value` lines describing the build:
+//!
+//! ```text
+//! version=0.6.0
+//! target=x86_64-unknown-linux-gnu
+//! features=fs,i18n-common,signals
+//! ```
+//!
+//! The features are the cargo features uucore was built with, in the spelling cargo
+//! gives them to build scripts, i.e. lowercase with `-` for `_`.
+
+// spell-checker:ignore UUCORE
+
+use std::io::{self, Write};
+
+use clap::Command;
+
+/// The environment variable that requests the build details after the version line
+pub const DETAILS_ENV: &str = "UUTILS_VERSION_DETAILS";
+
+/// The target triple uucore was built for
+pub const TARGET: &str = env!("UUCORE_TARGET");
+
+/// The cargo features uucore was built with, sorted and comma separated
+pub const FEATURES: &str = env!("UUCORE_FEATURES");
+
+/// Whether the build details were requested with [`DETAILS_ENV`]
+pub fn details_requested() -> bool {
+    std::env::var_os(DETAILS_ENV).is_some_and(|value| !value.is_empty())
+}
+
+/// The machine-readable build details, starting with a blank line
+pub fn details() -> String {
+    format!(
+        "\nversion={}\ntarget={TARGET}\nfeatures={FEATURES}\n",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// The `--version` output of `cmd`, followed by the build details if they were requested
+pub fn render(cmd: &Command) -> String {
+    let mut text = cmd.render_version();
+    if details_requested() {
+        text.push_str(&details());
+    }
+    text
+}
+
+/// Write the build details to stdout if they were requested, after a version line
+/// that has been printed already
+pub fn print_requested_details() -> io::Result<()> {
+    if details_requested() {
+        let mut stdout = io::stdout();
+        stdout.write_all(details().as_bytes())?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_details() {
+        let details = details();
+        assert!(details.starts_with("\nversion="));
+        assert!(details.contains(&format!("\ntarget={TARGET}\n")));
+        assert!(details.ends_with(&format!("features={FEATURES}\n")));
+        assert!(FEATURES.split(',').is_sorted());
+    }
+}
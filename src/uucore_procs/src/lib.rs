@@ -21,7 +21,7 @@ use quote::quote;
 /// - SIGPIPE state capture at process startup (before Rust runtime overrides it)
 /// - SIGPIPE restoration to default if parent didn't explicitly ignore it
 /// - Disabling Rust signal handlers for proper core dumps
-/// - Error handling and exit code management
+/// - Error handling and exit code management, without ever exiting the process
 #[proc_macro_attribute]
 pub fn main(_args: TokenStream, stream: TokenStream) -> TokenStream {
     let stream = proc_macro2::TokenStream::from(stream);
@@ -47,6 +47,9 @@ pub fn main(_args: TokenStream, stream: TokenStream) -> TokenStream {
             // disable rust signal handlers (otherwise processes don't dump core after e.g. one SIGSEGV)
             #[cfg(unix)]
             uucore::disable_rust_signal_handlers().expect("Disabling rust signal handlers failed");
+
+            // Start from a clean exit code, a host may call uumain several times in one process.
+            uucore::error::set_exit_code(0);
             let result = uumain(args);
             match result {
                 Ok(()) => uucore::error::get_exit_code(),
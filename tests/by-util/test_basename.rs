@@ -31,6 +31,28 @@ fn test_version() {
     }
 }
 
+#[test]
+fn test_version_details() {
+    let out = new_ucmd!()
+        .arg("--version")
+        .env("UUTILS_VERSION_DETAILS", "1")
+        .succeeds();
+    let lines: Vec<&str> = out.stdout_str().lines().collect();
+    assert!(lines[0].starts_with("basename (uutils coreutils) "));
+    assert_eq!(lines[1], "");
+    assert_eq!(lines[2], format!("version={}", env!("CARGO_PKG_VERSION")));
+    assert!(lines[3].starts_with("target="));
+    assert!(lines[4].starts_with("features="));
+    assert_eq!(lines.len(), 5);
+
+    // An empty value does not ask for the details
+    new_ucmd!()
+        .arg("--version")
+        .env("UUTILS_VERSION_DETAILS", "")
+        .succeeds()
+        .stdout_does_not_contain("version=");
+}
+
 #[test]
 fn test_directory() {
     new_ucmd!()
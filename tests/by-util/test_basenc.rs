@@ -3,7 +3,7 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
-// spell-checker: ignore (encodings) lsbf msbf
+// spell-checker: ignore (encodings) lsbf msbf uuencode
 // spell-checker: ignore autopad MFRGG MFRGGZDF abcdeabc baddecode CPNMUO
 
 use uutests::{at_and_ucmd, new_ucmd};
@@ -294,6 +294,81 @@ fn test_base58_large_file_no_chunking() {
     // spell-checker:enable
 }
 
+#[test]
+fn test_uuencode() {
+    new_ucmd!()
+        .arg("--uuencode")
+        .pipe_in("hello world\n")
+        .succeeds()
+        .stdout_only("begin 644 -\n,:&5L;&\\@=V]R;&0*\n`\nend\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_uuencode_file_mode_and_name() {
+    use std::fs::{Permissions, set_permissions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("dir");
+    at.write("dir/file", "");
+    set_permissions(at.plus("dir/file"), Permissions::from_mode(0o640)).unwrap();
+    ucmd.args(&["--uuencode-base64", "dir/file"])
+        .succeeds()
+        .stdout_only("begin-base64 640 file\n====\n");
+}
+
+#[test]
+fn test_uuencode_base64() {
+    // Lines hold 57 bytes, i.e. 76 characters.
+    new_ucmd!()
+        .arg("--uuencode-base64")
+        .pipe_in("a".repeat(60))
+        .succeeds()
+        .stdout_only(format!(
+            "begin-base64 644 -\n{}\nYWFh\n====\n",
+            "YWFh".repeat(19)
+        ));
+}
+
+#[test]
+fn test_uuencode_decode_either_format() {
+    // Lines before the header are skipped, and the name is ignored.
+    for encoding in ["--uuencode", "--uuencode-base64"] {
+        new_ucmd!()
+            .args(&[encoding, "-d"])
+            .pipe_in("From: someone\nbegin 600 x\n,:&5L;&\\@=V]R;&0*\n`\nend\n")
+            .succeeds()
+            .stdout_only("hello world\n");
+        new_ucmd!()
+            .args(&[encoding, "-d"])
+            .pipe_in("begin-base64 600 x\r\naGVsbG8g\r\nd29ybGQK\r\n====\r\n")
+            .succeeds()
+            .stdout_only("hello world\n");
+    }
+}
+
+#[test]
+fn test_uuencode_decode_invalid() {
+    for input in [
+        "hello\n",
+        "begin 644 x\n,:&5L\n",
+        "begin-base64 644 x\naGVs\n",
+    ] {
+        new_ucmd!()
+            .args(&["--uuencode", "-d"])
+            .pipe_in(input)
+            .fails_with_code(1)
+            .stderr_is("basenc: error: invalid input\n");
+    }
+    // What was decoded before the missing trailer is kept.
+    new_ucmd!()
+        .args(&["--uuencode", "-d"])
+        .pipe_in("begin 644 x\n#:&5L\n")
+        .fails()
+        .stdout_is("hel");
+}
+
 #[test]
 fn test_choose_last_encoding_base64() {
     new_ucmd!()
@@ -186,6 +186,43 @@ fn test_piped_to_regular_file() {
     }
 }
 
+#[test]
+#[cfg(unix)]
+fn test_file_to_regular_file() {
+    use std::fs::read;
+
+    // Files copied straight into a file, in the kernel on Linux, as well as
+    // a /proc file that reports no size and an output opened for appending.
+    let big: Vec<u8> = (0..=255).cycle().take(3 << 20).collect();
+    for append in [true, false] {
+        let s = TestScenario::new(util_name!());
+        s.fixtures.write_bytes("big", &big);
+        s.fixtures.touch("empty");
+        s.fixtures.write("out", "head\n");
+        let mut args = vec!["big", "empty", "alpha.txt", "big"];
+        if cfg!(target_os = "linux") {
+            args.push("/proc/self/stat");
+        }
+
+        let out = OpenOptions::new()
+            .write(true)
+            .append(append)
+            .open(s.fixtures.plus("out"))
+            .unwrap();
+        s.ucmd().args(&args).set_stdout(out).succeeds();
+
+        let contents = read(s.fixtures.plus("out")).unwrap();
+        let mut expected = if append { b"head\n".to_vec() } else { vec![] };
+        expected.extend_from_slice(&big);
+        expected.extend_from_slice(b"abcde\nfghij\nklmno\npqrst\nuvwxyz\n"); // spell-checker:disable-line
+        expected.extend_from_slice(&big);
+        assert_eq!(contents[..expected.len()], expected[..]);
+        if cfg!(target_os = "linux") {
+            assert!(contents.len() > expected.len());
+        }
+    }
+}
+
 #[test]
 #[cfg(unix)]
 fn test_piped_to_dev_null() {
@@ -455,6 +492,35 @@ fn test_stdin_nonprinting_and_tabs_repeated() {
         .stdout_only("^I^@\n");
 }
 
+#[test]
+fn test_show_all_aliases() {
+    // -A is -vET, -e is -vE and -t is -vT, so they combine into each other.
+    for args in [
+        &["-A"][..],
+        &["-vET"],
+        &["-et"],
+        &["-e", "-T"],
+        &["-t", "-E"],
+        &["-Av"],
+    ] {
+        new_ucmd!()
+            .args(args)
+            .pipe_in(b"\ta\x89\r\n\xa0\xff\r\r\n".as_slice())
+            .succeeds()
+            .stdout_only("^IaM-^I^M$\nM- M-^?^M^M$\n");
+    }
+}
+
+#[test]
+fn test_show_ends_and_tabs_high_bit() {
+    // Without -v, bytes with the high bit set are written as they are.
+    new_ucmd!()
+        .args(&["-ET"])
+        .pipe_in(b"\t\x89\xff\r\n".as_slice())
+        .succeeds()
+        .stdout_only_bytes(b"^I\x89\xff^M$\n");
+}
+
 #[test]
 fn test_stdin_tabs_no_newline() {
     new_ucmd!()
@@ -520,6 +586,21 @@ fn test_squeeze_blank_before_numbering() {
     }
 }
 
+#[test]
+fn test_squeeze_blank_line_ending_at_buffer_end() {
+    // The line ends exactly at the end of a read, so the newline after it is
+    // read with the blank line that follows.
+    let mut input = "a".repeat(31 * 1024);
+    input.push_str("\n\n\nb\n");
+    let mut expected = "a".repeat(31 * 1024);
+    expected.push_str("\n\nb\n");
+    new_ucmd!()
+        .arg("-s")
+        .pipe_in(input)
+        .succeeds()
+        .stdout_only(expected);
+}
+
 /// This tests reading from Unix character devices
 #[test]
 #[cfg(unix)]
@@ -630,6 +711,19 @@ fn test_write_to_self_empty() {
     s.ucmd().set_stdout(file).arg(&file_path).succeeds();
 }
 
+#[test]
+#[cfg(unix)]
+fn test_error_order_with_stderr_to_stdout() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "a\n");
+    at.write("b", "b\n");
+    at.mkdir("d");
+    ucmd.args(&["a", "missing", "d", "b"])
+        .stderr_to_stdout()
+        .fails_with_code(1)
+        .stdout_only("a\ncat: missing: No such file or directory\ncat: d: Is a directory\nb\n");
+}
+
 #[test]
 fn test_write_to_self() {
     let s = TestScenario::new(util_name!());
@@ -649,7 +743,7 @@ fn test_write_to_self() {
         .arg("first_file")
         .arg("first_file")
         .arg("second_file")
-        .fails_with_code(2)
+        .fails_with_code(1)
         .stderr_only("cat: first_file: input file is output file\ncat: first_file: input file is output file\n");
 
     assert_eq!(
@@ -777,6 +871,21 @@ fn test_cat_non_utf8_paths() {
     assert_eq!(output, "Hello, non-UTF-8 world!\n");
 }
 
+#[test]
+fn test_invalid_utf8_content_is_unchanged() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write_bytes("bin", b"a\xFF\xE2\x82\n\xE2\x82\xAC\n");
+    ucmd.arg("bin")
+        .succeeds()
+        .stdout_only_bytes(b"a\xFF\xE2\x82\n\xE2\x82\xAC\n");
+
+    new_ucmd!()
+        .arg("-n")
+        .pipe_in(&b"\xC3\n\xFE"[..])
+        .succeeds()
+        .stdout_only_bytes(b"     1\t\xC3\n     2\t\xFE");
+}
+
 #[test]
 #[cfg(target_os = "linux")]
 fn test_appending_same_input_output() {
@@ -796,6 +905,26 @@ fn test_appending_same_input_output() {
         .stderr_contains("input file is output file");
 }
 
+/// `cat foo >> foo`
+#[test]
+#[cfg(unix)]
+fn test_appending_file_operand_to_itself() {
+    let (at, mut ucmd) = at_and_ucmd!();
+
+    at.write("foo", "content");
+    let file_write = OpenOptions::new()
+        .append(true)
+        .open(at.plus("foo"))
+        .unwrap();
+
+    ucmd.arg("foo")
+        .set_stdout(file_write)
+        .fails_with_code(1)
+        .stderr_only("cat: foo: input file is output file\n");
+
+    assert_eq!(at.read("foo"), "content");
+}
+
 #[cfg(unix)]
 #[test]
 fn test_uchild_when_no_capture_reading_from_infinite_source() {
@@ -861,7 +990,24 @@ fn test_write_error_handling() {
         .set_stdout(dev_full)
         .fails()
         .code_is(1)
-        .stderr_contains("No space left on device");
+        .stderr_is("cat: write error: No space left on device\n");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_write_error_stops_processing() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.write("a", "first\n");
+
+    // the error names no file, and cat gives up instead of reporting "missing"
+    for args in [&["a", "missing"][..], &["-n", "a", "missing"][..]] {
+        scene
+            .ucmd()
+            .args(args)
+            .set_stdout(File::create("/dev/full").unwrap())
+            .fails_with_code(1)
+            .stderr_is("cat: write error: No space left on device\n");
+    }
 }
 
 #[test]
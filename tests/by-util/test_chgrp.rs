@@ -641,6 +641,49 @@ fn test_chgrp_recursive_on_file() {
     );
 }
 
+#[test]
+fn test_chgrp_recursive_skips_files_with_matching_group() {
+    use std::os::unix::fs::MetadataExt;
+    let (at, mut ucmd) = at_and_ucmd!();
+
+    at.mkdir_all("dir/sub");
+    at.touch("dir/file");
+    at.touch("dir/sub/file");
+    let current_gid = getegid();
+    if at.plus("dir/sub/file").metadata().unwrap().gid() != current_gid {
+        // e.g. a set-group-ID temporary directory
+        return;
+    }
+
+    let paths = ["dir", "dir/file", "dir/sub", "dir/sub/file"];
+    let ctimes = |at: &uutests::util::AtPath| {
+        paths.map(|p| {
+            let meta = at.plus(p).metadata().unwrap();
+            (meta.ctime(), meta.ctime_nsec())
+        })
+    };
+    let before = ctimes(&at);
+    // give a change of ownership time to show in the ctime
+    std::thread::sleep(std::time::Duration::from_millis(50));
+
+    ucmd.arg("-R")
+        .arg("-c")
+        .arg(current_gid.to_string())
+        .arg("dir")
+        .succeeds()
+        .no_output();
+
+    assert_eq!(ctimes(&at), before);
+
+    new_ucmd!()
+        .arg("-R")
+        .arg("-v")
+        .arg(current_gid.to_string())
+        .arg(at.plus("dir"))
+        .succeeds()
+        .stderr_contains("file' retained as");
+}
+
 #[test]
 fn test_chgrp_exit_code_not_being_overwritten_by_last_file() {
     use std::os::unix::prelude::PermissionsExt;
@@ -1054,6 +1054,47 @@ fn test_chmod_dangling_symlink_recursive_combos() {
     }
 }
 
+#[test]
+fn test_chmod_dangling_symlink_during_traversal() {
+    // Only -L follows the symlink, so only -L notices it dangles
+    let scenarios = [
+        (vec!["-R"], false),
+        (vec!["-R", "-H"], false),
+        (vec!["-R", "-L"], true),
+        (vec!["-R", "-P"], false),
+    ];
+
+    for (flags, reports_dangling) in scenarios {
+        let scene = TestScenario::new(util_name!());
+        let at = &scene.fixtures;
+
+        at.mkdir("dir");
+        at.symlink_file("nonexistent_file", "dir/dangling");
+        at.touch("dir/file");
+        set_permissions(at.plus("dir/file"), Permissions::from_mode(0o644)).unwrap();
+
+        let result = scene.ucmd().args(&flags).arg("u+x").arg("dir").run();
+        if reports_dangling {
+            result
+                .failure()
+                .code_is(1)
+                .stderr_is("chmod: cannot operate on dangling symlink 'dir/dangling'\n");
+            scene
+                .ucmd()
+                .args(&flags)
+                .arg("-f")
+                .arg("u+x")
+                .arg("dir")
+                .fails_with_code(1)
+                .no_output();
+        } else {
+            result.success().no_output();
+        }
+        // The rest of the directory is processed either way
+        assert_eq!(at.metadata("dir/file").permissions().mode(), 0o100_744);
+    }
+}
+
 #[test]
 fn test_chmod_traverse_symlink_combo() {
     let scenarios = [
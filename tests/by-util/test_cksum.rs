@@ -1071,6 +1071,38 @@ mod output_format {
             .stderr_contains("--text mode is only supported with --untagged");
     }
 
+    #[test]
+    fn test_short_text_no_untagged() {
+        let (at, mut ucmd) = at_and_ucmd!();
+        at.touch("f");
+
+        ucmd.arg("-t")
+            .args(&["-a", "md5"])
+            .arg(at.subdir.join("f"))
+            .fails_with_code(1)
+            .stderr_contains("--text mode is only supported with --untagged");
+    }
+
+    #[test]
+    fn test_abbreviated_options() {
+        let (at, mut ucmd) = at_and_ucmd!();
+        at.touch("f");
+
+        ucmd.args(&["--unt", "--bin", "-a", "md5", "f"])
+            .succeeds()
+            .stdout_only("d41d8cd98f00b204e9800998ecf8427e *f\n");
+    }
+
+    #[test]
+    fn test_options_after_double_dash_are_files() {
+        let (at, mut ucmd) = at_and_ucmd!();
+        at.touch("--untagged");
+
+        ucmd.args(&["-a", "md5", "--", "--untagged"])
+            .succeeds()
+            .stdout_only("MD5 (--untagged) = d41d8cd98f00b204e9800998ecf8427e\n");
+    }
+
     #[test]
     fn test_text_binary() {
         let (at, mut ucmd) = at_and_ucmd!();
@@ -376,6 +376,27 @@ fn defaultcheck_order_identical_bad_order_files() {
         .stderr_is("comm: file 1 is not in sorted order\n");
 }
 
+#[test]
+fn test_disorder_reported_after_preceding_lines() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write("a", "a\nc\nb\n");
+    at.write("b", "b\nc\nd\n");
+    // stdout and stderr share a file, like with `2>&1`
+    let out = std::fs::File::create(at.plus("out")).unwrap();
+    scene
+        .ucmd()
+        .args(&["--total", "a", "b"])
+        .set_stdout(out.try_clone().unwrap())
+        .set_stderr(out)
+        .fails_with_code(1);
+    assert_eq!(
+        at.read("out"),
+        "a\n\tb\n\t\tc\ncomm: file 1 is not in sorted order\nb\n\td\n\
+         2\t2\t1\ttotal\ncomm: input is not in sorted order\n"
+    );
+}
+
 // * the third: (it is not know whether this is a bug or not)
 // for the first incident, and only the first incident,
 // where both lines are different and one or both file lines being
@@ -611,6 +632,31 @@ fn comm_emoji_sorted_inputs() {
         .stdout_only("💐\n\t\t🦀\n\t🪽\n");
 }
 
+#[cfg(unix)]
+#[test]
+fn test_locale_collation() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+
+    at.write("file1", "a\nB\n");
+    at.write("file2", "A\nb\n");
+
+    scene
+        .ucmd()
+        .args(&["--check-order", "file1", "file2"])
+        .env("LC_ALL", "en_US.UTF-8")
+        .succeeds()
+        .stdout_only("a\n\tA\n\tb\nB\n");
+
+    scene
+        .ucmd()
+        .args(&["--check-order", "file1", "file2"])
+        .env("LC_ALL", "C")
+        .fails_with_code(1)
+        .stdout_is("\tA\na\n")
+        .stderr_is("comm: file 1 is not in sorted order\n");
+}
+
 #[test]
 fn test_comm_eintr_handling() {
     // Test that comm properly handles EINTR (ErrorKind::Interrupted) during file comparison
@@ -110,6 +110,57 @@ fn test_cp_cp() {
     assert_eq!(at.read(TEST_HELLO_WORLD_DEST), "Hello, World!\n");
 }
 
+#[test]
+fn test_cp_progress_not_a_terminal() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.arg("--progress")
+        .arg(TEST_HELLO_WORLD_SOURCE)
+        .arg(TEST_HELLO_WORLD_DEST)
+        .succeeds()
+        .no_output();
+
+    assert_eq!(at.read(TEST_HELLO_WORLD_DEST), "Hello, World!\n");
+}
+
+#[test]
+fn test_cp_sync() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("dir");
+    at.write("dir/file", "content");
+    at.symlink_file("file", "dir/link");
+    ucmd.args(&[
+        "--sync",
+        "-r",
+        "dir",
+        TEST_HELLO_WORLD_SOURCE,
+        TEST_COPY_TO_FOLDER,
+    ])
+    .succeeds()
+    .no_output();
+
+    assert_eq!(
+        at.read(&format!("{TEST_COPY_TO_FOLDER}dir/file")),
+        "content"
+    );
+    assert!(at.is_symlink(&format!("{TEST_COPY_TO_FOLDER}dir/link")));
+    assert_eq!(
+        at.read(&format!("{TEST_COPY_TO_FOLDER}{TEST_HELLO_WORLD_SOURCE}")),
+        "Hello, World!\n"
+    );
+}
+
+#[test]
+fn test_cp_sync_from_env() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.env("UUTILS_SYNC", "1")
+        .arg(TEST_HELLO_WORLD_SOURCE)
+        .arg(TEST_HELLO_WORLD_DEST)
+        .succeeds()
+        .no_output();
+
+    assert_eq!(at.read(TEST_HELLO_WORLD_DEST), "Hello, World!\n");
+}
+
 #[test]
 fn test_cp_existing_target() {
     let (at, mut ucmd) = at_and_ucmd!();
@@ -336,6 +387,32 @@ fn test_cp_recurse_several() {
     assert_eq!(at.read(TEST_COPY_TO_FOLDER_NEW_FILE), "Hello, World!\n");
 }
 
+#[test]
+#[cfg(unix)]
+fn test_cp_recurse_many_small_files() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("src/a/b");
+    for i in 0..50 {
+        at.write(&format!("src/a/b/{i}"), &i.to_string().repeat(i));
+    }
+    // Larger than a small file, so it takes the general path.
+    at.write("src/big", &"x".repeat(512 * 1024));
+    at.set_mode("src/a/b/7", 0o640);
+
+    ucmd.arg("-r").arg("src").arg("dest").succeeds();
+
+    for i in 0..50 {
+        assert_eq!(at.read(&format!("dest/a/b/{i}")), i.to_string().repeat(i));
+    }
+    assert_eq!(at.read("dest/big").len(), 512 * 1024);
+    assert_eq!(
+        at.metadata("dest/a/b/7").permissions().mode() & 0o777,
+        0o640
+    );
+}
+
 #[test]
 fn test_cp_recurse_source_path_ends_with_slash_dot() {
     let source_dir = "source_dir";
@@ -2528,7 +2605,10 @@ fn test_cp_reflink_always() {
         // Check the content of the destination file
         assert_eq!(at.read(TEST_EXISTING_FILE), "Hello, World!\n");
     } else {
-        // Older Linux versions do not support cloning.
+        // Older Linux versions and some file systems do not support cloning.
+        result.stderr_contains(format!(
+            "failed to clone '{TEST_EXISTING_FILE}' from '{TEST_HELLO_WORLD_SOURCE}'"
+        ));
     }
 }
 
@@ -2751,7 +2831,9 @@ fn test_cp_sparse_never_reflink_always() {
         "src_file1",
         "dst_file",
     ])
-    .fails();
+    .fails()
+    .stderr_contains("--reflink can be used only with --sparse=auto");
+    assert!(!at.file_exists("dst_file"));
 }
 
 #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -4265,6 +4347,53 @@ fn test_cp_attributes_only() {
     assert_eq!(mode_b, at.metadata(b).mode());
 }
 
+#[test]
+#[cfg(all(unix, not(target_os = "freebsd"), not(target_os = "openbsd")))]
+fn test_cp_attributes_only_preserve_subset() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let a = "file_a";
+    let b = "file_b";
+
+    at.write(a, "a");
+    at.write(b, "bbb");
+    at.set_mode(a, 0o0640);
+    at.set_mode(b, 0o0777);
+    let ts = time::OffsetDateTime::now_utc();
+    let previous = FileTime::from_unix_time(ts.unix_timestamp() - 3600, ts.nanosecond());
+    filetime::set_file_times(at.plus_as_string(a), previous, previous).unwrap();
+    let mtime_b = at.metadata(b).modified().unwrap();
+
+    // only the mode is copied: the data and the timestamps of the destination are kept
+    ucmd.args(&[
+        "--attributes-only",
+        "--preserve=mode,timestamps",
+        "--no-preserve=timestamps",
+        a,
+        b,
+    ])
+    .succeeds()
+    .no_output();
+
+    assert_eq!("bbb", at.read(b));
+    assert_eq!(at.metadata(a).mode(), at.metadata(b).mode());
+    assert_eq!(mtime_b, at.metadata(b).modified().unwrap());
+}
+
+#[test]
+#[cfg(unix)]
+fn test_cp_attributes_only_creates_empty_file() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "content");
+    at.set_mode("a", 0o0600);
+
+    ucmd.args(&["--attributes-only", "--preserve=mode", "a", "b"])
+        .succeeds()
+        .no_output();
+
+    assert_eq!("", at.read("b"));
+    assert_eq!(at.metadata("a").mode(), at.metadata("b").mode());
+}
+
 #[test]
 fn test_cp_seen_file() {
     let ts = TestScenario::new(util_name!());
@@ -4461,7 +4590,7 @@ fn test_cp_debug_default_less_than_512_bytes() {
         .arg("a")
         .arg("b")
         .succeeds()
-        .stdout_contains("copy offload: yes, reflink: unsupported, sparse detection: no");
+        .stdout_contains("copy offload: no, reflink: unsupported, sparse detection: no");
 }
 
 #[test]
@@ -4481,7 +4610,7 @@ fn test_cp_debug_default_without_hole() {
         .arg("a")
         .arg("b")
         .succeeds()
-        .stdout_contains("copy offload: yes, reflink: unsupported, sparse detection: no");
+        .stdout_contains("copy offload: no, reflink: unsupported, sparse detection: no");
 }
 
 #[test]
@@ -4718,7 +4847,7 @@ fn test_cp_debug_reflink_never_less_than_512_bytes() {
         .arg("a")
         .arg("b")
         .succeeds()
-        .stdout_contains("copy offload: avoided, reflink: no, sparse detection: no");
+        .stdout_contains("copy offload: no, reflink: no, sparse detection: no");
 }
 
 #[test]
@@ -4786,7 +4915,7 @@ fn test_cp_debug_sparse_never_less_than_512_bytes() {
         .arg("a")
         .arg("b")
         .succeeds()
-        .stdout_contains("copy offload: avoided, reflink: no, sparse detection: no");
+        .stdout_contains("copy offload: no, reflink: unsupported, sparse detection: no");
 }
 
 #[test]
@@ -4808,7 +4937,7 @@ fn test_cp_debug_sparse_never_without_hole() {
         .arg("a")
         .arg("b")
         .succeeds()
-        .stdout_contains("copy offload: avoided, reflink: no, sparse detection: no");
+        .stdout_contains("copy offload: no, reflink: unsupported, sparse detection: no");
 }
 
 #[test]
@@ -4919,7 +5048,7 @@ fn test_cp_debug_reflink_never_without_hole() {
         .arg("a")
         .arg("b")
         .succeeds()
-        .stdout_contains("copy offload: avoided, reflink: no, sparse detection: no");
+        .stdout_contains("copy offload: no, reflink: no, sparse detection: no");
 }
 
 #[test]
@@ -140,6 +140,28 @@ fn test_stdin_stdout_count() {
         .stdout_only(output);
 }
 
+#[test]
+fn test_sync_from_env() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.env("UUTILS_SYNC", "1")
+        .args(&["of=out", "status=none"])
+        .pipe_in("data")
+        .succeeds()
+        .no_output();
+    assert_eq!(at.read("out"), "data");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_sync_from_env_to_device() {
+    new_ucmd!()
+        .env("UUTILS_SYNC", "1")
+        .args(&["of=/dev/null", "status=none"])
+        .pipe_in("data")
+        .succeeds()
+        .no_output();
+}
+
 #[test]
 fn test_stdin_stdout_count_bytes() {
     let input = build_ascii_block(521);
@@ -724,6 +746,25 @@ fn test_seek_do_not_overwrite() {
     assert_eq!(at.read("outfile"), "a2");
 }
 
+#[test]
+fn test_excl_nocreat_conflict() {
+    new_ucmd!()
+        .args(&["conv=excl,nocreat", "of=outfile"])
+        .fails_with_code(1)
+        .stderr_only("dd: cannot combine excl and nocreat\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_seek_dev_null_is_not_truncated() {
+    new_ucmd!()
+        .args(&["bs=1", "seek=10", "status=noxfer", "of=/dev/null"])
+        .pipe_in("abc")
+        .succeeds()
+        .stderr_is("3+0 records in\n3+0 records out\n")
+        .no_stdout();
+}
+
 #[test]
 fn test_partial_records_out() {
     new_ucmd!()
@@ -1492,6 +1533,32 @@ fn test_sparse() {
     assert_eq!(at.metadata("infile").len(), at.metadata("outfile").len());
 }
 
+/// Test that with `conv=notrunc` a sparse copy still extends the output
+/// past the blocks it skipped over.
+#[test]
+fn test_sparse_notrunc_extends_output() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let mut data = vec![b'x'; 512];
+    data.resize(2048, 0);
+    at.write_bytes("infile", &data);
+    at.write("outfile", "abcdefghijklmnopqrstuvwxyz");
+
+    ucmd.args(&[
+        "bs=512",
+        "if=infile",
+        "of=outfile",
+        "conv=sparse,notrunc",
+        "status=none",
+    ])
+    .succeeds()
+    .no_output();
+
+    let output = at.read_bytes("outfile");
+    assert_eq!(output.len(), 2048);
+    assert_eq!(&output[..512], &data[..512]);
+    assert!(output[512..].iter().all(|&b| b == 0));
+}
+
 /// Test that a seek on an output FIFO results in a read.
 #[test]
 #[cfg(unix)]
@@ -1887,6 +1954,25 @@ fn test_oflag_direct_partial_block() {
     at.remove(output_file);
 }
 
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_iflag_direct() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let data: Vec<u8> = (0..3 * 4096 + 100).map(|i| (i % 251) as u8).collect();
+    at.write_bytes("infile", &data);
+
+    ucmd.args(&[
+        "if=infile",
+        "of=outfile",
+        "iflag=direct",
+        "bs=4096",
+        "status=none",
+    ])
+    .succeeds()
+    .no_output();
+    assert_eq!(at.read_bytes("outfile"), data);
+}
+
 #[test]
 fn test_skip_overflow() {
     new_ucmd!()
@@ -1945,3 +2031,63 @@ fn test_nocache_eof_fadvise_zero_length() {
         "Expected len=0 at EOF: {strace}"
     );
 }
+
+/// Test that the status signal prints the transfer stats mid-copy.
+#[test]
+#[cfg(unix)]
+fn test_status_signal_prints_stats() {
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkfifo("fifo");
+    let child = ucmd.args(&["if=fifo", "of=/dev/null"]).run_no_wait();
+    {
+        let mut fifo = OpenOptions::new()
+            .write(true)
+            .open(at.plus("fifo"))
+            .unwrap();
+        fifo.write_all(&[0; 512]).unwrap();
+        sleep(Duration::from_millis(200));
+        kill(
+            Pid::from_raw(child.id().try_into().unwrap()),
+            uucore::signals::STATUS_SIGNAL,
+        )
+        .unwrap();
+        sleep(Duration::from_millis(100));
+        fifo.write_all(&[0; 512]).unwrap();
+    }
+    let result = child.wait().unwrap();
+    result.success();
+    let stderr = result.stderr_str();
+    assert_eq!(stderr.matches("records in\n").count(), 2, "{stderr}");
+    assert_eq!(stderr.matches(" copied, ").count(), 2, "{stderr}");
+}
+
+/// Test that `SIGUSR1` terminates dd under POSIXLY_CORRECT, like GNU.
+#[test]
+#[cfg(target_os = "linux")]
+fn test_sigusr1_posixly_correct() {
+    use nix::sys::signal::{Signal, kill};
+    use nix::unistd::Pid;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkfifo("fifo");
+    let mut child = ucmd
+        .env("POSIXLY_CORRECT", "1")
+        .args(&["if=fifo", "of=/dev/null"])
+        .run_no_wait();
+    let mut fifo = OpenOptions::new()
+        .write(true)
+        .open(at.plus("fifo"))
+        .unwrap();
+    fifo.write_all(&[0; 512]).unwrap();
+    sleep(Duration::from_millis(200));
+    kill(
+        Pid::from_raw(child.id().try_into().unwrap()),
+        Signal::SIGUSR1,
+    )
+    .unwrap();
+    child.delay(100);
+    child.wait().unwrap().signal_name_is("USR1");
+}
@@ -292,7 +292,13 @@ fn test_output_mp_repeat() {
 #[test]
 fn test_output_conflict_options() {
     for option in ["-i", "-T", "-P"] {
-        new_ucmd!().arg("--output=source").arg(option).fails();
+        new_ucmd!()
+            .arg("--output=source")
+            .arg(option)
+            .fails()
+            .usage_error(format!(
+                "options {option} and --output are mutually exclusive"
+            ));
     }
 }
 
@@ -1035,6 +1041,26 @@ fn test_output_field_no_more_than_once() {
         .usage_error("option --output: field 'target' used more than once");
 }
 
+#[test]
+fn test_output_field_unknown() {
+    for (arg, field) in [("--output=invalid", "invalid"), ("--output=size,", "")] {
+        new_ucmd!()
+            .arg(arg)
+            .fails()
+            .usage_error(format!("option --output: field '{field}' unknown"));
+    }
+}
+
+#[test]
+fn test_output_without_field_list_along_with_one() {
+    // a bare `--output` only stands for all the fields if none are named
+    let output = new_ucmd!()
+        .args(&["--output", "--output=size", "."])
+        .succeeds()
+        .stdout_str_lossy();
+    assert_eq!(output.lines().next().unwrap(), "1K-blocks");
+}
+
 #[test]
 #[cfg(not(any(target_os = "freebsd", target_os = "windows")))] // FIXME: fix test for FreeBSD & Win
 fn test_nonexistent_file() {
@@ -1054,8 +1080,7 @@ fn test_nonexistent_file() {
 fn test_df_all_shows_binfmt_misc() {
     // Check if binfmt_misc is mounted
     let is_mounted = std::fs::read_to_string("/proc/self/mountinfo")
-        .map(|content| content.lines().any(|line| line.contains("binfmt_misc")))
-        .unwrap_or(false);
+        .is_ok_and(|content| content.lines().any(|line| line.contains("binfmt_misc")));
 
     if is_mounted {
         let output = new_ucmd!()
@@ -1076,8 +1101,7 @@ fn test_df_all_shows_binfmt_misc() {
 fn test_df_hides_binfmt_misc_by_default() {
     // Check if binfmt_misc is mounted
     let is_mounted = std::fs::read_to_string("/proc/self/mountinfo")
-        .map(|content| content.lines().any(|line| line.contains("binfmt_misc")))
-        .unwrap_or(false);
+        .is_ok_and(|content| content.lines().any(|line| line.contains("binfmt_misc")));
 
     if is_mounted {
         let output = new_ucmd!()
@@ -1152,3 +1176,60 @@ fn test_df_masked_proc_fallback() {
         }
     }
 }
+
+#[test]
+fn test_json() {
+    let output = new_ucmd!()
+        .args(&["--json", "--total", "-hi", "."])
+        .succeeds()
+        .stdout_str_lossy();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+    let record = regex::Regex::new(
+        r#"^\{"source":.*,"size":\d+,"used":\d+,"avail":\d+,"pcent":(\d+|null),"itotal":\d+,"iused":\d+,"iavail":\d+,"ipcent":(\d+|null),"file":.*\}$"#,
+    )
+    .unwrap();
+    assert!(lines.iter().all(|line| record.is_match(line)), "{output}");
+    assert!(lines[0].ends_with(r#""file":"."}"#));
+    assert!(lines[1].starts_with(r#"{"source":"total","fstype":null,"target":null,"#));
+}
+
+#[test]
+#[cfg(windows)]
+fn test_mount_point_of_file_on_windows() {
+    let output = new_ucmd!()
+        .args(&["--output=target", "."])
+        .succeeds()
+        .stdout_str_lossy();
+    let target = output.lines().nth(1).unwrap();
+    assert!(target.ends_with('\\'), "{target:?} is not a mount point");
+}
+
+/// The exit code for each class of failure, as GNU's: 1 for all of them.
+#[test]
+fn test_exit_code_matrix() {
+    let cases: &[(&[&str], i32)] = &[
+        // usage
+        (&["--bogus"], 1),
+        (&["--output=bogus"], 1),
+        (&["--block-size=x"], 1),
+        // operand
+        (&["missing"], 1),
+        (&[".", "missing"], 1),
+    ];
+    for (args, code) in cases {
+        new_ucmd!().args(args).fails_with_code(*code);
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_write_error() {
+    for args in [&["."][..], &["--output=source", "."]] {
+        new_ucmd!()
+            .args(args)
+            .set_stdout(std::fs::File::create("/dev/full").unwrap())
+            .fails_with_code(1)
+            .stderr_is("df: write error: No space left on device\n");
+    }
+}
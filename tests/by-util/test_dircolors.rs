@@ -109,11 +109,36 @@ fn test_internal_db() {
 #[test]
 fn test_ls_colors() {
     new_ucmd!()
+        .env("TERM", "xterm")
         .arg("--print-ls-colors")
         .succeeds()
         .stdout_is_fixture("ls_colors.expected");
 }
 
+#[test]
+fn test_internal_db_unknown_term() {
+    new_ucmd!()
+        .env("TERM", "dumb")
+        .arg("-b")
+        .succeeds()
+        .stdout_is("LS_COLORS='';\nexport LS_COLORS\n");
+    new_ucmd!()
+        .env("TERM", "dumb")
+        .arg("--print-ls-colors")
+        .succeeds()
+        .no_output();
+}
+
+#[test]
+fn test_internal_db_colorterm() {
+    new_ucmd!()
+        .env("TERM", "dumb")
+        .env("COLORTERM", "truecolor")
+        .arg("-b")
+        .succeeds()
+        .stdout_is_fixture("bash_def.expected");
+}
+
 #[test]
 fn test_bash_default() {
     new_ucmd!()
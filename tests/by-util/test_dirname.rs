@@ -261,3 +261,17 @@ fn test_dot_slash_component_preservation() {
         .succeeds()
         .stdout_is("/path/./to\n");
 }
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_write_error_handling() {
+    use std::fs::File;
+
+    let dev_full = File::create("/dev/full").unwrap();
+
+    new_ucmd!()
+        .arg("a/b")
+        .set_stdout(dev_full)
+        .fails_with_code(1)
+        .stderr_contains("No space left on device");
+}
@@ -749,6 +749,38 @@ fn test_du_inodes() {
     }
 }
 
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_du_inodes_threshold() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+
+    at.mkdir_all("dir/a");
+    at.mkdir("dir/b");
+    at.touch("dir/a/f1");
+    at.touch("dir/a/f2");
+    at.touch("dir/a/f3");
+    at.touch("dir/b/f1");
+
+    // The threshold applies to the inode counts
+    ts.ucmd()
+        .args(&["--inodes", "-t", "3", "dir"])
+        .succeeds()
+        .stdout_is("4\tdir/a\n7\tdir\n");
+    ts.ucmd()
+        .args(&["--inodes", "-a", "-t", "-1", "--max-depth=1", "dir"])
+        .succeeds()
+        .stdout_is("");
+    ts.ucmd()
+        .args(&["--inodes", "-s", "-t", "8", "dir"])
+        .succeeds()
+        .stdout_is("");
+    ts.ucmd()
+        .args(&["--inodes", "-a", "-t", "-1", "dir/b"])
+        .succeeds()
+        .stdout_is("1\tdir/b/f1\n");
+}
+
 #[cfg(not(target_os = "android"))]
 #[test]
 fn test_du_inodes_with_count_links() {
@@ -1377,6 +1409,39 @@ fn test_du_exclude_mix() {
     assert!(result.stdout_str().contains("xcwww"));
 }
 
+#[test]
+fn test_du_exclude_from_unreadable() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.mkdir("dir");
+
+    ts.ucmd()
+        .arg("--exclude-from=missing")
+        .fails_with_code(1)
+        .no_stdout()
+        .stderr_contains("du: missing: No such file or directory\n")
+        .stderr_contains("for more information");
+    ts.ucmd()
+        .arg("--exclude-from=dir")
+        .fails_with_code(1)
+        .no_stdout()
+        .stderr_contains("du: dir: Is a directory\n");
+}
+
+#[test]
+fn test_du_exclude_from_stdin() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.mkdir_all("dir/skipped");
+    at.mkdir("dir/kept");
+
+    ts.ucmd()
+        .args(&["--exclude-from=-", "dir"])
+        .pipe_in("skipped\n")
+        .succeeds()
+        .stdout_contains("dir/kept")
+        .stdout_does_not_contain("skipped");
+}
+
 #[test]
 // Disable on Windows because we are looking for /
 // And the tests would be more complex if we have to support \ too
@@ -1431,6 +1496,83 @@ fn test_du_exclude_several_components() {
     assert!(!result.stdout_str().contains("a/b"));
 }
 
+#[test]
+fn test_du_exclude_trailing_components() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+
+    at.mkdir_all("a/b/c");
+    at.mkdir_all("a/bb/c");
+
+    // Like GNU, "b/c" matches the trailing components of "a/b/c"
+    let result = ts.ucmd().arg("--exclude=b/c").arg("a").succeeds();
+    assert!(!result.stdout_str().contains("a/b/c"));
+    assert!(result.stdout_str().contains("a/bb/c"));
+}
+
+#[test]
+fn test_du_exclude_globstar() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+
+    at.mkdir_all("a/b/c/d");
+    at.mkdir_all("a/e");
+    at.touch("a/b/c/d/f.o");
+    at.touch("a/e/g.o");
+
+    // Without --exclude-globstar, "**" is the same as "*", which matches '/'
+    let result = ts
+        .ucmd()
+        .arg("-a")
+        .arg("--exclude=a**c")
+        .arg("a")
+        .succeeds();
+    assert!(!result.stdout_str().contains("a/b/c"));
+    assert!(result.stdout_str().contains("a/b\n"));
+
+    let result = ts
+        .ucmd()
+        .arg("-a")
+        .arg("--exclude-globstar")
+        .arg("--exclude=b/**/*.o")
+        // "*" no longer matches '/', so this does not match "e/g.o"
+        .arg("--exclude=e*o")
+        .arg("a")
+        .succeeds();
+    let stdout = result.stdout_str();
+    assert!(!stdout.contains("f.o"));
+    assert!(stdout.contains("a/b/c/d\n"));
+    assert!(stdout.contains("a/e/g.o"));
+
+    ts.ucmd()
+        .arg("--exclude-globstar")
+        .arg("--exclude=a**c")
+        .arg("a")
+        .fails()
+        .stderr_contains("du: Invalid exclude syntax");
+}
+
+#[test]
+fn test_du_exclude_ignore_case() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+
+    at.mkdir_all("a/Build");
+    at.mkdir_all("a/src");
+
+    let result = ts.ucmd().arg("--exclude=build").arg("a").succeeds();
+    assert!(result.stdout_str().contains("a/Build"));
+
+    let result = ts
+        .ucmd()
+        .arg("--exclude-ignore-case")
+        .arg("--exclude=build")
+        .arg("a")
+        .succeeds();
+    assert!(!result.stdout_str().contains("a/Build"));
+    assert!(result.stdout_str().contains("a/src"));
+}
+
 #[test]
 fn test_du_exclude_invalid_syntax() {
     let ts = TestScenario::new(util_name!());
@@ -2040,6 +2182,31 @@ fn test_du_hard_links_multiple_links_in_args() {
     result.stdout_does_not_contain("dir1/link");
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn test_du_threads_same_output() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+
+    for i in 0..20 {
+        at.mkdir_all(&format!("top/d{i}/sub"));
+        at.write(&format!("top/d{i}/file"), &"x".repeat(5000 * i));
+        // the first link met is the one counted
+        at.hard_link("top/d0/file", &format!("top/d{i}/sub/link"));
+    }
+
+    let run = |threads: &str| {
+        ts.ucmd()
+            .env("RAYON_NUM_THREADS", threads)
+            .args(&["-a", "--exclude=d5", "top"])
+            .succeeds()
+            .stdout_move_str()
+    };
+    let sequential = run("1");
+    assert!(!sequential.contains("top/d5"));
+    assert_eq!(run("4"), sequential);
+}
+
 #[test]
 #[cfg(target_os = "linux")]
 fn test_du_symlinks_multiple_links_in_args() {
@@ -2203,3 +2370,61 @@ fn test_overriding_block_size_arg_with_invalid_value_still_errors() {
         .fails_with_code(1)
         .stderr_contains("invalid --block-size argument 'abc'");
 }
+
+#[test]
+fn test_du_json() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("dir");
+    at.write("dir/file", "abcd");
+
+    ucmd.args(&["--json", "--apparent-size", "-a", "-c", "-h", "dir/file"])
+        .succeeds()
+        .stdout_only(concat!(
+            r#"{"path":"dir/file","size":4,"inodes":1,"time":null,"total":false}"#,
+            "\n",
+            r#"{"path":null,"size":4,"inodes":1,"time":null,"total":true}"#,
+            "\n",
+        ));
+}
+
+/// The exit code for each class of failure, as GNU's: 1 for all of them.
+#[test]
+fn test_exit_code_matrix() {
+    let scene = TestScenario::new(util_name!());
+    let cases: &[(&[&str], i32)] = &[
+        // usage
+        (&["--bogus"], 1),
+        (&["--block-size=x"], 1),
+        (&["--threshold=x"], 1),
+        // operand
+        (&["missing"], 1),
+        (&[".", "missing"], 1),
+        (&["--exclude-from=missing", "."], 1),
+    ];
+    for (args, code) in cases {
+        scene.ucmd().args(args).fails_with_code(*code);
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_write_error() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.mkdir("dir");
+
+    scene
+        .ucmd()
+        .arg("dir")
+        .set_stdout(std::fs::File::create("/dev/full").unwrap())
+        .fails_with_code(1)
+        .stderr_is("du: write error: No space left on device\n");
+
+    // Like GNU, the other operands are still processed.
+    scene
+        .ucmd()
+        .args(&["dir", "missing"])
+        .set_stdout(std::fs::File::create("/dev/full").unwrap())
+        .fails_with_code(1)
+        .stderr_contains("du: cannot access 'missing'")
+        .stderr_contains("du: write error: No space left on device\n");
+}
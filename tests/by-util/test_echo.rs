@@ -826,3 +826,15 @@ fn test_emoji_output() {
         .succeeds()
         .stdout_only("🦀 loves 🚀 and 🌟\n");
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_failed_write_is_reported() {
+    for args in [vec!["hello"], vec!["-n", "hello"]] {
+        new_ucmd!()
+            .args(&args)
+            .set_stdout(std::fs::File::create("/dev/full").unwrap())
+            .fails_with_code(1)
+            .stderr_is("echo: write error: No space left on device\n");
+    }
+}
@@ -464,6 +464,30 @@ fn test_change_directory() {
     assert_eq!(out.trim(), temporary_path.as_os_str());
 }
 
+#[test]
+#[cfg(unix)]
+fn test_change_directory_debug() {
+    new_ucmd!()
+        .args(&["-v", "-C", "/", "-a", "x", "true"])
+        .succeeds()
+        .no_stdout()
+        .stderr_is("chdir:    '/'\nargv0:     'x'\nexecuting: true\n   arg[0]= 'x'\n");
+}
+
+#[cfg(windows)]
+#[test]
+fn test_argv0_ignored() {
+    // COMSPEC is a variable that contains the full path to cmd.exe
+    let cmd_path = env::var("COMSPEC").unwrap();
+
+    new_ucmd!()
+        .args(&["-a", "other"])
+        .args(&[&*cmd_path, "/C", "echo", "hi"])
+        .succeeds()
+        .stdout_is("hi\r\n")
+        .stderr_is("env: warning: --argv0 is not supported on this platform, ignoring 'other'\n");
+}
+
 #[test]
 fn test_fail_change_directory() {
     let scene = TestScenario::new(util_name!());
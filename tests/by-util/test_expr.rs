@@ -1975,3 +1975,19 @@ fn test_emoji_operations() {
         .succeeds()
         .stdout_only("1\n");
 }
+
+#[test]
+fn test_dash_operands_are_not_options() {
+    new_ucmd!()
+        .args(&["--", "--help"])
+        .succeeds()
+        .stdout_only("--help\n");
+    new_ucmd!()
+        .args(&["--", "--", "=", "--"])
+        .succeeds()
+        .stdout_only("1\n");
+    new_ucmd!()
+        .args(&["-1", "+", "1"])
+        .fails_with_code(1)
+        .stdout_only("0\n");
+}
@@ -14,6 +14,7 @@
     not(target_os = "openbsd")
 ))]
 use std::io::Read;
+use uutests::at_and_ucmd;
 use uutests::new_ucmd;
 use uutests::util::TestScenario;
 use uutests::util_name;
@@ -278,6 +279,48 @@ fn test_zero_terminated() {
         .stdout_is_fixture("zero_terminated.expected");
 }
 
+#[test]
+fn test_zero_terminated_file() {
+    // Reading a regular file seeks from the end for -n -N, so check it
+    // counts NUL-terminated records there as well as on a pipe.
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("f", "a\nb\0c\0d\ne");
+    ucmd.args(&["-z", "-n", "-2", "f"])
+        .succeeds()
+        .stdout_only("a\nb\0");
+    new_ucmd!()
+        .args(&["-z", "-n", "-2"])
+        .pipe_in("a\nb\0c\0d\ne")
+        .succeeds()
+        .stdout_only("a\nb\0");
+}
+
+#[test]
+fn test_zero_terminated_bytes() {
+    // -c counts bytes whatever the record delimiter is.
+    new_ucmd!()
+        .args(&["-z", "-c", "3"])
+        .pipe_in("a\0b\0c\0")
+        .succeeds()
+        .stdout_only("a\0b");
+    new_ucmd!()
+        .args(&["-z", "-c", "-3"])
+        .pipe_in("a\0b\0c\0")
+        .succeeds()
+        .stdout_only("a\0b");
+}
+
+#[test]
+fn test_zero_terminated_multiple_files() {
+    // The headers keep ending in newlines.
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "1\x002\x003\0");
+    at.write("b", "4\n5\x006");
+    ucmd.args(&["-z", "-n", "1", "a", "b"])
+        .succeeds()
+        .stdout_only("==> a <==\n1\0\n==> b <==\n4\n5\0");
+}
+
 #[test]
 fn test_obsolete_extras() {
     new_ucmd!()
@@ -312,6 +355,57 @@ hello
         );
 }
 
+#[test]
+fn test_repeated_stdin_headers() {
+    new_ucmd!()
+        .args(&["-", "-"])
+        .pipe_in("hello\n")
+        .succeeds()
+        .stdout_is("==> standard input <==\nhello\n\n==> standard input <==\n");
+}
+
+#[test]
+fn test_last_of_quiet_and_verbose_wins() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "a\n");
+    ucmd.args(&["-q", "-v", "a", "a"])
+        .succeeds()
+        .stdout_is("==> a <==\na\n\n==> a <==\na\n");
+    new_ucmd!()
+        .args(&["-v", "-q", "emptyfile.txt", "emptyfile.txt"])
+        .succeeds()
+        .no_output();
+}
+
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_directory_operand_keeps_going() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("d");
+    at.write("a", "a\n");
+    ucmd.args(&["d", "a"])
+        .fails_with_code(1)
+        .stdout_is("==> d <==\n\n==> a <==\na\n")
+        .stderr_is("head: error reading 'd': Is a directory\n");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_read_error_keeps_going() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "abc\n");
+    at.write("b", "xyz\n");
+    ucmd.args(&["-c", "2", "a", "/proc/self/mem", "missing", "b"])
+        .stderr_to_stdout()
+        .fails_with_code(1)
+        .stdout_only(
+            "==> a <==\nab\n==> /proc/self/mem <==\n\
+             head: error reading '/proc/self/mem': Input/output error\n\
+             head: cannot open 'missing' for reading: No such file or directory\n\
+             \n==> b <==\nxy",
+        );
+}
+
 #[test]
 fn test_bad_utf8() {
     let bytes: &[u8] = b"\xfc\x80\x80\x80\x80\xaf";
@@ -853,8 +947,8 @@ fn test_write_to_dev_full() {
             new_ucmd!()
                 .pipe_in_fixture(INPUT)
                 .set_stdout(dev_full)
-                .fails()
-                .stderr_contains("error writing 'standard output': No space left on device");
+                .fails_with_code(1)
+                .stderr_is("head: error writing 'standard output': No space left on device\n");
         }
     }
 }
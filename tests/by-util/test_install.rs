@@ -45,6 +45,28 @@ fn test_install_basic() {
     assert!(at.file_exists(format!("{dir}/{file2}")));
 }
 
+#[test]
+fn test_install_sync() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write("file", "content");
+
+    scene
+        .ucmd()
+        .args(&["--sync", "-D", "file", "a/b/file"])
+        .succeeds()
+        .no_output();
+    assert_eq!(at.read("a/b/file"), "content");
+
+    scene
+        .ucmd()
+        .env("UUTILS_SYNC", "1")
+        .args(&["-d", "c/d"])
+        .succeeds()
+        .no_output();
+    assert!(at.dir_exists("c/d"));
+}
+
 #[test]
 fn test_install_twice_dir() {
     let dir = "dir";
@@ -71,7 +93,7 @@ fn test_install_failing_not_dir() {
         .arg(file2)
         .arg(file3)
         .fails()
-        .stderr_contains("not a directory");
+        .stderr_contains("target 'file3': Not a directory");
 }
 
 #[test]
@@ -614,7 +636,7 @@ fn test_install_failing_omitting_directory() {
         .arg(dir1)
         .arg(no_dir2)
         .fails()
-        .stderr_contains("is not a directory");
+        .stderr_contains("target 'no-dir2': No such file or directory");
 
     // file1 will be copied before install fails on dir1
     scene
@@ -1012,7 +1034,7 @@ fn test_install_creating_leading_dirs_with_single_source_and_target_dir() {
         .arg(source1)
         .arg(at.plus(target_dir))
         .fails()
-        .stderr_contains("missing_target_dir/' is not a directory");
+        .stderr_contains("missing_target_dir/': Not a directory");
 
     assert!(!at.dir_exists(target_dir));
 
@@ -1048,7 +1070,7 @@ fn test_install_creating_leading_dirs_with_multiple_sources_and_target_dir() {
         .arg(source2)
         .arg(at.plus(target_dir))
         .fails()
-        .stderr_contains("missing_target_dir' is not a directory");
+        .stderr_contains("missing_target_dir': No such file or directory");
 
     assert!(!at.dir_exists(target_dir));
 
@@ -2232,7 +2254,7 @@ fn test_install_no_target_directory_creating_leading_dirs_with_single_source_and
         .arg(source1)
         .arg(at.plus(target_dir))
         .fails()
-        .stderr_contains("missing_target_dir/' is not a directory");
+        .stderr_contains("missing_target_dir/': Not a directory");
 
     assert!(!at.dir_exists(target_dir));
 }
@@ -2253,9 +2275,7 @@ fn test_install_no_target_directory_failing_combine_with_target_directory() {
         .arg("-t")
         .arg(dir1)
         .fails()
-        .stderr_contains(
-            "Options --target-directory and --no-target-directory are mutually exclusive",
-        );
+        .stderr_contains("cannot combine --target-directory (-t) and --no-target-directory (-T)");
 }
 
 #[test]
@@ -2301,7 +2321,7 @@ fn test_install_no_target_multiple_sources_and_target_dir() {
         .arg(dir1)
         .fails()
         .stderr_contains("extra operand 'dir1'")
-        .stderr_contains("[OPTION]... [FILE]...");
+        .stderr_contains("for more information");
 
     scene
         .ucmd()
@@ -2312,7 +2332,7 @@ fn test_install_no_target_multiple_sources_and_target_dir() {
         .arg(dir2)
         .fails()
         .stderr_contains("extra operand 'dir1'")
-        .stderr_contains("[OPTION]... [FILE]...");
+        .stderr_contains("for more information");
 }
 
 #[test]
@@ -2658,3 +2678,146 @@ fn test_install_d_symlink_race_condition_concurrent() {
         "Intermediate directory should be a real directory, not a symlink"
     );
 }
+
+#[test]
+fn test_install_target_directory_must_exist() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.touch("file");
+
+    scene
+        .ucmd()
+        .args(&["-t", "missing", "file"])
+        .fails_with_code(1)
+        .stderr_only("install: failed to access 'missing': No such file or directory\n");
+    assert!(!at.file_exists("missing"));
+
+    scene
+        .ucmd()
+        .args(&["-t", "file", "file"])
+        .fails_with_code(1)
+        .stderr_only("install: failed to access 'file': Not a directory\n");
+
+    scene
+        .ucmd()
+        .args(&["-t", "dir1", "-t", "dir2", "file"])
+        .fails_with_code(1)
+        .stderr_only("install: multiple target directories specified\n");
+}
+
+#[test]
+fn test_install_single_source_target_errors() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.touch("file");
+    at.mkdir("dir");
+
+    scene
+        .ucmd()
+        .args(&["missing", "target"])
+        .fails_with_code(1)
+        .stderr_only("install: cannot stat 'missing': No such file or directory\n");
+    scene
+        .ucmd()
+        .args(&["-T", "file", "dir"])
+        .fails_with_code(1)
+        .stderr_only("install: cannot overwrite directory 'dir' with non-directory\n");
+    scene
+        .ucmd()
+        .args(&["file", "new/"])
+        .fails_with_code(1)
+        .stderr_only("install: cannot create regular file 'new/': Not a directory\n");
+    scene
+        .ucmd()
+        .args(&["file", "file/"])
+        .fails_with_code(1)
+        .stderr_only("install: cannot stat 'file/': Not a directory\n");
+    scene
+        .ucmd()
+        .args(&["file", "no/file"])
+        .fails_with_code(1)
+        .stderr_only("install: cannot create regular file 'no/file': No such file or directory\n");
+
+    // -D creates the parent of a target with a trailing slash, like GNU
+    scene
+        .ucmd()
+        .args(&["-D", "file", "a/b/"])
+        .fails_with_code(1)
+        .stderr_only("install: cannot create regular file 'a/b/': Not a directory\n");
+    assert!(at.dir_exists("a"));
+    assert!(!at.file_exists("a/b"));
+}
+
+#[test]
+fn test_install_directory_operand_errors() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.touch("file");
+    let file_mode = at.metadata("file").permissions().mode();
+
+    scene
+        .ucmd()
+        .arg("-d")
+        .fails_with_code(1)
+        .stderr_contains("install: missing file operand\n")
+        .stderr_contains("for more information");
+    scene
+        .ucmd()
+        .args(&["-d", "-t", "dir", "sub"])
+        .fails_with_code(1)
+        .stderr_only("install: target directory not allowed when installing a directory\n");
+    scene
+        .ucmd()
+        .args(&["-d", "-s", "sub"])
+        .fails_with_code(1)
+        .stderr_only("install: the strip option may not be used when installing a directory\n");
+    scene
+        .ucmd()
+        .args(&["-d", "-m", "700", "file", "sub"])
+        .fails_with_code(1)
+        .stderr_only("install: cannot create directory 'file': File exists\n");
+    assert_eq!(at.metadata("file").permissions().mode(), file_mode);
+    assert!(at.dir_exists("sub"));
+}
+
+#[test]
+fn test_install_directory_verbose_each_level() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("a");
+
+    ucmd.args(&["-dv", "-m", "700", "a/b/c"])
+        .umask(0o022)
+        .succeeds()
+        .stdout_only("install: creating directory 'a/b'\ninstall: creating directory 'a/b/c'\n");
+    // Only the last level gets the mode.
+    assert_eq!(at.metadata("a/b").permissions().mode() & 0o777, 0o755);
+    assert_eq!(at.metadata("a/b/c").permissions().mode() & 0o777, 0o700);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_install_dir_mode_setgid_parent() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.mkdir("sg");
+    at.set_mode("sg", 0o2775);
+
+    // A mode that leaves the set-group-ID bit alone keeps the inherited one,
+    // on new and existing directories alike
+    scene.ucmd().args(&["-d", "-m", "755", "sg/new"]).succeeds();
+    assert_eq!(at.metadata("sg/new").permissions().mode() & 0o7777, 0o2755);
+    scene.ucmd().args(&["-d", "-m", "700", "sg"]).succeeds();
+    assert_eq!(at.metadata("sg").permissions().mode() & 0o7777, 0o2700);
+
+    // Without -m the default mode clears it, as do modes naming it
+    scene.ucmd().args(&["-d", "sg/default"]).succeeds();
+    assert_eq!(
+        at.metadata("sg/default").permissions().mode() & 0o7777,
+        0o755
+    );
+    scene
+        .ucmd()
+        .args(&["-d", "-m", "00755", "sg/new"])
+        .succeeds();
+    assert_eq!(at.metadata("sg/new").permissions().mode() & 0o7777, 0o755);
+}
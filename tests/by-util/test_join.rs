@@ -266,6 +266,18 @@ fn unpaired_lines_format() {
         .stdout_only_fixture("unpaired_lines_format.expected");
 }
 
+#[test]
+fn invalid_unpaired_file_number() {
+    for args in [["-a", "3"], ["-v", "0"], ["-a", "x"]] {
+        new_ucmd!()
+            .args(&args)
+            .arg("fields_1.txt")
+            .arg("fields_2.txt")
+            .fails_with_code(1)
+            .stderr_is(format!("join: invalid field number: '{}'\n", args[1]));
+    }
+}
+
 #[test]
 fn autoformat() {
     new_ucmd!()
@@ -299,6 +311,48 @@ fn autoformat() {
         .stdout_only("1 x y z a\n2 p . . b\n99 a b . .\n");
 }
 
+#[test]
+fn empty_fields_are_filled() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.write("a", "a,1,,2\nb,3\nc,,4\n");
+    ts.fixtures.write("b", "a,x\nb,,y\nd,z\n");
+    ts.ucmd()
+        .args(&["-t,", "-e", "X", "-a1", "a", "b"])
+        .succeeds()
+        .stdout_only("a,1,X,2,x\nb,3,X,y\nc,X,4\n");
+    ts.ucmd()
+        .args(&["-t,", "-e", "X", "-o", "1.3,2.2", "-a1", "-a2", "a", "b"])
+        .succeeds()
+        .stdout_only("X,x\nX,X\n4,X\nX,z\n");
+}
+
+#[test]
+fn repeated_format_lists() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.write("a", "1 a p\n2 b q\n");
+    ts.fixtures.write("b", "1 x\n2 y\n");
+    ts.ucmd()
+        .args(&["-o", "1.3", "-o", "2.2,0", "a", "b"])
+        .succeeds()
+        .stdout_only("p x 1\nq y 2\n");
+    // An explicit list takes precedence over auto.
+    ts.ucmd()
+        .args(&["-o", "auto", "-o", "1.2", "a", "b"])
+        .succeeds()
+        .stdout_only("a\nb\n");
+}
+
+#[test]
+fn whole_line_format_fields_on_separate_lines() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.write("a", "a\nb\n");
+    ts.fixtures.write("b", "b\nc\n");
+    ts.ucmd()
+        .args(&["-t", "", "-o", "1.1,2.1", "-a2", "-e", "-", "a", "b"])
+        .succeeds()
+        .stdout_only("b\nb\n-\nc\n");
+}
+
 #[test]
 fn empty_format() {
     new_ucmd!()
@@ -374,6 +428,22 @@ fn wrong_line_order() {
         ));
 }
 
+#[test]
+fn wrong_line_order_reported_after_output() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.write("a", "1 a\n3 b\n2 c\n");
+    ts.fixtures.write("b", "1 x\n2 y\n3 z\n");
+    ts.ucmd()
+        .args(&["-a1", "a", "b"])
+        .stderr_to_stdout()
+        .fails_with_code(1)
+        .stdout_only(format!(
+            "1 a x\n{0} {1}: a:3: is not sorted: 2 c\n3 b z\n2 c\n{0} {1}: input is not in sorted order\n",
+            ts.bin_path.to_string_lossy(),
+            ts.util_name
+        ));
+}
+
 #[test]
 fn both_files_wrong_line_order() {
     let ts = TestScenario::new(util_name!());
@@ -422,6 +492,23 @@ fn headers_autoformat() {
         .stdout_only_fixture("header_autoformat.expected");
 }
 
+#[test]
+fn headers_unpaired_autoformat_empty() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.write("a", "id,name\n1,a\n2,b\n");
+    ts.fixtures.write("b", "id,city,zip\n1,X,10\n3,Y\n");
+    ts.ucmd()
+        .args(&[
+            "--header", "-t,", "-a1", "-a2", "-o", "auto", "-e", "NA", "a", "b",
+        ])
+        .succeeds()
+        .stdout_only("id,name,city,zip\n1,a,X,10\n2,b,NA,NA\n3,NA,Y,NA\n");
+    ts.ucmd()
+        .args(&["--header", "-t,", "-v2", "-o", "auto", "-e", "NA", "a", "b"])
+        .succeeds()
+        .stdout_only("id,name,city,zip\n3,NA,Y,NA\n");
+}
+
 #[test]
 fn single_file_with_header() {
     new_ucmd!()
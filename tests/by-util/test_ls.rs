@@ -1102,6 +1102,48 @@ fn test_ls_zero() {
         .stdout_contains("total ");
 }
 
+#[test]
+fn test_ls_zero_grid() {
+    // Formats given after --zero are kept, but their lines still end with NUL.
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    for name in ["aa", "bb", "cc", "dd"] {
+        at.touch(name);
+    }
+
+    scene
+        .ucmd()
+        .args(&["--zero", "-C", "-w", "8"])
+        .succeeds()
+        .stdout_only("aa  cc\x00bb  dd\x00");
+    scene
+        .ucmd()
+        .args(&["--zero", "-x", "-w", "8"])
+        .succeeds()
+        .stdout_only("aa  bb\x00cc  dd\x00");
+    scene
+        .ucmd()
+        .args(&["--zero", "-C", "-w", "0"])
+        .succeeds()
+        .stdout_only("aa  bb  cc  dd\x00");
+
+    #[cfg(unix)]
+    {
+        at.touch("e\ne");
+        scene
+            .ucmd()
+            .args(&["--zero", "-x", "-w", "8"])
+            .succeeds()
+            .stdout_only("aa  bb\x00cc  dd\x00e\ne\x00");
+    }
+
+    scene
+        .ucmd()
+        .args(&["--zero", "-l", "--dired"])
+        .fails_with_code(2)
+        .stderr_contains("--dired and --zero are incompatible");
+}
+
 #[test]
 fn test_ls_commas_trailing() {
     let scene = TestScenario::new(util_name!());
@@ -2099,6 +2141,46 @@ fn test_ls_order_size() {
     result.stdout_only("test-1\ntest-2\ntest-3\ntest-4\n");
 }
 
+/// Large directories have their entries looked up on several threads, which
+/// must not change what is listed, nor where errors show up.
+#[test]
+fn test_ls_order_size_large_directory() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+
+    at.mkdir("big");
+    for i in 1..=300 {
+        at.write(&format!("big/{i:03}"), &"x".repeat(i));
+    }
+
+    let names: Vec<String> = (1..=300).rev().map(|i| format!("{i:03}")).collect();
+    let expected = names.join("\n") + "\n";
+    scene
+        .ucmd()
+        .env("RAYON_NUM_THREADS", "4")
+        .args(&["-S", "big"])
+        .succeeds()
+        .stdout_only(&expected);
+
+    let result = scene
+        .ucmd()
+        .env("RAYON_NUM_THREADS", "4")
+        .args(&["-S", "-s", "big"])
+        .succeeds();
+    assert_eq!(result.stdout_str().lines().count(), 301);
+
+    at.symlink_file("nowhere", "big/dangling");
+    scene
+        .ucmd()
+        .env("RAYON_NUM_THREADS", "4")
+        .args(&["-L", "-l", "big"])
+        .fails_with_code(1)
+        .stderr_is(format!(
+            "ls: cannot access '{}': No such file or directory\n",
+            Path::new("big").join("dangling").display()
+        ));
+}
+
 #[test]
 fn test_ls_long_ctime() {
     let scene = TestScenario::new(util_name!());
@@ -2109,12 +2191,45 @@ fn test_ls_long_ctime() {
     for arg in ["-c", "--time=ctime", "--time=status"] {
         let result = scene.ucmd().arg("-l").arg(arg).succeeds();
 
-        // Should show the time on Unix, but question marks on windows.
+        // Should show the time on Unix, but a question mark on windows.
         #[cfg(unix)]
         result.stdout_contains(":");
         #[cfg(not(unix))]
-        result.stdout_contains("???");
+        result.stdout_contains("           ? test-long-ctime-1");
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_ls_long_unknown_birth_time() {
+    // procfs does not record birth times. Like GNU, a "?" as wide as a
+    // timestamp is shown instead.
+    new_ucmd!()
+        .args(&["-l", "--time=birth", "/proc/version"])
+        .succeeds()
+        .stdout_matches(&Regex::new(r" 0 {12}\? /proc/version\n$").unwrap());
+    new_ucmd!()
+        .args(&["-l", "--full-time", "--time=birth", "/proc/version"])
+        .succeeds()
+        .stdout_matches(&Regex::new(r" 0 {35}\? /proc/version\n$").unwrap());
+}
+
+#[test]
+fn test_ls_order_ties_by_name() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+
+    let time = SystemTime::now() - Duration::from_secs(60);
+    for name in ["m", "z", "a", "k"] {
+        at.make_file(name).set_modified(time).unwrap();
     }
+
+    scene.ucmd().arg("-t").succeeds().stdout_is("a\nk\nm\nz\n");
+    scene
+        .ucmd()
+        .args(&["-S", "-r"])
+        .succeeds()
+        .stdout_is("z\nm\nk\na\n");
 }
 
 #[test]
@@ -3162,6 +3277,46 @@ mod quoting {
                 .stdout_only(utf_8_ref);
         }
     }
+
+    #[test]
+    fn test_ls_quoting_locale() {
+        let scene = TestScenario::new(util_name!());
+        let at = &scene.fixtures;
+        at.touch("it's");
+        at.touch("new\nline");
+
+        for (style, ascii_ref, utf_8_ref) in [
+            (
+                "locale",
+                "'it\\'s'\n'new\\nline'\n",
+                "\u{2018}it's\u{2019}\n\u{2018}new\\nline\u{2019}\n",
+            ),
+            (
+                "clocale",
+                "\"it's\"\n\"new\\nline\"\n",
+                "\u{2018}it's\u{2019}\n\u{2018}new\\nline\u{2019}\n",
+            ),
+        ] {
+            scene
+                .ucmd()
+                .arg(format!("--quoting-style={style}"))
+                .succeeds()
+                .stdout_only(ascii_ref);
+
+            scene
+                .ucmd()
+                .env("LC_ALL", "en_US.UTF-8")
+                .arg(format!("--quoting-style={style}"))
+                .succeeds()
+                .stdout_only(utf_8_ref);
+
+            scene
+                .ucmd()
+                .env("QUOTING_STYLE", style)
+                .succeeds()
+                .stdout_only(ascii_ref);
+        }
+    }
 }
 
 #[test]
@@ -4983,6 +5138,21 @@ fn test_symlink_target_extension_color() {
     assert!(target.contains("31m")); // 31 = red (our configured archive color)
 }
 
+/// With `ln=target` the link takes the file type of its target, but like GNU ls
+/// extensions are still matched against the name of the link itself.
+#[test]
+fn test_ln_target_matches_extension_of_link_name() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.touch("plain");
+    at.touch("f.tar");
+    at.relative_symlink_file("plain", "q.tar");
+    at.relative_symlink_file("f.tar", "ltar");
+    ucmd.env("LS_COLORS", "ln=target:*.tar=31")
+        .args(&["--color=always", "q.tar", "ltar"])
+        .succeeds()
+        .stdout_is("ltar\n\x1b[0m\x1b[31mq.tar\x1b[0m\n");
+}
+
 #[test]
 fn test_tabsize_option() {
     let scene = TestScenario::new(util_name!());
@@ -5654,6 +5824,96 @@ fn test_posixly_correct_and_block_size_env_vars_with_k() {
         .stdout_contains(" 1024 ");
 }
 
+#[cfg(all(unix, feature = "dd"))]
+#[test]
+#[cfg(not(target_os = "openbsd"))]
+fn test_total_rounds_allocated_size_once() {
+    let scene = TestScenario::new(util_name!());
+
+    for file in ["a", "b"] {
+        scene
+            .ccmd("dd")
+            .arg("if=/dev/zero")
+            .arg(format!("of={file}"))
+            .arg("bs=1024")
+            .arg("count=1")
+            .succeeds();
+    }
+
+    // Each file rounds up to a whole block, but the total is rounded from
+    // the sum of the allocated sizes.
+    scene
+        .ucmd()
+        .arg("-s1")
+        .arg("--block-size=1M")
+        .succeeds()
+        .stdout_is("total 1\n1 a\n1 b\n");
+
+    scene
+        .ucmd()
+        .arg("-s1")
+        .env("BLOCK_SIZE", "1M")
+        .succeeds()
+        .stdout_is("total 1\n1 a\n1 b\n");
+}
+
+#[cfg(all(unix, feature = "dd"))]
+#[test]
+#[cfg(not(target_os = "openbsd"))]
+fn test_block_size_unit_without_number() {
+    let scene = TestScenario::new(util_name!());
+
+    scene
+        .ccmd("dd")
+        .arg("if=/dev/zero")
+        .arg("of=file")
+        .arg("bs=1024")
+        .arg("count=1")
+        .succeeds();
+
+    scene
+        .ucmd()
+        .arg("-ls")
+        .arg("--block-size=K")
+        .succeeds()
+        .stdout_contains_line("total 4K")
+        .stdout_contains("4K -")
+        .stdout_contains(" 1K ");
+
+    scene
+        .ucmd()
+        .arg("-ls")
+        .env("LS_BLOCK_SIZE", "KiB")
+        .succeeds()
+        .stdout_contains_line("total 4KiB")
+        .stdout_contains(" 1KiB ");
+
+    scene
+        .ucmd()
+        .arg("-ls")
+        .env("BLOCK_SIZE", "KB")
+        .succeeds()
+        .stdout_contains_line("total 5kB")
+        .stdout_contains(" 2kB ");
+
+    // BLOCKSIZE only applies to the allocated size.
+    scene
+        .ucmd()
+        .arg("-ls")
+        .env("BLOCKSIZE", "K")
+        .succeeds()
+        .stdout_contains_line("total 4K")
+        .stdout_contains(" 1024 ");
+
+    // A number before the unit prints plain numbers.
+    scene
+        .ucmd()
+        .arg("-ls")
+        .arg("--block-size=1K")
+        .succeeds()
+        .stdout_contains_line("total 4");
+}
+
 #[test]
 fn test_ls_invalid_block_size() {
     new_ucmd!()
@@ -5993,6 +6253,42 @@ fn test_ls_hyperlink_symlink_target_handling() {
     assert!(missing_target_part.contains("\x1b["));
 }
 
+#[test]
+#[cfg(unix)]
+fn test_ls_hyperlink_symlinks_point_to_resolved_target() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.touch("file");
+    at.relative_symlink_file("file", "link");
+    at.relative_symlink_file("nowhere", "dangling");
+
+    let path = regex::escape(&at.root_dir_resolved());
+    let url = |name: &str| format!(r"\x1b\]8;;file://[^/]*{path}/{name}\x1b\\");
+    let end = r"\x1b\]8;;\x1b\\";
+
+    // a broken symlink still links to where its target would be
+    let result = scene.ucmd().args(&["--hyperlink", "dangling"]).succeeds();
+    let re = Regex::new(&format!("^{}dangling{end}\n$", url("nowhere"))).unwrap();
+    assert!(
+        re.is_match(result.stdout_str()),
+        "{:?}",
+        result.stdout_str()
+    );
+
+    // in long format, the target shown after the arrow gets the same link
+    let result = scene.ucmd().args(&["--hyperlink", "-l", "link"]).succeeds();
+    let re = Regex::new(&format!(
+        "{url}link{end} -> {url}file{end}\n$",
+        url = url("file")
+    ))
+    .unwrap();
+    assert!(
+        re.is_match(result.stdout_str()),
+        "{:?}",
+        result.stdout_str()
+    );
+}
+
 #[test]
 fn test_ls_hyperlink_utf8_encoding() {
     let scene = TestScenario::new(util_name!());
@@ -6099,6 +6395,38 @@ fn test_term_colorterm() {
     );
 }
 
+#[test]
+fn test_term_database_patterns() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.mkdir("dir");
+
+    // "*color*" and "con[0-9]*x[0-9]*" are patterns of the dircolors database
+    for term in ["st-256color", "con80x25"] {
+        scene
+            .ucmd()
+            .arg("--color=always")
+            .env("TERM", term)
+            .succeeds()
+            .stdout_is("\x1b[0m\x1b[01;34mdir\x1b[0m\n");
+    }
+
+    // A non-empty LS_COLORS or COLORTERM allows colors with any TERM
+    scene
+        .ucmd()
+        .arg("--color=always")
+        .env("TERM", "dumb")
+        .env("LS_COLORS", "di=31")
+        .succeeds()
+        .stdout_is("\x1b[0m\x1b[31mdir\x1b[0m\n");
+    scene
+        .ucmd()
+        .arg("--color=always")
+        .env("TERM", "dumb")
+        .env("COLORTERM", "truecolor")
+        .succeeds()
+        .stdout_is("\x1b[0m\x1b[01;34mdir\x1b[0m\n");
+}
+
 #[cfg(all(unix, not(target_os = "macos")))]
 #[test]
 fn test_acl_display() {
@@ -6579,6 +6907,50 @@ fn test_acl_display_symlink() {
     assert!(iter.all(|i| i == first));
 }
 
+#[cfg(all(unix, not(target_os = "macos")))]
+#[test]
+fn test_acl_display_alignment() {
+    use std::process::Command;
+
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+
+    for dir in ["acl1", "acl2", "plain"] {
+        at.mkdir(dir);
+    }
+    for dir in ["acl1", "acl2"] {
+        match Command::new("setfacl")
+            .args(["-d", "-m", "group::rwx", &at.plus_as_string(dir)])
+            .status()
+            .map(|status| status.code())
+        {
+            Ok(Some(0)) => {}
+            Ok(_) => {
+                println!("test skipped: setfacl failed");
+                return;
+            }
+            Err(e) => {
+                println!("test skipped: setfacl failed with {e}");
+                return;
+            }
+        }
+    }
+
+    // The "+" only widens the permissions by one column, however many
+    // files have an ACL:
+    // drwxr-xr-x+ 2 user group 40 Apr 21 12:44 acl1
+    // drwxr-xr-x+ 2 user group 40 Apr 21 12:44 acl2
+    // drwxr-xr-x  2 user group 40 Apr 21 12:44 plain
+    let result = scene.ucmd().arg("-l").succeeds();
+    let lines: Vec<&str> = result.stdout_str().lines().skip(1).collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(&lines[0][10..12], "+ ");
+    assert_eq!(&lines[1][10..12], "+ ");
+    assert_eq!(&lines[2][10..12], "  ");
+    let link_count_column = |line: &str| line.find(|c: char| c.is_ascii_digit());
+    assert!(lines.iter().all(|line| link_count_column(line) == Some(12)));
+}
+
 #[test]
 fn ls_emoji_alignment() {
     let scene = TestScenario::new(util_name!());
@@ -7107,3 +7479,94 @@ fn test_ls_recursive_no_fd_leak() {
         .succeeds()
         .stderr_is("");
 }
+
+#[test]
+#[cfg(unix)]
+fn test_ls_json() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("dir");
+    at.write("dir/file", "abc");
+    std::os::unix::fs::symlink("file", at.plus("dir/link")).unwrap();
+
+    let output = ucmd.args(&["--json", "--color=always", "dir"]).succeeds();
+    let lines: Vec<&str> = output.stdout_str().lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(r#"{"name":"file","path":"dir/file","type":"file","size":3,"#));
+    assert!(lines[0].ends_with(r#""target":null}"#));
+    assert!(lines[1].starts_with(r#"{"name":"link","path":"dir/link","type":"symlink","#));
+    assert!(lines[1].ends_with(r#""target":"file"}"#));
+    assert!(!output.stdout_str().contains('\x1b'));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_ls_json_recursive_has_no_headings() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir_all("a/b");
+    at.touch("a/b/c");
+
+    let output = ucmd.args(&["--json", "-R", "a"]).succeeds();
+    let lines: Vec<&str> = output.stdout_str().lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(
+        lines
+            .iter()
+            .all(|line| line.starts_with('{') && line.ends_with('}'))
+    );
+    assert!(lines[1].contains(r#""path":"a/b/c""#));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_ls_json_non_utf8_name() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("dir");
+    at.touch(OsStr::from_bytes(b"dir/a\xff\xc3"));
+
+    ucmd.args(&["--json", "dir"])
+        .succeeds()
+        .stdout_contains(r#"{"name":"a\udcff\udcc3","path":"dir/a\udcff\udcc3","#);
+}
+
+/// The exit code for each class of failure, as GNU's: 2 for serious trouble,
+/// 1 for minor problems.
+#[test]
+fn test_exit_code_matrix() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.mkdir("dir");
+    #[cfg(unix)]
+    scene.fixtures.symlink_file("missing", "dir/dangling");
+
+    let cases: &[(&[&str], i32)] = &[
+        // usage
+        (&["--bogus"], 2),
+        (&["--width=x"], 2),
+        (&["--block-size=x"], 2),
+        // operand
+        (&["missing"], 2),
+        (&["dir", "missing"], 2),
+        // nested
+        #[cfg(unix)]
+        (&["-lL", "dir"], 1),
+    ];
+    for (args, code) in cases {
+        scene.ucmd().args(args).fails_with_code(*code);
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_write_error() {
+    let scene = TestScenario::new(util_name!());
+    scene.fixtures.mkdir("dir");
+    scene.fixtures.touch("dir/file");
+
+    for args in [&["dir"][..], &["-l", "dir"], &["-R", "."]] {
+        scene
+            .ucmd()
+            .args(args)
+            .set_stdout(std::fs::File::create("/dev/full").unwrap())
+            .fails_with_code(2)
+            .stderr_is("ls: write error: No space left on device\n");
+    }
+}
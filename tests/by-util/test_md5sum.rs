@@ -3,6 +3,7 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 
+use uutests::at_and_ucmd;
 use uutests::new_ucmd;
 use uutests::util::TestScenario;
 use uutests::util_name;
@@ -138,6 +139,78 @@ fn test_check_md5_ignore_missing() {
         );
 }
 
+#[test]
+fn test_check_md5_fail_fast() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+
+    at.write("a", "foobar\n");
+    at.write("b", "foobar\n");
+    at.write(
+        "sums",
+        "14758f1afd44c09b7992073ccf00b43d  a\n\
+         00000000000000000000000000000000  b\n\
+         00000000000000000000000000000000  a\n",
+    );
+
+    scene
+        .ccmd("md5sum")
+        .args(&["-c", "sums"])
+        .fails()
+        .stdout_is("a: OK\nb: FAILED\na: FAILED\n")
+        .stderr_is("md5sum: WARNING: 2 computed checksums did NOT match\n");
+
+    // Neither the rest of the file nor the next file are checked.
+    scene
+        .ccmd("md5sum")
+        .args(&["-c", "--fail-fast", "sums", "sums"])
+        .fails()
+        .stdout_is("a: OK\nb: FAILED\n")
+        .stderr_is("md5sum: WARNING: 1 computed checksum did NOT match\n");
+
+    scene
+        .ccmd("md5sum")
+        .args(&["--fail-fast", "a"])
+        .fails()
+        .stderr_contains(
+            "md5sum: the --fail-fast option is meaningful only when verifying checksums",
+        );
+}
+
+#[test]
+fn test_check_md5_in_parallel_keeps_order() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+
+    let names: Vec<_> = (0..300).map(|i| format!("f{i}")).collect();
+    for (i, name) in names.iter().enumerate() {
+        at.write(name, &"x".repeat(i * 100));
+    }
+    let mut sums = scene
+        .ccmd("md5sum")
+        .args(&names)
+        .succeeds()
+        .stdout_move_str();
+    sums.push_str("14758f1afd44c09b7992073ccf00b43d  missing\n");
+    sums.push_str("14758f1afd44c09b7992073ccf00b43d  f7\n");
+    at.write("sums", &sums);
+
+    let mut expected: Vec<_> = names.iter().map(|name| format!("{name}: OK")).collect();
+    expected.extend(["missing: FAILED open or read".into(), "f7: FAILED".into()]);
+    expected.push(String::new());
+    scene
+        .ccmd("md5sum")
+        .env("RAYON_NUM_THREADS", "4")
+        .args(&["-c", "sums"])
+        .fails()
+        .stdout_is(expected.join("\n"))
+        .stderr_is(
+            "md5sum: missing: No such file or directory\n\
+             md5sum: WARNING: 1 computed checksum did NOT match\n\
+             md5sum: WARNING: 1 listed file could not be read\n",
+        );
+}
+
 // Asterisk `*` is a reserved paths character on win32, nor the path can end with a whitespace.
 // ref: https://learn.microsoft.com/en-us/windows/win32/fileio/naming-a-file#naming-conventions
 #[test]
@@ -308,6 +381,21 @@ fn test_invalid_arg() {
 fn test_conflicting_arg() {
     new_ucmd!().arg("--tag").arg("--check").fails_with_code(1);
     new_ucmd!().arg("--tag").arg("--text").fails_with_code(1);
+    new_ucmd!()
+        .arg("--ta")
+        .arg("-t")
+        .fails_with_code(1)
+        .stderr_contains("--tag does not support --text mode");
+}
+
+#[test]
+fn test_output_flags_after_double_dash_are_files() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.touch("--text");
+
+    ucmd.args(&["--tag", "--", "--text"])
+        .succeeds()
+        .stdout_only("MD5 (--text) = d41d8cd98f00b204e9800998ecf8427e\n");
 }
 
 #[test]
@@ -989,3 +989,50 @@ fn test_mkdir_concurrent_creation() {
         assert!(at.dir_exists(&path_str));
     }
 }
+
+#[cfg(not(windows))]
+#[test]
+fn test_mkdir_mode_special_bits() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    for (mode, expected) in [
+        ("2755", 0o2755),
+        ("4755", 0o4755),
+        ("1777", 0o1777),
+        ("u+s,g+s", 0o6777),
+    ] {
+        let dir = format!("dir_{mode}");
+        scene
+            .ucmd()
+            .args(&["-m", mode, &dir])
+            .umask(0o077)
+            .succeeds();
+        let perms = at.metadata(&dir).permissions().mode() & 0o7777;
+        assert_eq!(perms, expected, "mode {mode} gave {perms:o}");
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_mkdir_mode_setgid_parent() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.mkdir("sg");
+    at.set_mode("sg", 0o2775);
+    // The set-group-ID bit of the parent is kept unless the mode names it
+    for (mode, expected) in [
+        ("755", 0o2755),
+        ("=rwx", 0o2700),
+        ("00755", 0o755),
+        ("g-s", 0o777),
+    ] {
+        let dir = format!("sg/dir_{mode}");
+        scene
+            .ucmd()
+            .args(&["-m", mode, &dir])
+            .umask(0o077)
+            .succeeds();
+        let perms = at.metadata(&dir).permissions().mode() & 0o7777;
+        assert_eq!(perms, expected, "mode {mode} gave {perms:o}");
+    }
+}
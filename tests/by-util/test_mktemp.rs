@@ -1174,3 +1174,14 @@ fn test_non_utf8_tmpdir_directory_creation() {
     // but we can verify the command succeeds
     ucmd.arg("-d").arg("-p").arg(at.plus(dir_name)).succeeds();
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_directory_setgid_parent() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("sg");
+    at.set_mode("sg", 0o2775);
+    let result = ucmd.args(&["-d", "-p", "sg", "XXX"]).succeeds();
+    let dirname = result.no_stderr().stdout_str().trim_end();
+    assert_eq!(at.metadata(dirname).permissions().mode(), 0o42700);
+}
@@ -2472,6 +2472,30 @@ mod inter_partition_copying {
         let moved_fifo = other_fs_tempdir.path().join("dir/fifo");
         assert!(moved_fifo.symlink_metadata().unwrap().file_type().is_fifo());
     }
+
+    // The bar drawn by --progress for a file copied across partitions stays
+    // off standard error when that isn't a terminal.
+    #[test]
+    pub(crate) fn test_mv_progress_across_partitions() {
+        let scene = TestScenario::new(util_name!());
+        let at = &scene.fixtures;
+
+        at.write("src", "src contents");
+        let other_fs_tempdir =
+            TempDir::new_in("/dev/shm/").expect("Unable to create temp directory");
+        let dest = other_fs_tempdir.path().join("dest");
+
+        scene
+            .ucmd()
+            .arg("--progress")
+            .arg("src")
+            .arg(dest.to_str().unwrap())
+            .succeeds()
+            .no_output();
+
+        assert!(!at.file_exists("src"));
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "src contents");
+    }
 }
 
 #[test]
@@ -649,6 +649,118 @@ fn test_round_with_to_unit() {
     }
 }
 
+#[test]
+fn test_round_with_from() {
+    // a suffix drops the precision of the input, without one it is kept
+    for (method, exp) in [
+        ("from-zero", ["1250", "2", "-2", "1.25"]),
+        ("towards-zero", ["1250", "1", "-1", "1.25"]),
+        ("up", ["1250", "2", "-1", "1.25"]),
+        ("down", ["1250", "1", "-2", "1.25"]),
+        ("nearest", ["1250", "2", "-2", "1.25"]),
+    ] {
+        new_ucmd!()
+            .args(&[
+                "--from=si",
+                &format!("--round={method}"),
+                "--",
+                "1.25K",
+                "0.0015K",
+                "-0.0015K",
+                "1.25",
+            ])
+            .succeeds()
+            .stdout_only(exp.join("\n") + "\n");
+    }
+}
+
+#[test]
+fn test_round_with_to_and_precision() {
+    // numbers are only rounded as far as the scaling reaches, and printed
+    // with the requested precision
+    for (method, exp) in [
+        ("from-zero", ["2.00", "-2.00", "1.24k", "-1.24k"]),
+        ("towards-zero", ["1.00", "-1.00", "1.23k", "-1.23k"]),
+        ("up", ["2.00", "-1.00", "1.24k", "-1.23k"]),
+        ("down", ["1.00", "-2.00", "1.23k", "-1.24k"]),
+        ("nearest", ["1.00", "-1.00", "1.23k", "-1.23k"]),
+    ] {
+        new_ucmd!()
+            .args(&[
+                "--to=si",
+                "--format=%.2f",
+                &format!("--round={method}"),
+                "--",
+                "1.005",
+                "-1.005",
+                "1234",
+                "-1234",
+            ])
+            .succeeds()
+            .stdout_only(exp.join("\n") + "\n");
+    }
+
+    new_ucmd!()
+        .args(&["--to=si", "--format=%.5f", "1234", "1234567"])
+        .succeeds()
+        .stdout_only("1.23400k\n1.23457M\n");
+    new_ucmd!()
+        .args(&["--to=si", "--format=%.0f", "1500", "2500"])
+        .succeeds()
+        .stdout_only("2k\n3k\n");
+}
+
+#[test]
+fn test_round_unscaled_to_half_even() {
+    // numbers that are not scaled keep one decimal when rounded, which is
+    // dropped when printing, rounding half to even
+    new_ucmd!()
+        .args(&["--to=si", "--", "0.5", "1.5", "2.5", "3.5", "-2.5", "4.5"])
+        .succeeds()
+        .stdout_only("0\n2\n2\n4\n-2\n4\n");
+    new_ucmd!()
+        .args(&["--to=iec", "--round=up", "2.01"])
+        .succeeds()
+        .stdout_only("2\n");
+}
+
+#[test]
+fn test_round_to_zero_is_positive() {
+    new_ucmd!()
+        .args(&["--to-unit=1000", "--round=up", "--", "-1.5"])
+        .succeeds()
+        .stdout_only("0.0\n");
+    new_ucmd!()
+        .args(&["--to=si", "--round=up", "--", "-0.04"])
+        .succeeds()
+        .stdout_only("0\n");
+}
+
+#[test]
+fn test_round_decimal_input_exactly() {
+    // 9.95 has no exact binary representation, but is not rounded down to 9.94
+    new_ucmd!()
+        .args(&["--round=down", "9.95"])
+        .succeeds()
+        .stdout_only("9.95\n");
+    new_ucmd!()
+        .args(&["--to=si", "--round=down", "--format=%.2f", "9950"])
+        .succeeds()
+        .stdout_only("9.95k\n");
+}
+
+#[test]
+fn test_to_unit_with_to() {
+    new_ucmd!()
+        .args(&["--to-unit=1000", "--to=si", "1500000"])
+        .succeeds()
+        .stdout_only("1.5k\n");
+    new_ucmd!()
+        .args(&["--to-unit=1024", "--to=iec", "3000000"])
+        .succeeds()
+        .stdout_only("2.9K\n");
+}
+
 #[test]
 fn test_suffix_is_added_if_not_supplied() {
     new_ucmd!()
@@ -871,6 +871,50 @@ fn test_skip_bytes_error() {
         .failure();
 }
 
+#[test]
+fn test_skip_bytes_past_end_of_combined_input() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "abc");
+    at.write("b", "defgh");
+    ucmd.args(&["-c", "-j", "9", "a", "b"])
+        .fails_with_code(1)
+        .stderr_only("od: cannot skip past end of combined input\n");
+}
+
+#[test]
+fn test_skip_bytes_after_missing_input() {
+    // the final offset is printed as long as one of the inputs could be read
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "abc");
+    at.write("b", "defgh");
+    ucmd.args(&["-c", "-j", "2", "missing", "a", "b"])
+        .fails_with_code(1)
+        .stdout_is("0000002   c   d   e   f   g   h\n0000010\n")
+        .stderr_is("od: missing: No such file or directory\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_skip_bytes_seeks_stdin() {
+    let scene = TestScenario::new(util_name!());
+    let input: Vec<u8> = (0..10000).map(|i| (i % 251) as u8).collect();
+    scene.fixtures.write_bytes("f1", &input);
+    let file = scene.fixtures.open("f1");
+    let mut file_shadow = file.try_clone().unwrap();
+
+    scene
+        .ucmd()
+        .args(&["-An", "-tx1", "-j", "5000", "-N", "4"])
+        .set_stdin(file)
+        .succeeds()
+        .stdout_only(" e7 e8 e9 ea\n");
+
+    // the skipped bytes were seeked over and only those printed were read
+    let mut rest = vec![];
+    file_shadow.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, &input[5004..]);
+}
+
 #[test]
 fn test_read_bytes() {
     let scene = TestScenario::new(util_name!());
@@ -672,12 +672,7 @@ fn test_columns() {
     let whitespace = " ".repeat(50);
     let datetime_pattern = r"\d\d\d\d-\d\d-\d\d \d\d:\d\d";
     let header = format!("\n\n{datetime_pattern}{whitespace}Page 1\n\n\n");
-    // TODO Our output still does not match the behavior of GNU
-    // pr. The correct output should be:
-    //
-    //     "a\t\t\t\t    b\n";
-    //
-    let data = "a                                  \tb                                  \n";
+    let data = "a\t\t\t\t    b\n";
     let blank_lines_60 = "\n".repeat(60);
     let pattern = format!("{header}{data}{blank_lines_60}");
     let regex = Regex::new(&pattern).unwrap();
@@ -700,15 +695,8 @@ fn test_merge() {
     let whitespace = " ".repeat(50);
     let datetime_pattern = r"\d\d\d\d-\d\d-\d\d \d\d:\d\d";
     let header = format!("\n\n{datetime_pattern}{whitespace}Page 1\n\n\n");
-    // TODO Our output still does not match the behavior of GNU
-    // pr. The correct output should be:
-    //
-    //     "a\t\t\t\t    b\n";
-    //
-    // and the blank lines should actually be empty lines.
-    let data = "a                                  \tb                                  \n";
-    let blank_lines_55 =
-        "                                   \t                                   \n".repeat(55);
+    let data = "a\t\t\t\t    b\n";
+    let blank_lines_55 = "\n".repeat(55);
     let footer = "\n".repeat(5);
     let pattern = format!("{header}{data}{blank_lines_55}{footer}");
     let regex = Regex::new(&pattern).unwrap();
@@ -731,13 +719,13 @@ fn test_merge_one_long_one_short() {
     let whitespace = " ".repeat(50);
     let datetime_pattern = r"\d\d\d\d-\d\d-\d\d \d\d:\d\d";
     let header = format!("\n\n{datetime_pattern}{whitespace}Page 1\n\n\n");
-    let data = "a                                  \tb                                  \n";
+    let data = "a\t\t\t\t    b\n";
     let footer = "\n".repeat(5);
     let page1 = format!("{header}{data}{footer}");
 
     // Page 2 should have just the second line of `f`.
     let header = format!("\n\n{datetime_pattern}{whitespace}Page 2\n\n\n");
-    let data = "a                                  \t                                   \n";
+    let data = "a\t\t\t\t    \n";
     let page2 = format!("{header}{data}{footer}");
 
     let pattern = format!("{page1}{page2}");
@@ -757,6 +745,71 @@ fn test_merge_one_long_one_short() {
         .stdout_matches(&regex);
 }
 
+#[test]
+fn test_number_lines_in_columns() {
+    // Each column holds its own line numbers, and the page width is shared
+    // between them. Like the margin, the white space is written with tabs.
+    let input = "abcdefghijklmnopqrstuvwxyz\nshort\n1\n2\n";
+    new_ucmd!()
+        .args(&["-t", "-l2", "-2", "-n", "-W30"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_str_check(|s| {
+            s.starts_with("    1\tabcdef \t   3   1\n    2\tshort  \t   4   2\n")
+        });
+    new_ucmd!()
+        .args(&["-t", "-l2", "-2", "-n:", "-o3", "-w30"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_str_check(|s| {
+            s.starts_with("       1:abcdefgh     3:1\n       2:short\t      4:2\n")
+        });
+}
+
+#[test]
+fn test_page_width_single_column() {
+    let input = "abcdefghijklmnopqrstuvwxyz\nshort\n";
+    // -W truncates, counting the line number...
+    new_ucmd!()
+        .args(&["-t", "-l2", "-n", "-W12"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_str_check(|s| s.starts_with("    1\tabcd\n    2\tshor\n"));
+    // ...and -w only applies to multi-column output.
+    new_ucmd!()
+        .args(&["-t", "-l2", "-n", "-w12"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_str_check(|s| s.starts_with("    1\tabcdefghijklmnopqrstuvwxyz\n    2\tshort\n"));
+}
+
+#[test]
+fn test_column_separator_without_width() {
+    let input = "abcdefghijklmnopqrstuvwxyz\nshort\n1\n2\n";
+    // Without a width, -s leaves the columns unaligned, but -S does not.
+    new_ucmd!()
+        .args(&["-t", "-l2", "-2", "-s|"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_str_check(|s| s.starts_with("abcdefghijklmnopqrstuvwxyz|1\nshort|2\n"));
+    new_ucmd!()
+        .args(&["-t", "-l2", "-2", "-S|"])
+        .pipe_in(input)
+        .succeeds()
+        .stdout_str_check(|s| {
+            s.starts_with("abcdefghijklmnopqrstuvwxyz\t   |1\nshort\t\t\t\t   |2\n")
+        });
+}
+
+#[test]
+fn test_page_width_too_narrow() {
+    new_ucmd!()
+        .args(&["-3", "-w4"])
+        .pipe_in("a\n")
+        .fails()
+        .stderr_is("pr: page width too narrow\n");
+}
+
 #[test]
 fn test_simple_expand_tab() {
     let whitespace = " ".repeat(50);
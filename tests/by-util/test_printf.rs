@@ -1505,3 +1505,33 @@ fn test_q_string_control_chars_with_quotes() {
         .succeeds()
         .stdout_only("''$'\\001'\\'''$'\\001'");
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_failed_write_is_reported() {
+    // without a trailing newline the error only surfaces on the final flush
+    for format in ["hello", "hello\n"] {
+        new_ucmd!()
+            .arg(format)
+            .set_stdout(std::fs::File::create("/dev/full").unwrap())
+            .fails_with_code(1)
+            .stderr_is("printf: write error: No space left on device\n");
+    }
+}
+
+#[test]
+fn test_operands_are_not_options() {
+    new_ucmd!()
+        .args(&["%s\n", "--help", "--version"])
+        .succeeds()
+        .stdout_only("--help\n--version\n");
+    // only a `--` before FORMAT is skipped
+    new_ucmd!()
+        .args(&["--", "%s\n", "--"])
+        .succeeds()
+        .stdout_only("--\n");
+    new_ucmd!()
+        .args(&["--", "--help"])
+        .succeeds()
+        .stdout_only("--help");
+}
@@ -38,6 +38,20 @@ fn test_resolve() {
     ucmd.arg("bar").succeeds().stdout_contains("foo\n");
 }
 
+#[test]
+fn test_resolve_unquoted() {
+    let (at, mut ucmd) = at_and_ucmd!();
+
+    at.touch("it's");
+    at.relative_symlink_file("it's", "bar");
+
+    // unlike `stat %N` and `ls -l`, the target is printed as it is
+    ucmd.env("QUOTING_STYLE", "locale")
+        .arg("bar")
+        .succeeds()
+        .stdout_only("it's\n");
+}
+
 #[test]
 fn test_canonicalize() {
     let (at, mut ucmd) = at_and_ucmd!();
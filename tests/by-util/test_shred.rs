@@ -61,6 +61,28 @@ fn test_shred_remove() {
     assert!(!at.file_exists(file));
 }
 
+#[test]
+fn test_shred_sync() {
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    at.write("kept", "data");
+    at.write("removed", "data");
+
+    scene
+        .ucmd()
+        .args(&["--sync", "kept"])
+        .succeeds()
+        .no_output();
+    assert_ne!(at.read_bytes("kept"), b"data");
+
+    scene
+        .ucmd()
+        .args(&["--sync", "--remove", "removed"])
+        .succeeds()
+        .no_output();
+    assert!(!at.file_exists("removed"));
+}
+
 #[test]
 fn test_shred_remove_unlink() {
     // spell-checker:disable-next-line
@@ -441,6 +441,36 @@ fn test_head_count() {
     );
 }
 
+#[test]
+fn test_head_count_sample_pipe() {
+    // Most of the lines are read after the sample is full, and the last one
+    // has no separator.
+    let input = (1..=10_000)
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join("\0");
+
+    let result = new_ucmd!()
+        .args(&["-z", "-n", "3"])
+        .pipe_in(input.as_bytes())
+        .succeeds();
+    result.no_stderr();
+
+    let mut result_seq: Vec<u32> = result
+        .stdout_str()
+        .split_terminator('\0')
+        .map(|x| x.parse().unwrap())
+        .collect();
+    result_seq.sort_unstable();
+    result_seq.dedup();
+    assert_eq!(result_seq.len(), 3, "Output is not 3 distinct lines");
+    assert!(
+        result_seq.iter().all(|x| (1..=10_000).contains(x)),
+        "Output includes element not from input: {}",
+        result.stdout_str()
+    );
+}
+
 #[test]
 fn test_zero_head_count_pipe() {
     let result = new_ucmd!().arg("-n0").pipe_in(vec![]).succeeds();
@@ -486,6 +516,17 @@ fn test_zero_head_count_file_touch_output_positive_new() {
     );
 }
 
+#[test]
+fn test_output_is_input() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("file", "a\nb\nc\n");
+
+    ucmd.args(&["-o", "file", "file"]).succeeds().no_output();
+    let mut lines: Vec<_> = at.read("file").lines().map(String::from).collect();
+    lines.sort();
+    assert_eq!(lines, ["a", "b", "c"]);
+}
+
 #[test]
 fn test_zero_head_count_file_touch_output_positive_existing() {
     let (at, mut ucmd) = at_and_ucmd!();
@@ -657,6 +698,20 @@ fn test_repeat_multi() {
     );
 }
 
+#[test]
+#[cfg(unix)]
+fn test_repeat_without_count_streams_until_sigpipe() {
+    use std::os::unix::process::ExitStatusExt;
+
+    // More than one output buffer's worth, so lines have to be streamed out
+    // while sampling is still going on.
+    let expected = "x\n".repeat(64 * 1024);
+    let result = new_ucmd!()
+        .args(&["-r", "-e", "x"])
+        .run_stdout_starts_with(expected.as_bytes());
+    assert_eq!(result.exit_status().signal(), Some(libc::SIGPIPE));
+}
+
 #[test]
 fn test_file_input() {
     let expected_seq = vec![11, 12, 13, 14, 15, 16, 17, 18, 19, 20];
@@ -984,26 +1039,47 @@ fn test_gnu_compat_limited_from_file() {
         .stdout_is("7\n1\n2\n5\n3\n");
 }
 
-// This specific case causes GNU to give different results than other modes.
-#[ignore = "disabled until fixed"]
+// With --head-count GNU samples a pipe while reading it, which gives different
+// results than other modes.
 #[test]
-fn test_gnu_compat_limited_from_stdin() {
+fn test_gnu_compat_limited_from_pipe() {
     let (at, mut ucmd) = at_and_ucmd!();
     at.append_bytes(
         "random_bytes.bin",
         b"\xd1\xfd\xb9\x9a\xf5\x81\x71\x42\xf9\x7a\x59\x79\xd4\x9c\x8c\x7d",
     );
 
-    at.append("input.txt", "1\n2\n3\n4\n5\n6\n7\n");
-
     ucmd.arg("--random-source=random_bytes.bin")
         .arg("-n7")
-        .set_stdin(at.open("input.txt"))
+        .pipe_in("1\n2\n3\n4\n5\n6\n7\n")
         .succeeds()
         .no_stderr()
         .stdout_is("6\n5\n1\n3\n2\n7\n4\n");
 }
 
+#[test]
+fn test_gnu_compat_sample_from_pipe() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.append_bytes(
+        "random_bytes.bin",
+        b"\x0d\x6e\xcf\x30\x91\xf2\x53\xb4\x15\x76\xd7\x38\x99\xfa\x5b\xbc\
+          \x1d\x7e\xdf\x40\xa1\x02\x63\xc4\x25\x86\xe7\x48\xa9\x0a\x6b\xcc\
+          \x2d\x8e\xef\x50\xb1\x12\x73\xd4\x35\x96\xf7\x58\xb9\x1a\x7b\xdc\
+          \x3d\x9e\xff\x60\xc1\x22\x83\xe4\x45\xa6\x07\x68\xc9\x2a\x8b\xec",
+    );
+
+    let mut input = String::new();
+    for n in 1..=20 {
+        writeln!(&mut input, "{n}").unwrap();
+    }
+    ucmd.arg("--random-source=random_bytes.bin")
+        .arg("-n3")
+        .pipe_in(input)
+        .succeeds()
+        .no_stderr()
+        .stdout_is("16\n9\n20\n");
+}
+
 #[test]
 fn test_gnu_compat_range_no_repeat() {
     let (at, mut ucmd) = at_and_ucmd!();
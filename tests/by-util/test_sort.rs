@@ -434,6 +434,20 @@ fn test_month_stable() {
     test_helper("month_stable", &["-Ms"]);
 }
 
+#[test]
+fn test_month_several_keys() {
+    new_ucmd!()
+        .args(&["-k2M", "-k1M"])
+        .pipe_in("x feb\nmar jan\nfeb jan\nzz FEB\n  apr  x\n")
+        .succeeds()
+        .stdout_is("  apr  x\nfeb jan\nmar jan\nx feb\nzz FEB\n");
+    new_ucmd!()
+        .args(&["-s", "-k2M"])
+        .pipe_in("x feb\nmar jan\nfeb jan\nzz FEB\n  apr  x\n")
+        .succeeds()
+        .stdout_is("  apr  x\nmar jan\nfeb jan\nx feb\nzz FEB\n");
+}
+
 #[test]
 fn test_default_unsorted_ints() {
     test_helper("default_unsorted_ints", &[""]);
@@ -477,6 +491,62 @@ fn test_non_printing_chars() {
         .stdout_only("a👦🏻aa\naaaa\n"); // spell-checker:disable-line
 }
 
+#[test]
+fn test_c_locale_byte_classes() {
+    // Only space, tab and newline are blanks; other whitespace is neither
+    // kept by -d nor skipped by -b.
+    new_ucmd!()
+        .pipe_in("\rb\na\n\x0cc\n")
+        .arg("-d")
+        .succeeds()
+        .stdout_only("a\n\rb\n\x0cc\n");
+    new_ucmd!()
+        .pipe_in(" \x0cb\n \ra\n")
+        .arg("-b")
+        .succeeds()
+        .stdout_only(" \x0cb\n \ra\n");
+    // DEL is not printable.
+    new_ucmd!()
+        .pipe_in("a\x7fc\nab\n")
+        .arg("-i")
+        .succeeds()
+        .stdout_only("ab\na\x7fc\n");
+}
+
+#[test]
+fn test_key_char_positions_multibyte() {
+    // Character positions count bytes in the C locale...
+    new_ucmd!()
+        .pipe_in("éa\neb\n")
+        .args(&["-k1.2,1.2"])
+        .succeeds()
+        .stdout_only("eb\néa\n");
+    // ...and UTF-8 characters in UTF-8 locales, where invalid bytes count
+    // as one character each.
+    new_ucmd!()
+        .env("LC_ALL", "en_US.UTF-8")
+        .pipe_in(&b"\xc3\xa9a\neb\n\xffc\n"[..])
+        .args(&["-k1.2,1.2"])
+        .succeeds()
+        .stdout_only_bytes(b"\xc3\xa9a\neb\n\xffc\n");
+}
+
+#[test]
+fn test_dictionary_and_non_printing_multibyte() {
+    new_ucmd!()
+        .env("LC_ALL", "en_US.UTF-8")
+        .pipe_in("é-b\ne-a\n«c»\n")
+        .arg("-d")
+        .succeeds()
+        .stdout_only("«c»\ne-a\né-b\n");
+    new_ucmd!()
+        .env("LC_ALL", "en_US.UTF-8")
+        .pipe_in(&b"x\xc2\x85z\nx\x85y\nx\xc3\xa9y\n"[..])
+        .arg("-i")
+        .succeeds()
+        .stdout_only_bytes(b"x\x85y\nx\xc2\x85z\nx\xc3\xa9y\n");
+}
+
 #[test]
 fn test_exponents_general() {
     test_helper("exponents_general", &["-g"]);
@@ -1171,6 +1241,97 @@ fn test_merge_batches() {
         .stdout_only_fixture("ext_sort.expected");
 }
 
+#[test]
+fn test_multiple_temporary_directories() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("tmp_a");
+    at.mkdir("tmp_b");
+    ucmd.args(&["ext_sort.txt", "-n", "-S", "150b", "--batch-size=3"])
+        .args(&["-T", "tmp_a", "-T", "tmp_b"])
+        .succeeds()
+        .stdout_only_fixture("ext_sort.expected");
+    assert!(
+        std::fs::read_dir(at.plus("tmp_a"))
+            .unwrap()
+            .next()
+            .is_none()
+    );
+    assert!(
+        std::fs::read_dir(at.plus("tmp_b"))
+            .unwrap()
+            .next()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_parallel_invalid() {
+    new_ucmd!()
+        .arg("--parallel=0")
+        .fails_with_code(2)
+        .stderr_only("sort: number in parallel must be nonzero\n");
+    new_ucmd!()
+        .arg("--parallel=x")
+        .fails_with_code(2)
+        .stderr_only("sort: invalid --parallel argument 'x'\n");
+    new_ucmd!()
+        .arg("--parallel=-1")
+        .fails_with_code(2)
+        .stderr_only("sort: invalid --parallel argument '-1'\n");
+}
+
+#[test]
+fn test_parallel_keys_match_single_thread() {
+    // large enough for the keys of a chunk to be extracted on several threads
+    let mut input = String::new();
+    for i in 0..100_000u32 {
+        writeln!(
+            input,
+            "{} k{} {}.5",
+            i.wrapping_mul(7919) % 1000,
+            i % 97,
+            i % 13
+        )
+        .unwrap();
+    }
+    for args in [
+        &["-n"][..],
+        &["-k2,2", "-k1,1n"],
+        &["-k3,3g", "-s"],
+        &["-h"],
+    ] {
+        let single = new_ucmd!()
+            .arg("--parallel=1")
+            .args(args)
+            .pipe_in(input.clone())
+            .succeeds()
+            .stdout_move_bytes();
+        new_ucmd!()
+            .arg("--parallel=4")
+            .args(args)
+            .pipe_in(input.clone())
+            .succeeds()
+            .stdout_is_bytes(single);
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn test_temporary_directories_used_in_turn() {
+    let ts = TestScenario::new("sort");
+    ts.fixtures.mkdir("tmp_a");
+    // the second directory is needed as soon as there are two runs to spill
+    for dirs in [["tmp_a", "missing"], ["missing", "tmp_a"]] {
+        ts.ucmd()
+            .args(&["ext_sort.txt", "-n", "-S", "150b"])
+            .args(&["-T", dirs[0], "-T", dirs[1]])
+            .fails_with_code(2)
+            .stderr_only(
+                "sort: cannot create temporary file in 'missing': No such file or directory\n",
+            );
+    }
+}
+
 #[test]
 fn test_batch_size_invalid() {
     new_ucmd!()
@@ -1347,6 +1508,29 @@ fn test_output_is_input() {
     assert_eq!(at.read("file"), input);
 }
 
+#[test]
+fn test_output_is_input_sorted_in_place() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("file", "c\nb\na\n");
+
+    ucmd.args(&["-o", "file", "file"]).succeeds().no_output();
+    assert_eq!(at.read("file"), "a\nb\nc\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_output_is_input_keeps_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("file", "b\na\n");
+    at.set_mode("file", 0o640);
+
+    ucmd.args(&["-o", "file", "file"]).succeeds().no_output();
+    assert_eq!(at.read("file"), "a\nb\n");
+    assert_eq!(at.metadata("file").permissions().mode() & 0o777, 0o640);
+}
+
 #[test]
 #[cfg(unix)]
 fn test_output_device() {
@@ -1666,6 +1850,30 @@ fn test_files0_from_zero_length() {
         .stderr_only("sort: -:2: invalid zero-length file name\n");
 }
 
+#[test]
+fn test_files0_from_read_error() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkdir("dir");
+    ucmd.args(&["--files0-from", "dir"])
+        .fails_with_code(2)
+        .stderr_only("sort: cannot read file names from 'dir'\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_files0_from_non_utf8_name() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    let name = OsStr::from_bytes(b"f\xff");
+    std::fs::write(at.plus(name), "b\na\n").unwrap();
+    ucmd.args(&["--files0-from", "-"])
+        .pipe_in(b"f\xff\0".to_vec())
+        .succeeds()
+        .stdout_only("a\nb\n");
+}
+
 #[test]
 // Test for GNU tests/sort/sort-float.sh
 fn test_g_float() {
@@ -2713,6 +2921,84 @@ fn test_locale_complex_utf8_sorting() {
         .stdout_is("apple\nApple\nbanana\nBanana\nzebra\nZebra\n");
 }
 
+#[test]
+fn test_debug_key_warnings() {
+    let warnings = |args: &[&str]| {
+        new_ucmd!()
+            .env("LC_ALL", "C")
+            .arg("--debug")
+            .args(args)
+            .arg("/dev/null")
+            .succeeds()
+            .stderr_move_str()
+    };
+    let byte_comparison = "sort: text ordering performed using simple byte comparison\n";
+    // character offsets within the first field are not affected by blanks
+    assert_eq!(warnings(&["-k1.2,1.3"]), byte_comparison);
+    assert_eq!(
+        warnings(&["-k2.2n"]),
+        format!(
+            "{byte_comparison}\
+             sort: leading blanks are significant in key 1; consider also specifying 'b'\n\
+             sort: key 1 is numeric and spans multiple fields\n\
+             sort: note numbers use '.' as a decimal point in this locale\n"
+        )
+    );
+    assert_eq!(
+        warnings(&["-t-", "-k1,1n"]),
+        format!("{byte_comparison}sort: note numbers use '.' as a decimal point in this locale\n")
+    );
+    assert_eq!(
+        warnings(&["-t.", "-k1,1n"]),
+        format!("{byte_comparison}sort: numbers use '.' as a decimal point in this locale\n")
+    );
+    assert_eq!(
+        warnings(&["-t.", "-k1n"]),
+        format!(
+            "{byte_comparison}\
+             sort: key 1 is numeric and spans multiple fields\n\
+             sort: field separator '.' is treated as a decimal point in numbers\n"
+        )
+    );
+    assert_eq!(
+        warnings(&["+1.2", "-2.3"]),
+        format!(
+            "{byte_comparison}\
+             sort: obsolescent key '+1 -3' used; consider '-k 2,3' instead\n\
+             sort: leading blanks are significant in key 1; consider also specifying 'b'\n"
+        )
+    );
+    assert_eq!(
+        warnings(&["+0"]),
+        format!(
+            "{byte_comparison}\
+             sort: obsolescent key '+0' used; consider '-k 1' instead\n\
+             sort: leading blanks are significant in key 1; consider also specifying 'b'\n"
+        )
+    );
+}
+
+#[test]
+fn test_debug_last_resort_annotation() {
+    // -r alone reverses the whole-line comparison, there is no last resort
+    new_ucmd!()
+        .args(&["--debug", "-r"])
+        .pipe_in("a\nb\n")
+        .succeeds()
+        .stdout_is("b\n_\na\n_\n");
+    new_ucmd!()
+        .args(&["--debug", "-k1"])
+        .pipe_in("b\na\n")
+        .succeeds()
+        .stdout_is("a\n_\n_\nb\n_\n_\n");
+    // a missing number is reported where the number would start
+    new_ucmd!()
+        .args(&["--debug", "-n"])
+        .pipe_in("  x\n")
+        .succeeds()
+        .stdout_is("  x\n  ^ no match for key\n___\n");
+}
+
 #[test]
 fn test_locale_posix_sort_debug_message() {
     new_ucmd!()
@@ -2751,3 +3037,134 @@ e f 5436 down data path1 path2 path3 path4 path5\n";
 }
 
 /* spell-checker: enable */
+
+/// Key options in many combinations, with the outputs of GNU sort.
+#[test]
+fn test_key_option_combinations() {
+    let input = "1K v1.10 x\n2e v1.9 y\n1E V1.9 x\n3k a1.2 z\n-1M v1.10 y\n1K V1.9~rc z\n 2e b-1.2 x\n10 v1.9 X\n";
+    for (args, expected) in [
+        (
+            "-k2,2hr -k1,1V",
+            "1E V1.9 x\n1K V1.9~rc z\n1K v1.10 x\n2e v1.9 y\n3k a1.2 z\n10 v1.9 X\n 2e b-1.2 x\n-1M v1.10 y\n",
+        ),
+        (
+            "-k1,1hr -k2,2V",
+            "1E V1.9 x\n3k a1.2 z\n1K V1.9~rc z\n1K v1.10 x\n10 v1.9 X\n 2e b-1.2 x\n2e v1.9 y\n-1M v1.10 y\n",
+        ),
+        (
+            "-k1,1hf -k2,2V",
+            "-1M v1.10 y\n10 v1.9 X\n1K V1.9~rc z\n1K v1.10 x\n3k a1.2 z\n1E V1.9 x\n 2e b-1.2 x\n2e v1.9 y\n",
+        ),
+        (
+            "-k1,1hfr -k2,2fV",
+            " 2e b-1.2 x\n2e v1.9 y\n1E V1.9 x\n3k a1.2 z\n1K V1.9~rc z\n1K v1.10 x\n10 v1.9 X\n-1M v1.10 y\n",
+        ),
+        (
+            "-k2,2fV -k1,1h",
+            "3k a1.2 z\n 2e b-1.2 x\n1K V1.9~rc z\n2e v1.9 y\n10 v1.9 X\n1E V1.9 x\n-1M v1.10 y\n1K v1.10 x\n",
+        ),
+        (
+            "-k2,2dV -k3,3r",
+            "1E V1.9 x\n1K V1.9~rc z\n3k a1.2 z\n 2e b-1.2 x\n2e v1.9 y\n10 v1.9 X\n-1M v1.10 y\n1K v1.10 x\n",
+        ),
+        (
+            "-k2,2Vr -k1,1bh",
+            "-1M v1.10 y\n1K v1.10 x\n2e v1.9 y\n10 v1.9 X\n 2e b-1.2 x\n3k a1.2 z\n1E V1.9 x\n1K V1.9~rc z\n",
+        ),
+        (
+            "-k1,1bh -k2,2Vfr -s",
+            "-1M v1.10 y\n2e v1.9 y\n 2e b-1.2 x\n10 v1.9 X\n1K v1.10 x\n1K V1.9~rc z\n3k a1.2 z\n1E V1.9 x\n",
+        ),
+        (
+            "-r -k2,2V -k1,1h",
+            "1K V1.9~rc z\n1E V1.9 x\n3k a1.2 z\n 2e b-1.2 x\n2e v1.9 y\n10 v1.9 X\n-1M v1.10 y\n1K v1.10 x\n",
+        ),
+        (
+            "-k1r",
+            "3k a1.2 z\n2e v1.9 y\n1K v1.10 x\n1K V1.9~rc z\n1E V1.9 x\n10 v1.9 X\n-1M v1.10 y\n 2e b-1.2 x\n",
+        ),
+        (
+            "-k3r -k2,2V",
+            "1K V1.9~rc z\n3k a1.2 z\n2e v1.9 y\n-1M v1.10 y\n1E V1.9 x\n 2e b-1.2 x\n1K v1.10 x\n10 v1.9 X\n",
+        ),
+        (
+            "-k3,3fr -k1,1bhr",
+            "3k a1.2 z\n1K V1.9~rc z\n2e v1.9 y\n-1M v1.10 y\n1E V1.9 x\n1K v1.10 x\n10 v1.9 X\n 2e b-1.2 x\n",
+        ),
+        (
+            "-h -k2,2V",
+            "1K V1.9~rc z\n1E V1.9 x\n3k a1.2 z\n 2e b-1.2 x\n10 v1.9 X\n2e v1.9 y\n-1M v1.10 y\n1K v1.10 x\n",
+        ),
+        (
+            "-k2V -k1hr -u",
+            "1K V1.9~rc z\n1E V1.9 x\n3k a1.2 z\n 2e b-1.2 x\n10 v1.9 X\n2e v1.9 y\n1K v1.10 x\n-1M v1.10 y\n",
+        ),
+        (
+            "-f -k2,2V -k1,1h",
+            "1K V1.9~rc z\n1E V1.9 x\n3k a1.2 z\n 2e b-1.2 x\n2e v1.9 y\n10 v1.9 X\n-1M v1.10 y\n1K v1.10 x\n",
+        ),
+    ] {
+        new_ucmd!()
+            .env("LC_ALL", "C")
+            .args(&args.split_whitespace().collect::<Vec<_>>())
+            .pipe_in(input)
+            .succeeds()
+            .stdout_only(expected);
+    }
+}
+
+#[test]
+fn test_random_last_resort() {
+    // Lines whose keys tie are ordered by their bytes, like GNU.
+    let result = new_ucmd!()
+        .args(&["-fR"])
+        .pipe_in("b\nA\nB\na\nc\nC\n")
+        .succeeds()
+        .stdout_move_str();
+    let lines: Vec<_> = result.lines().collect();
+    assert_eq!(lines.len(), 6);
+    for pair in lines.chunks(2) {
+        assert_eq!(pair[0], pair[1].to_uppercase(), "{result}");
+        assert!(pair[0] < pair[1], "{result}");
+    }
+}
+
+/// The exit code for each class of failure, as GNU's: 2 for all of them,
+/// while 1 reports disorder found by `-c`.
+#[test]
+fn test_exit_code_matrix() {
+    let ts = TestScenario::new("sort");
+    ts.fixtures.write("unsorted", "b\na\n");
+
+    let cases: &[(&[&str], i32)] = &[
+        // usage
+        (&["--bogus"], 2),
+        (&["-k", "0", "unsorted"], 2),
+        (&["-S", "x", "unsorted"], 2),
+        // operand
+        (&["missing"], 2),
+        (&["unsorted", "missing"], 2),
+        // disorder
+        (&["-c", "unsorted"], 1),
+    ];
+    for (args, code) in cases {
+        ts.ucmd().args(args).fails_with_code(*code);
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_write_error() {
+    let ts = TestScenario::new("sort");
+    ts.fixtures.write("unsorted", "b\na\n");
+
+    ts.ucmd()
+        .arg("unsorted")
+        .set_stdout(std::fs::File::create("/dev/full").unwrap())
+        .fails_with_code(2)
+        .stderr_is("sort: write failed: 'standard output': No space left on device\n");
+    ts.ucmd()
+        .args(&["-o", "/dev/full", "unsorted"])
+        .fails_with_code(2)
+        .stderr_is("sort: write failed: /dev/full: No space left on device\n");
+}
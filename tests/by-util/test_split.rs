@@ -292,6 +292,54 @@ fn test_split_additional_suffix_hyphen_value() {
     assert_eq!(glob.collate(), at.read_bytes(name));
 }
 
+#[test]
+#[cfg(unix)]
+fn test_preserve_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("in", "1\n2\n3\n");
+    // group and other write would be masked out by a usual umask
+    at.set_mode("in", 0o777);
+    ucmd.args(&["--preserve-mode", "-l", "2", "in"]).succeeds();
+
+    for name in ["xaa", "xab"] {
+        let mode = at.metadata(name).permissions().mode();
+        assert_eq!(mode & 0o7777, 0o777, "{name}");
+    }
+    assert_eq!(at.read("xab"), "3\n");
+}
+
+#[test]
+#[cfg(unix)]
+fn test_preserve_mode_number_round_robin() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("in", "1\n2\n3\n");
+    at.set_mode("in", 0o640);
+    ucmd.args(&["--preserve-mode", "-n", "r/3", "in"])
+        .succeeds();
+
+    for name in ["xaa", "xab", "xac"] {
+        let mode = at.metadata(name).permissions().mode();
+        assert_eq!(mode & 0o7777, 0o640, "{name}");
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn test_without_preserve_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("in", "1\n");
+    at.set_mode("in", 0o700);
+    ucmd.arg("in").succeeds();
+
+    assert_ne!(at.metadata("xaa").permissions().mode() & 0o100, 0o100);
+}
+
 // note: the test_filter* tests below are unix-only
 // windows support has been waived for now because of the difficulty of getting
 // the `cmd` call right
@@ -400,6 +448,39 @@ fn test_filter_with_kth_chunk() {
         .stderr_contains("--filter does not process a chunk extracted to stdout");
 }
 
+#[test]
+#[cfg(unix)]
+fn test_filter_number_numeric_suffixes() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    ucmd.args(&["--filter=cat > $FILE.out", "-n", "3", "-d", "-", "part"])
+        .pipe_in("abcdefgh")
+        .succeeds()
+        .no_output();
+    assert_eq!(at.read("part00.out"), "abc");
+    assert_eq!(at.read("part01.out"), "def");
+    assert_eq!(at.read("part02.out"), "gh");
+    assert!(!at.file_exists("part00"));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_filter_verbose() {
+    new_ucmd!()
+        .args(&["--filter=cat > /dev/null", "-n", "r/2", "--verbose"])
+        .pipe_in("a\nb\nc\n")
+        .succeeds()
+        .stdout_only("executing with FILE=xaa\nexecuting with FILE=xab\n");
+}
+
+#[test]
+fn test_verbose_number() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("in", "a\nb\nc\n");
+    ucmd.args(&["-n", "l/2", "--verbose", "in"])
+        .succeeds()
+        .stdout_only("creating file 'xaa'\ncreating file 'xab'\n");
+}
+
 #[test]
 fn test_split_lines_number() {
     // Test if stdout/stderr for '--lines' option is correct
@@ -1282,6 +1363,24 @@ fn test_number_by_lines_kth_no_end_sep() {
         .stdout_only("3\n4");
 }
 
+#[test]
+fn test_number_by_lines_fewer_bytes_than_chunks() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("in", "abc");
+    ucmd.args(&["-n", "l/7", "in"]).succeeds().no_output();
+    assert_eq!(at.read("xaa"), "abc");
+    for name in ["xab", "xac", "xad", "xae", "xaf", "xag"] {
+        assert_eq!(at.read(name), "");
+    }
+    assert!(!at.file_exists("xah"));
+
+    new_ucmd!()
+        .args(&["-n", "l/7/7"])
+        .pipe_in("abc")
+        .succeeds()
+        .no_output();
+}
+
 #[test]
 fn test_number_by_lines_rr_kth_no_end_sep() {
     new_ucmd!()
@@ -447,6 +447,89 @@ fn test_quoting_style_locale() {
         .stdout_only("\'\"\'\n");
 }
 
+#[cfg(unix)]
+#[test]
+fn test_quoting_style_symlink() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.touch("it's");
+    at.touch("new\nline");
+    at.relative_symlink_file("it's", "lq");
+    at.relative_symlink_file("new\nline", "lnl");
+
+    for (style, expected) in [
+        (None, "'lq' -> \"it's\"\n'lnl' -> 'new'$'\\n''line'\n"),
+        (Some("locale"), "'lq' -> 'it\\'s'\n'lnl' -> 'new\\nline'\n"),
+        (Some("c"), "\"lq\" -> \"it's\"\n\"lnl\" -> \"new\\nline\"\n"),
+        (Some("escape"), "lq -> it's\nlnl -> new\\nline\n"),
+        (Some("shell"), "lq -> \"it's\"\nlnl -> 'new\nline'\n"),
+        (Some("literal"), "lq -> it's\nlnl -> new\nline\n"),
+    ] {
+        let mut cmd = ts.ucmd();
+        if let Some(style) = style {
+            cmd.env("QUOTING_STYLE", style);
+        }
+        cmd.args(&["-c", "%N", "lq", "lnl"])
+            .succeeds()
+            .stdout_only(expected);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_quoting_style_default_format() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.touch("new\nline");
+    at.relative_symlink_file("new\nline", "lnl");
+
+    // GNU prints the names unquoted, whatever QUOTING_STYLE says
+    ts.ucmd()
+        .env("QUOTING_STYLE", "c")
+        .arg("lnl")
+        .succeeds()
+        .stdout_contains("  File: lnl -> new\nline\n");
+}
+
+#[test]
+fn test_quoting_style_invalid() {
+    let ts = TestScenario::new(util_name!());
+    ts.fixtures.touch("f");
+    ts.ucmd()
+        .env("QUOTING_STYLE", "bogus")
+        .args(&["-c", "%N", "f"])
+        .succeeds()
+        .stdout_is("'f'\n")
+        .stderr_is("stat: ignoring invalid value of environment variable QUOTING_STYLE: 'bogus'\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dereference_describes_target() {
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.write("target", "data");
+    at.symlink_file("target", "link");
+
+    let target = ts
+        .ucmd()
+        .args(&["-c", "%i %F %s", "target"])
+        .succeeds()
+        .stdout_move_str();
+    ts.ucmd()
+        .args(&["-L", "-c", "%i %F %s", "link"])
+        .succeeds()
+        .stdout_only(&target);
+    ts.ucmd()
+        .args(&["-L", "-c", "%N", "link"])
+        .succeeds()
+        .stdout_only("'link'\n");
+    ts.ucmd()
+        .args(&["-c", "%F", "link"])
+        .succeeds()
+        .stdout_only("symbolic link\n");
+}
+
 #[test]
 fn test_printf_octal_1() {
     let ts = TestScenario::new(util_name!());
@@ -632,3 +715,35 @@ fn test_correct_metadata() {
         assert_eq!(output, &expected);
     }
 }
+
+#[test]
+fn test_json() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("file", "abc");
+    let uid = metadata(at.plus("file")).unwrap().uid();
+
+    let output = ucmd.args(&["--json", "file"]).succeeds();
+    let line = output.stdout_str().trim_end();
+    assert!(line.starts_with(r#"{"name":"file","type":"file","size":3,"#));
+    assert!(line.contains(&format!(r#""uid":{uid},"#)));
+    assert!(line.ends_with(r#""target":null}"#));
+}
+
+#[test]
+fn test_json_file_system() {
+    new_ucmd!()
+        .args(&["--json", "-f", "."])
+        .succeeds()
+        .stdout_matches(
+            &regex::Regex::new(r#"^\{"name":"\.","id":\d+,"type":".*"inodes_free":\d+\}\n$"#)
+                .unwrap(),
+        );
+}
+
+#[test]
+fn test_json_conflicts_with_format() {
+    new_ucmd!()
+        .args(&["--json", "-c", "%n", "."])
+        .fails_with_code(1)
+        .stderr_contains("cannot be used with");
+}
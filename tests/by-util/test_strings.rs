@@ -0,0 +1,219 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+use uutests::at_and_ucmd;
+use uutests::new_ucmd;
+
+#[test]
+fn test_invalid_arg() {
+    new_ucmd!().arg("--definitely-invalid").fails_with_code(1);
+}
+
+#[test]
+fn test_stdin() {
+    new_ucmd!()
+        .pipe_in(b"\x00\x01hello\x02abc\x7fworld!\n\x03".as_slice())
+        .succeeds()
+        .stdout_only("hello\nworld!\n");
+}
+
+#[test]
+fn test_file() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write_bytes("a.bin", b"\x00\x00binary\x00data\xff\x00\x00");
+
+    ucmd.arg("a.bin").succeeds().stdout_only("binary\ndata\n");
+}
+
+#[test]
+fn test_string_at_end_of_input() {
+    new_ucmd!()
+        .pipe_in(b"\x00tail".as_slice())
+        .succeeds()
+        .stdout_only("tail\n");
+}
+
+#[test]
+fn test_tab_is_printable() {
+    new_ucmd!()
+        .pipe_in(b"\x00a\tbc\x00".as_slice())
+        .succeeds()
+        .stdout_only("a\tbc\n");
+}
+
+#[test]
+fn test_min_length() {
+    for args in [["-n", "6"], ["--bytes", "6"]] {
+        new_ucmd!()
+            .args(&args)
+            .pipe_in(b"short\x00longer\x00".as_slice())
+            .succeeds()
+            .stdout_only("longer\n");
+    }
+}
+
+#[test]
+fn test_obsolete_min_length() {
+    new_ucmd!()
+        .arg("-2")
+        .pipe_in(b"ab\x00c\x00".as_slice())
+        .succeeds()
+        .stdout_only("ab\n");
+}
+
+#[test]
+fn test_invalid_min_length() {
+    for length in ["0", "x"] {
+        new_ucmd!()
+            .args(&["-n", length])
+            .pipe_in("")
+            .fails_with_code(1)
+            .stderr_only(format!("strings: invalid minimum string length {length}\n"));
+    }
+}
+
+#[test]
+fn test_radix() {
+    let input = b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00first\x00second";
+    for (radix, expected) in [
+        ("o", "     12 first\n     20 second\n"),
+        ("d", "     10 first\n     16 second\n"),
+        ("x", "      a first\n     10 second\n"),
+    ] {
+        new_ucmd!()
+            .args(&["-t", radix])
+            .pipe_in(input.as_slice())
+            .succeeds()
+            .stdout_only(expected);
+    }
+    new_ucmd!()
+        .arg("-o")
+        .pipe_in(input.as_slice())
+        .succeeds()
+        .stdout_only("     12 first\n     20 second\n");
+}
+
+#[test]
+fn test_invalid_radix() {
+    new_ucmd!()
+        .args(&["-t", "b"])
+        .pipe_in("")
+        .fails_with_code(1);
+}
+
+#[test]
+fn test_print_file_name() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write_bytes("a", b"first\x00");
+    at.write_bytes("b", b"second\x00");
+
+    ucmd.args(&["-f", "a", "b"])
+        .succeeds()
+        .stdout_only("a: first\nb: second\n");
+}
+
+#[test]
+fn test_print_file_name_stdin() {
+    new_ucmd!()
+        .arg("-f")
+        .pipe_in("text")
+        .succeeds()
+        .stdout_only("{standard input}: text\n");
+}
+
+#[test]
+fn test_output_separator() {
+    new_ucmd!()
+        .args(&["-s", "|"])
+        .pipe_in(b"first\x00second".as_slice())
+        .succeeds()
+        .stdout_only("first|second|");
+}
+
+#[test]
+fn test_include_all_whitespace() {
+    new_ucmd!()
+        .pipe_in("two\nlines\n")
+        .succeeds()
+        .stdout_only("lines\n");
+    new_ucmd!()
+        .arg("-w")
+        .pipe_in("two\nlines\n")
+        .succeeds()
+        .stdout_only("two\nlines\n\n");
+}
+
+#[test]
+fn test_eight_bit_encoding() {
+    new_ucmd!()
+        .args(&["-e", "S"])
+        .pipe_in(b"\x00ab\xe9cd\x00".as_slice())
+        .succeeds()
+        .stdout_only_bytes(b"ab\xe9cd\n");
+    new_ucmd!()
+        .pipe_in(b"\x00ab\xe9cd\x00".as_slice())
+        .succeeds()
+        .no_output();
+}
+
+#[test]
+fn test_utf16_encoding() {
+    new_ucmd!()
+        .args(&["-e", "l"])
+        .pipe_in(b"w\x00i\x00d\x00e\x00\x00\x00ascii".as_slice())
+        .succeeds()
+        .stdout_only("wide\n");
+    new_ucmd!()
+        .args(&["-e", "b"])
+        .pipe_in(b"\x00w\x00i\x00d\x00e".as_slice())
+        .succeeds()
+        .stdout_only("wide\n");
+}
+
+#[test]
+fn test_utf32_encoding() {
+    new_ucmd!()
+        .args(&["-e", "L"])
+        .pipe_in(b"w\x00\x00\x00i\x00\x00\x00d\x00\x00\x00e\x00\x00\x00".as_slice())
+        .succeeds()
+        .stdout_only("wide\n");
+}
+
+#[test]
+fn test_unicode_locale() {
+    new_ucmd!()
+        .args(&["-n", "3", "--unicode=locale"])
+        .pipe_in("\x00héé\x00".as_bytes())
+        .succeeds()
+        .stdout_only("héé\n");
+    new_ucmd!()
+        .args(&["-n", "3", "-U", "l"])
+        .pipe_in(b"\x00h\xc3\x00\xa9\xa9\x00".as_slice())
+        .succeeds()
+        .no_output();
+    new_ucmd!()
+        .args(&["-n", "3"])
+        .pipe_in("\x00héé\x00".as_bytes())
+        .succeeds()
+        .no_output();
+}
+
+#[test]
+fn test_nonexistent_file() {
+    new_ucmd!()
+        .arg("missing")
+        .fails_with_code(1)
+        .stderr_only("strings: missing: No such file or directory\n");
+}
+
+#[test]
+fn test_nonexistent_file_continues() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write_bytes("a", b"found\x00");
+
+    ucmd.args(&["missing", "a"])
+        .fails_with_code(1)
+        .stdout_is("found\n")
+        .stderr_is("strings: missing: No such file or directory\n");
+}
@@ -3,7 +3,7 @@
 // For the full copyright and license information, please view the LICENSE
 // file that was distributed with this source code.
 // spell-checker:ignore axxbxx bxxaxx axxx axxxx xxaxx xxax xxxxa axyz zyax zyxa bbaaa aaabc bcdddd cddddaaabc xyzabc abcxyzabc nbbaaa
-#[cfg(target_os = "linux")]
+#[cfg(unix)]
 use uutests::at_and_ucmd;
 use uutests::new_ucmd;
 use uutests::util::TestScenario;
@@ -321,6 +321,34 @@ fn test_stdin_bad_tmpdir_fallback() {
         .stdout_is("c\nb\na\n");
 }
 
+#[cfg(unix)]
+#[test]
+fn test_fifo_argument() {
+    use std::io::Write;
+
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.mkfifo("fifo");
+    let child = ucmd.args(&["-s", ":", "fifo"]).run_no_wait();
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(at.plus("fifo"))
+        .unwrap()
+        .write_all(b"a:bb:ccc")
+        .unwrap();
+    child.wait().unwrap().success().stdout_only("cccbb:a:");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_file_with_zero_size() {
+    // files in /proc report a size of zero, but are not empty
+    let version = std::fs::read_to_string("/proc/version").unwrap();
+    new_ucmd!()
+        .arg("/proc/version")
+        .succeeds()
+        .stdout_only(version);
+}
+
 #[test]
 fn test_regex_or_operator() {
     new_ucmd!()
@@ -201,6 +201,37 @@ fn test_stdin_redirect_offset2() {
         );
 }
 
+#[test]
+#[cfg(not(target_vendor = "apple"))] // FIXME: for currently not working platforms
+fn test_stdin_redirect_offset_large_file_from_start() {
+    // Large enough to be seeked through rather than read
+    let scene = TestScenario::new(util_name!());
+    let at = &scene.fixtures;
+    let data: String = (1..=20000).map(|i| i.to_string() + "\n").collect();
+    at.write("k", &data);
+
+    for (args, expected) in [(["-c", "+3"], &data[6..]), (["-n", "+3"], &data[8..])] {
+        let mut fh = File::open(at.plus("k")).unwrap();
+        fh.seek(SeekFrom::Start(4)).unwrap();
+        scene
+            .ucmd()
+            .args(&args)
+            .set_stdin(fh)
+            .succeeds()
+            .stdout_only(expected);
+    }
+}
+
+#[test]
+fn test_bytes_from_start_large_skip_pipe() {
+    let data = vec![b'x'; 1_000_000];
+    new_ucmd!()
+        .args(&["-c", "+999998"])
+        .pipe_in(data)
+        .succeeds()
+        .stdout_only("xxx");
+}
+
 #[test]
 fn test_nc_0_wo_follow() {
     // verify that -[nc]0 without -f, exit without reading
@@ -974,6 +1005,25 @@ fn test_multiple_input_quiet_flag_overrides_verbose_flag_for_suppressing_headers
         .stdout_is_fixture("foobar_multiple_quiet.expected");
 }
 
+#[test]
+fn test_multiple_input_verbose_flag_overrides_earlier_quiet_flag() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "a\n");
+    ucmd.args(&["-q", "-v", "a", "a"])
+        .succeeds()
+        .stdout_only("==> a <==\na\n\n==> a <==\na\n");
+}
+
+#[test]
+fn test_multiple_input_files_with_stdin_headers() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "a\n");
+    ucmd.args(&["-", "a", "-"])
+        .pipe_in("x\n")
+        .succeeds()
+        .stdout_only("==> standard input <==\nx\n\n==> a <==\na\n\n==> standard input <==\n");
+}
+
 #[test]
 fn test_dir() {
     let (at, mut ucmd) = at_and_ucmd!();
@@ -1804,6 +1854,70 @@ fn test_follow_descriptor_vs_rename1() {
     }
 }
 
+#[test]
+#[cfg(all(
+    not(target_vendor = "apple"),
+    not(target_os = "android"),
+    not(target_os = "windows"),
+    not(target_os = "freebsd"),
+    not(target_os = "openbsd")
+))] // FIXME: for currently not working platforms
+fn test_follow_descriptor_ignores_new_file_with_the_name() {
+    // $ echo A > FILE_A && tail --follow=descriptor FILE_A &
+    // $ mv FILE_A FILE_B && echo B >> FILE_B && echo X > FILE_A
+    // $ rm FILE_B && echo Y > FILE_B
+    // A
+    // B
+
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    let file_a = "FILE_A";
+    let file_b = "FILE_B";
+
+    let mut args = vec![
+        "--follow=descriptor",
+        "-s.1",
+        "--max-unchanged-stats=1",
+        file_a,
+        "---disable-inotify",
+    ];
+
+    let mut delay = 1500;
+    for _ in 0..2 {
+        at.write(file_a, "A\n");
+
+        let mut p = ts.ucmd().args(&args).run_no_wait();
+
+        p.make_assertion_with_delay(delay).is_alive();
+
+        at.rename(file_a, file_b);
+        p.delay(delay);
+
+        at.append(file_b, "B\n");
+        p.delay(delay);
+
+        at.write(file_a, "X\n");
+        p.delay(delay);
+
+        at.remove(file_b);
+        p.delay(delay);
+
+        at.write(file_b, "Y\n");
+        p.delay(delay);
+
+        p.make_assertion().is_alive();
+        p.kill()
+            .make_assertion()
+            .with_all_output()
+            .stdout_only("A\nB\n");
+
+        at.remove(file_a);
+        at.remove(file_b);
+        args.pop();
+        delay /= 3;
+    }
+}
+
 #[test]
 #[cfg(all(
     not(target_vendor = "apple"),
@@ -2695,6 +2809,43 @@ fn test_lines_zero_terminated() {
         .stdout_only("b\0c\0d\0e\0");
 }
 
+#[test]
+fn test_lines_zero_terminated_file() {
+    // A regular file is read backwards from its end rather than buffered.
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("f", &"x\n".repeat(10_000));
+    at.append("f", "a\nb\0c\0d\ne");
+    ucmd.args(&["-z", "-n", "2", "f"])
+        .succeeds()
+        .stdout_only("c\0d\ne");
+}
+
+#[test]
+fn test_bytes_zero_terminated() {
+    // -c counts bytes whatever the record delimiter is.
+    new_ucmd!()
+        .args(&["-z", "-c", "3"])
+        .pipe_in("a\0b\0c\0")
+        .succeeds()
+        .stdout_only("\0c\0");
+    new_ucmd!()
+        .args(&["-z", "-c", "+4"])
+        .pipe_in("a\0b\0c\0")
+        .succeeds()
+        .stdout_only("\0c\0");
+}
+
+#[test]
+fn test_lines_zero_terminated_multiple_files() {
+    // The headers keep ending in newlines.
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "1\x002\x003\0");
+    at.write("b", "4\n5\x006");
+    ucmd.args(&["-z", "-n", "1", "a", "b"])
+        .succeeds()
+        .stdout_only("==> a <==\n3\0\n==> b <==\n6");
+}
+
 #[test]
 fn test_presume_input_pipe_default() {
     new_ucmd!()
@@ -4994,6 +5145,46 @@ fn test_following_with_pid() {
     child.kill();
 }
 
+#[test]
+#[cfg(unix)]
+#[cfg(not(target_vendor = "apple"))] // FIXME: for currently not working platforms
+fn test_following_with_multiple_pids() {
+    use std::process::Command;
+
+    let ts = TestScenario::new(util_name!());
+    let at = &ts.fixtures;
+    at.touch("f");
+
+    let mut first = Command::new("sleep").arg("999d").spawn().unwrap();
+    let mut second = Command::new("sleep").arg("999d").spawn().unwrap();
+
+    // tail terminates only once every process of --pid has died
+    let mut child = ts
+        .ucmd()
+        .args(&[
+            "-f",
+            "-s.1",
+            &format!("--pid={}", first.id()),
+            &format!("--pid={}", second.id()),
+            "f",
+        ])
+        .run_no_wait();
+    child.make_assertion_with_delay(500).is_alive();
+
+    first.kill().unwrap();
+    let _ = first.wait();
+    child.make_assertion_with_delay(500).is_alive();
+
+    second.kill().unwrap();
+    let _ = second.wait();
+    child
+        .make_assertion_with_delay(500)
+        .is_not_alive()
+        .with_all_output()
+        .no_output()
+        .success();
+}
+
 // This error was first detected when running tail so tail is used here but
 // should fail with any command that takes piped input.
 // See also https://github.com/uutils/coreutils/issues/3895
@@ -5027,6 +5218,23 @@ fn test_when_output_closed_then_no_broken_pie() {
     child.wait().unwrap().fails_silently();
 }
 
+#[test]
+#[cfg(target_os = "linux")]
+fn test_follow_exits_when_output_closed() {
+    // Nothing is written while waiting for the file to change, so the closed
+    // pipe must be noticed without a write, and before --sleep-interval ends.
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("data", "a\n");
+    let mut child = ucmd
+        .args(&["-f", "-s", "100", "data"])
+        .set_stdout(Stdio::piped())
+        .run_no_wait();
+    child.make_assertion_with_delay(500).is_alive();
+    child.close_stdout();
+    child.make_assertion_with_delay(1000).is_not_alive();
+    child.wait().unwrap().fails_silently();
+}
+
 #[test]
 fn test_child_when_run_with_stderr_to_stdout() {
     let ts = TestScenario::new("tail");
@@ -5051,7 +5259,31 @@ fn test_failed_write_is_reported() {
         .pipe_in("hello")
         .set_stdout(File::create("/dev/full").unwrap())
         .fails()
-        .stderr_is("tail: No space left on device\n");
+        .stderr_is("tail: error writing 'standard output': No space left on device\n");
+
+    // A file large enough to be read from its end
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("big", &"x\n".repeat(100_000));
+    ucmd.arg("big")
+        .set_stdout(File::create("/dev/full").unwrap())
+        .fails_with_code(1)
+        .stderr_is("tail: error writing 'standard output': No space left on device\n");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_read_error_keeps_going() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("a", "abc\n");
+    at.write("b", "xyz\n");
+    ucmd.args(&["-n", "1", "a", "/proc/self/mem", "b"])
+        .stderr_to_stdout()
+        .fails_with_code(1)
+        .stdout_only(
+            "==> a <==\nabc\n\n==> /proc/self/mem <==\n\
+             tail: error reading '/proc/self/mem': Input/output error\n\
+             \n==> b <==\nxyz\n",
+        );
 }
 
 #[test]
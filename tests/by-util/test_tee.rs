@@ -115,6 +115,33 @@ fn test_tee_multiple_append_flags() {
     assert_eq!(at.read(file2), format!("existing2\n{content}"));
 }
 
+#[test]
+fn test_tee_same_file_operands() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let content = "tee_sample_content";
+    at.mkdir("dir");
+    at.write("file", "old content that is longer");
+
+    ucmd.args(&["file", "./file", "dir/../file"])
+        .pipe_in(content)
+        .succeeds()
+        .stdout_is(content);
+    assert_eq!(at.read("file"), content);
+}
+
+#[test]
+fn test_tee_same_file_operands_append() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    let content = "tee_sample_content";
+    at.write("file", "existing\n");
+
+    ucmd.args(&["-a", "file", "./file"])
+        .pipe_in(content)
+        .succeeds()
+        .stdout_is(content);
+    assert_eq!(at.read("file"), format!("existing\n{content}{content}"));
+}
+
 #[test]
 fn test_readonly() {
     let (at, mut ucmd) = at_and_ucmd!();
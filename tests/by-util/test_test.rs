@@ -1048,9 +1048,62 @@ fn test_string_lt_gt_operator() {
         .no_output();
 }
 
+#[test]
+fn test_string_lt_gt_operator_locale() {
+    // Upper case letters sort before lower case ones in the C locale only.
+    new_ucmd!()
+        .env("LC_ALL", "C")
+        .args(&["B", "<", "a"])
+        .succeeds()
+        .no_output();
+    new_ucmd!()
+        .env("LC_ALL", "en_US.UTF-8")
+        .args(&["B", "<", "a"])
+        .fails_with_code(1)
+        .no_output();
+    new_ucmd!()
+        .env("LC_ALL", "en_US.UTF-8")
+        .args(&["B", ">", "a"])
+        .succeeds()
+        .no_output();
+}
+
+#[test]
+fn test_string_lt_gt_operator_with_bool_op() {
+    new_ucmd!()
+        .args(&["a", "<", "b", "-a", "c", ">", "b"])
+        .succeeds()
+        .no_output();
+    new_ucmd!()
+        .args(&["!", "a", ">", "b", "-o", "x", "==", "y"])
+        .succeeds()
+        .no_output();
+}
+
 #[test]
 fn test_unary_op_as_literal_in_three_arg_form() {
     // `-f = a` is string comparison "-f" = "a", not file test
     new_ucmd!().args(&["-f", "=", "a"]).fails_with_code(1);
     new_ucmd!().args(&["-f", "=", "a", "-o", "b"]).succeeds();
 }
+
+#[test]
+fn test_dash_operand_where_unary_op_expected() {
+    // like GNU test, `--` and `-l` are neither options nor known operators
+    for (args, operator) in [
+        (&["--", "--"][..], "--"),
+        (&["--", "-n"], "--"),
+        (&["-l", "abc"], "-l"),
+        (&["--", "-a", "--"], "--"),
+        (&["foo", "-a", "-q"], "-q"),
+        (&["!", "--", "x"], "--"),
+    ] {
+        new_ucmd!()
+            .args(args)
+            .fails_with_code(2)
+            .stderr_only(format!("test: '{operator}': unary operator expected\n"));
+    }
+    // as the only operand, or compared as a string, it is a plain string
+    new_ucmd!().args(&["--", "=", "--"]).succeeds();
+    new_ucmd!().args(&["-n", "--"]).succeeds();
+}
@@ -22,6 +22,17 @@ fn test_version() {
         .stdout_matches(&re);
 }
 
+#[test]
+fn test_version_details() {
+    let re = Regex::new(r"^true .*\d+\.\d+\.\d+\n\nversion=.*\ntarget=.*\nfeatures=.*\n$").unwrap();
+
+    new_ucmd!()
+        .arg("--version")
+        .env("UUTILS_VERSION_DETAILS", "1")
+        .succeeds()
+        .stdout_matches(&re);
+}
+
 #[test]
 fn test_help() {
     new_ucmd!()
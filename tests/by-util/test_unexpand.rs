@@ -453,3 +453,43 @@ fn test_buffered_read_edgecase_behaviour() {
             .stdout_only(String::from_utf8(expected).unwrap());
     }
 }
+
+#[test]
+fn unexpand_single_blank_before_tab_stop() {
+    // POSIX: a single space just before a tab stop is not turned into a tab,
+    // unless more blanks follow it.
+    new_ucmd!()
+        .arg("-a")
+        .pipe_in("abcdefg h\nabcdefg  h\nabcdefg \th\nab       c\n")
+        .succeeds()
+        .stdout_is("abcdefg h\nabcdefg\t h\nabcdefg\t\th\nab\t c\n");
+}
+
+#[test]
+fn unexpand_backspace_goes_back_one_column() {
+    new_ucmd!()
+        .arg("-a")
+        .pipe_in("ab\x08\x08        x\n   \x08      y\n")
+        .succeeds()
+        .stdout_is("ab\x08\x08\tx\n   \x08\ty\n");
+}
+
+#[test]
+fn unexpand_blanks_past_last_tab_stop() {
+    // the blanks up to the last stop of the list are converted, the rest not
+    new_ucmd!()
+        .args(&["-t", "3,7"])
+        .pipe_in("          x\n")
+        .succeeds()
+        .stdout_is("\t\t   x\n");
+}
+
+#[test]
+fn unexpand_line_continues_in_next_file() {
+    let (at, mut ucmd) = at_and_ucmd!();
+    at.write("first", "ab   ");
+    at.write("second", "   cd\n");
+    ucmd.args(&["-a", "first", "second"])
+        .succeeds()
+        .stdout_is("ab\tcd\n");
+}
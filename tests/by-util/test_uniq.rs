@@ -325,6 +325,27 @@ fn test_group_both() {
         .stdout_is_fixture("group-both.expected");
 }
 
+#[test]
+fn test_group_mutually_exclusive() {
+    // the GNU tests below cover `--group` followed by each option
+    for args in [
+        &["-c", "--group=both"][..],
+        &["-D", "--group"],
+        &["--group=append", "--all-repeated=separate"],
+    ] {
+        new_ucmd!()
+            .args(args)
+            .pipe_in("a\na\n")
+            .ignore_stdin_write_error()
+            .fails_with_code(1)
+            .no_stdout()
+            .stderr_is(concat!(
+                "uniq: --group is mutually exclusive with -c/-d/-D/-u\n",
+                "Try 'uniq --help' for more information.\n"
+            ));
+    }
+}
+
 #[test]
 fn test_group_separate() {
     new_ucmd!()
@@ -1066,9 +1087,8 @@ fn gnu_tests() {
             input: "",
             stdout: Some(""),
             stderr: Some(concat!(
-                "error: the argument '--group[=<group-method>]' cannot be used with '--count'\n",
-                "\n",
-                "For more information, try '--help'.\n"
+                "uniq: --group is mutually exclusive with -c/-d/-D/-u\n",
+                "Try 'uniq --help' for more information.\n"
             )),
             exit: Some(1),
         },
@@ -1078,9 +1098,8 @@ fn gnu_tests() {
             input: "",
             stdout: Some(""),
             stderr: Some(concat!(
-                "error: the argument '--group[=<group-method>]' cannot be used with '--repeated'\n",
-                "\n",
-                "For more information, try '--help'.\n"
+                "uniq: --group is mutually exclusive with -c/-d/-D/-u\n",
+                "Try 'uniq --help' for more information.\n"
             )),
             exit: Some(1),
         },
@@ -1090,9 +1109,8 @@ fn gnu_tests() {
             input: "",
             stdout: Some(""),
             stderr: Some(concat!(
-                "error: the argument '--group[=<group-method>]' cannot be used with '--unique'\n",
-                "\n",
-                "For more information, try '--help'.\n"
+                "uniq: --group is mutually exclusive with -c/-d/-D/-u\n",
+                "Try 'uniq --help' for more information.\n"
             )),
             exit: Some(1),
         },
@@ -1102,9 +1120,8 @@ fn gnu_tests() {
             input: "",
             stdout: Some(""),
             stderr: Some(concat!(
-                "error: the argument '--group[=<group-method>]' cannot be used with '--all-repeated[=<delimit-method>]'\n",
-                "\n",
-                "For more information, try '--help'.\n"
+                "uniq: --group is mutually exclusive with -c/-d/-D/-u\n",
+                "Try 'uniq --help' for more information.\n"
             )),
             exit: Some(1),
         },
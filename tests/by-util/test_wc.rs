@@ -456,7 +456,7 @@ fn test_read_error_order_with_stderr_to_stdout() {
     at.mkdir("ioerrdir");
 
     let expected = format!(
-        "{:>7} {:>7} {:>7} ioerrdir\nwc: ioerrdir: Is a directory\n",
+        "wc: ioerrdir: Is a directory\n{:>7} {:>7} {:>7} ioerrdir\n",
         0, 0, 0
     );
 
@@ -921,3 +921,27 @@ fn test_posixly_correct_whitespace() {
         .succeeds()
         .stdout_is("1\n");
 }
+
+#[test]
+fn test_words_across_blocks() {
+    // Words are counted 64 bytes at a time while the input is ASCII; check
+    // words that straddle those blocks and the switch to multi-byte text.
+    let mut input = "x".repeat(63);
+    input.push_str(" y");
+    input.push_str(&"z ".repeat(40));
+    input.push_str("\u{3000}a\u{2028}b\u{00A0}\u{00E9}\u{2029}");
+    input.push_str(&"w".repeat(70));
+    input.push('\n');
+
+    new_ucmd!()
+        .arg("-lwm")
+        .pipe_in(input.clone())
+        .succeeds()
+        .stdout_is("      1      45     223\n");
+    new_ucmd!()
+        .arg("-w")
+        .env("POSIXLY_CORRECT", "1")
+        .pipe_in(input)
+        .succeeds()
+        .stdout_is("42\n");
+}
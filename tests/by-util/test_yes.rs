@@ -89,8 +89,8 @@ fn test_piped_to_dev_full() {
 
             new_ucmd!()
                 .set_stdout(dev_full)
-                .fails()
-                .stderr_contains("No space left on device");
+                .fails_with_code(1)
+                .stderr_is("yes: standard output: No space left on device\n");
         }
     }
 }
@@ -209,6 +209,30 @@ fn util_version() {
     }
 }
 
+#[test]
+fn util_version_details() {
+    use std::process::Command;
+
+    let scenario = TestScenario::new("--version");
+    if !scenario.bin_path.exists() {
+        println!("Skipping test: Binary not found at {:?}", scenario.bin_path);
+        return;
+    }
+    let output = Command::new(&scenario.bin_path)
+        .arg("--version")
+        .env("UUTILS_VERSION_DETAILS", "1")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let output_str = String::from_utf8(output.stdout).unwrap();
+    let mut lines = output_str.lines();
+    assert!(lines.next().unwrap().ends_with("(multi-call binary)"));
+    assert_eq!(lines.next(), Some(""));
+    let utilities = lines.last().unwrap();
+    assert!(utilities.starts_with("utilities="));
+    assert_ne!(utilities, "utilities=");
+}
+
 #[test]
 #[cfg(target_env = "musl")]
 fn test_musl_no_dynamic_deps() {
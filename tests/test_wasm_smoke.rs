@@ -0,0 +1,230 @@
+// This file is part of the uutils coreutils package.
+//
+// For the full copyright and license information, please view the LICENSE
+// file that was distributed with this source code.
+
+//! Runs the utilities that need nothing but their standard streams the way a
+//! WASM host does, inside `uucore::wasm_io::with_wasm_io` with no other hooks
+//! installed, and compares what they write with golden output. Only built for
+//! WASM targets, e.g.
+//! `cargo test --test test_wasm_smoke --target wasm32-wasip2 --no-default-features --features feat_wasm`
+//! with a WASI runner set up.
+
+// spell-checker:ignore tsort shuf
+
+#![cfg(target_family = "wasm")]
+
+use std::cell::RefCell;
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use uucore::wasm_io;
+
+/// Stands in for one of the host's output streams, keeping what reaches it
+#[derive(Clone, Default)]
+struct Captured(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Output {
+    code: i32,
+    stdout: String,
+    stderr: String,
+}
+
+/// Run `util_func` with `args` and `input` as stdin, handing it only the
+/// stream overrides and a fixed random source
+fn run<F>(util_func: F, args: &[&str], input: &'static [u8]) -> Output
+where
+    F: FnOnce(std::vec::IntoIter<OsString>) -> i32,
+{
+    let stdout = Captured::default();
+    let stderr = Captured::default();
+    let args: Vec<OsString> = std::iter::once("wasm-smoke".into())
+        .chain(args.iter().map(Into::into))
+        .collect();
+    let code = wasm_io::with_wasm_io(
+        Box::new(input),
+        Box::new(stdout.clone()),
+        Box::new(stderr.clone()),
+        || {
+            wasm_io::set_random_hook(Box::new(|dest| dest.fill(7)));
+            util_func(args.into_iter())
+        },
+    );
+    let text = |c: Captured| String::from_utf8(c.0.take()).unwrap();
+    Output {
+        code,
+        stdout: text(stdout),
+        stderr: text(stderr),
+    }
+}
+
+/// Check that `util_func` succeeds and writes exactly `expected`
+#[track_caller]
+fn check<F>(util_func: F, args: &[&str], input: &'static [u8], expected: &str)
+where
+    F: FnOnce(std::vec::IntoIter<OsString>) -> i32,
+{
+    let out = run(util_func, args, input);
+    assert_eq!(out.stderr, "", "args: {args:?}");
+    assert_eq!(out.code, 0, "args: {args:?}");
+    assert_eq!(out.stdout, expected, "args: {args:?}");
+}
+
+#[test]
+#[cfg(feature = "seq")]
+fn test_seq() {
+    check(seq::uumain, &["3"], b"", "1\n2\n3\n");
+    check(seq::uumain, &["-s", ",", "1", "2", "7"], b"", "1,3,5,7\n");
+    check(seq::uumain, &["-w", "8", "10"], b"", "08\n09\n10\n");
+    check(
+        seq::uumain,
+        &["-f", "%.2f", "0.5", "0.25", "1"],
+        b"",
+        "0.50\n0.75\n1.00\n",
+    );
+}
+
+#[test]
+#[cfg(feature = "tsort")]
+fn test_tsort() {
+    check(tsort::uumain, &[], b"a b\nb c\n", "a\nb\nc\n");
+    check(tsort::uumain, &["-"], b"x y x z\n", "x\nz\ny\n");
+}
+
+#[test]
+#[cfg(feature = "tsort")]
+fn test_tsort_loop() {
+    // The messages are not translated without the multicall binary, so only
+    // check that the loop is reported.
+    let out = run(tsort::uumain, &[], b"a b\nb a\n");
+    assert_eq!(out.code, 1);
+    assert_eq!(out.stdout, "a\nb\n");
+    assert_ne!(out.stderr, "");
+}
+
+#[test]
+#[cfg(feature = "shuf")]
+fn test_shuf() {
+    check(shuf::uumain, &["-e", "only"], b"", "only\n");
+    check(shuf::uumain, &["-n", "0", "-i", "1-9"], b"", "");
+
+    // The order depends on the random source; the lines do not.
+    for args in [&["-i", "1-5"][..], &[], &["-r", "-n", "5", "-i", "1-1"]] {
+        let out = run(shuf::uumain, args, b"1\n2\n3\n4\n5\n");
+        assert_eq!(out.code, 0, "args: {args:?}");
+        let mut lines: Vec<&str> = out.stdout.lines().collect();
+        lines.sort_unstable();
+        if args.contains(&"-r") {
+            assert_eq!(lines, ["1"; 5]);
+        } else {
+            assert_eq!(lines, ["1", "2", "3", "4", "5"]);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "factor")]
+fn test_factor() {
+    check(factor::uumain, &["12", "17"], b"", "12: 2 2 3\n17: 17\n");
+    check(factor::uumain, &[], b"100\n", "100: 2 2 5 5\n");
+}
+
+#[test]
+#[cfg(feature = "echo")]
+fn test_echo() {
+    check(echo::uumain, &["-n", "a", "b"], b"", "a b");
+    check(echo::uumain, &["-e", "x\\ty"], b"", "x\ty\n");
+}
+
+#[test]
+#[cfg(feature = "printf")]
+fn test_printf() {
+    check(printf::uumain, &["%s-%03d\\n", "a", "7"], b"", "a-007\n");
+}
+
+#[test]
+#[cfg(feature = "basename")]
+fn test_basename() {
+    check(basename::uumain, &["/usr/lib/x.rs", ".rs"], b"", "x\n");
+}
+
+#[test]
+#[cfg(feature = "dirname")]
+fn test_dirname() {
+    check(
+        dirname::uumain,
+        &["/usr/lib/x.rs", "x"],
+        b"",
+        "/usr/lib\n.\n",
+    );
+}
+
+#[test]
+#[cfg(feature = "cat")]
+fn test_cat() {
+    check(cat::uumain, &["-n"], b"a\nb\n", "     1\ta\n     2\tb\n");
+}
+
+#[test]
+#[cfg(feature = "tr")]
+fn test_tr() {
+    check(tr::uumain, &["a-z", "A-Z"], b"hello\n", "HELLO\n");
+}
+
+#[test]
+#[cfg(feature = "cut")]
+fn test_cut() {
+    check(cut::uumain, &["-d:", "-f2"], b"a:b:c\n", "b\n");
+}
+
+#[test]
+#[cfg(feature = "sort")]
+fn test_sort() {
+    check(sort::uumain, &["-n"], b"10\n9\n100\n", "9\n10\n100\n");
+}
+
+#[test]
+#[cfg(feature = "uniq")]
+fn test_uniq() {
+    check(
+        uniq::uumain,
+        &["-c"],
+        b"a\na\nb\n",
+        "      2 a\n      1 b\n",
+    );
+}
+
+#[test]
+#[cfg(feature = "wc")]
+fn test_wc() {
+    check(
+        wc::uumain,
+        &["-lw"],
+        b"one two\nthree\n",
+        "      2       3\n",
+    );
+}
+
+#[test]
+#[cfg(feature = "head")]
+fn test_head() {
+    check(head::uumain, &["-n", "2"], b"1\n2\n3\n", "1\n2\n");
+}
+
+#[test]
+#[cfg(feature = "tail")]
+fn test_tail() {
+    check(tail::uumain, &["-n", "2"], b"1\n2\n3\n", "2\n3\n");
+}
@@ -348,6 +348,10 @@ mod test_stat;
 #[path = "by-util/test_stdbuf.rs"]
 mod test_stdbuf;
 
+#[cfg(feature = "strings")]
+#[path = "by-util/test_strings.rs"]
+mod test_strings;
+
 #[cfg(feature = "stty")]
 #[path = "by-util/test_stty.rs"]
 mod test_stty;